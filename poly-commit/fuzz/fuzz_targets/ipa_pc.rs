@@ -0,0 +1,12 @@
+#![no_main]
+
+use std::sync::OnceLock;
+
+use ark_poly_commit_fuzz::{ipa_pc, IPAHarness};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    static HARNESS: OnceLock<IPAHarness> = OnceLock::new();
+    let harness = HARNESS.get_or_init(ipa_pc);
+    assert_eq!(harness.run(data), 0);
+});
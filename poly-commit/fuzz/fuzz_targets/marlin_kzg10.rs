@@ -0,0 +1,12 @@
+#![no_main]
+
+use std::sync::OnceLock;
+
+use ark_poly_commit_fuzz::{marlin_kzg10, MarlinKZG10Harness};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    static HARNESS: OnceLock<MarlinKZG10Harness> = OnceLock::new();
+    let harness = HARNESS.get_or_init(marlin_kzg10);
+    assert_eq!(harness.run(data), 0);
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use std::sync::OnceLock;
+
+use ark_poly_commit_fuzz::{multilinear_brakedown, MLBrakedownHarness};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    static HARNESS: OnceLock<MLBrakedownHarness> = OnceLock::new();
+    let harness = HARNESS.get_or_init(multilinear_brakedown);
+    assert_eq!(harness.run(data), 0);
+});
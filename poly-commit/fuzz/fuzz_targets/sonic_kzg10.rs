@@ -0,0 +1,12 @@
+#![no_main]
+
+use std::sync::OnceLock;
+
+use ark_poly_commit_fuzz::{sonic_kzg10, SonicKZG10Harness};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    static HARNESS: OnceLock<SonicKZG10Harness> = OnceLock::new();
+    let harness = HARNESS.get_or_init(sonic_kzg10);
+    assert_eq!(harness.run(data), 0);
+});
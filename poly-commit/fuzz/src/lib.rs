@@ -0,0 +1,191 @@
+//! Verification instances shared by the fuzz targets. Each one is built once,
+//! deterministically, and then fed every input of the fuzzing session.
+
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_crypto_primitives::sponge::poseidon::PoseidonSponge;
+use ark_ed_on_bls12_381::{EdwardsAffine, Fr as EdFr};
+use ark_ff::PrimeField;
+use ark_poly::{
+    multivariate::{SparsePolynomial, SparseTerm},
+    univariate::DensePolynomial,
+    DenseMVPolynomial, DenseMultilinearExtension, DenseUVPolynomial, MultilinearExtension,
+    SparseMultilinearExtension,
+};
+use ark_poly_commit::{
+    hyrax::HyraxPC,
+    ipa_pc::InnerProductArgPC,
+    linear_codes::{PoseidonMLBrakedown, PoseidonMLLigero, PoseidonUVLigero},
+    marlin_pc::MarlinKZG10,
+    marlin_pst13_pc::MarlinPST13,
+    sonic_pc::SonicKZG10,
+    test_sponge, FuzzHarness, LabeledPolynomial,
+};
+use ark_std::{rand::RngCore, test_rng, UniformRand};
+use blake2::Blake2s256;
+
+type UniPoly<F> = DensePolynomial<F>;
+type Harness<F, P, PC> = FuzzHarness<F, P, PC, PoseidonSponge<F>>;
+
+/// Two univariate polynomials of degree 15, the second one with a degree
+/// bound of 10 if `degree_bound` is set.
+fn uv_polys<F: PrimeField>(
+    degree_bound: bool,
+    rng: &mut impl RngCore,
+) -> Vec<LabeledPolynomial<F, UniPoly<F>>> {
+    let mut polys = vec![LabeledPolynomial::new(
+        "a".to_string(),
+        UniPoly::rand(15, rng),
+        None,
+        None,
+    )];
+    if degree_bound {
+        polys.push(LabeledPolynomial::new(
+            "b".to_string(),
+            UniPoly::rand(10, rng),
+            Some(10),
+            None,
+        ));
+    }
+    polys
+}
+
+fn points<F: PrimeField>(num_vars: usize, rng: &mut impl RngCore) -> (Vec<F>, Vec<F>) {
+    let point = (0..num_vars).map(|_| F::rand(rng)).collect();
+    let other_point = (0..num_vars).map(|_| F::rand(rng)).collect();
+    (point, other_point)
+}
+
+/// The harness returned by [`marlin_kzg10`].
+pub type MarlinKZG10Harness = Harness<Fr, UniPoly<Fr>, MarlinKZG10<Bls12_381, UniPoly<Fr>>>;
+
+/// MarlinKZG10 over BLS12-381.
+pub fn marlin_kzg10() -> MarlinKZG10Harness {
+    let rng = &mut test_rng();
+    let polys = uv_polys(true, rng);
+    let (point, other_point) = (Fr::rand(rng), Fr::rand(rng));
+    FuzzHarness::new(15, None, polys, point, other_point, test_sponge(), rng).unwrap()
+}
+
+/// The harness returned by [`sonic_kzg10`].
+pub type SonicKZG10Harness = Harness<Fr, UniPoly<Fr>, SonicKZG10<Bls12_381, UniPoly<Fr>>>;
+
+/// SonicKZG10 over BLS12-381.
+pub fn sonic_kzg10() -> SonicKZG10Harness {
+    let rng = &mut test_rng();
+    let polys = uv_polys(true, rng);
+    let (point, other_point) = (Fr::rand(rng), Fr::rand(rng));
+    FuzzHarness::new(15, None, polys, point, other_point, test_sponge(), rng).unwrap()
+}
+
+type MVPoly = SparsePolynomial<Fr, SparseTerm>;
+
+/// The harness returned by [`marlin_pst13`].
+pub type MarlinPST13Harness = Harness<Fr, MVPoly, MarlinPST13<Bls12_381, MVPoly>>;
+
+/// MarlinPST13 over BLS12-381, for 3 variables.
+pub fn marlin_pst13() -> MarlinPST13Harness {
+    let rng = &mut test_rng();
+    let poly = LabeledPolynomial::new("a".to_string(), MVPoly::rand(4, 3, rng), None, None);
+    let (point, other_point) = points(3, rng);
+    FuzzHarness::new(
+        4,
+        Some(3),
+        vec![poly],
+        point,
+        other_point,
+        test_sponge(),
+        rng,
+    )
+    .unwrap()
+}
+
+/// The harness returned by [`ipa_pc`].
+pub type IPAHarness =
+    Harness<EdFr, UniPoly<EdFr>, InnerProductArgPC<EdwardsAffine, Blake2s256, UniPoly<EdFr>>>;
+
+/// The inner-product argument over Jubjub.
+pub fn ipa_pc() -> IPAHarness {
+    let rng = &mut test_rng();
+    let polys = uv_polys(true, rng);
+    let (point, other_point) = (EdFr::rand(rng), EdFr::rand(rng));
+    FuzzHarness::new(15, None, polys, point, other_point, test_sponge(), rng).unwrap()
+}
+
+type MLE<F> = DenseMultilinearExtension<F>;
+
+/// The harness returned by [`hyrax`].
+pub type HyraxHarness = Harness<EdFr, MLE<EdFr>, HyraxPC<EdwardsAffine, MLE<EdFr>>>;
+
+/// Hyrax over Jubjub, for 4 variables.
+pub fn hyrax() -> HyraxHarness {
+    let rng = &mut test_rng();
+    let poly = LabeledPolynomial::new("a".to_string(), MLE::rand(4, rng), None, None);
+    let (point, other_point) = points(4, rng);
+    FuzzHarness::new(
+        1,
+        Some(4),
+        vec![poly],
+        point,
+        other_point,
+        test_sponge(),
+        rng,
+    )
+    .unwrap()
+}
+
+// The linear-code schemes use the Poseidon Merkle tree config, whose digests
+// are field elements: byte digests would be deserialised by ark-serialize,
+// which reserves capacity from their untrusted length prefix.
+
+/// The harness returned by [`univariate_ligero`].
+pub type UVLigeroHarness = Harness<Fr, UniPoly<Fr>, PoseidonUVLigero<Fr, UniPoly<Fr>>>;
+
+/// Univariate Ligero over the BLS12-381 scalar field.
+pub fn univariate_ligero() -> UVLigeroHarness {
+    let rng = &mut test_rng();
+    let polys = uv_polys(false, rng);
+    let (point, other_point) = (Fr::rand(rng), Fr::rand(rng));
+    FuzzHarness::new(15, None, polys, point, other_point, test_sponge(), rng).unwrap()
+}
+
+type SparseMLE<F> = SparseMultilinearExtension<F>;
+
+/// The harness returned by [`multilinear_ligero`].
+pub type MLLigeroHarness = Harness<Fr, SparseMLE<Fr>, PoseidonMLLigero<Fr, SparseMLE<Fr>>>;
+
+/// Multilinear Ligero over the BLS12-381 scalar field, for 4 variables.
+pub fn multilinear_ligero() -> MLLigeroHarness {
+    let rng = &mut test_rng();
+    let poly = LabeledPolynomial::new("a".to_string(), SparseMLE::rand(4, rng), None, None);
+    let (point, other_point) = points(4, rng);
+    FuzzHarness::new(
+        1,
+        Some(4),
+        vec![poly],
+        point,
+        other_point,
+        test_sponge(),
+        rng,
+    )
+    .unwrap()
+}
+
+/// The harness returned by [`multilinear_brakedown`].
+pub type MLBrakedownHarness = Harness<Fr, SparseMLE<Fr>, PoseidonMLBrakedown<Fr, SparseMLE<Fr>>>;
+
+/// Multilinear Brakedown over the BLS12-381 scalar field, for 7 variables.
+pub fn multilinear_brakedown() -> MLBrakedownHarness {
+    let rng = &mut test_rng();
+    let poly = LabeledPolynomial::new("a".to_string(), SparseMLE::rand(7, rng), None, None);
+    let (point, other_point) = points(7, rng);
+    FuzzHarness::new(
+        1,
+        Some(7),
+        vec![poly],
+        point,
+        other_point,
+        test_sponge(),
+        rng,
+    )
+    .unwrap()
+}
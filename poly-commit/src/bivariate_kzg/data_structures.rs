@@ -0,0 +1,347 @@
+use crate::{kzg10, Error};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::PrimeField;
+use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, EvaluationDomain};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::{
+    borrow::Cow,
+    format,
+    io::{Read, Write},
+    rand::RngCore,
+};
+#[cfg(not(feature = "std"))]
+use ark_std::{string::ToString, vec::Vec};
+
+/// A variable of a bivariate polynomial `f(X, Y)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Variable {
+    /// The variable `X`, which indexes the rows of the coefficient grid.
+    X,
+    /// The variable `Y`, which indexes the columns of the coefficient grid.
+    Y,
+}
+
+/// A bivariate polynomial `f(X, Y) = Σ f_ij X^i Y^j`, stored as the grid of
+/// its coefficients `f_ij`, with `degree_x + 1` rows of `degree_y + 1`
+/// coefficients each.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BivariatePolynomial<F: PrimeField> {
+    coeffs: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> BivariatePolynomial<F> {
+    /// Outputs the polynomial with the coefficients `coeffs`, where
+    /// `coeffs[i][j]` is the coefficient of `X^i Y^j`. The grid must be
+    /// non-empty and all its rows must have the same length.
+    pub fn from_coefficients(coeffs: Vec<Vec<F>>) -> Result<Self, Error> {
+        let num_columns = coeffs.first().map_or(0, Vec::len);
+        if num_columns == 0 || coeffs.iter().any(|row| row.len() != num_columns) {
+            return Err(Error::IncorrectInputLength(
+                "The coefficients must form a non-empty rectangular grid".to_string(),
+            ));
+        }
+        Ok(Self { coeffs })
+    }
+
+    /// Interpolates the polynomial of degree less than `domain_x.size()` in
+    /// `X` and less than `domain_y.size()` in `Y` whose evaluation at the
+    /// `a`-th element of `domain_x` and the `b`-th element of `domain_y` is
+    /// `evals[a][b]`.
+    pub fn from_lagrange_grid<D: EvaluationDomain<F>>(
+        evals: Vec<Vec<F>>,
+        domain_x: D,
+        domain_y: D,
+    ) -> Result<Self, Error> {
+        if evals.len() != domain_x.size() || evals.iter().any(|row| row.len() != domain_y.size()) {
+            return Err(Error::IncorrectInputLength(format!(
+                "Expected a grid of {} by {} evaluations",
+                domain_x.size(),
+                domain_y.size()
+            )));
+        }
+        // Interpolate each row in `Y`, and then each column in `X`.
+        let mut coeffs: Vec<Vec<F>> = evals.into_iter().map(|row| domain_y.ifft(&row)).collect();
+        for j in 0..domain_y.size() {
+            let column: Vec<F> = coeffs.iter().map(|row| row[j]).collect();
+            for (row, c) in coeffs.iter_mut().zip(domain_x.ifft(&column)) {
+                row[j] = c;
+            }
+        }
+        Self::from_coefficients(coeffs)
+    }
+
+    /// Outputs a random polynomial of degree `degree_x` in `X` and
+    /// `degree_y` in `Y`.
+    pub fn rand<R: RngCore>(degree_x: usize, degree_y: usize, rng: &mut R) -> Self {
+        Self {
+            coeffs: (0..=degree_x)
+                .map(|_| (0..=degree_y).map(|_| F::rand(rng)).collect())
+                .collect(),
+        }
+    }
+
+    /// The grid of coefficients of `self`, where `coeffs()[i][j]` is the
+    /// coefficient of `X^i Y^j`.
+    pub fn coeffs(&self) -> &[Vec<F>] {
+        &self.coeffs
+    }
+
+    /// The degree of `self` in `X`, counting leading zero rows.
+    pub fn degree_x(&self) -> usize {
+        self.coeffs.len() - 1
+    }
+
+    /// The degree of `self` in `Y`, counting leading zero columns.
+    pub fn degree_y(&self) -> usize {
+        self.coeffs[0].len() - 1
+    }
+
+    /// Evaluates `self` at `(x, y)`.
+    pub fn evaluate(&self, x: F, y: F) -> F {
+        horner(&self.partial_evaluate(Variable::X, x).coeffs, y)
+    }
+
+    /// Outputs the univariate polynomial in the other variable obtained by
+    /// fixing `variable` to `point`: `f(x, Y)` for `X`, and `f(X, y)` for `Y`.
+    pub fn partial_evaluate(&self, variable: Variable, point: F) -> DensePolynomial<F> {
+        let coeffs = match variable {
+            Variable::X => {
+                let mut result = vec![F::zero(); self.degree_y() + 1];
+                for row in self.coeffs.iter().rev() {
+                    for (r, c) in result.iter_mut().zip(row) {
+                        *r = *r * point + c;
+                    }
+                }
+                result
+            }
+            Variable::Y => self.coeffs.iter().map(|row| horner(row, point)).collect(),
+        };
+        DensePolynomial::from_coefficients_vec(coeffs)
+    }
+}
+
+/// Evaluates the univariate polynomial with coefficients `coeffs` at `point`.
+fn horner<F: PrimeField>(coeffs: &[F], point: F) -> F {
+    coeffs
+        .iter()
+        .rev()
+        .fold(F::zero(), |acc, c| acc * point + c)
+}
+
+/// `UniversalParams` are the universal parameters for the bivariate KZG
+/// scheme.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct UniversalParams<E: Pairing> {
+    /// Group elements of the form `τ_x^i τ_y^j G`, where `powers_of_g[i][j]`
+    /// is that of `X^i Y^j`.
+    pub powers_of_g: Vec<Vec<E::G1Affine>>,
+    /// The generator of G2.
+    pub h: E::G2Affine,
+    /// `τ_x` times the above generator of G2.
+    pub tau_x_h: E::G2Affine,
+    /// `τ_y` times the above generator of G2.
+    pub tau_y_h: E::G2Affine,
+}
+
+impl<E: Pairing> UniversalParams<E> {
+    /// The maximum degrees in `X` and in `Y` of the polynomials supported by
+    /// `self`.
+    pub fn max_degrees(&self) -> (usize, usize) {
+        (
+            self.powers_of_g.len() - 1,
+            self.powers_of_g.first().map_or(1, Vec::len) - 1,
+        )
+    }
+}
+
+/// `CommitterKey` is used to commit to bivariate polynomials and to create
+/// their evaluation proofs.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct CommitterKey<E: Pairing> {
+    /// Group elements of the form `τ_x^i τ_y^j G`, where `powers_of_g[i][j]`
+    /// is that of `X^i Y^j`.
+    pub powers_of_g: Vec<Vec<E::G1Affine>>,
+}
+
+impl<E: Pairing> CommitterKey<E> {
+    /// The maximum degrees in `X` and in `Y` of the polynomials supported by
+    /// `self`.
+    pub fn supported_degrees(&self) -> (usize, usize) {
+        (
+            self.powers_of_g.len() - 1,
+            self.powers_of_g.first().map_or(1, Vec::len) - 1,
+        )
+    }
+
+    /// The powers of `τ_x G` for `Variable::X`, or of `τ_y G` for
+    /// `Variable::Y`, with which univariate polynomials in that variable are
+    /// committed to by `KZG10`. These commit to the results of partial
+    /// openings in the other variable, and do not support hiding.
+    pub fn powers(&self, variable: Variable) -> kzg10::Powers<'_, E> {
+        let powers_of_g = match variable {
+            Variable::X => Cow::Owned(self.powers_of_g.iter().map(|row| row[0]).collect()),
+            Variable::Y => Cow::Borrowed(self.powers_of_g[0].as_slice()),
+        };
+        kzg10::Powers {
+            powers_of_g,
+            powers_of_gamma_g: Cow::Owned(Vec::new()),
+        }
+    }
+}
+
+/// `VerifierKey` is used to check evaluation proofs for a given commitment.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+pub struct VerifierKey<E: Pairing> {
+    /// The generator of G1.
+    pub g: E::G1Affine,
+    /// The generator of G2.
+    pub h: E::G2Affine,
+    /// `τ_x` times the above generator of G2.
+    pub tau_x_h: E::G2Affine,
+    /// `τ_y` times the above generator of G2.
+    pub tau_y_h: E::G2Affine,
+    /// The generator of G2, prepared for use in pairings.
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub prepared_h: E::G2Prepared,
+    /// `τ_x` times the above generator of G2, prepared for use in pairings.
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub prepared_tau_x_h: E::G2Prepared,
+    /// `τ_y` times the above generator of G2, prepared for use in pairings.
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub prepared_tau_y_h: E::G2Prepared,
+}
+
+impl<E: Pairing> VerifierKey<E> {
+    /// Constructs the verifier key from its unprepared elements.
+    pub fn new(g: E::G1Affine, h: E::G2Affine, tau_x_h: E::G2Affine, tau_y_h: E::G2Affine) -> Self {
+        Self {
+            g,
+            h,
+            tau_x_h,
+            tau_y_h,
+            prepared_h: h.into(),
+            prepared_tau_x_h: tau_x_h.into(),
+            prepared_tau_y_h: tau_y_h.into(),
+        }
+    }
+
+    /// `τ_x h` for `Variable::X`, and `τ_y h` for `Variable::Y`, prepared
+    /// for use in pairings.
+    pub(crate) fn prepared_tau_h(&self, variable: Variable) -> &E::G2Prepared {
+        match variable {
+            Variable::X => &self.prepared_tau_x_h,
+            Variable::Y => &self.prepared_tau_y_h,
+        }
+    }
+
+    /// The `KZG10` verifier key of the univariate polynomials in `variable`
+    /// committed to with `CommitterKey::powers(variable)`. It has no hiding
+    /// generator, so only non-hiding proofs can be checked with it.
+    pub fn kzg10_verifier_key(&self, variable: Variable) -> kzg10::VerifierKey<E> {
+        kzg10::VerifierKey {
+            g: self.g,
+            gamma_g: E::G1Affine::zero(),
+            h: self.h,
+            beta_h: match variable {
+                Variable::X => self.tau_x_h,
+                Variable::Y => self.tau_y_h,
+            },
+            prepared_h: self.prepared_h.clone(),
+            prepared_beta_h: self.prepared_tau_h(variable).clone(),
+        }
+    }
+}
+
+impl<E: Pairing> Valid for VerifierKey<E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.g.check()?;
+        self.h.check()?;
+        self.tau_x_h.check()?;
+        self.tau_y_h.check()
+    }
+}
+
+impl<E: Pairing> CanonicalSerialize for VerifierKey<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.g.serialize_with_mode(&mut writer, compress)?;
+        self.h.serialize_with_mode(&mut writer, compress)?;
+        self.tau_x_h.serialize_with_mode(&mut writer, compress)?;
+        self.tau_y_h.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.g.serialized_size(compress)
+            + self.h.serialized_size(compress)
+            + self.tau_x_h.serialized_size(compress)
+            + self.tau_y_h.serialized_size(compress)
+    }
+}
+
+impl<E: Pairing> CanonicalDeserialize for VerifierKey<E> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let g = E::G1Affine::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let h = E::G2Affine::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let tau_x_h = E::G2Affine::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let tau_y_h = E::G2Affine::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+
+        let result = Self::new(g, h, tau_x_h, tau_y_h);
+        if let Validate::Yes = validate {
+            result.check()?;
+        }
+        Ok(result)
+    }
+}
+
+/// `Proof` is an evaluation proof at a point `(x, y)` that is output by
+/// `BivariateKZG::open`.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Default(bound = ""),
+    Hash(bound = ""),
+    Clone(bound = ""),
+    Copy(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+pub struct Proof<E: Pairing> {
+    /// The commitment to the quotient `(f(X, Y) - f(x, Y)) / (X - x)`.
+    pub w_x: E::G1Affine,
+    /// The commitment to the quotient `(f(x, Y) - f(x, y)) / (Y - y)`.
+    pub w_y: E::G1Affine,
+}
+
+/// `PartialProof` proves that a univariate commitment is to a partial
+/// evaluation of a bivariate polynomial, and is output by
+/// `BivariateKZG::open_partial`.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Default(bound = ""),
+    Hash(bound = ""),
+    Clone(bound = ""),
+    Copy(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+pub struct PartialProof<E: Pairing> {
+    /// The commitment to the quotient of `f` minus its partial evaluation by
+    /// the linear polynomial vanishing at the point in the opened variable.
+    pub w: E::G1Affine,
+}
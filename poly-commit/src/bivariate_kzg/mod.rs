@@ -0,0 +1,611 @@
+//! Here we construct a commitment to bivariate polynomials `f(X, Y)` which
+//! extends [[KZG10]][kzg] to two trapdoors `τ_x` and `τ_y`, as used by
+//! distributed provers such as [[LXZ+23, "Pianist"]][pianist] and by lookup
+//! arguments. A polynomial can be opened at a point `(x, y)`, or partially at
+//! `X = x` (resp. `Y = y`), which yields a `KZG10` commitment to the
+//! univariate polynomial `f(x, Y)` (resp. `f(X, y)`) that can in turn be
+//! opened with the powers and verifier key of that variable.
+//!
+//! [kzg]: http://cacr.uwaterloo.ca/techreports/2010/cacr2010-10.pdf
+//! [pianist]: https://eprint.iacr.org/2023/1271
+use crate::{kzg10, utils::check_lengths, Error, PCCommitmentState};
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::{pairing::Pairing, scalar_mul::ScalarMul, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{One, PrimeField, UniformRand, Zero};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain};
+#[cfg(not(feature = "std"))]
+use ark_std::vec::Vec;
+use ark_std::{format, marker::PhantomData, ops::Mul, rand::RngCore};
+
+mod data_structures;
+pub use data_structures::*;
+
+/// `BivariateKZG` is an implementation of the bivariate extension of the
+/// polynomial commitment scheme of [[KZG10]][kzg]. Its commitments are
+/// `KZG10` commitments, and are not hiding.
+///
+/// [kzg]: http://cacr.uwaterloo.ca/techreports/2010/cacr2010-10.pdf
+pub struct BivariateKZG<E: Pairing> {
+    _engine: PhantomData<E>,
+}
+
+impl<E> BivariateKZG<E>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+{
+    /// Constructs public parameters for polynomials of degree at most
+    /// `max_degree_x` in `X` and `max_degree_y` in `Y`.
+    pub fn setup<R: RngCore>(
+        max_degree_x: usize,
+        max_degree_y: usize,
+        rng: &mut R,
+    ) -> Result<UniversalParams<E>, Error> {
+        phase_span!("BivariateKZG::setup", max_degree_x, max_degree_y);
+        if max_degree_x < 1 || max_degree_y < 1 {
+            return Err(Error::DegreeIsZero);
+        }
+        let setup_time = start_timer!(|| format!(
+            "BivariateKZG::Setup with degrees ({}, {})",
+            max_degree_x, max_degree_y
+        ));
+        let tau_x = E::ScalarField::rand(rng);
+        let tau_y = E::ScalarField::rand(rng);
+        let g = E::G1::rand(rng);
+        let h = E::G2::rand(rng);
+
+        let powers_of_tau_y = powers(tau_y, max_degree_y + 1);
+        let powers_of_tau: Vec<_> = powers(tau_x, max_degree_x + 1)
+            .into_iter()
+            .flat_map(|tau_x_i| powers_of_tau_y.iter().map(move |tau_y_j| tau_x_i * tau_y_j))
+            .collect();
+
+        let g_time = start_timer!(|| "Generating powers of G");
+        let powers_of_g = g
+            .batch_mul(&powers_of_tau)
+            .chunks(max_degree_y + 1)
+            .map(<[_]>::to_vec)
+            .collect();
+        end_timer!(g_time);
+
+        let pp = UniversalParams {
+            powers_of_g,
+            h: h.into_affine(),
+            tau_x_h: h.mul(tau_x).into_affine(),
+            tau_y_h: h.mul(tau_y).into_affine(),
+        };
+        end_timer!(setup_time);
+        Ok(pp)
+    }
+
+    /// Specializes the public parameters to polynomials of degree at most
+    /// `degree_x` in `X` and `degree_y` in `Y`.
+    pub fn trim(
+        pp: &UniversalParams<E>,
+        degree_x: usize,
+        degree_y: usize,
+    ) -> Result<(CommitterKey<E>, VerifierKey<E>), Error> {
+        phase_span!("BivariateKZG::trim", degree_x, degree_y);
+        let (max_degree_x, max_degree_y) = pp.max_degrees();
+        if degree_x > max_degree_x || degree_y > max_degree_y {
+            return Err(Error::TrimmingDegreeTooLarge);
+        }
+        let ck = CommitterKey {
+            powers_of_g: pp.powers_of_g[..=degree_x]
+                .iter()
+                .map(|row| row[..=degree_y].to_vec())
+                .collect(),
+        };
+        let vk = VerifierKey::new(pp.powers_of_g[0][0], pp.h, pp.tau_x_h, pp.tau_y_h);
+        Ok((ck, vk))
+    }
+
+    /// Outputs a commitment to `polynomial`.
+    pub fn commit(
+        ck: &CommitterKey<E>,
+        polynomial: &BivariatePolynomial<E::ScalarField>,
+    ) -> Result<kzg10::Commitment<E>, Error> {
+        phase_span!(
+            "BivariateKZG::commit",
+            degree_x = polynomial.degree_x(),
+            degree_y = polynomial.degree_y()
+        );
+        let commit_time = start_timer!(|| format!(
+            "Committing to bivariate polynomial of degrees ({}, {})",
+            polynomial.degree_x(),
+            polynomial.degree_y()
+        ));
+        let commitment = commit_grid(&ck.powers_of_g, polynomial.coeffs())?;
+        end_timer!(commit_time);
+        Ok(kzg10::Commitment(commitment.into_affine()))
+    }
+
+    /// Outputs a commitment to the polynomial given by its evaluations
+    /// `evals` over `domain_x × domain_y`, as in
+    /// `BivariatePolynomial::from_lagrange_grid`.
+    pub fn commit_lagrange<D: EvaluationDomain<E::ScalarField>>(
+        ck: &CommitterKey<E>,
+        evals: Vec<Vec<E::ScalarField>>,
+        domain_x: D,
+        domain_y: D,
+    ) -> Result<kzg10::Commitment<E>, Error> {
+        let polynomial = BivariatePolynomial::from_lagrange_grid(evals, domain_x, domain_y)?;
+        Self::commit(ck, &polynomial)
+    }
+
+    /// On input a polynomial `polynomial` and a point `(x, y)`, outputs a
+    /// proof of its evaluation at that point.
+    pub fn open(
+        ck: &CommitterKey<E>,
+        polynomial: &BivariatePolynomial<E::ScalarField>,
+        x: E::ScalarField,
+        y: E::ScalarField,
+    ) -> Result<Proof<E>, Error> {
+        phase_span!("BivariateKZG::open");
+        let open_time = start_timer!(|| "Opening bivariate polynomial");
+        let (quotient_x, partial) = divide_rows(polynomial.coeffs(), x);
+        let (quotient_y, _) = divide_by_linear(&partial, y);
+
+        let w_x = commit_grid(&ck.powers_of_g, &quotient_x)?;
+        let w_y = commit_grid(&ck.powers_of_g, &[quotient_y])?;
+        let w = E::G1::normalize_batch(&[w_x, w_y]);
+        end_timer!(open_time);
+        Ok(Proof {
+            w_x: w[0],
+            w_y: w[1],
+        })
+    }
+
+    /// On input a polynomial `polynomial`, outputs a commitment to its
+    /// partial evaluation at `variable = point`, along with a proof that the
+    /// latter is consistent with the commitment to `polynomial`. The partial
+    /// evaluation is committed to with `CommitterKey::powers` of the other
+    /// variable.
+    pub fn open_partial(
+        ck: &CommitterKey<E>,
+        polynomial: &BivariatePolynomial<E::ScalarField>,
+        variable: Variable,
+        point: E::ScalarField,
+    ) -> Result<(kzg10::Commitment<E>, PartialProof<E>), Error> {
+        phase_span!("BivariateKZG::open_partial", variable = ?variable);
+        let open_time = start_timer!(|| format!("Opening bivariate polynomial at {:?}", variable));
+        let (partial_commitment, w) = match variable {
+            Variable::X => {
+                let (quotient, partial) = divide_rows(polynomial.coeffs(), point);
+                (
+                    commit_grid(&ck.powers_of_g, &[partial])?,
+                    commit_grid(&ck.powers_of_g, &quotient)?,
+                )
+            }
+            Variable::Y => {
+                let (quotient, partial): (Vec<_>, Vec<_>) = polynomial
+                    .coeffs()
+                    .iter()
+                    .map(|row| divide_by_linear(row, point))
+                    .unzip();
+                let partial: Vec<_> = partial.into_iter().map(|c| vec![c]).collect();
+                (
+                    commit_grid(&ck.powers_of_g, &partial)?,
+                    commit_grid(&ck.powers_of_g, &quotient)?,
+                )
+            }
+        };
+        let affine = E::G1::normalize_batch(&[partial_commitment, w]);
+        end_timer!(open_time);
+        Ok((kzg10::Commitment(affine[0]), PartialProof { w: affine[1] }))
+    }
+
+    /// On input the partial evaluation of a bivariate polynomial at
+    /// `variable`, which is a univariate polynomial in the other variable,
+    /// outputs a `KZG10` proof of its evaluation at `point`. The proof is
+    /// checked by `KZG10::check` against `VerifierKey::kzg10_verifier_key`
+    /// of the other variable.
+    pub fn open_univariate(
+        ck: &CommitterKey<E>,
+        partial: &DensePolynomial<E::ScalarField>,
+        variable: Variable,
+        point: E::ScalarField,
+    ) -> Result<kzg10::Proof<E>, Error> {
+        let other = match variable {
+            Variable::X => Variable::Y,
+            Variable::Y => Variable::X,
+        };
+        kzg10::KZG10::<E, DensePolynomial<E::ScalarField>>::open(
+            &ck.powers(other),
+            partial,
+            point,
+            &kzg10::Randomness::empty(),
+        )
+    }
+
+    /// Verifies that `value` is the evaluation at `(x, y)` of the polynomial
+    /// committed inside `commitment`.
+    pub fn check(
+        vk: &VerifierKey<E>,
+        commitment: &kzg10::Commitment<E>,
+        x: E::ScalarField,
+        y: E::ScalarField,
+        value: E::ScalarField,
+        proof: &Proof<E>,
+    ) -> Result<bool, Error> {
+        Self::batch_check_with_randomizers(
+            vk,
+            &[*commitment],
+            &[(x, y)],
+            &[value],
+            &[*proof],
+            &mut || E::ScalarField::one(),
+        )
+    }
+
+    /// Check that each `proof_i` in `proofs` is a valid proof of evaluation
+    /// for `commitment_i` at `point_i`.
+    pub fn batch_check<R: RngCore>(
+        vk: &VerifierKey<E>,
+        commitments: &[kzg10::Commitment<E>],
+        points: &[(E::ScalarField, E::ScalarField)],
+        values: &[E::ScalarField],
+        proofs: &[Proof<E>],
+        rng: &mut R,
+    ) -> Result<bool, Error> {
+        Self::batch_check_with_randomizers(vk, commitments, points, values, proofs, &mut || {
+            // We don't need to sample randomizers from the full field,
+            // only from 128-bit strings.
+            u128::rand(rng).into()
+        })
+    }
+
+    fn batch_check_with_randomizers(
+        vk: &VerifierKey<E>,
+        commitments: &[kzg10::Commitment<E>],
+        points: &[(E::ScalarField, E::ScalarField)],
+        values: &[E::ScalarField],
+        proofs: &[Proof<E>],
+        randomizer: &mut dyn FnMut() -> E::ScalarField,
+    ) -> Result<bool, Error> {
+        phase_span!("BivariateKZG::batch_check", num_proofs = commitments.len());
+        check_lengths(
+            commitments.len(),
+            &[points.len(), values.len(), proofs.len()],
+        )?;
+        let check_time =
+            start_timer!(|| format!("Checking {} evaluation proofs", commitments.len()));
+
+        // Each proof satisfies `C - v G = (τ_x - x) W_x + (τ_y - y) W_y`. We
+        // check a random linear combination of these equations, accumulating
+        // the coefficient of `G` and multiplying it at the end.
+        let mut total_c = E::G1::zero();
+        let mut total_w_x = E::G1::zero();
+        let mut total_w_y = E::G1::zero();
+        let mut g_multiplier = E::ScalarField::zero();
+        for (((c, (x, y)), v), proof) in commitments.iter().zip(points).zip(values).zip(proofs) {
+            let r = randomizer();
+            total_c += (proof.w_x.mul(*x) + proof.w_y.mul(*y) + c.0).mul(r);
+            total_w_x += proof.w_x.mul(r);
+            total_w_y += proof.w_y.mul(r);
+            g_multiplier += r * v;
+        }
+        total_c -= vk.g.mul(g_multiplier);
+
+        let affine_points = E::G1::normalize_batch(&[-total_w_x, -total_w_y, total_c]);
+        count!(PAIRING, 3);
+        let result = E::multi_pairing(
+            affine_points,
+            [
+                vk.prepared_tau_x_h.clone(),
+                vk.prepared_tau_y_h.clone(),
+                vk.prepared_h.clone(),
+            ],
+        )
+        .0
+        .is_one();
+        end_timer!(check_time, || format!("Result: {}", result));
+        Ok(result)
+    }
+
+    /// Verifies that `partial_commitment` is a commitment to the partial
+    /// evaluation at `variable = point` of the polynomial committed inside
+    /// `commitment`.
+    pub fn check_partial(
+        vk: &VerifierKey<E>,
+        commitment: &kzg10::Commitment<E>,
+        variable: Variable,
+        point: E::ScalarField,
+        partial_commitment: &kzg10::Commitment<E>,
+        proof: &PartialProof<E>,
+    ) -> Result<bool, Error> {
+        Self::batch_check_partial_with_randomizers(
+            vk,
+            &[*commitment],
+            &[(variable, point)],
+            &[*partial_commitment],
+            &[*proof],
+            &mut || E::ScalarField::one(),
+        )
+    }
+
+    /// Check that each `proof_i` in `proofs` is a valid proof that
+    /// `partial_commitment_i` is a commitment to the partial evaluation at
+    /// `query_i = (variable_i, point_i)` of the polynomial committed inside
+    /// `commitment_i`. Queries in either variable can be mixed.
+    pub fn batch_check_partial<R: RngCore>(
+        vk: &VerifierKey<E>,
+        commitments: &[kzg10::Commitment<E>],
+        queries: &[(Variable, E::ScalarField)],
+        partial_commitments: &[kzg10::Commitment<E>],
+        proofs: &[PartialProof<E>],
+        rng: &mut R,
+    ) -> Result<bool, Error> {
+        Self::batch_check_partial_with_randomizers(
+            vk,
+            commitments,
+            queries,
+            partial_commitments,
+            proofs,
+            &mut || u128::rand(rng).into(),
+        )
+    }
+
+    fn batch_check_partial_with_randomizers(
+        vk: &VerifierKey<E>,
+        commitments: &[kzg10::Commitment<E>],
+        queries: &[(Variable, E::ScalarField)],
+        partial_commitments: &[kzg10::Commitment<E>],
+        proofs: &[PartialProof<E>],
+        randomizer: &mut dyn FnMut() -> E::ScalarField,
+    ) -> Result<bool, Error> {
+        phase_span!(
+            "BivariateKZG::check_partial",
+            num_proofs = commitments.len()
+        );
+        check_lengths(
+            commitments.len(),
+            &[queries.len(), partial_commitments.len(), proofs.len()],
+        )?;
+        let check_time =
+            start_timer!(|| format!("Checking {} partial evaluation proofs", commitments.len()));
+
+        // Each proof satisfies `C - C' = (τ - z) W`, where `τ` is the trapdoor
+        // of the opened variable, so the proofs are accumulated per variable.
+        let mut total_c = E::G1::zero();
+        let mut total_w_x = E::G1::zero();
+        let mut total_w_y = E::G1::zero();
+        for (((c, (variable, z)), partial), proof) in commitments
+            .iter()
+            .zip(queries)
+            .zip(partial_commitments)
+            .zip(proofs)
+        {
+            let r = randomizer();
+            total_c += (proof.w.mul(*z) + c.0 - partial.0).mul(r);
+            match variable {
+                Variable::X => total_w_x += proof.w.mul(r),
+                Variable::Y => total_w_y += proof.w.mul(r),
+            }
+        }
+
+        let affine_points = E::G1::normalize_batch(&[-total_w_x, -total_w_y, total_c]);
+        count!(PAIRING, 3);
+        let result = E::multi_pairing(
+            affine_points,
+            [
+                vk.prepared_tau_h(Variable::X).clone(),
+                vk.prepared_tau_h(Variable::Y).clone(),
+                vk.prepared_h.clone(),
+            ],
+        )
+        .0
+        .is_one();
+        end_timer!(check_time, || format!("Result: {}", result));
+        Ok(result)
+    }
+}
+
+/// Outputs `[1, τ, ..., τ^(n - 1)]`.
+fn powers<F: PrimeField>(tau: F, n: usize) -> Vec<F> {
+    ark_std::iter::successors(Some(F::one()), |p| Some(*p * tau))
+        .take(n)
+        .collect()
+}
+
+/// Computes the MSM of the grid of coefficients `coeffs` with the
+/// corresponding powers of `powers_of_g`.
+fn commit_grid<G: AffineRepr>(
+    powers_of_g: &[Vec<G>],
+    coeffs: &[Vec<G::ScalarField>],
+) -> Result<G::Group, Error> {
+    let num_columns = coeffs.first().map_or(0, Vec::len);
+    let num_powers = powers_of_g.first().map_or(0, Vec::len);
+    if coeffs.len() > powers_of_g.len() {
+        return Err(Error::TooManyCoefficients {
+            num_coefficients: coeffs.len(),
+            num_powers: powers_of_g.len(),
+        });
+    }
+    if num_columns > num_powers {
+        return Err(Error::TooManyCoefficients {
+            num_coefficients: num_columns,
+            num_powers,
+        });
+    }
+    let bases: Vec<G> = powers_of_g
+        .iter()
+        .zip(coeffs)
+        .flat_map(|(row, c)| row[..c.len()].iter().copied())
+        .collect();
+    let scalars: Vec<_> = coeffs.iter().flatten().map(|c| c.into_bigint()).collect();
+    count!(MSM, scalars.len());
+    Ok(<G::Group as VariableBaseMSM>::msm_bigint(&bases, &scalars))
+}
+
+/// Divides the univariate polynomial with coefficients `coeffs` by `X - point`,
+/// outputting the coefficients of the quotient and the remainder.
+fn divide_by_linear<F: PrimeField>(coeffs: &[F], point: F) -> (Vec<F>, F) {
+    let mut quotient = vec![F::zero(); coeffs.len().saturating_sub(1)];
+    let mut acc = F::zero();
+    for (i, c) in coeffs.iter().enumerate().rev() {
+        acc = acc * point + c;
+        if i > 0 {
+            quotient[i - 1] = acc;
+        }
+    }
+    let remainder = if coeffs.is_empty() { F::zero() } else { acc };
+    (quotient, remainder)
+}
+
+/// Divides the bivariate polynomial with coefficients `coeffs` by `X - point`,
+/// outputting the coefficients of the quotient and those of the remainder,
+/// which is the univariate polynomial `f(point, Y)`.
+fn divide_rows<F: PrimeField>(coeffs: &[Vec<F>], point: F) -> (Vec<Vec<F>>, Vec<F>) {
+    let mut quotient = vec![Vec::new(); coeffs.len().saturating_sub(1)];
+    let mut acc = vec![F::zero(); coeffs.first().map_or(0, Vec::len)];
+    for (i, row) in coeffs.iter().enumerate().rev() {
+        for (a, c) in acc.iter_mut().zip(row) {
+            *a = *a * point + c;
+        }
+        if i > 0 {
+            quotient[i - 1] = acc.clone();
+        }
+    }
+    (quotient, acc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_poly::{GeneralEvaluationDomain, Polynomial};
+    use ark_std::test_rng;
+
+    type Bkzg = BivariateKZG<Bls12_381>;
+    type Kzg = kzg10::KZG10<Bls12_381, DensePolynomial<Fr>>;
+
+    #[test]
+    fn open_and_check() {
+        let rng = &mut test_rng();
+        let pp = Bkzg::setup(7, 5, rng).unwrap();
+        let (ck, vk) = Bkzg::trim(&pp, 6, 3).unwrap();
+        let f = BivariatePolynomial::rand(6, 3, rng);
+        let comm = Bkzg::commit(&ck, &f).unwrap();
+
+        let (x, y) = (Fr::rand(rng), Fr::rand(rng));
+        let value = f.evaluate(x, y);
+        let proof = Bkzg::open(&ck, &f, x, y).unwrap();
+        assert!(Bkzg::check(&vk, &comm, x, y, value, &proof).unwrap());
+        assert!(!Bkzg::check(&vk, &comm, x, y, value + Fr::one(), &proof).unwrap());
+        assert!(!Bkzg::check(&vk, &comm, y, x, value, &proof).unwrap());
+
+        let too_large = BivariatePolynomial::rand(7, 3, rng);
+        assert!(matches!(
+            Bkzg::commit(&ck, &too_large),
+            Err(Error::TooManyCoefficients { .. })
+        ));
+    }
+
+    #[test]
+    fn partial_openings_are_kzg10_commitments() {
+        let rng = &mut test_rng();
+        let pp = Bkzg::setup(4, 6, rng).unwrap();
+        let (ck, vk) = Bkzg::trim(&pp, 4, 6).unwrap();
+        let f = BivariatePolynomial::rand(4, 6, rng);
+        let comm = Bkzg::commit(&ck, &f).unwrap();
+
+        for (variable, other) in [(Variable::X, Variable::Y), (Variable::Y, Variable::X)] {
+            let point = Fr::rand(rng);
+            let (partial_comm, proof) = Bkzg::open_partial(&ck, &f, variable, point).unwrap();
+            assert!(
+                Bkzg::check_partial(&vk, &comm, variable, point, &partial_comm, &proof).unwrap()
+            );
+            assert!(!Bkzg::check_partial(&vk, &comm, other, point, &partial_comm, &proof).unwrap());
+
+            let partial = f.partial_evaluate(variable, point);
+            let (expected, _) = Kzg::commit(&ck.powers(other), &partial, None, None).unwrap();
+            assert_eq!(partial_comm, expected);
+
+            let z = Fr::rand(rng);
+            let univariate_proof = Bkzg::open_univariate(&ck, &partial, variable, z).unwrap();
+            let kzg10_vk = vk.kzg10_verifier_key(other);
+            let value = partial.evaluate(&z);
+            assert!(Kzg::check(&kzg10_vk, &partial_comm, z, value, &univariate_proof).unwrap());
+        }
+    }
+
+    #[test]
+    fn lagrange_grid_commitment() {
+        let rng = &mut test_rng();
+        let pp = Bkzg::setup(7, 3, rng).unwrap();
+        let (ck, _) = Bkzg::trim(&pp, 7, 3).unwrap();
+        let domain_x = GeneralEvaluationDomain::<Fr>::new(8).unwrap();
+        let domain_y = GeneralEvaluationDomain::<Fr>::new(4).unwrap();
+        let f = BivariatePolynomial::rand(7, 3, rng);
+        let evals: Vec<Vec<Fr>> = domain_x
+            .elements()
+            .map(|x| domain_y.elements().map(|y| f.evaluate(x, y)).collect())
+            .collect();
+
+        assert_eq!(
+            BivariatePolynomial::from_lagrange_grid(evals.clone(), domain_x, domain_y).unwrap(),
+            f
+        );
+        assert_eq!(
+            Bkzg::commit_lagrange(&ck, evals, domain_x, domain_y).unwrap(),
+            Bkzg::commit(&ck, &f).unwrap()
+        );
+    }
+
+    #[test]
+    fn batch_check() {
+        let rng = &mut test_rng();
+        let pp = Bkzg::setup(5, 5, rng).unwrap();
+        let (ck, vk) = Bkzg::trim(&pp, 5, 5).unwrap();
+        let mut commitments = Vec::new();
+        let mut points = Vec::new();
+        let mut values = Vec::new();
+        let mut proofs = Vec::new();
+        let mut partial_commitments = Vec::new();
+        let mut queries = Vec::new();
+        let mut partial_proofs = Vec::new();
+        for i in 0..6 {
+            let f = BivariatePolynomial::rand(5 - i % 3, 2 + i % 4, rng);
+            let comm = Bkzg::commit(&ck, &f).unwrap();
+            let (x, y) = (Fr::rand(rng), Fr::rand(rng));
+            commitments.push(comm);
+            points.push((x, y));
+            values.push(f.evaluate(x, y));
+            proofs.push(Bkzg::open(&ck, &f, x, y).unwrap());
+
+            let variable = if i % 2 == 0 { Variable::X } else { Variable::Y };
+            let (partial_comm, proof) = Bkzg::open_partial(&ck, &f, variable, x).unwrap();
+            queries.push((variable, x));
+            partial_commitments.push(partial_comm);
+            partial_proofs.push(proof);
+        }
+        assert!(Bkzg::batch_check(&vk, &commitments, &points, &values, &proofs, rng).unwrap());
+        assert!(Bkzg::batch_check_partial(
+            &vk,
+            &commitments,
+            &queries,
+            &partial_commitments,
+            &partial_proofs,
+            rng
+        )
+        .unwrap());
+
+        values[3] += Fr::one();
+        assert!(!Bkzg::batch_check(&vk, &commitments, &points, &values, &proofs, rng).unwrap());
+        partial_commitments.swap(0, 1);
+        assert!(!Bkzg::batch_check_partial(
+            &vk,
+            &commitments,
+            &queries,
+            &partial_commitments,
+            &partial_proofs,
+            rng
+        )
+        .unwrap());
+        assert!(matches!(
+            Bkzg::batch_check(&vk, &commitments, &points[1..], &values, &proofs, rng),
+            Err(Error::IncorrectInputLength(_))
+        ));
+    }
+}
@@ -0,0 +1,530 @@
+use crate::{
+    utils::{check_lengths, deserialize_vec},
+    BatchLCProof, Error, Evaluations, LabeledCommitment, LabeledPolynomial, LinearCombination,
+    PCCommitment, PCCommitmentState, PCCommitterKey, PCVerifierKey, PolynomialCommitment, QuerySet,
+};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ff::PrimeField;
+use ark_poly::DenseUVPolynomial;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+};
+use ark_std::{format, marker::PhantomData, rand::RngCore};
+#[cfg(not(feature = "std"))]
+use ark_std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Label of the point at which the chunks of the polynomials are opened.
+const POINT_LABEL: &str = "point";
+
+/// Commitment to a polynomial split into chunks, each of which is committed
+/// to with the underlying scheme.
+#[derive(Derivative, CanonicalSerialize, Absorb)]
+#[derivative(Default(bound = ""), Clone(bound = ""))]
+pub struct ChunkedCommitment<C: PCCommitment> {
+    /// The commitments to the chunks, lowest-degree chunk first.
+    pub chunks: Vec<C>,
+}
+
+impl<C: PCCommitment> Valid for ChunkedCommitment<C> {
+    fn check(&self) -> Result<(), SerializationError> {
+        if self.chunks.is_empty() {
+            return Err(SerializationError::InvalidData);
+        }
+        self.chunks.check()
+    }
+}
+
+impl<C: PCCommitment> CanonicalDeserialize for ChunkedCommitment<C> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let chunks = deserialize_vec(reader, compress)?;
+        let result = Self { chunks };
+        if let Validate::Yes = validate {
+            result.check()?;
+        }
+        Ok(result)
+    }
+}
+
+impl<C: PCCommitment> PCCommitment for ChunkedCommitment<C> {
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            chunks: vec![C::empty()],
+        }
+    }
+
+    fn has_degree_bound(&self) -> bool {
+        false
+    }
+}
+
+/// Commitment state of a polynomial split into chunks: the state of the
+/// commitment to each chunk.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(Clone(bound = ""))]
+pub struct ChunkedCommitmentState<S: PCCommitmentState> {
+    /// The states of the commitments to the chunks, lowest-degree chunk first.
+    pub chunks: Vec<S>,
+}
+
+impl<S: PCCommitmentState> PCCommitmentState for ChunkedCommitmentState<S> {
+    type Randomness = S::Randomness;
+
+    fn empty() -> Self {
+        Self {
+            chunks: vec![S::empty()],
+        }
+    }
+
+    fn rand<R: RngCore>(
+        num_queries: usize,
+        has_degree_bound: bool,
+        num_vars: Option<usize>,
+        rng: &mut R,
+    ) -> Self::Randomness {
+        S::rand(num_queries, has_degree_bound, num_vars, rng)
+    }
+}
+
+/// Univariate polynomial commitment scheme which lifts the degree limit of the
+/// committer key of `PC`. A polynomial with more coefficients than the key
+/// supports is split into chunks `p_i` of `D = supported_degree + 1`
+/// coefficients, with `p(X) = Σ X^{iD} p_i(X)`, and each chunk is committed to
+/// with `PC`. An opening at `z` proves the evaluation of the linear
+/// combination `Σ z^{iD} p_i` with `PC::open_combinations`, which homomorphic
+/// schemes such as `MarlinKZG10`, `SonicKZG10` and `InnerProductArgPC` prove
+/// with a single opening per point.
+///
+/// Polynomials that fit in the key are committed to as a single chunk. Strict
+/// degree bounds are not supported.
+pub struct ChunkedPC<F, P, PC> {
+    _field: PhantomData<F>,
+    _poly: PhantomData<P>,
+    _pc: PhantomData<PC>,
+}
+
+impl<F, P, PC> ChunkedPC<F, P, PC>
+where
+    F: PrimeField,
+    P: DenseUVPolynomial<F, Point = F>,
+    PC: PolynomialCommitment<F, P>,
+{
+    fn chunk_label(label: &str, index: usize) -> String {
+        format!("{}[{}]", label, index)
+    }
+
+    /// Splits `polynomial` into chunks of `chunk_len` coefficients.
+    fn chunks(
+        polynomial: &LabeledPolynomial<F, P>,
+        chunk_len: usize,
+    ) -> Result<Vec<LabeledPolynomial<F, P>>, Error> {
+        if let Some(degree_bound) = polynomial.degree_bound() {
+            return Err(Error::UnsupportedDegreeBound(degree_bound));
+        }
+        let coeffs = polynomial.coeffs();
+        let chunks = if coeffs.is_empty() {
+            vec![P::zero()]
+        } else {
+            coeffs
+                .chunks(chunk_len)
+                .map(P::from_coefficients_slice)
+                .collect()
+        };
+        Ok(chunks
+            .into_iter()
+            .enumerate()
+            .map(|(i, chunk)| {
+                LabeledPolynomial::new(
+                    Self::chunk_label(polynomial.label(), i),
+                    chunk,
+                    None,
+                    polynomial.hiding_bound(),
+                )
+            })
+            .collect())
+    }
+
+    /// Splits `commitment` into labelled commitments to its chunks.
+    fn chunk_commitments(
+        commitment: &LabeledCommitment<ChunkedCommitment<PC::Commitment>>,
+    ) -> impl Iterator<Item = LabeledCommitment<PC::Commitment>> + '_ {
+        let label = commitment.label();
+        commitment
+            .commitment()
+            .chunks
+            .iter()
+            .enumerate()
+            .map(move |(i, chunk)| {
+                LabeledCommitment::new(Self::chunk_label(label, i), chunk.clone(), None)
+            })
+    }
+
+    /// The linear combination `Σ point^{i * chunk_len} p_i` of the chunks of
+    /// the polynomial labelled `label`.
+    fn combination(
+        label: &str,
+        num_chunks: usize,
+        chunk_len: usize,
+        point: &F,
+    ) -> LinearCombination<F> {
+        let shift = point.pow([chunk_len as u64]);
+        let mut coeff = F::one();
+        let terms = (0..num_chunks)
+            .map(|i| {
+                let term = (coeff, Self::chunk_label(label, i));
+                coeff *= shift;
+                term
+            })
+            .collect();
+        LinearCombination::new(label.to_string(), terms)
+    }
+}
+
+impl<F, P, PC> PolynomialCommitment<F, P> for ChunkedPC<F, P, PC>
+where
+    F: PrimeField,
+    P: DenseUVPolynomial<F, Point = F>,
+    PC: PolynomialCommitment<F, P>,
+    PC::Commitment: 'static,
+    PC::CommitmentState: 'static,
+{
+    type UniversalParams = PC::UniversalParams;
+    type CommitterKey = PC::CommitterKey;
+    type VerifierKey = PC::VerifierKey;
+    type Commitment = ChunkedCommitment<PC::Commitment>;
+    type CommitmentState = ChunkedCommitmentState<PC::CommitmentState>;
+    type Proof = BatchLCProof<F, PC::BatchProof>;
+    type BatchProof = Vec<Self::Proof>;
+    type Error = PC::Error;
+
+    fn setup<R: RngCore>(
+        max_degree: usize,
+        num_vars: Option<usize>,
+        rng: &mut R,
+    ) -> Result<Self::UniversalParams, Self::Error> {
+        PC::setup(max_degree, num_vars, rng)
+    }
+
+    fn trim(
+        pp: &Self::UniversalParams,
+        supported_degree: usize,
+        supported_hiding_bound: usize,
+        enforced_degree_bounds: Option<&[usize]>,
+    ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
+        PC::trim(
+            pp,
+            supported_degree,
+            supported_hiding_bound,
+            enforced_degree_bounds,
+        )
+    }
+
+    /// Outputs a commitment to each polynomial, split into chunks of
+    /// `ck.supported_degree() + 1` coefficients.
+    fn commit<'a>(
+        ck: &Self::CommitterKey,
+        polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<Self::Commitment>>,
+            Vec<Self::CommitmentState>,
+        ),
+        Self::Error,
+    >
+    where
+        P: 'a,
+    {
+        let rng = &mut crate::optional_rng::OptionalRng(rng);
+        let chunk_len = ck.supported_degree() + 1;
+
+        let mut commitments = Vec::new();
+        let mut states = Vec::new();
+        for polynomial in polynomials {
+            let chunks = Self::chunks(polynomial, chunk_len)?;
+            let (chunk_comms, chunk_states) = PC::commit(ck, &chunks, Some(rng))?;
+            let commitment = ChunkedCommitment {
+                chunks: chunk_comms
+                    .into_iter()
+                    .map(|c| c.commitment().clone())
+                    .collect(),
+            };
+            commitments.push(LabeledCommitment::new(
+                polynomial.label().clone(),
+                commitment,
+                None,
+            ));
+            states.push(ChunkedCommitmentState {
+                chunks: chunk_states,
+            });
+        }
+        Ok((commitments, states))
+    }
+
+    /// Opens the linear combinations `Σ point^{iD} p_i` of the chunks of the
+    /// polynomials at `point`.
+    fn open<'a>(
+        ck: &Self::CommitterKey,
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Self::Error>
+    where
+        P: 'a,
+        Self::CommitmentState: 'a,
+        Self::Commitment: 'a,
+    {
+        let chunk_len = ck.supported_degree() + 1;
+        let polynomials: Vec<_> = labeled_polynomials.into_iter().collect();
+        let commitments: Vec<_> = commitments.into_iter().collect();
+        let states: Vec<_> = states.into_iter().collect();
+        check_lengths(polynomials.len(), &[commitments.len(), states.len()])?;
+
+        let mut linear_combinations = Vec::new();
+        let mut chunks = Vec::new();
+        let mut chunk_comms = Vec::new();
+        let mut chunk_states = Vec::new();
+        let mut query_set = QuerySet::new();
+        for ((polynomial, commitment), state) in
+            polynomials.into_iter().zip(commitments).zip(states)
+        {
+            let label = polynomial.label();
+            let poly_chunks = Self::chunks(polynomial, chunk_len)?;
+            let num_chunks = poly_chunks.len();
+            if commitment.commitment().chunks.len() != num_chunks
+                || state.chunks.len() != num_chunks
+            {
+                return Err(Error::IncorrectCommitmentSize {
+                    encountered: commitment.commitment().chunks.len(),
+                    expected: num_chunks,
+                }
+                .into());
+            }
+
+            linear_combinations.push(Self::combination(label, num_chunks, chunk_len, point));
+            chunks.extend(poly_chunks);
+            chunk_comms.extend(Self::chunk_commitments(commitment));
+            chunk_states.extend(state.chunks.iter());
+            query_set.insert((label.clone(), (POINT_LABEL.to_string(), *point)));
+        }
+
+        PC::open_combinations(
+            ck,
+            &linear_combinations,
+            &chunks,
+            &chunk_comms,
+            &query_set,
+            sponge,
+            chunk_states,
+            rng,
+        )
+    }
+
+    /// Verifies the opening of the linear combinations `Σ point^{iD} p_i` of
+    /// the chunks of the committed polynomials at `point`. The `rng` is
+    /// forwarded to `PC::check_combinations`, and must be provided if the
+    /// latter uses it.
+    fn check<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        values: impl IntoIterator<Item = F>,
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a,
+    {
+        let chunk_len = vk.supported_degree() + 1;
+        let commitments: Vec<_> = commitments.into_iter().collect();
+        let values: Vec<_> = values.into_iter().collect();
+        check_lengths(commitments.len(), &[values.len()])?;
+
+        let mut linear_combinations = Vec::new();
+        let mut chunk_comms = Vec::new();
+        let mut query_set = QuerySet::new();
+        let mut evaluations = Evaluations::new();
+        for (commitment, value) in commitments.into_iter().zip(values) {
+            let label = commitment.label();
+            let num_chunks = commitment.commitment().chunks.len();
+
+            linear_combinations.push(Self::combination(label, num_chunks, chunk_len, point));
+            chunk_comms.extend(Self::chunk_commitments(commitment));
+            query_set.insert((label.clone(), (POINT_LABEL.to_string(), *point)));
+            evaluations.insert((label.clone(), *point), value);
+        }
+
+        PC::check_combinations(
+            vk,
+            &linear_combinations,
+            &chunk_comms,
+            &query_set,
+            &evaluations,
+            proof,
+            sponge,
+            &mut crate::optional_rng::OptionalRng(rng),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ipa_pc::InnerProductArgPC, marlin_pc::MarlinKZG10, sonic_pc::SonicKZG10,
+        tests::poseidon_sponge_for_test,
+    };
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::pairing::Pairing;
+    use ark_ed_on_bls12_381::EdwardsAffine;
+    use ark_poly::univariate::DensePolynomial;
+    use ark_std::test_rng;
+    use blake2::Blake2s256;
+
+    type Fr = <Bls12_381 as Pairing>::ScalarField;
+    type JubjubFr = <EdwardsAffine as ark_ec::AffineRepr>::ScalarField;
+
+    type Marlin = MarlinKZG10<Bls12_381, DensePolynomial<Fr>>;
+    type Sonic = SonicKZG10<Bls12_381, DensePolynomial<Fr>>;
+    type Ipa = InnerProductArgPC<EdwardsAffine, Blake2s256, DensePolynomial<JubjubFr>>;
+
+    fn chunked_test<F, PC>()
+    where
+        F: PrimeField + Absorb,
+        PC: PolynomialCommitment<F, DensePolynomial<F>>,
+        PC::Commitment: 'static,
+        PC::CommitmentState: 'static,
+    {
+        type Chunked<F, PC> = ChunkedPC<F, DensePolynomial<F>, PC>;
+        let rng = &mut test_rng();
+        let sponge = poseidon_sponge_for_test::<F>();
+
+        let pp = Chunked::<F, PC>::setup(16, None, rng).unwrap();
+        let (ck, vk) = Chunked::<F, PC>::trim(&pp, 7, 1, None).unwrap();
+
+        for hiding_bound in [None, Some(1)] {
+            // 31 coefficients are split into 4 chunks of 8, while the second
+            // polynomial fits in the key.
+            let polynomials = [30, 5]
+                .iter()
+                .enumerate()
+                .map(|(i, degree)| {
+                    LabeledPolynomial::new(
+                        format!("Test{}", i),
+                        DensePolynomial::rand(*degree, rng),
+                        None,
+                        hiding_bound,
+                    )
+                })
+                .collect::<Vec<_>>();
+            let (comms, states) = Chunked::<F, PC>::commit(&ck, &polynomials, Some(rng)).unwrap();
+            assert_eq!(comms[0].commitment().chunks.len(), 4);
+            assert_eq!(comms[1].commitment().chunks.len(), 1);
+
+            let point = F::rand(rng);
+            let values = polynomials
+                .iter()
+                .map(|p| p.evaluate(&point))
+                .collect::<Vec<_>>();
+            let proof = Chunked::<F, PC>::open(
+                &ck,
+                &polynomials,
+                &comms,
+                &point,
+                &mut (sponge.clone()),
+                &states,
+                Some(rng),
+            )
+            .unwrap();
+            Chunked::<F, PC>::check(
+                &vk,
+                &comms,
+                &point,
+                values.clone(),
+                &proof,
+                &mut (sponge.clone()),
+                Some(rng),
+            )
+            .unwrap();
+
+            let wrong_values = [values[0] + F::one(), values[1]];
+            assert!(Chunked::<F, PC>::check(
+                &vk,
+                &comms,
+                &point,
+                wrong_values,
+                &proof,
+                &mut (sponge.clone()),
+                Some(rng),
+            )
+            .is_err());
+
+            // Every polynomial of the batch has a commitment, a state and a
+            // value.
+            assert!(Chunked::<F, PC>::open(
+                &ck,
+                &polynomials,
+                &comms[..1],
+                &point,
+                &mut (sponge.clone()),
+                &states,
+                Some(rng),
+            )
+            .is_err());
+            assert!(Chunked::<F, PC>::check(
+                &vk,
+                &comms,
+                &point,
+                values[..1].to_vec(),
+                &proof,
+                &mut (sponge.clone()),
+                Some(rng),
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn marlin_chunked_test() {
+        chunked_test::<Fr, Marlin>();
+    }
+
+    #[test]
+    fn sonic_chunked_test() {
+        chunked_test::<Fr, Sonic>();
+    }
+
+    #[test]
+    fn ipa_chunked_test() {
+        chunked_test::<JubjubFr, Ipa>();
+    }
+
+    #[test]
+    fn degree_bounds_are_rejected() {
+        let rng = &mut test_rng();
+        let pp = ChunkedPC::<Fr, DensePolynomial<Fr>, Marlin>::setup(16, None, rng).unwrap();
+        let (ck, _) =
+            ChunkedPC::<Fr, DensePolynomial<Fr>, Marlin>::trim(&pp, 7, 0, Some(&[4])).unwrap();
+        let polynomial = LabeledPolynomial::new(
+            "Test".to_string(),
+            DensePolynomial::rand(3, rng),
+            Some(4),
+            None,
+        );
+        assert!(matches!(
+            ChunkedPC::<Fr, DensePolynomial<Fr>, Marlin>::commit(&ck, [&polynomial], Some(rng)),
+            Err(Error::UnsupportedDegreeBound(4))
+        ));
+    }
+}
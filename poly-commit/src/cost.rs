@@ -0,0 +1,158 @@
+use crate::Error;
+
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::CanonicalSerialize;
+#[cfg(not(feature = "std"))]
+use ark_std::vec::Vec;
+
+/// Serialized length prefix of a `Vec`.
+pub(crate) const LEN_BYTES: usize = 8;
+/// Serialized tag of an `Option`.
+pub(crate) const OPTION_BYTES: usize = 1;
+
+/// The shape of a batch of openings: `num_polys` polynomials, each of which is
+/// opened at each of `num_points` points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BatchShape {
+    /// The number of committed polynomials.
+    pub num_polys: usize,
+    /// The number of points at which every polynomial is opened.
+    pub num_points: usize,
+}
+
+impl BatchShape {
+    /// Create a batch of `num_polys` polynomials opened at `num_points` points.
+    pub fn new(num_polys: usize, num_points: usize) -> Self {
+        Self {
+            num_polys,
+            num_points,
+        }
+    }
+}
+
+impl Default for BatchShape {
+    /// A single polynomial opened at a single point.
+    fn default() -> Self {
+        Self::new(1, 1)
+    }
+}
+
+/// The operations performed by the prover to commit to a batch of polynomials
+/// and open them with `batch_open`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProverCost {
+    /// The size of each multi-scalar multiplication.
+    pub msms: Vec<usize>,
+    /// The length of each codeword computed when encoding a row of a matrix:
+    /// an FFT for Reed-Solomon codes, or the linear-time encoding of Brakedown.
+    pub encodings: Vec<usize>,
+    /// The number of invocations of the hash functions of the scheme (column
+    /// hashes and Merkle tree hashes, or hashes of the public parameters).
+    pub hashes: usize,
+}
+
+/// The operations performed by the verifier to check a batch of openings
+/// with `batch_check`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerifierCost {
+    /// The number of pairings, which are computed as a single product of
+    /// pairings with one final exponentiation.
+    pub pairings: usize,
+    /// The size of each multi-scalar multiplication.
+    pub msms: Vec<usize>,
+    /// The length of each codeword computed when encoding a vector.
+    pub encodings: Vec<usize>,
+    /// The number of invocations of the hash functions of the scheme.
+    pub hashes: usize,
+}
+
+/// The predicted sizes and costs of a batch of openings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CostEstimate {
+    /// The compressed size of the commitment to a single polynomial.
+    pub commitment_bytes: usize,
+    /// The compressed size of the proof output by `batch_open`.
+    pub proof_bytes: usize,
+    /// The work of the prover.
+    pub prover: ProverCost,
+    /// The work of the verifier.
+    pub verifier: VerifierCost,
+}
+
+/// Public parameters or keys that can predict the proof size and the cost of
+/// the scheme they are used with, without running the prover.
+///
+/// The predictions assume that the polynomials are neither hiding nor degree
+/// bounded, and that every polynomial is opened at every point of the batch.
+/// Independent scalar multiplications whose results are added up are counted
+/// as a single multi-scalar multiplication, while field operations and sponge
+/// absorptions are not accounted for.
+pub trait CostModel {
+    /// Predict the costs of committing to `batch.num_polys` polynomials with
+    /// `poly_len` coefficients (or evaluations over the Boolean hypercube, for
+    /// multilinear polynomials) and opening them at `batch.num_points` points.
+    ///
+    /// Returns an error if the parameters do not support such polynomials.
+    fn estimate_cost(&self, poly_len: usize, batch: &BatchShape) -> Result<CostEstimate, Error>;
+}
+
+/// The compressed size of `value`.
+pub(crate) fn compressed_size<T: CanonicalSerialize>(value: &T) -> usize {
+    value.compressed_size()
+}
+
+/// The compressed size of a `Vec` of `len` elements of `elem_bytes` bytes.
+pub(crate) fn vec_bytes(len: usize, elem_bytes: usize) -> usize {
+    LEN_BYTES + len * elem_bytes
+}
+
+/// `len` copies of `value`.
+pub(crate) fn repeat(value: usize, len: usize) -> Vec<usize> {
+    vec![value; len]
+}
+
+/// The cost of a KZG-based scheme whose commitments have `commitment_bytes`
+/// bytes, and whose `batch_open` outputs one KZG proof per point, which are
+/// checked with `pairings` pairings in total.
+pub(crate) fn kzg_cost<E: Pairing>(
+    commitment_bytes: usize,
+    poly_len: usize,
+    batch: &BatchShape,
+    pairings: usize,
+) -> CostEstimate {
+    let proof_bytes = compressed_size(&E::G1Affine::zero()) + OPTION_BYTES;
+    CostEstimate {
+        commitment_bytes,
+        proof_bytes: vec_bytes(batch.num_points, proof_bytes),
+        prover: ProverCost {
+            // A commitment to each polynomial, and to the witness polynomial
+            // of the combination of the polynomials opened at each point.
+            msms: [
+                repeat(poly_len, batch.num_polys),
+                repeat(poly_len.saturating_sub(1).max(1), batch.num_points),
+            ]
+            .concat(),
+            encodings: Vec::new(),
+            hashes: 0,
+        },
+        verifier: VerifierCost {
+            pairings,
+            // Each commitment and proof is scaled by a batching challenge.
+            msms: vec![batch.num_points * (batch.num_polys + 3) + 2],
+            encodings: Vec::new(),
+            hashes: 0,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialized_sizes() {
+        assert_eq!(vec_bytes(3, 32), compressed_size(&vec![[0u8; 32]; 3]));
+        assert_eq!(OPTION_BYTES, compressed_size(&None::<u64>));
+        assert_eq!(OPTION_BYTES + 8, compressed_size(&Some(0u64)));
+    }
+}
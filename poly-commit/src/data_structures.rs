@@ -1,6 +1,7 @@
 use crate::Polynomial;
 use ark_crypto_primitives::sponge::Absorb;
-use ark_ff::{Field, PrimeField, ToConstraintField};
+use ark_ff::{FftField, Field, PrimeField, ToConstraintField};
+use ark_poly::{EvaluationDomain, Evaluations as UVEvaluations};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
     borrow::Borrow,
@@ -180,6 +181,52 @@ impl<'a, F: Field, P: Polynomial<F>> LabeledPolynomial<F, P> {
     }
 }
 
+/// The evaluations of a univariate polynomial over an evaluation domain, along
+/// with a label and the maximum number of queries that will be made to it.
+/// This is the counterpart of `LabeledPolynomial` for committer keys that
+/// carry their powers in the Lagrange basis of the domain.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LabeledEvaluations<F: FftField, D: EvaluationDomain<F>> {
+    label: PolynomialLabel,
+    evaluations: UVEvaluations<F, D>,
+    hiding_bound: Option<usize>,
+}
+
+impl<F: FftField, D: EvaluationDomain<F>> LabeledEvaluations<F, D> {
+    /// Construct new labeled evaluations.
+    pub fn new(
+        label: PolynomialLabel,
+        evaluations: UVEvaluations<F, D>,
+        hiding_bound: Option<usize>,
+    ) -> Self {
+        Self {
+            label,
+            evaluations,
+            hiding_bound,
+        }
+    }
+
+    /// Return the label for `self`.
+    pub fn label(&self) -> &String {
+        &self.label
+    }
+
+    /// Retrieve an immutable reference to the evaluations contained in `self`.
+    pub fn evaluations(&self) -> &UVEvaluations<F, D> {
+        &self.evaluations
+    }
+
+    /// Retrieve whether the polynomial in `self` should be hidden.
+    pub fn is_hiding(&self) -> bool {
+        self.hiding_bound.is_some()
+    }
+
+    /// Retrieve the hiding bound for the polynomial in `self`.
+    pub fn hiding_bound(&self) -> Option<usize> {
+        self.hiding_bound
+    }
+}
+
 /// A commitment along with information about its degree bound (if any).
 #[derive(Clone)]
 pub struct LabeledCommitment<C: PCCommitment> {
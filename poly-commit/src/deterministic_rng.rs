@@ -0,0 +1,247 @@
+//! Deterministic derivation of the randomness of hiding commitments and
+//! openings, in the spirit of [RFC 6979][rfc6979].
+//!
+//! A [`DeterministicRng`] is a random number generator whose stream is
+//! derived from a secret seed of the prover, the labeled polynomials that it
+//! hides and, for openings, the state of the transcript. It can be passed
+//! wherever the schemes of this crate take an `rng`, so that their hiding
+//! commitments and proofs are reproducible byte-for-byte, without relying on
+//! the randomness of the system.
+//!
+//! The key of the stream is the BLAKE2s-256 digest of
+//! `domain || field(seed) || field(kind) || field(polynomial)*` for commitments
+//! and of the same input followed by
+//! `field(commitment)* || field(query)* || field(transcript)` for openings,
+//! where `field(m) = le64(len(m)) || m`, `kind` is `commit`, `open` or
+//! `batch_open`, each `polynomial` is the compressed serialization of a
+//! labeled polynomial, along with its label and bounds, each `commitment` is
+//! that of a labeled commitment, along with its label, each `query` is that of
+//! the opening point or, for batch openings, of an element of the query set,
+//! along with its labels, and `transcript` is 32 bytes squeezed from a copy of
+//! the sponge. Block `i` of the stream is the BLAKE2s-256 digest of
+//! `key || le64(i)`.
+//!
+//! As in RFC 6979, the seed must be secret and uniformly random, and it must
+//! not be used for anything else. Distinct polynomials, commitments, points or
+//! transcripts give independent streams, so that masks are never reused: two
+//! openings of a polynomial with the same masks at distinct points would leak
+//! it.
+//!
+//! [rfc6979]: https://www.rfc-editor.org/rfc/rfc6979
+
+use crate::{Error, LabeledCommitment, LabeledPolynomial, PCCommitment, QuerySet};
+use ark_crypto_primitives::sponge::CryptographicSponge;
+use ark_ff::Field;
+use ark_poly::Polynomial;
+use ark_serialize::CanonicalSerialize;
+use ark_std::rand::{Error as RandError, RngCore};
+#[cfg(not(feature = "std"))]
+use ark_std::vec::Vec;
+use blake2::{Blake2s256, Digest};
+
+const DOMAIN: &[u8] = b"ark-poly-commit/deterministic-rng/v1";
+
+/// A random number generator whose stream is derived from a secret seed, the
+/// hidden polynomials and, for openings, their commitments, the query and the
+/// transcript, as in the
+/// [module documentation](self).
+#[derive(Clone)]
+pub struct DeterministicRng {
+    key: [u8; 32],
+    counter: u64,
+    block: [u8; 32],
+    position: usize,
+}
+
+impl DeterministicRng {
+    /// Outputs the generator of the randomness used to commit to
+    /// `labeled_polynomials` with the secret `seed`.
+    pub fn for_commit<'a, F, P>(
+        seed: &[u8; 32],
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
+    ) -> Result<Self, Error>
+    where
+        F: Field,
+        P: 'a + Polynomial<F>,
+    {
+        let hasher = Self::hash_polynomials(seed, b"commit", labeled_polynomials)?;
+        Ok(Self::from_key(hasher.finalize().into()))
+    }
+
+    /// Outputs the generator of the randomness used to open
+    /// `labeled_polynomials`, committed to in `commitments`, at `point` with
+    /// the secret `seed`, in the transcript of `sponge`, which is left
+    /// untouched.
+    pub fn for_open<'a, F, P, C, S>(
+        seed: &[u8; 32],
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<C>>,
+        point: &P::Point,
+        sponge: &S,
+    ) -> Result<Self, Error>
+    where
+        F: Field,
+        P: 'a + Polynomial<F>,
+        P::Point: CanonicalSerialize,
+        C: 'a + PCCommitment,
+        S: CryptographicSponge,
+    {
+        let mut hasher = Self::hash_polynomials(seed, b"open", labeled_polynomials)?;
+        Self::hash_commitments(&mut hasher, commitments)?;
+        update_field(&mut hasher, &serialize(point)?);
+        Ok(Self::finalize(hasher, sponge))
+    }
+
+    /// Outputs the generator of the randomness used to open
+    /// `labeled_polynomials`, committed to in `commitments`, at the queries of
+    /// `query_set` with the secret `seed`, in the transcript of `sponge`,
+    /// which is left untouched.
+    pub fn for_batch_open<'a, F, P, C, S>(
+        seed: &[u8; 32],
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<C>>,
+        query_set: &QuerySet<P::Point>,
+        sponge: &S,
+    ) -> Result<Self, Error>
+    where
+        F: Field,
+        P: 'a + Polynomial<F>,
+        P::Point: CanonicalSerialize,
+        C: 'a + PCCommitment,
+        S: CryptographicSponge,
+    {
+        let mut hasher = Self::hash_polynomials(seed, b"batch_open", labeled_polynomials)?;
+        Self::hash_commitments(&mut hasher, commitments)?;
+        for (label, (point_label, point)) in query_set {
+            let mut bytes = Vec::new();
+            extend_with_label(&mut bytes, label);
+            extend_with_label(&mut bytes, point_label);
+            bytes.extend_from_slice(&serialize(point)?);
+            update_field(&mut hasher, &bytes);
+        }
+        Ok(Self::finalize(hasher, sponge))
+    }
+
+    fn hash_polynomials<'a, F, P>(
+        seed: &[u8; 32],
+        kind: &[u8],
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
+    ) -> Result<Blake2s256, Error>
+    where
+        F: Field,
+        P: 'a + Polynomial<F>,
+    {
+        let mut hasher = Blake2s256::new();
+        hasher.update(DOMAIN);
+        update_field(&mut hasher, seed);
+        update_field(&mut hasher, kind);
+        for labeled_polynomial in labeled_polynomials {
+            let mut bytes = Vec::new();
+            extend_with_label(&mut bytes, labeled_polynomial.label());
+            for bound in [
+                labeled_polynomial.degree_bound(),
+                labeled_polynomial.hiding_bound(),
+            ] {
+                // `u64::MAX` stands for the absence of a bound.
+                let bound = bound.map_or(u64::MAX, |bound| bound as u64);
+                bytes.extend_from_slice(&bound.to_le_bytes());
+            }
+            bytes.extend_from_slice(&serialize(labeled_polynomial.polynomial())?);
+            update_field(&mut hasher, &bytes);
+        }
+        Ok(hasher)
+    }
+
+    fn hash_commitments<'a, C: 'a + PCCommitment>(
+        hasher: &mut Blake2s256,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<C>>,
+    ) -> Result<(), Error> {
+        for labeled_commitment in commitments {
+            let mut bytes = Vec::new();
+            extend_with_label(&mut bytes, labeled_commitment.label());
+            bytes.extend_from_slice(&serialize(labeled_commitment.commitment())?);
+            update_field(hasher, &bytes);
+        }
+        Ok(())
+    }
+
+    fn finalize<S: CryptographicSponge>(mut hasher: Blake2s256, sponge: &S) -> Self {
+        let transcript = sponge.clone().squeeze_bytes(32);
+        update_field(&mut hasher, &transcript);
+        Self::from_key(hasher.finalize().into())
+    }
+
+    fn from_key(key: [u8; 32]) -> Self {
+        let mut rng = Self {
+            key,
+            counter: 0,
+            block: [0u8; 32],
+            position: 0,
+        };
+        rng.refill();
+        rng
+    }
+
+    fn refill(&mut self) {
+        let mut hasher = Blake2s256::new();
+        hasher.update(self.key);
+        hasher.update(self.counter.to_le_bytes());
+        self.block = hasher.finalize().into();
+        self.counter += 1;
+        self.position = 0;
+    }
+}
+
+fn update_field(hasher: &mut Blake2s256, field: &[u8]) {
+    hasher.update((field.len() as u64).to_le_bytes());
+    hasher.update(field);
+}
+
+fn extend_with_label(bytes: &mut Vec<u8>, label: &str) {
+    bytes.extend_from_slice(&(label.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(label.as_bytes());
+}
+
+fn serialize<T: CanonicalSerialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    value
+        .serialize_compressed(&mut bytes)
+        .map_err(|_| Error::HashingError)?;
+    Ok(bytes)
+}
+
+impl RngCore for DeterministicRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut filled = 0;
+        while filled < dest.len() {
+            if self.position == self.block.len() {
+                self.refill();
+            }
+            let len = (dest.len() - filled).min(self.block.len() - self.position);
+            dest[filled..filled + len]
+                .copy_from_slice(&self.block[self.position..self.position + len]);
+            filled += len;
+            self.position += len;
+        }
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RandError> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
@@ -0,0 +1,323 @@
+use super::*;
+use ark_ff::FftField;
+use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial};
+use ark_std::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use ark_std::vec;
+
+/// The number of field elements in a blob extended with Reed-Solomon codes.
+pub const FIELD_ELEMENTS_PER_EXT_BLOB: usize = 2 * FIELD_ELEMENTS_PER_BLOB;
+/// The number of field elements in a cell.
+pub const FIELD_ELEMENTS_PER_CELL: usize = 64;
+/// The number of bytes of a cell.
+pub const BYTES_PER_CELL: usize = FIELD_ELEMENTS_PER_CELL * BYTES_PER_FIELD_ELEMENT;
+/// The number of cells in an extended blob.
+pub const CELLS_PER_EXT_BLOB: usize = FIELD_ELEMENTS_PER_EXT_BLOB / FIELD_ELEMENTS_PER_CELL;
+/// The domain separator of the random challenge of batch verification of cells.
+pub const RANDOM_CHALLENGE_KZG_CELL_BATCH_DOMAIN: &[u8; 16] = b"RCKZGCBATCH__V1_";
+
+/// A cell: the evaluations of the polynomial of a blob at a coset of the
+/// roots of unity of order `FIELD_ELEMENTS_PER_CELL`, in bit-reversed order.
+pub type Cell = [u8; BYTES_PER_CELL];
+
+type UniPoly = DensePolynomial<Fr>;
+
+impl TrustedSetup {
+    /// Outputs the cells of the extension of `blob`.
+    pub fn compute_cells(&self, blob: &Blob) -> Result<Vec<Cell>, Error> {
+        let coeffs = polynomial_eval_to_coeff(&blob_to_polynomial(blob)?);
+        Ok(compute_cells_polynomialcoeff(&coeffs))
+    }
+
+    /// Outputs the cells of the extension of `blob`, along with a proof for
+    /// each cell.
+    pub fn compute_cells_and_kzg_proofs(
+        &self,
+        blob: &Blob,
+    ) -> Result<(Vec<Cell>, Vec<KZGProof>), Error> {
+        let coeffs = polynomial_eval_to_coeff(&blob_to_polynomial(blob)?);
+        Ok(self.compute_cells_and_kzg_proofs_polynomialcoeff(&coeffs))
+    }
+
+    /// Verifies that `proofs[k]` shows that `cells[k]` is the cell of index
+    /// `cell_indices[k]` of the blob committed to in `commitments[k]`, with a
+    /// random linear combination of the pairing equations.
+    pub fn verify_cell_kzg_proof_batch(
+        &self,
+        commitments: &[Bytes48],
+        cell_indices: &[u64],
+        cells: &[Cell],
+        proofs: &[Bytes48],
+    ) -> Result<bool, Error> {
+        let n = cells.len();
+        if commitments.len() != n || cell_indices.len() != n || proofs.len() != n {
+            return Err(Error::IncorrectInputLength(format!(
+                "{} commitments, {} cell indices, {} cells and {} proofs were provided",
+                commitments.len(),
+                cell_indices.len(),
+                n,
+                proofs.len()
+            )));
+        }
+        let cell_indices = check_cell_indices(cell_indices)?;
+        let check_time = start_timer!(|| format!("Checking {} cell proofs", n));
+
+        // Deduplicate the commitments, in order of first appearance.
+        let mut deduplicated: Vec<Bytes48> = Vec::new();
+        let mut positions = BTreeMap::new();
+        let commitment_indices: Vec<usize> = commitments
+            .iter()
+            .map(|c| {
+                *positions.entry(*c).or_insert_with(|| {
+                    deduplicated.push(*c);
+                    deduplicated.len() - 1
+                })
+            })
+            .collect();
+        let commitment_points = deduplicated
+            .iter()
+            .map(bytes_to_kzg_g1)
+            .collect::<Result<Vec<_>, _>>()?;
+        let cosets_evals = cells
+            .iter()
+            .map(cell_to_coset_evals)
+            .collect::<Result<Vec<_>, _>>()?;
+        let proof_points = proofs
+            .iter()
+            .map(bytes_to_kzg_g1)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The challenge binds all the inputs.
+        let mut data = RANDOM_CHALLENGE_KZG_CELL_BATCH_DOMAIN.to_vec();
+        data.extend_from_slice(&(FIELD_ELEMENTS_PER_BLOB as u64).to_be_bytes());
+        data.extend_from_slice(&(FIELD_ELEMENTS_PER_CELL as u64).to_be_bytes());
+        data.extend_from_slice(&(deduplicated.len() as u64).to_be_bytes());
+        data.extend_from_slice(&(n as u64).to_be_bytes());
+        for commitment in &deduplicated {
+            data.extend_from_slice(commitment);
+        }
+        for k in 0..n {
+            data.extend_from_slice(&(commitment_indices[k] as u64).to_be_bytes());
+            data.extend_from_slice(&(cell_indices[k] as u64).to_be_bytes());
+            data.extend_from_slice(&cells[k]);
+            data.extend_from_slice(&proofs[k]);
+        }
+        let r_powers = compute_powers(hash_to_bls_field(&data), n);
+
+        // Checks `e(\sum r^k proof_k, [\tau^n]) = e(RLC - RLI + RLP, [1])` where
+        // `RLC = \sum r^k C_k`, `RLI = [\sum r^k I_k(\tau)]` for the interpolant
+        // `I_k` of the `k`-th cell and `RLP = \sum r^k h_k^n proof_k` for the
+        // shift `h_k` of the coset of the `k`-th cell.
+        let mut weights = vec![Fr::zero(); deduplicated.len()];
+        let mut interpolants = UniPoly::zero();
+        let mut weighted_r_powers = Vec::with_capacity(n);
+        for k in 0..n {
+            weights[commitment_indices[k]] += r_powers[k];
+            let shift = coset_shift_for_cell(cell_indices[k]);
+            let interpolant = interpolate_coset(shift, &cosets_evals[k]);
+            interpolants += (r_powers[k], &interpolant);
+            weighted_r_powers.push(r_powers[k] * shift.pow([FIELD_ELEMENTS_PER_CELL as u64]));
+        }
+        let ll = g1_lincomb(&proof_points, &r_powers);
+        let rl = g1_lincomb(&commitment_points, &weights)
+            - g1_lincomb(&self.g1_monomial, &interpolants.coeffs)
+            + g1_lincomb(&proof_points, &weighted_r_powers);
+        let result = pairing_check(
+            [ll, rl],
+            [
+                self.g2_monomial[FIELD_ELEMENTS_PER_CELL].into_group(),
+                -self.g2_monomial[0].into_group(),
+            ],
+        );
+        end_timer!(check_time);
+        Ok(result)
+    }
+
+    /// Recovers all the cells of an extended blob, and their proofs, from at
+    /// least half of them, given with their indices in ascending order.
+    pub fn recover_cells_and_kzg_proofs(
+        &self,
+        cell_indices: &[u64],
+        cells: &[Cell],
+    ) -> Result<(Vec<Cell>, Vec<KZGProof>), Error> {
+        if cell_indices.len() != cells.len() {
+            return Err(Error::IncorrectInputLength(format!(
+                "{} cell indices and {} cells were provided",
+                cell_indices.len(),
+                cells.len()
+            )));
+        }
+        if cells.len() < CELLS_PER_EXT_BLOB / 2 || cells.len() > CELLS_PER_EXT_BLOB {
+            return Err(Error::IncorrectInputLength(format!(
+                "{} cells were provided, but between {} and {} are needed",
+                cells.len(),
+                CELLS_PER_EXT_BLOB / 2,
+                CELLS_PER_EXT_BLOB
+            )));
+        }
+        let cell_indices = check_cell_indices(cell_indices)?;
+        if let Some(w) = cell_indices.windows(2).find(|w| w[0] >= w[1]) {
+            return Err(Error::InvalidIndex {
+                index: w[1],
+                len: CELLS_PER_EXT_BLOB,
+            });
+        }
+        let cosets_evals = cells
+            .iter()
+            .map(cell_to_coset_evals)
+            .collect::<Result<Vec<_>, _>>()?;
+        let coeffs = recover_polynomialcoeff(&cell_indices, &cosets_evals);
+        Ok(self.compute_cells_and_kzg_proofs_polynomialcoeff(&coeffs))
+    }
+
+    fn compute_cells_and_kzg_proofs_polynomialcoeff(
+        &self,
+        coeffs: &[Fr],
+    ) -> (Vec<Cell>, Vec<KZGProof>) {
+        let proofs_time = start_timer!(|| "Computing the proofs of all cells");
+        let cells = compute_cells_polynomialcoeff(coeffs);
+        let proofs = ark_std::cfg_into_iter!(0..CELLS_PER_EXT_BLOB)
+            .map(|i| {
+                // The coset of the cell is the set of roots of `X^n - h^n`.
+                let shift_pow = coset_shift_for_cell(i).pow([FIELD_ELEMENTS_PER_CELL as u64]);
+                let mut remainder = coeffs.to_vec();
+                let mut quotient =
+                    vec![Fr::zero(); coeffs.len().saturating_sub(FIELD_ELEMENTS_PER_CELL)];
+                for j in (FIELD_ELEMENTS_PER_CELL..coeffs.len()).rev() {
+                    quotient[j - FIELD_ELEMENTS_PER_CELL] = remainder[j];
+                    let carry = remainder[j] * shift_pow;
+                    remainder[j - FIELD_ELEMENTS_PER_CELL] += carry;
+                }
+                g1_to_bytes(&g1_lincomb(&self.g1_monomial, &quotient).into_affine())
+            })
+            .collect();
+        end_timer!(proofs_time);
+        (cells, proofs)
+    }
+}
+
+/// Checks that the cell indices are in range and converts them.
+fn check_cell_indices(cell_indices: &[u64]) -> Result<Vec<usize>, Error> {
+    cell_indices
+        .iter()
+        .map(|&i| {
+            if i < CELLS_PER_EXT_BLOB as u64 {
+                Ok(i as usize)
+            } else {
+                Err(Error::InvalidIndex {
+                    index: i as usize,
+                    len: CELLS_PER_EXT_BLOB,
+                })
+            }
+        })
+        .collect()
+}
+
+/// Decodes the field elements of a cell.
+pub(crate) fn cell_to_coset_evals(cell: &Cell) -> Result<Vec<Fr>, Error> {
+    cell.chunks(BYTES_PER_FIELD_ELEMENT)
+        .map(|chunk| bytes_to_bls_field(chunk.try_into().unwrap()))
+        .collect()
+}
+
+/// Encodes the field elements of a cell.
+fn coset_evals_to_cell(evals: &[Fr]) -> Cell {
+    let mut cell = [0u8; BYTES_PER_CELL];
+    for (chunk, eval) in cell.chunks_mut(BYTES_PER_FIELD_ELEMENT).zip(evals) {
+        chunk.copy_from_slice(&bls_field_to_bytes(eval));
+    }
+    cell
+}
+
+/// The coefficients of the polynomial of a blob, given by its evaluations at
+/// the roots of unity in bit-reversed order.
+pub(crate) fn polynomial_eval_to_coeff(polynomial: &[Fr]) -> Vec<Fr> {
+    let domain = Radix2EvaluationDomain::<Fr>::new(FIELD_ELEMENTS_PER_BLOB).unwrap();
+    domain.ifft(&bit_reversal_permutation(polynomial))
+}
+
+fn extended_domain() -> Radix2EvaluationDomain<Fr> {
+    Radix2EvaluationDomain::new(FIELD_ELEMENTS_PER_EXT_BLOB).unwrap()
+}
+
+/// The cell of index `i` holds the evaluations at the elements `64 i` to
+/// `64 i + 63` of the roots of unity of order `FIELD_ELEMENTS_PER_EXT_BLOB` in
+/// bit-reversed order, which are the coset `h_i H` of the subgroup `H` of order
+/// `FIELD_ELEMENTS_PER_CELL` (in bit-reversed order) for the shift `h_i` below.
+fn coset_shift_for_cell(i: usize) -> Fr {
+    extended_domain().element(reverse_bits(i, CELLS_PER_EXT_BLOB))
+}
+
+/// Outputs the cells of the polynomial with coefficients `coeffs`.
+fn compute_cells_polynomialcoeff(coeffs: &[Fr]) -> Vec<Cell> {
+    let extended = bit_reversal_permutation(&extended_domain().fft(coeffs));
+    extended
+        .chunks(FIELD_ELEMENTS_PER_CELL)
+        .map(coset_evals_to_cell)
+        .collect()
+}
+
+/// Outputs the polynomial of degree less than `FIELD_ELEMENTS_PER_CELL` which
+/// takes the values `evals` at the coset of shift `shift`, in bit-reversed order.
+fn interpolate_coset(shift: Fr, evals: &[Fr]) -> UniPoly {
+    let coset = Radix2EvaluationDomain::<Fr>::new(FIELD_ELEMENTS_PER_CELL)
+        .unwrap()
+        .get_coset(shift)
+        .unwrap();
+    UniPoly::from_coefficients_vec(coset.ifft(&bit_reversal_permutation(evals)))
+}
+
+/// Recovers the coefficients of a polynomial of degree less than
+/// `FIELD_ELEMENTS_PER_BLOB` from the cells at `cell_indices`. The extended
+/// evaluations `E`, with zeros at the missing cells, are multiplied by the
+/// polynomial `Z` vanishing at the missing cells, so that `E Z = P Z` on the
+/// extended domain. `P` is then `(E Z) / Z` on a coset, where `Z` has no roots.
+fn recover_polynomialcoeff(cell_indices: &[usize], cosets_evals: &[Vec<Fr>]) -> Vec<Fr> {
+    let recover_time = start_timer!(|| "Recovering the polynomial of the cells");
+    let domain = extended_domain();
+    let mut extended_rbo = vec![Fr::zero(); FIELD_ELEMENTS_PER_EXT_BLOB];
+    for (&i, evals) in cell_indices.iter().zip(cosets_evals) {
+        extended_rbo[i * FIELD_ELEMENTS_PER_CELL..(i + 1) * FIELD_ELEMENTS_PER_CELL]
+            .copy_from_slice(evals);
+    }
+    let extended = bit_reversal_permutation(&extended_rbo);
+
+    // The `n`-th powers of the elements of the coset of the cell `i` are
+    // `h_i^n`, a root of unity of order `CELLS_PER_EXT_BLOB`, so `Z(X)` is
+    // `Z'(X^n)` for the polynomial `Z'` vanishing at the `h_i^n` of the missing
+    // cells.
+    let missing_roots: Vec<Fr> = (0..CELLS_PER_EXT_BLOB)
+        .filter(|i| !cell_indices.contains(i))
+        .map(|i| coset_shift_for_cell(i).pow([FIELD_ELEMENTS_PER_CELL as u64]))
+        .collect();
+    let short_zero_poly = missing_roots.iter().fold(
+        UniPoly::from_coefficients_vec(vec![Fr::one()]),
+        |acc, root| &acc * &UniPoly::from_coefficients_vec(vec![-*root, Fr::one()]),
+    );
+    let mut zero_poly = vec![Fr::zero(); FIELD_ELEMENTS_PER_EXT_BLOB];
+    for (i, c) in short_zero_poly.coeffs.iter().enumerate() {
+        zero_poly[i * FIELD_ELEMENTS_PER_CELL] = *c;
+    }
+
+    let zero_poly_evals = domain.fft(&zero_poly);
+    let extended_times_zero: Vec<Fr> = extended
+        .iter()
+        .zip(&zero_poly_evals)
+        .map(|(e, z)| *e * z)
+        .collect();
+    let extended_times_zero_coeffs = domain.ifft(&extended_times_zero);
+
+    let coset = domain.get_coset(Fr::GENERATOR).unwrap();
+    let mut zero_poly_over_coset = coset.fft(&zero_poly);
+    batch_inversion(&mut zero_poly_over_coset);
+    let quotient_over_coset: Vec<Fr> = coset
+        .fft(&extended_times_zero_coeffs)
+        .iter()
+        .zip(&zero_poly_over_coset)
+        .map(|(a, b)| *a * b)
+        .collect();
+    let mut coeffs = coset.ifft(&quotient_over_coset);
+    coeffs.truncate(FIELD_ELEMENTS_PER_BLOB);
+    end_timer!(recover_time);
+    coeffs
+}
@@ -0,0 +1,522 @@
+use crate::Error;
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{batch_inversion, BigInteger, Field, One, PrimeField, Zero};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{convert::TryInto, format, str::FromStr};
+#[cfg(not(feature = "std"))]
+use ark_std::{string::ToString, vec::Vec};
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+mod cells;
+pub use cells::*;
+
+#[cfg(test)]
+mod tests;
+
+/// The number of bytes of the encoding of a field element.
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+/// The number of field elements in a blob.
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+/// The number of bytes of a blob.
+pub const BYTES_PER_BLOB: usize = BYTES_PER_FIELD_ELEMENT * FIELD_ELEMENTS_PER_BLOB;
+/// The number of bytes of the compressed encoding of a commitment.
+pub const BYTES_PER_COMMITMENT: usize = 48;
+/// The number of bytes of the compressed encoding of a proof.
+pub const BYTES_PER_PROOF: usize = 48;
+/// The number of bytes of the compressed encoding of a point of G2.
+pub const BYTES_PER_G2_POINT: usize = 96;
+
+/// The domain separator of the evaluation challenge of a blob.
+pub const FIAT_SHAMIR_PROTOCOL_DOMAIN: &[u8; 16] = b"FSBLOBVERIFY_V1_";
+/// The domain separator of the random challenge of batch verification.
+pub const RANDOM_CHALLENGE_KZG_BATCH_DOMAIN: &[u8; 16] = b"RCKZGBATCH___V1_";
+
+/// The compressed encoding of the point at infinity of G1.
+pub const G1_POINT_AT_INFINITY: Bytes48 = {
+    let mut bytes = [0u8; 48];
+    bytes[0] = 0xc0;
+    bytes
+};
+
+/// 32 bytes, the big-endian encoding of a field element.
+pub type Bytes32 = [u8; 32];
+/// 48 bytes, the compressed encoding of a point of G1.
+pub type Bytes48 = [u8; 48];
+/// The compressed encoding of a commitment to a blob.
+pub type KZGCommitment = Bytes48;
+/// The compressed encoding of an evaluation proof.
+pub type KZGProof = Bytes48;
+/// A blob: the evaluations of a polynomial of degree less than
+/// `FIELD_ELEMENTS_PER_BLOB` at the roots of unity, in bit-reversed order.
+pub type Blob = [u8; BYTES_PER_BLOB];
+
+/// The trusted setup of the [Ethereum KZG ceremony][ceremony], against which
+/// blobs are committed to and opened as specified by [EIP-4844][eip4844] and
+/// [EIP-7594][eip7594]. The functions of the [consensus specs][specs] are
+/// implemented as methods which take and return the same byte encodings:
+/// big-endian field elements and compressed points in the ZCash format.
+///
+/// [ceremony]: https://github.com/ethereum/kzg-ceremony
+/// [eip4844]: https://eips.ethereum.org/EIPS/eip-4844
+/// [eip7594]: https://eips.ethereum.org/EIPS/eip-7594
+/// [specs]: https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/polynomial-commitments.md
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrustedSetup {
+    /// `[L_i(\tau)]_1` for the Lagrange polynomials `L_i` of the roots of unity
+    /// of order `FIELD_ELEMENTS_PER_BLOB`, in bit-reversed order.
+    g1_lagrange_brp: Vec<G1Affine>,
+    /// `[\tau^i]_1` for `i` in `0..FIELD_ELEMENTS_PER_BLOB`.
+    g1_monomial: Vec<G1Affine>,
+    /// `[\tau^i]_2` for `i` in `0..=FIELD_ELEMENTS_PER_CELL`.
+    g2_monomial: Vec<G2Affine>,
+    /// The roots of unity of order `FIELD_ELEMENTS_PER_BLOB`, in bit-reversed order.
+    roots_of_unity_brp: Vec<Fr>,
+}
+
+impl TrustedSetup {
+    /// The number of points of G2 in the trusted setup.
+    pub const NUM_G2_POINTS: usize = FIELD_ELEMENTS_PER_CELL + 1;
+
+    /// Constructs the trusted setup from the points `[\tau^i]_1` and
+    /// `[L_i(\tau)]_1` (with the roots of unity in their natural order) for
+    /// `i < FIELD_ELEMENTS_PER_BLOB`, and the points `[\tau^i]_2` for
+    /// `i < NUM_G2_POINTS`.
+    pub fn new(
+        g1_monomial: Vec<G1Affine>,
+        g1_lagrange: Vec<G1Affine>,
+        g2_monomial: Vec<G2Affine>,
+    ) -> Result<Self, Error> {
+        if g1_monomial.len() != FIELD_ELEMENTS_PER_BLOB
+            || g1_lagrange.len() != FIELD_ELEMENTS_PER_BLOB
+            || g2_monomial.len() != Self::NUM_G2_POINTS
+        {
+            return Err(Error::IncorrectInputLength(format!(
+                "expected {} points of G1 in each basis and {} points of G2, got {}, {} and {}",
+                FIELD_ELEMENTS_PER_BLOB,
+                Self::NUM_G2_POINTS,
+                g1_monomial.len(),
+                g1_lagrange.len(),
+                g2_monomial.len()
+            )));
+        }
+        Ok(Self {
+            g1_lagrange_brp: bit_reversal_permutation(&g1_lagrange),
+            g1_monomial,
+            g2_monomial,
+            roots_of_unity_brp: bit_reversal_permutation(&roots_of_unity(FIELD_ELEMENTS_PER_BLOB)),
+        })
+    }
+
+    /// Parses a trusted setup in the text format of the reference
+    /// implementation, `trusted_setup.txt`: the number of points of G1 and
+    /// the number of points of G2, followed by the hex encodings of the
+    /// points `[L_i(\tau)]_1`, `[\tau^i]_2` and `[\tau^i]_1`. The last ones are
+    /// missing from older files, in which case they are computed from the
+    /// Lagrange basis.
+    pub fn from_text(text: &str) -> Result<Self, Error> {
+        let setup_time = start_timer!(|| "Parsing the trusted setup");
+        let mut tokens = text.split_whitespace();
+        let mut count = |expected: usize| -> Result<(), Error> {
+            let n = tokens.next().and_then(|t| usize::from_str(t).ok()).ok_or(
+                Error::InvalidEncoding(
+                    "the trusted setup does not start with the numbers of points".to_string(),
+                ),
+            )?;
+            if n != expected {
+                return Err(Error::IncorrectInputLength(format!(
+                    "the trusted setup has {} points instead of {}",
+                    n, expected
+                )));
+            }
+            Ok(())
+        };
+        count(FIELD_ELEMENTS_PER_BLOB)?;
+        count(Self::NUM_G2_POINTS)?;
+
+        let tokens: Vec<&str> = tokens.collect();
+        let (lagrange, rest) = tokens.split_at(FIELD_ELEMENTS_PER_BLOB.min(tokens.len()));
+        let (g2, monomial) = rest.split_at(Self::NUM_G2_POINTS.min(rest.len()));
+        let g1_lagrange = parse_points::<G1Affine>(lagrange, BYTES_PER_COMMITMENT)?;
+        let g2_monomial = parse_points::<G2Affine>(g2, BYTES_PER_G2_POINT)?;
+        let g1_monomial = if monomial.is_empty() {
+            // `[\tau^j]_1 = \sum_i \omega^{ij} [L_i(\tau)]_1`
+            let domain = Radix2EvaluationDomain::<Fr>::new(FIELD_ELEMENTS_PER_BLOB).unwrap();
+            let lagrange: Vec<G1Projective> = g1_lagrange.iter().map(|p| p.into_group()).collect();
+            G1Projective::normalize_batch(&domain.fft(&lagrange))
+        } else {
+            parse_points::<G1Affine>(monomial, BYTES_PER_COMMITMENT)?
+        };
+        end_timer!(setup_time);
+        Self::new(g1_monomial, g1_lagrange, g2_monomial)
+    }
+
+    /// Loads a trusted setup in the text format of `from_text` from the file
+    /// at `path`.
+    #[cfg(feature = "std")]
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            Error::InvalidParameters(format!("could not read the trusted setup: {}", e))
+        })?;
+        Self::from_text(&text)
+    }
+
+    /// Outputs the commitment to the polynomial whose evaluations are `blob`.
+    pub fn blob_to_kzg_commitment(&self, blob: &Blob) -> Result<KZGCommitment, Error> {
+        let polynomial = blob_to_polynomial(blob)?;
+        Ok(g1_to_bytes(
+            &g1_lincomb(&self.g1_lagrange_brp, &polynomial).into_affine(),
+        ))
+    }
+
+    /// Outputs a proof of the evaluation of the polynomial whose evaluations
+    /// are `blob` at the point `z`, along with the evaluation.
+    pub fn compute_kzg_proof(
+        &self,
+        blob: &Blob,
+        z: &Bytes32,
+    ) -> Result<(KZGProof, Bytes32), Error> {
+        let polynomial = blob_to_polynomial(blob)?;
+        let z = bytes_to_bls_field(z)?;
+        let (proof, y) = self.compute_kzg_proof_impl(&polynomial, z);
+        Ok((g1_to_bytes(&proof), bls_field_to_bytes(&y)))
+    }
+
+    /// Outputs a proof of the evaluation of the polynomial whose evaluations
+    /// are `blob` at the Fiat-Shamir challenge derived from `blob` and
+    /// `commitment`.
+    pub fn compute_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment: &Bytes48,
+    ) -> Result<KZGProof, Error> {
+        // Only a valid commitment is accepted, although it is not used.
+        let _ = bytes_to_kzg_g1(commitment)?;
+        let polynomial = blob_to_polynomial(blob)?;
+        let challenge = compute_challenge(blob, commitment);
+        let (proof, _) = self.compute_kzg_proof_impl(&polynomial, challenge);
+        Ok(g1_to_bytes(&proof))
+    }
+
+    /// Verifies that `proof` shows that the polynomial committed to in
+    /// `commitment` evaluates to `y` at `z`.
+    pub fn verify_kzg_proof(
+        &self,
+        commitment: &Bytes48,
+        z: &Bytes32,
+        y: &Bytes32,
+        proof: &Bytes48,
+    ) -> Result<bool, Error> {
+        let commitment = bytes_to_kzg_g1(commitment)?;
+        let z = bytes_to_bls_field(z)?;
+        let y = bytes_to_bls_field(y)?;
+        let proof = bytes_to_kzg_g1(proof)?;
+        Ok(self.verify_kzg_proof_impl(commitment, z, y, proof))
+    }
+
+    /// Verifies a proof output by `compute_blob_kzg_proof`.
+    pub fn verify_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment: &Bytes48,
+        proof: &Bytes48,
+    ) -> Result<bool, Error> {
+        let commitment_point = bytes_to_kzg_g1(commitment)?;
+        let polynomial = blob_to_polynomial(blob)?;
+        let challenge = compute_challenge(blob, commitment);
+        let y = self.evaluate_polynomial_in_evaluation_form(&polynomial, challenge);
+        let proof = bytes_to_kzg_g1(proof)?;
+        Ok(self.verify_kzg_proof_impl(commitment_point, challenge, y, proof))
+    }
+
+    /// Verifies proofs output by `compute_blob_kzg_proof` for several blobs
+    /// at once, with a random linear combination of the pairing equations.
+    pub fn verify_blob_kzg_proof_batch(
+        &self,
+        blobs: &[Blob],
+        commitments: &[Bytes48],
+        proofs: &[Bytes48],
+    ) -> Result<bool, Error> {
+        if blobs.len() != commitments.len() || blobs.len() != proofs.len() {
+            return Err(Error::IncorrectInputLength(format!(
+                "{} blobs, {} commitments and {} proofs were provided",
+                blobs.len(),
+                commitments.len(),
+                proofs.len()
+            )));
+        }
+        let check_time = start_timer!(|| format!("Checking {} blob proofs", blobs.len()));
+        let mut points = Vec::with_capacity(blobs.len());
+        let mut ys = Vec::with_capacity(blobs.len());
+        for (blob, commitment) in blobs.iter().zip(commitments) {
+            let _ = bytes_to_kzg_g1(commitment)?;
+            let polynomial = blob_to_polynomial(blob)?;
+            let challenge = compute_challenge(blob, commitment);
+            ys.push(self.evaluate_polynomial_in_evaluation_form(&polynomial, challenge));
+            points.push(challenge);
+        }
+        let result = self.verify_kzg_proof_batch(commitments, &points, &ys, proofs)?;
+        end_timer!(check_time);
+        Ok(result)
+    }
+
+    /// Checks `e(\sum r^i proof_i, [\tau]) = e(\sum r^i (C_i - [y_i] + z_i proof_i), [1])`
+    /// for a Fiat-Shamir challenge `r`.
+    fn verify_kzg_proof_batch(
+        &self,
+        commitments: &[Bytes48],
+        zs: &[Fr],
+        ys: &[Fr],
+        proofs: &[Bytes48],
+    ) -> Result<bool, Error> {
+        let mut data = RANDOM_CHALLENGE_KZG_BATCH_DOMAIN.to_vec();
+        data.extend_from_slice(&(FIELD_ELEMENTS_PER_BLOB as u64).to_be_bytes());
+        data.extend_from_slice(&(commitments.len() as u64).to_be_bytes());
+        for i in 0..commitments.len() {
+            data.extend_from_slice(&commitments[i]);
+            data.extend_from_slice(&bls_field_to_bytes(&zs[i]));
+            data.extend_from_slice(&bls_field_to_bytes(&ys[i]));
+            data.extend_from_slice(&proofs[i]);
+        }
+        let r_powers = compute_powers(hash_to_bls_field(&data), commitments.len());
+
+        let commitments = commitments
+            .iter()
+            .map(bytes_to_kzg_g1)
+            .collect::<Result<Vec<_>, _>>()?;
+        let proofs = proofs
+            .iter()
+            .map(bytes_to_kzg_g1)
+            .collect::<Result<Vec<_>, _>>()?;
+        let proof_lincomb = g1_lincomb(&proofs, &r_powers);
+        let z_r_powers: Vec<Fr> = zs.iter().zip(&r_powers).map(|(z, r)| *z * r).collect();
+        let y_lincomb: Fr = ys.iter().zip(&r_powers).map(|(y, r)| *y * r).sum();
+        let rhs = g1_lincomb(&commitments, &r_powers) + g1_lincomb(&proofs, &z_r_powers)
+            - G1Affine::generator() * y_lincomb;
+        Ok(pairing_check(
+            [proof_lincomb, rhs],
+            [
+                -self.g2_monomial[1].into_group(),
+                G2Affine::generator().into_group(),
+            ],
+        ))
+    }
+
+    /// Checks `e(C - [y], -[1]) e(proof, [\tau - z]) = 1`.
+    fn verify_kzg_proof_impl(&self, commitment: G1Affine, z: Fr, y: Fr, proof: G1Affine) -> bool {
+        let x_minus_z = self.g2_monomial[1].into_group() - G2Affine::generator() * z;
+        let p_minus_y = commitment.into_group() - G1Affine::generator() * y;
+        pairing_check(
+            [p_minus_y, proof.into_group()],
+            [-G2Affine::generator().into_group(), x_minus_z],
+        )
+    }
+
+    /// Outputs the evaluation at `z` of the polynomial given by its
+    /// evaluations at the roots of unity in bit-reversed order, with the
+    /// barycentric formula.
+    fn evaluate_polynomial_in_evaluation_form(&self, polynomial: &[Fr], z: Fr) -> Fr {
+        if let Some(i) = self.roots_of_unity_brp.iter().position(|w| *w == z) {
+            return polynomial[i];
+        }
+        let mut denominators: Vec<Fr> = self.roots_of_unity_brp.iter().map(|w| z - w).collect();
+        batch_inversion(&mut denominators);
+        let sum: Fr = polynomial
+            .iter()
+            .zip(&self.roots_of_unity_brp)
+            .zip(&denominators)
+            .map(|((p, w), d)| *p * w * d)
+            .sum();
+        let width = Fr::from(FIELD_ELEMENTS_PER_BLOB as u64);
+        sum * (z.pow([FIELD_ELEMENTS_PER_BLOB as u64]) - Fr::one()) * width.inverse().unwrap()
+    }
+
+    /// Outputs the commitment to the quotient `(p(X) - p(z)) / (X - z)`,
+    /// computed in evaluation form, along with `p(z)`.
+    fn compute_kzg_proof_impl(&self, polynomial: &[Fr], z: Fr) -> (G1Affine, Fr) {
+        let y = self.evaluate_polynomial_in_evaluation_form(polynomial, z);
+        let mut denominators: Vec<Fr> = self.roots_of_unity_brp.iter().map(|w| *w - z).collect();
+        batch_inversion(&mut denominators);
+        let quotient: Vec<Fr> = polynomial
+            .iter()
+            .zip(&self.roots_of_unity_brp)
+            .zip(&denominators)
+            .map(|((p, w), d)| {
+                if *w == z {
+                    self.compute_quotient_eval_within_domain(z, polynomial, y)
+                } else {
+                    (*p - y) * d
+                }
+            })
+            .collect();
+        (
+            g1_lincomb(&self.g1_lagrange_brp, &quotient).into_affine(),
+            y,
+        )
+    }
+
+    /// Outputs the evaluation of the quotient at `z`, when `z` is a root of unity.
+    fn compute_quotient_eval_within_domain(&self, z: Fr, polynomial: &[Fr], y: Fr) -> Fr {
+        let mut denominators: Vec<Fr> = self
+            .roots_of_unity_brp
+            .iter()
+            .map(|w| if *w == z { Fr::one() } else { z * (z - w) })
+            .collect();
+        batch_inversion(&mut denominators);
+        polynomial
+            .iter()
+            .zip(&self.roots_of_unity_brp)
+            .zip(&denominators)
+            .filter(|((_, w), _)| **w != z)
+            .map(|((p, w), d)| (*p - y) * w * d)
+            .sum()
+    }
+}
+
+/// Parses the hex encodings of compressed points.
+fn parse_points<P: AffineRepr>(tokens: &[&str], num_bytes: usize) -> Result<Vec<P>, Error> {
+    ark_std::cfg_iter!(tokens)
+        .map(|token| {
+            let bytes = decode_hex(token)?;
+            if bytes.len() != num_bytes {
+                return Err(Error::IncorrectInputLength(format!(
+                    "a point of the trusted setup has {} bytes instead of {}",
+                    bytes.len(),
+                    num_bytes
+                )));
+            }
+            P::deserialize_compressed(&bytes[..])
+                .map_err(|_| Error::InvalidEncoding(format!("invalid point {}", token)))
+        })
+        .collect()
+}
+
+/// Decodes a hex string, with an optional `0x` prefix.
+pub(crate) fn decode_hex(s: &str) -> Result<Vec<u8>, Error> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    if s.len() % 2 == 1 || !s.is_ascii() {
+        return Err(Error::InvalidEncoding(format!("invalid hex string {}", s)));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&s[i..i + 2], 16)
+                .map_err(|_| Error::InvalidEncoding(format!("invalid hex string {}", s)))
+        })
+        .collect()
+}
+
+/// Reverses the `log2(n)` low bits of `i`, for a power of two `n`.
+pub(crate) fn reverse_bits(i: usize, n: usize) -> usize {
+    let bits = ark_std::log2(n);
+    if bits == 0 {
+        i
+    } else {
+        i.reverse_bits() >> (usize::BITS - bits)
+    }
+}
+
+/// Permutes `values`, of length a power of two, by reversing the bits of
+/// the indices.
+pub(crate) fn bit_reversal_permutation<T: Clone>(values: &[T]) -> Vec<T> {
+    (0..values.len())
+        .map(|i| values[reverse_bits(i, values.len())].clone())
+        .collect()
+}
+
+/// The roots of unity `\omega^i` of order `n`, in their natural order. The
+/// generator `\omega` is `7^{(p - 1) / n}`, as in the specs.
+pub(crate) fn roots_of_unity(n: usize) -> Vec<Fr> {
+    Radix2EvaluationDomain::<Fr>::new(n)
+        .unwrap()
+        .elements()
+        .collect()
+}
+
+/// Outputs `[1, x, x^2, ..., x^{n - 1}]`.
+pub(crate) fn compute_powers(x: Fr, n: usize) -> Vec<Fr> {
+    let mut powers = Vec::with_capacity(n);
+    let mut current = Fr::one();
+    for _ in 0..n {
+        powers.push(current);
+        current *= x;
+    }
+    powers
+}
+
+/// Decodes a big-endian field element, which must be canonical.
+pub fn bytes_to_bls_field(bytes: &Bytes32) -> Result<Fr, Error> {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    Fr::from_bigint(ark_ff::BigInt(limbs)).ok_or(Error::InvalidEncoding(
+        "the field element is not canonical".to_string(),
+    ))
+}
+
+/// Encodes a field element in big-endian.
+pub fn bls_field_to_bytes(x: &Fr) -> Bytes32 {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&x.into_bigint().to_bytes_be());
+    bytes
+}
+
+/// Decodes a compressed point of G1, which must be in the prime-order
+/// subgroup. The point at infinity only has the canonical encoding
+/// `G1_POINT_AT_INFINITY`.
+pub fn bytes_to_kzg_g1(bytes: &Bytes48) -> Result<G1Affine, Error> {
+    if *bytes == G1_POINT_AT_INFINITY {
+        return Ok(G1Affine::zero());
+    }
+    match G1Affine::deserialize_compressed(&bytes[..]) {
+        Ok(p) if !p.is_zero() => Ok(p),
+        _ => Err(Error::InvalidEncoding(
+            "the bytes do not encode a point of G1".to_string(),
+        )),
+    }
+}
+
+/// Encodes a point of G1 in compressed form.
+pub fn g1_to_bytes(p: &G1Affine) -> Bytes48 {
+    let mut bytes = [0u8; 48];
+    p.serialize_compressed(&mut bytes[..]).unwrap();
+    bytes
+}
+
+/// Decodes the field elements of a blob.
+fn blob_to_polynomial(blob: &Blob) -> Result<Vec<Fr>, Error> {
+    blob.chunks(BYTES_PER_FIELD_ELEMENT)
+        .map(|chunk| bytes_to_bls_field(chunk.try_into().unwrap()))
+        .collect()
+}
+
+/// Hashes `data` with SHA-256 to a field element.
+fn hash_to_bls_field(data: &[u8]) -> Fr {
+    Fr::from_be_bytes_mod_order(&Sha256::digest(data))
+}
+
+/// The Fiat-Shamir challenge at which a blob is opened by `compute_blob_kzg_proof`.
+fn compute_challenge(blob: &Blob, commitment: &Bytes48) -> Fr {
+    let mut data = FIAT_SHAMIR_PROTOCOL_DOMAIN.to_vec();
+    data.extend_from_slice(&(FIELD_ELEMENTS_PER_BLOB as u128).to_be_bytes());
+    data.extend_from_slice(blob);
+    data.extend_from_slice(commitment);
+    hash_to_bls_field(&data)
+}
+
+fn g1_lincomb(points: &[G1Affine], scalars: &[Fr]) -> G1Projective {
+    G1Projective::msm_unchecked(&points[..scalars.len()], scalars)
+}
+
+fn pairing_check(a: [G1Projective; 2], b: [<Bls12_381 as Pairing>::G2; 2]) -> bool {
+    Bls12_381::multi_pairing(
+        G1Projective::normalize_batch(&a),
+        <Bls12_381 as Pairing>::G2::normalize_batch(&b),
+    )
+    .is_zero()
+}
@@ -0,0 +1,728 @@
+use super::{cells::*, *};
+use crate::kzg10::{Powers, KZG10};
+use ark_ec::scalar_mul::ScalarMul;
+use ark_ff::{FftField, UniformRand};
+use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, Polynomial};
+use ark_std::{borrow::Cow, rand::Rng, test_rng};
+use std::{fs, path::Path, sync::OnceLock};
+
+// ******** auxiliary test functions ********
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// A trusted setup for the known trapdoor `tau`, in the text format of the
+/// reference implementation.
+fn insecure_setup_text(tau: Fr) -> String {
+    let powers = compute_powers(tau, FIELD_ELEMENTS_PER_BLOB);
+    let domain = Radix2EvaluationDomain::<Fr>::new(FIELD_ELEMENTS_PER_BLOB).unwrap();
+    let lagrange = domain.evaluate_all_lagrange_coefficients(tau);
+    let g1 = G1Affine::generator().into_group();
+    let g2 = <Bls12_381 as Pairing>::G2::from(G2Affine::generator());
+
+    let mut text = format!(
+        "{}\n{}\n",
+        FIELD_ELEMENTS_PER_BLOB,
+        TrustedSetup::NUM_G2_POINTS
+    );
+    let mut push = |bytes: Vec<u8>| {
+        text.push_str(&encode_hex(&bytes));
+        text.push('\n');
+    };
+    for p in g1.batch_mul(&lagrange) {
+        push(g1_to_bytes(&p).to_vec());
+    }
+    for p in g2.batch_mul(&powers[..TrustedSetup::NUM_G2_POINTS]) {
+        let mut bytes = vec![];
+        p.serialize_compressed(&mut bytes).unwrap();
+        push(bytes);
+    }
+    for p in g1.batch_mul(&powers) {
+        push(g1_to_bytes(&p).to_vec());
+    }
+    text
+}
+
+fn setup() -> &'static TrustedSetup {
+    static SETUP: OnceLock<TrustedSetup> = OnceLock::new();
+    SETUP.get_or_init(|| {
+        let tau = Fr::rand(&mut test_rng());
+        TrustedSetup::from_text(&insecure_setup_text(tau)).unwrap()
+    })
+}
+
+fn random_blob<R: Rng>(rng: &mut R) -> Box<Blob> {
+    let mut blob = Box::new([0u8; BYTES_PER_BLOB]);
+    for chunk in blob.chunks_mut(BYTES_PER_FIELD_ELEMENT) {
+        chunk.copy_from_slice(&bls_field_to_bytes(&Fr::rand(rng)));
+    }
+    blob
+}
+
+fn polynomial_of(blob: &Blob) -> DensePolynomial<Fr> {
+    DensePolynomial::from_coefficients_vec(polynomial_eval_to_coeff(
+        &blob_to_polynomial(blob).unwrap(),
+    ))
+}
+
+// ****************** tests ******************
+
+#[test]
+fn encodings_test() {
+    // The ZCash encodings of the generators.
+    assert_eq!(
+        encode_hex(&g1_to_bytes(&G1Affine::generator())),
+        "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+    );
+    assert_eq!(g1_to_bytes(&G1Affine::zero()), G1_POINT_AT_INFINITY);
+    assert!(bytes_to_kzg_g1(&G1_POINT_AT_INFINITY).unwrap().is_zero());
+    let mut non_canonical_infinity = G1_POINT_AT_INFINITY;
+    non_canonical_infinity[47] = 1;
+    assert!(bytes_to_kzg_g1(&non_canonical_infinity).is_err());
+
+    let x = Fr::rand(&mut test_rng());
+    assert_eq!(bytes_to_bls_field(&bls_field_to_bytes(&x)).unwrap(), x);
+    let modulus: Bytes32 = Fr::MODULUS.to_bytes_be().try_into().unwrap();
+    assert!(bytes_to_bls_field(&modulus).is_err());
+
+    // The roots of unity are generated by 7, as in the specs.
+    assert_eq!(Fr::GENERATOR, Fr::from(7u64));
+    assert_eq!(Fr::TWO_ADIC_ROOT_OF_UNITY, Fr::GENERATOR.pow(Fr::TRACE));
+
+    assert_eq!(
+        bit_reversal_permutation(&[0, 1, 2, 3, 4, 5, 6, 7]),
+        vec![0, 4, 2, 6, 1, 5, 3, 7]
+    );
+}
+
+#[test]
+fn trusted_setup_test() {
+    let tau = Fr::rand(&mut test_rng());
+    let text = insecure_setup_text(tau);
+    let setup = TrustedSetup::from_text(&text).unwrap();
+    assert_eq!(
+        setup.g1_monomial[1],
+        (G1Affine::generator() * tau).into_affine()
+    );
+
+    // Without the monomial basis, it is computed from the Lagrange basis.
+    let legacy: Vec<&str> = text
+        .lines()
+        .take(2 + FIELD_ELEMENTS_PER_BLOB + TrustedSetup::NUM_G2_POINTS)
+        .collect();
+    assert_eq!(TrustedSetup::from_text(&legacy.join("\n")).unwrap(), setup);
+
+    assert!(TrustedSetup::from_text(&text.replacen("4096", "4095", 1)).is_err());
+    let truncated: Vec<&str> = text.lines().take(100).collect();
+    assert!(TrustedSetup::from_text(&truncated.join("\n")).is_err());
+}
+
+#[test]
+fn blob_to_kzg_commitment_test() {
+    let setup = setup();
+    let blob = random_blob(&mut test_rng());
+    let commitment = setup.blob_to_kzg_commitment(&blob).unwrap();
+
+    // The commitment is the KZG10 commitment to the polynomial of the blob.
+    let powers = Powers::<Bls12_381> {
+        powers_of_g: Cow::Borrowed(&setup.g1_monomial),
+        powers_of_gamma_g: Cow::Owned(vec![]),
+    };
+    let (expected, _) =
+        KZG10::<Bls12_381, DensePolynomial<Fr>>::commit(&powers, &polynomial_of(&blob), None, None)
+            .unwrap();
+    assert_eq!(commitment, g1_to_bytes(&expected.0));
+
+    let zero_blob = Box::new([0u8; BYTES_PER_BLOB]);
+    assert_eq!(
+        setup.blob_to_kzg_commitment(&zero_blob).unwrap(),
+        G1_POINT_AT_INFINITY
+    );
+    let mut invalid_blob = blob.clone();
+    invalid_blob[..32].copy_from_slice(&[0xff; 32]);
+    assert!(setup.blob_to_kzg_commitment(&invalid_blob).is_err());
+}
+
+#[test]
+fn kzg_proof_test() {
+    let setup = setup();
+    let rng = &mut test_rng();
+    let blob = random_blob(rng);
+    let commitment = setup.blob_to_kzg_commitment(&blob).unwrap();
+    let polynomial = polynomial_of(&blob);
+
+    let outside = bls_field_to_bytes(&Fr::rand(rng));
+    let inside = bls_field_to_bytes(&setup.roots_of_unity_brp[5]);
+    for z in [outside, inside] {
+        let (proof, y) = setup.compute_kzg_proof(&blob, &z).unwrap();
+        let expected_y = polynomial.evaluate(&bytes_to_bls_field(&z).unwrap());
+        assert_eq!(y, bls_field_to_bytes(&expected_y));
+        assert!(setup.verify_kzg_proof(&commitment, &z, &y, &proof).unwrap());
+
+        let wrong_y = bls_field_to_bytes(&(expected_y + Fr::one()));
+        assert!(!setup
+            .verify_kzg_proof(&commitment, &z, &wrong_y, &proof)
+            .unwrap());
+    }
+    // An evaluation at a root of unity is an element of the blob.
+    let (_, y) = setup.compute_kzg_proof(&blob, &inside).unwrap();
+    assert_eq!(y[..], blob[5 * 32..6 * 32]);
+
+    assert!(setup
+        .verify_kzg_proof(&[0u8; 48], &outside, &outside, &G1_POINT_AT_INFINITY)
+        .is_err());
+}
+
+#[test]
+fn blob_kzg_proof_batch_test() {
+    let setup = setup();
+    let rng = &mut test_rng();
+    let blobs: Vec<Blob> = (0..3).map(|_| *random_blob(rng)).collect();
+    let commitments: Vec<Bytes48> = blobs
+        .iter()
+        .map(|b| setup.blob_to_kzg_commitment(b).unwrap())
+        .collect();
+    let proofs: Vec<Bytes48> = blobs
+        .iter()
+        .zip(&commitments)
+        .map(|(b, c)| setup.compute_blob_kzg_proof(b, c).unwrap())
+        .collect();
+
+    for i in 0..3 {
+        assert!(setup
+            .verify_blob_kzg_proof(&blobs[i], &commitments[i], &proofs[i])
+            .unwrap());
+    }
+    assert!(!setup
+        .verify_blob_kzg_proof(&blobs[0], &commitments[0], &proofs[1])
+        .unwrap());
+    assert!(setup
+        .verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs)
+        .unwrap());
+    assert!(setup.verify_blob_kzg_proof_batch(&[], &[], &[]).unwrap());
+
+    let swapped = [proofs[1], proofs[0], proofs[2]];
+    assert!(!setup
+        .verify_blob_kzg_proof_batch(&blobs, &commitments, &swapped)
+        .unwrap());
+    assert!(setup
+        .verify_blob_kzg_proof_batch(&blobs, &commitments[..2], &proofs)
+        .is_err());
+}
+
+#[test]
+fn cells_test() {
+    let setup = setup();
+    let rng = &mut test_rng();
+    let blob = random_blob(rng);
+    let commitment = setup.blob_to_kzg_commitment(&blob).unwrap();
+    let (cells, proofs) = setup.compute_cells_and_kzg_proofs(&blob).unwrap();
+    assert_eq!(cells, setup.compute_cells(&blob).unwrap());
+
+    // The first half of the extended blob is the blob itself, and the cells
+    // are evaluations of the polynomial of the blob.
+    assert_eq!(cells[..CELLS_PER_EXT_BLOB / 2].concat(), blob.to_vec());
+    let polynomial = polynomial_of(&blob);
+    let roots = bit_reversal_permutation(&roots_of_unity(FIELD_ELEMENTS_PER_EXT_BLOB));
+    let evals = cell_to_coset_evals(&cells[100]).unwrap();
+    for (j, eval) in evals.iter().enumerate() {
+        assert_eq!(polynomial.evaluate(&roots[100 * 64 + j]), *eval);
+    }
+
+    // Cells of two blobs, with repeated commitments.
+    let other_blob = random_blob(rng);
+    let other_commitment = setup.blob_to_kzg_commitment(&other_blob).unwrap();
+    let (other_cells, other_proofs) = setup.compute_cells_and_kzg_proofs(&other_blob).unwrap();
+    let commitments = [commitment, other_commitment, commitment, other_commitment];
+    let indices = [0u64, 5, 127, 64];
+    let batch_cells = [cells[0], other_cells[5], cells[127], other_cells[64]];
+    let batch_proofs = [proofs[0], other_proofs[5], proofs[127], other_proofs[64]];
+    assert!(setup
+        .verify_cell_kzg_proof_batch(&commitments, &indices, &batch_cells, &batch_proofs)
+        .unwrap());
+    assert!(setup
+        .verify_cell_kzg_proof_batch(&[], &[], &[], &[])
+        .unwrap());
+
+    let wrong_indices = [0u64, 5, 126, 64];
+    assert!(!setup
+        .verify_cell_kzg_proof_batch(&commitments, &wrong_indices, &batch_cells, &batch_proofs)
+        .unwrap());
+    let mut wrong_cells = batch_cells;
+    wrong_cells[1][31] ^= 1;
+    assert!(!setup
+        .verify_cell_kzg_proof_batch(&commitments, &indices, &wrong_cells, &batch_proofs)
+        .unwrap());
+    assert!(setup
+        .verify_cell_kzg_proof_batch(&commitments, &[0, 5, 128, 64], &batch_cells, &batch_proofs)
+        .is_err());
+}
+
+#[test]
+fn recover_cells_test() {
+    let setup = setup();
+    let rng = &mut test_rng();
+    let blob = random_blob(rng);
+    let (cells, proofs) = setup.compute_cells_and_kzg_proofs(&blob).unwrap();
+
+    let mut indices: Vec<u64> = (0..CELLS_PER_EXT_BLOB as u64).collect();
+    while indices.len() > CELLS_PER_EXT_BLOB / 2 {
+        indices.remove(rng.gen_range(0..indices.len()));
+    }
+    let available: Vec<Cell> = indices.iter().map(|&i| cells[i as usize]).collect();
+    let (recovered_cells, recovered_proofs) = setup
+        .recover_cells_and_kzg_proofs(&indices, &available)
+        .unwrap();
+    assert_eq!(recovered_cells, cells);
+    assert_eq!(recovered_proofs, proofs);
+
+    assert!(setup
+        .recover_cells_and_kzg_proofs(&indices[1..], &available[1..])
+        .is_err());
+    let mut shuffled = indices.clone();
+    shuffled.swap(0, 1);
+    assert!(setup
+        .recover_cells_and_kzg_proofs(&shuffled, &available)
+        .is_err());
+}
+
+// ******** test vectors ********
+
+/// The subset of YAML used by the test vectors of the consensus specs.
+#[derive(Clone, Debug, PartialEq)]
+enum Yaml {
+    Null,
+    Bool(bool),
+    Int(u64),
+    Str(String),
+    Seq(Vec<Yaml>),
+    Map(Vec<(String, Yaml)>),
+}
+
+impl Yaml {
+    fn parse(text: &str) -> Yaml {
+        let lines: Vec<&str> = text
+            .lines()
+            .filter(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+            .collect();
+        let mut pos = 0;
+        Self::parse_block(&lines, &mut pos, 0)
+    }
+
+    fn indent(line: &str) -> usize {
+        line.len() - line.trim_start().len()
+    }
+
+    /// Parses a block mapping or sequence whose lines are indented by `indent`.
+    fn parse_block(lines: &[&str], pos: &mut usize, indent: usize) -> Yaml {
+        let is_seq = lines[*pos].trim_start().starts_with('-');
+        let mut seq = Vec::new();
+        let mut map = Vec::new();
+        while *pos < lines.len() && Self::indent(lines[*pos]) == indent {
+            let line = lines[*pos].trim();
+            if is_seq && !line.starts_with('-') {
+                break;
+            }
+            *pos += 1;
+            if is_seq {
+                let rest = line.trim_start_matches('-').trim();
+                seq.push(Self::parse_value(rest, lines, pos, indent));
+            } else {
+                let (key, rest) = line.split_once(':').unwrap();
+                map.push((
+                    key.trim().to_string(),
+                    Self::parse_value(rest.trim(), lines, pos, indent),
+                ));
+            }
+        }
+        if is_seq {
+            Yaml::Seq(seq)
+        } else {
+            Yaml::Map(map)
+        }
+    }
+
+    fn parse_value(rest: &str, lines: &[&str], pos: &mut usize, indent: usize) -> Yaml {
+        if !rest.is_empty() {
+            return Self::parse_flow(&mut rest.chars().peekable());
+        }
+        match lines.get(*pos) {
+            Some(next) if Self::indent(next) > indent => {
+                Self::parse_block(lines, pos, Self::indent(next))
+            }
+            // A sequence may be at the same indentation as its key.
+            Some(next) if next.trim_start().starts_with('-') && Self::indent(next) == indent => {
+                Self::parse_block(lines, pos, indent)
+            }
+            _ => Yaml::Null,
+        }
+    }
+
+    fn parse_flow(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Yaml {
+        while chars.peek() == Some(&' ') {
+            chars.next();
+        }
+        match chars.peek() {
+            Some('[') | Some('{') => {
+                let is_seq = chars.next() == Some('[');
+                let mut items = Vec::new();
+                loop {
+                    while matches!(chars.peek(), Some(' ') | Some(',')) {
+                        chars.next();
+                    }
+                    if matches!(chars.peek(), Some(']') | Some('}')) {
+                        chars.next();
+                        break;
+                    }
+                    if is_seq {
+                        items.push((String::new(), Self::parse_flow(chars)));
+                    } else {
+                        let key: String = chars.by_ref().take_while(|c| *c != ':').collect();
+                        items.push((key.trim().to_string(), Self::parse_flow(chars)));
+                    }
+                }
+                if is_seq {
+                    Yaml::Seq(items.into_iter().map(|(_, v)| v).collect())
+                } else {
+                    Yaml::Map(items)
+                }
+            }
+            Some('\'') | Some('"') => {
+                let quote = chars.next().unwrap();
+                Yaml::Str(chars.by_ref().take_while(|c| *c != quote).collect())
+            }
+            _ => {
+                let mut scalar = String::new();
+                while let Some(c) = chars.peek() {
+                    if matches!(c, ',' | ']' | '}') {
+                        break;
+                    }
+                    scalar.push(*c);
+                    chars.next();
+                }
+                match scalar.trim() {
+                    "null" | "~" | "" => Yaml::Null,
+                    "true" => Yaml::Bool(true),
+                    "false" => Yaml::Bool(false),
+                    s => s
+                        .parse()
+                        .map(Yaml::Int)
+                        .unwrap_or_else(|_| Yaml::Str(s.to_string())),
+                }
+            }
+        }
+    }
+
+    fn get(&self, key: &str) -> &Yaml {
+        match self {
+            Yaml::Map(entries) => &entries.iter().find(|(k, _)| k == key).unwrap().1,
+            _ => panic!("not a mapping"),
+        }
+    }
+
+    fn seq(&self) -> &[Yaml] {
+        match self {
+            Yaml::Seq(items) => items,
+            _ => panic!("not a sequence"),
+        }
+    }
+
+    /// Decodes a hex string to `N` bytes. Inputs of the wrong length are
+    /// invalid, and their test cases expect an error.
+    fn bytes<const N: usize>(&self) -> Result<Box<[u8; N]>, Error> {
+        match self {
+            Yaml::Str(s) => decode_hex(s)?
+                .into_boxed_slice()
+                .try_into()
+                .map_err(|_| Error::IncorrectInputLength(format!("{} is not {} bytes", s, N))),
+            _ => panic!("not a string"),
+        }
+    }
+
+    fn bytes_seq<const N: usize>(&self) -> Result<Vec<[u8; N]>, Error> {
+        self.seq()
+            .iter()
+            .map(|y| y.bytes::<N>().map(|b| *b))
+            .collect()
+    }
+
+    fn blob(&self) -> Result<Box<Blob>, Error> {
+        self.bytes()
+    }
+
+    fn blobs(&self) -> Result<Vec<Blob>, Error> {
+        self.seq().iter().map(|y| y.blob().map(|b| *b)).collect()
+    }
+
+    fn hex(bytes: &[u8]) -> Yaml {
+        Yaml::Str(format!("0x{}", encode_hex(bytes)))
+    }
+
+    fn hex_seq<T: AsRef<[u8]>>(items: &[T]) -> Yaml {
+        Yaml::Seq(items.iter().map(|b| Yaml::hex(b.as_ref())).collect())
+    }
+}
+
+/// Runs the test case `data` of the consensus specs for the function
+/// `handler`, and checks its output. An output of `null` expects an error.
+fn run_test_vector(setup: &TrustedSetup, handler: &str, data: &Yaml) {
+    let input = data.get("input");
+    let cells_and_proofs = |(cells, proofs): (Vec<Cell>, Vec<KZGProof>)| {
+        Yaml::Seq(vec![Yaml::hex_seq(&cells), Yaml::hex_seq(&proofs)])
+    };
+    let output: Result<Yaml, Error> = match handler {
+        "blob_to_kzg_commitment" => input
+            .get("blob")
+            .blob()
+            .and_then(|blob| setup.blob_to_kzg_commitment(&blob))
+            .map(|c| Yaml::hex(&c)),
+        "compute_kzg_proof" => (|| {
+            let (proof, y) =
+                setup.compute_kzg_proof(&*input.get("blob").blob()?, &*input.get("z").bytes()?)?;
+            Ok(Yaml::Seq(vec![Yaml::hex(&proof), Yaml::hex(&y)]))
+        })(),
+        "compute_blob_kzg_proof" => (|| {
+            let proof = setup.compute_blob_kzg_proof(
+                &*input.get("blob").blob()?,
+                &*input.get("commitment").bytes()?,
+            )?;
+            Ok(Yaml::hex(&proof))
+        })(),
+        "verify_kzg_proof" => (|| {
+            setup.verify_kzg_proof(
+                &*input.get("commitment").bytes()?,
+                &*input.get("z").bytes()?,
+                &*input.get("y").bytes()?,
+                &*input.get("proof").bytes()?,
+            )
+        })()
+        .map(Yaml::Bool),
+        "verify_blob_kzg_proof" => (|| {
+            setup.verify_blob_kzg_proof(
+                &*input.get("blob").blob()?,
+                &*input.get("commitment").bytes()?,
+                &*input.get("proof").bytes()?,
+            )
+        })()
+        .map(Yaml::Bool),
+        "verify_blob_kzg_proof_batch" => (|| {
+            setup.verify_blob_kzg_proof_batch(
+                &input.get("blobs").blobs()?,
+                &input.get("commitments").bytes_seq()?,
+                &input.get("proofs").bytes_seq()?,
+            )
+        })()
+        .map(Yaml::Bool),
+        "compute_cells" => input
+            .get("blob")
+            .blob()
+            .and_then(|blob| setup.compute_cells(&blob))
+            .map(|cells| Yaml::hex_seq(&cells)),
+        "compute_cells_and_kzg_proofs" => input
+            .get("blob")
+            .blob()
+            .and_then(|blob| setup.compute_cells_and_kzg_proofs(&blob))
+            .map(cells_and_proofs),
+        "verify_cell_kzg_proof_batch" => (|| {
+            setup.verify_cell_kzg_proof_batch(
+                &input.get("commitments").bytes_seq()?,
+                &cell_indices(input.get("cell_indices")),
+                &input.get("cells").bytes_seq()?,
+                &input.get("proofs").bytes_seq()?,
+            )
+        })()
+        .map(Yaml::Bool),
+        "recover_cells_and_kzg_proofs" => (|| {
+            setup.recover_cells_and_kzg_proofs(
+                &cell_indices(input.get("cell_indices")),
+                &input.get("cells").bytes_seq()?,
+            )
+        })()
+        .map(cells_and_proofs),
+        _ => panic!("unknown handler {}", handler),
+    };
+    match data.get("output") {
+        Yaml::Null => assert!(output.is_err(), "{} expected an error", handler),
+        expected => assert_eq!(
+            output.as_ref().ok(),
+            Some(&normalize(expected)),
+            "{} returned {:?}",
+            handler,
+            output
+        ),
+    }
+}
+
+fn cell_indices(yaml: &Yaml) -> Vec<u64> {
+    yaml.seq()
+        .iter()
+        .map(|i| match i {
+            Yaml::Int(i) => *i,
+            _ => panic!("not an integer"),
+        })
+        .collect()
+}
+
+/// Lowercases the hex strings of an expected output.
+fn normalize(yaml: &Yaml) -> Yaml {
+    match yaml {
+        Yaml::Str(s) => Yaml::Str(s.to_lowercase()),
+        Yaml::Seq(items) => Yaml::Seq(items.iter().map(normalize).collect()),
+        other => other.clone(),
+    }
+}
+
+#[test]
+fn test_vector_format_test() {
+    let setup = setup();
+    let rng = &mut test_rng();
+    let blob = random_blob(rng);
+    let blob_hex = format!("0x{}", encode_hex(&blob[..]));
+    let commitment = setup.blob_to_kzg_commitment(&blob).unwrap();
+    let proof = setup.compute_blob_kzg_proof(&blob, &commitment).unwrap();
+    let z = bls_field_to_bytes(&Fr::from(2u64));
+    let (z_proof, y) = setup.compute_kzg_proof(&blob, &z).unwrap();
+    let (cells, cell_proofs) = setup.compute_cells_and_kzg_proofs(&blob).unwrap();
+    let hex = |b: &[u8]| format!("0x{}", encode_hex(b));
+
+    let cases = [
+        (
+            "blob_to_kzg_commitment",
+            format!("input: {{blob: '{}'}}\noutput: '{}'\n", blob_hex, hex(&commitment)),
+        ),
+        (
+            "blob_to_kzg_commitment",
+            "input: {blob: '0x00'}\noutput: null\n".to_string(),
+        ),
+        (
+            "compute_kzg_proof",
+            format!(
+                "input:\n  blob: '{}'\n  z: '{}'\noutput: ['{}', '{}']\n",
+                blob_hex,
+                hex(&z),
+                hex(&z_proof),
+                hex(&y)
+            ),
+        ),
+        (
+            "compute_blob_kzg_proof",
+            format!(
+                "input: {{blob: '{}', commitment: '{}'}}\noutput: '{}'\n",
+                blob_hex,
+                hex(&commitment),
+                hex(&proof)
+            ),
+        ),
+        (
+            "verify_kzg_proof",
+            format!(
+                "input: {{commitment: '{}', z: '{}', y: '{}', proof: '{}'}}\noutput: true\n",
+                hex(&commitment),
+                hex(&z),
+                hex(&y),
+                hex(&z_proof)
+            ),
+        ),
+        (
+            "verify_blob_kzg_proof",
+            format!(
+                "input: {{blob: '{}', commitment: '{}', proof: '{}'}}\noutput: false\n",
+                blob_hex,
+                hex(&commitment),
+                hex(&z_proof)
+            ),
+        ),
+        (
+            "verify_blob_kzg_proof_batch",
+            format!(
+                "input:\n  blobs:\n  - '{}'\n  commitments: ['{}']\n  proofs: ['{}']\noutput: true\n",
+                blob_hex,
+                hex(&commitment),
+                hex(&proof)
+            ),
+        ),
+        (
+            "verify_blob_kzg_proof_batch",
+            "input: {blobs: [], commitments: [], proofs: []}\noutput: true\n".to_string(),
+        ),
+        (
+            "verify_cell_kzg_proof_batch",
+            format!(
+                "input:\n  commitments: ['{}', '{}']\n  cell_indices: [3, 127]\n  cells: ['{}', '{}']\n  proofs: ['{}', '{}']\noutput: true\n",
+                hex(&commitment),
+                hex(&commitment),
+                hex(&cells[3]),
+                hex(&cells[127]),
+                hex(&cell_proofs[3]),
+                hex(&cell_proofs[127])
+            ),
+        ),
+        (
+            "verify_cell_kzg_proof_batch",
+            format!(
+                "input: {{commitments: ['{}'], cell_indices: [128], cells: ['{}'], proofs: ['{}']}}\noutput: null\n",
+                hex(&commitment),
+                hex(&cells[3]),
+                hex(&cell_proofs[3])
+            ),
+        ),
+    ];
+    for (handler, text) in cases.iter() {
+        run_test_vector(setup, handler, &Yaml::parse(text));
+    }
+
+    let output = Yaml::Seq(vec![Yaml::hex_seq(&cells), Yaml::hex_seq(&cell_proofs)]);
+    let mut text = String::from("input:\n  cell_indices: [");
+    let indices: Vec<String> = (64..128).map(|i: usize| i.to_string()).collect();
+    text.push_str(&indices.join(", "));
+    text.push_str("]\n  cells:\n");
+    for cell in &cells[64..] {
+        text.push_str(&format!("  - '{}'\n", hex(cell)));
+    }
+    let data = Yaml::parse(&text);
+    let data = Yaml::Map(vec![
+        ("input".to_string(), data.get("input").clone()),
+        ("output".to_string(), output),
+    ]);
+    run_test_vector(setup, "recover_cells_and_kzg_proofs", &data);
+}
+
+/// Runs the KZG test vectors of the consensus specs, vendored under
+/// `test_vectors` as in the `tests/general` directory of a
+/// `consensus-spec-tests` release, with the cases in
+/// `<fork>/kzg/<handler>/kzg-mainnet/<case>/data.yaml`, along with the
+/// `trusted_setup.txt` of the mainnet ceremony. They are vendored by
+/// `scripts/vendor-eip4844-test-vectors.sh`, and this test is ignored until
+/// they are committed.
+#[test]
+#[ignore]
+fn official_test_vectors() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/eip4844/test_vectors");
+    let setup = TrustedSetup::load(root.join("trusted_setup.txt")).unwrap();
+    let dirs = |path: &Path| -> Vec<std::path::PathBuf> {
+        match fs::read_dir(path) {
+            Ok(entries) => entries
+                .map(|e| e.unwrap().path())
+                .filter(|p| p.is_dir())
+                .collect(),
+            Err(_) => vec![],
+        }
+    };
+    let mut num_cases = 0;
+    for fork in dirs(&root) {
+        for handler in dirs(&fork.join("kzg")) {
+            let name = handler.file_name().unwrap().to_str().unwrap().to_string();
+            for case in dirs(&handler.join("kzg-mainnet")) {
+                let text = fs::read_to_string(case.join("data.yaml")).unwrap();
+                run_test_vector(&setup, &name, &Yaml::parse(&text));
+                num_cases += 1;
+            }
+        }
+    }
+    assert!(
+        num_cases > 0,
+        "no test vectors found in {}, vendor them with scripts/vendor-eip4844-test-vectors.sh",
+        root.display()
+    );
+}
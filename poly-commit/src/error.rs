@@ -25,6 +25,7 @@ pub enum Error {
     },
 
     /// The provided polynomial was meant to be hiding, but `rng` was `None`.
+    /// A `DeterministicRng` can be provided where no system RNG is available.
     MissingRng,
 
     /// The degree provided in setup was too small; degree 0 polynomials
@@ -140,6 +141,142 @@ pub enum Error {
         /// The number of variables of the point
         point_nv: usize,
     },
+
+    /// This means that bytes do not encode a valid object, such as a
+    /// canonical field element or a point of the prime-order subgroup.
+    InvalidEncoding(String),
+
+    /// This means an index of a committed vector was out of range, or was
+    /// repeated in the subset of indices to be opened.
+    InvalidIndex {
+        /// The offending index.
+        index: usize,
+        /// The length of the committed vector.
+        len: usize,
+    },
+
+    /// A proof was rejected by `check`, `batch_check` or
+    /// `check_combinations`, for the given reason.
+    VerificationFailed(VerificationFailure),
+}
+
+/// The reason why a verifier rejected a proof. Points are formatted with
+/// their `Debug` implementation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerificationFailure {
+    /// The claimed evaluation of the polynomial or linear combination
+    /// `label` at `point` is not the one that the proof attests to.
+    EvaluationMismatch {
+        /// The label of the polynomial or linear combination.
+        label: String,
+        /// The point of the evaluation.
+        point: String,
+    },
+
+    /// The Merkle path of a queried column of the commitment to `label`
+    /// does not lead to its root, or is not the path of that column.
+    MerklePath {
+        /// The label of the commitment.
+        label: String,
+        /// The index of the column.
+        column: usize,
+    },
+
+    /// A queried column of the commitment to `label` is inconsistent with
+    /// the encoding of the combinations of rows sent in the proof.
+    ColumnCheck {
+        /// The label of the commitment.
+        label: String,
+        /// The index of the column.
+        column: usize,
+    },
+
+    /// The pairing check of the openings at `point` failed, or that of the
+    /// openings at all points if `point` is `None`.
+    PairingCheck {
+        /// The point of the openings.
+        point: Option<String>,
+    },
+
+    /// The inner-product argument of the openings at `point` failed, or the
+    /// combined check of the arguments at all points if `point` is `None`.
+    /// `label` is set when the argument is for one commitment only.
+    InnerProduct {
+        /// The label of the commitment, if the argument is for one only.
+        label: Option<String>,
+        /// The point of the openings.
+        point: Option<String>,
+    },
+
+    /// The commitment to `label` is inconsistent with its degree bound.
+    DegreeBound {
+        /// The label of the commitment.
+        label: String,
+    },
+
+    /// The proof, or the part of it for the commitment to `label`, does not
+    /// have the shape that the verifier expects.
+    MalformedProof {
+        /// The label of the commitment, if the proof is malformed for it only.
+        label: Option<String>,
+        /// What the verifier expected.
+        reason: String,
+    },
+}
+
+impl From<VerificationFailure> for Error {
+    fn from(failure: VerificationFailure) -> Self {
+        Error::VerificationFailed(failure)
+    }
+}
+
+impl core::fmt::Display for VerificationFailure {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            VerificationFailure::EvaluationMismatch { label, point } => write!(
+                f,
+                "the claimed evaluation of {} at {} is incorrect",
+                label, point
+            ),
+            VerificationFailure::MerklePath { label, column } => write!(
+                f,
+                "the Merkle path of column {} of {} is invalid",
+                column, label
+            ),
+            VerificationFailure::ColumnCheck { label, column } => write!(
+                f,
+                "column {} of {} is inconsistent with the proof",
+                column, label
+            ),
+            VerificationFailure::PairingCheck { point: Some(point) } => {
+                write!(f, "the pairing check of the openings at {} failed", point)
+            }
+            VerificationFailure::PairingCheck { point: None } => {
+                write!(f, "the pairing check of the openings failed")
+            }
+            VerificationFailure::InnerProduct { label, point } => {
+                write!(f, "the inner-product argument")?;
+                if let Some(label) = label {
+                    write!(f, " for {}", label)?;
+                }
+                if let Some(point) = point {
+                    write!(f, " at {}", point)?;
+                }
+                write!(f, " failed")
+            }
+            VerificationFailure::DegreeBound { label } => {
+                write!(f, "the commitment to {} violates its degree bound", label)
+            }
+            VerificationFailure::MalformedProof {
+                label: Some(label),
+                reason,
+            } => write!(f, "malformed proof for {}: {}", label, reason),
+            VerificationFailure::MalformedProof {
+                label: None,
+                reason,
+            } => write!(f, "malformed proof: {}", reason),
+        }
+    }
 }
 
 impl core::fmt::Display for Error {
@@ -249,6 +386,15 @@ impl core::fmt::Display for Error {
                     poly_nv,
                     point_nv,
                 ),
+            Error::InvalidEncoding(err) => write!(f, "invalid encoding: {}", err),
+            Error::InvalidIndex { index, len } => write!(
+                f,
+                "index {} is out of range or repeated for a vector of length {}",
+                index, len
+            ),
+            Error::VerificationFailed(failure) => {
+                write!(f, "verification failed: {}", failure)
+            }
         }
     }
 }
@@ -0,0 +1,168 @@
+use crate::{
+    kzg10, Error, LabeledCommitment, LabeledPolynomial, PCCommitment, PolynomialCommitment,
+};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_poly::{DenseUVPolynomial, Polynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{fmt::Debug, format, rand::RngCore};
+#[cfg(not(feature = "std"))]
+use ark_std::{string::ToString, vec::Vec};
+
+/// Describes the interface of the polynomial commitment schemes which can
+/// prove that committed polynomials evaluate at a point to values which are
+/// only given to the verifier as hiding Pedersen commitments
+/// `value G + randomness H`, for some generators `G` and `H` of the scheme.
+///
+/// The committed polynomials must be hiding, since otherwise their
+/// evaluations could be recomputed from their commitments.
+pub trait HiddenEvaluations<F: PrimeField, P: Polynomial<F>>: PolynomialCommitment<F, P> {
+    /// A hiding commitment to an evaluation of a committed polynomial.
+    type EvaluationCommitment: Clone + Debug + PartialEq + CanonicalSerialize + CanonicalDeserialize;
+
+    /// Outputs the commitment to `value` with `randomness`, which is how the
+    /// evaluation commitments output by `open_hidden` are opened.
+    fn commit_evaluation(
+        vk: &Self::VerifierKey,
+        value: F,
+        randomness: F,
+    ) -> Self::EvaluationCommitment;
+
+    /// On input a list of labeled polynomials, their commitments and states,
+    /// and a query point, outputs a proof of the evaluations of the
+    /// polynomials at that point along with, for each polynomial, a
+    /// commitment to its evaluation and the randomness of that commitment.
+    /// The randomness is sampled from `rng`, which is required.
+    #[allow(clippy::type_complexity)]
+    fn open_hidden<'a>(
+        ck: &Self::CommitterKey,
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(Self::Proof, Vec<(Self::EvaluationCommitment, F)>), Self::Error>
+    where
+        P: 'a,
+        Self::CommitmentState: 'a,
+        Self::Commitment: 'a;
+
+    /// Verifies that the polynomials committed inside `commitments` evaluate
+    /// at `point` to the values committed inside `evaluation_commitments`.
+    fn check_hidden<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        evaluation_commitments: impl IntoIterator<Item = &'a Self::EvaluationCommitment>,
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a,
+        Self::EvaluationCommitment: 'a;
+}
+
+// The schemes below whose evaluation commitments are commitments to constant
+// polynomials prove that `p(z) = v` for a commitment `V` to `v` by opening
+// the commitment to `p - v`, which is the difference of the commitments to
+// `p` and to `v`, to zero at `z`.
+
+/// Fails unless the commitment to `polynomial` is hiding.
+pub(crate) fn check_hiding<F: PrimeField, P: Polynomial<F>>(
+    polynomial: &LabeledPolynomial<F, P>,
+    is_hiding: bool,
+) -> Result<(), Error> {
+    if is_hiding {
+        Ok(())
+    } else {
+        Err(Error::InvalidParameters(format!(
+            "The commitment to {} must be hiding to open it to a hidden evaluation",
+            polynomial.label()
+        )))
+    }
+}
+
+/// Fails if there is a degree bound, for the schemes which cannot shift the
+/// commitments to constant polynomials.
+pub(crate) fn check_no_degree_bound(degree_bound: Option<usize>) -> Result<(), Error> {
+    match degree_bound {
+        Some(degree_bound) => Err(Error::UnsupportedDegreeBound(degree_bound)),
+        None => Ok(()),
+    }
+}
+
+/// Pairs each commitment with its evaluation commitment, failing if there
+/// are not as many of each.
+#[allow(clippy::type_complexity)]
+pub(crate) fn pair_evaluation_commitments<'a, C: PCCommitment + 'a, V: 'a>(
+    commitments: impl IntoIterator<Item = &'a LabeledCommitment<C>>,
+    evaluation_commitments: impl IntoIterator<Item = &'a V>,
+) -> Result<Vec<(&'a LabeledCommitment<C>, &'a V)>, Error> {
+    let commitments: Vec<_> = commitments.into_iter().collect();
+    let evaluation_commitments: Vec<_> = evaluation_commitments.into_iter().collect();
+    if commitments.len() != evaluation_commitments.len() {
+        return Err(Error::IncorrectInputLength(
+            "There must be one evaluation commitment per commitment".to_string(),
+        ));
+    }
+    Ok(commitments
+        .into_iter()
+        .zip(evaluation_commitments)
+        .collect())
+}
+
+/// Outputs `polynomial - value`, with the label and bounds of `polynomial`.
+pub(crate) fn subtract_constant<F: PrimeField, P: DenseUVPolynomial<F>>(
+    polynomial: &LabeledPolynomial<F, P>,
+    value: F,
+) -> LabeledPolynomial<F, P> {
+    let mut difference = polynomial.polynomial().clone();
+    difference -= &P::from_coefficients_vec(vec![value]);
+    LabeledPolynomial::new(
+        polynomial.label().clone(),
+        difference,
+        polynomial.degree_bound(),
+        polynomial.hiding_bound(),
+    )
+}
+
+/// Outputs `value G + randomness γG`, which is the hiding `KZG10` commitment
+/// to the constant polynomial `value` with the constant blinding polynomial
+/// `randomness`.
+pub(crate) fn kzg10_commit_constant<E>(
+    g: E::G1Affine,
+    gamma_g: E::G1Affine,
+    value: E::ScalarField,
+    randomness: E::ScalarField,
+) -> kzg10::Commitment<E>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+{
+    kzg10::Commitment((g * value + gamma_g * randomness).into_affine())
+}
+
+/// Outputs `commitment - evaluation_commitment` for `KZG10` commitments.
+pub(crate) fn kzg10_subtract<E>(
+    commitment: &kzg10::Commitment<E>,
+    evaluation_commitment: &kzg10::Commitment<E>,
+) -> kzg10::Commitment<E>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+{
+    kzg10::Commitment((commitment.0.into_group() - evaluation_commitment.0).into_affine())
+}
+
+/// Outputs the randomness of the difference of a commitment with randomness
+/// `rand` and of a commitment of `kzg10_commit_constant` with `randomness`.
+pub(crate) fn kzg10_subtract_randomness<F: PrimeField, P: DenseUVPolynomial<F>>(
+    rand: &kzg10::Randomness<F, P>,
+    randomness: F,
+) -> kzg10::Randomness<F, P> {
+    let mut difference = rand.clone();
+    difference.blinding_polynomial -= &P::from_coefficients_vec(vec![randomness]);
+    difference
+}
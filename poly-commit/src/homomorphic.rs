@@ -0,0 +1,74 @@
+use crate::{Error, LabeledCommitment, PCCommitment, PolynomialCommitment, PolynomialLabel};
+use ark_ff::PrimeField;
+use ark_poly::Polynomial;
+#[cfg(not(feature = "std"))]
+use ark_std::string::ToString;
+
+/// A term `(coeff, commitment, state)` of a linear combination of commitments.
+pub type CombinationTerm<'a, F, C, S> = (F, &'a LabeledCommitment<C>, &'a S);
+
+/// Describes the interface of the polynomial commitment schemes whose
+/// commitments and commitment states are additively homomorphic: the linear
+/// combination of the commitments to some polynomials, along with the same
+/// linear combination of their states, is a commitment to the linear
+/// combination of the polynomials which can be opened as such.
+///
+/// The commitments combined must share the same degree bound, if any.
+/// `MultilinearPC`, which does not implement `PolynomialCommitment`, offers
+/// the same operations on its commitments as inherent functions.
+pub trait HomomorphicCommitment<F: PrimeField, P: Polynomial<F>>:
+    PolynomialCommitment<F, P>
+{
+    /// Outputs the commitment to `Σ coeff_i p_i`, labelled `label`, and its
+    /// state, given the terms `(coeff_i, commitment_i, state_i)` of the
+    /// combination.
+    #[allow(clippy::type_complexity)]
+    fn linear_combination(
+        label: PolynomialLabel,
+        terms: &[CombinationTerm<F, Self::Commitment, Self::CommitmentState>],
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error>;
+
+    /// Outputs the commitment to the sum of two committed polynomials.
+    #[allow(clippy::type_complexity)]
+    fn add(
+        label: PolynomialLabel,
+        left: (&LabeledCommitment<Self::Commitment>, &Self::CommitmentState),
+        right: (&LabeledCommitment<Self::Commitment>, &Self::CommitmentState),
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error> {
+        Self::linear_combination(
+            label,
+            &[(F::one(), left.0, left.1), (F::one(), right.0, right.1)],
+        )
+    }
+
+    /// Outputs the commitment to a committed polynomial multiplied by
+    /// `coeff`.
+    #[allow(clippy::type_complexity)]
+    fn scale(
+        label: PolynomialLabel,
+        coeff: F,
+        commitment: &LabeledCommitment<Self::Commitment>,
+        state: &Self::CommitmentState,
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error> {
+        Self::linear_combination(label, &[(coeff, commitment, state)])
+    }
+}
+
+/// Outputs the degree bound shared by the commitments of `terms`, failing if
+/// there are no terms or if they have different degree bounds.
+pub(crate) fn common_degree_bound<F, C: PCCommitment, S>(
+    label: &str,
+    terms: &[CombinationTerm<F, C, S>],
+) -> Result<Option<usize>, Error> {
+    let (_, first, _) = terms.first().ok_or_else(|| {
+        Error::IncorrectInputLength("a linear combination needs at least one term".to_string())
+    })?;
+    let degree_bound = first.degree_bound();
+    if terms
+        .iter()
+        .any(|(_, c, _)| c.degree_bound() != degree_bound)
+    {
+        return Err(Error::EquationHasDegreeBounds(label.to_string()));
+    }
+    Ok(degree_bound)
+}
@@ -1,12 +1,22 @@
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::AffineRepr;
-use ark_ff::PrimeField;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{rand::RngCore, vec::Vec};
+use ark_ff::{PrimeField, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::{io::Read, rand::RngCore, vec::Vec};
 
 use crate::{
-    utils::Matrix, PCCommitment, PCCommitmentState, PCCommitterKey, PCUniversalParams,
-    PCVerifierKey,
+    cost::{
+        compressed_size, repeat, vec_bytes, BatchShape, CostEstimate, CostModel, ProverCost,
+        VerifierCost,
+    },
+    security::{
+        dlog_bits, field_bits, statistical_bits, SecurityAssumptions, SecurityEstimate,
+        SecurityEstimator,
+    },
+    utils::{deserialize_vec, Matrix},
+    Error, PCCommitment, PCCommitmentState, PCCommitterKey, PCUniversalParams, PCVerifierKey,
 };
 
 /// `UniversalParams` amounts to a Pederson commitment key of sufficient length
@@ -54,9 +64,74 @@ impl<G: AffineRepr> PCVerifierKey for HyraxVerifierKey<G> {
     }
 }
 
+impl<G: AffineRepr> CostModel for HyraxVerifierKey<G> {
+    fn estimate_cost(&self, poly_len: usize, batch: &BatchShape) -> Result<CostEstimate, Error> {
+        // The evaluations are arranged in a square matrix, each of whose rows
+        // is committed to with the whole `com_key`
+        let n = ark_std::log2(poly_len) as usize;
+        let dim = 1 << (n / 2);
+        if n % 2 == 1 || dim != self.com_key.len() {
+            return Err(Error::InvalidNumberOfVariables);
+        }
+        let g_bytes = compressed_size(&self.h);
+        let f_bytes = compressed_size(&G::ScalarField::zero());
+        let proof_bytes = 3 * g_bytes + vec_bytes(dim, f_bytes) + 2 * f_bytes;
+        let num_openings = batch.num_polys * batch.num_points;
+
+        Ok(CostEstimate {
+            commitment_bytes: vec_bytes(dim, g_bytes),
+            proof_bytes: vec_bytes(batch.num_points, vec_bytes(batch.num_polys, proof_bytes)),
+            prover: ProverCost {
+                // One commitment per row, then the commitments to the
+                // evaluation, to `d` and to `b` for each opening.
+                msms: [
+                    repeat(dim, dim * batch.num_polys),
+                    [2, dim + 1, 2].repeat(num_openings),
+                ]
+                .concat(),
+                encodings: Vec::new(),
+                // The public parameters are hashed into the transcript.
+                hashes: num_openings,
+            },
+            verifier: VerifierCost {
+                pairings: 0,
+                msms: [2, dim, dim + 1, 2].repeat(num_openings),
+                encodings: Vec::new(),
+                hashes: num_openings,
+            },
+        })
+    }
+}
+
+impl<G: AffineRepr> SecurityEstimator for HyraxVerifierKey<G> {
+    fn estimate_security(
+        &self,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<SecurityEstimate, Error> {
+        // The evaluations are arranged in a square matrix, whose rows are
+        // committed to with `com_key`
+        let n = ark_std::log2(poly_len) as usize;
+        if n % 2 == 1 || 1 << (n / 2) > self.com_key.len() {
+            return Err(Error::InvalidNumberOfVariables);
+        }
+        Ok(hyrax_security::<G::ScalarField>(assumptions))
+    }
+}
+
+/// The security of Hyrax, which does not depend on the dimensions: the only
+/// challenge is the one of the sigma protocol proving the evaluation, which
+/// has special soundness.
+pub(crate) fn hyrax_security<F: PrimeField>(assumptions: &SecurityAssumptions) -> SecurityEstimate {
+    SecurityEstimate {
+        statistical_bits: statistical_bits(-field_bits::<F>(), assumptions),
+        computational_bits: Some(dlog_bits::<F>()),
+    }
+}
+
 /// Hyrax commitment to a polynomial consisting of one multi-commit per row of
 /// the coefficient matrix
-#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize, Absorb)]
+#[derive(Derivative, CanonicalSerialize, Absorb)]
 #[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
 pub struct HyraxCommitment<G: AffineRepr + Absorb> {
     /// A list of multi-commits to each row of the matrix representing the
@@ -64,6 +139,32 @@ pub struct HyraxCommitment<G: AffineRepr + Absorb> {
     pub row_coms: Vec<G>,
 }
 
+impl<G: AffineRepr + Absorb> Valid for HyraxCommitment<G> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.row_coms.check()?;
+        // The coefficient matrix is square, with a power of two rows
+        if !self.row_coms.len().is_power_of_two() {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(())
+    }
+}
+
+impl<G: AffineRepr + Absorb> CanonicalDeserialize for HyraxCommitment<G> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let row_coms = deserialize_vec(reader, compress)?;
+        let result = Self { row_coms };
+        if let Validate::Yes = validate {
+            result.check()?;
+        }
+        Ok(result)
+    }
+}
+
 impl<G: AffineRepr + Absorb> PCCommitment for HyraxCommitment<G> {
     #[inline]
     fn empty() -> Self {
@@ -113,7 +214,7 @@ impl<F: PrimeField> PCCommitmentState for HyraxCommitmentState<F> {
 
 /// Proof of a Hyrax opening, containing various commitments
 /// and auxiliary values generated randomly during the opening
-#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Derivative, CanonicalSerialize)]
 #[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
 pub struct HyraxProof<G: AffineRepr> {
     /// Commitment to the evaluation of the polynomial at the requested point
@@ -129,3 +230,85 @@ pub struct HyraxProof<G: AffineRepr> {
     /// Auxiliary random scalar
     pub z_b: G::ScalarField,
 }
+
+impl<G: AffineRepr> Valid for HyraxProof<G> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.com_eval.check()?;
+        self.com_d.check()?;
+        self.com_b.check()?;
+        self.z.check()?;
+        self.z_d.check()?;
+        self.z_b.check()?;
+        // `z` has one entry per column of the coefficient matrix
+        if !self.z.len().is_power_of_two() {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(())
+    }
+}
+
+impl<G: AffineRepr> CanonicalDeserialize for HyraxProof<G> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let com_eval = G::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let com_d = G::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let com_b = G::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let z = deserialize_vec(&mut reader, compress)?;
+        let z_d = G::ScalarField::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let z_b = G::ScalarField::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let result = Self {
+            com_eval,
+            com_d,
+            com_b,
+            z,
+            z_d,
+            z_b,
+        };
+        if let Validate::Yes = validate {
+            result.check()?;
+        }
+        Ok(result)
+    }
+}
+
+/// The proofs of the openings of a batch of polynomials, one per polynomial.
+#[derive(Derivative, CanonicalSerialize)]
+#[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
+pub struct HyraxProofArray<G: AffineRepr>(pub Vec<HyraxProof<G>>);
+
+impl<G: AffineRepr> core::ops::Deref for HyraxProofArray<G> {
+    type Target = Vec<HyraxProof<G>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<G: AffineRepr> core::ops::DerefMut for HyraxProofArray<G> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<G: AffineRepr> Valid for HyraxProofArray<G> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.0.check()
+    }
+}
+
+impl<G: AffineRepr> CanonicalDeserialize for HyraxProofArray<G> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let result = Self(deserialize_vec(reader, compress)?);
+        if let Validate::Yes = validate {
+            result.check()?;
+        }
+        Ok(result)
+    }
+}
@@ -1,15 +1,23 @@
 use crate::hyrax::utils::tensor_prime;
 use crate::utils::{inner_product, scalar_by_vector, vector_sum, Matrix};
 use crate::{
-    hyrax::utils::flat_to_matrix_column_major, Error, LabeledCommitment, LabeledPolynomial,
-    PolynomialCommitment,
+    hidden::{pair_evaluation_commitments, HiddenEvaluations},
+    homomorphic::{common_degree_bound, CombinationTerm, HomomorphicCommitment},
+    hyrax::utils::flat_to_matrix_column_major,
+    security::SecurityAssumptions,
+    tensor::{Query, Tensor, TensorOpenings},
+    update::{merge_deltas, UpdatableCommitment},
+    Error, LabeledCommitment, LabeledPolynomial, PolynomialCommitment, PolynomialLabel, Transcript,
+    VerificationFailure,
 };
-use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge, FieldElementSize};
 use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
-use ark_ff::PrimeField;
+use ark_ff::{PrimeField, Zero};
 use ark_poly::MultilinearExtension;
-use ark_serialize::serialize_to_vec;
-use ark_std::{marker::PhantomData, rand::RngCore, string::ToString, vec::Vec, UniformRand};
+use ark_std::{
+    collections::BTreeMap, format, marker::PhantomData, rand::RngCore, string::ToString, vec::Vec,
+    UniformRand,
+};
 use blake2::Blake2s256;
 use digest::Digest;
 
@@ -91,6 +99,26 @@ where
         // Multi-exponentiation in the group of points of the EC
         <G::Group as VariableBaseMSM>::msm_bigint(&key, &scalars_bigint)
     }
+
+    /// Suggest the `num_vars` to pass to `setup` in order to commit to
+    /// polynomials with `poly_len` evaluations with at least `target_bits`
+    /// of security under `assumptions`. This is the smallest even number of
+    /// variables that fits `poly_len` evaluations.
+    ///
+    /// Returns an error if the group is too small to reach `target_bits`.
+    pub fn suggest_num_vars(
+        target_bits: f64,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<usize, Error> {
+        if hyrax_security::<G::ScalarField>(assumptions).bits() < target_bits {
+            return Err(Error::InvalidParameters(
+                "The target security cannot be reached in this group".to_string(),
+            ));
+        }
+        let n = ark_std::log2(poly_len) as usize;
+        Ok(n + n % 2)
+    }
 }
 
 impl<G, P> PolynomialCommitment<G::ScalarField, P> for HyraxPC<G, P>
@@ -104,7 +132,7 @@ where
     type VerifierKey = HyraxVerifierKey<G>;
     type Commitment = HyraxCommitment<G>;
     type CommitmentState = HyraxCommitmentState<G::ScalarField>;
-    type Proof = Vec<HyraxProof<G>>;
+    type Proof = HyraxProofArray<G>;
     type BatchProof = Vec<Self::Proof>;
     type Error = Error;
 
@@ -121,6 +149,7 @@ where
         num_vars: Option<usize>,
         _rng: &mut R,
     ) -> Result<Self::UniversalParams, Self::Error> {
+        phase_span!("HyraxPC::setup", num_vars = ?num_vars);
         if num_vars.is_none() {
             return Err(Error::InvalidNumberOfVariables);
         }
@@ -179,6 +208,7 @@ where
         _supported_hiding_bound: usize,
         _enforced_degree_bounds: Option<&[usize]>,
     ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
+        phase_span!("HyraxPC::trim");
         Ok((pp.clone(), pp.clone()))
     }
 
@@ -204,10 +234,10 @@ where
     where
         P: 'a,
     {
+        phase_span!("HyraxPC::commit");
         let mut coms = Vec::new();
         let mut states = Vec::new();
 
-        #[cfg(not(feature = "parallel"))]
         let rng_inner = rng.expect("Committing to polynomials requires a random generator");
 
         for l_poly in polynomials {
@@ -216,6 +246,7 @@ where
 
             let n = poly.num_vars();
             let dim = 1 << n / 2;
+            phase_span!("commit_polynomial", label = %label, num_vars = n);
 
             if n % 2 == 1 {
                 // Only polynomials with an even number of variables are
@@ -229,17 +260,22 @@ where
 
             let m = flat_to_matrix_column_major(&poly.to_evaluations(), dim, dim);
 
+            // The randomness is sampled sequentially from `rng`, so that the
+            // commitments only depend on it, even when computed in parallel
+            let com_rands: Vec<G::ScalarField> =
+                (0..dim).map(|_| G::ScalarField::rand(rng_inner)).collect();
+
             // Commiting to the matrix with one multi-commitment per row
-            let (row_coms, com_rands): (Vec<_>, Vec<_>) = cfg_iter!(m)
-                .map(|row| {
-                    #[cfg(not(feature = "parallel"))]
-                    let r = G::ScalarField::rand(rng_inner);
-                    #[cfg(feature = "parallel")]
-                    let r = G::ScalarField::rand(&mut rand::thread_rng());
-                    let c = (Self::pedersen_commit(&ck.com_key, row) + ck.h * r).into();
-                    (c, r)
-                })
-                .unzip();
+            let row_coms: Vec<G> = cfg_iter!(m)
+                .zip(cfg_iter!(com_rands))
+                .map(|(row, r)| (Self::pedersen_commit(&ck.com_key, row) + ck.h * r).into())
+                .collect();
+            // The MSMs are counted here, in the span of the commitment,
+            // rather than in the threads which compute them.
+            #[cfg(feature = "tracing")]
+            for row in &m {
+                count!(MSM, row.len());
+            }
 
             let com = HyraxCommitment { row_coms };
             let l_comm = LabeledCommitment::new(label.to_string(), com, Some(1));
@@ -284,32 +320,140 @@ where
         Self::CommitmentState: 'a,
         P: 'a,
     {
-        let n = point.len();
+        phase_span!("HyraxPC::open");
+        Self::open_with_evaluation_randomness(
+            ck,
+            labeled_polynomials,
+            commitments,
+            Query::Point(point),
+            sponge,
+            states,
+            rng,
+            false,
+        )
+        .map(|(proofs, _)| proofs)
+    }
 
-        if n % 2 == 1 {
-            // Only polynomials with an even number of variables are
-            // supported in this implementation
-            return Err(Error::InvalidNumberOfVariables);
-        }
+    /// Verifies a list of opening proofs and confirms the evaluation of the
+    /// committed polynomials at the desired point.
+    ///
+    /// # Panics
+    /// - If the point doesn't have an even number of variables.
+    /// - If the length of a commitment does not correspond to the length of the
+    /// point (specifically, commitment length should be 2^(point-length/2)).
+    ///
+    /// # Disregarded arguments
+    /// - `rng`
+    fn check<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        values: impl IntoIterator<Item = G::ScalarField>,
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a,
+    {
+        phase_span!("HyraxPC::check");
+        Self::check_values(vk, commitments, Query::Point(point), values, proof, sponge)
+    }
+}
 
-        let dim = 1 << n / 2;
+impl<G, P> HyraxPC<G, P>
+where
+    G: AffineRepr + Absorb,
+    G::ScalarField: Absorb,
+    P: MultilinearExtension<G::ScalarField>,
+{
+    /// Outputs the number of variables `n` of the polynomials which can be
+    /// opened at `query`, and the vectors `(l, r)` which multiply their
+    /// coefficient matrices to answer it.
+    #[allow(clippy::type_complexity)]
+    fn query_tensors(
+        query: &Query<'_, G::ScalarField, P::Point>,
+    ) -> Result<(usize, Vec<G::ScalarField>, Vec<G::ScalarField>), Error> {
+        match query {
+            Query::Point(point) => {
+                let n = point.len();
+
+                if n % 2 == 1 {
+                    // Only polynomials with an even number of variables are
+                    // supported in this implementation
+                    return Err(Error::InvalidNumberOfVariables);
+                }
+
+                // Reversing the point is necessary because the MLE interface returns
+                // evaluations in little-endian order
+                let point_rev: Vec<G::ScalarField> = point.iter().rev().cloned().collect();
 
-        // Reversing the point is necessary because the MLE interface returns
-        // evaluations in little-endian order
-        let point_rev: Vec<G::ScalarField> = point.iter().rev().cloned().collect();
+                let point_lower = &point_rev[n / 2..];
+                let point_upper = &point_rev[..n / 2];
 
-        let point_lower = &point_rev[n / 2..];
-        let point_upper = &point_rev[..n / 2];
+                // Deriving the tensors which result in the evaluation of the polynomial
+                // when they are multiplied by the coefficient matrix.
+                let l = tensor_prime(point_lower);
+                let r = tensor_prime(point_upper);
+                Ok((n, l, r))
+            }
+            Query::Tensor(tensor) => {
+                // The coefficient matrices are square, with `l` applied to the
+                // low half of the variables
+                let dim = tensor.a.len();
+                tensor.check_dimensions(dim, dim)?;
+                if !dim.is_power_of_two() {
+                    return Err(Error::InvalidNumberOfVariables);
+                }
+                Ok((
+                    2 * dim.trailing_zeros() as usize,
+                    tensor.a.clone(),
+                    tensor.b.clone(),
+                ))
+            }
+        }
+    }
+
+    fn absorb_query(
+        sponge: &mut impl CryptographicSponge,
+        query: &Query<'_, G::ScalarField, P::Point>,
+    ) {
+        match query {
+            Query::Point(point) => sponge.absorb_labelled(b"point", *point),
+            Query::Tensor(tensor) => tensor.absorb(sponge),
+        }
+    }
 
-        // Deriving the tensors which result in the evaluation of the polynomial
-        // when they are multiplied by the coefficient matrix.
-        let l = tensor_prime(point_lower);
-        let r = tensor_prime(point_upper);
+    /// Opens the commitments as in `PolynomialCommitment::open`, committing
+    /// to the evaluations with randomness sampled from `rng` if
+    /// `hide_evaluations` is set, and with no randomness otherwise. Also
+    /// outputs the randomness of each evaluation commitment.
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    fn open_with_evaluation_randomness<'a>(
+        ck: &HyraxCommitterKey<G>,
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<G::ScalarField, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<HyraxCommitment<G>>>,
+        query: Query<'_, G::ScalarField, P::Point>,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a HyraxCommitmentState<G::ScalarField>>,
+        rng: Option<&mut dyn RngCore>,
+        hide_evaluations: bool,
+    ) -> Result<(HyraxProofArray<G>, Vec<G::ScalarField>), Error>
+    where
+        P: 'a,
+    {
+        let (n, l, r) = Self::query_tensors(&query)?;
+        let dim = l.len();
 
         let mut proofs = Vec::new();
+        let mut evaluation_randomness = Vec::new();
 
         let rng_inner = rng.expect("Opening polynomials requires randomness");
 
+        // Binding the transcript to the scheme and its public parameters, once
+        // for all the commitments
+        sponge.bind_context(PROTOCOL_NAME, ck)?;
+
         for (l_poly, (l_com, state)) in labeled_polynomials
             .into_iter()
             .zip(commitments.into_iter().zip(states.into_iter()))
@@ -332,17 +476,11 @@ where
                 });
             }
 
-            // Absorbing public parameters
-            sponge.absorb(
-                &Blake2s256::digest(serialize_to_vec!(*ck).map_err(|_| Error::TranscriptError)?)
-                    .as_slice(),
-            );
-
             // Absorbing the commitment to the polynomial
-            sponge.absorb(&serialize_to_vec!(com.row_coms).map_err(|_| Error::TranscriptError)?);
+            sponge.absorb_serialized(b"row commitments", &com.row_coms)?;
 
-            // Absorbing the point
-            sponge.absorb(point);
+            // Absorbing the query
+            Self::absorb_query(sponge, &query);
 
             // Commiting to the matrix formed by the polynomial coefficients
             let t = &state.mat;
@@ -358,11 +496,14 @@ where
 
             let eval = inner_product(&lt, &r);
 
-            // Singleton commit
-            let (com_eval, r_eval) = {
-                let r = G::ScalarField::rand(rng_inner);
-                ((ck.com_key[0] * eval + ck.h * r).into(), r)
+            // Singleton commit, without blinding if the evaluation is
+            // revealed to the verifier, who checks it against `com_eval`
+            let r_eval = if hide_evaluations {
+                G::ScalarField::rand(rng_inner)
+            } else {
+                G::ScalarField::zero()
             };
+            let com_eval: G = (ck.com_key[0] * eval + ck.h * r_eval).into();
 
             // ******** Dot product argument ********
             // Appendix A.2 in the reference article
@@ -375,21 +516,23 @@ where
             // Multi-commit
             let r_d = G::ScalarField::rand(rng_inner);
             let com_d = (Self::pedersen_commit(&ck.com_key, &d) + ck.h * r_d).into();
+            count!(MSM, d.len());
 
             // Singleton commit
             let r_b = G::ScalarField::rand(rng_inner);
             let com_b = (ck.com_key[0] * b + ck.h * r_b).into();
 
             // Absorbing the commitment to the evaluation
-            sponge.absorb(&serialize_to_vec!(com_eval).map_err(|_| Error::TranscriptError)?);
+            sponge.absorb_serialized(b"evaluation commitment", &com_eval)?;
 
             // Absorbing the two auxiliary commitments
-            sponge.absorb(&serialize_to_vec!(com_d).map_err(|_| Error::TranscriptError)?);
-            sponge.absorb(&serialize_to_vec!(com_b).map_err(|_| Error::TranscriptError)?);
+            sponge.absorb_serialized(b"d commitment", &com_d)?;
+            sponge.absorb_serialized(b"b commitment", &com_b)?;
 
             // Receive the random challenge c from the verifier, i.e. squeeze
             // it from the transcript.
-            let c = sponge.squeeze_field_elements(1)[0];
+            let c =
+                sponge.squeeze_labelled_field_elements(b"challenge", &[FieldElementSize::Full])[0];
 
             let z = vector_sum(&d, &scalar_by_vector(c, &lt));
             let z_d = c * r_lt + r_d;
@@ -403,55 +546,76 @@ where
                 z_d,
                 z_b,
             });
+            evaluation_randomness.push(r_eval);
         }
 
-        Ok(proofs)
+        Ok((HyraxProofArray(proofs), evaluation_randomness))
     }
 
-    /// Verifies a list of opening proofs and confirms the evaluation of the
-    /// committed polynomials at the desired point.
-    ///
-    /// # Panics
-    /// - If the point doesn't have an even number of variables.
-    /// - If the length of a commitment does not correspond to the length of the
-    /// point (specifically, commitment length should be 2^(point-length/2)).
-    ///
-    /// # Disregarded arguments
-    /// - `rng`
-    fn check<'a>(
-        vk: &Self::VerifierKey,
-        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
-        point: &'a P::Point,
-        _values: impl IntoIterator<Item = G::ScalarField>,
-        proof: &Self::Proof,
+    /// Verifies the proofs as in `PolynomialCommitment::check`, for the
+    /// answers `values` to `query`.
+    fn check_values<'a>(
+        vk: &HyraxVerifierKey<G>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<HyraxCommitment<G>>>,
+        query: Query<'_, G::ScalarField, P::Point>,
+        values: impl IntoIterator<Item = G::ScalarField>,
+        proof: &[HyraxProof<G>],
         sponge: &mut impl CryptographicSponge,
-        _rng: Option<&mut dyn RngCore>,
-    ) -> Result<bool, Self::Error>
-    where
-        Self::Commitment: 'a,
-    {
-        let n = point.len();
+    ) -> Result<(), Error> {
+        let mut values = values.into_iter();
+        Self::check_with(vk, commitments, query, proof, sponge, |com, com_eval| {
+            // The evaluation is committed to without blinding
+            let value = values.next().ok_or(Error::IncorrectInputLength(
+                "Fewer values than commitments were provided".to_string(),
+            ))?;
+            if (vk.com_key[0] * value).into() != *com_eval {
+                return Err(VerificationFailure::EvaluationMismatch {
+                    label: com.label().clone(),
+                    point: query.describe(),
+                }
+                .into());
+            }
+            Ok(())
+        })
+    }
 
-        if n % 2 == 1 {
-            // Only polynomials with an even number of variables are
-            // supported in this implementation
-            return Err(Error::InvalidNumberOfVariables);
+    /// Verifies the proofs as in `PolynomialCommitment::check`, except that
+    /// the evaluation commitment of each proof is checked by
+    /// `check_com_eval`.
+    fn check_with<'a>(
+        vk: &HyraxVerifierKey<G>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<HyraxCommitment<G>>>,
+        query: Query<'_, G::ScalarField, P::Point>,
+        proof: &[HyraxProof<G>],
+        sponge: &mut impl CryptographicSponge,
+        mut check_com_eval: impl FnMut(&LabeledCommitment<HyraxCommitment<G>>, &G) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let (n, l, r) = Self::query_tensors(&query)?;
+
+        // The key commits to rows of `dim` evaluations, which must be those of
+        // a polynomial in `n` variables
+        let dim = vk.com_key.len();
+        if 1usize.checked_shl((n / 2) as u32) != Some(dim) {
+            return Err(Error::MismatchedNumVars {
+                poly_nv: 2 * dim.trailing_zeros() as usize,
+                point_nv: n,
+            });
         }
 
-        // Reversing the point is necessary because the MLE interface returns
-        // evaluations in little-endian order
-        let point_rev: Vec<G::ScalarField> = point.iter().rev().cloned().collect();
-
-        let point_lower = &point_rev[n / 2..];
-        let point_upper = &point_rev[..n / 2];
+        // Binding the transcript to the scheme and its public parameters, once
+        // for all the commitments
+        sponge.bind_context(PROTOCOL_NAME, vk)?;
 
-        // Deriving the tensors which result in the evaluation of the polynomial
-        // when they are multiplied by the coefficient matrix.
-        let l = tensor_prime(point_lower);
-        let r = tensor_prime(point_upper);
-
-        for (com, h_proof) in commitments.into_iter().zip(proof.iter()) {
+        let mut num_commitments = 0;
+        for (i, com) in commitments.into_iter().enumerate() {
+            num_commitments += 1;
             let row_coms = &com.commitment().row_coms;
+            let h_proof = proof
+                .get(i)
+                .ok_or_else(|| VerificationFailure::MalformedProof {
+                    label: Some(com.label().clone()),
+                    reason: "missing proof".to_string(),
+                })?;
 
             // extract each field from h_proof
             let HyraxProof {
@@ -463,41 +627,50 @@ where
                 z_b,
             } = h_proof;
 
-            if row_coms.len() != 1 << n / 2 {
+            if row_coms.len() != dim {
                 return Err(Error::IncorrectCommitmentSize {
                     encountered: row_coms.len(),
-                    expected: 1 << n / 2,
+                    expected: dim,
                 });
             }
 
-            // Absorbing public parameters
-            sponge.absorb(
-                &Blake2s256::digest(serialize_to_vec!(*vk).map_err(|_| Error::TranscriptError)?)
-                    .as_slice(),
-            );
+            if z.len() != dim {
+                return Err(VerificationFailure::MalformedProof {
+                    label: Some(com.label().clone()),
+                    reason: format!("expected a vector z of length {}, found {}", dim, z.len()),
+                }
+                .into());
+            }
+
+            check_com_eval(com, com_eval)?;
 
             // Absorbing the commitment to the polynomial
-            sponge.absorb(&serialize_to_vec!(*row_coms).map_err(|_| Error::TranscriptError)?);
+            sponge.absorb_serialized(b"row commitments", row_coms)?;
 
-            // Absorbing the point
-            sponge.absorb(point);
+            // Absorbing the query
+            Self::absorb_query(sponge, &query);
 
             // Absorbing the commitment to the evaluation
-            sponge.absorb(&serialize_to_vec!(*com_eval).map_err(|_| Error::TranscriptError)?);
+            sponge.absorb_serialized(b"evaluation commitment", com_eval)?;
 
             // Absorbing the two auxiliary commitments
-            sponge.absorb(&serialize_to_vec!(*com_d).map_err(|_| Error::TranscriptError)?);
-            sponge.absorb(&serialize_to_vec!(*com_b).map_err(|_| Error::TranscriptError)?);
+            sponge.absorb_serialized(b"d commitment", com_d)?;
+            sponge.absorb_serialized(b"b commitment", com_b)?;
 
             // Receive the random challenge c from the verifier, i.e. squeeze
             // it from the transcript.
-            let c: G::ScalarField = sponge.squeeze_field_elements(1)[0];
+            let c: G::ScalarField =
+                sponge.squeeze_labelled_field_elements(b"challenge", &[FieldElementSize::Full])[0];
 
             // Second check from the paper (figure 6, equation (14))
             // Moved here for potential early return
             let com_dp = (vk.com_key[0] * inner_product(&r, z) + vk.h * z_b).into();
+            let failure = || VerificationFailure::InnerProduct {
+                label: Some(com.label().clone()),
+                point: Some(query.describe()),
+            };
             if com_dp != (com_eval.mul(c) + com_b).into() {
-                return Ok(false);
+                return Err(failure().into());
             }
 
             // Computing t_prime with a multi-exponentiation
@@ -505,14 +678,316 @@ where
                 .map(|chi| chi.into_bigint())
                 .collect::<Vec<_>>();
             let t_prime: G = <G::Group as VariableBaseMSM>::msm_bigint(&row_coms, &l_bigint).into();
+            count!(MSM, l_bigint.len());
 
             // First check from the paper (figure 6, equation (13))
             let com_z_zd = (Self::pedersen_commit(&vk.com_key, z) + vk.h * z_d).into();
+            count!(MSM, z.len());
             if com_z_zd != (t_prime.mul(c) + com_d).into() {
-                return Ok(false);
+                return Err(failure().into());
+            }
+        }
+
+        if proof.len() != num_commitments {
+            return Err(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!(
+                    "expected {} proofs, one per commitment, found {}",
+                    num_commitments,
+                    proof.len()
+                ),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+impl<G, P> HiddenEvaluations<G::ScalarField, P> for HyraxPC<G, P>
+where
+    G: AffineRepr + Absorb,
+    G::ScalarField: Absorb,
+    P: MultilinearExtension<G::ScalarField>,
+{
+    type EvaluationCommitment = G;
+
+    /// Evaluations are committed to as `value G_0 + randomness H`, which is
+    /// the commitment `com_eval` of the proofs.
+    fn commit_evaluation(
+        vk: &Self::VerifierKey,
+        value: G::ScalarField,
+        randomness: G::ScalarField,
+    ) -> Self::EvaluationCommitment {
+        (vk.com_key[0] * value + vk.h * randomness).into()
+    }
+
+    /// The evaluation commitments are the commitments `com_eval` of the
+    /// proofs, which are blinded instead of being revealed.
+    fn open_hidden<'a>(
+        ck: &Self::CommitterKey,
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<G::ScalarField, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Self::Proof,
+            Vec<(Self::EvaluationCommitment, G::ScalarField)>,
+        ),
+        Self::Error,
+    >
+    where
+        P: 'a,
+        Self::CommitmentState: 'a,
+        Self::Commitment: 'a,
+    {
+        if rng.is_none() {
+            return Err(Error::MissingRng);
+        }
+        let (proofs, randomness) = Self::open_with_evaluation_randomness(
+            ck,
+            labeled_polynomials,
+            commitments,
+            Query::Point(point),
+            sponge,
+            states,
+            rng,
+            true,
+        )?;
+        let evaluations = proofs
+            .iter()
+            .zip(randomness)
+            .map(|(proof, r)| (proof.com_eval, r))
+            .collect();
+        Ok((proofs, evaluations))
+    }
+
+    fn check_hidden<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        evaluation_commitments: impl IntoIterator<Item = &'a Self::EvaluationCommitment>,
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a,
+        Self::EvaluationCommitment: 'a,
+    {
+        let pairs = pair_evaluation_commitments(commitments, evaluation_commitments)?;
+        let mut evaluation_commitments = pairs.iter().map(|(_, c)| *c);
+        let query = Query::<G::ScalarField, _>::Point(point);
+        Self::check_with(
+            vk,
+            pairs.iter().map(|(c, _)| *c),
+            Query::Point(point),
+            proof,
+            sponge,
+            |com, com_eval| {
+                if evaluation_commitments.next() != Some(com_eval) {
+                    return Err(VerificationFailure::EvaluationMismatch {
+                        label: com.label().clone(),
+                        point: query.describe(),
+                    }
+                    .into());
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+impl<G, P> TensorOpenings<G::ScalarField, P> for HyraxPC<G, P>
+where
+    G: AffineRepr + Absorb,
+    G::ScalarField: Absorb,
+    P: MultilinearExtension<G::ScalarField>,
+{
+    /// The tensors have one entry per row and column of the square matrix of
+    /// evaluations, with `a` applied to the low half of the variables.
+    fn tensor_dimensions(commitment: &Self::Commitment) -> (usize, usize) {
+        (commitment.row_coms.len(), commitment.row_coms.len())
+    }
+
+    fn open_tensor<'a>(
+        ck: &Self::CommitterKey,
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<G::ScalarField, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        tensor: &Tensor<G::ScalarField>,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Self::Error>
+    where
+        P: 'a,
+        Self::CommitmentState: 'a,
+        Self::Commitment: 'a,
+    {
+        phase_span!("HyraxPC::open_tensor");
+        Self::open_with_evaluation_randomness(
+            ck,
+            labeled_polynomials,
+            commitments,
+            Query::Tensor(tensor),
+            sponge,
+            states,
+            rng,
+            false,
+        )
+        .map(|(proofs, _)| proofs)
+    }
+
+    fn check_tensor<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        tensor: &Tensor<G::ScalarField>,
+        values: impl IntoIterator<Item = G::ScalarField>,
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a,
+    {
+        phase_span!("HyraxPC::check_tensor");
+        Self::check_values(
+            vk,
+            commitments,
+            Query::Tensor(tensor),
+            values,
+            proof,
+            sponge,
+        )
+    }
+}
+
+impl<G, P> HomomorphicCommitment<G::ScalarField, P> for HyraxPC<G, P>
+where
+    G: AffineRepr + Absorb,
+    G::ScalarField: Absorb,
+    P: MultilinearExtension<G::ScalarField>,
+{
+    fn linear_combination(
+        label: PolynomialLabel,
+        terms: &[CombinationTerm<G::ScalarField, Self::Commitment, Self::CommitmentState>],
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error> {
+        let degree_bound = common_degree_bound(&label, terms)?;
+        let dim = terms[0].2.mat.n;
+        for (_, c, s) in terms {
+            let row_coms = &c.commitment().row_coms;
+            if row_coms.len() != dim || s.mat.n != dim || s.randomness.len() != dim {
+                return Err(Error::IncorrectCommitmentSize {
+                    encountered: row_coms.len(),
+                    expected: dim,
+                });
+            }
+        }
+
+        let mut row_coms = vec![G::Group::zero(); dim];
+        let mut randomness = vec![G::ScalarField::zero(); dim];
+        let mut rows = vec![vec![G::ScalarField::zero(); terms[0].2.mat.m]; dim];
+        for (coeff, c, s) in terms {
+            let state_rows = s.mat.rows();
+            for j in 0..dim {
+                row_coms[j] += c.commitment().row_coms[j] * coeff;
+                randomness[j] += s.randomness[j] * coeff;
+                for (entry, e) in rows[j].iter_mut().zip(&state_rows[j]) {
+                    *entry += *e * coeff;
+                }
+            }
+        }
+
+        let com = HyraxCommitment {
+            row_coms: G::Group::normalize_batch(&row_coms),
+        };
+        let state = HyraxCommitmentState {
+            randomness,
+            mat: Matrix::new_from_rows(rows),
+        };
+        Ok((LabeledCommitment::new(label, com, degree_bound), state))
+    }
+}
+
+impl<G, P> UpdatableCommitment<G::ScalarField, P> for HyraxPC<G, P>
+where
+    G: AffineRepr + Absorb,
+    G::ScalarField: Absorb,
+    P: MultilinearExtension<G::ScalarField>,
+{
+    /// Updates the row commitments touched by `deltas`. Since Hyrax
+    /// commitments are always hiding, every row is re-randomised, touched or
+    /// not, so that the updated commitment does not reveal which rows of the
+    /// evaluation matrix have changed, and `rng` is required.
+    fn update_commitment(
+        ck: &Self::CommitterKey,
+        commitment: &LabeledCommitment<Self::Commitment>,
+        state: &Self::CommitmentState,
+        deltas: &[(usize, G::ScalarField)],
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error> {
+        let rng = rng.ok_or(Error::MissingRng)?;
+        let deltas = merge_deltas(deltas);
+        let label = commitment.label();
+
+        let dim = state.mat.n;
+        let row_coms = &commitment.commitment().row_coms;
+        if dim > ck.com_key.len() {
+            return Err(Error::InvalidNumberOfVariables);
+        }
+        if row_coms.len() != dim {
+            return Err(Error::IncorrectCommitmentSize {
+                encountered: row_coms.len(),
+                expected: dim,
+            });
+        }
+        if let Some((index, _)) = deltas.last().filter(|(i, _)| *i >= dim * dim) {
+            return Err(Error::InvalidIndex {
+                index: *index,
+                len: dim * dim,
+            });
+        }
+
+        // The evaluation matrix is stored column-major, so that the index
+        // `i` of the evaluations is in row `i % dim` and column `i / dim`.
+        let mut row_deltas = BTreeMap::new();
+        for (i, d) in deltas {
+            row_deltas
+                .entry(i % dim)
+                .or_insert_with(Vec::new)
+                .push((i / dim, d));
+        }
+
+        let mut rows = state.mat.rows();
+        let mut randomness = state.randomness.clone();
+        let mut new_row_coms = Vec::with_capacity(dim);
+        for (row, row_com) in row_coms.iter().enumerate() {
+            let r = G::ScalarField::rand(rng);
+            let mut new_row_com = ck.h * r + *row_com;
+            if let Some(deltas) = row_deltas.get(&row) {
+                let (bases, scalars): (Vec<_>, Vec<_>) =
+                    deltas.iter().map(|(col, d)| (ck.com_key[*col], *d)).unzip();
+                new_row_com += Self::pedersen_commit(&bases, &scalars);
+                for (col, d) in deltas {
+                    rows[row][*col] += d;
+                }
             }
+            randomness[row] += r;
+            new_row_coms.push(new_row_com);
         }
 
-        Ok(true)
+        let com = HyraxCommitment {
+            row_coms: G::Group::normalize_batch(&new_row_coms),
+        };
+        let state = HyraxCommitmentState {
+            randomness,
+            mat: Matrix::new_from_rows(rows),
+        };
+        Ok((
+            LabeledCommitment::new(label.to_string(), com, commitment.degree_bound()),
+            state,
+        ))
     }
 }
@@ -1,7 +1,7 @@
 use crate::test_types::test_sponge;
 use crate::tests::*;
-use crate::LabeledPolynomial;
-use crate::{hyrax::HyraxPC, PolynomialCommitment};
+use crate::{hyrax::HyraxPC, update::UpdatableCommitment, PolynomialCommitment};
+use crate::{Error, LabeledPolynomial, VerificationFailure};
 use ark_bls12_377::G1Affine;
 use ark_ec::AffineRepr;
 use ark_ed_on_bls12_381::EdwardsAffine;
@@ -45,6 +45,17 @@ fn constant_poly<Fr: PrimeField>(
     }
 }
 
+fn apply_evaluation_deltas<F: PrimeField>(
+    p: &DenseMultilinearExtension<F>,
+    deltas: &[(usize, F)],
+) -> DenseMultilinearExtension<F> {
+    let mut evals = p.to_evaluations();
+    for (i, d) in deltas {
+        evals[*i] += d;
+    }
+    DenseMultilinearExtension::from_evaluations_vec(p.num_vars(), evals)
+}
+
 fn rand_point<F: PrimeField>(num_vars: Option<usize>, rng: &mut ChaCha20Rng) -> Vec<F> {
     match num_vars {
         Some(n) => (0..n).map(|_| F::rand(rng)).collect(),
@@ -91,7 +102,7 @@ fn test_hyrax_construction() {
     )
     .unwrap();
 
-    assert!(Hyrax381::check(
+    Hyrax381::check(
         &vk,
         &c,
         &point,
@@ -100,7 +111,81 @@ fn test_hyrax_construction() {
         &mut test_sponge,
         Some(chacha),
     )
-    .unwrap());
+    .unwrap();
+}
+
+#[test]
+fn test_hyrax_forged_evaluation() {
+    let n = 4;
+
+    let chacha = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+
+    let pp = Hyrax381::setup(1, Some(n), chacha).unwrap();
+
+    let (ck, vk) = Hyrax381::trim(&pp, 1, 1, None).unwrap();
+
+    let l_poly = LabeledPolynomial::new(
+        "test_poly".to_string(),
+        rand_poly::<Fr>(0, Some(n), chacha),
+        None,
+        None,
+    );
+
+    let (c, rands) = Hyrax381::commit(&ck, &[l_poly.clone()], Some(chacha)).unwrap();
+
+    let point: Vec<Fr> = rand_point(Some(n), chacha);
+    let forged_value = l_poly.evaluate(&point) + Fr::from(1u64);
+
+    let test_sponge = test_sponge::<Fr>();
+
+    let proof = Hyrax381::open(
+        &ck,
+        &[l_poly],
+        &c,
+        &point,
+        &mut (test_sponge.clone()),
+        &rands,
+        Some(chacha),
+    )
+    .unwrap();
+
+    // An honest proof used to verify for any claimed value, since the
+    // verifier never looked at the values. A wrong evaluation is reported
+    // against the label of the commitment.
+    let result = Hyrax381::check(
+        &vk,
+        &c,
+        &point,
+        [forged_value],
+        &proof,
+        &mut (test_sponge.clone()),
+        Some(chacha),
+    );
+    assert!(matches!(
+        result,
+        Err(Error::VerificationFailed(VerificationFailure::EvaluationMismatch { label, .. }))
+            if label == "test_poly"
+    ));
+
+    // Swapping in a commitment to the forged value breaks the dot product
+    // argument, which binds `com_eval` to the actual evaluation.
+    let mut forged_proof = proof.clone();
+    forged_proof[0].com_eval = (ck.com_key[0] * forged_value).into();
+    let result = Hyrax381::check(
+        &vk,
+        &c,
+        &point,
+        [forged_value],
+        &forged_proof,
+        &mut (test_sponge.clone()),
+        Some(chacha),
+    );
+    assert!(matches!(
+        result,
+        Err(Error::VerificationFailed(
+            VerificationFailure::InnerProduct { .. }
+        ))
+    ));
 }
 
 #[test]
@@ -210,3 +295,115 @@ fn hyrax_full_end_to_end_equation_test() {
     )
     .expect("test failed for bls12-381");
 }
+
+#[test]
+fn hyrax_cost_model_test() {
+    cost_model_test::<_, _, Hyrax377, _>(
+        1,
+        Some(10),
+        1 << 10,
+        rand_poly,
+        rand_point,
+        poseidon_sponge_for_test::<Fq>,
+    )
+    .expect("test failed for bls12-377");
+    cost_model_test::<_, _, Hyrax381, _>(
+        1,
+        Some(10),
+        1 << 10,
+        rand_poly,
+        rand_point,
+        poseidon_sponge_for_test::<Fr>,
+    )
+    .expect("test failed for bls12-381");
+}
+
+#[test]
+fn hyrax_update_commitment_test() {
+    update_commitment_test::<_, _, Hyrax381, _>(
+        1,
+        Some(10),
+        None,
+        false,
+        rand_poly,
+        rand_point,
+        apply_evaluation_deltas,
+        poseidon_sponge_for_test::<Fr>,
+    )
+    .expect("test failed for bls12-381");
+}
+
+#[test]
+fn hyrax_update_rerandomises_all_rows_test() {
+    let n = 4;
+    let chacha = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+    let pp = Hyrax381::setup(1, Some(n), chacha).unwrap();
+    let (ck, _) = Hyrax381::trim(&pp, 1, 1, None).unwrap();
+    let l_poly = LabeledPolynomial::new(
+        "test_poly".to_string(),
+        rand_poly::<Fr>(0, Some(n), chacha),
+        None,
+        None,
+    );
+    let (c, states) = Hyrax381::commit(&ck, &[l_poly], Some(chacha)).unwrap();
+
+    // The delta only touches the first row, but no row commitment is kept.
+    let (updated, _) =
+        Hyrax381::update_commitment(&ck, &c[0], &states[0], &[(0, Fr::from(1u64))], Some(chacha))
+            .unwrap();
+    let old_rows = &c[0].commitment().row_coms;
+    let new_rows = &updated.commitment().row_coms;
+    assert_eq!(old_rows.len(), new_rows.len());
+    assert!(old_rows.iter().zip(new_rows).all(|(old, new)| old != new));
+}
+
+#[test]
+fn hyrax_homomorphic_commitment_test() {
+    homomorphic_commitment_test::<_, _, Hyrax381, _>(
+        1,
+        Some(10),
+        None,
+        rand_poly,
+        rand_point,
+        poseidon_sponge_for_test::<Fr>,
+    )
+    .expect("test failed for bls12-381");
+}
+
+#[test]
+fn hyrax_hidden_evaluations_test() {
+    hidden_evaluations_test::<_, _, Hyrax381, _>(
+        1,
+        Some(10),
+        None,
+        false,
+        rand_poly,
+        rand_point,
+        poseidon_sponge_for_test::<Fr>,
+    )
+    .expect("test failed for bls12-381");
+}
+
+#[test]
+fn hyrax_deterministic_hiding_test() {
+    deterministic_hiding_test::<_, _, Hyrax381, _>(
+        1,
+        Some(10),
+        rand_poly,
+        rand_point,
+        poseidon_sponge_for_test::<Fr>,
+    )
+    .expect("test failed for bls12-381");
+}
+
+#[test]
+fn hyrax_tensor_openings_test() {
+    tensor_openings_test::<_, _, Hyrax381, _>(
+        1,
+        Some(10),
+        rand_poly,
+        MultilinearExtension::to_evaluations,
+        poseidon_sponge_for_test::<Fr>,
+    )
+    .expect("test failed for bls12-381");
+}
@@ -0,0 +1,351 @@
+//! Structured instrumentation of the schemes of this crate with the
+//! [`tracing`](https://docs.rs/tracing) crate, enabled by the `tracing`
+//! feature. Without it, the macros of this module expand to nothing.
+//!
+//! # Spans
+//!
+//! Every scheme enters a `debug` span named `<Scheme>::<phase>` around each
+//! of its phases: `setup`, `trim`, `commit`, `open` and `check`, along with
+//! `batch_open` and `batch_check` where the scheme implements them natively.
+//! The spans carry the sizes of their inputs:
+//!
+//! - `setup`: `max_degree` and `num_vars`, or the dimensions of the scheme;
+//! - `trim`: `supported_degree`, and `supported_hiding_bound` for the
+//!   schemes with hiding commitments;
+//! - `batch_open` and `batch_check`: `num_queries` and `num_proofs`.
+//!
+//! Inside `commit`, each polynomial gets a `commit_polynomial` span with its
+//! `label` and its `degree` or `num_vars`. The Merkle trees of the schemes
+//! based on linear codes are built in a `merkle_tree` span with its number
+//! of `leaves` and its `depth`.
+//!
+//! # Counters
+//!
+//! Counters are `trace` events with the target [`METRICS_TARGET`] and two
+//! fields: `counter`, one of the names below, and `value`, a `u64`. Each
+//! event is emitted inside the span of the phase which performs the work, so
+//! that a subscriber can aggregate the counters of each proof. The names
+//! mirror the fields of [`ProverCost`](crate::cost::ProverCost) and
+//! [`VerifierCost`](crate::cost::VerifierCost):
+//!
+//! - [`MSM`]: a multi-scalar multiplication, of `value` bases;
+//! - [`ENCODING`]: the encoding of a row into a codeword of length `value`,
+//!   which is the size of the FFT for Reed-Solomon codes;
+//! - [`HASH`]: `value` invocations of the hash functions of the scheme;
+//! - [`PAIRING`]: a product of `value` pairings.
+//!
+//! # Timers
+//!
+//! The finer steps of the schemes are timed with `start_timer!` and
+//! `end_timer!`, which this module defines in place of those of `ark_std`.
+//! Each timer is a `trace` span named `timer`, whose `message` describes the
+//! step, entered until the timer ends. Unlike the output of the former
+//! `print-trace` feature, these spans can be recorded from several threads
+//! at once.
+
+/// The target of the counter events.
+pub const METRICS_TARGET: &str = "ark_poly_commit::metrics";
+
+/// The counter of multi-scalar multiplications, whose value is their size.
+pub const MSM: &str = "msm";
+
+/// The counter of encodings, whose value is the length of the codeword.
+pub const ENCODING: &str = "encoding";
+
+/// The counter of hash invocations.
+pub const HASH: &str = "hash";
+
+/// The counter of products of pairings, whose value is their number of
+/// pairings.
+pub const PAIRING: &str = "pairing";
+
+/// Enters a `debug` span named `$name`, with the given `tracing` fields, for
+/// the rest of the enclosing block.
+macro_rules! phase_span {
+    ($name:literal $(, $($fields:tt)*)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($name $(, $($fields)*)?).entered();
+    };
+}
+
+/// Emits the counter `$counter`, one of the constants of
+/// `crate::instrumentation`, with the `usize` value `$value`.
+macro_rules! count {
+    ($counter:ident, $value:expr) => {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            target: $crate::instrumentation::METRICS_TARGET,
+            counter = $crate::instrumentation::$counter,
+            value = {
+                let value: usize = $value;
+                value as u64
+            },
+        );
+    };
+}
+
+/// Enters a `trace` span named `timer`, whose `message` is the output of the
+/// closure `$msg`, until the returned timer is passed to `end_timer!`.
+#[cfg(feature = "tracing")]
+macro_rules! start_timer {
+    ($msg:expr) => {
+        tracing::trace_span!("timer", message = %$msg()).entered()
+    };
+}
+
+/// Ends a timer started with `start_timer!`, after emitting the output of the
+/// closure `$msg` as a `trace` event in its span.
+#[cfg(feature = "tracing")]
+macro_rules! end_timer {
+    ($time:expr) => {
+        ark_std::mem::drop($time)
+    };
+    ($time:expr, $msg:expr) => {{
+        tracing::trace!("{}", $msg());
+        ark_std::mem::drop($time)
+    }};
+}
+
+/// A timer, when the `tracing` feature is disabled.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Timer;
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! start_timer {
+    ($msg:expr) => {
+        $crate::instrumentation::Timer
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! end_timer {
+    ($time:expr) => {
+        let _ = $time;
+    };
+    ($time:expr, $msg:expr) => {
+        let _ = $msg;
+        let _ = $time;
+    };
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::*;
+    use ark_std::{collections::BTreeMap, fmt::Debug, string::String, sync::Mutex, vec::Vec};
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    /// Records the names of the spans and sums up the counters.
+    #[derive(Default)]
+    struct Recorder {
+        spans: Mutex<Vec<&'static str>>,
+        counters: Mutex<BTreeMap<String, u64>>,
+    }
+
+    #[derive(Default)]
+    struct CounterVisitor {
+        counter: Option<String>,
+        value: u64,
+    }
+
+    impl Visit for CounterVisitor {
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            if field.name() == "value" {
+                self.value = value;
+            }
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "counter" {
+                self.counter = Some(value.into());
+            }
+        }
+
+        fn record_debug(&mut self, _field: &Field, _value: &dyn Debug) {}
+    }
+
+    impl Subscriber for &'static Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(span.metadata().name());
+            span::Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            if event.metadata().target() == METRICS_TARGET {
+                let mut visitor = CounterVisitor::default();
+                event.record(&mut visitor);
+                let counter = visitor.counter.expect("counters have a name");
+                *self.counters.lock().unwrap().entry(counter).or_default() += visitor.value;
+            }
+        }
+
+        fn enter(&self, _span: &span::Id) {}
+
+        fn exit(&self, _span: &span::Id) {}
+    }
+
+    #[test]
+    fn marlin_kzg10_spans_and_counters_test() {
+        use crate::{
+            marlin_pc::MarlinKZG10, tests::poseidon_sponge_for_test, LabeledPolynomial,
+            PolynomialCommitment,
+        };
+        use ark_bls12_381::{Bls12_381, Fr};
+        use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial};
+        use ark_std::test_rng;
+
+        type PC = MarlinKZG10<Bls12_381, DensePolynomial<Fr>>;
+        let recorder: &'static Recorder = Box::leak(Box::default());
+        tracing::subscriber::with_default(recorder, || {
+            let rng = &mut test_rng();
+            let pp = PC::setup(15, None, rng).unwrap();
+            let (ck, vk) = PC::trim(&pp, 15, 0, None).unwrap();
+            let polynomial =
+                LabeledPolynomial::new("Test".into(), DensePolynomial::rand(15, rng), None, None);
+            let (comms, states) = PC::commit(&ck, [&polynomial], Some(rng)).unwrap();
+            let point = Fr::from(7u64);
+            let sponge = poseidon_sponge_for_test::<Fr>();
+            let proof = PC::open(
+                &ck,
+                [&polynomial],
+                &comms,
+                &point,
+                &mut sponge.clone(),
+                &states,
+                None,
+            )
+            .unwrap();
+            PC::check(
+                &vk,
+                &comms,
+                &point,
+                [polynomial.evaluate(&point)],
+                &proof,
+                &mut sponge.clone(),
+                None,
+            )
+            .unwrap();
+        });
+
+        let spans = recorder.spans.lock().unwrap();
+        for name in [
+            "MarlinKZG10::setup",
+            "MarlinKZG10::trim",
+            "MarlinKZG10::commit",
+            "commit_polynomial",
+            "MarlinKZG10::open",
+            "MarlinKZG10::check",
+            "timer",
+        ] {
+            assert!(spans.contains(&name), "missing span {}", name);
+        }
+        let counters = recorder.counters.lock().unwrap();
+        // The commitment and the witness of a polynomial of degree 15 are
+        // each computed with an MSM of 16 bases, or fewer for the witness.
+        assert!(counters[MSM] >= 16);
+        assert_eq!(counters[PAIRING], 2);
+    }
+
+    #[test]
+    fn ligero_compact_open_counters_test() {
+        use crate::{
+            linear_codes::{CommitmentStateMode, LigeroPCParams},
+            test_types::{
+                test_sponge, FieldToBytesColHasher, LeafIdentityHasher, TestMerkleTreeParams,
+                TestUVLigero,
+            },
+            LabeledPolynomial, PolynomialCommitment,
+        };
+        use ark_bls12_377::Fr;
+        use ark_crypto_primitives::crh::{sha256::Sha256, CRHScheme, TwoToOneCRHScheme};
+        use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial};
+        use ark_std::test_rng;
+        use blake2::Blake2s256;
+
+        type ColHasher = FieldToBytesColHasher<Fr, Blake2s256>;
+        let rng = &mut test_rng();
+        let polynomial =
+            LabeledPolynomial::new("Test".into(), DensePolynomial::rand(1023, rng), None, None);
+
+        // With few queries, opening from a compact state only evaluates the
+        // queried columns; with many, it encodes every row once.
+        for (sec_param, queried_columns_only) in [(2, true), (128, false)] {
+            let pp = LigeroPCParams::<Fr, TestMerkleTreeParams, ColHasher>::new(
+                sec_param,
+                4,
+                false,
+                <LeafIdentityHasher as CRHScheme>::setup(rng).unwrap(),
+                <Sha256 as TwoToOneCRHScheme>::setup(rng).unwrap(),
+                <ColHasher as CRHScheme>::setup(rng).unwrap(),
+            );
+            let (ck, vk) = TestUVLigero::<Fr>::trim(&pp, 0, 0, None).unwrap();
+            let (comms, states) = TestUVLigero::<Fr>::commit_with_state_mode(
+                &ck,
+                [&polynomial],
+                CommitmentStateMode::Compact,
+            )
+            .unwrap();
+
+            let point = Fr::from(7u64);
+            let recorder: &'static Recorder = Box::leak(Box::default());
+            let mut proof = None;
+            record(recorder, || {
+                proof = Some(
+                    TestUVLigero::<Fr>::open(
+                        &ck,
+                        [&polynomial],
+                        &comms,
+                        &point,
+                        &mut test_sponge::<Fr>(),
+                        &states,
+                        None,
+                    )
+                    .unwrap(),
+                );
+            });
+            TestUVLigero::<Fr>::check(
+                &vk,
+                &comms,
+                &point,
+                [polynomial.evaluate(&point)],
+                &proof.unwrap(),
+                &mut test_sponge::<Fr>(),
+                None,
+            )
+            .unwrap();
+
+            let counters = recorder.counters.lock().unwrap();
+            let metadata = &comms[0].commitment().metadata;
+            let encodings = counters.get(ENCODING).copied().unwrap_or(0);
+            if queried_columns_only {
+                assert_eq!(encodings, 0);
+            } else {
+                assert_eq!(encodings, (metadata.n_rows * metadata.n_ext_cols) as u64);
+            }
+        }
+    }
+
+    /// Runs `f` with `recorder` as the default subscriber, on a single thread
+    /// so that the counters emitted by parallel iterators reach it.
+    fn record(recorder: &'static Recorder, f: impl FnOnce() + Send) {
+        #[cfg(feature = "parallel")]
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap()
+            .install(|| tracing::subscriber::with_default(recorder, f));
+        #[cfg(not(feature = "parallel"))]
+        tracing::subscriber::with_default(recorder, f);
+    }
+}
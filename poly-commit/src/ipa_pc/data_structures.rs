@@ -1,9 +1,24 @@
+use crate::cost::{
+    compressed_size, repeat, vec_bytes, BatchShape, CostEstimate, CostModel, ProverCost,
+    VerifierCost, OPTION_BYTES,
+};
+use crate::security::{
+    challenge_bits, dlog_bits, statistical_bits, SecurityAssumptions, SecurityEstimate,
+    SecurityEstimator,
+};
+use crate::srs_file::{FileBackedSrs, SrsFile, SrsFileBuilder};
+use crate::utils::deserialize_vec;
 use crate::*;
 use crate::{PCCommitterKey, PCVerifierKey};
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::AffineRepr;
-use ark_ff::{UniformRand, Zero};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_ff::{PrimeField, UniformRand, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::io::Read;
+#[cfg(all(not(feature = "std"), target_arch = "aarch64"))]
+use num_traits::Float;
 
 /// `UniversalParams` are the universal parameters for the inner product arg scheme.
 #[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
@@ -25,6 +40,35 @@ impl<G: AffineRepr> PCUniversalParams for UniversalParams<G> {
     }
 }
 
+/// The file holds `comm_key` as its only section.
+impl<G: AffineRepr> FileBackedSrs for UniversalParams<G> {
+    const TAG: [u8; 8] = *b"IPA_PC\0\0";
+
+    fn build<'a>(&'a self, builder: &mut SrsFileBuilder<'a>) -> Result<(), Error> {
+        builder.metadata(&(self.h, self.s))?;
+        builder.section(&self.comm_key);
+        Ok(())
+    }
+
+    fn validate(file: &SrsFile<'_, Self>) -> Result<(), Error> {
+        file.metadata::<(G, G)>()?;
+        file.validate_section::<G>(0)
+    }
+
+    /// Reads the parameters for polynomials of degree at most `max_degree`.
+    fn load(file: &SrsFile<'_, Self>, max_degree: usize) -> Result<Self, Error> {
+        let (h, s) = file.metadata::<(G, G)>()?;
+        if max_degree >= file.section_len(0).unwrap_or(0) {
+            return Err(Error::TrimmingDegreeTooLarge);
+        }
+        Ok(Self {
+            comm_key: file.read_section(0, 0..max_degree + 1)?,
+            h,
+            s,
+        })
+    }
+}
+
 /// `CommitterKey` is used to commit to, and create evaluation proofs for, a given
 /// polynomial.
 #[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
@@ -72,6 +116,88 @@ impl<G: AffineRepr> PCVerifierKey for VerifierKey<G> {
     }
 }
 
+impl<G: AffineRepr> CostModel for VerifierKey<G> {
+    fn estimate_cost(&self, poly_len: usize, batch: &BatchShape) -> Result<CostEstimate, Error> {
+        let d = self.comm_key.len();
+        if poly_len > d {
+            return Err(Error::TooManyCoefficients {
+                num_coefficients: poly_len,
+                num_powers: d,
+            });
+        }
+        let log_d = ark_std::log2(d) as usize;
+        let g_bytes = compressed_size(&self.h);
+        let f_bytes = compressed_size(&G::ScalarField::zero());
+        let proof_bytes = 2 * vec_bytes(log_d, g_bytes) + g_bytes + f_bytes + 2 * OPTION_BYTES;
+
+        // Each round of the argument computes `L` and `R` over half of the
+        // current key, and folds the key in half.
+        let rounds = (0..log_d)
+            .flat_map(|j| {
+                let half = (d >> j) / 2;
+                [half + 1, half + 1, half]
+            })
+            .collect::<Vec<_>>();
+        let mut prover_msms = repeat(poly_len, batch.num_polys);
+        for _ in 0..batch.num_points {
+            prover_msms.push(batch.num_polys);
+            prover_msms.extend_from_slice(&rounds);
+        }
+        // The succinct checks of all points are combined into a single
+        // check of the final commitment keys.
+        let mut verifier_msms = repeat(batch.num_polys + 2 * log_d + 2, batch.num_points);
+        verifier_msms.push(d);
+
+        Ok(CostEstimate {
+            commitment_bytes: g_bytes + OPTION_BYTES,
+            proof_bytes: vec_bytes(batch.num_points, proof_bytes),
+            prover: ProverCost {
+                msms: prover_msms,
+                encodings: Vec::new(),
+                hashes: 0,
+            },
+            verifier: VerifierCost {
+                pairings: 0,
+                msms: verifier_msms,
+                encodings: Vec::new(),
+                hashes: 0,
+            },
+        })
+    }
+}
+
+impl<G: AffineRepr> SecurityEstimator for VerifierKey<G> {
+    fn estimate_security(
+        &self,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<SecurityEstimate, Error> {
+        let d = self.comm_key.len();
+        if poly_len > d {
+            return Err(Error::TooManyCoefficients {
+                num_coefficients: poly_len,
+                num_powers: d,
+            });
+        }
+        Ok(ipa_security::<G::ScalarField>(d, assumptions))
+    }
+}
+
+/// The security of the inner product argument with a commitment key of
+/// length `d`: evaluating the polynomial at a random point, and running
+/// `log2(d)` rounds, each with an error of `3 / |C|` over the challenge space `C`.
+pub(crate) fn ipa_security<F: PrimeField>(
+    d: usize,
+    assumptions: &SecurityAssumptions,
+) -> SecurityEstimate {
+    let d = d.max(2) as f64;
+    let log2_error = (d + 3.0 * d.log2()).log2() - challenge_bits::<F>();
+    SecurityEstimate {
+        statistical_bits: statistical_bits(log2_error, assumptions),
+        computational_bits: Some(dlog_bits::<F>()),
+    }
+}
+
 /// Nothing to do to prepare this verifier key (for now).
 pub type PreparedVerifierKey<G> = VerifierKey<G>;
 
@@ -167,7 +293,7 @@ impl<G: AffineRepr> PCCommitmentState for Randomness<G> {
 }
 
 /// `Proof` is an evaluation proof that is output by `InnerProductArg::open`.
-#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Derivative, CanonicalSerialize)]
 #[derivative(
     Default(bound = ""),
     Hash(bound = ""),
@@ -196,6 +322,49 @@ pub struct Proof<G: AffineRepr> {
     pub rand: Option<G::ScalarField>,
 }
 
+impl<G: AffineRepr> Valid for Proof<G> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.l_vec.check()?;
+        self.r_vec.check()?;
+        self.final_comm_key.check()?;
+        self.c.check()?;
+        self.hiding_comm.check()?;
+        self.rand.check()?;
+        if self.l_vec.len() != self.r_vec.len() || self.hiding_comm.is_some() != self.rand.is_some()
+        {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(())
+    }
+}
+
+impl<G: AffineRepr> CanonicalDeserialize for Proof<G> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let l_vec = deserialize_vec(&mut reader, compress)?;
+        let r_vec = deserialize_vec(&mut reader, compress)?;
+        let final_comm_key = G::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let c = G::ScalarField::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let hiding_comm = Option::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let rand = Option::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let result = Self {
+            l_vec,
+            r_vec,
+            final_comm_key,
+            c,
+            hiding_comm,
+            rand,
+        };
+        if let Validate::Yes = validate {
+            result.check()?;
+        }
+        Ok(result)
+    }
+}
+
 /// `SuccinctCheckPolynomial` is a succinctly-representated polynomial
 /// generated from the `log_d` random oracle challenges generated in `open`.
 /// It has the special property that can be evaluated in `O(log_d)` time.
@@ -1,12 +1,18 @@
 use crate::{
+    hidden::{
+        check_hiding, check_no_degree_bound, pair_evaluation_commitments, subtract_constant,
+        HiddenEvaluations,
+    },
+    homomorphic::{common_degree_bound, CombinationTerm, HomomorphicCommitment},
+    security::SecurityAssumptions,
+    update::{merge_deltas, UpdatableCommitment},
     BTreeMap, BTreeSet, BatchLCProof, DenseUVPolynomial, Error, Evaluations, LabeledCommitment,
     LabeledPolynomial, LinearCombination, PCCommitmentState, PCCommitterKey, PCUniversalParams,
-    PolynomialCommitment, QuerySet, CHALLENGE_SIZE,
+    PolynomialCommitment, PolynomialLabel, QuerySet, Transcript, VerificationFailure,
 };
 use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
 use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
 use ark_ff::{Field, One, PrimeField, UniformRand, Zero};
-use ark_serialize::CanonicalSerialize;
 use ark_std::{convert::TryInto, format, marker::PhantomData, ops::Mul, rand::RngCore};
 #[cfg(not(feature = "std"))]
 use ark_std::{
@@ -46,9 +52,29 @@ where
     D: Digest,
     P: DenseUVPolynomial<G::ScalarField>,
 {
-    /// `PROTOCOL_NAME` is used as a seed for the setup function.
+    /// `PROTOCOL_NAME` is used as a seed for the setup function, and binds the
+    /// transcript of every opening to this scheme.
     pub const PROTOCOL_NAME: &'static [u8] = b"PC-DL-2020";
 
+    /// Suggest the `max_degree` to pass to `setup` in order to commit to
+    /// polynomials with `poly_len` coefficients with at least `target_bits`
+    /// of security under `assumptions`.
+    ///
+    /// Returns an error if the group is too small to reach `target_bits`.
+    pub fn suggest_max_degree(
+        target_bits: f64,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<usize, Error> {
+        let d = poly_len.max(1).next_power_of_two();
+        if ipa_security::<G::ScalarField>(d, assumptions).bits() < target_bits {
+            return Err(Error::InvalidParameters(
+                "The target security cannot be reached in this group".to_string(),
+            ));
+        }
+        Ok(d - 1)
+    }
+
     /// Create a Pedersen commitment to `scalars` using the commitment key `comm_key`.
     /// Optionally, randomize the commitment using `hiding_generator` and `randomizer`.
     fn cm_commit(
@@ -62,6 +88,7 @@ where
             .collect::<Vec<_>>();
 
         let mut comm = <G::Group as VariableBaseMSM>::msm_bigint(comm_key, &scalars_bigint);
+        count!(MSM, scalars_bigint.len());
 
         if randomizer.is_some() {
             assert!(hiding_generator.is_some());
@@ -71,19 +98,17 @@ where
         comm
     }
 
-    fn compute_random_oracle_challenge(bytes: &[u8]) -> G::ScalarField {
-        let mut i = 0u64;
-        let mut challenge = None;
-        while challenge.is_none() {
-            let mut hash_input = bytes.to_vec();
-            hash_input.extend(i.to_le_bytes());
-            let hash = D::digest(&hash_input.as_slice());
-            challenge = <G::ScalarField as Field>::from_random_bytes(&hash);
-
-            i += 1;
-        }
-
-        challenge.unwrap()
+    /// Absorbs the combined commitment, the point and the combined value of an
+    /// opening, which the hiding and round challenges depend on.
+    fn absorb_combination(
+        sponge: &mut impl CryptographicSponge,
+        combined_commitment: &G,
+        point: &G::ScalarField,
+        combined_v: &G::ScalarField,
+    ) -> Result<(), Error> {
+        sponge.absorb_serialized(b"combined commitment", combined_commitment)?;
+        sponge.absorb_serialized(b"point", point)?;
+        sponge.absorb_serialized(b"combined value", combined_v)
     }
 
     #[inline]
@@ -100,19 +125,32 @@ where
         values: impl IntoIterator<Item = G::ScalarField>,
         proof: &Proof<G>,
         sponge: &mut impl CryptographicSponge,
-    ) -> Option<SuccinctCheckPolynomial<G::ScalarField>> {
+    ) -> Result<Option<SuccinctCheckPolynomial<G::ScalarField>>, Error> {
         let check_time = start_timer!(|| "Succinct checking");
+        sponge.bind_context(Self::PROTOCOL_NAME, vk)?;
 
         let d = vk.supported_degree();
 
         // `log_d` is ceil(log2 (d + 1)), which is the number of steps to compute all of the challenges
         let log_d = ark_std::log2(d + 1) as usize;
 
+        if proof.l_vec.len() != proof.r_vec.len() || proof.l_vec.len() != log_d {
+            return Err(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!(
+                    "expected proof vectors of length {}, found l_vec of length {} and r_vec of length {}",
+                    log_d,
+                    proof.l_vec.len(),
+                    proof.r_vec.len()
+                ),
+            }
+            .into());
+        }
+
         let mut combined_commitment_proj = G::Group::zero();
         let mut combined_v = G::ScalarField::zero();
 
-        let mut cur_challenge: G::ScalarField =
-            sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+        let mut cur_challenge: G::ScalarField = sponge.challenge(b"opening challenge");
 
         let labeled_commitments = commitments.into_iter();
         let values = values.into_iter();
@@ -121,49 +159,52 @@ where
             let commitment = labeled_commitment.commitment();
             combined_v += &(cur_challenge * &value);
             combined_commitment_proj += &labeled_commitment.commitment().comm.mul(cur_challenge);
-            cur_challenge = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+            cur_challenge = sponge.challenge(b"opening challenge");
 
             let degree_bound = labeled_commitment.degree_bound();
-            assert_eq!(degree_bound.is_some(), commitment.shifted_comm.is_some());
+            if degree_bound.is_some() != commitment.shifted_comm.is_some() {
+                return Err(VerificationFailure::MalformedProof {
+                    label: Some(labeled_commitment.label().clone()),
+                    reason: "shifted commitment does not match the degree bound".to_string(),
+                }
+                .into());
+            }
 
             if let Some(degree_bound) = degree_bound {
-                let shift = point.pow([(vk.supported_degree() - degree_bound) as u64]);
+                if degree_bound > d {
+                    return Err(Error::UnsupportedDegreeBound(degree_bound));
+                }
+                let shift = point.pow([(d - degree_bound) as u64]);
                 combined_v += &(cur_challenge * &value * &shift);
                 combined_commitment_proj += &commitment.shifted_comm.unwrap().mul(cur_challenge);
             }
 
-            cur_challenge = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+            cur_challenge = sponge.challenge(b"opening challenge");
         }
 
         let mut combined_commitment = combined_commitment_proj.into_affine();
 
-        assert_eq!(proof.hiding_comm.is_some(), proof.rand.is_some());
+        if proof.hiding_comm.is_some() != proof.rand.is_some() {
+            return Err(VerificationFailure::MalformedProof {
+                label: None,
+                reason: "hiding commitment and randomness must be given together".to_string(),
+            }
+            .into());
+        }
         if proof.hiding_comm.is_some() {
             let hiding_comm = proof.hiding_comm.unwrap();
             let rand = proof.rand.unwrap();
-            let mut byte_vec = Vec::new();
-            combined_commitment
-                .serialize_uncompressed(&mut byte_vec)
-                .unwrap();
-            point.serialize_uncompressed(&mut byte_vec).unwrap();
-            combined_v.serialize_uncompressed(&mut byte_vec).unwrap();
-            hiding_comm.serialize_uncompressed(&mut byte_vec).unwrap();
-            let bytes = byte_vec.as_slice();
-            let hiding_challenge = Self::compute_random_oracle_challenge(bytes);
+            Self::absorb_combination(sponge, &combined_commitment, &point, &combined_v)?;
+            sponge.absorb_serialized(b"hiding commitment", &hiding_comm)?;
+            let hiding_challenge: G::ScalarField = sponge.challenge(b"hiding challenge");
             combined_commitment_proj += &(hiding_comm.mul(hiding_challenge) - &vk.s.mul(rand));
             combined_commitment = combined_commitment_proj.into_affine();
         }
 
         // Challenge for each round
         let mut round_challenges = Vec::with_capacity(log_d);
-        let mut byte_vec = Vec::new();
-        combined_commitment
-            .serialize_uncompressed(&mut byte_vec)
-            .unwrap();
-        point.serialize_uncompressed(&mut byte_vec).unwrap();
-        combined_v.serialize_uncompressed(&mut byte_vec).unwrap();
-        let bytes = byte_vec.as_slice();
-        let mut round_challenge = Self::compute_random_oracle_challenge(bytes);
+        Self::absorb_combination(sponge, &combined_commitment, &point, &combined_v)?;
+        let mut round_challenge: G::ScalarField = sponge.challenge(b"round challenge");
 
         let h_prime = vk.h.mul(round_challenge);
 
@@ -173,18 +214,18 @@ where
         let r_iter = proof.r_vec.iter();
 
         for (l, r) in l_iter.zip(r_iter) {
-            let mut byte_vec = Vec::new();
-            round_challenge
-                .serialize_uncompressed(&mut byte_vec)
-                .unwrap();
-            l.serialize_uncompressed(&mut byte_vec).unwrap();
-            r.serialize_uncompressed(&mut byte_vec).unwrap();
-            let bytes = byte_vec.as_slice();
-
-            round_challenge = Self::compute_random_oracle_challenge(bytes);
+            sponge.absorb_serialized(b"left commitment", l)?;
+            sponge.absorb_serialized(b"right commitment", r)?;
+            round_challenge = sponge.challenge(b"round challenge");
             round_challenges.push(round_challenge);
-            round_commitment_proj +=
-                &(l.mul(round_challenge.inverse().unwrap()) + &r.mul(round_challenge));
+            let round_challenge_inv = match round_challenge.inverse() {
+                Some(inverse) => inverse,
+                None => {
+                    end_timer!(check_time);
+                    return Ok(None);
+                }
+            };
+            round_commitment_proj += &(l.mul(round_challenge_inv) + r.mul(round_challenge));
         }
 
         let check_poly = SuccinctCheckPolynomial::<G::ScalarField>(round_challenges);
@@ -200,11 +241,11 @@ where
 
         if !(round_commitment_proj - &check_commitment_elem).is_zero() {
             end_timer!(check_time);
-            return None;
+            return Ok(None);
         }
 
         end_timer!(check_time);
-        Some(check_poly)
+        Ok(Some(check_poly))
     }
 
     fn check_degrees_and_bounds(
@@ -351,6 +392,7 @@ where
         _: Option<usize>,
         _rng: &mut R,
     ) -> Result<Self::UniversalParams, Self::Error> {
+        phase_span!("InnerProductArgPC::setup", max_degree);
         // Ensure that max_degree + 1 is a power of 2
         let max_degree = (max_degree + 1).next_power_of_two() - 1;
 
@@ -376,6 +418,7 @@ where
         _supported_hiding_bound: usize,
         _enforced_degree_bounds: Option<&[usize]>,
     ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
+        phase_span!("InnerProductArgPC::trim", supported_degree);
         // Ensure that supported_degree + 1 is a power of two
         let supported_degree = (supported_degree + 1).next_power_of_two() - 1;
         if supported_degree > pp.max_degree() {
@@ -419,6 +462,7 @@ where
     where
         P: 'a,
     {
+        phase_span!("InnerProductArgPC::commit");
         let rng = &mut crate::optional_rng::OptionalRng(rng);
         let mut comms = Vec::new();
         let mut states = Vec::new();
@@ -432,6 +476,7 @@ where
             let hiding_bound = labeled_polynomial.hiding_bound();
             let degree_bound = labeled_polynomial.degree_bound();
 
+            phase_span!("commit_polynomial", label = %label, degree = polynomial.degree());
             let commit_time = start_timer!(|| format!(
                 "Polynomial {} of degree {}, degree bound {:?}, and hiding bound {:?}",
                 label,
@@ -491,6 +536,7 @@ where
         Self::CommitmentState: 'a,
         P: 'a,
     {
+        phase_span!("InnerProductArgPC::open");
         let mut combined_polynomial = P::zero();
         let mut combined_rand = G::ScalarField::zero();
         let mut combined_commitment_proj = G::Group::zero();
@@ -503,7 +549,8 @@ where
 
         let combine_time = start_timer!(|| "Combining polynomials, randomness, and commitments.");
 
-        let mut cur_challenge = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+        sponge.bind_context(Self::PROTOCOL_NAME, ck)?;
+        let mut cur_challenge = sponge.challenge(b"opening challenge");
 
         for (labeled_polynomial, (labeled_commitment, state)) in
             polys_iter.zip(comms_iter.zip(states_iter))
@@ -525,7 +572,7 @@ where
                 combined_rand += &(cur_challenge * &state.rand);
             }
 
-            cur_challenge = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+            cur_challenge = sponge.challenge(b"opening challenge");
 
             let has_degree_bound = degree_bound.is_some();
 
@@ -558,7 +605,7 @@ where
                 }
             }
 
-            cur_challenge = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+            cur_challenge = sponge.challenge(b"opening challenge");
         }
 
         end_timer!(combine_time);
@@ -592,18 +639,9 @@ where
             hiding_commitment = Some(batch.pop().unwrap());
             combined_commitment = batch.pop().unwrap();
 
-            let mut byte_vec = Vec::new();
-            combined_commitment
-                .serialize_uncompressed(&mut byte_vec)
-                .unwrap();
-            point.serialize_uncompressed(&mut byte_vec).unwrap();
-            combined_v.serialize_uncompressed(&mut byte_vec).unwrap();
-            hiding_commitment
-                .unwrap()
-                .serialize_uncompressed(&mut byte_vec)
-                .unwrap();
-            let bytes = byte_vec.as_slice();
-            let hiding_challenge = Self::compute_random_oracle_challenge(bytes);
+            Self::absorb_combination(sponge, &combined_commitment, point, &combined_v)?;
+            sponge.absorb_serialized(b"hiding commitment", &hiding_commitment.unwrap())?;
+            let hiding_challenge: G::ScalarField = sponge.challenge(b"hiding challenge");
             combined_polynomial += (hiding_challenge, &hiding_polynomial);
             combined_rand += &(hiding_challenge * &hiding_rand);
             combined_commitment_proj +=
@@ -624,14 +662,8 @@ where
         combined_commitment = combined_commitment_proj.into_affine();
 
         // ith challenge
-        let mut byte_vec = Vec::new();
-        combined_commitment
-            .serialize_uncompressed(&mut byte_vec)
-            .unwrap();
-        point.serialize_uncompressed(&mut byte_vec).unwrap();
-        combined_v.serialize_uncompressed(&mut byte_vec).unwrap();
-        let bytes = byte_vec.as_slice();
-        let mut round_challenge = Self::compute_random_oracle_challenge(bytes);
+        Self::absorb_combination(sponge, &combined_commitment, point, &combined_v)?;
+        let mut round_challenge: G::ScalarField = sponge.challenge(b"round challenge");
 
         let h_prime = ck.h.mul(round_challenge).into_affine();
 
@@ -683,14 +715,9 @@ where
             l_vec.push(lr[0]);
             r_vec.push(lr[1]);
 
-            let mut byte_vec = Vec::new();
-            round_challenge
-                .serialize_uncompressed(&mut byte_vec)
-                .unwrap();
-            lr[0].serialize_uncompressed(&mut byte_vec).unwrap();
-            lr[1].serialize_uncompressed(&mut byte_vec).unwrap();
-            let bytes = byte_vec.as_slice();
-            round_challenge = Self::compute_random_oracle_challenge(bytes);
+            sponge.absorb_serialized(b"left commitment", &lr[0])?;
+            sponge.absorb_serialized(b"right commitment", &lr[1])?;
+            round_challenge = sponge.challenge(b"round challenge");
             let round_challenge_inv = round_challenge.inverse().unwrap();
 
             ark_std::cfg_iter_mut!(coeffs_l)
@@ -735,34 +762,23 @@ where
         proof: &Self::Proof,
         sponge: &mut impl CryptographicSponge,
         _rng: Option<&mut dyn RngCore>,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
+        phase_span!("InnerProductArgPC::check");
         let check_time = start_timer!(|| "Checking evaluations");
-        let d = vk.supported_degree();
-
-        // `log_d` is ceil(log2 (d + 1)), which is the number of steps to compute all of the challenges
-        let log_d = ark_std::log2(d + 1) as usize;
-
-        if proof.l_vec.len() != proof.r_vec.len() || proof.l_vec.len() != log_d {
-            return Err(Error::IncorrectInputLength(
-                format!(
-                    "Expected proof vectors to be {:}. Instead, l_vec size is {:} and r_vec size is {:}",
-                    log_d,
-                    proof.l_vec.len(),
-                    proof.r_vec.len()
-                )
-            ));
-        }
 
-        let check_poly = Self::succinct_check(vk, commitments, *point, values, proof, sponge);
-
-        if check_poly.is_none() {
-            return Ok(false);
-        }
+        let failure = || {
+            Error::from(VerificationFailure::InnerProduct {
+                label: None,
+                point: Some(format!("{:?}", point)),
+            })
+        };
+        let check_poly = Self::succinct_check(vk, commitments, *point, values, proof, sponge)?
+            .ok_or_else(failure)?;
 
-        let check_poly_coeffs = check_poly.unwrap().compute_coeffs();
+        let check_poly_coeffs = check_poly.compute_coeffs();
         let final_key = Self::cm_commit(
             vk.comm_key.as_slice(),
             check_poly_coeffs.as_slice(),
@@ -770,11 +786,11 @@ where
             None,
         );
         if !(final_key - &proof.final_comm_key.into()).is_zero() {
-            return Ok(false);
+            return Err(failure());
         }
 
         end_timer!(check_time);
-        Ok(true)
+        Ok(())
     }
 
     fn batch_check<'a, R: RngCore>(
@@ -785,10 +801,11 @@ where
         proof: &Self::BatchProof,
         sponge: &mut impl CryptographicSponge,
         rng: &mut R,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
+        phase_span!("InnerProductArgPC::batch_check", num_proofs = proof.len());
         let commitments: BTreeMap<_, _> = commitments.into_iter().map(|c| (c.label(), c)).collect();
         let mut query_to_labels_map = BTreeMap::new();
 
@@ -799,7 +816,17 @@ where
             labels.1.insert(label);
         }
 
-        assert_eq!(proof.len(), query_to_labels_map.len());
+        if proof.len() != query_to_labels_map.len() {
+            return Err(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!(
+                    "expected {} proofs, one per point, found {}",
+                    query_to_labels_map.len(),
+                    proof.len()
+                ),
+            }
+            .into());
+        }
 
         let mut randomizer = G::ScalarField::one();
 
@@ -827,13 +854,13 @@ where
             }
 
             let check_poly =
-                Self::succinct_check(vk, comms.into_iter(), *point, vals.into_iter(), p, sponge);
-
-            if check_poly.is_none() {
-                return Ok(false);
-            }
+                Self::succinct_check(vk, comms.into_iter(), *point, vals.into_iter(), p, sponge)?
+                    .ok_or_else(|| VerificationFailure::InnerProduct {
+                    label: None,
+                    point: Some(format!("{:?}", point)),
+                })?;
 
-            let check_poly = P::from_coefficients_vec(check_poly.unwrap().compute_coeffs());
+            let check_poly = P::from_coefficients_vec(check_poly.compute_coeffs());
             combined_check_poly += (randomizer, &check_poly);
             combined_final_key += &p.final_comm_key.mul(randomizer);
 
@@ -849,12 +876,16 @@ where
             None,
         );
         if !(final_key - &combined_final_key).is_zero() {
-            return Ok(false);
+            return Err(VerificationFailure::InnerProduct {
+                label: None,
+                point: None,
+            }
+            .into());
         }
 
         end_timer!(proof_time);
 
-        Ok(true)
+        Ok(())
     }
 
     fn open_combinations<'a>(
@@ -911,7 +942,6 @@ where
                     );
                     degree_bound = cur_poly.degree_bound();
                 } else if cur_poly.degree_bound().is_some() {
-                    eprintln!("Degree bound when number of equations is non-zero");
                     return Err(Self::Error::EquationHasDegreeBounds(lc_label));
                 }
 
@@ -976,7 +1006,7 @@ where
         proof: &BatchLCProof<G::ScalarField, Self::BatchProof>,
         sponge: &mut impl CryptographicSponge,
         rng: &mut R,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
@@ -1053,6 +1083,246 @@ where
     }
 }
 
+impl<G, D, P> HomomorphicCommitment<G::ScalarField, P> for InnerProductArgPC<G, D, P>
+where
+    G: AffineRepr + Absorb,
+    G::Group: VariableBaseMSM<MulBase = G>,
+    D: Digest,
+    P: DenseUVPolynomial<G::ScalarField, Point = G::ScalarField>,
+{
+    fn linear_combination(
+        label: PolynomialLabel,
+        terms: &[CombinationTerm<G::ScalarField, Self::Commitment, Self::CommitmentState>],
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error> {
+        let degree_bound = common_degree_bound(&label, terms)?;
+        let mut comm = G::Group::zero();
+        let mut shifted_comm = degree_bound.map(|_| G::Group::zero());
+        let mut rand = G::ScalarField::zero();
+        let mut shifted_rand = None;
+        for (coeff, c, s) in terms {
+            let c = c.commitment();
+            comm += c.comm.mul(*coeff);
+            if let (Some(sum), Some(shifted)) = (shifted_comm.as_mut(), c.shifted_comm) {
+                *sum += shifted.mul(*coeff);
+            }
+            rand += s.rand * coeff;
+            if let Some(r) = s.shifted_rand {
+                *shifted_rand.get_or_insert_with(G::ScalarField::zero) += r * coeff;
+            }
+        }
+
+        let comm = Commitment {
+            comm: comm.into(),
+            shifted_comm: shifted_comm.map(Into::into),
+        };
+        let state = Randomness { rand, shifted_rand };
+        Ok((LabeledCommitment::new(label, comm, degree_bound), state))
+    }
+}
+
+impl<G, D, P> UpdatableCommitment<G::ScalarField, P> for InnerProductArgPC<G, D, P>
+where
+    G: AffineRepr + Absorb,
+    G::Group: VariableBaseMSM<MulBase = G>,
+    D: Digest,
+    P: DenseUVPolynomial<G::ScalarField, Point = G::ScalarField>,
+{
+    fn update_commitment(
+        ck: &Self::CommitterKey,
+        commitment: &LabeledCommitment<Self::Commitment>,
+        state: &Self::CommitmentState,
+        deltas: &[(usize, G::ScalarField)],
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error> {
+        let deltas = merge_deltas(deltas);
+        let label = commitment.label();
+        let degree_bound = commitment.degree_bound();
+        let update_time = start_timer!(|| format!(
+            "Updating {} coefficients of polynomial {}",
+            deltas.len(),
+            label
+        ));
+
+        let supported_degree = ck.supported_degree();
+        if let Some(d) = degree_bound.filter(|d| *d > supported_degree) {
+            return Err(Error::UnsupportedDegreeBound(d));
+        }
+        if let Some((degree, _)) = deltas.last() {
+            let max_degree = degree_bound.unwrap_or(supported_degree);
+            if *degree > max_degree {
+                return Err(Error::PolynomialDegreeTooLarge {
+                    poly_degree: *degree,
+                    supported_degree: max_degree,
+                    label: label.to_string(),
+                });
+            }
+        }
+
+        let mut rng = rng;
+        let mut sample = |hiding: bool| -> Result<Option<G::ScalarField>, Error> {
+            if hiding {
+                let rng = rng.as_deref_mut().ok_or(Error::MissingRng)?;
+                Ok(Some(G::ScalarField::rand(rng)))
+            } else {
+                Ok(None)
+            }
+        };
+        let rand = sample(!state.rand.is_zero())?;
+        let shifted_rand = sample(state.shifted_rand.is_some_and(|r| !r.is_zero()))?;
+
+        let scalars: Vec<_> = deltas.iter().map(|(_, d)| *d).collect();
+        let bases: Vec<_> = deltas.iter().map(|(i, _)| ck.comm_key[*i]).collect();
+        let comm = commitment.commitment().comm.into_group()
+            + Self::cm_commit(&bases, &scalars, Some(ck.s), rand);
+
+        let shifted_comm = match (degree_bound, commitment.commitment().shifted_comm) {
+            (Some(d), Some(shifted_comm)) => {
+                let bases: Vec<_> = deltas
+                    .iter()
+                    .map(|(i, _)| ck.comm_key[supported_degree - d + i])
+                    .collect();
+                let shifted_comm = shifted_comm.into_group()
+                    + Self::cm_commit(&bases, &scalars, Some(ck.s), shifted_rand);
+                Some(shifted_comm.into())
+            }
+            _ => None,
+        };
+
+        let comm = Commitment {
+            comm: comm.into(),
+            shifted_comm,
+        };
+        let state = Randomness {
+            rand: state.rand + rand.unwrap_or_else(G::ScalarField::zero),
+            shifted_rand: state
+                .shifted_rand
+                .map(|r| r + shifted_rand.unwrap_or_else(G::ScalarField::zero)),
+        };
+        end_timer!(update_time);
+        Ok((
+            LabeledCommitment::new(label.to_string(), comm, degree_bound),
+            state,
+        ))
+    }
+}
+
+impl<G, D, P> HiddenEvaluations<G::ScalarField, P> for InnerProductArgPC<G, D, P>
+where
+    G: AffineRepr + Absorb,
+    G::Group: VariableBaseMSM<MulBase = G>,
+    D: Digest,
+    P: DenseUVPolynomial<G::ScalarField, Point = G::ScalarField>,
+{
+    type EvaluationCommitment = G;
+
+    /// Evaluations are committed to as `value G_0 + randomness S`, which is
+    /// the commitment to the constant polynomial `value`.
+    fn commit_evaluation(
+        vk: &Self::VerifierKey,
+        value: G::ScalarField,
+        randomness: G::ScalarField,
+    ) -> Self::EvaluationCommitment {
+        Self::cm_commit(&vk.comm_key[..1], &[value], Some(vk.s), Some(randomness)).into()
+    }
+
+    /// Polynomials with degree bounds are not supported.
+    fn open_hidden<'a>(
+        ck: &Self::CommitterKey,
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<G::ScalarField, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Self::Proof,
+            Vec<(Self::EvaluationCommitment, G::ScalarField)>,
+        ),
+        Self::Error,
+    >
+    where
+        P: 'a,
+        Self::CommitmentState: 'a,
+        Self::Commitment: 'a,
+    {
+        let rng = rng.ok_or(Error::MissingRng)?;
+        let mut differences = Vec::new();
+        let mut difference_comms = Vec::new();
+        let mut difference_states = Vec::new();
+        let mut evaluations = Vec::new();
+        for ((polynomial, commitment), state) in
+            labeled_polynomials.into_iter().zip(commitments).zip(states)
+        {
+            check_no_degree_bound(polynomial.degree_bound())?;
+            check_hiding(polynomial, polynomial.hiding_bound().is_some())?;
+            let value = polynomial.evaluate(point);
+            let randomness = G::ScalarField::rand(rng);
+            let evaluation_comm = Self::commit_evaluation(ck, value, randomness);
+
+            differences.push(subtract_constant(polynomial, value));
+            difference_comms.push(LabeledCommitment::new(
+                commitment.label().clone(),
+                Commitment {
+                    comm: (commitment.commitment().comm.into_group() - evaluation_comm).into(),
+                    shifted_comm: None,
+                },
+                None,
+            ));
+            difference_states.push(Randomness {
+                rand: state.rand - randomness,
+                shifted_rand: None,
+            });
+            evaluations.push((evaluation_comm, randomness));
+        }
+
+        let evaluation_comms: Vec<_> = evaluations.iter().map(|(c, _)| *c).collect();
+        sponge.absorb_serialized(b"evaluation commitments", &evaluation_comms)?;
+        let proof = Self::open(
+            ck,
+            &differences,
+            &difference_comms,
+            point,
+            sponge,
+            &difference_states,
+            Some(rng),
+        )?;
+        Ok((proof, evaluations))
+    }
+
+    fn check_hidden<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        evaluation_commitments: impl IntoIterator<Item = &'a Self::EvaluationCommitment>,
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a,
+        Self::EvaluationCommitment: 'a,
+    {
+        let pairs = pair_evaluation_commitments(commitments, evaluation_commitments)?;
+        let mut difference_comms = Vec::new();
+        for (commitment, evaluation_comm) in &pairs {
+            check_no_degree_bound(commitment.degree_bound())?;
+            difference_comms.push(LabeledCommitment::new(
+                commitment.label().clone(),
+                Commitment {
+                    comm: (commitment.commitment().comm.into_group() - **evaluation_comm).into(),
+                    shifted_comm: None,
+                },
+                None,
+            ));
+        }
+
+        let evaluation_comms: Vec<_> = pairs.iter().map(|(_, c)| **c).collect();
+        sponge.absorb_serialized(b"evaluation commitments", &evaluation_comms)?;
+        let zeros = vec![G::ScalarField::zero(); difference_comms.len()];
+        Self::check(vk, &difference_comms, point, zeros, proof, sponge, None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(non_camel_case_types)]
@@ -1243,4 +1513,80 @@ mod tests {
         .expect("test failed for ed_on_bls12_381-blake2s");
         println!("Finished ed_on_bls12_381-blake2s");
     }
+
+    #[test]
+    fn cost_model_test() {
+        use crate::tests::*;
+        cost_model_test::<_, _, PC_JJB2S, _>(
+            20,
+            None,
+            21,
+            rand_poly::<Fr>,
+            rand_point::<Fr>,
+            poseidon_sponge_for_test::<Fr>,
+        )
+        .expect("test failed for ed_on_bls12_381-blake2s");
+    }
+
+    #[test]
+    fn update_commitment_test() {
+        use crate::tests::*;
+        for degree_bound in [None, Some(10)] {
+            update_commitment_test::<_, _, PC_JJB2S, _>(
+                20,
+                None,
+                degree_bound,
+                true,
+                rand_poly::<Fr>,
+                rand_point::<Fr>,
+                apply_coefficient_deltas,
+                poseidon_sponge_for_test::<Fr>,
+            )
+            .expect("test failed for ed_on_bls12_381-blake2s");
+        }
+    }
+
+    #[test]
+    fn homomorphic_commitment_test() {
+        use crate::tests::*;
+        for degree_bound in [None, Some(10)] {
+            homomorphic_commitment_test::<_, _, PC_JJB2S, _>(
+                20,
+                None,
+                degree_bound,
+                rand_poly::<Fr>,
+                rand_point::<Fr>,
+                poseidon_sponge_for_test::<Fr>,
+            )
+            .expect("test failed for ed_on_bls12_381-blake2s");
+        }
+    }
+
+    #[test]
+    fn hidden_evaluations_test() {
+        use crate::tests::*;
+        hidden_evaluations_test::<_, _, PC_JJB2S, _>(
+            20,
+            None,
+            None,
+            true,
+            rand_poly::<Fr>,
+            rand_point::<Fr>,
+            poseidon_sponge_for_test::<Fr>,
+        )
+        .expect("test failed for ed_on_bls12_381-blake2s");
+    }
+
+    #[test]
+    fn deterministic_hiding_test() {
+        use crate::tests::*;
+        deterministic_hiding_test::<_, _, PC_JJB2S, _>(
+            20,
+            None,
+            rand_poly::<Fr>,
+            rand_point::<Fr>,
+            poseidon_sponge_for_test::<Fr>,
+        )
+        .expect("test failed for ed_on_bls12_381-blake2s");
+    }
 }
@@ -1,12 +1,21 @@
-use crate::*;
+use crate::{
+    security::{pairing_scheme_security, SecurityAssumptions, SecurityEstimate, SecurityEstimator},
+    srs_file::{FileBackedSrs, SrsFile, SrsFileBuilder},
+    transcript::parameters_digest,
+    *,
+};
 use ark_crypto_primitives::sponge::Absorb;
-use ark_ec::{pairing::Pairing, AdditiveGroup, AffineRepr};
-use ark_ff::{PrimeField, ToConstraintField};
+use ark_ec::{pairing::Pairing, AdditiveGroup, AffineRepr, CurveGroup};
+use ark_ff::{One, PrimeField, ToConstraintField};
+use ark_poly::{EvaluationDomain, Evaluations as UVEvaluations};
 use ark_serialize::{
     CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
 };
+#[cfg(not(feature = "std"))]
+use ark_std::string::ToString;
 use ark_std::{
     borrow::Cow,
+    format,
     io::{Read, Write},
     marker::PhantomData,
     ops::{Add, AddAssign},
@@ -55,6 +64,26 @@ impl<E: Pairing> PCUniversalParams for UniversalParams<E> {
     }
 }
 
+impl<E: Pairing> SecurityEstimator for UniversalParams<E> {
+    fn estimate_security(
+        &self,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<SecurityEstimate, Error> {
+        if poly_len > self.powers_of_g.len() {
+            return Err(Error::TooManyCoefficients {
+                num_coefficients: poly_len,
+                num_powers: self.powers_of_g.len(),
+            });
+        }
+        Ok(pairing_scheme_security::<E>(
+            poly_len,
+            self.max_degree(),
+            assumptions,
+        ))
+    }
+}
+
 impl<E: Pairing> CanonicalSerialize for UniversalParams<E> {
     fn serialize_with_mode<W: Write>(
         &self,
@@ -112,6 +141,125 @@ impl<E: Pairing> CanonicalDeserialize for UniversalParams<E> {
     }
 }
 
+/// Checks that the keys of `map` are `0, 1, ...`, so that its values can be
+/// stored as a section of an SRS file.
+fn check_contiguous_keys<G>(map: &BTreeMap<usize, G>) -> Result<(), Error> {
+    if !map.keys().copied().eq(0..map.len()) {
+        return Err(Error::InvalidParameters(
+            "the powers must be indexed by 0, 1, ...".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The file holds `powers_of_g`, and the values of `powers_of_gamma_g` and of
+/// `neg_powers_of_h`, as three sections.
+impl<E: Pairing> FileBackedSrs for UniversalParams<E> {
+    const TAG: [u8; 8] = *b"KZG10\0\0\0";
+
+    fn build<'a>(&'a self, builder: &mut SrsFileBuilder<'a>) -> Result<(), Error> {
+        check_contiguous_keys(&self.powers_of_gamma_g)?;
+        check_contiguous_keys(&self.neg_powers_of_h)?;
+        builder.metadata(&(self.h, self.beta_h))?;
+        builder.section(&self.powers_of_g);
+        builder.section_with(self.powers_of_gamma_g.len(), move |i| {
+            &self.powers_of_gamma_g[&i]
+        });
+        builder.section_with(self.neg_powers_of_h.len(), move |i| {
+            &self.neg_powers_of_h[&i]
+        });
+        Ok(())
+    }
+
+    fn validate(file: &SrsFile<'_, Self>) -> Result<(), Error> {
+        file.metadata::<(E::G2Affine, E::G2Affine)>()?;
+        file.validate_section::<E::G1Affine>(0)?;
+        file.validate_section::<E::G1Affine>(1)?;
+        file.validate_section::<E::G2Affine>(2)
+    }
+
+    /// Reads the parameters for polynomials of degree at most `max_degree`:
+    /// the first `max_degree + 1` powers of each kind, and one more power of
+    /// `gamma_g` for hiding.
+    fn load(file: &SrsFile<'_, Self>, max_degree: usize) -> Result<Self, Error> {
+        let (h, beta_h) = file.metadata::<(E::G2Affine, E::G2Affine)>()?;
+        let len = |i| file.section_len(i).unwrap_or(0);
+        if max_degree >= len(0) {
+            return Err(Error::TrimmingDegreeTooLarge);
+        }
+        let powers_of_g = file.read_section(0, 0..max_degree + 1)?;
+        let powers_of_gamma_g = file
+            .read_section(1, 0..len(1).min(max_degree + 2))?
+            .into_iter()
+            .enumerate()
+            .collect();
+        let neg_powers_of_h = file
+            .read_section(2, 0..len(2).min(max_degree + 1))?
+            .into_iter()
+            .enumerate()
+            .collect();
+
+        Ok(Self {
+            powers_of_g,
+            powers_of_gamma_g,
+            h,
+            beta_h,
+            neg_powers_of_h,
+            prepared_h: E::G2Prepared::from(h),
+            prepared_beta_h: E::G2Prepared::from(beta_h),
+        })
+    }
+}
+
+impl<E: Pairing> SrsFile<'_, UniversalParams<E>> {
+    /// Reads the powers for polynomials of degree at most `supported_degree`,
+    /// and the verifier key.
+    ///
+    /// The powers are decoded once from the file into a [`FilePowers`], which
+    /// the [`Powers`] used to commit borrow.
+    pub fn trim(&self, supported_degree: usize) -> Result<(FilePowers<E>, VerifierKey<E>), Error> {
+        let (h, beta_h) = self.metadata::<(E::G2Affine, E::G2Affine)>()?;
+        let len = |i| self.section_len(i).unwrap_or(0);
+        if supported_degree >= len(0) || supported_degree >= len(1) {
+            return Err(Error::TrimmingDegreeTooLarge);
+        }
+        let powers = FilePowers {
+            powers_of_g: self.read_section(0, 0..supported_degree + 1)?,
+            powers_of_gamma_g: self.read_section(1, 0..supported_degree + 1)?,
+        };
+
+        let vk = VerifierKey {
+            g: powers.powers_of_g[0],
+            gamma_g: powers.powers_of_gamma_g[0],
+            h,
+            beta_h,
+            prepared_h: E::G2Prepared::from(h),
+            prepared_beta_h: E::G2Prepared::from(beta_h),
+        };
+        Ok((powers, vk))
+    }
+}
+
+/// The powers decoded from an [`SrsFile`] by [`SrsFile::trim`], which can be
+/// shared between the [`Powers`] that borrow them. They are copies of the
+/// points of the file, not views into its bytes.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), PartialEq(bound = ""))]
+pub struct FilePowers<E: Pairing> {
+    powers_of_g: Vec<E::G1Affine>,
+    powers_of_gamma_g: Vec<E::G1Affine>,
+}
+
+impl<E: Pairing> FilePowers<E> {
+    /// The powers to commit with, borrowed from `self`.
+    pub fn powers(&self) -> Powers<'_, E> {
+        Powers {
+            powers_of_g: self.powers_of_g.as_slice().into(),
+            powers_of_gamma_g: self.powers_of_gamma_g.as_slice().into(),
+        }
+    }
+}
+
 /// `Powers` is used to commit to and create evaluation proofs for a given
 /// polynomial.
 #[derive(Derivative)]
@@ -176,6 +324,80 @@ impl<'a, E: Pairing> CanonicalDeserialize for Powers<'a, E> {
         Ok(result)
     }
 }
+
+/// `LagrangePowers` is used to commit to and create evaluation proofs for
+/// polynomials given by their evaluations over `domain`.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), PartialEq(bound = ""))]
+pub struct LagrangePowers<'a, E: Pairing, D: EvaluationDomain<E::ScalarField>> {
+    /// The domain over which the committed polynomials are evaluated.
+    pub domain: D,
+    /// Group elements of the form `L_i(β) G`, where `L_i` is the `i`-th
+    /// Lagrange polynomial of `domain`.
+    pub lagrange_powers_of_g: Cow<'a, [E::G1Affine]>,
+    /// Group elements of the form `β^i γG`, for different values of `i`.
+    /// Hiding polynomials are kept in coefficient form.
+    pub powers_of_gamma_g: Cow<'a, [E::G1Affine]>,
+}
+
+impl<'a, E: Pairing, D: EvaluationDomain<E::ScalarField>> LagrangePowers<'a, E, D> {
+    /// Converts the first `domain.size()` powers of `powers` to the Lagrange
+    /// basis of `domain` with an inverse FFT over the group.
+    pub fn new(powers: &Powers<'a, E>, domain: D) -> Result<Self, Error> {
+        let size = domain.size();
+        if powers.size() < size {
+            return Err(Error::TooManyCoefficients {
+                num_coefficients: size,
+                num_powers: powers.size(),
+            });
+        }
+        // The `i`-th Lagrange polynomial of a coset `cH` maps `X` to the `i`-th
+        // Lagrange polynomial of `H` at `X / c`.
+        let subgroup = domain
+            .get_coset(E::ScalarField::one())
+            .ok_or(Error::InvalidParameters(
+                "The domain is not a coset of a subgroup".to_string(),
+            ))?;
+        let mut lagrange_powers_of_g: Vec<E::G1> = powers.powers_of_g[..size]
+            .iter()
+            .map(|g| g.into_group())
+            .collect();
+        D::distribute_powers(&mut lagrange_powers_of_g, domain.coset_offset_inv());
+        subgroup.ifft_in_place(&mut lagrange_powers_of_g);
+
+        Ok(Self {
+            domain,
+            lagrange_powers_of_g: Cow::Owned(E::G1::normalize_batch(&lagrange_powers_of_g)),
+            powers_of_gamma_g: powers.powers_of_gamma_g.clone(),
+        })
+    }
+
+    /// The number of evaluations that `self` can commit to.
+    pub fn size(&self) -> usize {
+        self.lagrange_powers_of_g.len()
+    }
+
+    /// Checks that `evaluations` are over the domain of `self`.
+    pub(crate) fn check_evaluations(
+        &self,
+        evaluations: &UVEvaluations<E::ScalarField, D>,
+    ) -> Result<(), Error> {
+        if evaluations.domain() != self.domain {
+            Err(Error::InvalidParameters(
+                "The evaluations are not over the domain of the committer key".to_string(),
+            ))
+        } else if evaluations.evals.len() != self.size() {
+            Err(Error::IncorrectInputLength(format!(
+                "Expected {} evaluations, got {}",
+                self.size(),
+                evaluations.evals.len()
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
+
 /// `VerifierKey` is used to check evaluation proofs for a given commitment.
 #[derive(Derivative)]
 #[derivative(
@@ -510,3 +732,22 @@ pub struct Proof<E: Pairing> {
     /// the evaluation proof was produced.
     pub random_v: Option<E::ScalarField>,
 }
+
+/// The parameters of a scheme based on KZG10 that are bound into the
+/// transcript of its openings: the digest of its whole verifier key, which
+/// the committer key records when both are trimmed. A proof is thus bound to
+/// every element that its check pairs with, `h` and `beta_h` included.
+#[derive(CanonicalSerialize)]
+pub(crate) struct TranscriptParameters {
+    /// The digest of the compressed serialization of the verifier key.
+    pub(crate) verifier_key_digest: Vec<u8>,
+}
+
+impl TranscriptParameters {
+    /// Outputs the parameters of the scheme with verifier key `vk`.
+    pub(crate) fn new<T: CanonicalSerialize>(vk: &T) -> Result<Self, Error> {
+        Ok(Self {
+            verifier_key_digest: parameters_digest(vk)?,
+        })
+    }
+}
@@ -5,11 +5,17 @@
 //! proposed by Kate, Zaverucha, and Goldberg ([KZG10](http://cacr.uwaterloo.ca/techreports/2010/cacr2010-10.pdf)).
 //! This construction achieves extractability in the algebraic group model (AGM).
 //!
-use crate::{data_structures::PCCommitmentState, BTreeMap, Error, LabeledPolynomial};
+use crate::{
+    data_structures::PCCommitmentState, BTreeMap, BTreeSet, Error, LabeledEvaluations,
+    LabeledPolynomial, QuerySet,
+};
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::{pairing::Pairing, scalar_mul::ScalarMul, AffineRepr, CurveGroup, VariableBaseMSM};
-use ark_ff::{One, PrimeField, UniformRand, Zero};
-use ark_poly::DenseUVPolynomial;
+use ark_ff::{batch_inversion, FftField, One, PrimeField, UniformRand, Zero};
+use ark_poly::{
+    domain::DomainCoeff, DenseUVPolynomial, EvaluationDomain, Evaluations as UVEvaluations,
+    GeneralEvaluationDomain,
+};
 use ark_std::{format, marker::PhantomData, ops::Div, ops::Mul, rand::RngCore};
 #[cfg(not(feature = "std"))]
 use ark_std::{string::ToString, vec::Vec};
@@ -56,12 +62,23 @@ where
         max_degree: usize,
         produce_g2_powers: bool,
         rng: &mut R,
+    ) -> Result<UniversalParams<E>, Error> {
+        phase_span!("KZG10::setup", max_degree, produce_g2_powers);
+        let beta = E::ScalarField::rand(rng);
+        Self::setup_with_trapdoor(max_degree, produce_g2_powers, beta, rng)
+    }
+
+    /// Constructs public parameters as in `setup`, for the trapdoor `beta`.
+    pub(crate) fn setup_with_trapdoor<R: RngCore>(
+        max_degree: usize,
+        produce_g2_powers: bool,
+        beta: E::ScalarField,
+        rng: &mut R,
     ) -> Result<UniversalParams<E>, Error> {
         if max_degree < 1 {
             return Err(Error::DegreeIsZero);
         }
         let setup_time = start_timer!(|| format!("KZG10::Setup with degree {}", max_degree));
-        let beta = E::ScalarField::rand(rng);
         let g = E::G1::rand(rng);
         let gamma_g = E::G1::rand(rng);
         let h = E::G2::rand(rng);
@@ -162,6 +179,7 @@ where
         hiding_bound: Option<usize>,
         rng: Option<&mut dyn RngCore>,
     ) -> Result<(Commitment<E>, Randomness<E::ScalarField, P>), Error> {
+        phase_span!("KZG10::commit", degree = polynomial.degree(), hiding_bound = ?hiding_bound);
         Self::check_degree_is_too_large(polynomial.degree(), powers.size())?;
 
         let commit_time = start_timer!(|| format!(
@@ -178,8 +196,60 @@ where
             &powers.powers_of_g[num_leading_zeros..],
             &plain_coeffs,
         );
+        count!(MSM, plain_coeffs.len());
         end_timer!(msm_time);
 
+        let (random_commitment, randomness) =
+            Self::commit_randomness(&powers.powers_of_gamma_g, hiding_bound, rng)?;
+        commitment += &random_commitment;
+
+        end_timer!(commit_time);
+        Ok((Commitment(commitment.into()), randomness))
+    }
+
+    /// Outputs a commitment to the polynomial whose only non-zero coefficients
+    /// are given by `coeffs`, as pairs `(i, c_i)` for the coefficient of `x^i`.
+    /// This costs a multi-scalar multiplication of the size of `coeffs`,
+    /// whatever the degree of the polynomial.
+    #[allow(clippy::type_complexity)]
+    pub fn commit_sparse(
+        powers: &Powers<E>,
+        coeffs: &[(usize, E::ScalarField)],
+        hiding_bound: Option<usize>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(Commitment<E>, Randomness<E::ScalarField, P>), Error> {
+        if let Some(degree) = coeffs.iter().map(|(i, _)| *i).max() {
+            Self::check_degree_is_too_large(degree, powers.size())?;
+        }
+
+        phase_span!("KZG10::commit_sparse", num_coeffs = coeffs.len(), hiding_bound = ?hiding_bound);
+        let commit_time = start_timer!(|| format!(
+            "Committing to {} coefficients with hiding_bound: {:?}",
+            coeffs.len(),
+            hiding_bound,
+        ));
+
+        let bases: Vec<_> = coeffs.iter().map(|(i, _)| powers.powers_of_g[*i]).collect();
+        let scalars: Vec<_> = coeffs.iter().map(|(_, c)| *c).collect();
+        let mut commitment = <E::G1 as VariableBaseMSM>::msm(&bases, &scalars).unwrap();
+        count!(MSM, bases.len());
+
+        let (random_commitment, randomness) =
+            Self::commit_randomness(&powers.powers_of_gamma_g, hiding_bound, rng)?;
+        commitment += &random_commitment;
+
+        end_timer!(commit_time);
+        Ok((Commitment(commitment.into()), randomness))
+    }
+
+    /// Samples a blinding polynomial of degree `hiding_bound`, if any, and
+    /// outputs its commitment under `powers_of_gamma_g`.
+    #[allow(clippy::type_complexity)]
+    fn commit_randomness(
+        powers_of_gamma_g: &[E::G1Affine],
+        hiding_bound: Option<usize>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(E::G1Affine, Randomness<E::ScalarField, P>), Error> {
         let mut randomness = Randomness::<E::ScalarField, P>::empty();
         if let Some(hiding_degree) = hiding_bound {
             let mut rng = rng.ok_or(Error::MissingRng)?;
@@ -191,24 +261,20 @@ where
             randomness = Randomness::rand(hiding_degree, false, None, &mut rng);
             Self::check_hiding_bound(
                 randomness.blinding_polynomial.degree(),
-                powers.powers_of_gamma_g.len(),
+                powers_of_gamma_g.len(),
             )?;
             end_timer!(sample_random_poly_time);
         }
 
-        let random_ints = convert_to_bigints(&randomness.blinding_polynomial.coeffs());
+        let random_ints = convert_to_bigints(randomness.blinding_polynomial.coeffs());
         let msm_time = start_timer!(|| "MSM to compute commitment to random poly");
-        let random_commitment = <E::G1 as VariableBaseMSM>::msm_bigint(
-            &powers.powers_of_gamma_g,
-            random_ints.as_slice(),
-        )
-        .into_affine();
+        let random_commitment =
+            <E::G1 as VariableBaseMSM>::msm_bigint(powers_of_gamma_g, random_ints.as_slice())
+                .into_affine();
+        count!(MSM, random_ints.len());
         end_timer!(msm_time);
 
-        commitment += &random_commitment;
-
-        end_timer!(commit_time);
-        Ok((Commitment(commitment.into()), randomness))
+        Ok((random_commitment, randomness))
     }
 
     /// Compute witness polynomial.
@@ -257,6 +323,7 @@ where
             &powers.powers_of_g[num_leading_zeros..],
             &witness_coeffs,
         );
+        count!(MSM, witness_coeffs.len());
         end_timer!(witness_comm_time);
 
         let random_v = if let Some(hiding_witness_polynomial) = hiding_witness_polynomial {
@@ -272,6 +339,7 @@ where
                 &powers.powers_of_gamma_g,
                 &random_witness_coeffs,
             );
+            count!(MSM, random_witness_coeffs.len());
             end_timer!(witness_comm_time);
             Some(blinding_evaluation)
         } else {
@@ -292,6 +360,7 @@ where
         rand: &Randomness<E::ScalarField, P>,
     ) -> Result<Proof<E>, Error> {
         Self::check_degree_is_too_large(p.degree(), powers.size())?;
+        phase_span!("KZG10::open", degree = p.degree());
         let open_time = start_timer!(|| format!("Opening polynomial of degree {}", p.degree()));
 
         let witness_time = start_timer!(|| "Computing witness polynomials");
@@ -310,6 +379,164 @@ where
         proof
     }
 
+    /// Outputs a commitment to the polynomial given by its `evaluations` over
+    /// the domain of `powers`. The commitment is the same as the one output by
+    /// `commit` on the interpolated polynomial, and is checked with the same
+    /// verifier key.
+    #[allow(clippy::type_complexity)]
+    pub fn commit_evaluations<D: EvaluationDomain<E::ScalarField>>(
+        powers: &LagrangePowers<E, D>,
+        evaluations: &UVEvaluations<E::ScalarField, D>,
+        hiding_bound: Option<usize>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(Commitment<E>, Randomness<E::ScalarField, P>), Error> {
+        powers.check_evaluations(evaluations)?;
+
+        phase_span!(
+            "KZG10::commit_evaluations",
+            num_evaluations = evaluations.evals.len(),
+            hiding_bound = ?hiding_bound,
+        );
+        let commit_time = start_timer!(|| format!(
+            "Committing to {} evaluations with hiding_bound: {:?}",
+            evaluations.evals.len(),
+            hiding_bound,
+        ));
+
+        let msm_time = start_timer!(|| "MSM to compute commitment to plaintext evaluations");
+        let mut commitment = <E::G1 as VariableBaseMSM>::msm_bigint(
+            &powers.lagrange_powers_of_g,
+            &convert_to_bigints(&evaluations.evals),
+        );
+        count!(MSM, evaluations.evals.len());
+        end_timer!(msm_time);
+
+        let (random_commitment, randomness) =
+            Self::commit_randomness(&powers.powers_of_gamma_g, hiding_bound, rng)?;
+        commitment += &random_commitment;
+
+        end_timer!(commit_time);
+        Ok((Commitment(commitment.into()), randomness))
+    }
+
+    /// On input the `evaluations` of a polynomial over the domain of `powers`
+    /// and a point `point`, outputs a proof for the same. The evaluations of the
+    /// witness polynomial are computed from barycentric quotients, without
+    /// interpolating the polynomial.
+    pub fn open_evaluations<D: EvaluationDomain<E::ScalarField>>(
+        powers: &LagrangePowers<E, D>,
+        evaluations: &UVEvaluations<E::ScalarField, D>,
+        point: E::ScalarField,
+        rand: &Randomness<E::ScalarField, P>,
+    ) -> Result<Proof<E>, Error> {
+        powers.check_evaluations(evaluations)?;
+        phase_span!(
+            "KZG10::open_evaluations",
+            num_evaluations = evaluations.evals.len()
+        );
+        let open_time = start_timer!(|| format!(
+            "Opening polynomial given by {} evaluations",
+            evaluations.evals.len()
+        ));
+
+        let witness_time = start_timer!(|| "Computing witness evaluations");
+        let value = evaluate_evaluations(evaluations, point);
+        let witness_evals = compute_witness_evaluations(evaluations, point, value);
+        end_timer!(witness_time);
+
+        let witness_comm_time = start_timer!(|| "Computing commitment to witness polynomial");
+        let mut w = <E::G1 as VariableBaseMSM>::msm_bigint(
+            &powers.lagrange_powers_of_g,
+            &convert_to_bigints(&witness_evals),
+        );
+        count!(MSM, witness_evals.len());
+        end_timer!(witness_comm_time);
+
+        let random_v = if rand.is_hiding() {
+            let blinding_p = &rand.blinding_polynomial;
+            let divisor = P::from_coefficients_vec(vec![-point, E::ScalarField::one()]);
+            let random_witness_polynomial = blinding_p / &divisor;
+            let random_witness_coeffs = convert_to_bigints(random_witness_polynomial.coeffs());
+            w += &<E::G1 as VariableBaseMSM>::msm_bigint(
+                &powers.powers_of_gamma_g,
+                &random_witness_coeffs,
+            );
+            count!(MSM, random_witness_coeffs.len());
+            Some(blinding_p.evaluate(&point))
+        } else {
+            None
+        };
+
+        end_timer!(open_time);
+        Ok(Proof {
+            w: w.into_affine(),
+            random_v,
+        })
+    }
+
+    /// On input a polynomial `p` and an evaluation domain `domain`, outputs a
+    /// proof for every element of `domain`, in the order of `domain.elements()`.
+    /// The proofs are the same as those output by `open`, but are computed in
+    /// `O(n log n)` group operations with the amortised algorithm of
+    /// [[FK20]][fk20].
+    ///
+    /// [fk20]: https://eprint.iacr.org/2023/033
+    pub fn open_all<D: EvaluationDomain<E::ScalarField>>(
+        powers: &Powers<E>,
+        p: &P,
+        domain: D,
+        rand: &Randomness<E::ScalarField, P>,
+    ) -> Result<Vec<Proof<E>>, Error> {
+        Self::check_degree_is_too_large(p.degree(), powers.size())?;
+        phase_span!(
+            "KZG10::open_all",
+            degree = p.degree(),
+            num_points = domain.size()
+        );
+        let open_time = start_timer!(|| format!(
+            "Opening polynomial of degree {} at {} points",
+            p.degree(),
+            domain.size()
+        ));
+
+        let witness_time = start_timer!(|| "Computing commitments to witness polynomials");
+        let mut w = all_witness_commitments::<E::G1, D>(&powers.powers_of_g, p.coeffs(), domain)?;
+        end_timer!(witness_time);
+
+        let random_v = if rand.is_hiding() {
+            let blinding_p = &rand.blinding_polynomial;
+            let witness_time =
+                start_timer!(|| "Computing commitments to random witness polynomials");
+            let random_w = all_witness_commitments::<E::G1, D>(
+                &powers.powers_of_gamma_g,
+                blinding_p.coeffs(),
+                domain,
+            )?;
+            end_timer!(witness_time);
+            ark_std::cfg_iter_mut!(w)
+                .zip(random_w)
+                .for_each(|(w, r)| *w += r);
+            Some(coset_fft(blinding_p.coeffs().to_vec(), domain)?)
+        } else {
+            None
+        };
+
+        let w = E::G1::normalize_batch(&w);
+        let proofs = match random_v {
+            Some(random_v) => w
+                .into_iter()
+                .zip(random_v)
+                .map(|(w, v)| Proof {
+                    w,
+                    random_v: Some(v),
+                })
+                .collect(),
+            None => w.into_iter().map(|w| Proof { w, random_v: None }).collect(),
+        };
+        end_timer!(open_time);
+        Ok(proofs)
+    }
+
     /// Verifies that `value` is the evaluation at `point` of the polynomial
     /// committed inside `comm`.
     pub fn check(
@@ -319,6 +546,7 @@ where
         value: E::ScalarField,
         proof: &Proof<E>,
     ) -> Result<bool, Error> {
+        phase_span!("KZG10::check");
         let check_time = start_timer!(|| "Checking evaluation");
         let mut inner = comm.0.into_group() - &vk.g.mul(value);
         if let Some(random_v) = proof.random_v {
@@ -328,6 +556,7 @@ where
 
         let inner = vk.beta_h.into_group() - &vk.h.mul(point);
         let rhs = E::pairing(proof.w, inner);
+        count!(PAIRING, 2);
 
         end_timer!(check_time, || format!("Result: {}", lhs == rhs));
         Ok(lhs == rhs)
@@ -343,6 +572,7 @@ where
         proofs: &[Proof<E>],
         rng: &mut R,
     ) -> Result<bool, Error> {
+        phase_span!("KZG10::batch_check", num_proofs = proofs.len());
         let check_time =
             start_timer!(|| format!("Checking {} evaluation proofs", commitments.len()));
 
@@ -386,6 +616,7 @@ where
         )
         .0
         .is_one();
+        count!(PAIRING, 2);
         end_timer!(pairing_time);
         end_timer!(check_time, || format!("Result: {}", result));
         Ok(result)
@@ -450,6 +681,169 @@ where
     }
 }
 
+/// Evaluates at `point` the polynomial given by its `evaluations` over their
+/// domain, with the barycentric formula.
+pub fn evaluate_evaluations<F: FftField, D: EvaluationDomain<F>>(
+    evaluations: &UVEvaluations<F, D>,
+    point: F,
+) -> F {
+    let lagrange_coeffs = evaluations
+        .domain()
+        .evaluate_all_lagrange_coefficients(point);
+    ark_std::cfg_iter!(lagrange_coeffs)
+        .zip(&evaluations.evals)
+        .map(|(l, e)| *l * e)
+        .sum()
+}
+
+/// Computes the evaluations over their domain of the witness polynomial
+/// `(p(X) - value) / (X - point)`, where `p` is given by its `evaluations` and
+/// `value = p(point)`.
+fn compute_witness_evaluations<F: FftField, D: EvaluationDomain<F>>(
+    evaluations: &UVEvaluations<F, D>,
+    point: F,
+    value: F,
+) -> Vec<F> {
+    let elements: Vec<F> = evaluations.domain().elements().collect();
+    let mut denominators: Vec<F> = elements.iter().map(|x| *x - point).collect();
+    let point_index = denominators.iter().position(|d| d.is_zero());
+    // Zero denominators are left untouched.
+    batch_inversion(&mut denominators);
+
+    let mut witness_evals: Vec<F> = ark_std::cfg_iter!(evaluations.evals)
+        .zip(denominators)
+        .map(|(e, d)| (*e - value) * d)
+        .collect();
+
+    // If `point = x_m` lies in the domain, the quotient at `x_m` is recovered from
+    // the others: the witness polynomial has degree at most `n - 2`, so that
+    // `sum_i q(x_i) / Z'(x_i) = 0`, where `Z'(x_i) = n c^n / x_i` for the
+    // vanishing polynomial `Z(X) = X^n - c^n` of a coset `cH`.
+    if let Some(m) = point_index {
+        let sum: F = ark_std::cfg_iter!(witness_evals)
+            .zip(&elements)
+            .map(|(q, x)| *q * x)
+            .sum();
+        witness_evals[m] = -sum * point.inverse().unwrap();
+    }
+    witness_evals
+}
+
+/// Computes `h_m = sum_{k > m} coeffs[k] bases[k - m - 1]` for `m < deg`,
+/// so that the commitment to the witness polynomial of `coeffs` at `z` is
+/// `sum_m z^m h_m`. This is the Toeplitz matrix-vector product of [[FK20]][fk20],
+/// computed as a cyclic convolution with FFTs over the group.
+///
+/// [fk20]: https://eprint.iacr.org/2023/033
+fn toeplitz_witness_coefficients<G: CurveGroup>(
+    bases: &[G::Affine],
+    coeffs: &[G::ScalarField],
+) -> Result<Vec<G>, Error> {
+    let degree = coeffs.len().saturating_sub(1);
+    if degree == 0 {
+        return Ok(Vec::new());
+    }
+    let fft_domain = GeneralEvaluationDomain::<G::ScalarField>::new(2 * degree).ok_or(
+        Error::InvalidParameters(format!(
+            "The field has no evaluation domain of size {}",
+            2 * degree
+        )),
+    )?;
+
+    // `h_m` is the coefficient `degree + m` of the product of `coeffs` with the
+    // reversed bases.
+    let mut h: Vec<G> = bases[..degree]
+        .iter()
+        .rev()
+        .map(|b| b.into_group())
+        .collect();
+    fft_domain.fft_in_place(&mut h);
+    let coeffs = fft_domain.fft(coeffs);
+    ark_std::cfg_iter_mut!(h)
+        .zip(coeffs)
+        .for_each(|(h, c)| *h *= c);
+    fft_domain.ifft_in_place(&mut h);
+
+    Ok(h[degree..2 * degree].to_vec())
+}
+
+/// Evaluates the polynomial with coefficients `coeffs` over `domain`, which
+/// may be smaller than the number of coefficients.
+fn coset_fft<F, T, D>(mut coeffs: Vec<T>, domain: D) -> Result<Vec<T>, Error>
+where
+    F: FftField,
+    T: DomainCoeff<F>,
+    D: EvaluationDomain<F>,
+{
+    let subgroup = domain.get_coset(F::one()).ok_or(Error::InvalidParameters(
+        "The domain is not a coset of a subgroup".to_string(),
+    ))?;
+    D::distribute_powers(&mut coeffs, domain.coset_offset());
+
+    // Reduce the polynomial modulo `X^n - 1`, which vanishes on the subgroup.
+    let size = domain.size();
+    let mut evals = vec![T::zero(); size];
+    for (i, c) in coeffs.into_iter().enumerate() {
+        evals[i % size] += c;
+    }
+    subgroup.fft_in_place(&mut evals);
+    Ok(evals)
+}
+
+/// Computes the commitments to the witness polynomials of `coeffs` at every
+/// element of `domain`, with respect to `bases`.
+fn all_witness_commitments<G: CurveGroup, D: EvaluationDomain<G::ScalarField>>(
+    bases: &[G::Affine],
+    coeffs: &[G::ScalarField],
+    domain: D,
+) -> Result<Vec<G>, Error> {
+    coset_fft(toeplitz_witness_coefficients::<G>(bases, coeffs)?, domain)
+}
+
+/// Opens the `labeled_evaluations` at the points of `query_set`, gathering all
+/// polynomials queried at the same point into a single call to `open`, as done
+/// by the default `PolynomialCommitment::batch_open`.
+pub(crate) fn batch_open_evaluations<'a, F, D, S, T>(
+    labeled_evaluations: impl IntoIterator<Item = &'a LabeledEvaluations<F, D>>,
+    states: impl IntoIterator<Item = &'a S>,
+    query_set: &QuerySet<F>,
+    mut open: impl FnMut(&[&'a LabeledEvaluations<F, D>], &[&'a S], F) -> Result<T, Error>,
+) -> Result<Vec<T>, Error>
+where
+    F: FftField,
+    D: EvaluationDomain<F> + 'a,
+    S: 'a,
+{
+    let evals_st: BTreeMap<_, _> = labeled_evaluations
+        .into_iter()
+        .zip(states)
+        .map(|(evals, st)| (evals.label(), (evals, st)))
+        .collect();
+
+    let mut query_to_labels_map = BTreeMap::new();
+    for (label, (point_label, point)) in query_set.iter() {
+        let labels = query_to_labels_map
+            .entry(point_label)
+            .or_insert((point, BTreeSet::new()));
+        labels.1.insert(label);
+    }
+
+    let mut proofs = Vec::new();
+    for (_point_label, (point, labels)) in query_to_labels_map.into_iter() {
+        let mut query_evals = Vec::new();
+        let mut query_states = Vec::new();
+        for label in labels {
+            let (evals, st) = evals_st.get(label).ok_or(Error::MissingPolynomial {
+                label: label.to_string(),
+            })?;
+            query_evals.push(*evals);
+            query_states.push(*st);
+        }
+        proofs.push(open(&query_evals, &query_states, *point)?);
+    }
+    Ok(proofs)
+}
+
 fn skip_leading_zeros_and_convert_to_bigints<F: PrimeField, P: DenseUVPolynomial<F>>(
     p: &P,
 ) -> (usize, Vec<F::BigInt>) {
@@ -480,6 +874,7 @@ mod tests {
     use ark_bls12_381::Bls12_381;
     use ark_bls12_381::Fr;
     use ark_poly::univariate::DensePolynomial as DensePoly;
+    use ark_poly::GeneralEvaluationDomain;
     use ark_std::test_rng;
 
     type UniPoly_381 = DensePoly<<Bls12_381 as Pairing>::ScalarField>;
@@ -645,6 +1040,37 @@ mod tests {
         Ok(())
     }
 
+    fn open_all_test_template<E, P>() -> Result<(), Error>
+    where
+        E: Pairing,
+        E::G1Affine: Absorb,
+        P: DenseUVPolynomial<E::ScalarField, Point = E::ScalarField>,
+        for<'a, 'b> &'a P: Div<&'b P, Output = P>,
+    {
+        let rng = &mut test_rng();
+        // Domains larger than, smaller than and as large as the polynomial.
+        for (degree, domain_size) in [(12, 16), (20, 8), (7, 8)] {
+            let pp = KZG10::<E, P>::setup(degree, false, rng)?;
+            let (ck, vk) = KZG10::<E, P>::trim(&pp, degree)?;
+            let p = P::rand(degree, rng);
+            let subgroup = GeneralEvaluationDomain::<E::ScalarField>::new(domain_size).unwrap();
+            let coset = subgroup.get_coset(E::ScalarField::GENERATOR).unwrap();
+            for hiding_bound in [None, Some(1)] {
+                let (comm, rand) = KZG10::<E, P>::commit(&ck, &p, hiding_bound, Some(rng))?;
+                for domain in [subgroup, coset] {
+                    let proofs = KZG10::<E, P>::open_all(&ck, &p, domain, &rand)?;
+                    assert_eq!(proofs.len(), domain.size());
+                    for (point, proof) in domain.elements().zip(&proofs) {
+                        assert_eq!(*proof, KZG10::<E, P>::open(&ck, &p, point, &rand)?);
+                        let value = p.evaluate(&point);
+                        assert!(KZG10::<E, P>::check(&vk, &comm, point, value, proof)?);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn end_to_end_test() {
         end_to_end_test_template::<Bls12_377, UniPoly_377>().expect("test failed for bls12-377");
@@ -664,6 +1090,12 @@ mod tests {
         batch_check_test_template::<Bls12_381, UniPoly_381>().expect("test failed for bls12-381");
     }
 
+    #[test]
+    fn open_all_test() {
+        open_all_test_template::<Bls12_377, UniPoly_377>().expect("test failed for bls12-377");
+        open_all_test_template::<Bls12_381, UniPoly_381>().expect("test failed for bls12-381");
+    }
+
     #[test]
     fn test_degree_is_too_large() {
         let rng = &mut test_rng();
@@ -17,6 +17,11 @@ extern crate derivative;
 #[macro_use]
 extern crate ark_std;
 
+/// Structured instrumentation of the schemes with `tracing` spans and
+/// counters.
+#[macro_use]
+pub mod instrumentation;
+
 use ark_ff::{Field, PrimeField};
 pub use ark_poly::{DenseUVPolynomial, Polynomial};
 use ark_std::{
@@ -68,11 +73,15 @@ pub mod marlin;
 /// checker.
 pub mod optional_rng;
 
-#[cfg(not(feature = "std"))]
-macro_rules! eprintln {
-    () => {};
-    ($($arg: tt)*) => {};
-}
+/// A random number generator for the hiding commitments and openings, derived
+/// deterministically from a secret seed, the polynomials and the transcript.
+pub mod deterministic_rng;
+
+/// Labelled Fiat–Shamir transcripts, which bind the openings of each scheme
+/// to the scheme and its parameters.
+pub mod transcript;
+pub use transcript::Transcript;
+
 #[cfg(all(test, not(feature = "std")))]
 macro_rules! println {
     () => {};
@@ -83,6 +92,13 @@ macro_rules! println {
 /// [kzg]: http://cacr.uwaterloo.ca/techreports/2010/cacr2010-10.pdf
 pub mod kzg10;
 
+/// Commitments to bivariate polynomials from [[KZG10]][kzg] with two
+/// trapdoors, with openings at a point and partial openings in either
+/// variable.
+///
+/// [kzg]: http://cacr.uwaterloo.ca/techreports/2010/cacr2010-10.pdf
+pub mod bivariate_kzg;
+
 /// Polynomial commitment scheme from [[KZG10]][kzg] that enforces
 /// strict degree bounds and (optionally) enables hiding commitments by
 /// following the approach outlined in [[CHMMVW20, "Marlin"]][marlin].
@@ -151,6 +167,64 @@ pub mod linear_codes;
 /// [hyrax]: https://eprint.iacr.org/2017/1132.pdf
 pub mod hyrax;
 
+/// Concrete security estimates for the schemes of this crate, and
+/// parameter suggestions for a target security level.
+pub mod security;
+
+/// Static models of the proof sizes and of the prover and verifier costs of
+/// the schemes of this crate.
+pub mod cost;
+
+/// A file format for large universal parameters, which are validated once
+/// and then loaded lazily, and only in part, from the bytes of the file.
+pub mod srs_file;
+
+/// Commitments to univariate polynomials larger than the committer key of a
+/// scheme, split into chunks which fit in the key.
+pub mod chunked;
+
+/// Linear combinations of the commitments of additively homomorphic schemes,
+/// along with their commitment states.
+pub mod homomorphic;
+
+/// Openings of committed polynomials to evaluations which are themselves
+/// only given as hiding commitments.
+pub mod hidden;
+
+/// Updates of the commitments of homomorphic schemes after sparse changes of
+/// the committed polynomials.
+pub mod update;
+
+/// Vector commitments with openings at subsets of indices, derived from the
+/// polynomial commitment schemes of this crate or implemented natively.
+pub mod vector_commitment;
+
+/// Commitments to univariate polynomials with the multilinear schemes of this
+/// crate, through tensor queries on the coefficients.
+pub mod univariate_adapter;
+
+/// Openings of committed polynomials at tensor-structured linear functionals
+/// of their coefficients.
+pub mod tensor;
+
+/// Key/value commitments with Verkle trees of IPA commitments, whose proofs
+/// for many keys share a single inner-product argument.
+pub mod verkle;
+
+/// Commitments to sparse multilinear polynomials through dense commitments to
+/// their non-zero evaluations, with openings by offline memory checking.
+pub mod spark;
+
+/// Blob commitments over BLS12-381 as specified by EIP-4844 and EIP-7594, on
+/// top of the trusted setup of the Ethereum KZG ceremony.
+#[cfg(feature = "eip4844")]
+pub mod eip4844;
+
+/// Generation of Solidity verifiers for proofs of `MarlinKZG10` and
+/// `SonicKZG10` over BN254, with the encoding of their inputs.
+#[cfg(feature = "solidity")]
+pub mod solidity;
+
 /// `QuerySet` is the set of queries that are to be made to a set of labeled polynomials/equations
 /// `p` that have previously been committed to. Each element of a `QuerySet` is a pair of
 /// `(label, (point_label, point))`, where `label` is the label of a polynomial in `p`,
@@ -252,6 +326,9 @@ pub trait PolynomialCommitment<F: PrimeField, P: Polynomial<F>>: Sized {
         Self::Commitment: 'a;
 
     /// check but with individual challenges
+    ///
+    /// A rejected proof is reported as [`Error::VerificationFailed`], with
+    /// the reason of the rejection.
     fn check<'a>(
         vk: &Self::VerifierKey,
         commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
@@ -260,7 +337,7 @@ pub trait PolynomialCommitment<F: PrimeField, P: Polynomial<F>>: Sized {
         proof: &Self::Proof,
         sponge: &mut impl CryptographicSponge,
         rng: Option<&mut dyn RngCore>,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a;
 
@@ -377,6 +454,8 @@ pub trait PolynomialCommitment<F: PrimeField, P: Polynomial<F>>: Sized {
     /// `batch_open` for the same commitment list and query set).H
     ///
     /// The opening challenges are independent for each batch of polynomials.
+    /// As in `check`, a rejected proof is reported as
+    /// [`Error::VerificationFailed`].
     fn batch_check<'a, R: RngCore>(
         vk: &Self::VerifierKey,
         commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
@@ -385,7 +464,7 @@ pub trait PolynomialCommitment<F: PrimeField, P: Polynomial<F>>: Sized {
         proof: &Self::BatchProof,
         sponge: &mut impl CryptographicSponge,
         rng: &mut R,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
@@ -413,9 +492,18 @@ pub trait PolynomialCommitment<F: PrimeField, P: Polynomial<F>>: Sized {
         // Implicit assumption: proofs are ordered in same manner as queries in
         // `query_to_labels_map`.
         let proofs: Vec<_> = proof.clone().into();
-        assert_eq!(proofs.len(), query_to_labels_map.len());
+        if proofs.len() != query_to_labels_map.len() {
+            return Err(Error::from(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!(
+                    "expected {} proofs, one per point, found {}",
+                    query_to_labels_map.len(),
+                    proofs.len()
+                ),
+            })
+            .into());
+        }
 
-        let mut result = true;
         for ((_point_label, (point, labels)), proof) in query_to_labels_map.into_iter().zip(proofs)
         {
             // Constructing matching vectors with the commitment and claimed
@@ -441,10 +529,10 @@ pub trait PolynomialCommitment<F: PrimeField, P: Polynomial<F>>: Sized {
 
             // Verify all proofs referring to the current point simultaneously
             // with a single call to `check`
-            result &= Self::check(vk, comms, &point, values, &proof, sponge, Some(rng))?;
+            Self::check(vk, comms, point, values, &proof, sponge, Some(rng))?;
             end_timer!(proof_time);
         }
-        Ok(result)
+        Ok(())
     }
 
     /// Open commitments to all polynomials involved in a number of linear
@@ -492,7 +580,8 @@ pub trait PolynomialCommitment<F: PrimeField, P: Polynomial<F>>: Sized {
     }
 
     /// Verify opening proofs for all polynomials involved in a number of
-    /// linear combinations (LC) simultaneously.
+    /// linear combinations (LC) simultaneously. As in `check`, a rejected
+    /// proof is reported as [`Error::VerificationFailed`].
     fn check_combinations<'a, R: RngCore>(
         vk: &Self::VerifierKey,
         linear_combinations: impl IntoIterator<Item = &'a LinearCombination<F>>,
@@ -502,7 +591,7 @@ pub trait PolynomialCommitment<F: PrimeField, P: Polynomial<F>>: Sized {
         proof: &BatchLCProof<F, Self::BatchProof>,
         sponge: &mut impl CryptographicSponge,
         rng: &mut R,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
@@ -522,10 +611,16 @@ pub trait PolynomialCommitment<F: PrimeField, P: Polynomial<F>>: Sized {
             .map(|(poly_label, v)| ((poly_label.clone(), v.1), v.0))
             .collect();
 
+        let evals = evals
+            .as_ref()
+            .ok_or(Error::from(VerificationFailure::MalformedProof {
+                label: None,
+                reason: "missing evaluations of the polynomials".to_string(),
+            }))?;
         let poly_evals = Evaluations::from_iter(
             sorted_by_poly_and_query_label
                 .into_iter()
-                .zip(evals.clone().unwrap())
+                .zip(evals.iter().copied())
                 .map(|(((poly_label, point), _query_label), eval)| ((poly_label, point), eval)),
         );
 
@@ -557,15 +652,18 @@ pub trait PolynomialCommitment<F: PrimeField, P: Polynomial<F>>: Sized {
 
                 // Checking the computed evaluation matches the claimed one
                 if claimed_rhs != actual_rhs {
-                    eprintln!("Claimed evaluation of {} is incorrect", lc.label());
-                    return Ok(false);
+                    return Err(Error::from(VerificationFailure::EvaluationMismatch {
+                        label: lc_label.clone(),
+                        point: format!("{:?}", point),
+                    })
+                    .into());
                 }
             }
         }
 
         // Verify the claimed evaluation for each polynomial appearing in the
         // linear combinations, batched by point
-        let pc_result = Self::batch_check(
+        Self::batch_check(
             vk,
             commitments,
             &poly_query_set,
@@ -573,13 +671,7 @@ pub trait PolynomialCommitment<F: PrimeField, P: Polynomial<F>>: Sized {
             proof,
             sponge,
             rng,
-        )?;
-        if !pc_result {
-            eprintln!("Evaluation proofs failed to verify");
-            return Ok(false);
-        }
-
-        Ok(true)
+        )
     }
 }
 
@@ -757,8 +849,10 @@ pub mod tests {
                 &proof,
                 &mut (sponge.clone()),
                 rng,
-            )?;
-            assert!(result, "proof was incorrect, Query set: {:#?}", query_set);
+            );
+            if let Err(e) = result {
+                panic!("proof was incorrect ({}), Query set: {:#?}", e, query_set);
+            }
         }
 
         Ok(())
@@ -888,8 +982,8 @@ pub mod tests {
                 &proof,
                 &mut (sponge.clone()),
                 rng,
-            )?;
-            if !result {
+            );
+            if let Err(e) = result {
                 println!(
                     "Failed with {} polynomials, num_points_in_query_set: {:?}",
                     num_polynomials, num_points_in_query_set
@@ -898,8 +992,8 @@ pub mod tests {
                 for poly in polynomials {
                     println!("Degree: {:?}", poly.degree());
                 }
+                panic!("proof was incorrect ({}), Query set: {:#?}", e, query_set);
             }
-            assert!(result, "proof was incorrect, Query set: {:#?}", query_set);
         }
 
         Ok(())
@@ -1065,8 +1159,8 @@ pub mod tests {
                 &proof,
                 &mut (sponge.clone()),
                 rng,
-            )?;
-            if !result {
+            );
+            if let Err(e) = result {
                 println!(
                     "Failed with {} polynomials, num_points_in_query_set: {:?}",
                     num_polynomials, num_points_in_query_set
@@ -1075,12 +1169,11 @@ pub mod tests {
                 for poly in polynomials {
                     println!("Degree: {:?}", poly.degree());
                 }
+                panic!(
+                    "proof was incorrect ({}), equations: {:#?}",
+                    e, linear_combinations
+                );
             }
-            assert!(
-                result,
-                "proof was incorrect, equations: {:#?}",
-                linear_combinations
-            );
         }
 
         Ok(())
@@ -1388,6 +1481,705 @@ pub mod tests {
         equation_test_template::<F, P, PC, S>(info)
     }
 
+    /// Sets up `PC` for polynomials of degree `degree`, or with `num_vars`
+    /// variables, and trims it for hiding bounds up to `hiding_bound` and for
+    /// the optional `degree_bound`.
+    fn setup_and_trim<F, P, PC>(
+        degree: usize,
+        num_vars: Option<usize>,
+        hiding_bound: usize,
+        degree_bound: Option<usize>,
+        rng: &mut ChaCha20Rng,
+    ) -> Result<(PC::CommitterKey, PC::VerifierKey), PC::Error>
+    where
+        F: PrimeField,
+        P: Polynomial<F>,
+        PC: PolynomialCommitment<F, P>,
+    {
+        let pp = PC::setup(degree, num_vars, rng)?;
+        let bounds = degree_bound.map(|d| vec![d]);
+        PC::trim(&pp, degree, hiding_bound, bounds.as_deref())
+    }
+
+    /// Samples `num_polynomials` polynomials labelled `Test0`, `Test1`, ...,
+    /// of degree `degree_bound` if there is one and `degree` otherwise.
+    fn rand_labeled_polys<F, P>(
+        num_polynomials: usize,
+        degree: usize,
+        num_vars: Option<usize>,
+        degree_bound: Option<usize>,
+        hiding_bound: Option<usize>,
+        rand_poly: fn(usize, Option<usize>, &mut ChaCha20Rng) -> P,
+        rng: &mut ChaCha20Rng,
+    ) -> Vec<LabeledPolynomial<F, P>>
+    where
+        F: PrimeField,
+        P: Polynomial<F>,
+    {
+        (0..num_polynomials)
+            .map(|i| {
+                LabeledPolynomial::new(
+                    format!("Test{}", i),
+                    rand_poly(degree_bound.unwrap_or(degree), num_vars, rng),
+                    degree_bound,
+                    hiding_bound,
+                )
+            })
+            .collect()
+    }
+
+    /// Checks the sizes predicted by the `CostModel` of `PC` against the
+    /// serialized commitments and batch proofs, for polynomials of degree
+    /// `degree` (or with `num_vars` variables) with `poly_len` coefficients.
+    pub fn cost_model_test<F, P, PC, S>(
+        degree: usize,
+        num_vars: Option<usize>,
+        poly_len: usize,
+        rand_poly: fn(usize, Option<usize>, &mut ChaCha20Rng) -> P,
+        rand_point: fn(Option<usize>, &mut ChaCha20Rng) -> P::Point,
+        sponge: fn() -> S,
+    ) -> Result<(), PC::Error>
+    where
+        F: PrimeField,
+        P: Polynomial<F>,
+        PC: PolynomialCommitment<F, P>,
+        PC::VerifierKey: cost::CostModel,
+        S: CryptographicSponge,
+    {
+        use cost::{BatchShape, CostModel};
+
+        let sponge = sponge();
+        let rng = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+        let (ck, vk) = setup_and_trim::<F, P, PC>(degree, num_vars, 0, None, rng)?;
+
+        for batch in [BatchShape::default(), BatchShape::new(3, 2)] {
+            let polynomials = rand_labeled_polys(
+                batch.num_polys,
+                degree,
+                num_vars,
+                None,
+                None,
+                rand_poly,
+                rng,
+            );
+            let (comms, states) = PC::commit(&ck, &polynomials, Some(rng))?;
+
+            let mut query_set = QuerySet::new();
+            let mut values = Evaluations::new();
+            for j in 0..batch.num_points {
+                let point = rand_point(num_vars, rng);
+                for polynomial in &polynomials {
+                    let label = polynomial.label().clone();
+                    query_set.insert((label.clone(), (format!("{}", j), point.clone())));
+                    values.insert((label, point.clone()), polynomial.evaluate(&point));
+                }
+            }
+
+            let proof = PC::batch_open(
+                &ck,
+                &polynomials,
+                &comms,
+                &query_set,
+                &mut (sponge.clone()),
+                &states,
+                Some(rng),
+            )?;
+            PC::batch_check(
+                &vk,
+                &comms,
+                &query_set,
+                &values,
+                &proof,
+                &mut (sponge.clone()),
+                rng,
+            )?;
+
+            let estimate = vk.estimate_cost(poly_len, &batch).unwrap();
+            for comm in &comms {
+                assert_eq!(
+                    estimate.commitment_bytes,
+                    comm.commitment().compressed_size()
+                );
+            }
+            assert_eq!(estimate.proof_bytes, proof.compressed_size());
+        }
+        Ok(())
+    }
+
+    /// Applies sparse coefficient deltas to a univariate polynomial, as
+    /// expected by `update_commitment_test`.
+    pub fn apply_coefficient_deltas<F, P>(p: &P, deltas: &[(usize, F)]) -> P
+    where
+        F: PrimeField,
+        P: DenseUVPolynomial<F>,
+    {
+        let mut coeffs = p.coeffs().to_vec();
+        for (i, d) in deltas {
+            if *i >= coeffs.len() {
+                coeffs.resize(*i + 1, F::zero());
+            }
+            coeffs[*i] += d;
+        }
+        P::from_coefficients_vec(coeffs)
+    }
+
+    /// Checks that updating a commitment of `PC` with sparse deltas yields a
+    /// commitment which opens to the updated polynomial, both with and
+    /// without hiding. If `PC` commits deterministically to non-hiding
+    /// polynomials, the updated commitment must also equal the commitment to
+    /// the updated polynomial.
+    pub fn update_commitment_test<F, P, PC, S>(
+        degree: usize,
+        num_vars: Option<usize>,
+        degree_bound: Option<usize>,
+        commits_deterministically: bool,
+        rand_poly: fn(usize, Option<usize>, &mut ChaCha20Rng) -> P,
+        rand_point: fn(Option<usize>, &mut ChaCha20Rng) -> P::Point,
+        apply_deltas: fn(&P, &[(usize, F)]) -> P,
+        sponge: fn() -> S,
+    ) -> Result<(), PC::Error>
+    where
+        F: PrimeField,
+        P: Polynomial<F>,
+        PC: update::UpdatableCommitment<F, P>,
+        S: CryptographicSponge,
+    {
+        let sponge = sponge();
+        let rng = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+        let (ck, vk) = setup_and_trim::<F, P, PC>(degree, num_vars, 2, degree_bound, rng)?;
+        let num_indices = degree_bound.unwrap_or(degree) + 1;
+        let num_indices = num_vars.map_or(num_indices, |n| 1 << n);
+
+        for hiding_bound in [None, Some(1)] {
+            let polynomial = rand_labeled_polys(
+                1,
+                degree,
+                num_vars,
+                degree_bound,
+                hiding_bound,
+                rand_poly,
+                rng,
+            )
+            .remove(0);
+            let (comms, states) = PC::commit(&ck, [&polynomial], Some(rng))?;
+
+            // The first index is repeated, so that its deltas are summed.
+            let mut deltas = (0..4)
+                .map(|_| (rng.gen_range(0..num_indices), F::rand(rng)))
+                .collect::<Vec<_>>();
+            deltas.push((deltas[0].0, F::rand(rng)));
+            let update = PC::update_commitment(&ck, &comms[0], &states[0], &deltas, Some(rng))?;
+
+            let updated = LabeledPolynomial::new(
+                polynomial.label().clone(),
+                apply_deltas(polynomial.polynomial(), &deltas),
+                degree_bound,
+                hiding_bound,
+            );
+            if hiding_bound.is_none() && commits_deterministically {
+                let (fresh, _) = PC::commit(&ck, [&updated], Some(rng))?;
+                let mut expected = Vec::new();
+                let mut actual = Vec::new();
+                fresh[0]
+                    .commitment()
+                    .serialize_compressed(&mut expected)
+                    .unwrap();
+                update
+                    .0
+                    .commitment()
+                    .serialize_compressed(&mut actual)
+                    .unwrap();
+                assert_eq!(expected, actual);
+            }
+
+            let point = rand_point(num_vars, rng);
+            open_and_check::<F, P, PC, S>(&ck, &vk, &updated, &update, &point, &sponge, rng)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the linear combination of commitments of `PC` and of their
+    /// states, computed directly or through `add` and `scale`, opens to the
+    /// linear combination of the committed polynomials, both with and without
+    /// hiding.
+    pub fn homomorphic_commitment_test<F, P, PC, S>(
+        degree: usize,
+        num_vars: Option<usize>,
+        degree_bound: Option<usize>,
+        rand_poly: fn(usize, Option<usize>, &mut ChaCha20Rng) -> P,
+        rand_point: fn(Option<usize>, &mut ChaCha20Rng) -> P::Point,
+        sponge: fn() -> S,
+    ) -> Result<(), PC::Error>
+    where
+        F: PrimeField,
+        P: Polynomial<F>,
+        PC: homomorphic::HomomorphicCommitment<F, P>,
+        S: CryptographicSponge,
+    {
+        let sponge = sponge();
+        let rng = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+        let (ck, vk) = setup_and_trim::<F, P, PC>(degree, num_vars, 2, degree_bound, rng)?;
+
+        for hiding_bound in [None, Some(1)] {
+            let polynomials = rand_labeled_polys(
+                3,
+                degree,
+                num_vars,
+                degree_bound,
+                hiding_bound,
+                rand_poly,
+                rng,
+            );
+            let (comms, states) = PC::commit(&ck, &polynomials, Some(rng))?;
+            let coeffs = (0..3).map(|_| F::rand(rng)).collect::<Vec<_>>();
+
+            let terms = coeffs
+                .iter()
+                .zip(&comms)
+                .zip(&states)
+                .map(|((c, comm), state)| (*c, comm, state))
+                .collect::<Vec<_>>();
+            let combination = PC::linear_combination("Combined".to_string(), &terms)?;
+
+            let mut sum = PC::scale("Sum".to_string(), coeffs[0], &comms[0], &states[0])?;
+            for i in 1..3 {
+                let scaled = PC::scale("Scaled".to_string(), coeffs[i], &comms[i], &states[i])?;
+                sum = PC::add("Sum".to_string(), (&sum.0, &sum.1), (&scaled.0, &scaled.1))?;
+            }
+            let mut expected = Vec::new();
+            let mut actual = Vec::new();
+            combination
+                .0
+                .commitment()
+                .serialize_compressed(&mut expected)
+                .unwrap();
+            sum.0
+                .commitment()
+                .serialize_compressed(&mut actual)
+                .unwrap();
+            assert_eq!(expected, actual);
+
+            let mut combined = P::zero();
+            for (c, p) in coeffs.iter().zip(&polynomials) {
+                combined += (*c, p.polynomial());
+            }
+            let combined = LabeledPolynomial::new(
+                "Combined".to_string(),
+                combined,
+                degree_bound,
+                hiding_bound,
+            );
+
+            let point = rand_point(num_vars, rng);
+            open_and_check::<F, P, PC, S>(&ck, &vk, &combined, &combination, &point, &sponge, rng)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that `PC` opens hiding commitments to evaluations which are
+    /// only given as commitments, that these commitments open to the
+    /// evaluations but are blinded afresh at each opening, so that neither
+    /// they nor the proof reveal the evaluations, and that commitments to
+    /// other values are rejected. If `requires_hiding`, opening a non-hiding
+    /// commitment must fail.
+    pub fn hidden_evaluations_test<F, P, PC, S>(
+        degree: usize,
+        num_vars: Option<usize>,
+        degree_bound: Option<usize>,
+        requires_hiding: bool,
+        rand_poly: fn(usize, Option<usize>, &mut ChaCha20Rng) -> P,
+        rand_point: fn(Option<usize>, &mut ChaCha20Rng) -> P::Point,
+        sponge: fn() -> S,
+    ) -> Result<(), PC::Error>
+    where
+        F: PrimeField,
+        P: Polynomial<F>,
+        PC: hidden::HiddenEvaluations<F, P>,
+        PC::Proof: CanonicalSerialize,
+        S: CryptographicSponge,
+    {
+        let sponge = sponge();
+        let rng = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+        let (ck, vk) = setup_and_trim::<F, P, PC>(degree, num_vars, 2, degree_bound, rng)?;
+
+        let polynomials =
+            rand_labeled_polys(2, degree, num_vars, degree_bound, Some(1), rand_poly, rng);
+        let (comms, states) = PC::commit(&ck, &polynomials, Some(rng))?;
+        let point = rand_point(num_vars, rng);
+        let values: Vec<F> = polynomials.iter().map(|p| p.evaluate(&point)).collect();
+
+        let mut openings = Vec::new();
+        for _ in 0..2 {
+            let (proof, evaluations) = PC::open_hidden(
+                &ck,
+                &polynomials,
+                &comms,
+                &point,
+                &mut (sponge.clone()),
+                &states,
+                Some(rng),
+            )?;
+            let evaluation_comms: Vec<_> = evaluations.iter().map(|(c, _)| c.clone()).collect();
+            PC::check_hidden(
+                &vk,
+                &comms,
+                &point,
+                &evaluation_comms,
+                &proof,
+                &mut (sponge.clone()),
+            )?;
+
+            let mut proof_bytes = Vec::new();
+            proof.serialize_compressed(&mut proof_bytes).unwrap();
+            for ((comm, randomness), value) in evaluations.iter().zip(&values) {
+                assert_eq!(*comm, PC::commit_evaluation(&vk, *value, *randomness));
+                assert_ne!(*comm, PC::commit_evaluation(&vk, *value, F::zero()));
+                let mut value_bytes = Vec::new();
+                value.serialize_compressed(&mut value_bytes).unwrap();
+                assert!(!proof_bytes
+                    .windows(value_bytes.len())
+                    .any(|w| w == value_bytes.as_slice()));
+            }
+
+            // A commitment to another value, even with the same randomness,
+            // is rejected.
+            let mut wrong_comms = evaluation_comms.clone();
+            let (_, randomness) = evaluations[1];
+            wrong_comms[1] = PC::commit_evaluation(&vk, values[1] + F::one(), randomness);
+            assert!(PC::check_hidden(
+                &vk,
+                &comms,
+                &point,
+                &wrong_comms,
+                &proof,
+                &mut (sponge.clone()),
+            )
+            .is_err());
+            assert!(PC::check_hidden(
+                &vk,
+                &comms,
+                &point,
+                &evaluation_comms[..1],
+                &proof,
+                &mut (sponge.clone()),
+            )
+            .is_err());
+            openings.push(evaluation_comms);
+        }
+        // The evaluations are committed to with fresh randomness, so that
+        // openings of the same polynomials cannot be linked.
+        for (first, second) in openings[0].iter().zip(&openings[1]) {
+            assert_ne!(first, second);
+        }
+
+        if requires_hiding {
+            let polynomial = LabeledPolynomial::new(
+                "NotHiding".to_string(),
+                rand_poly(degree_bound.unwrap_or(degree), num_vars, rng),
+                degree_bound,
+                None,
+            );
+            let (comms, states) = PC::commit(&ck, [&polynomial], Some(rng))?;
+            assert!(PC::open_hidden(
+                &ck,
+                [&polynomial],
+                &comms,
+                &point,
+                &mut (sponge.clone()),
+                &states,
+                Some(rng),
+            )
+            .is_err());
+        }
+        Ok(())
+    }
+
+    /// Checks that the hiding commitments and openings of `PC`, with
+    /// randomness derived by `DeterministicRng`, are reproducible
+    /// byte-for-byte, and that distinct polynomials, commitments, points or
+    /// transcripts never share their masks.
+    pub fn deterministic_hiding_test<F, P, PC, S>(
+        degree: usize,
+        num_vars: Option<usize>,
+        rand_poly: fn(usize, Option<usize>, &mut ChaCha20Rng) -> P,
+        rand_point: fn(Option<usize>, &mut ChaCha20Rng) -> P::Point,
+        sponge: fn() -> S,
+    ) -> Result<(), PC::Error>
+    where
+        F: PrimeField,
+        P: Polynomial<F>,
+        P::Point: CanonicalSerialize,
+        PC: PolynomialCommitment<F, P>,
+        PC::Proof: CanonicalSerialize,
+        S: CryptographicSponge,
+    {
+        use ark_std::rand::RngCore;
+        use deterministic_rng::DeterministicRng;
+
+        let sponge = sponge();
+        let rng = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+        let (ck, vk) = setup_and_trim::<F, P, PC>(degree, num_vars, 2, None, rng)?;
+        let seed = [7u8; 32];
+        let derive = |polynomials: &[LabeledPolynomial<F, P>]| {
+            DeterministicRng::for_commit(&seed, polynomials).unwrap()
+        };
+        let serialize = |comms: &[LabeledCommitment<PC::Commitment>]| {
+            let mut bytes = Vec::new();
+            for comm in comms {
+                comm.commitment().serialize_compressed(&mut bytes).unwrap();
+            }
+            bytes
+        };
+
+        let polynomials = rand_labeled_polys(2, degree, num_vars, None, Some(1), rand_poly, rng);
+        let (comms, states) = PC::commit(&ck, &polynomials, Some(&mut derive(&polynomials)))?;
+        let (comms_again, _) = PC::commit(&ck, &polynomials, Some(&mut derive(&polynomials)))?;
+        assert_eq!(serialize(&comms), serialize(&comms_again));
+
+        // The same polynomial, committed to with the masks derived for
+        // another polynomial, has another commitment.
+        let first = &polynomials[..1];
+        let other = [LabeledPolynomial::new(
+            "Test0".to_string(),
+            rand_poly(degree, num_vars, rng),
+            None,
+            Some(1),
+        )];
+        let (own, _) = PC::commit(&ck, first, Some(&mut derive(first)))?;
+        let (reused, _) = PC::commit(&ck, first, Some(&mut derive(&other)))?;
+        assert_ne!(serialize(&own), serialize(&reused));
+        assert_eq!(
+            serialize(&own),
+            serialize(&PC::commit(&ck, first, Some(&mut derive(first)))?.0)
+        );
+
+        let point = rand_point(num_vars, rng);
+        let values: Vec<F> = polynomials.iter().map(|p| p.evaluate(&point)).collect();
+        let open = |sponge: &S| -> Result<Vec<u8>, PC::Error> {
+            let mut open_rng =
+                DeterministicRng::for_open(&seed, &polynomials, &comms, &point, sponge).unwrap();
+            let proof = PC::open(
+                &ck,
+                &polynomials,
+                &comms,
+                &point,
+                &mut sponge.clone(),
+                &states,
+                Some(&mut open_rng),
+            )?;
+            PC::check(
+                &vk,
+                &comms,
+                &point,
+                values.clone(),
+                &proof,
+                &mut sponge.clone(),
+                None,
+            )?;
+            let mut bytes = Vec::new();
+            proof.serialize_compressed(&mut bytes).unwrap();
+            Ok(bytes)
+        };
+        let proof = open(&sponge)?;
+        assert_eq!(proof, open(&sponge)?);
+
+        // Another transcript gives other masks.
+        let mut other_sponge = sponge.clone();
+        other_sponge.absorb(&b"another transcript".as_slice());
+        assert_ne!(proof, open(&other_sponge)?);
+
+        // Openings of the same polynomials, in the same transcript, at
+        // another point or of other commitments draw other randomness.
+        let stream = |mut rng: DeterministicRng| {
+            let mut bytes = [0u8; 64];
+            rng.fill_bytes(&mut bytes);
+            bytes
+        };
+        let other_point = rand_point(num_vars, rng);
+        assert_ne!(point, other_point);
+        let at = |comms: &[LabeledCommitment<PC::Commitment>], point: &P::Point| {
+            stream(DeterministicRng::for_open(&seed, &polynomials, comms, point, &sponge).unwrap())
+        };
+        assert_eq!(at(&comms, &point), at(&comms, &point));
+        assert_ne!(at(&comms, &point), at(&comms, &other_point));
+        let (other_comms, _) = PC::commit(&ck, &polynomials, Some(rng))?;
+        assert_ne!(at(&comms, &point), at(&other_comms, &point));
+
+        // The same holds for the query sets of batch openings.
+        let query_set = |point: &P::Point| {
+            let mut query_set = QuerySet::new();
+            for polynomial in &polynomials {
+                query_set.insert((
+                    polynomial.label().clone(),
+                    ("point".to_string(), point.clone()),
+                ));
+            }
+            query_set
+        };
+        let batch_at = |point: &P::Point| {
+            stream(
+                DeterministicRng::for_batch_open(
+                    &seed,
+                    &polynomials,
+                    &comms,
+                    &query_set(point),
+                    &sponge,
+                )
+                .unwrap(),
+            )
+        };
+        assert_eq!(batch_at(&point), batch_at(&point));
+        assert_ne!(batch_at(&point), batch_at(&other_point));
+        assert_ne!(batch_at(&point), at(&comms, &point));
+        Ok(())
+    }
+
+    /// Checks that `PC` opens its commitments at random tensors, whose values
+    /// on the vectors output by `to_vec` are computed by `Tensor::apply`, and
+    /// that the proofs are bound to their tensor.
+    pub fn tensor_openings_test<F, P, PC, S>(
+        degree: usize,
+        num_vars: Option<usize>,
+        rand_poly: fn(usize, Option<usize>, &mut ChaCha20Rng) -> P,
+        to_vec: fn(&P) -> Vec<F>,
+        sponge: fn() -> S,
+    ) -> Result<(), PC::Error>
+    where
+        F: PrimeField,
+        P: Polynomial<F>,
+        PC: tensor::TensorOpenings<F, P>,
+        S: CryptographicSponge,
+    {
+        use tensor::Tensor;
+
+        let sponge = sponge();
+        let rng = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+        let (ck, vk) = setup_and_trim::<F, P, PC>(degree, num_vars, 1, None, rng)?;
+
+        let polynomials = rand_labeled_polys(2, degree, num_vars, None, Some(1), rand_poly, rng);
+        let (comms, states) = PC::commit(&ck, &polynomials, Some(rng))?;
+
+        let (n_cols, n_rows) = PC::tensor_dimensions(comms[0].commitment());
+        let rand_vec = |len: usize, rng: &mut ChaCha20Rng| -> Vec<F> {
+            (0..len).map(|_| F::rand(rng)).collect()
+        };
+        let tensor = Tensor::new(rand_vec(n_cols, rng), rand_vec(n_rows, rng));
+        let values = |tensor: &Tensor<F>| -> Vec<F> {
+            polynomials
+                .iter()
+                .map(|p| tensor.apply(&to_vec(p)).unwrap())
+                .collect()
+        };
+        assert_eq!(
+            values(&tensor)[0],
+            tensor
+                .to_vec()
+                .iter()
+                .zip(to_vec(&polynomials[0]))
+                .map(|(t, f)| *t * f)
+                .sum::<F>()
+        );
+
+        let proof = PC::open_tensor(
+            &ck,
+            &polynomials,
+            &comms,
+            &tensor,
+            &mut (sponge.clone()),
+            &states,
+            Some(rng),
+        )?;
+        PC::check_tensor(
+            &vk,
+            &comms,
+            &tensor,
+            values(&tensor),
+            &proof,
+            &mut (sponge.clone()),
+            Some(rng),
+        )?;
+
+        let mut wrong_values = values(&tensor);
+        wrong_values[1] += F::one();
+        assert!(PC::check_tensor(
+            &vk,
+            &comms,
+            &tensor,
+            wrong_values,
+            &proof,
+            &mut (sponge.clone()),
+            Some(rng),
+        )
+        .is_err());
+
+        // The proof does not open the commitments at another tensor, even to
+        // the correct values.
+        let other = Tensor::new(tensor.a.clone(), rand_vec(n_rows, rng));
+        assert!(PC::check_tensor(
+            &vk,
+            &comms,
+            &other,
+            values(&other),
+            &proof,
+            &mut (sponge.clone()),
+            Some(rng),
+        )
+        .is_err());
+
+        // Tensors must have the dimensions of the commitments.
+        let wrong_dimensions = Tensor::new(rand_vec(n_cols + 1, rng), rand_vec(n_rows, rng));
+        assert!(PC::open_tensor(
+            &ck,
+            &polynomials,
+            &comms,
+            &wrong_dimensions,
+            &mut (sponge.clone()),
+            &states,
+            Some(rng),
+        )
+        .is_err());
+        Ok(())
+    }
+
+    /// Opens the commitment of `committed` to the evaluation of `polynomial`
+    /// at `point`, and checks the proof.
+    fn open_and_check<F, P, PC, S>(
+        ck: &PC::CommitterKey,
+        vk: &PC::VerifierKey,
+        polynomial: &LabeledPolynomial<F, P>,
+        committed: &(LabeledCommitment<PC::Commitment>, PC::CommitmentState),
+        point: &P::Point,
+        sponge: &S,
+        rng: &mut ChaCha20Rng,
+    ) -> Result<(), PC::Error>
+    where
+        F: PrimeField,
+        P: Polynomial<F>,
+        PC: PolynomialCommitment<F, P>,
+        S: CryptographicSponge,
+    {
+        let (comm, state) = committed;
+        let value = polynomial.evaluate(point);
+        let proof = PC::open(
+            ck,
+            [polynomial],
+            [comm],
+            point,
+            &mut (sponge.clone()),
+            [state],
+            Some(rng),
+        )?;
+        PC::check(
+            vk,
+            [comm],
+            point,
+            [value],
+            &proof,
+            &mut (sponge.clone()),
+            Some(rng),
+        )
+    }
+
     pub(crate) fn poseidon_sponge_for_test<F: PrimeField>() -> PoseidonSponge<F> {
         PoseidonSponge::new(&poseidon_parameters_for_test())
     }
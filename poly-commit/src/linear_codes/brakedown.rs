@@ -1,8 +1,12 @@
-use super::{utils::SprsMat, BrakedownPCParams, LinCodeParametersInfo};
+use super::{
+    lin_code_cost, utils::SprsMat, BrakedownPCParams, BrakedownSuggestion, LinCodeParametersInfo,
+};
 use crate::{
-    linear_codes::utils::calculate_t,
+    cost::{BatchShape, CostEstimate, CostModel},
+    linear_codes::utils::{calculate_t, lin_code_security},
+    security::{SecurityAssumptions, SecurityEstimate, SecurityEstimator},
     utils::{ceil_div, ceil_mul, ent},
-    PCCommitterKey, PCUniversalParams, PCVerifierKey,
+    Error, PCCommitterKey, PCPreparedVerifierKey, PCUniversalParams, PCVerifierKey,
 };
 
 use ark_crypto_primitives::{
@@ -10,7 +14,7 @@ use ark_crypto_primitives::{
     merkle_tree::{Config, LeafParam, TwoToOneParam},
 };
 use ark_ff::PrimeField;
-use ark_std::{log2, rand::RngCore, vec::Vec};
+use ark_std::{borrow::Borrow, format, log2, rand::RngCore, string::ToString, vec::Vec};
 #[cfg(all(not(feature = "std"), target_arch = "aarch64"))]
 use num_traits::Float;
 
@@ -55,6 +59,17 @@ where
     }
 }
 
+impl<F, C, H> PCPreparedVerifierKey<Self> for BrakedownPCParams<F, C, H>
+where
+    F: PrimeField,
+    C: Config,
+    H: CRHScheme,
+{
+    fn prepare(vk: &Self) -> Self {
+        vk.clone()
+    }
+}
+
 impl<F, C, H> LinCodeParametersInfo<C, H> for BrakedownPCParams<F, C, H>
 where
     F: PrimeField,
@@ -96,12 +111,63 @@ where
     }
 }
 
+impl<F, C, H> CostModel for BrakedownPCParams<F, C, H>
+where
+    F: PrimeField,
+    C: Config,
+    C::Leaf: Default + Clone + Send + AsRef<C::Leaf>,
+    H: CRHScheme,
+    Vec<F>: Borrow<H::Input>,
+    H::Output: Into<C::Leaf>,
+{
+    fn estimate_cost(&self, poly_len: usize, batch: &BatchShape) -> Result<CostEstimate, Error> {
+        self.check_poly_len(poly_len)?;
+        lin_code_cost::<F, C, H>(self, (self.n, self.m), self.m_ext, batch)
+    }
+}
+
+impl<F, C, H> SecurityEstimator for BrakedownPCParams<F, C, H>
+where
+    F: PrimeField,
+    C: Config,
+    H: CRHScheme,
+{
+    fn estimate_security(
+        &self,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<SecurityEstimate, Error> {
+        self.check_poly_len(poly_len)?;
+        Self::security(
+            self.sec_param,
+            self.distance(),
+            self.n,
+            self.m,
+            self.m_ext,
+            assumptions,
+        )
+        .map(|(estimate, _)| estimate)
+    }
+}
+
 impl<F, C, H> BrakedownPCParams<F, C, H>
 where
     F: PrimeField,
     C: Config,
     H: CRHScheme,
 {
+    /// `alpha` from Fig. 2 of the paper.
+    const ALPHA: (usize, usize) = (178, 1000);
+    /// `beta` from Fig. 2 of the paper.
+    const BETA: (usize, usize) = (61, 1000);
+    /// The inverse of the code rate from Fig. 2 of the paper.
+    const RHO_INV: (usize, usize) = (1521, 1000);
+    /// Threshold of the base case of the encoding.
+    const BASE_LEN: usize = 30;
+    /// `log2` of the probability that the randomly sampled code does not have
+    /// the claimed distance, from Lemma 2 of the paper.
+    const LOG2_CODE_ERROR: f64 = -100.0;
+
     /// Create a default UniversalParams, with the values from Fig. 2 from the paper.
     pub fn default<R: RngCore>(
         rng: &mut R,
@@ -111,18 +177,32 @@ where
         two_to_one_hash_param: TwoToOneParam<C>,
         col_hash_params: H::Parameters,
     ) -> Self {
-        let sec_param = 128;
-        let a = (178, 1000);
-        let b = (61, 1000);
-        let r = (1521, 1000);
-        let base_len = 30;
-        let t = calculate_t::<F>(sec_param, (b.0 * r.1, b.1 * r.0), poly_len).unwrap(); // we want to get a rough idea what t is
-        let n = 1 << log2((ceil_div(2 * poly_len, t) as f64).sqrt().ceil() as usize);
-        let m = ceil_div(poly_len, n);
-        let c = Self::cn_const(a, b);
-        let d = Self::dn_const(a, b, r);
-        let ct = Constants { a, b, r, c, d };
-        let (a_dims, b_dims) = Self::mat_size(m, base_len, &ct);
+        Self::with_sec_param(
+            rng,
+            128,
+            poly_len,
+            check_well_formedness,
+            leaf_hash_param,
+            two_to_one_hash_param,
+            col_hash_params,
+        )
+    }
+
+    /// Same as [`Self::default`], but with the given security parameter, e.g.
+    /// the one suggested by [`Self::suggest`].
+    pub fn with_sec_param<R: RngCore>(
+        rng: &mut R,
+        sec_param: usize,
+        poly_len: usize,
+        check_well_formedness: bool,
+        leaf_hash_param: LeafParam<C>,
+        two_to_one_hash_param: TwoToOneParam<C>,
+        col_hash_params: H::Parameters,
+    ) -> Self {
+        let (a, b, r) = (Self::ALPHA, Self::BETA, Self::RHO_INV);
+        let ct = Self::default_constants();
+        let (n, m) = Self::dimensions(sec_param, poly_len).unwrap();
+        let (a_dims, b_dims) = Self::mat_size(m, Self::BASE_LEN, &ct);
         let a_mats = Self::make_all(rng, &a_dims);
         let b_mats = Self::make_all(rng, &b_dims);
 
@@ -131,7 +211,7 @@ where
             a,
             b,
             r,
-            base_len,
+            Self::BASE_LEN,
             n,
             m,
             a_dims,
@@ -145,6 +225,113 @@ where
         )
     }
 
+    /// Suggest the `sec_param` to pass to [`Self::with_sec_param`] in order to
+    /// commit to polynomials with `poly_len` coefficients with at least
+    /// `target_bits` of security under `assumptions`.
+    pub fn suggest(
+        target_bits: f64,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<BrakedownSuggestion, Error> {
+        const MAX_SEC_PARAM: usize = 512;
+
+        let ct = Self::default_constants();
+        let distance = (
+            Self::RHO_INV.1 * Self::BETA.0,
+            Self::RHO_INV.0 * Self::BETA.1,
+        );
+        for sec_param in 1..=MAX_SEC_PARAM {
+            // `calculate_t` fails for every larger `sec_param` as well.
+            let Ok((n, m)) = Self::dimensions(sec_param, poly_len) else {
+                break;
+            };
+            let (a_dims, b_dims) = Self::mat_size(m, Self::BASE_LEN, &ct);
+            let m_ext = Self::ext_len(m, &a_dims, &b_dims);
+            let Ok((estimate, num_queries)) =
+                Self::security(sec_param, distance, n, m, m_ext, assumptions)
+            else {
+                break;
+            };
+            if estimate.bits() >= target_bits {
+                return Ok(BrakedownSuggestion {
+                    sec_param,
+                    num_queries,
+                    estimate,
+                });
+            }
+        }
+
+        Err(Error::InvalidParameters(
+            "The target security cannot be reached over this field".to_string(),
+        ))
+    }
+
+    /// Checks that the matrix fits polynomials with `poly_len` coefficients.
+    fn check_poly_len(&self, poly_len: usize) -> Result<(), Error> {
+        if poly_len > self.n * self.m {
+            return Err(Error::IncorrectInputLength(format!(
+                "The parameters support polynomials with at most {} coefficients, but {} were requested",
+                self.n * self.m,
+                poly_len
+            )));
+        }
+        Ok(())
+    }
+
+    /// The constants of the code from Fig. 2 of the paper.
+    fn default_constants() -> Constants {
+        let (a, b, r) = (Self::ALPHA, Self::BETA, Self::RHO_INV);
+        let c = Self::cn_const(a, b);
+        let d = Self::dn_const(a, b, r);
+        Constants { a, b, r, c, d }
+    }
+
+    /// The matrix dimensions used for polynomials with `poly_len` coefficients.
+    fn dimensions(sec_param: usize, poly_len: usize) -> Result<(usize, usize), Error> {
+        let (b, r) = (Self::BETA, Self::RHO_INV);
+        let t = calculate_t::<F>(sec_param, (b.0 * r.1, b.1 * r.0), poly_len)?; // we want to get a rough idea what t is
+        let n = 1 << log2((ceil_div(2 * poly_len, t) as f64).sqrt().ceil() as usize);
+        let m = ceil_div(poly_len, n);
+        Ok((n, m))
+    }
+
+    /// The security estimate and the number of queries for an `n x m` matrix
+    /// encoded into rows of length `m_ext`.
+    fn security(
+        sec_param: usize,
+        distance: (usize, usize),
+        n: usize,
+        m: usize,
+        m_ext: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<(SecurityEstimate, usize), Error> {
+        let t = calculate_t::<F>(sec_param, distance, m_ext)?;
+        let estimate = lin_code_security::<F>(
+            t,
+            n,
+            m_ext,
+            m as f64 / m_ext as f64,
+            distance,
+            false,
+            Self::LOG2_CODE_ERROR,
+            assumptions,
+        )?;
+        Ok((estimate, t))
+    }
+
+    /// The length of the encoding of a row of length `m`.
+    fn ext_len(
+        m: usize,
+        a_dims: &[(usize, usize, usize)],
+        b_dims: &[(usize, usize, usize)],
+    ) -> usize {
+        if a_dims.is_empty() {
+            ceil_mul(m, Self::RHO_INV)
+        } else {
+            Self::codeword_len(a_dims, b_dims)
+        }
+    }
+
     /// This function creates a UniversalParams. It does not check if the paramters are consistent/correct.
     pub fn new(
         sec_param: usize,
@@ -0,0 +1,1731 @@
+//! Gadgets re-executing the verifiers of the linear-code-based schemes.
+//!
+//! [`LinearCodePCSGadget::check`] supports every [`LinearEncodeGadget`], i.e. the
+//! univariate and multilinear Ligero and the multilinear Brakedown schemes.
+//! [`PCCheckVar`] is only implemented for univariate Ligero, by
+//! [`PoseidonUVLigeroGadget`]: its query sets hold one emulated field element
+//! per point, which cannot represent the points of multilinear polynomials.
+//! The multilinear schemes are checked with [`LinearCodePCSGadget::check`].
+use super::{
+    poseidon::{
+        FieldIdentityLeafHasher, PoseidonColHasher, PoseidonMerkleTreeParams,
+        PoseidonTwoToOneHasher,
+    },
+    utils::{calculate_t, get_num_bytes},
+    LPCPArray, LigeroPCParams, LinCodePCCommitment, LinCodePCProof, LinCodeParametersInfo,
+    LinearEncode, Metadata, MultilinearBrakedown, MultilinearLigero, PoseidonUVLigero,
+    UnivariateLigero,
+};
+use crate::{
+    transcript::{challenge_header, context_messages, message_header},
+    BatchLCProof, EvaluationsVar, LCTerm, LabeledCommitment, LabeledPointVar,
+    LinearCombinationCoeffVar, LinearCombinationVar, PCCheckRandomDataVar, PCCheckVar,
+    PrepareGadget, QuerySetVar,
+};
+
+use ark_crypto_primitives::{
+    crh::{
+        poseidon::constraints::{CRHGadget, CRHParametersVar, TwoToOneCRHGadget},
+        CRHScheme, CRHSchemeGadget, TwoToOneCRHSchemeGadget,
+    },
+    merkle_tree::{
+        constraints::{ConfigGadget, PathVar},
+        Config, IdentityDigestConverter, Path,
+    },
+    sponge::{
+        constraints::CryptographicSpongeVar,
+        poseidon::{constraints::PoseidonSpongeVar, PoseidonSponge},
+        Absorb, CryptographicSponge,
+    },
+};
+use ark_ff::{FftField, PrimeField};
+use ark_poly::{
+    DenseUVPolynomial, EvaluationDomain, GeneralEvaluationDomain, MultilinearExtension, Polynomial,
+};
+use ark_r1cs_std::{
+    fields::{emulated_fp::EmulatedFpVar, fp::FpVar},
+    prelude::*,
+};
+use ark_relations::r1cs::{ConstraintSystemRef, Namespace, Result as R1CSResult, SynthesisError};
+use ark_std::{
+    borrow::Borrow,
+    collections::{BTreeMap, BTreeSet},
+    log2,
+    marker::PhantomData,
+};
+#[cfg(not(feature = "std"))]
+use ark_std::{string::String, vec::Vec};
+
+/// Gadget for [`FieldIdentityLeafHasher`].
+pub struct FieldIdentityLeafHasherGadget<F: PrimeField> {
+    _phantom: PhantomData<F>,
+}
+
+impl<F: PrimeField> CRHSchemeGadget<FieldIdentityLeafHasher<F>, F>
+    for FieldIdentityLeafHasherGadget<F>
+{
+    type InputVar = FpVar<F>;
+    type OutputVar = FpVar<F>;
+    type ParametersVar = ();
+
+    fn evaluate(
+        _parameters: &Self::ParametersVar,
+        input: &Self::InputVar,
+    ) -> R1CSResult<Self::OutputVar> {
+        Ok(input.clone())
+    }
+}
+
+/// Gadget for [`PoseidonColHasher`].
+pub struct PoseidonColHasherGadget<F: PrimeField + Absorb> {
+    _phantom: PhantomData<F>,
+}
+
+impl<F: PrimeField + Absorb> CRHSchemeGadget<PoseidonColHasher<F>, F>
+    for PoseidonColHasherGadget<F>
+{
+    type InputVar = [FpVar<F>];
+    type OutputVar = FpVar<F>;
+    type ParametersVar = CRHParametersVar<F>;
+
+    fn evaluate(
+        parameters: &Self::ParametersVar,
+        input: &Self::InputVar,
+    ) -> R1CSResult<Self::OutputVar> {
+        CRHGadget::<F>::evaluate(parameters, input)
+    }
+}
+
+/// Gadget for [`PoseidonTwoToOneHasher`].
+pub struct PoseidonTwoToOneHasherGadget<F: PrimeField + Absorb> {
+    _phantom: PhantomData<F>,
+}
+
+impl<F: PrimeField + Absorb> TwoToOneCRHSchemeGadget<PoseidonTwoToOneHasher<F>, F>
+    for PoseidonTwoToOneHasherGadget<F>
+{
+    type InputVar = FpVar<F>;
+    type OutputVar = FpVar<F>;
+    type ParametersVar = CRHParametersVar<F>;
+
+    fn evaluate(
+        parameters: &Self::ParametersVar,
+        left_input: &Self::InputVar,
+        right_input: &Self::InputVar,
+    ) -> R1CSResult<Self::OutputVar> {
+        TwoToOneCRHGadget::<F>::evaluate(parameters, left_input, right_input)
+    }
+
+    fn compress(
+        parameters: &Self::ParametersVar,
+        left_input: &Self::OutputVar,
+        right_input: &Self::OutputVar,
+    ) -> R1CSResult<Self::OutputVar> {
+        TwoToOneCRHGadget::<F>::compress(parameters, left_input, right_input)
+    }
+}
+
+/// Gadget for [`PoseidonMerkleTreeParams`].
+pub struct PoseidonMerkleTreeParamsVar<F: PrimeField + Absorb> {
+    _phantom: PhantomData<F>,
+}
+
+impl<F: PrimeField + Absorb> ConfigGadget<PoseidonMerkleTreeParams<F>, F>
+    for PoseidonMerkleTreeParamsVar<F>
+{
+    type Leaf = FpVar<F>;
+    type LeafDigest = FpVar<F>;
+    type LeafInnerConverter = IdentityDigestConverter<FpVar<F>>;
+    type InnerDigest = FpVar<F>;
+    type LeafHash = FieldIdentityLeafHasherGadget<F>;
+    type TwoToOneHash = PoseidonTwoToOneHasherGadget<F>;
+}
+
+type PoseidonPathVar<F> = PathVar<PoseidonMerkleTreeParams<F>, F, PoseidonMerkleTreeParamsVar<F>>;
+
+/// In-circuit counterpart of [`LinearEncode`], needed to re-execute the
+/// verifier of a [`LinearCodePCS`](super::LinearCodePCS) inside a circuit over `F`.
+pub trait LinearEncodeGadget<F, C, P, H>: LinearEncode<F, C, P, H>
+where
+    F: PrimeField,
+    C: Config,
+    H: CRHScheme,
+    P: Polynomial<F>,
+{
+    /// An allocated version of `P::Point`.
+    type PointVar: Clone;
+
+    /// In-circuit version of [`LinearEncode::point_to_vec`].
+    fn point_to_vec_var(point: &Self::PointVar) -> Vec<FpVar<F>>;
+
+    /// In-circuit version of [`LinearEncode::tensor`].
+    #[allow(clippy::type_complexity)]
+    fn tensor_var(
+        z: &Self::PointVar,
+        n: usize,
+        m: usize,
+    ) -> R1CSResult<(Vec<FpVar<F>>, Vec<FpVar<F>>)>;
+
+    /// Compute the entries of the encoding of `msg` at the positions in `indices`,
+    /// each given as little-endian bits.
+    ///
+    /// The default implementation only relies on the linearity of the code: it
+    /// computes the whole codeword as a linear combination of the encodings of
+    /// the unit vectors, which is free in R1CS, and then selects each entry.
+    fn encode_at_var(
+        msg: &[FpVar<F>],
+        indices: &[Vec<Boolean<F>>],
+        param: &Self::LinCodePCParams,
+    ) -> R1CSResult<Vec<FpVar<F>>> {
+        let mut codeword: Vec<FpVar<F>> = Vec::new();
+        let mut unit = vec![F::zero(); msg.len()];
+        for (k, m_k) in msg.iter().enumerate() {
+            unit[k] = F::one();
+            let column = Self::encode(&unit, param).map_err(|_| SynthesisError::Unsatisfiable)?;
+            unit[k] = F::zero();
+
+            if codeword.is_empty() {
+                codeword = vec![FpVar::zero(); column.len()];
+            }
+            for (w_j, g_kj) in codeword.iter_mut().zip(column) {
+                *w_j += m_k * g_kj;
+            }
+        }
+
+        let num_bits = log2(codeword.len()) as usize;
+        codeword.resize(1 << num_bits, FpVar::zero());
+        indices
+            .iter()
+            .map(|index| {
+                let mut position = index.clone();
+                position.resize(num_bits, Boolean::FALSE);
+                position.reverse();
+                FpVar::conditionally_select_power_of_two_vector(&position, &codeword)
+            })
+            .collect()
+    }
+}
+
+/// In-circuit Reed-Solomon encoding (as done by `reed_solomon`) at the given indices:
+/// the entry at `q` is the evaluation of `msg` at the `q`-th element of the domain.
+fn reed_solomon_at_var<F: FftField + PrimeField>(
+    msg: &[FpVar<F>],
+    indices: &[Vec<Boolean<F>>],
+    rho_inv: usize,
+) -> R1CSResult<Vec<FpVar<F>>> {
+    let domain = GeneralEvaluationDomain::<F>::new(msg.len() * rho_inv)
+        .ok_or(SynthesisError::Unsatisfiable)?;
+    let gen = domain.group_gen();
+
+    indices
+        .iter()
+        .map(|index| {
+            // x = gen^q, computed from the bits of q
+            let mut x = FpVar::one();
+            let mut gen_pow = gen;
+            for bit in index {
+                let factor =
+                    FpVar::conditionally_select(bit, &FpVar::constant(gen_pow), &FpVar::one())?;
+                x *= factor;
+                gen_pow.square_in_place();
+            }
+
+            // Horner's rule
+            let mut eval = FpVar::zero();
+            for m_k in msg.iter().rev() {
+                eval = eval * &x + m_k;
+            }
+            Ok(eval)
+        })
+        .collect()
+}
+
+fn tensor_vec_var<F: PrimeField>(values: &[FpVar<F>]) -> R1CSResult<Vec<FpVar<F>>> {
+    let mut layer = vec![FpVar::one()];
+
+    for value in values {
+        let anti_value = FpVar::one() - value;
+        let mut new_layer = Vec::with_capacity(2 * layer.len());
+        for v in &layer {
+            new_layer.push(v * &anti_value);
+        }
+        for v in &layer {
+            new_layer.push(v * value);
+        }
+        layer = new_layer;
+    }
+
+    Ok(layer)
+}
+
+impl<F, C, P, H> LinearEncodeGadget<F, C, P, H> for UnivariateLigero<F, C, P, H>
+where
+    F: PrimeField,
+    C: Config,
+    P: DenseUVPolynomial<F>,
+    P::Point: Into<F>,
+    H: CRHScheme,
+{
+    type PointVar = FpVar<F>;
+
+    fn point_to_vec_var(point: &Self::PointVar) -> Vec<FpVar<F>> {
+        vec![point.clone()]
+    }
+
+    fn tensor_var(
+        z: &Self::PointVar,
+        left: usize,
+        right: usize,
+    ) -> R1CSResult<(Vec<FpVar<F>>, Vec<FpVar<F>>)> {
+        let mut left_out = Vec::with_capacity(left);
+        let mut pow_a = FpVar::one();
+        for _ in 0..left {
+            left_out.push(pow_a.clone());
+            pow_a *= z;
+        }
+
+        let mut right_out = Vec::with_capacity(right);
+        let mut pow_b = FpVar::one();
+        for _ in 0..right {
+            right_out.push(pow_b.clone());
+            pow_b *= &pow_a;
+        }
+
+        Ok((left_out, right_out))
+    }
+
+    fn encode_at_var(
+        msg: &[FpVar<F>],
+        indices: &[Vec<Boolean<F>>],
+        param: &Self::LinCodePCParams,
+    ) -> R1CSResult<Vec<FpVar<F>>> {
+        reed_solomon_at_var(msg, indices, param.rho_inv)
+    }
+}
+
+impl<F, C, P, H> LinearEncodeGadget<F, C, P, H> for MultilinearLigero<F, C, P, H>
+where
+    F: PrimeField + FftField,
+    C: Config,
+    P: MultilinearExtension<F>,
+    <P as Polynomial<F>>::Point: Into<Vec<F>>,
+    H: CRHScheme,
+{
+    type PointVar = Vec<FpVar<F>>;
+
+    fn point_to_vec_var(point: &Self::PointVar) -> Vec<FpVar<F>> {
+        point.clone()
+    }
+
+    fn tensor_var(
+        point: &Self::PointVar,
+        left_len: usize,
+        _right_len: usize,
+    ) -> R1CSResult<(Vec<FpVar<F>>, Vec<FpVar<F>>)> {
+        let split = log2(left_len) as usize;
+        let left = &point[..split];
+        let right = &point[split..];
+        Ok((tensor_vec_var(left)?, tensor_vec_var(right)?))
+    }
+
+    fn encode_at_var(
+        msg: &[FpVar<F>],
+        indices: &[Vec<Boolean<F>>],
+        param: &Self::LinCodePCParams,
+    ) -> R1CSResult<Vec<FpVar<F>>> {
+        reed_solomon_at_var(msg, indices, param.rho_inv)
+    }
+}
+
+impl<F, C, P, H> LinearEncodeGadget<F, C, P, H> for MultilinearBrakedown<F, C, P, H>
+where
+    F: PrimeField,
+    C: Config,
+    P: MultilinearExtension<F>,
+    <P as Polynomial<F>>::Point: Into<Vec<F>>,
+    H: CRHScheme,
+{
+    type PointVar = Vec<FpVar<F>>;
+
+    fn point_to_vec_var(point: &Self::PointVar) -> Vec<FpVar<F>> {
+        point.clone()
+    }
+
+    fn tensor_var(
+        point: &Self::PointVar,
+        left_len: usize,
+        _right_len: usize,
+    ) -> R1CSResult<(Vec<FpVar<F>>, Vec<FpVar<F>>)> {
+        let split = log2(left_len) as usize;
+        let left = &point[..split];
+        let right = &point[split..];
+        Ok((tensor_vec_var(left)?, tensor_vec_var(right)?))
+    }
+}
+
+/// An allocated version of the parameters of a [`LinearCodePCS`](super::LinearCodePCS).
+/// The parameters are public and are always embedded as constants.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Params: Clone"))]
+pub struct LinCodePCParamsVar<F: PrimeField + Absorb, Params> {
+    pub(crate) params: Params,
+    pub(crate) col_hash_params: CRHParametersVar<F>,
+    pub(crate) two_to_one_hash_params: CRHParametersVar<F>,
+}
+
+impl<F, Params> AllocVar<Params, F> for LinCodePCParamsVar<F, Params>
+where
+    F: PrimeField + Absorb,
+    Params: LinCodeParametersInfo<PoseidonMerkleTreeParams<F>, PoseidonColHasher<F>> + Clone,
+{
+    fn new_variable<T: Borrow<Params>>(
+        _cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        _mode: AllocationMode,
+    ) -> R1CSResult<Self> {
+        let params = f()?.borrow().clone();
+        Ok(Self {
+            col_hash_params: CRHParametersVar {
+                parameters: params.col_hash_params().clone(),
+            },
+            two_to_one_hash_params: CRHParametersVar {
+                parameters: params.two_to_one_hash_param().clone(),
+            },
+            params,
+        })
+    }
+}
+
+impl<F: PrimeField + Absorb, Params: Clone> PrepareGadget<Self, F>
+    for LinCodePCParamsVar<F, Params>
+{
+    fn prepare(unprepared: &Self) -> R1CSResult<Self> {
+        Ok(unprepared.clone())
+    }
+}
+
+/// An allocated version of [`LinCodePCCommitment`]. The dimensions of the
+/// committed matrix determine the shape of the circuit, so they are constants,
+/// read from the commitment by `new_variable`, or computed from the parameters
+/// by [`Self::new_variable_with_params`], which also works in setup mode.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct LinCodePCCommitmentVar<F: PrimeField> {
+    pub(crate) metadata: Metadata,
+    pub(crate) root: FpVar<F>,
+}
+
+impl<F: PrimeField + Absorb> AllocVar<LinCodePCCommitment<PoseidonMerkleTreeParams<F>>, F>
+    for LinCodePCCommitmentVar<F>
+{
+    fn new_variable<T: Borrow<LinCodePCCommitment<PoseidonMerkleTreeParams<F>>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> R1CSResult<Self> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let commitment = f()?;
+        let commitment = commitment.borrow();
+        Ok(Self {
+            metadata: commitment.metadata.clone(),
+            root: FpVar::new_variable(cs, || Ok(commitment.root), mode)?,
+        })
+    }
+}
+
+impl<F: PrimeField + Absorb> LinCodePCCommitmentVar<F> {
+    /// Allocates a commitment of the scheme `L` with parameters `params` to a
+    /// polynomial of `poly_len` coefficients, or evaluations for multilinear
+    /// polynomials. Unlike `new_variable`, which reads the dimensions of the
+    /// committed matrix from the commitment, this computes them from
+    /// `params`, so that the circuit can also be generated in setup mode,
+    /// where `f` fails.
+    pub fn new_variable_with_params<L, P, T>(
+        cs: impl Into<Namespace<F>>,
+        params: &L::LinCodePCParams,
+        poly_len: usize,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> R1CSResult<Self>
+    where
+        L: LinearEncode<F, PoseidonMerkleTreeParams<F>, P, PoseidonColHasher<F>>,
+        P: Polynomial<F>,
+        T: Borrow<LinCodePCCommitment<PoseidonMerkleTreeParams<F>>>,
+    {
+        let ns = cs.into();
+        let cs = ns.cs();
+        Ok(Self {
+            metadata: metadata::<L, F, P>(params, poly_len)?,
+            root: FpVar::new_variable(cs, || f().map(|c| c.borrow().root), mode)?,
+        })
+    }
+}
+
+impl<F: PrimeField> PrepareGadget<Self, F> for LinCodePCCommitmentVar<F> {
+    fn prepare(unprepared: &Self) -> R1CSResult<Self> {
+        Ok(unprepared.clone())
+    }
+}
+
+/// An allocated version of `LabeledCommitment<LinCodePCCommitment>`.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct LabeledLinCodePCCommitmentVar<F: PrimeField> {
+    pub(crate) label: String,
+    pub(crate) commitment: LinCodePCCommitmentVar<F>,
+}
+
+impl<F: PrimeField + Absorb>
+    AllocVar<LabeledCommitment<LinCodePCCommitment<PoseidonMerkleTreeParams<F>>>, F>
+    for LabeledLinCodePCCommitmentVar<F>
+{
+    fn new_variable<T>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> R1CSResult<Self>
+    where
+        T: Borrow<LabeledCommitment<LinCodePCCommitment<PoseidonMerkleTreeParams<F>>>>,
+    {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let labeled_commitment = f()?;
+        let labeled_commitment = labeled_commitment.borrow();
+        Ok(Self {
+            label: labeled_commitment.label().clone(),
+            commitment: LinCodePCCommitmentVar::new_variable(
+                cs,
+                || Ok(labeled_commitment.commitment()),
+                mode,
+            )?,
+        })
+    }
+}
+
+/// An allocated version of [`LinCodePCProof`]. As for
+/// [`LinCodePCCommitmentVar`], its shape is read from the proof by
+/// `new_variable`, or computed from the parameters by
+/// [`Self::new_variable_with_params`].
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct LinCodePCProofVar<F: PrimeField + Absorb> {
+    pub(crate) paths: Vec<PoseidonPathVar<F>>,
+    pub(crate) v: Vec<FpVar<F>>,
+    pub(crate) columns: Vec<Vec<FpVar<F>>>,
+    pub(crate) well_formedness: Option<Vec<FpVar<F>>>,
+}
+
+impl<F: PrimeField + Absorb> AllocVar<LinCodePCProof<F, PoseidonMerkleTreeParams<F>>, F>
+    for LinCodePCProofVar<F>
+{
+    fn new_variable<T: Borrow<LinCodePCProof<F, PoseidonMerkleTreeParams<F>>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> R1CSResult<Self> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let proof = f()?;
+        let proof = proof.borrow();
+
+        let paths = Vec::new_variable(cs.clone(), || Ok(&proof.opening.paths[..]), mode)?;
+        let v = Vec::new_variable(cs.clone(), || Ok(&proof.opening.v[..]), mode)?;
+        let columns = proof
+            .opening
+            .columns
+            .iter()
+            .map(|column| Vec::new_variable(cs.clone(), || Ok(&column[..]), mode))
+            .collect::<R1CSResult<Vec<_>>>()?;
+        let well_formedness = proof
+            .well_formedness
+            .as_ref()
+            .map(|wf| Vec::new_variable(cs.clone(), || Ok(&wf[..]), mode))
+            .transpose()?;
+
+        Ok(Self {
+            paths,
+            v,
+            columns,
+            well_formedness,
+        })
+    }
+}
+
+impl<F: PrimeField + Absorb> LinCodePCProofVar<F> {
+    /// Allocates a proof of the scheme `L` with parameters `params` for a
+    /// polynomial of `poly_len` coefficients, or evaluations for multilinear
+    /// polynomials. Unlike `new_variable`, which reads the lengths of the
+    /// vectors and paths from the proof, this computes them from `params`, so
+    /// that the circuit can also be generated in setup mode, where `f` fails.
+    pub fn new_variable_with_params<L, P, T>(
+        cs: impl Into<Namespace<F>>,
+        params: &L::LinCodePCParams,
+        poly_len: usize,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> R1CSResult<Self>
+    where
+        L: LinearEncode<F, PoseidonMerkleTreeParams<F>, P, PoseidonColHasher<F>>,
+        P: Polynomial<F>,
+        T: Borrow<LinCodePCProof<F, PoseidonMerkleTreeParams<F>>>,
+    {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let Metadata {
+            n_rows,
+            n_cols,
+            n_ext_cols,
+        } = metadata::<L, F, P>(params, poly_len)?;
+        let t = calculate_t::<F>(params.sec_param(), params.distance(), n_ext_cols)
+            .map_err(|_| SynthesisError::Unsatisfiable)?;
+        // The columns are the leaves of a tree of `n_ext_cols` leaves, padded
+        // to a power of two, whose paths hold the sibling of the leaf apart.
+        let depth = log2(n_ext_cols.next_power_of_two()) as usize;
+
+        // In setup mode, the proof is not known, and is not needed since the
+        // closures of the witnesses are not called.
+        let proof = match f() {
+            Ok(proof) => Some(proof),
+            Err(_) if cs.is_in_setup_mode() => None,
+            Err(e) => return Err(e),
+        };
+        let proof = proof.as_ref().map(|proof| proof.borrow());
+
+        let vector = |values: Option<&Vec<F>>, len: usize| {
+            (0..len)
+                .map(|i| {
+                    FpVar::new_variable(
+                        cs.clone(),
+                        || {
+                            values
+                                .and_then(|values| values.get(i).copied())
+                                .ok_or(SynthesisError::AssignmentMissing)
+                        },
+                        mode,
+                    )
+                })
+                .collect::<R1CSResult<Vec<_>>>()
+        };
+        let dummy_path = Path {
+            leaf_sibling_hash: F::zero(),
+            auth_path: vec![F::zero(); depth.saturating_sub(1)],
+            leaf_index: 0,
+        };
+        let paths = (0..t)
+            .map(|j| {
+                let path = match proof {
+                    Some(proof) => proof
+                        .opening
+                        .paths
+                        .get(j)
+                        .ok_or(SynthesisError::AssignmentMissing)?,
+                    None => &dummy_path,
+                };
+                if path.auth_path.len() != dummy_path.auth_path.len() {
+                    return Err(SynthesisError::Unsatisfiable);
+                }
+                PoseidonPathVar::new_variable(cs.clone(), || Ok(path), mode)
+            })
+            .collect::<R1CSResult<Vec<_>>>()?;
+        let v = vector(proof.map(|proof| &proof.opening.v), n_cols)?;
+        let columns = (0..t)
+            .map(|j| vector(proof.and_then(|proof| proof.opening.columns.get(j)), n_rows))
+            .collect::<R1CSResult<Vec<_>>>()?;
+        let well_formedness = if params.check_well_formedness() {
+            Some(vector(
+                proof.and_then(|proof| proof.well_formedness.as_ref()),
+                n_cols,
+            )?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            paths,
+            v,
+            columns,
+            well_formedness,
+        })
+    }
+}
+
+/// An allocated version of the proof of a [`LinearCodePCS`](super::LinearCodePCS) at a single point,
+/// i.e. one [`LinCodePCProofVar`] per opened polynomial.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct LinCodePCProofArrayVar<F: PrimeField + Absorb>(pub(crate) Vec<LinCodePCProofVar<F>>);
+
+impl<F: PrimeField + Absorb> AllocVar<LPCPArray<F, PoseidonMerkleTreeParams<F>>, F>
+    for LinCodePCProofArrayVar<F>
+{
+    fn new_variable<T: Borrow<LPCPArray<F, PoseidonMerkleTreeParams<F>>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> R1CSResult<Self> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let proofs = f()?;
+        Ok(Self(Vec::new_variable(
+            cs,
+            || Ok(&proofs.borrow()[..]),
+            mode,
+        )?))
+    }
+}
+
+impl<F: PrimeField + Absorb> LinCodePCProofArrayVar<F> {
+    /// Allocates the proofs of the scheme `L` with parameters `params` for
+    /// polynomials of `poly_lens` coefficients, or evaluations for multilinear
+    /// polynomials, with [`LinCodePCProofVar::new_variable_with_params`].
+    pub fn new_variable_with_params<L, P, T>(
+        cs: impl Into<Namespace<F>>,
+        params: &L::LinCodePCParams,
+        poly_lens: &[usize],
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> R1CSResult<Self>
+    where
+        L: LinearEncode<F, PoseidonMerkleTreeParams<F>, P, PoseidonColHasher<F>>,
+        P: Polynomial<F>,
+        T: Borrow<LPCPArray<F, PoseidonMerkleTreeParams<F>>>,
+    {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let proofs = f();
+        poly_lens
+            .iter()
+            .enumerate()
+            .map(|(i, &poly_len)| {
+                LinCodePCProofVar::new_variable_with_params::<L, P, _>(
+                    cs.clone(),
+                    params,
+                    poly_len,
+                    || match &proofs {
+                        Ok(proofs) => proofs
+                            .borrow()
+                            .get(i)
+                            .ok_or(SynthesisError::AssignmentMissing),
+                        Err(e) => Err(*e),
+                    },
+                    mode,
+                )
+            })
+            .collect::<R1CSResult<Vec<_>>>()
+            .map(Self)
+    }
+}
+
+/// An allocated version of the `BatchLCProof` of a [`LinearCodePCS`](super::LinearCodePCS).
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct LinCodeBatchLCProofVar<F: PrimeField + Absorb> {
+    pub(crate) proofs: Vec<LinCodePCProofArrayVar<F>>,
+    pub(crate) evals: Option<Vec<FpVar<F>>>,
+}
+
+impl<F: PrimeField + Absorb>
+    AllocVar<BatchLCProof<F, Vec<LPCPArray<F, PoseidonMerkleTreeParams<F>>>>, F>
+    for LinCodeBatchLCProofVar<F>
+{
+    fn new_variable<T>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> R1CSResult<Self>
+    where
+        T: Borrow<BatchLCProof<F, Vec<LPCPArray<F, PoseidonMerkleTreeParams<F>>>>>,
+    {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let batch_proof = f()?;
+        let BatchLCProof { proof, evals } = batch_proof.borrow();
+
+        let proofs = Vec::new_variable(cs.clone(), || Ok(&proof[..]), mode)?;
+        let evals = evals
+            .as_ref()
+            .map(|evals| Vec::new_variable(cs.clone(), || Ok(&evals[..]), mode))
+            .transpose()?;
+        Ok(Self { proofs, evals })
+    }
+}
+
+/// The shape of the commitments of the scheme `L` with parameters `params` to
+/// polynomials of `poly_len` coefficients, as computed by `commit`.
+fn metadata<L, F, P>(params: &L::LinCodePCParams, poly_len: usize) -> R1CSResult<Metadata>
+where
+    L: LinearEncode<F, PoseidonMerkleTreeParams<F>, P, PoseidonColHasher<F>>,
+    F: PrimeField + Absorb,
+    P: Polynomial<F>,
+{
+    let (n_rows, n_cols) = params.compute_dimensions(poly_len);
+    let n_ext_cols = L::encode(&vec![F::zero(); n_cols], params)
+        .map_err(|_| SynthesisError::Unsatisfiable)?
+        .len();
+    Ok(Metadata {
+        n_rows,
+        n_cols,
+        n_ext_cols,
+    })
+}
+
+/// Generate the little-endian bits of `t` (not necessarily distinct) random
+/// indices in `[0, n)`, mirroring `get_indices_from_sponge`.
+fn get_indices_from_sponge_var<F, S, SV>(
+    n: usize,
+    t: usize,
+    sponge: &mut SV,
+) -> R1CSResult<Vec<Vec<Boolean<F>>>>
+where
+    F: PrimeField,
+    S: CryptographicSponge,
+    SV: CryptographicSpongeVar<F, S>,
+{
+    let bytes_to_squeeze = get_num_bytes(n);
+    let num_bits = log2(n) as usize;
+    let mut indices = Vec::with_capacity(t);
+    for _ in 0..t {
+        absorb_constant(sponge, &challenge_header(b"column index", bytes_to_squeeze))?;
+        let bytes = sponge.squeeze_bytes(bytes_to_squeeze)?;
+
+        // The bytes are read as a big-endian integer.
+        let mut bits = Vec::with_capacity(8 * bytes.len());
+        for byte in bytes.iter().rev() {
+            bits.extend(byte.to_bits_le()?);
+        }
+
+        if n.is_power_of_two() {
+            bits.truncate(num_bits);
+            indices.push(bits);
+            continue;
+        }
+
+        // Reduce modulo `n` by witnessing the quotient and the remainder.
+        let cs = sponge.cs();
+        let ind = bits.value().map(|bits| {
+            bits.iter()
+                .rev()
+                .fold(0usize, |acc, &b| (acc << 1) + b as usize)
+        });
+        let quotient = (0..bits.len())
+            .map(|i| Boolean::new_witness(cs.clone(), || ind.map(|ind| ((ind / n) >> i) & 1 == 1)))
+            .collect::<R1CSResult<Vec<_>>>()?;
+        let remainder = (0..num_bits)
+            .map(|i| Boolean::new_witness(cs.clone(), || ind.map(|ind| ((ind % n) >> i) & 1 == 1)))
+            .collect::<R1CSResult<Vec<_>>>()?;
+
+        let recomposed = Boolean::le_bits_to_fp(&quotient)? * F::from(n as u64)
+            + Boolean::le_bits_to_fp(&remainder)?;
+        recomposed.enforce_equal(&Boolean::le_bits_to_fp(&bits)?)?;
+        Boolean::enforce_smaller_or_equal_than_le(&remainder, [(n - 1) as u64])?;
+
+        indices.push(remainder);
+    }
+    Ok(indices)
+}
+
+/// Absorbs `bytes` as constants, as the native sponge absorbs them.
+fn absorb_constant<F, S, SV>(sponge: &mut SV, bytes: &[u8]) -> R1CSResult<()>
+where
+    F: PrimeField,
+    S: CryptographicSponge,
+    SV: CryptographicSpongeVar<F, S>,
+{
+    sponge.absorb(&UInt8::constant_vec(bytes))
+}
+
+/// In-circuit version of [`crate::Transcript::absorb_labelled`] for a vector of
+/// field elements.
+fn absorb_labelled_var<F, S, SV>(
+    sponge: &mut SV,
+    label: &[u8],
+    message: &[FpVar<F>],
+) -> R1CSResult<()>
+where
+    F: PrimeField,
+    S: CryptographicSponge,
+    SV: CryptographicSpongeVar<F, S>,
+{
+    let len = message.len() * F::zero().compressed_size();
+    absorb_constant(sponge, &message_header(label, len))?;
+    sponge.absorb(&message.to_vec())
+}
+
+fn inner_product_var<F: PrimeField>(v1: &[FpVar<F>], v2: &[FpVar<F>]) -> FpVar<F> {
+    v1.iter()
+        .zip(v2)
+        .map(|(li, ri)| li * ri)
+        .fold(FpVar::zero(), |acc, x| acc + x)
+}
+
+/// The value of `x`, which must be a constant, so that it is known when the
+/// constraints are generated.
+fn constant_value<F: PrimeField>(x: &EmulatedFpVar<F, F>) -> R1CSResult<F> {
+    if !x.is_constant() {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+    x.value()
+}
+
+/// Turn an emulated element of `F` over `F` itself into a native one.
+fn emulated_to_fp<F: PrimeField>(x: &EmulatedFpVar<F, F>) -> R1CSResult<FpVar<F>> {
+    match x {
+        EmulatedFpVar::Constant(c) => Ok(FpVar::constant(*c)),
+        EmulatedFpVar::Var(_) => Boolean::le_bits_to_fp(&x.to_bits_le()?),
+    }
+}
+
+/// Gadget re-executing the verifier of a [`LinearCodePCS`](super::LinearCodePCS) whose column hash
+/// and Merkle tree are the Poseidon-based ones of this module (see
+/// [`PoseidonMerkleTreeParams`]), inside a circuit over the same field `F`.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct LinearCodePCSGadget<L, F, P>
+where
+    F: PrimeField + Absorb,
+    P: Polynomial<F>,
+    L: LinearEncodeGadget<F, PoseidonMerkleTreeParams<F>, P, PoseidonColHasher<F>>,
+{
+    _phantom: PhantomData<(L, F, P)>,
+}
+
+impl<L, F, P> LinearCodePCSGadget<L, F, P>
+where
+    F: PrimeField + Absorb,
+    P: Polynomial<F>,
+    L: LinearEncodeGadget<F, PoseidonMerkleTreeParams<F>, P, PoseidonColHasher<F>>,
+    L::LinCodePCParams: Clone,
+{
+    /// In-circuit version of [`LinearCodePCS::check`](super::LinearCodePCS::check): `sponge` must be in the
+    /// same state as the sponge passed to `LinearCodePCS::open`. The output is
+    /// `true` iff the native `check` would have returned `Ok(())`, and in
+    /// particular `false` if `commitments`, `values` and `proof_array` do not
+    /// have the same length, or if a proof does not have the shape given by
+    /// its commitment.
+    pub fn check<S, SV>(
+        vk: &LinCodePCParamsVar<F, L::LinCodePCParams>,
+        commitments: &[LinCodePCCommitmentVar<F>],
+        point: &L::PointVar,
+        values: &[FpVar<F>],
+        proof_array: &LinCodePCProofArrayVar<F>,
+        sponge: &mut SV,
+    ) -> R1CSResult<Boolean<F>>
+    where
+        S: CryptographicSponge,
+        SV: CryptographicSpongeVar<F, S>,
+    {
+        if commitments.len() != values.len() || commitments.len() != proof_array.0.len() {
+            return Ok(Boolean::FALSE);
+        }
+
+        let mut result = Boolean::TRUE;
+        for bytes in context_messages(L::PROTOCOL_NAME, &vk.params)
+            .map_err(|_| SynthesisError::Unsatisfiable)?
+        {
+            absorb_constant(sponge, &bytes)?;
+        }
+
+        for ((commitment, value), proof) in commitments.iter().zip(values).zip(&proof_array.0) {
+            let n_rows = commitment.metadata.n_rows;
+            let n_cols = commitment.metadata.n_cols;
+            let n_ext_cols = commitment.metadata.n_ext_cols;
+            if n_rows == 0 || n_cols == 0 || n_ext_cols < n_cols {
+                return Ok(Boolean::FALSE);
+            }
+            let t = calculate_t::<F>(vk.params.sec_param(), vk.params.distance(), n_ext_cols)
+                .map_err(|_| SynthesisError::Unsatisfiable)?;
+
+            let root = commitment.root.to_bytes_le()?;
+            absorb_constant(sponge, &message_header(b"root", root.len()))?;
+            sponge.absorb(&root)?;
+
+            let out = if vk.params.check_well_formedness() {
+                let v = match &proof.well_formedness {
+                    Some(v) if v.len() == n_cols => v,
+                    _ => return Ok(Boolean::FALSE),
+                };
+                absorb_constant(
+                    sponge,
+                    &challenge_header(b"well-formedness challenge", n_rows),
+                )?;
+                let r = sponge.squeeze_field_elements(n_rows)?;
+                absorb_labelled_var(sponge, b"well-formedness", v)?;
+
+                Some((v, r))
+            } else if proof.well_formedness.is_some() {
+                return Ok(Boolean::FALSE);
+            } else {
+                None
+            };
+
+            // 1. Seed the transcript with the point and the received vector
+            absorb_labelled_var(sponge, b"point", &L::point_to_vec_var(point))?;
+            absorb_labelled_var(sponge, b"evaluation vector", &proof.v)?;
+
+            // 2. Ask random oracle for the `t` indices where the checks happen.
+            let indices = get_indices_from_sponge_var(n_ext_cols, t, sponge)?;
+
+            // The proof and the dimensions of the commitment are untrusted, and
+            // `inner_product_var` would ignore the entries of the longer
+            // operand: check their lengths as the native `check` does.
+            if proof.columns.len() != t
+                || proof.paths.len() != t
+                || proof.v.len() != n_cols
+                || proof.columns.iter().any(|column| column.len() != n_rows)
+            {
+                return Ok(Boolean::FALSE);
+            }
+
+            // 3. Hash the received columns into leaf hashes and
+            // 4. verify the paths for each of them.
+            for ((column, path), index) in proof.columns.iter().zip(&proof.paths).zip(&indices) {
+                let leaf = PoseidonColHasherGadget::evaluate(&vk.col_hash_params, column)?;
+                let mut path = path.clone();
+                let mut position = index.clone();
+                if position.is_empty() {
+                    position.push(Boolean::FALSE);
+                }
+                path.set_leaf_position(position);
+                result &= path.verify_membership(
+                    &(),
+                    &vk.two_to_one_hash_params,
+                    &commitment.root,
+                    &leaf,
+                )?;
+            }
+
+            // 5. Compute the encoding w = E(v) at the queried positions.
+            let w = L::encode_at_var(&proof.v, &indices, &vk.params)?;
+
+            // 6. Compute `a`, `b` to right- and left- multiply with the matrix `M`.
+            let (a, b) = L::tensor_var(point, n_cols, n_rows)?;
+
+            // 7. Probabilistic checks that whatever the prover sent,
+            // matches with what the verifier computed for himself.
+            if let Some((well_formedness, r)) = out {
+                let w_well_formedness = L::encode_at_var(well_formedness, &indices, &vk.params)?;
+                for (column, w_j) in proof.columns.iter().zip(&w_well_formedness) {
+                    result &= inner_product_var(&r, column).is_eq(w_j)?;
+                }
+            }
+            for (column, w_j) in proof.columns.iter().zip(&w) {
+                result &= inner_product_var(&b, column).is_eq(w_j)?;
+            }
+
+            result &= inner_product_var(&proof.v, &a).is_eq(value)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Mirrors the default `PolynomialCommitment::batch_check`: `queries` maps each
+    /// point label to the point and the labels of the polynomials opened there.
+    fn batch_check<S, SV>(
+        vk: &LinCodePCParamsVar<F, L::LinCodePCParams>,
+        commitments: &BTreeMap<&String, &LinCodePCCommitmentVar<F>>,
+        queries: &BTreeMap<String, (L::PointVar, BTreeSet<String>)>,
+        evaluations: impl Fn(&String, &String) -> R1CSResult<FpVar<F>>,
+        proofs: &[LinCodePCProofArrayVar<F>],
+        sponge: &mut SV,
+    ) -> R1CSResult<Boolean<F>>
+    where
+        S: CryptographicSponge,
+        SV: CryptographicSpongeVar<F, S>,
+    {
+        if proofs.len() != queries.len() {
+            return Ok(Boolean::FALSE);
+        }
+
+        let mut result = Boolean::TRUE;
+        for ((point_label, (point, labels)), proof) in queries.iter().zip(proofs) {
+            let mut comms = Vec::new();
+            let mut values = Vec::new();
+            for label in labels {
+                let commitment = commitments
+                    .get(label)
+                    .ok_or(SynthesisError::AssignmentMissing)?;
+                comms.push((*commitment).clone());
+                values.push(evaluations(label, point_label)?);
+            }
+            result &= Self::check(vk, &comms, point, &values, proof, sponge)?;
+        }
+        Ok(result)
+    }
+}
+
+/// Gadget re-executing the verifier of [`PoseidonUVLigero`], the only scheme
+/// of this module for which [`PCCheckVar`] is implemented.
+pub type PoseidonUVLigeroGadget<F, P> = LinearCodePCSGadget<
+    UnivariateLigero<F, PoseidonMerkleTreeParams<F>, P, PoseidonColHasher<F>>,
+    F,
+    P,
+>;
+
+type PoseidonLigeroPCParams<F> =
+    LigeroPCParams<F, PoseidonMerkleTreeParams<F>, PoseidonColHasher<F>>;
+
+impl<F, P> PCCheckVar<F, P, PoseidonUVLigero<F, P>, F> for PoseidonUVLigeroGadget<F, P>
+where
+    F: PrimeField + Absorb,
+    P: DenseUVPolynomial<F, Point = F>,
+{
+    type PreparedVerifierKey = PoseidonLigeroPCParams<F>;
+    type PreparedCommitment = LinCodePCCommitment<PoseidonMerkleTreeParams<F>>;
+    type VerifierKeyVar = LinCodePCParamsVar<F, PoseidonLigeroPCParams<F>>;
+    type PreparedVerifierKeyVar = LinCodePCParamsVar<F, PoseidonLigeroPCParams<F>>;
+    type CommitmentVar = LinCodePCCommitmentVar<F>;
+    type PreparedCommitmentVar = LinCodePCCommitmentVar<F>;
+    type LabeledCommitmentVar = LabeledLinCodePCCommitmentVar<F>;
+    type PreparedLabeledCommitmentVar = LabeledLinCodePCCommitmentVar<F>;
+    type ProofVar = LinCodePCProofArrayVar<F>;
+    type BatchLCProofVar = LinCodeBatchLCProofVar<F>;
+
+    /// The Fiat-Shamir transcript is a fresh Poseidon sponge instantiated with
+    /// the column hash parameters, i.e. the native counterpart is
+    /// `PoseidonSponge::new(vk.col_hash_params())`. `rand_data` is not used, as
+    /// all the challenges of the scheme are squeezed from the transcript.
+    fn batch_check_evaluations(
+        cs: ConstraintSystemRef<F>,
+        verification_key: &Self::VerifierKeyVar,
+        commitments: &[Self::LabeledCommitmentVar],
+        query_set: &QuerySetVar<F, F>,
+        evaluations: &EvaluationsVar<F, F>,
+        proofs: &[Self::ProofVar],
+        _rand_data: &PCCheckRandomDataVar<F, F>,
+    ) -> R1CSResult<Boolean<F>> {
+        let commitments: BTreeMap<_, _> = commitments
+            .iter()
+            .map(|c| (&c.label, &c.commitment))
+            .collect();
+
+        let mut queries = BTreeMap::new();
+        let mut emulated_points = BTreeMap::new();
+        for (label, point) in query_set.0.iter() {
+            if !queries.contains_key(&point.name) {
+                queries.insert(
+                    point.name.clone(),
+                    (emulated_to_fp(&point.value)?, BTreeSet::new()),
+                );
+                emulated_points.insert(point.name.clone(), point.clone());
+            }
+            queries
+                .get_mut(&point.name)
+                .unwrap()
+                .1
+                .insert(label.clone());
+        }
+
+        let eval_lookup = |label: &String, point_label: &String| {
+            let mut key = emulated_points
+                .get(point_label)
+                .ok_or(SynthesisError::AssignmentMissing)?
+                .clone();
+            key.name = label.clone();
+            evaluations
+                .0
+                .get(&key)
+                .ok_or(SynthesisError::AssignmentMissing)
+                .and_then(emulated_to_fp)
+        };
+
+        let mut sponge = PoseidonSpongeVar::new(cs, verification_key.params.col_hash_params());
+        Self::batch_check::<PoseidonSponge<F>, _>(
+            verification_key,
+            &commitments,
+            &queries,
+            eval_lookup,
+            proofs,
+            &mut sponge,
+        )
+    }
+
+    /// Mirrors the default `PolynomialCommitment::check_combinations`, with the
+    /// transcript of [`Self::batch_check_evaluations`]. The order of the evaluations
+    /// in `proof` depends on the values of the query points, so the points of
+    /// `query_set` must be constants: otherwise, the constraints would depend on
+    /// the witness, and `SynthesisError::Unsatisfiable` is returned.
+    fn prepared_check_combinations(
+        cs: ConstraintSystemRef<F>,
+        prepared_verification_key: &Self::PreparedVerifierKeyVar,
+        linear_combinations: &[LinearCombinationVar<F, F>],
+        prepared_commitments: &[Self::PreparedLabeledCommitmentVar],
+        query_set: &QuerySetVar<F, F>,
+        evaluations: &EvaluationsVar<F, F>,
+        proof: &Self::BatchLCProofVar,
+        _rand_data: &PCCheckRandomDataVar<F, F>,
+    ) -> R1CSResult<Boolean<F>> {
+        let lc_s: BTreeMap<_, _> = linear_combinations
+            .iter()
+            .map(|lc| (&lc.label, lc))
+            .collect();
+
+        // Rearrange the queries on linear combinations into queries on polynomials.
+        let mut points = BTreeMap::new();
+        let mut poly_query_set = BTreeSet::new();
+        for (lc_label, point) in query_set.0.iter() {
+            if let Some(lc) = lc_s.get(lc_label) {
+                let point_value = constant_value(&point.value)?;
+                points
+                    .entry(point.name.clone())
+                    .or_insert((point_value, emulated_to_fp(&point.value)?));
+                for (_, term) in lc.terms.iter() {
+                    if let LCTerm::PolyLabel(l) = term {
+                        poly_query_set.insert((l.clone(), point_value, point.name.clone()));
+                    }
+                }
+            }
+        }
+
+        let evals = match &proof.evals {
+            Some(evals) if evals.len() == poly_query_set.len() => evals,
+            _ => return Ok(Boolean::FALSE),
+        };
+        let mut queries: BTreeMap<String, (FpVar<F>, BTreeSet<String>)> = BTreeMap::new();
+        let mut poly_evals = BTreeMap::new();
+        for ((poly_label, point_value, point_label), eval) in poly_query_set.into_iter().zip(evals)
+        {
+            queries
+                .entry(point_label.clone())
+                .or_insert((points[&point_label].1.clone(), BTreeSet::new()))
+                .1
+                .insert(poly_label.clone());
+            poly_evals.insert((poly_label, point_value), eval.clone());
+        }
+
+        let mut result = Boolean::TRUE;
+        for (lc_label, point) in query_set.0.iter() {
+            if let Some(lc) = lc_s.get(lc_label) {
+                let key = LabeledPointVar {
+                    name: lc_label.clone(),
+                    value: point.value.clone(),
+                };
+                let claimed_rhs = evaluations
+                    .0
+                    .get(&key)
+                    .ok_or(SynthesisError::AssignmentMissing)
+                    .and_then(emulated_to_fp)?;
+                let point_value = constant_value(&point.value)?;
+
+                let mut actual_rhs = FpVar::zero();
+                for (coeff, term) in lc.terms.iter() {
+                    let eval = match term {
+                        LCTerm::One => FpVar::one(),
+                        LCTerm::PolyLabel(l) => poly_evals
+                            .get(&(l.clone(), point_value))
+                            .ok_or(SynthesisError::AssignmentMissing)?
+                            .clone(),
+                    };
+                    match coeff {
+                        LinearCombinationCoeffVar::One => actual_rhs += eval,
+                        LinearCombinationCoeffVar::MinusOne => actual_rhs -= eval,
+                        LinearCombinationCoeffVar::Var(c) => {
+                            actual_rhs += emulated_to_fp(c)? * eval
+                        }
+                    }
+                }
+
+                result &= claimed_rhs.is_eq(&actual_rhs)?;
+            }
+        }
+
+        let commitments: BTreeMap<_, _> = prepared_commitments
+            .iter()
+            .map(|c| (&c.label, &c.commitment))
+            .collect();
+        let eval_lookup = |label: &String, point_label: &String| {
+            let point_value = points
+                .get(point_label)
+                .ok_or(SynthesisError::AssignmentMissing)?
+                .0;
+            poly_evals
+                .get(&(label.clone(), point_value))
+                .cloned()
+                .ok_or(SynthesisError::AssignmentMissing)
+        };
+
+        let mut sponge =
+            PoseidonSpongeVar::new(cs, prepared_verification_key.params.col_hash_params());
+        result &= Self::batch_check::<PoseidonSponge<F>, _>(
+            prepared_verification_key,
+            &commitments,
+            &queries,
+            eval_lookup,
+            &proof.proofs,
+            &mut sponge,
+        )?;
+
+        Ok(result)
+    }
+
+    fn create_labeled_commitment(
+        label: String,
+        commitment: Self::CommitmentVar,
+        _degree_bound: Option<FpVar<F>>,
+    ) -> Self::LabeledCommitmentVar {
+        LabeledLinCodePCCommitmentVar { label, commitment }
+    }
+
+    fn create_prepared_labeled_commitment(
+        label: String,
+        commitment: Self::PreparedCommitmentVar,
+        _degree_bound: Option<FpVar<F>>,
+    ) -> Self::PreparedLabeledCommitmentVar {
+        LabeledLinCodePCCommitmentVar { label, commitment }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        linear_codes::{default_poseidon_config, BrakedownPCParams, LinearCodePCS},
+        LabeledPolynomial, LinearCombination, PolynomialCommitment, QuerySet,
+    };
+
+    use ark_bls12_381::Fr;
+    use ark_poly::{univariate::DensePolynomial, DenseMultilinearExtension};
+    use ark_relations::r1cs::{ConstraintSystem, SynthesisMode};
+    use ark_std::{test_rng, UniformRand};
+
+    type UVPoly = DensePolynomial<Fr>;
+    type MLPoly = DenseMultilinearExtension<Fr>;
+    type PoseidonLinearCodePCS<L, F, P> =
+        LinearCodePCS<L, F, P, PoseidonMerkleTreeParams<F>, PoseidonColHasher<F>>;
+    type UVLigero =
+        UnivariateLigero<Fr, PoseidonMerkleTreeParams<Fr>, UVPoly, PoseidonColHasher<Fr>>;
+
+    fn ligero_params() -> LigeroPCParams<Fr, PoseidonMerkleTreeParams<Fr>, PoseidonColHasher<Fr>> {
+        LigeroPCParams::new(
+            128,
+            4,
+            true,
+            (),
+            default_poseidon_config().unwrap(),
+            default_poseidon_config().unwrap(),
+        )
+    }
+
+    type ProofArray = LPCPArray<Fr, PoseidonMerkleTreeParams<Fr>>;
+    type Opening = (
+        LinCodePCCommitment<PoseidonMerkleTreeParams<Fr>>,
+        ProofArray,
+        Fr,
+    );
+
+    /// Ways of tampering with a valid proof, each of which the gadget must
+    /// reject.
+    fn tamperings() -> [fn(&mut ProofArray); 5] {
+        [
+            |proof| proof[0].opening.paths[0].leaf_sibling_hash += Fr::from(1u8),
+            |proof| proof[0].opening.columns[0][0] += Fr::from(1u8),
+            |proof| proof[0].opening.v[0] += Fr::from(1u8),
+            |proof| {
+                proof[0].opening.v.pop();
+            },
+            |proof| {
+                proof[0].opening.columns[0].pop();
+            },
+        ]
+    }
+
+    /// Commits to `poly` and opens it at `point` natively, and checks the
+    /// proof.
+    fn open_natively<L, P>(pp: &L::LinCodePCParams, poly: P, point: &P::Point) -> Opening
+    where
+        P: Polynomial<Fr>,
+        L: LinearEncodeGadget<Fr, PoseidonMerkleTreeParams<Fr>, P, PoseidonColHasher<Fr>>,
+        PoseidonLinearCodePCS<L, Fr, P>: PolynomialCommitment<
+            Fr,
+            P,
+            VerifierKey = L::LinCodePCParams,
+            CommitterKey = L::LinCodePCParams,
+            Commitment = LinCodePCCommitment<PoseidonMerkleTreeParams<Fr>>,
+            Proof = ProofArray,
+        >,
+    {
+        let labeled_poly = LabeledPolynomial::new("test".to_string(), poly, None, None);
+        let value = labeled_poly.evaluate(point);
+        let sponge = PoseidonSponge::new(pp.col_hash_params());
+
+        let (comms, states) =
+            PoseidonLinearCodePCS::<L, Fr, P>::commit(pp, [&labeled_poly], None).unwrap();
+        let proof = PoseidonLinearCodePCS::<L, Fr, P>::open(
+            pp,
+            [&labeled_poly],
+            &comms,
+            point,
+            &mut sponge.clone(),
+            &states,
+            None,
+        )
+        .unwrap();
+        PoseidonLinearCodePCS::<L, Fr, P>::check(
+            pp,
+            &comms,
+            point,
+            [value],
+            &proof,
+            &mut sponge.clone(),
+            None,
+        )
+        .unwrap();
+        (comms[0].commitment().clone(), proof, value)
+    }
+
+    /// Opens `poly` at `point` natively and runs the gadget on the result,
+    /// tampered with by `tamper`, with the claimed value shifted by `offset`.
+    fn check_gadget<L, P>(
+        pp: &L::LinCodePCParams,
+        poly: P,
+        point: P::Point,
+        point_var: impl FnOnce(ConstraintSystemRef<Fr>) -> L::PointVar,
+        offset: Fr,
+        tamper: fn(&mut ProofArray),
+    ) -> bool
+    where
+        P: Polynomial<Fr>,
+        L: LinearEncodeGadget<Fr, PoseidonMerkleTreeParams<Fr>, P, PoseidonColHasher<Fr>>,
+        L::LinCodePCParams: Clone,
+        PoseidonLinearCodePCS<L, Fr, P>: PolynomialCommitment<
+            Fr,
+            P,
+            VerifierKey = L::LinCodePCParams,
+            CommitterKey = L::LinCodePCParams,
+            Commitment = LinCodePCCommitment<PoseidonMerkleTreeParams<Fr>>,
+            Proof = ProofArray,
+        >,
+    {
+        let (comm, mut proof, value) = open_natively::<L, P>(pp, poly, &point);
+        tamper(&mut proof);
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let vk_var = LinCodePCParamsVar::new_constant(cs.clone(), pp.clone()).unwrap();
+        let comm_var = LinCodePCCommitmentVar::new_witness(cs.clone(), || Ok(&comm)).unwrap();
+        let point_var = point_var(cs.clone());
+        let value_var = FpVar::new_input(cs.clone(), || Ok(value + offset)).unwrap();
+        let proof_var = LinCodePCProofArrayVar::new_witness(cs.clone(), || Ok(&proof)).unwrap();
+        let mut sponge_var = PoseidonSpongeVar::new(cs.clone(), pp.col_hash_params());
+
+        let result = LinearCodePCSGadget::<L, Fr, P>::check(
+            &vk_var,
+            &[comm_var],
+            &point_var,
+            &[value_var],
+            &proof_var,
+            &mut sponge_var,
+        )
+        .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        result.value().unwrap()
+    }
+
+    /// Checks that the gadget accepts the opening of `poly` at `point`, and
+    /// rejects wrong values and tampered proofs.
+    fn gadget_test<L, P>(
+        pp: &L::LinCodePCParams,
+        poly: P,
+        point: P::Point,
+        point_var: impl Fn(ConstraintSystemRef<Fr>) -> L::PointVar,
+    ) where
+        P: Polynomial<Fr>,
+        L: LinearEncodeGadget<Fr, PoseidonMerkleTreeParams<Fr>, P, PoseidonColHasher<Fr>>,
+        L::LinCodePCParams: Clone,
+        PoseidonLinearCodePCS<L, Fr, P>: PolynomialCommitment<
+            Fr,
+            P,
+            VerifierKey = L::LinCodePCParams,
+            CommitterKey = L::LinCodePCParams,
+            Commitment = LinCodePCCommitment<PoseidonMerkleTreeParams<Fr>>,
+            Proof = ProofArray,
+        >,
+    {
+        let check = |offset: u8, tamper: fn(&mut ProofArray)| {
+            check_gadget::<L, P>(
+                pp,
+                poly.clone(),
+                point.clone(),
+                &point_var,
+                Fr::from(offset),
+                tamper,
+            )
+        };
+        assert!(check(0, |_| {}));
+        assert!(!check(1, |_| {}));
+        for tamper in tamperings() {
+            assert!(!check(0, tamper));
+        }
+    }
+
+    #[test]
+    fn uv_ligero_gadget_test() {
+        let rng = &mut test_rng();
+        let pp = ligero_params();
+        let poly = UVPoly::rand(15, rng);
+        let point = Fr::rand(rng);
+
+        let point_var = |cs| FpVar::new_witness(cs, || Ok(point)).unwrap();
+        gadget_test::<UVLigero, _>(&pp, poly, point, point_var);
+    }
+
+    #[test]
+    fn ml_ligero_gadget_test() {
+        type L = MultilinearLigero<Fr, PoseidonMerkleTreeParams<Fr>, MLPoly, PoseidonColHasher<Fr>>;
+
+        let rng = &mut test_rng();
+        let pp = ligero_params();
+        let poly = MLPoly::rand(5, rng);
+        let point: Vec<Fr> = (0..5).map(|_| Fr::rand(rng)).collect();
+
+        let point_var = |cs| Vec::new_witness(cs, || Ok(&point[..])).unwrap();
+        gadget_test::<L, _>(&pp, poly, point.clone(), point_var);
+    }
+
+    #[test]
+    fn ml_brakedown_gadget_test() {
+        type L =
+            MultilinearBrakedown<Fr, PoseidonMerkleTreeParams<Fr>, MLPoly, PoseidonColHasher<Fr>>;
+
+        let rng = &mut test_rng();
+        let num_vars = 5;
+        let pp = BrakedownPCParams::default(
+            rng,
+            1 << num_vars,
+            true,
+            (),
+            default_poseidon_config().unwrap(),
+            default_poseidon_config().unwrap(),
+        );
+        let poly = MLPoly::rand(num_vars, rng);
+        let point: Vec<Fr> = (0..num_vars).map(|_| Fr::rand(rng)).collect();
+
+        let point_var = |cs| Vec::new_witness(cs, || Ok(&point[..])).unwrap();
+        gadget_test::<L, _>(&pp, poly, point.clone(), point_var);
+    }
+
+    /// Generates the circuit of the gadget for an opening of a polynomial of
+    /// `poly_len` coefficients, allocated with `new_variable_with_params`, in
+    /// setup mode or, given the `opening`, in proving mode. Outputs the number
+    /// of constraints and, in proving mode, the output of the gadget.
+    fn check_gadget_with_params<L, P>(
+        pp: &L::LinCodePCParams,
+        poly_len: usize,
+        opening: Option<&Opening>,
+        point_var: impl FnOnce(ConstraintSystemRef<Fr>) -> L::PointVar,
+    ) -> (usize, Option<bool>)
+    where
+        P: Polynomial<Fr>,
+        L: LinearEncodeGadget<Fr, PoseidonMerkleTreeParams<Fr>, P, PoseidonColHasher<Fr>>,
+        L::LinCodePCParams: Clone,
+    {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        if opening.is_none() {
+            cs.set_mode(SynthesisMode::Setup);
+        }
+        let vk_var = LinCodePCParamsVar::new_constant(cs.clone(), pp.clone()).unwrap();
+        let comm_var = LinCodePCCommitmentVar::new_variable_with_params::<L, P, _>(
+            cs.clone(),
+            pp,
+            poly_len,
+            || {
+                opening
+                    .map(|o| &o.0)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+            AllocationMode::Witness,
+        )
+        .unwrap();
+        let point_var = point_var(cs.clone());
+        let value_var = FpVar::new_input(cs.clone(), || {
+            opening
+                .map(|o| o.2)
+                .ok_or(SynthesisError::AssignmentMissing)
+        })
+        .unwrap();
+        let proof_var = LinCodePCProofArrayVar::new_variable_with_params::<L, P, _>(
+            cs.clone(),
+            pp,
+            &[poly_len],
+            || {
+                opening
+                    .map(|o| &o.1)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+            AllocationMode::Witness,
+        )
+        .unwrap();
+        let mut sponge_var = PoseidonSpongeVar::new(cs.clone(), pp.col_hash_params());
+
+        let result = LinearCodePCSGadget::<L, Fr, P>::check(
+            &vk_var,
+            &[comm_var],
+            &point_var,
+            &[value_var],
+            &proof_var,
+            &mut sponge_var,
+        )
+        .unwrap();
+        let output = opening.map(|_| {
+            assert!(cs.is_satisfied().unwrap());
+            result.value().unwrap()
+        });
+        (cs.num_constraints(), output)
+    }
+
+    #[test]
+    fn setup_mode_gadget_test() {
+        type L =
+            MultilinearBrakedown<Fr, PoseidonMerkleTreeParams<Fr>, MLPoly, PoseidonColHasher<Fr>>;
+
+        let rng = &mut test_rng();
+        let pp = ligero_params();
+        let poly = UVPoly::rand(15, rng);
+        let point = Fr::rand(rng);
+        let opening = open_natively::<UVLigero, _>(&pp, poly, &point);
+
+        let missing = |cs: ConstraintSystemRef<Fr>| {
+            FpVar::new_witness(cs, || Err::<Fr, _>(SynthesisError::AssignmentMissing)).unwrap()
+        };
+        let (setup, _) = check_gadget_with_params::<UVLigero, UVPoly>(&pp, 16, None, missing);
+        let (proving, output) =
+            check_gadget_with_params::<UVLigero, UVPoly>(&pp, 16, Some(&opening), |cs| {
+                FpVar::new_witness(cs, || Ok(point)).unwrap()
+            });
+        assert_eq!(setup, proving);
+        assert_eq!(output, Some(true));
+
+        let num_vars = 5;
+        let pp = BrakedownPCParams::default(
+            rng,
+            1 << num_vars,
+            true,
+            (),
+            default_poseidon_config().unwrap(),
+            default_poseidon_config().unwrap(),
+        );
+        let poly = MLPoly::rand(num_vars, rng);
+        let point: Vec<Fr> = (0..num_vars).map(|_| Fr::rand(rng)).collect();
+        let opening = open_natively::<L, _>(&pp, poly, &point);
+
+        let missing = |cs: ConstraintSystemRef<Fr>| {
+            (0..num_vars)
+                .map(|_| {
+                    FpVar::new_witness(cs.clone(), || {
+                        Err::<Fr, _>(SynthesisError::AssignmentMissing)
+                    })
+                    .unwrap()
+                })
+                .collect()
+        };
+        let (setup, _) = check_gadget_with_params::<L, MLPoly>(&pp, 1 << num_vars, None, missing);
+        let (proving, output) =
+            check_gadget_with_params::<L, MLPoly>(&pp, 1 << num_vars, Some(&opening), |cs| {
+                Vec::new_witness(cs, || Ok(&point[..])).unwrap()
+            });
+        assert_eq!(setup, proving);
+        assert_eq!(output, Some(true));
+    }
+
+    /// Runs `prepared_check_combinations` on a proof for `a + coeff * b` at one
+    /// point, allocated in the constraint system with `point_var`.
+    fn check_combinations_gadget(
+        point_var: impl FnOnce(ConstraintSystemRef<Fr>, Fr) -> EmulatedFpVar<Fr, Fr>,
+    ) -> R1CSResult<bool> {
+        let rng = &mut test_rng();
+        let pp = ligero_params();
+        let polys: Vec<_> = ["a", "b"]
+            .iter()
+            .map(|label| {
+                LabeledPolynomial::new(label.to_string(), UVPoly::rand(15, rng), None, None)
+            })
+            .collect();
+        let point = Fr::rand(rng);
+        let coeff = Fr::rand(rng);
+        let lc = LinearCombination::new("lc", vec![(Fr::from(1u8), "a"), (coeff, "b")]);
+        let mut query_set = QuerySet::new();
+        query_set.insert(("lc".to_string(), ("z".to_string(), point)));
+        let value = polys[0].evaluate(&point) + coeff * polys[1].evaluate(&point);
+
+        let (comms, states) = PoseidonUVLigero::<Fr, UVPoly>::commit(&pp, &polys, None).unwrap();
+        let proof = PoseidonUVLigero::<Fr, UVPoly>::open_combinations(
+            &pp,
+            [&lc],
+            &polys,
+            &comms,
+            &query_set,
+            &mut PoseidonSponge::new(pp.col_hash_params()),
+            &states,
+            None,
+        )
+        .unwrap();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let vk_var = LinCodePCParamsVar::new_constant(cs.clone(), pp.clone()).unwrap();
+        let comm_vars = comms
+            .iter()
+            .map(|c| LabeledLinCodePCCommitmentVar::new_witness(cs.clone(), || Ok(c)).unwrap())
+            .collect::<Vec<_>>();
+        let point_var = LabeledPointVar {
+            name: "z".to_string(),
+            value: point_var(cs.clone(), point),
+        };
+        let lc_var = LinearCombinationVar {
+            label: "lc".to_string(),
+            terms: vec![
+                (LinearCombinationCoeffVar::One, "a".into()),
+                (
+                    LinearCombinationCoeffVar::Var(
+                        EmulatedFpVar::new_witness(cs.clone(), || Ok(coeff)).unwrap(),
+                    ),
+                    "b".into(),
+                ),
+            ],
+        };
+        let query_set_var = QuerySetVar(
+            vec![("lc".to_string(), point_var.clone())]
+                .into_iter()
+                .collect(),
+        );
+        let mut evaluations_var = EvaluationsVar(Default::default());
+        evaluations_var.0.insert(
+            LabeledPointVar {
+                name: "lc".to_string(),
+                value: point_var.value.clone(),
+            },
+            EmulatedFpVar::new_input(cs.clone(), || Ok(value)).unwrap(),
+        );
+        let proof_var = LinCodeBatchLCProofVar::new_witness(cs.clone(), || Ok(&proof)).unwrap();
+        let rand_data = PCCheckRandomDataVar {
+            opening_challenges: vec![],
+            opening_challenges_bits: vec![],
+            batching_rands: vec![],
+            batching_rands_bits: vec![],
+        };
+
+        let result =
+            <PoseidonUVLigeroGadget<Fr, UVPoly> as PCCheckVar<_, _, _, _>>::prepared_check_combinations(
+                cs.clone(),
+                &vk_var,
+                &[lc_var],
+                &comm_vars,
+                &query_set_var,
+                &evaluations_var,
+                &proof_var,
+                &rand_data,
+            )?;
+        assert!(cs.is_satisfied().unwrap());
+        result.value()
+    }
+
+    #[test]
+    fn uv_ligero_check_combinations_gadget_test() {
+        assert!(check_combinations_gadget(
+            |cs, point| EmulatedFpVar::new_constant(cs, point).unwrap()
+        )
+        .unwrap());
+        assert!(check_combinations_gadget(|cs, point| {
+            EmulatedFpVar::new_witness(cs, || Ok(point)).unwrap()
+        })
+        .is_err());
+    }
+}
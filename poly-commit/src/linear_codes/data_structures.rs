@@ -1,13 +1,19 @@
-use super::utils::SprsMat;
-use crate::{utils::Matrix, PCCommitment, PCCommitmentState};
+use super::utils::{ColumnTree, SprsMat};
+use crate::{
+    security::SecurityEstimate,
+    utils::{deserialize_vec, deserialize_vec_with, Matrix},
+    PCCommitment, PCCommitmentState, PCPreparedCommitment,
+};
 use ark_crypto_primitives::{
     crh::CRHScheme,
     merkle_tree::{Config, LeafParam, Path, TwoToOneParam},
     sponge::Absorb,
 };
 use ark_ff::PrimeField;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{marker::PhantomData, rand::RngCore, vec::Vec};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::{io::Read, marker::PhantomData, rand::RngCore, vec::Vec};
 
 #[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
 #[derivative(Clone(bound = ""), Debug(bound = ""))]
@@ -31,6 +37,19 @@ pub struct LigeroPCParams<F: PrimeField, C: Config, H: CRHScheme> {
     pub(crate) col_hash_params: H::Parameters,
 }
 
+/// Parameters of Ligero PCS suggested by `LigeroPCParams::suggest`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LigeroSuggestion {
+    /// The security parameter to pass to `LigeroPCParams::new`.
+    pub sec_param: usize,
+    /// The inverse of the code rate to pass to `LigeroPCParams::new`.
+    pub rho_inv: usize,
+    /// The number of columns queried in each opening.
+    pub num_queries: usize,
+    /// The security estimate of the suggested parameters.
+    pub estimate: SecurityEstimate,
+}
+
 #[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
 #[derivative(Clone(bound = ""), Debug(bound = ""))]
 /// The public parameters for Brakedown PCS.
@@ -82,7 +101,18 @@ pub struct BrakedownPCParams<F: PrimeField, C: Config, H: CRHScheme> {
     pub(crate) col_hash_params: H::Parameters,
 }
 
-#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize, Absorb)]
+/// Parameters of Brakedown PCS suggested by `BrakedownPCParams::suggest`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BrakedownSuggestion {
+    /// The security parameter to pass to `BrakedownPCParams::with_sec_param`.
+    pub sec_param: usize,
+    /// The number of columns queried in each opening.
+    pub num_queries: usize,
+    /// The security estimate of the suggested parameters.
+    pub estimate: SecurityEstimate,
+}
+
+#[derive(Derivative, CanonicalSerialize, Absorb)]
 #[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
 pub(crate) struct Metadata {
     pub(crate) n_rows: usize,
@@ -90,6 +120,37 @@ pub(crate) struct Metadata {
     pub(crate) n_ext_cols: usize,
 }
 
+impl Valid for Metadata {
+    fn check(&self) -> Result<(), SerializationError> {
+        // The encoding of a non-empty row is at least as long as the row
+        if self.n_rows == 0 || self.n_cols == 0 || self.n_ext_cols < self.n_cols {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for Metadata {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let n_rows = usize::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let n_cols = usize::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let n_ext_cols = usize::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let result = Self {
+            n_rows,
+            n_cols,
+            n_ext_cols,
+        };
+        if let Validate::Yes = validate {
+            result.check()?;
+        }
+        Ok(result)
+    }
+}
+
 /// The commitment to a polynomial is a root of the merkle tree,
 /// where each node is a hash of the column of the encoded coefficient matrix U.
 #[derive(Derivative, CanonicalSerialize, CanonicalDeserialize, Absorb)]
@@ -110,21 +171,66 @@ impl<C: Config> PCCommitment for LinCodePCCommitment<C> {
     }
 }
 
+impl<C: Config> PCPreparedCommitment<Self> for LinCodePCCommitment<C> {
+    fn prepare(comm: &Self) -> Self {
+        comm.clone()
+    }
+}
+
+/// How much of the commitment is kept in a [`LinCodePCCommitmentState`].
+/// Both representations produce identical proofs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CommitmentStateMode {
+    /// Keep the encoded matrix and the column hashes. `open` rebuilds the
+    /// Merkle tree from the column hashes.
+    #[default]
+    Full,
+    /// Keep the Merkle tree but not the encoded matrix. `open` recomputes the
+    /// queried columns from the rows with
+    /// [`LinearEncode::encode_at`](super::LinearEncode::encode_at). This is
+    /// a fraction of the size of the full state, and is meant to be serialized
+    /// between commitment and opening.
+    Compact,
+}
+
+/// The state kept by the committer between `commit` and `open`.
 #[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
 #[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
-pub struct LinCodePCCommitmentState<F, H>
+pub struct LinCodePCCommitmentState<F, C, H>
 where
     F: PrimeField,
+    C: Config,
     H: CRHScheme,
 {
     pub(crate) mat: Matrix<F>,
-    pub(crate) ext_mat: Matrix<F>,
+    /// Only kept in `CommitmentStateMode::Full`.
+    pub(crate) ext_mat: Option<Matrix<F>>,
+    /// Only kept in `CommitmentStateMode::Full`.
     pub(crate) leaves: Vec<H::Output>,
+    /// Only kept in `CommitmentStateMode::Compact`.
+    pub(crate) tree: Option<ColumnTree<C>>,
 }
 
-impl<F, H> PCCommitmentState for LinCodePCCommitmentState<F, H>
+impl<F, C, H> LinCodePCCommitmentState<F, C, H>
 where
     F: PrimeField,
+    C: Config,
+    H: CRHScheme,
+{
+    /// The representation of this state.
+    pub fn mode(&self) -> CommitmentStateMode {
+        if self.tree.is_some() {
+            CommitmentStateMode::Compact
+        } else {
+            CommitmentStateMode::Full
+        }
+    }
+}
+
+impl<F, C, H> PCCommitmentState for LinCodePCCommitmentState<F, C, H>
+where
+    F: PrimeField,
+    C: Config,
     H: CRHScheme,
 {
     type Randomness = ();
@@ -143,7 +249,7 @@ where
 }
 
 /// Proof of an individual linear code well-formedness check or opening
-#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Derivative, CanonicalSerialize)]
 #[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
 pub(crate) struct LinCodePCProofSingle<F, C>
 where
@@ -159,8 +265,61 @@ where
     pub(crate) columns: Vec<Vec<F>>,
 }
 
+impl<F, C> Valid for LinCodePCProofSingle<F, C>
+where
+    F: PrimeField,
+    C: Config,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.paths.check()?;
+        self.v.check()?;
+        self.columns.check()?;
+        // Each queried column comes with its path, and all columns have the
+        // same number of rows
+        let n_rows = self.columns.first().map_or(0, Vec::len);
+        if self.paths.len() != self.columns.len()
+            || self.columns.iter().any(|column| column.len() != n_rows)
+        {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(())
+    }
+}
+
+impl<F, C> CanonicalDeserialize for LinCodePCProofSingle<F, C>
+where
+    F: PrimeField,
+    C: Config,
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let paths = deserialize_vec_with(&mut reader, compress, |r| {
+            let leaf_sibling_hash =
+                C::LeafDigest::deserialize_with_mode(&mut *r, compress, Validate::No)?;
+            let auth_path = deserialize_vec(&mut *r, compress)?;
+            let leaf_index = usize::deserialize_with_mode(r, compress, Validate::No)?;
+            Ok(Path {
+                leaf_sibling_hash,
+                auth_path,
+                leaf_index,
+            })
+        })?;
+        let v = deserialize_vec(&mut reader, compress)?;
+        let columns =
+            deserialize_vec_with(&mut reader, compress, |r| deserialize_vec(r, compress))?;
+        let result = Self { paths, v, columns };
+        if let Validate::Yes = validate {
+            result.check()?;
+        }
+        Ok(result)
+    }
+}
+
 /// The Proof type for linear code PCS, which amounts to an array of individual proofs
-#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Derivative, CanonicalSerialize)]
 #[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
 pub struct LinCodePCProof<F, C>
 where
@@ -171,5 +330,100 @@ where
     pub(crate) well_formedness: Option<Vec<F>>,
 }
 
-// Multiple poly at one point
-pub(crate) type LPCPArray<F, C> = Vec<LinCodePCProof<F, C>>;
+impl<F, C> Valid for LinCodePCProof<F, C>
+where
+    F: PrimeField,
+    C: Config,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.opening.check()?;
+        self.well_formedness.check()
+    }
+}
+
+impl<F, C> CanonicalDeserialize for LinCodePCProof<F, C>
+where
+    F: PrimeField,
+    C: Config,
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let opening =
+            LinCodePCProofSingle::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let well_formedness = if bool::deserialize_with_mode(&mut reader, compress, Validate::No)? {
+            Some(deserialize_vec(&mut reader, compress)?)
+        } else {
+            None
+        };
+        let result = Self {
+            opening,
+            well_formedness,
+        };
+        if let Validate::Yes = validate {
+            result.check()?;
+        }
+        Ok(result)
+    }
+}
+
+/// The proofs of the openings of a batch of polynomials at one point, one per
+/// polynomial.
+#[derive(Derivative, CanonicalSerialize)]
+#[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
+pub struct LPCPArray<F, C>(pub Vec<LinCodePCProof<F, C>>)
+where
+    F: PrimeField,
+    C: Config;
+
+impl<F, C> core::ops::Deref for LPCPArray<F, C>
+where
+    F: PrimeField,
+    C: Config,
+{
+    type Target = Vec<LinCodePCProof<F, C>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<F, C> core::ops::DerefMut for LPCPArray<F, C>
+where
+    F: PrimeField,
+    C: Config,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<F, C> Valid for LPCPArray<F, C>
+where
+    F: PrimeField,
+    C: Config,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.0.check()
+    }
+}
+
+impl<F, C> CanonicalDeserialize for LPCPArray<F, C>
+where
+    F: PrimeField,
+    C: Config,
+{
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let result = Self(deserialize_vec(reader, compress)?);
+        if let Validate::Yes = validate {
+            result.check()?;
+        }
+        Ok(result)
+    }
+}
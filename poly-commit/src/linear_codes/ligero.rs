@@ -1,8 +1,13 @@
-use super::{LigeroPCParams, LinCodeParametersInfo};
+use super::{
+    lin_code_cost, LigeroPCParams, LigeroSuggestion, LinCodeParametersInfo, FIELD_SIZE_ERROR,
+};
 
 use crate::{
-    linear_codes::utils::calculate_t, utils::ceil_div, PCCommitterKey, PCUniversalParams,
-    PCVerifierKey,
+    cost::{BatchShape, CostEstimate, CostModel},
+    linear_codes::utils::{calculate_t, lin_code_security},
+    security::{SecurityAssumptions, SecurityEstimate, SecurityEstimator},
+    utils::ceil_div,
+    Error, PCCommitterKey, PCPreparedVerifierKey, PCUniversalParams, PCVerifierKey,
 };
 
 use ark_crypto_primitives::{
@@ -10,7 +15,8 @@ use ark_crypto_primitives::{
     merkle_tree::{Config, LeafParam, TwoToOneParam},
 };
 use ark_ff::PrimeField;
-use ark_std::{log2, marker::PhantomData};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_std::{borrow::Borrow, log2, marker::PhantomData, string::ToString, vec::Vec};
 #[cfg(not(feature = "std"))]
 use num_traits::Float;
 
@@ -39,6 +45,135 @@ where
             col_hash_params,
         }
     }
+
+    /// Suggest the `sec_param` and `rho_inv` to pass to [`Self::new`] in order to
+    /// commit to polynomials with `poly_len` coefficients with at least
+    /// `target_bits` of security under `assumptions`.
+    ///
+    /// Among the rates `1/2, 1/4, 1/8, 1/16`, the suggestion minimises the number
+    /// of field elements in an opening proof, i.e. `t * n_rows + n_cols`.
+    pub fn suggest(
+        target_bits: f64,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<LigeroSuggestion, Error> {
+        const MAX_SEC_PARAM: usize = 512;
+
+        let mut best: Option<(usize, LigeroSuggestion)> = None;
+        for rho_inv in [2, 4, 8, 16] {
+            for sec_param in 1..=MAX_SEC_PARAM {
+                // `calculate_t` fails for every larger `sec_param` as well.
+                let Ok((estimate, num_queries, (n_rows, n_cols))) =
+                    Self::security(sec_param, rho_inv, poly_len, assumptions)
+                else {
+                    break;
+                };
+                if estimate.bits() < target_bits {
+                    continue;
+                }
+
+                let proof_len = num_queries * n_rows + n_cols;
+                if !matches!(best, Some((len, _)) if len <= proof_len) {
+                    let suggestion = LigeroSuggestion {
+                        sec_param,
+                        rho_inv,
+                        num_queries,
+                        estimate,
+                    };
+                    best = Some((proof_len, suggestion));
+                }
+                break;
+            }
+        }
+
+        best.map(|(_, suggestion)| suggestion).ok_or_else(|| {
+            Error::InvalidParameters(
+                "The target security cannot be reached over this field".to_string(),
+            )
+        })
+    }
+
+    /// The matrix dimensions used for polynomials with `poly_len` coefficients.
+    fn dimensions(
+        sec_param: usize,
+        distance: (usize, usize),
+        poly_len: usize,
+    ) -> Result<(usize, usize), Error> {
+        let t = calculate_t::<F>(sec_param, distance, poly_len)?;
+        let n = 1 << log2((ceil_div(2 * poly_len, t) as f64).sqrt().ceil() as usize);
+        let m = ceil_div(poly_len, n);
+        Ok((n, m))
+    }
+
+    /// The length of the Reed-Solomon encoding of a row of length `n_cols`.
+    fn codeword_len(n_cols: usize, rho_inv: usize) -> Result<usize, Error> {
+        GeneralEvaluationDomain::<F>::new(n_cols * rho_inv)
+            .map(|domain| domain.size())
+            .ok_or_else(|| Error::InvalidParameters(FIELD_SIZE_ERROR.to_string()))
+    }
+
+    /// The security estimate, the number of queries, and the matrix dimensions
+    /// for the given parameters.
+    fn security(
+        sec_param: usize,
+        rho_inv: usize,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<(SecurityEstimate, usize, (usize, usize)), Error> {
+        if rho_inv < 2 {
+            return Err(Error::InvalidParameters(
+                "rho_inv must be at least 2".to_string(),
+            ));
+        }
+        let distance = (rho_inv - 1, rho_inv);
+        let (n_rows, n_cols) = Self::dimensions(sec_param, distance, poly_len)?;
+        let codeword_len = Self::codeword_len(n_cols, rho_inv)?;
+        let t = calculate_t::<F>(sec_param, distance, codeword_len)?;
+
+        let estimate = lin_code_security::<F>(
+            t,
+            n_rows,
+            codeword_len,
+            n_cols as f64 / codeword_len as f64,
+            distance,
+            true,
+            f64::NEG_INFINITY,
+            assumptions,
+        )?;
+        Ok((estimate, t, (n_rows, n_cols)))
+    }
+}
+
+impl<F, C, H> CostModel for LigeroPCParams<F, C, H>
+where
+    F: PrimeField,
+    C: Config,
+    C::Leaf: Default + Clone + Send + AsRef<C::Leaf>,
+    H: CRHScheme,
+    Vec<F>: Borrow<H::Input>,
+    H::Output: Into<C::Leaf>,
+{
+    fn estimate_cost(&self, poly_len: usize, batch: &BatchShape) -> Result<CostEstimate, Error> {
+        let (n_rows, n_cols) = Self::dimensions(self.sec_param, self.distance(), poly_len)?;
+        let n_ext_cols = Self::codeword_len(n_cols, self.rho_inv)?;
+        lin_code_cost::<F, C, H>(self, (n_rows, n_cols), n_ext_cols, batch)
+    }
+}
+
+impl<F, C, H> SecurityEstimator for LigeroPCParams<F, C, H>
+where
+    F: PrimeField,
+    C: Config,
+    H: CRHScheme,
+{
+    fn estimate_security(
+        &self,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<SecurityEstimate, Error> {
+        Self::security(self.sec_param, self.rho_inv, poly_len, assumptions)
+            .map(|(estimate, _, _)| estimate)
+    }
 }
 
 impl<F, C, H> PCUniversalParams for LigeroPCParams<F, C, H>
@@ -96,6 +231,17 @@ where
     }
 }
 
+impl<F, C, H> PCPreparedVerifierKey<Self> for LigeroPCParams<F, C, H>
+where
+    F: PrimeField,
+    C: Config,
+    H: CRHScheme,
+{
+    fn prepare(vk: &Self) -> Self {
+        vk.clone()
+    }
+}
+
 impl<F, C, H> LinCodeParametersInfo<C, H> for LigeroPCParams<F, C, H>
 where
     F: PrimeField,
@@ -127,10 +273,7 @@ where
             poly_len,
             "n cannot be converted to f64: aborting"
         );
-        let t = calculate_t::<F>(self.sec_param(), self.distance(), poly_len).unwrap();
-        let n = 1 << log2((ceil_div(2 * poly_len, t) as f64).sqrt().ceil() as usize);
-        let m = ceil_div(poly_len, n);
-        (n, m)
+        Self::dimensions(self.sec_param(), self.distance(), poly_len).unwrap()
     }
 
     fn leaf_hash_param(&self) -> &<<C as Config>::LeafHash as CRHScheme>::Parameters {
@@ -1,21 +1,29 @@
 use crate::utils::{inner_product, Matrix};
 use crate::{
-    to_bytes, Error, LabeledCommitment, LabeledPolynomial, PCCommitterKey, PCUniversalParams,
-    PCVerifierKey, PolynomialCommitment,
+    cost::{
+        compressed_size, repeat, vec_bytes, BatchShape, CostEstimate, ProverCost, VerifierCost,
+        OPTION_BYTES,
+    },
+    security::SecurityEstimator,
+    tensor::{Query, Tensor, TensorOpenings},
+    Error, LabeledCommitment, LabeledPolynomial, PCCommitterKey, PCUniversalParams, PCVerifierKey,
+    PolynomialCommitment, Transcript, VerificationFailure,
 };
 
 use ark_crypto_primitives::crh::{CRHScheme, TwoToOneCRHScheme};
-use ark_crypto_primitives::merkle_tree::MerkleTree;
+use ark_crypto_primitives::merkle_tree::{MerkleTree, Path};
 use ark_crypto_primitives::{
     merkle_tree::Config,
-    sponge::{Absorb, CryptographicSponge},
+    sponge::{Absorb, CryptographicSponge, FieldElementSize},
 };
 use ark_ff::PrimeField;
 use ark_poly::Polynomial;
 use ark_std::borrow::Borrow;
+use ark_std::format;
+use ark_std::log2;
 use ark_std::marker::PhantomData;
 use ark_std::rand::RngCore;
-use ark_std::string::ToString;
+use ark_std::string::{String, ToString};
 use ark_std::vec::Vec;
 
 #[cfg(feature = "parallel")]
@@ -35,19 +43,33 @@ mod brakedown;
 mod data_structures;
 mod ligero;
 
-pub use data_structures::BrakedownPCParams;
 use data_structures::*;
+pub use data_structures::{
+    BrakedownPCParams, BrakedownSuggestion, CommitmentStateMode, LigeroPCParams, LigeroSuggestion,
+    LinCodePCCommitmentState,
+};
+
+pub use data_structures::{LPCPArray, LinCodePCProof};
+
+mod poseidon;
+pub use poseidon::*;
 
-pub use data_structures::LinCodePCProof;
+mod streaming;
+pub use streaming::*;
 
-use utils::{calculate_t, get_indices_from_sponge};
+#[cfg(feature = "r1cs")]
+mod constraints;
+#[cfg(feature = "r1cs")]
+pub use constraints::*;
+
+use utils::{calculate_t, get_indices_from_sponge, select_entries, ColumnTree};
 
 const FIELD_SIZE_ERROR: &str = "This field is not suitable for the proposed parameters";
 
 /// For linear code PC schemes, the universal parameters, committer key
 /// and verifier key are all the same. This trait abstracts the common
 /// information contained in these.
-pub trait LinCodeParametersInfo<C, H>
+pub trait LinCodeParametersInfo<C, H>: SecurityEstimator
 where
     C: Config,
     H: CRHScheme,
@@ -96,6 +118,10 @@ where
         + LinCodeParametersInfo<C, H>
         + Sync;
 
+    /// The name of the scheme, which binds the transcript of every opening
+    /// to it, together with its parameters.
+    const PROTOCOL_NAME: &'static [u8];
+
     /// Does a default setup for the PCS.
     fn setup<R: RngCore>(
         max_degree: usize,
@@ -110,6 +136,22 @@ where
     /// of a polynomial of degree m - 1.
     fn encode(msg: &[F], param: &Self::LinCodePCParams) -> Result<Vec<F>, Error>;
 
+    /// Compute the entries of the encoding of `msg` at the positions in
+    /// `indices`, which is how the queried columns of a commitment are opened
+    /// from a compact state.
+    ///
+    /// The default implementation encodes the whole message and selects the
+    /// entries; codes which can compute single entries for less should do so.
+    fn encode_at(
+        msg: &[F],
+        indices: &[usize],
+        param: &Self::LinCodePCParams,
+    ) -> Result<Vec<F>, Error> {
+        let codeword = Self::encode(msg, param)?;
+        count!(ENCODING, codeword.len());
+        select_entries(&codeword, indices)
+    }
+
     /// Represent the polynomial as either coefficients,
     /// in the univariate case, or evaluations over
     /// the Boolean hypercube, in the multilinear case.
@@ -150,6 +192,13 @@ where
     /// p(z) = b^T.M.a
     /// returns the evaluation of p at z.
     fn tensor(z: &P::Point, n: usize, m: usize) -> (Vec<F>, Vec<F>);
+
+    /// Check that a coefficient matrix with `n_rows` rows and `n_cols`
+    /// columns can be tensored with `point`. The verifier reads the dimensions
+    /// from the commitment, which it cannot trust.
+    fn check_dimensions(_point: &P::Point, _n_rows: usize, _n_cols: usize) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 /// Any linear-code-based commitment scheme.
@@ -164,7 +213,7 @@ where
     _phantom: PhantomData<(L, F, P, C, H)>,
 }
 
-impl<L, F, P, C, H> PolynomialCommitment<F, P> for LinearCodePCS<L, F, P, C, H>
+impl<L, F, P, C, H> LinearCodePCS<L, F, P, C, H>
 where
     L: LinearEncode<F, C, P, H>,
     F: PrimeField + Absorb,
@@ -175,81 +224,31 @@ where
     C::Leaf: Sized + Clone + Default + Send + AsRef<C::Leaf>,
     H: CRHScheme + 'static,
 {
-    type UniversalParams = L::LinCodePCParams;
-
-    type CommitterKey = L::LinCodePCParams;
-
-    type VerifierKey = L::LinCodePCParams;
-
-    type Commitment = LinCodePCCommitment<C>;
-
-    type CommitmentState = LinCodePCCommitmentState<F, H>;
-
-    type Proof = LPCPArray<F, C>;
-
-    type BatchProof = Vec<Self::Proof>;
-
-    type Error = Error;
-
-    /// This is only a default setup with reasonable parameters.
-    /// To create your own public parameters (from which vk/ck can be derived by `trim`),
-    /// see the documentation for `BrakedownPCUniversalParams`.
-    fn setup<R: RngCore>(
-        max_degree: usize,
-        num_vars: Option<usize>,
-        rng: &mut R,
-    ) -> Result<Self::UniversalParams, Self::Error> {
-        let leaf_hash_param = <C::LeafHash as CRHScheme>::setup(rng).unwrap();
-        let two_to_one_hash_param = <C::TwoToOneHash as TwoToOneCRHScheme>::setup(rng)
-            .unwrap()
-            .clone();
-        let col_hash_params = <H as CRHScheme>::setup(rng).unwrap();
-        let pp = L::setup::<R>(
-            max_degree,
-            num_vars,
-            rng,
-            leaf_hash_param,
-            two_to_one_hash_param,
-            col_hash_params,
-        );
-        let real_max_degree = <Self::UniversalParams as PCUniversalParams>::max_degree(&pp);
-        if max_degree > real_max_degree || real_max_degree == 0 {
-            return Err(Error::InvalidParameters(FIELD_SIZE_ERROR.to_string()));
-        }
-        Ok(pp)
-    }
-
-    fn trim(
-        pp: &Self::UniversalParams,
-        _supported_degree: usize,
-        _supported_hiding_bound: usize,
-        _enforced_degree_bounds: Option<&[usize]>,
-    ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
-        if <Self::UniversalParams as PCUniversalParams>::max_degree(pp) == 0 {
-            return Err(Error::InvalidParameters(FIELD_SIZE_ERROR.to_string()));
-        }
-        Ok((pp.clone(), pp.clone()))
-    }
-
-    fn commit<'a>(
-        ck: &Self::CommitterKey,
+    /// Same as `commit`, but the returned states are kept in the given `mode`.
+    /// The commitments, and the proofs computed from either kind of state,
+    /// are identical.
+    #[allow(clippy::type_complexity)]
+    pub fn commit_with_state_mode<'a>(
+        ck: &L::LinCodePCParams,
         polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
-        _rng: Option<&mut dyn RngCore>,
+        mode: CommitmentStateMode,
     ) -> Result<
         (
-            Vec<LabeledCommitment<Self::Commitment>>,
-            Vec<Self::CommitmentState>,
+            Vec<LabeledCommitment<LinCodePCCommitment<C>>>,
+            Vec<LinCodePCCommitmentState<F, C, H>>,
         ),
-        Self::Error,
+        Error,
     >
     where
         P: 'a,
     {
+        phase_span!("LinearCodePCS::commit");
         let mut commitments = Vec::new();
         let mut states = Vec::new();
 
         for labeled_polynomial in polynomials {
             let polynomial = labeled_polynomial.polynomial();
+            phase_span!("commit_polynomial", label = %labeled_polynomial.label());
 
             // 1. Arrange the coefficients of the polynomial into a matrix,
             // and apply encoding to get `ext_mat`.
@@ -257,31 +256,54 @@ where
             let n_rows = mat.n;
             let n_cols = mat.m;
             let n_ext_cols = ext_mat.m;
+            // The rows are encoded in parallel, and counted here, in the span
+            // of the commitment.
+            #[cfg(feature = "tracing")]
+            for _ in 0..n_rows {
+                count!(ENCODING, n_ext_cols);
+            }
 
             // 2. Create the Merkle tree from the hashes of each column.
             let ext_mat_cols = ext_mat.cols();
             let leaves: Vec<H::Output> = cfg_into_iter!(ext_mat_cols)
-                .map(|col| {
-                    H::evaluate(ck.col_hash_params(), col)
-                        .map_err(|_| Error::HashingError)
-                        .unwrap()
-                })
-                .collect();
-            let state = Self::CommitmentState {
-                mat,
-                ext_mat,
-                leaves,
+                .map(|col| H::evaluate(ck.col_hash_params(), col).map_err(|_| Error::HashingError))
+                .collect::<Result<_, _>>()?;
+            count!(HASH, leaves.len());
+            let mut col_hashes: Vec<C::Leaf> = leaves.iter().cloned().map(|h| h.into()).collect();
+
+            // 3. Obtain the MT root, and keep either the encoded matrix and the
+            // leaves, or the tree itself.
+            let (root, state) = match mode {
+                CommitmentStateMode::Full => {
+                    let col_tree = create_merkle_tree::<C>(
+                        &mut col_hashes,
+                        ck.leaf_hash_param(),
+                        ck.two_to_one_hash_param(),
+                    )?;
+                    let state = LinCodePCCommitmentState {
+                        mat,
+                        ext_mat: Some(ext_mat),
+                        leaves,
+                        tree: None,
+                    };
+                    (col_tree.root(), state)
+                }
+                CommitmentStateMode::Compact => {
+                    let col_tree = ColumnTree::new(
+                        col_hashes,
+                        ck.leaf_hash_param(),
+                        ck.two_to_one_hash_param(),
+                    )?;
+                    let root = col_tree.root().ok_or(Error::HashingError)?;
+                    let state = LinCodePCCommitmentState {
+                        mat,
+                        ext_mat: None,
+                        leaves: Vec::new(),
+                        tree: Some(col_tree),
+                    };
+                    (root, state)
+                }
             };
-            let mut leaves: Vec<C::Leaf> =
-                state.leaves.clone().into_iter().map(|h| h.into()).collect();
-            let col_tree = create_merkle_tree::<C>(
-                &mut leaves,
-                ck.leaf_hash_param(),
-                ck.two_to_one_hash_param(),
-            )?;
-
-            // 3. Obtain the MT root
-            let root = col_tree.root();
 
             // 4. The commitment is just the root, but since each commitment could be to a differently-sized polynomial, we also add some metadata.
             let commitment = LinCodePCCommitment {
@@ -303,21 +325,46 @@ where
         Ok((commitments, states))
     }
 
-    fn open<'a>(
-        ck: &Self::CommitterKey,
-        _labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
-        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
-        point: &'a P::Point,
+    /// Outputs the vectors `(a, b)` which right- and left-multiply the
+    /// matrix of a commitment with `n_cols` columns and `n_rows` rows to
+    /// answer `query`.
+    fn query_tensor(
+        query: &Query<'_, F, P::Point>,
+        n_cols: usize,
+        n_rows: usize,
+    ) -> Result<(Vec<F>, Vec<F>), Error> {
+        match query {
+            Query::Point(point) => {
+                L::check_dimensions(point, n_rows, n_cols)?;
+                Ok(L::tensor(point, n_cols, n_rows))
+            }
+            Query::Tensor(tensor) => {
+                tensor.check_dimensions(n_cols, n_rows)?;
+                Ok((tensor.a.clone(), tensor.b.clone()))
+            }
+        }
+    }
+
+    fn absorb_query(sponge: &mut impl CryptographicSponge, query: &Query<'_, F, P::Point>) {
+        match query {
+            Query::Point(point) => {
+                let point_vec = L::point_to_vec((*point).clone());
+                sponge.absorb_labelled(b"point", &point_vec);
+            }
+            Query::Tensor(tensor) => tensor.absorb(sponge),
+        }
+    }
+
+    /// Opens the commitments at `query`, as in `PolynomialCommitment::open`.
+    fn open_query<'a>(
+        ck: &L::LinCodePCParams,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<LinCodePCCommitment<C>>>,
+        query: Query<'_, F, P::Point>,
         sponge: &mut impl CryptographicSponge,
-        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
-        _rng: Option<&mut dyn RngCore>,
-    ) -> Result<Self::Proof, Self::Error>
-    where
-        P: 'a,
-        Self::CommitmentState: 'a,
-        Self::Commitment: 'a,
-    {
+        states: impl IntoIterator<Item = &'a LinCodePCCommitmentState<F, C, H>>,
+    ) -> Result<LPCPArray<F, C>, Error> {
         let mut proof_array = LPCPArray::default();
+        sponge.bind_context(L::PROTOCOL_NAME, ck)?;
 
         for (labeled_commitment, state) in commitments.into_iter().zip(states) {
             let commitment = labeled_commitment.commitment();
@@ -327,50 +374,102 @@ where
             // 1. Arrange the coefficients of the polynomial into a matrix,
             // and apply encoding to get `ext_mat`.
             // 2. Create the Merkle tree from the hashes of each column.
-            let Self::CommitmentState {
-                mat,
-                ext_mat,
-                leaves: col_hashes,
-            } = state;
-            let mut col_hashes: Vec<C::Leaf> =
-                col_hashes.clone().into_iter().map(|h| h.into()).collect();
-
-            let col_tree = create_merkle_tree::<C>(
-                &mut col_hashes,
-                ck.leaf_hash_param(),
-                ck.two_to_one_hash_param(),
-            )?;
+            // Both are kept in a full state, whereas a compact state keeps the
+            // tree and only recomputes the queried columns.
+            let mat = &state.mat;
+            let col_tree = match (&state.ext_mat, &state.tree) {
+                (Some(ext_mat), None) => {
+                    let mut col_hashes: Vec<C::Leaf> =
+                        state.leaves.iter().cloned().map(|h| h.into()).collect();
+                    let tree = create_merkle_tree::<C>(
+                        &mut col_hashes,
+                        ck.leaf_hash_param(),
+                        ck.two_to_one_hash_param(),
+                    )?;
+                    OpeningTree::Rebuilt { ext_mat, tree }
+                }
+                (None, Some(tree)) if tree.root().as_ref() == Some(&commitment.root) => {
+                    OpeningTree::Stored(tree)
+                }
+                _ => {
+                    return Err(Error::IncorrectInputLength(
+                        "The commitment state does not match the commitment".to_string(),
+                    ))
+                }
+            };
 
             // 3. Generate vector `b` to left-multiply the matrix.
-            let (_, b) = L::tensor(point, n_cols, n_rows);
+            let (_, b) = Self::query_tensor(&query, n_cols, n_rows)?;
 
-            sponge.absorb(&to_bytes!(&commitment.root).map_err(|_| Error::TranscriptError)?);
+            sponge.absorb_serialized(b"root", &commitment.root)?;
 
             // If we are checking well-formedness, we need to compute the well-formedness proof (which is just r.M) and append it to the transcript.
             let well_formedness = if ck.check_well_formedness() {
-                let r = sponge.squeeze_field_elements::<F>(n_rows);
+                let r = squeeze_well_formedness_challenge::<F>(sponge, n_rows);
                 let v = mat.row_mul(&r);
 
-                sponge.absorb(&v);
+                sponge.absorb_labelled(b"well-formedness", &v);
                 Some(v)
             } else {
                 None
             };
 
-            let point_vec = L::point_to_vec(point.clone());
-            sponge.absorb(&point_vec);
+            Self::absorb_query(sponge, &query);
 
-            proof_array.push(LinCodePCProof {
-                // Compute the opening proof and append b.M to the transcript.
-                opening: generate_proof(
+            // Compute the opening proof and append b.M to the transcript.
+            let opening = match &col_tree {
+                OpeningTree::Rebuilt { ext_mat, tree } => generate_proof(
+                    ck.sec_param(),
+                    ck.distance(),
+                    &b,
+                    mat,
+                    ext_mat.m,
+                    sponge,
+                    |indices| {
+                        let ext_mat_cols = ext_mat.cols();
+                        let mut queried_columns = Vec::with_capacity(indices.len());
+                        let mut paths = Vec::with_capacity(indices.len());
+                        for &i in indices {
+                            queried_columns.push(ext_mat_cols[i].clone());
+                            paths.push(tree.generate_proof(i).map_err(|_| Error::TranscriptError)?);
+                        }
+                        Ok((queried_columns, paths))
+                    },
+                )?,
+                OpeningTree::Stored(tree) => generate_proof(
                     ck.sec_param(),
                     ck.distance(),
                     &b,
-                    &mat,
-                    &ext_mat,
-                    &col_tree,
+                    mat,
+                    commitment.metadata.n_ext_cols,
                     sponge,
+                    |indices| {
+                        // Only the entries of the distinct queried columns are
+                        // computed in each row.
+                        let mut distinct = indices.to_vec();
+                        distinct.sort_unstable();
+                        distinct.dedup();
+                        let rows = mat.rows();
+                        let queried_rows = cfg_iter!(rows)
+                            .map(|row| L::encode_at(row, &distinct, ck))
+                            .collect::<Result<Vec<Vec<F>>, Error>>()?;
+                        let queried_columns = indices
+                            .iter()
+                            .map(|j| {
+                                let k = distinct.binary_search(j).unwrap();
+                                queried_rows.iter().map(|row| row[k]).collect()
+                            })
+                            .collect();
+                        let paths = indices
+                            .iter()
+                            .map(|&j| tree.generate_proof(j))
+                            .collect::<Result<_, _>>()?;
+                        Ok((queried_columns, paths))
+                    },
                 )?,
+            };
+            proof_array.push(LinCodePCProof {
+                opening,
                 well_formedness,
             });
         }
@@ -378,25 +477,45 @@ where
         Ok(proof_array)
     }
 
-    fn check<'a>(
-        vk: &Self::VerifierKey,
-        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
-        point: &'a P::Point,
+    /// Verifies the opening of the commitments at `query`, as in
+    /// `PolynomialCommitment::check`.
+    fn check_query<'a>(
+        vk: &L::LinCodePCParams,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<LinCodePCCommitment<C>>>,
+        query: Query<'_, F, P::Point>,
         values: impl IntoIterator<Item = F>,
-        proof_array: &Self::Proof,
+        proof_array: &LPCPArray<F, C>,
         sponge: &mut impl CryptographicSponge,
-        _rng: Option<&mut dyn RngCore>,
-    ) -> Result<bool, Self::Error>
-    where
-        Self::Commitment: 'a,
-    {
+    ) -> Result<(), Error> {
         let leaf_hash_param: &<<C as Config>::LeafHash as CRHScheme>::Parameters =
             vk.leaf_hash_param();
         let two_to_one_hash_param: &<<C as Config>::TwoToOneHash as TwoToOneCRHScheme>::Parameters =
             vk.two_to_one_hash_param();
+        sponge.bind_context(L::PROTOCOL_NAME, vk)?;
+
+        let commitments: Vec<_> = commitments.into_iter().collect();
+        let values: Vec<F> = values.into_iter().collect();
+        if values.len() != commitments.len() {
+            return Err(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!(
+                    "expected {} values, one per commitment, found {}",
+                    commitments.len(),
+                    values.len()
+                ),
+            }
+            .into());
+        }
 
+        let num_commitments = commitments.len();
         for (i, (labeled_commitment, value)) in commitments.into_iter().zip(values).enumerate() {
-            let proof = &proof_array[i];
+            let label = labeled_commitment.label();
+            let proof = proof_array
+                .get(i)
+                .ok_or_else(|| VerificationFailure::MalformedProof {
+                    label: Some(label.clone()),
+                    reason: "missing proof".to_string(),
+                })?;
             let commitment = labeled_commitment.commitment();
             let n_rows = commitment.metadata.n_rows;
             let n_cols = commitment.metadata.n_cols;
@@ -404,27 +523,45 @@ where
             let root = &commitment.root;
             let t = calculate_t::<F>(vk.sec_param(), vk.distance(), n_ext_cols)?;
 
-            sponge.absorb(&to_bytes!(&commitment.root).map_err(|_| Error::TranscriptError)?);
+            // The proof and the metadata of the commitment are untrusted: check
+            // their shapes before indexing into them.
+            check_proof_shape(
+                label,
+                &commitment.metadata,
+                t,
+                proof,
+                vk.check_well_formedness(),
+            )?;
 
-            let out = if vk.check_well_formedness() {
-                if proof.well_formedness.is_none() {
-                    return Err(Error::InvalidCommitment);
-                }
-                let tmp = &proof.well_formedness.as_ref();
-                let v = tmp.unwrap();
-                let r = sponge.squeeze_field_elements::<F>(n_rows);
+            // Compute `a`, `b` to right- and left- multiply with the matrix `M`.
+            let (a, b) = Self::query_tensor(&query, n_cols, n_rows)?;
+
+            // Compute the encoding w = E(v), which must fill the columns of the
+            // encoded matrix.
+            let w = L::encode(&proof.opening.v, vk)?;
+            count!(ENCODING, w.len());
+            if w.len() != n_ext_cols {
+                return Err(Error::IncorrectCommitmentSize {
+                    encountered: n_ext_cols,
+                    expected: w.len(),
+                });
+            }
+
+            sponge.absorb_serialized(b"root", &commitment.root)?;
+
+            let out = if let Some(v) = proof.well_formedness.as_ref() {
+                let r = squeeze_well_formedness_challenge::<F>(sponge, n_rows);
                 // Upon sending `v` to the Verifier, add it to the sponge. The claim is that v = r.M.
-                sponge.absorb(&v);
+                sponge.absorb_labelled(b"well-formedness", v);
 
                 (Some(v), Some(r))
             } else {
                 (None, None)
             };
 
-            // 1. Seed the transcript with the point and the recieved vector
-            let point_vec = L::point_to_vec(point.clone());
-            sponge.absorb(&point_vec);
-            sponge.absorb(&proof.opening.v);
+            // 1. Seed the transcript with the query and the recieved vector
+            Self::absorb_query(sponge, &query);
+            sponge.absorb_labelled(b"evaluation vector", &proof.opening.v);
 
             // 2. Ask random oracle for the `t` indices where the checks happen.
             let indices = get_indices_from_sponge(n_ext_cols, t, sponge)?;
@@ -438,42 +575,45 @@ where
                     Err(_) => return Err(Error::HashingError),
                 }
             }
+            count!(HASH, col_hashes.len());
 
             // 4. Verify the paths for each of the leaf hashes - this is only run once,
             // even if we have a well-formedness check (i.e., we save sending and checking the columns).
             // See "Concrete optimizations to the commitment scheme", p.12 of [Brakedown](https://eprint.iacr.org/2021/1043.pdf).
             for (j, (leaf, q_j)) in col_hashes.iter().zip(indices.iter()).enumerate() {
                 let path = &proof.opening.paths[j];
-                if path.leaf_index != *q_j {
-                    return Err(Error::InvalidCommitment);
-                }
-
-                if !path
-                    .verify(leaf_hash_param, two_to_one_hash_param, root, leaf.clone())
-                    .map_err(|_| Error::HashingError)?
+                // The leaf hash, then one compression per level of the tree.
+                count!(HASH, 2 + path.auth_path.len());
+                if path.leaf_index != *q_j
+                    || !path
+                        .verify(leaf_hash_param, two_to_one_hash_param, root, leaf.clone())
+                        .map_err(|_| Error::HashingError)?
                 {
-                    return Ok(false);
+                    return Err(VerificationFailure::MerklePath {
+                        label: label.clone(),
+                        column: *q_j,
+                    }
+                    .into());
                 }
             }
 
-            // 5. Compute the encoding w = E(v).
-            let w = L::encode(&proof.opening.v, vk)?;
-
-            // 6. Compute `a`, `b` to right- and left- multiply with the matrix `M`.
-            let (a, b) = L::tensor(point, n_cols, n_rows);
-
-            // 7. Probabilistic checks that whatever the prover sent,
+            // 5. Probabilistic checks that whatever the prover sent,
             // matches with what the verifier computed for himself.
             // Note: we sacrifice some code repetition in order not to repeat execution.
+            let column_failure = |column: usize| VerificationFailure::ColumnCheck {
+                label: label.clone(),
+                column,
+            };
             if let (Some(well_formedness), Some(r)) = out {
                 let w_well_formedness = L::encode(well_formedness, vk)?;
+                count!(ENCODING, w_well_formedness.len());
                 for (transcript_index, matrix_index) in indices.iter().enumerate() {
                     if inner_product(&r, &proof.opening.columns[transcript_index])
                         != w_well_formedness[*matrix_index]
                         || inner_product(&b, &proof.opening.columns[transcript_index])
                             != w[*matrix_index]
                     {
-                        return Err(Error::InvalidCommitment);
+                        return Err(column_failure(*matrix_index).into());
                     }
                 }
             } else {
@@ -481,21 +621,292 @@ where
                     if inner_product(&b, &proof.opening.columns[transcript_index])
                         != w[*matrix_index]
                     {
-                        return Err(Error::InvalidCommitment);
+                        return Err(column_failure(*matrix_index).into());
                     }
                 }
             }
 
             if inner_product(&proof.opening.v, &a) != value {
-                eprintln!("Function check: claimed value in position {i} does not match the evaluation of the committed polynomial in the same position");
-                return Ok(false);
+                return Err(VerificationFailure::EvaluationMismatch {
+                    label: label.clone(),
+                    point: query.describe(),
+                }
+                .into());
+            }
+        }
+
+        if proof_array.len() != num_commitments {
+            return Err(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!(
+                    "expected {} proofs, one per commitment, found {}",
+                    num_commitments,
+                    proof_array.len()
+                ),
             }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+impl<L, F, P, C, H> PolynomialCommitment<F, P> for LinearCodePCS<L, F, P, C, H>
+where
+    L: LinearEncode<F, C, P, H>,
+    F: PrimeField + Absorb,
+    P: Polynomial<F>,
+    C: Config + 'static,
+    Vec<F>: Borrow<<H as CRHScheme>::Input>,
+    H::Output: Into<C::Leaf> + Send,
+    C::Leaf: Sized + Clone + Default + Send + AsRef<C::Leaf>,
+    H: CRHScheme + 'static,
+{
+    type UniversalParams = L::LinCodePCParams;
+
+    type CommitterKey = L::LinCodePCParams;
+
+    type VerifierKey = L::LinCodePCParams;
+
+    type Commitment = LinCodePCCommitment<C>;
+
+    type CommitmentState = LinCodePCCommitmentState<F, C, H>;
+
+    type Proof = LPCPArray<F, C>;
+
+    type BatchProof = Vec<Self::Proof>;
+
+    type Error = Error;
+
+    /// This is only a default setup with reasonable parameters.
+    /// To create your own public parameters (from which vk/ck can be derived by `trim`),
+    /// see the documentation for `BrakedownPCUniversalParams`.
+    fn setup<R: RngCore>(
+        max_degree: usize,
+        num_vars: Option<usize>,
+        rng: &mut R,
+    ) -> Result<Self::UniversalParams, Self::Error> {
+        phase_span!("LinearCodePCS::setup", max_degree, num_vars = ?num_vars);
+        let leaf_hash_param = <C::LeafHash as CRHScheme>::setup(rng).unwrap();
+        let two_to_one_hash_param = <C::TwoToOneHash as TwoToOneCRHScheme>::setup(rng)
+            .unwrap()
+            .clone();
+        let col_hash_params = <H as CRHScheme>::setup(rng).unwrap();
+        let pp = L::setup::<R>(
+            max_degree,
+            num_vars,
+            rng,
+            leaf_hash_param,
+            two_to_one_hash_param,
+            col_hash_params,
+        );
+        let real_max_degree = <Self::UniversalParams as PCUniversalParams>::max_degree(&pp);
+        if max_degree > real_max_degree || real_max_degree == 0 {
+            return Err(Error::InvalidParameters(FIELD_SIZE_ERROR.to_string()));
+        }
+        Ok(pp)
+    }
+
+    fn trim(
+        pp: &Self::UniversalParams,
+        _supported_degree: usize,
+        _supported_hiding_bound: usize,
+        _enforced_degree_bounds: Option<&[usize]>,
+    ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
+        phase_span!("LinearCodePCS::trim");
+        if <Self::UniversalParams as PCUniversalParams>::max_degree(pp) == 0 {
+            return Err(Error::InvalidParameters(FIELD_SIZE_ERROR.to_string()));
         }
+        Ok((pp.clone(), pp.clone()))
+    }
 
-        Ok(true)
+    fn commit<'a>(
+        ck: &Self::CommitterKey,
+        polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<Self::Commitment>>,
+            Vec<Self::CommitmentState>,
+        ),
+        Self::Error,
+    >
+    where
+        P: 'a,
+    {
+        Self::commit_with_state_mode(ck, polynomials, CommitmentStateMode::Full)
+    }
+
+    fn open<'a>(
+        ck: &Self::CommitterKey,
+        _labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Self::Error>
+    where
+        P: 'a,
+        Self::CommitmentState: 'a,
+        Self::Commitment: 'a,
+    {
+        phase_span!("LinearCodePCS::open");
+        Self::open_query(ck, commitments, Query::Point(point), sponge, states)
+    }
+
+    fn check<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        values: impl IntoIterator<Item = F>,
+        proof_array: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a,
+    {
+        phase_span!("LinearCodePCS::check");
+        Self::check_query(
+            vk,
+            commitments,
+            Query::Point(point),
+            values,
+            proof_array,
+            sponge,
+        )
     }
 }
 
+impl<L, F, P, C, H> TensorOpenings<F, P> for LinearCodePCS<L, F, P, C, H>
+where
+    L: LinearEncode<F, C, P, H>,
+    F: PrimeField + Absorb,
+    P: Polynomial<F>,
+    C: Config + 'static,
+    Vec<F>: Borrow<<H as CRHScheme>::Input>,
+    H::Output: Into<C::Leaf> + Send,
+    C::Leaf: Sized + Clone + Default + Send + AsRef<C::Leaf>,
+    H: CRHScheme + 'static,
+{
+    /// The tensors have one entry per column and row of the matrix of the
+    /// commitment.
+    fn tensor_dimensions(commitment: &Self::Commitment) -> (usize, usize) {
+        (commitment.metadata.n_cols, commitment.metadata.n_rows)
+    }
+
+    fn open_tensor<'a>(
+        ck: &Self::CommitterKey,
+        _labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        tensor: &Tensor<F>,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Self::Error>
+    where
+        P: 'a,
+        Self::CommitmentState: 'a,
+        Self::Commitment: 'a,
+    {
+        phase_span!("LinearCodePCS::open_tensor");
+        Self::open_query(ck, commitments, Query::Tensor(tensor), sponge, states)
+    }
+
+    fn check_tensor<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        tensor: &Tensor<F>,
+        values: impl IntoIterator<Item = F>,
+        proof_array: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a,
+    {
+        phase_span!("LinearCodePCS::check_tensor");
+        Self::check_query(
+            vk,
+            commitments,
+            Query::Tensor(tensor),
+            values,
+            proof_array,
+            sponge,
+        )
+    }
+}
+
+/// Checks that an untrusted `proof` for the commitment to `label` has `t`
+/// columns with their paths, and vectors of the lengths given by `metadata`.
+fn check_proof_shape<F, C>(
+    label: &str,
+    metadata: &Metadata,
+    t: usize,
+    proof: &LinCodePCProof<F, C>,
+    check_well_formedness: bool,
+) -> Result<(), Error>
+where
+    F: PrimeField,
+    C: Config,
+{
+    let malformed = |reason: String| {
+        Err(VerificationFailure::MalformedProof {
+            label: Some(label.to_string()),
+            reason,
+        }
+        .into())
+    };
+    if metadata.n_rows == 0 || metadata.n_cols == 0 || metadata.n_ext_cols < metadata.n_cols {
+        return Err(Error::InvalidCommitment);
+    }
+    match &proof.well_formedness {
+        None if check_well_formedness => {
+            return malformed("missing well-formedness vector".to_string())
+        }
+        Some(_) if !check_well_formedness => {
+            return malformed("unexpected well-formedness vector".to_string())
+        }
+        Some(v) if v.len() != metadata.n_cols => {
+            return malformed(format!(
+                "expected a well-formedness vector of length {}, found {}",
+                metadata.n_cols,
+                v.len()
+            ))
+        }
+        _ => {}
+    }
+    let opening = &proof.opening;
+    if opening.v.len() != metadata.n_cols {
+        return malformed(format!(
+            "expected an evaluation vector of length {}, found {}",
+            metadata.n_cols,
+            opening.v.len()
+        ));
+    }
+    if opening.columns.len() != t || opening.paths.len() != t {
+        return malformed(format!(
+            "expected {} columns and paths, found {} and {}",
+            t,
+            opening.columns.len(),
+            opening.paths.len()
+        ));
+    }
+    if let Some(column) = opening
+        .columns
+        .iter()
+        .find(|column| column.len() != metadata.n_rows)
+    {
+        return malformed(format!(
+            "expected columns of length {}, found {}",
+            metadata.n_rows,
+            column.len()
+        ));
+    }
+    Ok(())
+}
+
 // TODO maybe this can go to utils
 fn create_merkle_tree<C>(
     leaves: &mut Vec<C::Leaf>,
@@ -509,51 +920,144 @@ where
     // pad the column hashes with zeroes
     let next_pow_of_two = leaves.len().next_power_of_two();
     leaves.resize(next_pow_of_two, <C::Leaf>::default());
+    phase_span!(
+        "merkle_tree",
+        leaves = next_pow_of_two,
+        depth = next_pow_of_two.trailing_zeros()
+    );
+    count!(HASH, 2 * next_pow_of_two - 1);
 
     MerkleTree::<C>::new(leaf_hash_param, two_to_one_hash_param, leaves)
         .map_err(|_| Error::HashingError)
 }
 
+/// Predicts the cost of committing to `batch.num_polys` polynomials arranged in
+/// `n_rows x n_cols` matrices, whose rows are encoded into `n_ext_cols` columns,
+/// and of opening them at `batch.num_points` points.
+fn lin_code_cost<F, C, H>(
+    params: &impl LinCodeParametersInfo<C, H>,
+    (n_rows, n_cols): (usize, usize),
+    n_ext_cols: usize,
+    batch: &BatchShape,
+) -> Result<CostEstimate, Error>
+where
+    F: PrimeField,
+    C: Config,
+    C::Leaf: Default + Clone + Send + AsRef<C::Leaf>,
+    H: CRHScheme,
+    Vec<F>: Borrow<H::Input>,
+    H::Output: Into<C::Leaf>,
+{
+    // The digests are measured on a tree with two column hashes as leaves,
+    // whose paths do not contain any inner node.
+    let col_hash = H::evaluate(params.col_hash_params(), vec![F::zero(); n_rows])
+        .map_err(|_| Error::HashingError)?;
+    let tree = create_merkle_tree::<C>(
+        &mut vec![col_hash.into(); 2],
+        params.leaf_hash_param(),
+        params.two_to_one_hash_param(),
+    )?;
+    let root = tree.root();
+    let inner_digest_bytes = compressed_size(&root);
+    let leaf_path = tree.generate_proof(0).map_err(|_| Error::HashingError)?;
+    let commitment_bytes = compressed_size(&LinCodePCCommitment::<C> {
+        metadata: Metadata::default(),
+        root,
+    });
+
+    let num_leaves = n_ext_cols.next_power_of_two();
+    let depth = log2(num_leaves) as usize;
+    let path_bytes = compressed_size(&leaf_path) + depth.saturating_sub(1) * inner_digest_bytes;
+    let t = calculate_t::<F>(params.sec_param(), params.distance(), n_ext_cols)?;
+
+    let field_bytes = compressed_size(&F::zero());
+    let row_bytes = vec_bytes(n_cols, field_bytes);
+    let well_formedness_bytes = if params.check_well_formedness() {
+        row_bytes
+    } else {
+        0
+    };
+    let single_proof_bytes = vec_bytes(t, path_bytes)
+        + row_bytes
+        + vec_bytes(t, vec_bytes(n_rows, field_bytes))
+        + OPTION_BYTES
+        + well_formedness_bytes;
+
+    let num_openings = batch.num_polys * batch.num_points;
+    let num_encodings = if params.check_well_formedness() { 2 } else { 1 };
+    let tree_hashes = 2 * num_leaves - 1;
+    Ok(CostEstimate {
+        commitment_bytes,
+        proof_bytes: vec_bytes(
+            batch.num_points,
+            vec_bytes(batch.num_polys, single_proof_bytes),
+        ),
+        prover: ProverCost {
+            msms: Vec::new(),
+            encodings: repeat(n_ext_cols, n_rows * batch.num_polys),
+            // The opening rebuilds the Merkle tree from the column hashes.
+            hashes: batch.num_polys * (n_ext_cols + tree_hashes) + num_openings * tree_hashes,
+        },
+        verifier: VerifierCost {
+            pairings: 0,
+            msms: Vec::new(),
+            encodings: repeat(n_ext_cols, num_openings * num_encodings),
+            // A column hash, a leaf hash and `depth` compressions per query.
+            hashes: num_openings * t * (2 + depth),
+        },
+    })
+}
+
+/// Squeezes the coefficients `r` of the random linear combination of the rows
+/// whose result proves the well-formedness of the committed matrix.
+pub(crate) fn squeeze_well_formedness_challenge<F: PrimeField>(
+    sponge: &mut impl CryptographicSponge,
+    n_rows: usize,
+) -> Vec<F> {
+    sponge.squeeze_labelled_field_elements(
+        b"well-formedness challenge",
+        &vec![FieldElementSize::Full; n_rows],
+    )
+}
+
+/// The Merkle tree over the columns of the encoded matrix, as used by `open`.
+enum OpeningTree<'a, F: PrimeField, C: Config> {
+    /// Rebuilt from the column hashes of a full state.
+    Rebuilt {
+        ext_mat: &'a Matrix<F>,
+        tree: MerkleTree<C>,
+    },
+    /// Stored in a compact state, whose queried columns must be recomputed.
+    Stored(&'a ColumnTree<C>),
+}
+
+/// Computes `b.M` and appends it to the transcript, then squeezes the indices
+/// of the columns to query out of the `n_ext_cols` columns of the encoded matrix.
+/// `query_columns` returns these columns and their Merkle tree paths.
 fn generate_proof<F, C>(
     sec_param: usize,
     distance: (usize, usize),
     b: &[F],
     mat: &Matrix<F>,
-    ext_mat: &Matrix<F>,
-    col_tree: &MerkleTree<C>,
+    n_ext_cols: usize,
     sponge: &mut impl CryptographicSponge,
+    query_columns: impl FnOnce(&[usize]) -> Result<(Vec<Vec<F>>, Vec<Path<C>>), Error>,
 ) -> Result<LinCodePCProofSingle<F, C>, Error>
 where
     F: PrimeField + Absorb,
     C: Config,
 {
-    let t = calculate_t::<F>(sec_param, distance, ext_mat.m)?;
+    let t = calculate_t::<F>(sec_param, distance, n_ext_cols)?;
 
     // 1. left-multiply the matrix by `b`.
     let v = mat.row_mul(b);
-    sponge.absorb(&v);
+    sponge.absorb_labelled(b"evaluation vector", &v);
 
     // 2. Generate t column indices to test the linear combination on.
-    let indices = get_indices_from_sponge(ext_mat.m, t, sponge)?;
+    let indices = get_indices_from_sponge(n_ext_cols, t, sponge)?;
 
     // 3. Compute Merkle tree paths for the requested columns.
-    let mut queried_columns = Vec::with_capacity(t);
-    let mut paths = Vec::with_capacity(t);
+    let (columns, paths) = query_columns(&indices)?;
 
-    let ext_mat_cols = ext_mat.cols();
-
-    for i in indices {
-        queried_columns.push(ext_mat_cols[i].clone());
-        paths.push(
-            col_tree
-                .generate_proof(i)
-                .map_err(|_| Error::TranscriptError)?,
-        );
-    }
-
-    Ok(LinCodePCProofSingle {
-        paths,
-        v,
-        columns: queried_columns,
-    })
+    Ok(LinCodePCProofSingle { paths, v, columns })
 }
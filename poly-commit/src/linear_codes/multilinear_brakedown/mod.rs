@@ -1,6 +1,9 @@
 use crate::Error;
 
-use super::{utils::tensor_vec, BrakedownPCParams, LinearEncode};
+use super::{
+    utils::{check_hypercube_dimensions, select_entries, tensor_vec},
+    BrakedownPCParams, LinearEncode,
+};
 use ark_crypto_primitives::{
     crh::{CRHScheme, TwoToOneCRHScheme},
     merkle_tree::Config,
@@ -32,6 +35,8 @@ where
 {
     type LinCodePCParams = BrakedownPCParams<F, C, H>;
 
+    const PROTOCOL_NAME: &'static [u8] = b"ark-poly-commit/MultilinearBrakedown";
+
     fn setup<R: RngCore>(
         _max_degree: usize,
         num_vars: Option<usize>,
@@ -80,6 +85,24 @@ where
         Ok(cw.to_vec())
     }
 
+    /// The code is systematic: the entries at the positions of the message
+    /// are the message itself, and only the others need the whole codeword.
+    fn encode_at(
+        msg: &[F],
+        indices: &[usize],
+        pp: &Self::LinCodePCParams,
+    ) -> Result<Vec<F>, Error> {
+        if msg.len() != pp.m {
+            return Err(Error::EncodingError);
+        }
+        if indices.iter().all(|&j| j < pp.m) {
+            return select_entries(msg, indices);
+        }
+        let codeword = Self::encode(msg, pp)?;
+        count!(ENCODING, codeword.len());
+        select_entries(&codeword, indices)
+    }
+
     fn poly_to_vec(polynomial: &P) -> Vec<F> {
         polynomial.to_evaluations()
     }
@@ -102,6 +125,14 @@ where
         let right = &point[split..];
         (tensor_vec(left), tensor_vec(right))
     }
+
+    fn check_dimensions(
+        point: &<P as Polynomial<F>>::Point,
+        n_rows: usize,
+        n_cols: usize,
+    ) -> Result<(), Error> {
+        check_hypercube_dimensions(point.len(), n_rows, n_cols)
+    }
 }
 
 // This RS encoding is on points 1, ..., oe - s without relying on FFTs
@@ -1,7 +1,8 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        linear_codes::BrakedownPCParams,
+        linear_codes::{BrakedownPCParams, LinearEncode, MultilinearBrakedown},
+        security::{DecodingRegime, SecurityAssumptions, SecurityEstimator},
         test_types::{
             test_sponge, FieldToBytesColHasher, LeafIdentityHasher, TestMLBrakedown,
             TestMerkleTreeParams,
@@ -13,7 +14,7 @@ mod tests {
     use ark_bls12_381::Fr as Fr381;
     use ark_crypto_primitives::crh::{sha256::Sha256, CRHScheme, TwoToOneCRHScheme};
 
-    use ark_ff::{Field, PrimeField};
+    use ark_ff::{Field, PrimeField, UniformRand};
     use ark_poly::evaluations::multivariate::{MultilinearExtension, SparseMultilinearExtension};
     use ark_std::test_rng;
     use blake2::Blake2s256;
@@ -95,16 +96,44 @@ mod tests {
             None,
         )
         .unwrap();
-        assert!(TestMLBrakedown::<Fr>::check(
-            &vk,
-            &c,
-            &point,
-            [value],
-            &proof,
-            &mut test_sponge,
-            None
-        )
-        .unwrap());
+        TestMLBrakedown::<Fr>::check(&vk, &c, &point, [value], &proof, &mut test_sponge, None)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_encode_at() {
+        let mut rng = &mut test_rng();
+        let leaf_hash_param = <LeafIdentityHasher as CRHScheme>::setup(&mut rng).unwrap();
+        let two_to_one_hash_param = <Sha256 as TwoToOneCRHScheme>::setup(&mut rng)
+            .unwrap()
+            .clone();
+        let col_hash_params =
+            <FieldToBytesColHasher<Fr, Blake2s256> as CRHScheme>::setup(&mut rng).unwrap();
+        let pp: BrakedownPCParams<Fr, TestMerkleTreeParams, FieldToBytesColHasher<Fr, Blake2s256>> =
+            BrakedownPCParams::default(
+                rng,
+                1 << 11,
+                true,
+                leaf_hash_param,
+                two_to_one_hash_param,
+                col_hash_params,
+            );
+        type Encoder = MultilinearBrakedown<
+            Fr,
+            TestMerkleTreeParams,
+            SparseMultilinearExtension<Fr>,
+            FieldToBytesColHasher<Fr, Blake2s256>,
+        >;
+
+        let msg: Vec<Fr> = (0..pp.m).map(|_| Fr::rand(rng)).collect();
+        let codeword = Encoder::encode(&msg, &pp).unwrap();
+        // Entries of the message only, then entries of the whole codeword.
+        for indices in [vec![0, 3, 3, pp.m - 1], vec![1, pp.m, pp.m_ext - 1]] {
+            let expected: Vec<Fr> = indices.iter().map(|&j| codeword[j]).collect();
+            assert_eq!(Encoder::encode_at(&msg, &indices, &pp).unwrap(), expected);
+        }
+        assert!(Encoder::encode_at(&msg, &[pp.m_ext], &pp).is_err());
+        assert!(Encoder::encode_at(&msg[1..], &[0], &pp).is_err());
     }
 
     fn rand_point<F: Field>(num_vars: Option<usize>, rng: &mut ChaCha20Rng) -> Vec<F> {
@@ -235,4 +264,53 @@ mod tests {
         .expect("test failed for bls12-381");
         println!("Finished bls12-381");
     }
+
+    #[test]
+    fn test_security() {
+        let mut rng = &mut test_rng();
+        let leaf_hash_param = <LeafIdentityHasher as CRHScheme>::setup(&mut rng).unwrap();
+        let two_to_one_hash_param = <Sha256 as TwoToOneCRHScheme>::setup(&mut rng).unwrap();
+        let col_hash_params =
+            <FieldToBytesColHasher<Fr, Blake2s256> as CRHScheme>::setup(&mut rng).unwrap();
+        let poly_len = 1 << 12;
+        let assumptions = SecurityAssumptions::new(DecodingRegime::Unique, 20);
+
+        type Params =
+            BrakedownPCParams<Fr, TestMerkleTreeParams, FieldToBytesColHasher<Fr, Blake2s256>>;
+        let suggestion = Params::suggest(64.0, poly_len, &assumptions).unwrap();
+        let pp = Params::with_sec_param(
+            rng,
+            suggestion.sec_param,
+            poly_len,
+            true,
+            leaf_hash_param,
+            two_to_one_hash_param,
+            col_hash_params,
+        );
+        let estimate = pp.estimate_security(poly_len, &assumptions).unwrap();
+        assert!(estimate.bits() >= 64.0, "{:?}", estimate);
+        assert_eq!(estimate, suggestion.estimate);
+
+        // The random code only has the claimed distance with high probability.
+        assert!(Params::suggest(100.0, poly_len, &assumptions).is_err());
+        // Proximity gaps in the list decoding regime are not known for these codes.
+        let list_assumptions = SecurityAssumptions::new(DecodingRegime::List, 20);
+        assert!(pp.estimate_security(poly_len, &list_assumptions).is_err());
+        // The parameters are tied to the size of the polynomial.
+        assert!(pp.estimate_security(4 * poly_len, &assumptions).is_err());
+    }
+
+    #[test]
+    fn cost_model_test() {
+        use crate::tests::*;
+        cost_model_test::<_, _, TestMLBrakedown<Fr>, _>(
+            1,
+            Some(11),
+            1 << 11,
+            rand_poly::<Fr>,
+            rand_point::<Fr>,
+            poseidon_sponge_for_test::<Fr>,
+        )
+        .expect("test failed for bls12-377");
+    }
 }
@@ -1,5 +1,5 @@
 use super::{
-    utils::{reed_solomon, tensor_vec},
+    utils::{check_hypercube_dimensions, reed_solomon, reed_solomon_at, tensor_vec},
     LigeroPCParams, LinearEncode,
 };
 
@@ -34,6 +34,8 @@ where
 {
     type LinCodePCParams = LigeroPCParams<F, C, H>;
 
+    const PROTOCOL_NAME: &'static [u8] = b"ark-poly-commit/MultilinearLigero";
+
     fn setup<R>(
         _max_degree: usize,
         _num_vars: Option<usize>,
@@ -53,7 +55,15 @@ where
     }
 
     fn encode(msg: &[F], param: &Self::LinCodePCParams) -> Result<Vec<F>, Error> {
-        Ok(reed_solomon(msg, param.rho_inv))
+        reed_solomon(msg, param.rho_inv)
+    }
+
+    fn encode_at(
+        msg: &[F],
+        indices: &[usize],
+        param: &Self::LinCodePCParams,
+    ) -> Result<Vec<F>, Error> {
+        reed_solomon_at(msg, indices, param.rho_inv)
     }
 
     fn poly_to_vec(polynomial: &P) -> Vec<F> {
@@ -78,4 +88,12 @@ where
         let right = &point[split..];
         (tensor_vec(left), tensor_vec(right))
     }
+
+    fn check_dimensions(
+        point: &<P as Polynomial<F>>::Point,
+        n_rows: usize,
+        n_cols: usize,
+    ) -> Result<(), Error> {
+        check_hypercube_dimensions(point.len(), n_rows, n_cols)
+    }
 }
@@ -1,12 +1,12 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        linear_codes::LigeroPCParams,
+        linear_codes::{LigeroPCParams, MultilinearLigero},
         test_types::{
-            test_sponge, FieldToBytesColHasher, LeafIdentityHasher, TestMLLigero,
+            streaming_test, test_sponge, FieldToBytesColHasher, LeafIdentityHasher, TestMLLigero,
             TestMerkleTreeParams,
         },
-        LabeledPolynomial, PolynomialCommitment,
+        Error, LabeledPolynomial, PolynomialCommitment, VerificationFailure,
     };
 
     use ark_bls12_377::Fr;
@@ -94,16 +94,90 @@ mod tests {
             None,
         )
         .unwrap();
-        assert!(TestMLLigero::<Fr>::check(
+        TestMLLigero::<Fr>::check(
             &vk,
             &c,
             &point,
             [value],
             &proof,
-            &mut test_sponge,
-            None
+            &mut (test_sponge.clone()),
+            None,
         )
-        .unwrap());
+        .unwrap();
+
+        // A wrong value is reported as an evaluation mismatch.
+        let result = TestMLLigero::<Fr>::check(
+            &vk,
+            &c,
+            &point,
+            [value + Fr::ONE],
+            &proof,
+            &mut (test_sponge.clone()),
+            None,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::VerificationFailed(VerificationFailure::EvaluationMismatch { label, .. }))
+                if label == "test"
+        ));
+
+        // A tampered column no longer opens the Merkle root.
+        let mut tampered = proof.clone();
+        tampered[0].opening.columns[0][0] += Fr::ONE;
+        let column = tampered[0].opening.paths[0].leaf_index;
+        let result =
+            TestMLLigero::<Fr>::check(&vk, &c, &point, [value], &tampered, &mut test_sponge, None);
+        assert!(matches!(
+            result,
+            Err(Error::VerificationFailed(failure))
+                if failure == VerificationFailure::MerklePath { label: "test".to_string(), column }
+        ));
+    }
+
+    #[test]
+    fn test_streaming() {
+        let mut rng = &mut test_rng();
+        let num_vars = 9;
+        let leaf_hash_param = <LeafIdentityHasher as CRHScheme>::setup(&mut rng).unwrap();
+        let two_to_one_hash_param = <Sha256 as TwoToOneCRHScheme>::setup(&mut rng)
+            .unwrap()
+            .clone();
+        let col_hash_params =
+            <FieldToBytesColHasher<Fr, Blake2s256> as CRHScheme>::setup(&mut rng).unwrap();
+
+        let pp: LigeroPCParams<Fr, TestMerkleTreeParams, FieldToBytesColHasher<Fr, Blake2s256>> =
+            LigeroPCParams::new(
+                128,
+                4,
+                true,
+                leaf_hash_param,
+                two_to_one_hash_param,
+                col_hash_params,
+            );
+        let (ck, vk) = TestMLLigero::<Fr>::trim(&pp, 0, 0, None).unwrap();
+
+        let rand_chacha = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+        let labeled_poly = LabeledPolynomial::new(
+            "test".to_string(),
+            rand_poly(1, Some(num_vars), rand_chacha),
+            Some(num_vars),
+            Some(num_vars),
+        );
+        let evals = labeled_poly.to_evaluations();
+
+        let point = rand_point(Some(num_vars), rand_chacha);
+        streaming_test::<
+            MultilinearLigero<
+                Fr,
+                TestMerkleTreeParams,
+                SparseMultilinearExtension<Fr>,
+                FieldToBytesColHasher<Fr, Blake2s256>,
+            >,
+            _,
+            _,
+            TestMerkleTreeParams,
+            FieldToBytesColHasher<Fr, Blake2s256>,
+        >(&ck, &vk, labeled_poly, &evals, point);
     }
 
     fn rand_point<F: Field>(num_vars: Option<usize>, rng: &mut ChaCha20Rng) -> Vec<F> {
@@ -234,4 +308,17 @@ mod tests {
         .expect("test failed for bls12-381");
         println!("Finished bls12-381");
     }
+
+    #[test]
+    fn tensor_openings_test() {
+        use crate::tests::*;
+        tensor_openings_test::<_, _, TestMLLigero<Fr>, _>(
+            1,
+            Some(10),
+            rand_poly::<Fr>,
+            MultilinearExtension::to_evaluations,
+            poseidon_sponge_for_test::<Fr>,
+        )
+        .expect("test failed for bls12-377");
+    }
 }
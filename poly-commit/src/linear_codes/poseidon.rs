@@ -0,0 +1,211 @@
+use super::{LinearCodePCS, MultilinearBrakedown, MultilinearLigero, UnivariateLigero};
+use crate::Error;
+
+use ark_crypto_primitives::{
+    crh::{poseidon, CRHScheme, TwoToOneCRHScheme},
+    merkle_tree::{Config, IdentityDigestConverter},
+    sponge::{
+        poseidon::{find_poseidon_ark_and_mds, PoseidonConfig},
+        Absorb,
+    },
+};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{borrow::Borrow, marker::PhantomData, rand::Rng, string::ToString};
+#[cfg(not(feature = "std"))]
+use ark_std::{boxed::Box, vec::Vec};
+
+/// Rate of the default Poseidon permutation.
+const RATE: usize = 2;
+/// S-box exponent of the default Poseidon permutation.
+const ALPHA: u64 = 17;
+/// Number of full rounds of the default Poseidon permutation.
+const FULL_ROUNDS: usize = 8;
+/// Number of partial rounds of the default Poseidon permutation.
+const PARTIAL_ROUNDS: usize = 31;
+
+/// Poseidon parameters used by the `setup` of the hashers in this module.
+///
+/// These are the rate-2 parameters that `ark-crypto-primitives` ships as default
+/// for ~255-bit fields (`alpha = 17`, 8 full and 31 partial rounds), with round
+/// constants and MDS matrix derived from the Grain LFSR. Returns an error if
+/// `x -> x^17` is not a permutation of `F`, in which case the parameters should
+/// be provided explicitly (e.g. through `LigeroPCParams::new`).
+pub fn default_poseidon_config<F: PrimeField>(
+) -> Result<PoseidonConfig<F>, ark_crypto_primitives::Error> {
+    // `x -> x^alpha` is a permutation iff gcd(alpha, p - 1) = 1. Since alpha is a
+    // prime, it is enough to check that it does not divide p - 1.
+    let p_mod_alpha = F::MODULUS.as_ref().iter().rev().fold(0u128, |acc, &limb| {
+        ((acc << 64) + limb as u128) % ALPHA as u128
+    });
+    if p_mod_alpha == 1 {
+        return Err(ark_crypto_primitives::Error::GenericError(Box::new(
+            Error::InvalidParameters(
+                "The default Poseidon S-box is not a permutation of this field".to_string(),
+            ),
+        )));
+    }
+
+    let (ark, mds) = find_poseidon_ark_and_mds::<F>(
+        F::MODULUS_BIT_SIZE as u64,
+        RATE,
+        FULL_ROUNDS as u64,
+        PARTIAL_ROUNDS as u64,
+        0,
+    );
+    Ok(PoseidonConfig::new(
+        FULL_ROUNDS,
+        PARTIAL_ROUNDS,
+        ALPHA,
+        mds,
+        ark,
+        RATE,
+        1,
+    ))
+}
+
+/// Hashes a column of the encoded matrix into a single field element
+/// with the Poseidon sponge.
+pub struct PoseidonColHasher<F: PrimeField + Absorb> {
+    _phantom: PhantomData<F>,
+}
+
+impl<F: PrimeField + Absorb> CRHScheme for PoseidonColHasher<F> {
+    type Input = [F];
+    type Output = F;
+    type Parameters = PoseidonConfig<F>;
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, ark_crypto_primitives::Error> {
+        default_poseidon_config()
+    }
+
+    fn evaluate<T: Borrow<Self::Input>>(
+        parameters: &Self::Parameters,
+        input: T,
+    ) -> Result<Self::Output, ark_crypto_primitives::Error> {
+        poseidon::CRH::<F>::evaluate(parameters, input)
+    }
+}
+
+/// Compresses two nodes of the Merkle tree with the Poseidon sponge.
+pub struct PoseidonTwoToOneHasher<F: PrimeField + Absorb> {
+    _phantom: PhantomData<F>,
+}
+
+impl<F: PrimeField + Absorb> TwoToOneCRHScheme for PoseidonTwoToOneHasher<F> {
+    type Input = F;
+    type Output = F;
+    type Parameters = PoseidonConfig<F>;
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, ark_crypto_primitives::Error> {
+        default_poseidon_config()
+    }
+
+    fn evaluate<T: Borrow<Self::Input>>(
+        parameters: &Self::Parameters,
+        left_input: T,
+        right_input: T,
+    ) -> Result<Self::Output, ark_crypto_primitives::Error> {
+        poseidon::TwoToOneCRH::<F>::evaluate(parameters, left_input, right_input)
+    }
+
+    fn compress<T: Borrow<Self::Output>>(
+        parameters: &Self::Parameters,
+        left_input: T,
+        right_input: T,
+    ) -> Result<Self::Output, ark_crypto_primitives::Error> {
+        poseidon::TwoToOneCRH::<F>::compress(parameters, left_input, right_input)
+    }
+}
+
+/// A leaf of the Merkle tree, i.e. the Poseidon digest of a column.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Default(bound = ""),
+    Clone(bound = ""),
+    Copy(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+pub struct ColumnDigest<F: PrimeField>(pub F);
+
+impl<F: PrimeField> From<F> for ColumnDigest<F> {
+    fn from(digest: F) -> Self {
+        Self(digest)
+    }
+}
+
+impl<F: PrimeField> AsRef<ColumnDigest<F>> for ColumnDigest<F> {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+/// The column digests are already field elements, so they are used
+/// as leaf digests as they are.
+pub struct FieldIdentityLeafHasher<F: PrimeField> {
+    _phantom: PhantomData<F>,
+}
+
+impl<F: PrimeField> CRHScheme for FieldIdentityLeafHasher<F> {
+    type Input = ColumnDigest<F>;
+    type Output = F;
+    type Parameters = ();
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, ark_crypto_primitives::Error> {
+        Ok(())
+    }
+
+    fn evaluate<T: Borrow<Self::Input>>(
+        _parameters: &Self::Parameters,
+        input: T,
+    ) -> Result<Self::Output, ark_crypto_primitives::Error> {
+        Ok(input.borrow().0)
+    }
+}
+
+/// Merkle tree over the Poseidon digests of the columns, with Poseidon
+/// used to compress inner nodes. Every hash of the resulting scheme is
+/// native to `F`, which makes it suitable for recursive verification.
+pub struct PoseidonMerkleTreeParams<F: PrimeField + Absorb> {
+    _phantom: PhantomData<F>,
+}
+
+impl<F: PrimeField + Absorb> Config for PoseidonMerkleTreeParams<F> {
+    type Leaf = ColumnDigest<F>;
+
+    type LeafDigest = F;
+    type LeafInnerDigestConverter = IdentityDigestConverter<F>;
+    type InnerDigest = F;
+
+    type LeafHash = FieldIdentityLeafHasher<F>;
+    type TwoToOneHash = PoseidonTwoToOneHasher<F>;
+}
+
+/// Univariate Ligero with Poseidon-based column hashing and Merkle tree.
+pub type PoseidonUVLigero<F, P> = LinearCodePCS<
+    UnivariateLigero<F, PoseidonMerkleTreeParams<F>, P, PoseidonColHasher<F>>,
+    F,
+    P,
+    PoseidonMerkleTreeParams<F>,
+    PoseidonColHasher<F>,
+>;
+
+/// Multilinear Ligero with Poseidon-based column hashing and Merkle tree.
+pub type PoseidonMLLigero<F, P> = LinearCodePCS<
+    MultilinearLigero<F, PoseidonMerkleTreeParams<F>, P, PoseidonColHasher<F>>,
+    F,
+    P,
+    PoseidonMerkleTreeParams<F>,
+    PoseidonColHasher<F>,
+>;
+
+/// Multilinear Brakedown with Poseidon-based column hashing and Merkle tree.
+pub type PoseidonMLBrakedown<F, P> = LinearCodePCS<
+    MultilinearBrakedown<F, PoseidonMerkleTreeParams<F>, P, PoseidonColHasher<F>>,
+    F,
+    P,
+    PoseidonMerkleTreeParams<F>,
+    PoseidonColHasher<F>,
+>;
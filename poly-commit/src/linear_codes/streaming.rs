@@ -0,0 +1,337 @@
+//! Space-efficient commitment and opening for linear-code-based schemes.
+//!
+//! Instead of materialising the coefficient matrix and its encoding, the
+//! polynomial is read row by row from an [`Iterable`] stream: the committer
+//! only keeps one accumulator per column of the encoded matrix, and the
+//! opening re-streams the polynomial to compute `b.M` and the queried columns.
+use super::{
+    create_merkle_tree,
+    data_structures::{LinCodePCProofSingle, Metadata},
+    poseidon::PoseidonColHasher,
+    squeeze_well_formedness_challenge,
+    utils::{calculate_t, get_indices_from_sponge},
+    LinCodePCCommitment, LinCodePCProof, LinCodeParametersInfo, LinearCodePCS, LinearEncode,
+};
+use crate::{Error, Transcript};
+
+use ark_crypto_primitives::{
+    crh::CRHScheme,
+    merkle_tree::Config,
+    sponge::{poseidon::PoseidonSponge, Absorb, CryptographicSponge, DuplexSpongeMode},
+};
+use ark_ff::PrimeField;
+use ark_poly::Polynomial;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(not(feature = "std"))]
+use ark_std::vec::Vec;
+use ark_std::{borrow::Borrow, format, iterable::Iterable, mem};
+
+#[cfg(feature = "parallel")]
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
+
+/// A column hash whose output can be computed by absorbing the column one
+/// entry at a time. This is what allows [`LinearCodePCS::commit_stream`] to
+/// hash the columns of the encoded matrix while only reading its rows.
+///
+/// The streaming methods of [`LinearCodePCS`] are only available for the
+/// column hashes which implement this trait: [`PoseidonColHasher`], and
+/// `FieldToBytesColHasher` with the `test-types` feature.
+pub trait IncrementalColumnHash<F: PrimeField>: CRHScheme {
+    /// The state of the hash of a column that has been partially absorbed.
+    /// There is one per column of the encoded matrix, so it should not hold
+    /// its own copy of the parameters.
+    type Accumulator: Clone + Send;
+
+    /// Start hashing a column with `len` entries.
+    fn init(parameters: &Self::Parameters, len: usize) -> Self::Accumulator;
+
+    /// Absorb the next row of the encoded matrix, whose `j`-th entry is the
+    /// next entry of the column of `accumulators[j]`.
+    fn update(parameters: &Self::Parameters, accumulators: &mut [Self::Accumulator], row: &[F]);
+
+    /// Output the hash of the column. This must agree with `CRHScheme::evaluate`
+    /// on the whole column.
+    fn finalize(
+        parameters: &Self::Parameters,
+        accumulator: Self::Accumulator,
+    ) -> Result<Self::Output, ark_crypto_primitives::Error>;
+}
+
+/// The state of a Poseidon sponge which has absorbed part of a column, without
+/// the parameters of the permutation, which the columns share.
+#[derive(Clone, Debug)]
+pub struct PoseidonColumnState<F: PrimeField> {
+    state: Vec<F>,
+    mode: DuplexSpongeMode,
+}
+
+impl<F: PrimeField + Absorb> PoseidonColumnState<F> {
+    /// Absorb `entry` with `sponge`, whose own state is swapped with this one.
+    fn absorb(&mut self, sponge: &mut PoseidonSponge<F>, entry: &F) {
+        mem::swap(&mut sponge.state, &mut self.state);
+        sponge.mode = self.mode.clone();
+        sponge.absorb(entry);
+        mem::swap(&mut sponge.state, &mut self.state);
+        self.mode = sponge.mode.clone();
+    }
+}
+
+impl<F: PrimeField + Absorb> IncrementalColumnHash<F> for PoseidonColHasher<F> {
+    type Accumulator = PoseidonColumnState<F>;
+
+    fn init(parameters: &Self::Parameters, _len: usize) -> Self::Accumulator {
+        // The state of `PoseidonSponge::new(parameters)`.
+        PoseidonColumnState {
+            state: vec![F::zero(); parameters.rate + parameters.capacity],
+            mode: DuplexSpongeMode::Absorbing {
+                next_absorb_index: 0,
+            },
+        }
+    }
+
+    fn update(parameters: &Self::Parameters, accumulators: &mut [Self::Accumulator], row: &[F]) {
+        // Each thread absorbs its share of the columns with a sponge of its own.
+        #[cfg(feature = "parallel")]
+        accumulators.par_iter_mut().zip(row).for_each_init(
+            || PoseidonSponge::new(parameters),
+            |sponge, (acc, entry)| acc.absorb(sponge, entry),
+        );
+        #[cfg(not(feature = "parallel"))]
+        {
+            let mut sponge = PoseidonSponge::new(parameters);
+            for (acc, entry) in accumulators.iter_mut().zip(row) {
+                acc.absorb(&mut sponge, entry);
+            }
+        }
+    }
+
+    fn finalize(
+        parameters: &Self::Parameters,
+        accumulator: Self::Accumulator,
+    ) -> Result<Self::Output, ark_crypto_primitives::Error> {
+        let mut sponge = PoseidonSponge::new(parameters);
+        sponge.state = accumulator.state;
+        sponge.mode = accumulator.mode;
+        Ok(sponge.squeeze_field_elements::<F>(1)[0])
+    }
+}
+
+/// The state kept by the committer between [`LinearCodePCS::commit_stream`] and
+/// [`LinearCodePCS::open_stream`]: only the hashes of the columns of the encoded
+/// matrix, from which the Merkle tree is rebuilt.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
+pub struct LinCodePCStreamingState<H: CRHScheme> {
+    pub(crate) leaves: Vec<H::Output>,
+}
+
+/// Calls `f` on each row of the `n_rows x n_cols` matrix whose entries are read,
+/// row-major, from `polynomial` and padded with zeroes.
+fn for_each_row<F, S>(
+    polynomial: &S,
+    n_rows: usize,
+    n_cols: usize,
+    mut f: impl FnMut(usize, &[F]) -> Result<(), Error>,
+) -> Result<(), Error>
+where
+    F: PrimeField,
+    S: Iterable,
+    S::Item: Borrow<F>,
+{
+    if polynomial.len() > n_rows * n_cols {
+        return Err(Error::IncorrectInputLength(format!(
+            "The stream has {} elements, but the matrix has only {} entries",
+            polynomial.len(),
+            n_rows * n_cols
+        )));
+    }
+
+    let mut coeffs = polynomial.iter();
+    let mut row = Vec::with_capacity(n_cols);
+    let mut num_read = 0;
+    for i in 0..n_rows {
+        row.clear();
+        row.extend(coeffs.by_ref().take(n_cols).map(|c| *c.borrow()));
+        num_read += row.len();
+        row.resize(n_cols, F::zero());
+        f(i, &row)?;
+    }
+    // `len` is only a claim of the stream, on which the dimensions rely.
+    let num_left = coeffs.count();
+    if num_read + num_left != polynomial.len() {
+        return Err(Error::IncorrectInputLength(format!(
+            "The stream has {} elements, but its length is {}",
+            num_read + num_left,
+            polynomial.len()
+        )));
+    }
+    Ok(())
+}
+
+impl<L, F, P, C, H> LinearCodePCS<L, F, P, C, H>
+where
+    L: LinearEncode<F, C, P, H>,
+    F: PrimeField + Absorb,
+    P: Polynomial<F>,
+    C: Config + 'static,
+    H::Output: Into<C::Leaf> + Send,
+    C::Leaf: Sized + Clone + Default + Send + AsRef<C::Leaf>,
+    H: IncrementalColumnHash<F> + 'static,
+{
+    /// Commit to the polynomial whose coefficients (or evaluations over the
+    /// Boolean hypercube, in the multilinear case) are read from `polynomial`,
+    /// in the order given by `LinearEncode::poly_to_vec`.
+    ///
+    /// The resulting commitment is identical to the one computed by `commit`,
+    /// but only the encoding of one row and one hash accumulator per column
+    /// are kept in memory.
+    pub fn commit_stream<S>(
+        ck: &L::LinCodePCParams,
+        polynomial: &S,
+    ) -> Result<(LinCodePCCommitment<C>, LinCodePCStreamingState<H>), Error>
+    where
+        S: Iterable,
+        S::Item: Borrow<F>,
+    {
+        phase_span!("LinearCodePCS::commit_stream");
+        if polynomial.len() == 0 {
+            return Err(Error::IncorrectInputLength(
+                "The stream is empty".to_string(),
+            ));
+        }
+        let (n_rows, n_cols) = ck.compute_dimensions(polynomial.len());
+
+        // 1. Encode the matrix row by row, and absorb each encoded row
+        // into the accumulators of the column hashes.
+        let mut accumulators: Vec<H::Accumulator> = Vec::new();
+        for_each_row(polynomial, n_rows, n_cols, |i, row| {
+            let ext_row = L::encode(row, ck)?;
+            count!(ENCODING, ext_row.len());
+            if i == 0 {
+                accumulators = vec![H::init(ck.col_hash_params(), n_rows); ext_row.len()];
+            }
+            H::update(ck.col_hash_params(), &mut accumulators, &ext_row);
+            Ok(())
+        })?;
+        let n_ext_cols = accumulators.len();
+        if n_rows == 0 || n_ext_cols < n_cols {
+            return Err(Error::EncodingError);
+        }
+
+        // 2. Create the Merkle tree from the hashes of each column.
+        let leaves = accumulators
+            .into_iter()
+            .map(|acc| H::finalize(ck.col_hash_params(), acc).map_err(|_| Error::HashingError))
+            .collect::<Result<Vec<_>, _>>()?;
+        count!(HASH, leaves.len());
+        let mut col_hashes: Vec<C::Leaf> = leaves.iter().cloned().map(|h| h.into()).collect();
+        let col_tree = create_merkle_tree::<C>(
+            &mut col_hashes,
+            ck.leaf_hash_param(),
+            ck.two_to_one_hash_param(),
+        )?;
+
+        let commitment = LinCodePCCommitment {
+            metadata: Metadata {
+                n_rows,
+                n_cols,
+                n_ext_cols,
+            },
+            root: col_tree.root(),
+        };
+        Ok((commitment, LinCodePCStreamingState { leaves }))
+    }
+
+    /// Open the polynomial committed to with [`Self::commit_stream`] at `point`,
+    /// reading it again from `polynomial`, which is streamed twice.
+    ///
+    /// The proof is identical to the one that `open` outputs for this single
+    /// polynomial, so `LPCPArray(vec![proof])` can be verified with `check`.
+    pub fn open_stream<S>(
+        ck: &L::LinCodePCParams,
+        polynomial: &S,
+        commitment: &LinCodePCCommitment<C>,
+        state: &LinCodePCStreamingState<H>,
+        point: &P::Point,
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<LinCodePCProof<F, C>, Error>
+    where
+        S: Iterable,
+        S::Item: Borrow<F>,
+    {
+        phase_span!("LinearCodePCS::open_stream");
+        let n_rows = commitment.metadata.n_rows;
+        let n_cols = commitment.metadata.n_cols;
+        let n_ext_cols = commitment.metadata.n_ext_cols;
+
+        let mut col_hashes: Vec<C::Leaf> = state.leaves.iter().cloned().map(|h| h.into()).collect();
+        let col_tree = create_merkle_tree::<C>(
+            &mut col_hashes,
+            ck.leaf_hash_param(),
+            ck.two_to_one_hash_param(),
+        )?;
+
+        // 1. Generate vector `b` to left-multiply the matrix.
+        let (_, b) = L::tensor(point, n_cols, n_rows);
+
+        sponge.bind_context(L::PROTOCOL_NAME, ck)?;
+        sponge.absorb_serialized(b"root", &commitment.root)?;
+
+        let r = if ck.check_well_formedness() {
+            Some(squeeze_well_formedness_challenge::<F>(sponge, n_rows))
+        } else {
+            None
+        };
+
+        // 2. First pass: compute `b.M`, and `r.M` for the well-formedness check.
+        let mut v = vec![F::zero(); n_cols];
+        let mut well_formedness = r.as_ref().map(|_| vec![F::zero(); n_cols]);
+        for_each_row(polynomial, n_rows, n_cols, |i, row| {
+            for (v_j, m_ij) in v.iter_mut().zip(row) {
+                *v_j += b[i] * m_ij;
+            }
+            if let (Some(wf), Some(r)) = (well_formedness.as_mut(), r.as_ref()) {
+                for (wf_j, m_ij) in wf.iter_mut().zip(row) {
+                    *wf_j += r[i] * m_ij;
+                }
+            }
+            Ok(())
+        })?;
+
+        if let Some(wf) = &well_formedness {
+            sponge.absorb_labelled(b"well-formedness", wf);
+        }
+        sponge.absorb_labelled(b"point", &L::point_to_vec(point.clone()));
+        sponge.absorb_labelled(b"evaluation vector", &v);
+
+        // 3. Generate t column indices to test the linear combination on.
+        let t = calculate_t::<F>(ck.sec_param(), ck.distance(), n_ext_cols)?;
+        let indices = get_indices_from_sponge(n_ext_cols, t, sponge)?;
+
+        // 4. Second pass: collect the queried columns of the encoded matrix.
+        let mut columns = vec![Vec::with_capacity(n_rows); t];
+        for_each_row(polynomial, n_rows, n_cols, |_, row| {
+            let ext_row = L::encode(row, ck)?;
+            count!(ENCODING, ext_row.len());
+            for (column, &j) in columns.iter_mut().zip(&indices) {
+                column.push(ext_row[j]);
+            }
+            Ok(())
+        })?;
+
+        // 5. Compute Merkle tree paths for the requested columns.
+        let paths = indices
+            .iter()
+            .map(|&j| {
+                col_tree
+                    .generate_proof(j)
+                    .map_err(|_| Error::TranscriptError)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(LinCodePCProof {
+            opening: LinCodePCProofSingle { paths, v, columns },
+            well_formedness,
+        })
+    }
+}
@@ -1,4 +1,7 @@
-use super::{utils::reed_solomon, LigeroPCParams, LinearEncode};
+use super::{
+    utils::{reed_solomon, reed_solomon_at},
+    LigeroPCParams, LinearEncode,
+};
 
 use crate::Error;
 use ark_crypto_primitives::{
@@ -31,6 +34,8 @@ where
 {
     type LinCodePCParams = LigeroPCParams<F, C, H>;
 
+    const PROTOCOL_NAME: &'static [u8] = b"ark-poly-commit/UnivariateLigero";
+
     fn setup<R>(
         _max_degree: usize,
         _num_vars: Option<usize>,
@@ -50,7 +55,15 @@ where
     }
 
     fn encode(msg: &[F], param: &Self::LinCodePCParams) -> Result<Vec<F>, Error> {
-        Ok(reed_solomon(msg, param.rho_inv))
+        reed_solomon(msg, param.rho_inv)
+    }
+
+    fn encode_at(
+        msg: &[F],
+        indices: &[usize],
+        param: &Self::LinCodePCParams,
+    ) -> Result<Vec<F>, Error> {
+        reed_solomon_at(msg, indices, param.rho_inv)
     }
 
     /// For a univariate polynomial, we simply return the list of coefficients.
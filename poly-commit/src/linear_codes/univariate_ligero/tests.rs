@@ -1,12 +1,16 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        linear_codes::LigeroPCParams,
+        linear_codes::{
+            CommitmentStateMode, LigeroPCParams, PoseidonColHasher, PoseidonMerkleTreeParams,
+            PoseidonUVLigero, UnivariateLigero,
+        },
+        security::{DecodingRegime, SecurityAssumptions, SecurityEstimator},
         test_types::{
-            test_sponge, FieldToBytesColHasher, LeafIdentityHasher, TestMerkleTreeParams,
-            TestUVLigero,
+            streaming_test, test_sponge, FieldToBytesColHasher, LeafIdentityHasher,
+            TestMerkleTreeParams, TestUVLigero,
         },
-        LabeledPolynomial, PolynomialCommitment,
+        to_bytes, Error, LabeledPolynomial, PolynomialCommitment, VerificationFailure,
     };
 
     use ark_bls12_377::Fr;
@@ -14,6 +18,7 @@ mod tests {
     use ark_crypto_primitives::crh::{sha256::Sha256, CRHScheme, TwoToOneCRHScheme};
     use ark_ff::{Field, PrimeField, UniformRand};
     use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial};
+    use ark_serialize::CanonicalDeserialize;
     use ark_std::test_rng;
     use blake2::Blake2s256;
     use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
@@ -84,16 +89,212 @@ mod tests {
             None,
         )
         .unwrap();
-        assert!(TestUVLigero::<Fr>::check(
+        TestUVLigero::<Fr>::check(&vk, &c, &point, [value], &proof, &mut test_sponge, None)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_streaming() {
+        let degree = 100;
+        let mut rng = &mut test_rng();
+        let leaf_hash_param = <LeafIdentityHasher as CRHScheme>::setup(&mut rng).unwrap();
+        let two_to_one_hash_param = <Sha256 as TwoToOneCRHScheme>::setup(&mut rng)
+            .unwrap()
+            .clone();
+        let col_hash_params =
+            <FieldToBytesColHasher<Fr, Blake2s256> as CRHScheme>::setup(&mut rng).unwrap();
+
+        let pp: LigeroPCParams<Fr, TestMerkleTreeParams, FieldToBytesColHasher<Fr, Blake2s256>> =
+            LigeroPCParams::new(
+                128,
+                4,
+                true,
+                leaf_hash_param,
+                two_to_one_hash_param,
+                col_hash_params,
+            );
+        let (ck, vk) = TestUVLigero::<Fr>::trim(&pp, 0, 0, None).unwrap();
+
+        let rand_chacha = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+        let labeled_poly = LabeledPolynomial::new(
+            "test".to_string(),
+            rand_poly(degree, None, rand_chacha),
+            None,
+            None,
+        );
+        let coeffs = labeled_poly.coeffs().to_vec();
+
+        let point = Fr::rand(rand_chacha);
+        streaming_test::<
+            UnivariateLigero<
+                Fr,
+                TestMerkleTreeParams,
+                DensePolynomial<Fr>,
+                FieldToBytesColHasher<Fr, Blake2s256>,
+            >,
+            _,
+            _,
+            TestMerkleTreeParams,
+            FieldToBytesColHasher<Fr, Blake2s256>,
+        >(&ck, &vk, labeled_poly, &coeffs, point);
+    }
+
+    #[test]
+    fn test_streaming_poseidon() {
+        type PC = PoseidonUVLigero<Fr, DensePolynomial<Fr>>;
+        let degree = 100;
+        let rand_chacha = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+        let pp = PC::setup(degree, None, rand_chacha).unwrap();
+        let (ck, vk) = PC::trim(&pp, 0, 0, None).unwrap();
+
+        let labeled_poly = LabeledPolynomial::new(
+            "test".to_string(),
+            rand_poly(degree, None, rand_chacha),
+            None,
+            None,
+        );
+        let coeffs = labeled_poly.coeffs().to_vec();
+
+        let point = Fr::rand(rand_chacha);
+        streaming_test::<
+            UnivariateLigero<
+                Fr,
+                PoseidonMerkleTreeParams<Fr>,
+                DensePolynomial<Fr>,
+                PoseidonColHasher<Fr>,
+            >,
+            _,
+            _,
+            PoseidonMerkleTreeParams<Fr>,
+            PoseidonColHasher<Fr>,
+        >(&ck, &vk, labeled_poly, &coeffs, point);
+    }
+
+    #[test]
+    fn test_compact_state() {
+        let degree = 100;
+        let mut rng = &mut test_rng();
+        let leaf_hash_param = <LeafIdentityHasher as CRHScheme>::setup(&mut rng).unwrap();
+        let two_to_one_hash_param = <Sha256 as TwoToOneCRHScheme>::setup(&mut rng)
+            .unwrap()
+            .clone();
+        let col_hash_params =
+            <FieldToBytesColHasher<Fr, Blake2s256> as CRHScheme>::setup(&mut rng).unwrap();
+
+        let pp: LigeroPCParams<Fr, TestMerkleTreeParams, FieldToBytesColHasher<Fr, Blake2s256>> =
+            LigeroPCParams::new(
+                128,
+                4,
+                true,
+                leaf_hash_param,
+                two_to_one_hash_param,
+                col_hash_params,
+            );
+        let (ck, vk) = TestUVLigero::<Fr>::trim(&pp, 0, 0, None).unwrap();
+
+        let rand_chacha = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+        let labeled_polys = ["a", "b"]
+            .iter()
+            .map(|label| {
+                LabeledPolynomial::new(
+                    label.to_string(),
+                    rand_poly(degree, None, rand_chacha),
+                    None,
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let (c, states) = TestUVLigero::<Fr>::commit(&ck, &labeled_polys, None).unwrap();
+        let (c_compact, states_compact) = TestUVLigero::<Fr>::commit_with_state_mode(
+            &ck,
+            &labeled_polys,
+            CommitmentStateMode::Compact,
+        )
+        .unwrap();
+        assert_eq!(states[0].mode(), CommitmentStateMode::Full);
+        assert_eq!(states_compact[0].mode(), CommitmentStateMode::Compact);
+        for (c, c_compact) in c.iter().zip(&c_compact) {
+            assert_eq!(c.commitment().root, c_compact.commitment().root);
+        }
+
+        // The compact state is smaller, and survives a round trip to bytes.
+        let bytes = to_bytes!(&states_compact).unwrap();
+        assert!(bytes.len() < to_bytes!(&states).unwrap().len());
+        let states_compact: Vec<_> =
+            CanonicalDeserialize::deserialize_compressed(&bytes[..]).unwrap();
+
+        let point = Fr::rand(rand_chacha);
+        let values = labeled_polys
+            .iter()
+            .map(|p| p.evaluate(&point))
+            .collect::<Vec<_>>();
+        let test_sponge = test_sponge::<Fr>();
+
+        let proof = TestUVLigero::<Fr>::open(
+            &ck,
+            &labeled_polys,
+            &c,
+            &point,
+            &mut (test_sponge.clone()),
+            &states,
+            None,
+        )
+        .unwrap();
+        let proof_compact = TestUVLigero::<Fr>::open(
+            &ck,
+            &labeled_polys,
+            &c,
+            &point,
+            &mut (test_sponge.clone()),
+            &states_compact,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            to_bytes!(&proof).unwrap(),
+            to_bytes!(&proof_compact).unwrap()
+        );
+
+        TestUVLigero::<Fr>::check(
             &vk,
             &c,
             &point,
-            [value],
-            &proof,
-            &mut test_sponge,
-            None
+            values.clone(),
+            &proof_compact,
+            &mut (test_sponge.clone()),
+            None,
+        )
+        .unwrap();
+
+        // A missing value is reported rather than silently dropping the
+        // commitment it belongs to.
+        assert!(matches!(
+            TestUVLigero::<Fr>::check(
+                &vk,
+                &c,
+                &point,
+                values[..1].to_vec(),
+                &proof_compact,
+                &mut (test_sponge.clone()),
+                None,
+            ),
+            Err(Error::VerificationFailed(
+                VerificationFailure::MalformedProof { label: None, .. }
+            ))
+        ));
+
+        // A compact state is rejected when opened against another commitment.
+        assert!(TestUVLigero::<Fr>::open(
+            &ck,
+            &labeled_polys[..1],
+            &c[1..],
+            &point,
+            &mut (test_sponge.clone()),
+            &states_compact[..1],
+            None,
         )
-        .unwrap());
+        .is_err());
     }
 
     fn rand_point<F: Field>(_: Option<usize>, rng: &mut ChaCha20Rng) -> F {
@@ -339,4 +540,81 @@ mod tests {
         .expect("test failed for bls12-377");
         println!("Finished bls12-377");
     }
+
+    #[test]
+    fn test_security() {
+        let mut rng = &mut test_rng();
+        let leaf_hash_param = <LeafIdentityHasher as CRHScheme>::setup(&mut rng).unwrap();
+        let two_to_one_hash_param = <Sha256 as TwoToOneCRHScheme>::setup(&mut rng).unwrap();
+        let col_hash_params =
+            <FieldToBytesColHasher<Fr, Blake2s256> as CRHScheme>::setup(&mut rng).unwrap();
+        let poly_len = 1 << 10;
+
+        type Params =
+            LigeroPCParams<Fr, TestMerkleTreeParams, FieldToBytesColHasher<Fr, Blake2s256>>;
+        let new_params = |sec_param, rho_inv| {
+            Params::new(
+                sec_param,
+                rho_inv,
+                true,
+                leaf_hash_param.clone(),
+                two_to_one_hash_param.clone(),
+                col_hash_params.clone(),
+            )
+        };
+
+        // `sec_param` is the security in the unique decoding regime.
+        let pp = new_params(128, 4);
+        let estimate = pp
+            .estimate_security(poly_len, &SecurityAssumptions::default())
+            .unwrap();
+        assert!(estimate.bits() >= 128.0, "{:?}", estimate);
+        assert_eq!(estimate.computational_bits, None);
+
+        // Every Fiat-Shamir query costs a bit of security.
+        let fs_assumptions = SecurityAssumptions::new(DecodingRegime::Unique, 40);
+        let fs_estimate = pp.estimate_security(poly_len, &fs_assumptions).unwrap();
+        assert_eq!(estimate.bits() - fs_estimate.bits(), 40.0);
+
+        let suggestion = Params::suggest(100.0, poly_len, &fs_assumptions).unwrap();
+        let pp = new_params(suggestion.sec_param, suggestion.rho_inv);
+        let estimate = pp.estimate_security(poly_len, &fs_assumptions).unwrap();
+        assert!(estimate.bits() >= 100.0, "{:?}", estimate);
+        assert_eq!(estimate, suggestion.estimate);
+
+        // Reed-Solomon codes need fewer queries in the list decoding regime.
+        let list_assumptions = SecurityAssumptions::new(DecodingRegime::List, 40);
+        let list_suggestion = Params::suggest(100.0, poly_len, &list_assumptions).unwrap();
+        assert!(list_suggestion.num_queries < suggestion.num_queries);
+
+        // The target cannot exceed what the field allows.
+        assert!(Params::suggest(300.0, poly_len, &fs_assumptions).is_err());
+    }
+
+    #[test]
+    fn cost_model_test() {
+        use crate::tests::*;
+        cost_model_test::<_, _, TestUVLigero<Fr>, _>(
+            63,
+            None,
+            64,
+            rand_poly::<Fr>,
+            rand_point::<Fr>,
+            poseidon_sponge_for_test::<Fr>,
+        )
+        .expect("test failed for bls12-377");
+    }
+
+    #[test]
+    fn tensor_openings_test() {
+        use crate::tests::*;
+        tensor_openings_test::<_, _, TestUVLigero<Fr>, _>(
+            100,
+            None,
+            rand_poly::<Fr>,
+            |p| p.coeffs.clone(),
+            poseidon_sponge_for_test::<Fr>,
+        )
+        .expect("test failed for bls12-377");
+    }
 }
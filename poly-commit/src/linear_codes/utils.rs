@@ -1,38 +1,92 @@
-use crate::{utils::ceil_div, Error};
-use ark_crypto_primitives::sponge::CryptographicSponge;
+use crate::{
+    security::{
+        field_bits, log2_add, statistical_bits, DecodingRegime, SecurityAssumptions,
+        SecurityEstimate,
+    },
+    utils::ceil_div,
+    Error, Transcript,
+};
+use ark_crypto_primitives::{
+    crh::{CRHScheme, TwoToOneCRHScheme},
+    merkle_tree::{Config, DigestConverter, LeafParam, Path, TwoToOneParam},
+    sponge::CryptographicSponge,
+};
 use ark_ff::{FftField, Field, PrimeField};
 use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::log2;
 use ark_std::string::ToString;
 use ark_std::vec::Vec;
 
+#[cfg(feature = "parallel")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
 #[cfg(all(not(feature = "std"), target_arch = "aarch64"))]
 use num_traits::Float;
 
 #[cfg(test)]
-use {
-    ark_crypto_primitives::crh::CRHScheme,
-    ark_std::{borrow::Borrow, rand::RngCore},
-};
+use ark_std::{borrow::Borrow, rand::RngCore};
 
 /// Apply reed-solomon encoding to msg.
 /// Assumes msg.len() is equal to the order of some FFT domain in F.
-/// Returns a vector of length equal to the smallest FFT domain of size at least msg.len() * RHO_INV.
+/// Returns a vector of length equal to the smallest FFT domain of size at least msg.len() * RHO_INV,
+/// or an error if the field F cannot accomodate an FFT of that size.
 pub(crate) fn reed_solomon<F: FftField>(
     // msg, of length m, is interpreted as a vector of coefficients of a polynomial of degree m - 1
     msg: &[F],
     rho_inv: usize,
-) -> Vec<F> {
+) -> Result<Vec<F>, Error> {
     let m = msg.len();
 
-    let extended_domain = GeneralEvaluationDomain::<F>::new(m * rho_inv).unwrap_or_else(|| {
-        panic!(
-            "The field F cannot accomodate FFT for msg.len() * RHO_INV = {} elements (too many)",
-            m * rho_inv
-        )
-    });
+    let extended_domain = m
+        .checked_mul(rho_inv)
+        .and_then(GeneralEvaluationDomain::<F>::new)
+        .ok_or(Error::EncodingError)?;
+
+    Ok(extended_domain.fft(msg))
+}
+
+/// The entries at `indices` of `reed_solomon(msg, rho_inv)`, i.e. the
+/// evaluations of `msg` at the corresponding elements of the extended domain.
+/// Each evaluation costs `msg.len()` multiplications, against about
+/// `log2(n) / 2` per entry for an FFT over the domain of size `n`, so the whole
+/// codeword is computed instead when that is cheaper.
+pub(crate) fn reed_solomon_at<F: FftField>(
+    msg: &[F],
+    indices: &[usize],
+    rho_inv: usize,
+) -> Result<Vec<F>, Error> {
+    let extended_domain = msg
+        .len()
+        .checked_mul(rho_inv)
+        .and_then(GeneralEvaluationDomain::<F>::new)
+        .ok_or(Error::EncodingError)?;
+    let n = extended_domain.size();
+
+    if 2 * indices.len() * msg.len() >= n * log2(n) as usize {
+        let codeword = extended_domain.fft(msg);
+        count!(ENCODING, n);
+        return select_entries(&codeword, indices);
+    }
+    indices
+        .iter()
+        .map(|&j| {
+            if j >= n {
+                return Err(Error::EncodingError);
+            }
+            // Horner's rule
+            let x = extended_domain.element(j);
+            Ok(msg.iter().rev().fold(F::zero(), |eval, m_k| eval * x + m_k))
+        })
+        .collect()
+}
 
-    extended_domain.fft(msg)
+/// The entries of `codeword` at the positions in `indices`.
+pub(crate) fn select_entries<F: Field>(codeword: &[F], indices: &[usize]) -> Result<Vec<F>, Error> {
+    indices
+        .iter()
+        .map(|&j| codeword.get(j).copied().ok_or(Error::EncodingError))
+        .collect()
 }
 
 /// This is CSC format
@@ -139,8 +193,7 @@ pub(crate) fn get_indices_from_sponge<S: CryptographicSponge>(
     let bytes_to_squeeze = get_num_bytes(n);
     let mut indices = Vec::with_capacity(t);
     for _ in 0..t {
-        let bytes = sponge.squeeze_bytes(bytes_to_squeeze);
-        sponge.absorb(&bytes);
+        let bytes = sponge.squeeze_labelled_bytes(b"column index", bytes_to_squeeze);
 
         // get the usize from Vec<u8>:
         let ind = bytes.iter().fold(0, |acc, &x| (acc << 8) + x as usize);
@@ -181,6 +234,65 @@ pub(crate) fn calculate_t<F: PrimeField>(
     Ok(if t < codeword_len { t } else { codeword_len })
 }
 
+/// Estimate the security of the tests run by the verifier of a linear-code-based
+/// scheme on a matrix with `n_rows` rows, each encoded into a codeword of length
+/// `codeword_len` of a code with the given `rate` and relative `distance`, when
+/// `t` columns are queried.
+///
+/// As in [`calculate_t`], the proximity and consistency tests each accept a
+/// matrix far from the code with probability `(1 - delta)^t`, to which we add
+/// the error of the proximity gap of the code in the chosen regime [[BCI+20]][bci].
+/// `log2_code_error` accounts for codes that are sampled at random and only have
+/// the stated distance with high probability.
+///
+/// [bci]: https://eprint.iacr.org/2020/654
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn lin_code_security<F: PrimeField>(
+    t: usize,
+    n_rows: usize,
+    codeword_len: usize,
+    rate: f64,
+    distance: (usize, usize),
+    is_reed_solomon: bool,
+    log2_code_error: f64,
+    assumptions: &SecurityAssumptions,
+) -> Result<SecurityEstimate, Error> {
+    let field_bits = field_bits::<F>();
+    let codeword_len = codeword_len as f64;
+
+    let (delta, log2_gap_error) = match assumptions.regime {
+        DecodingRegime::Unique => (
+            0.5 * distance.0 as f64 / distance.1 as f64,
+            codeword_len.log2() - field_bits,
+        ),
+        DecodingRegime::List => {
+            if !is_reed_solomon {
+                return Err(Error::InvalidParameters(
+                    "Proximity gaps in the list-decoding regime are only known for Reed-Solomon codes"
+                        .to_string(),
+                ));
+            }
+            // Johnson bound, with the slack `eta` of Thm. 1.2 in BCI+20.
+            let sqrt_rate = rate.sqrt();
+            let eta = sqrt_rate / 20.0;
+            (
+                1.0 - sqrt_rate - eta,
+                (n_rows as f64).log2() + 2.0 * codeword_len.log2()
+                    - 7.0 * (2.0 * eta).log2()
+                    - field_bits,
+            )
+        }
+    };
+
+    let log2_query_error = 1.0 + t as f64 * (1.0 - delta).log2();
+    let log2_error = log2_add(log2_add(log2_query_error, log2_gap_error), log2_code_error);
+
+    Ok(SecurityEstimate {
+        statistical_bits: statistical_bits(log2_error, assumptions),
+        computational_bits: None,
+    })
+}
+
 #[cfg(test)]
 pub(crate) struct LeafIdentityHasher;
 
@@ -202,6 +314,141 @@ impl CRHScheme for LeafIdentityHasher {
     }
 }
 
+/// The Merkle tree over the column hashes of an encoded matrix. It has the same
+/// layout, root and paths as the `MerkleTree` built by `create_merkle_tree`, but
+/// it can be serialized, so the committer can keep it instead of rebuilding it
+/// from the column hashes on every opening.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
+pub(crate) struct ColumnTree<C: Config> {
+    /// The inner nodes in level order: the children of node `k` are the nodes
+    /// `2k + 1` and `2k + 2`, and the children of the last level are the leaves.
+    non_leaf_nodes: Vec<C::InnerDigest>,
+    leaf_nodes: Vec<C::LeafDigest>,
+}
+
+impl<C: Config> ColumnTree<C> {
+    /// Hash `leaves`, padded with default leaves to the next power of two.
+    pub(crate) fn new(
+        mut leaves: Vec<C::Leaf>,
+        leaf_hash_param: &LeafParam<C>,
+        two_to_one_hash_param: &TwoToOneParam<C>,
+    ) -> Result<Self, Error>
+    where
+        C::Leaf: Sized + Clone + Default + Send,
+    {
+        let num_leaves = leaves.len().next_power_of_two();
+        if num_leaves < 2 {
+            return Err(Error::HashingError);
+        }
+        leaves.resize(num_leaves, C::Leaf::default());
+        phase_span!(
+            "merkle_tree",
+            leaves = num_leaves,
+            depth = num_leaves.trailing_zeros()
+        );
+        count!(HASH, 2 * num_leaves - 1);
+
+        let leaf_nodes: Vec<C::LeafDigest> = cfg_into_iter!(leaves)
+            .map(|leaf| {
+                C::LeafHash::evaluate(leaf_hash_param, leaf).map_err(|_| Error::HashingError)
+            })
+            .collect::<Result<_, _>>()?;
+
+        // Hash the levels from the bottom up, then lay them out from the root down.
+        let mut level: Vec<C::InnerDigest> = cfg_into_iter!(0..num_leaves / 2)
+            .map(|i| {
+                let left = C::LeafInnerDigestConverter::convert(leaf_nodes[2 * i].clone());
+                let right = C::LeafInnerDigestConverter::convert(leaf_nodes[2 * i + 1].clone());
+                C::TwoToOneHash::evaluate(two_to_one_hash_param, left?, right?)
+            })
+            .map(|node| node.map_err(|_| Error::HashingError))
+            .collect::<Result<_, _>>()?;
+        let mut levels = Vec::new();
+        while level.len() > 1 {
+            let parents = cfg_into_iter!(0..level.len() / 2)
+                .map(|i| {
+                    C::TwoToOneHash::compress(
+                        two_to_one_hash_param,
+                        &level[2 * i],
+                        &level[2 * i + 1],
+                    )
+                    .map_err(|_| Error::HashingError)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            levels.push(ark_std::mem::replace(&mut level, parents));
+        }
+        levels.push(level);
+        let non_leaf_nodes = levels.into_iter().rev().flatten().collect();
+
+        Ok(Self {
+            non_leaf_nodes,
+            leaf_nodes,
+        })
+    }
+
+    /// The root of the tree, or `None` if the tree is malformed, e.g. after
+    /// deserializing untrusted bytes.
+    pub(crate) fn root(&self) -> Option<C::InnerDigest> {
+        let num_leaves = self.leaf_nodes.len();
+        if num_leaves < 2
+            || !num_leaves.is_power_of_two()
+            || self.non_leaf_nodes.len() != num_leaves - 1
+        {
+            return None;
+        }
+        Some(self.non_leaf_nodes[0].clone())
+    }
+
+    /// The authentication path of the leaf at `index`, as returned by
+    /// `MerkleTree::generate_proof`. Assumes that `root` is not `None`.
+    pub(crate) fn generate_proof(&self, index: usize) -> Result<Path<C>, Error> {
+        let num_leaves = self.leaf_nodes.len();
+        if index >= num_leaves {
+            return Err(Error::InvalidIndex {
+                index,
+                len: num_leaves,
+            });
+        }
+
+        // Walk from the parent of the leaf up to the root, collecting siblings.
+        let mut auth_path = Vec::new();
+        let mut node = (index + num_leaves - 2) / 2;
+        while node != 0 {
+            let sibling = if node % 2 == 1 { node + 1 } else { node - 1 };
+            auth_path.push(self.non_leaf_nodes[sibling].clone());
+            node = (node - 1) / 2;
+        }
+        auth_path.reverse();
+
+        Ok(Path {
+            leaf_sibling_hash: self.leaf_nodes[index ^ 1].clone(),
+            auth_path,
+            leaf_index: index,
+        })
+    }
+}
+
+/// Checks that a matrix with `n_rows` rows and `n_cols` columns holds the
+/// evaluations of a multilinear polynomial in `num_vars` variables.
+pub(crate) fn check_hypercube_dimensions(
+    num_vars: usize,
+    n_rows: usize,
+    n_cols: usize,
+) -> Result<(), Error> {
+    if !n_rows.is_power_of_two() || !n_cols.is_power_of_two() {
+        return Err(Error::InvalidCommitment);
+    }
+    let poly_nv = (n_rows.trailing_zeros() + n_cols.trailing_zeros()) as usize;
+    if poly_nv != num_vars {
+        return Err(Error::MismatchedNumVars {
+            poly_nv,
+            point_nv: num_vars,
+        });
+    }
+    Ok(())
+}
+
 pub(crate) fn tensor_vec<F: PrimeField>(values: &[F]) -> Vec<F> {
     let one = F::one();
     let anti_values: Vec<F> = values.iter().map(|v| one - *v).collect();
@@ -235,7 +482,7 @@ pub(crate) mod tests {
         domain::general::GeneralEvaluationDomain, univariate::DensePolynomial, DenseUVPolynomial,
         Polynomial,
     };
-    use ark_std::test_rng;
+    use ark_std::{test_rng, UniformRand};
     use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
 
     #[test]
@@ -257,7 +504,7 @@ pub(crate) mod tests {
             // size of evals might be larger than deg + 1 (the min. number of evals needed to interpolate): we could still do R-S encoding on smaller evals, but the resulting polynomial will differ, so for this test to work we should pass it in full
             let m = deg + 1;
 
-            let encoded = reed_solomon(&coeffs, rho_inv);
+            let encoded = reed_solomon(&coeffs, rho_inv).unwrap();
 
             let large_domain = GeneralEvaluationDomain::<Fr>::new(m * rho_inv).unwrap();
 
@@ -268,6 +515,23 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    fn test_reed_solomon_at() {
+        let rho_inv = 4;
+        let rand_chacha = &mut ChaCha20Rng::from_rng(test_rng()).unwrap();
+        let msg: Vec<Fr> = (0..64).map(|_| Fr::rand(rand_chacha)).collect();
+        let encoded = reed_solomon(&msg, rho_inv).unwrap();
+
+        // A few entries are evaluated one by one, and many are read off the
+        // whole codeword.
+        for num_indices in [3, 200] {
+            let indices: Vec<usize> = (0..num_indices).map(|k| (7 * k) % encoded.len()).collect();
+            let expected: Vec<Fr> = indices.iter().map(|&j| encoded[j]).collect();
+            assert_eq!(reed_solomon_at(&msg, &indices, rho_inv).unwrap(), expected);
+        }
+        assert!(reed_solomon_at(&msg, &[encoded.len()], rho_inv).is_err());
+    }
+
     #[test]
     fn test_sprs_row_mul() {
         // The columns major representation of a matrix.
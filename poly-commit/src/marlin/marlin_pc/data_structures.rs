@@ -1,10 +1,14 @@
 use crate::{
-    kzg10, DenseUVPolynomial, PCCommitment, PCCommitmentState, PCCommitterKey,
+    cost::{compressed_size, kzg_cost, BatchShape, CostEstimate, CostModel, OPTION_BYTES},
+    kzg10,
+    security::{pairing_scheme_security, SecurityAssumptions, SecurityEstimate, SecurityEstimator},
+    DenseUVPolynomial, Error, PCCommitment, PCCommitmentState, PCCommitterKey,
     PCPreparedCommitment, PCPreparedVerifierKey, PCVerifierKey,
 };
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::{pairing::Pairing, AdditiveGroup};
 use ark_ff::{Field, PrimeField, ToConstraintField};
+use ark_poly::EvaluationDomain;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 #[cfg(not(feature = "std"))]
 use ark_std::vec::Vec;
@@ -42,9 +46,19 @@ pub struct CommitterKey<E: Pairing> {
     /// The maximum degree supported by the `UniversalParams` `self` was derived
     /// from.
     pub max_degree: usize,
+    /// The digest of the verifier key that was trimmed along with `self`.
+    pub verifier_key_digest: Vec<u8>,
 }
 
 impl<E: Pairing> CommitterKey<E> {
+    /// Outputs the parameters that `self` shares with its verifier key, which
+    /// are bound into the transcript of openings.
+    pub(crate) fn transcript_parameters(&self) -> kzg10::TranscriptParameters {
+        kzg10::TranscriptParameters {
+            verifier_key_digest: self.verifier_key_digest.clone(),
+        }
+    }
+
     /// Obtain powers for the underlying KZG10 construction
     pub fn powers<'a>(&'a self) -> kzg10::Powers<'a, E> {
         kzg10::Powers {
@@ -82,6 +96,22 @@ impl<E: Pairing> CommitterKey<E> {
             ck
         })
     }
+
+    /// Extends `self` with its powers in the Lagrange basis of `domain`, so
+    /// that it can commit to polynomials given by their evaluations over it.
+    pub fn with_lagrange_basis<D: EvaluationDomain<E::ScalarField>>(
+        self,
+        domain: D,
+    ) -> Result<LagrangeCommitterKey<E, D>, Error> {
+        let lagrange_powers_of_g = kzg10::LagrangePowers::new(&self.powers(), domain)?
+            .lagrange_powers_of_g
+            .into_owned();
+        Ok(LagrangeCommitterKey {
+            ck: self,
+            domain,
+            lagrange_powers_of_g,
+        })
+    }
 }
 
 impl<E: Pairing> PCCommitterKey for CommitterKey<E> {
@@ -94,6 +124,31 @@ impl<E: Pairing> PCCommitterKey for CommitterKey<E> {
     }
 }
 
+/// `LagrangeCommitterKey` is a `CommitterKey` that also carries its powers in
+/// the Lagrange basis of an evaluation domain.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct LagrangeCommitterKey<E: Pairing, D: EvaluationDomain<E::ScalarField>> {
+    /// The key used to commit to polynomials in coefficient form.
+    pub ck: CommitterKey<E>,
+    /// The domain over which the committed evaluations are given.
+    pub domain: D,
+    /// The key used to commit to polynomials given by their evaluations over
+    /// `domain`.
+    pub lagrange_powers_of_g: Vec<E::G1Affine>,
+}
+
+impl<E: Pairing, D: EvaluationDomain<E::ScalarField>> LagrangeCommitterKey<E, D> {
+    /// Obtain Lagrange powers for the underlying KZG10 construction
+    pub fn lagrange_powers<'a>(&'a self) -> kzg10::LagrangePowers<'a, E, D> {
+        kzg10::LagrangePowers {
+            domain: self.domain,
+            lagrange_powers_of_g: self.lagrange_powers_of_g.as_slice().into(),
+            powers_of_gamma_g: self.ck.powers_of_gamma_g.as_slice().into(),
+        }
+    }
+}
+
 /// `VerifierKey` is used to check evaluation proofs for a given commitment.
 #[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
 #[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
@@ -114,6 +169,12 @@ pub struct VerifierKey<E: Pairing> {
 }
 
 impl<E: Pairing> VerifierKey<E> {
+    /// Outputs the parameters that `self` shares with its committer key, which
+    /// are bound into the transcript of openings.
+    pub(crate) fn transcript_parameters(&self) -> Result<kzg10::TranscriptParameters, Error> {
+        kzg10::TranscriptParameters::new(self)
+    }
+
     /// Find the appropriate shift for the degree bound.
     pub fn get_shift_power(&self, bound: usize) -> Option<E::G1Affine> {
         self.degree_bounds_and_shift_powers.as_ref().and_then(|v| {
@@ -134,6 +195,40 @@ impl<E: Pairing> PCVerifierKey for VerifierKey<E> {
     }
 }
 
+impl<E: Pairing> CostModel for VerifierKey<E> {
+    fn estimate_cost(&self, poly_len: usize, batch: &BatchShape) -> Result<CostEstimate, Error> {
+        if poly_len > self.supported_degree + 1 {
+            return Err(Error::TooManyCoefficients {
+                num_coefficients: poly_len,
+                num_powers: self.supported_degree + 1,
+            });
+        }
+        // A commitment without a degree bound has no shifted commitment.
+        let commitment_bytes = compressed_size(&self.vk.g) + OPTION_BYTES;
+        Ok(kzg_cost::<E>(commitment_bytes, poly_len, batch, 2))
+    }
+}
+
+impl<E: Pairing> SecurityEstimator for VerifierKey<E> {
+    fn estimate_security(
+        &self,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<SecurityEstimate, Error> {
+        if poly_len > self.supported_degree + 1 {
+            return Err(Error::TooManyCoefficients {
+                num_coefficients: poly_len,
+                num_powers: self.supported_degree + 1,
+            });
+        }
+        Ok(pairing_scheme_security::<E>(
+            poly_len,
+            self.max_degree,
+            assumptions,
+        ))
+    }
+}
+
 impl<E: Pairing> ToConstraintField<<E::TargetField as Field>::BasePrimeField> for VerifierKey<E>
 where
     E::G1Affine: ToConstraintField<<E::TargetField as Field>::BasePrimeField>,
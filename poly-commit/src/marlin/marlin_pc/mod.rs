@@ -1,14 +1,23 @@
 use crate::{
-    kzg10, marlin::Marlin, BTreeMap, BTreeSet, BatchLCProof, Error, Evaluations, LabeledCommitment,
+    hidden::{
+        check_hiding, check_no_degree_bound, kzg10_commit_constant, kzg10_subtract,
+        kzg10_subtract_randomness, pair_evaluation_commitments, subtract_constant,
+        HiddenEvaluations,
+    },
+    homomorphic::{CombinationTerm, HomomorphicCommitment},
+    kzg10,
+    marlin::Marlin,
+    update::{merge_deltas, UpdatableCommitment},
+    BTreeMap, BTreeSet, BatchLCProof, Error, Evaluations, LabeledCommitment, LabeledEvaluations,
     LabeledPolynomial, LinearCombination, PCCommitmentState, PCCommitterKey, PCUniversalParams,
-    PolynomialCommitment, QuerySet, CHALLENGE_SIZE,
+    PolynomialCommitment, PolynomialLabel, QuerySet, Transcript, VerificationFailure,
 };
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
-use ark_ff::Zero;
-use ark_poly::DenseUVPolynomial;
-use ark_std::{marker::PhantomData, ops::Div, rand::RngCore};
+use ark_ff::{UniformRand, Zero};
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, Evaluations as UVEvaluations};
 #[cfg(not(feature = "std"))]
-use ark_std::{string::ToString, vec::Vec};
+use ark_std::{format, string::ToString, vec::Vec};
+use ark_std::{marker::PhantomData, ops::Div, rand::RngCore};
 
 mod data_structures;
 use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
@@ -52,6 +61,141 @@ pub(crate) fn shift_polynomial<E: Pairing, P: DenseUVPolynomial<E::ScalarField>>
     }
 }
 
+impl<E, P> MarlinKZG10<E, P>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+    P: DenseUVPolynomial<E::ScalarField, Point = E::ScalarField>,
+    for<'a, 'b> &'a P: Div<&'b P, Output = P>,
+{
+    /// The name of the scheme, bound into the transcript of its openings.
+    pub const PROTOCOL_NAME: &'static [u8] = b"ark-poly-commit/MarlinKZG10";
+
+    /// Outputs commitments to polynomials given by their evaluations over the
+    /// domain of `ck`. These are the commitments output by `commit` on the
+    /// interpolated polynomials, which cannot have degree bounds.
+    #[allow(clippy::type_complexity)]
+    pub fn commit_evaluations<'a, D: EvaluationDomain<E::ScalarField> + 'a>(
+        ck: &LagrangeCommitterKey<E, D>,
+        evaluations: impl IntoIterator<Item = &'a LabeledEvaluations<E::ScalarField, D>>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<Commitment<E>>>,
+            Vec<Randomness<E::ScalarField, P>>,
+        ),
+        Error,
+    > {
+        phase_span!("MarlinKZG10::commit_evaluations");
+        let rng = &mut crate::optional_rng::OptionalRng(rng);
+        let commit_time = start_timer!(|| "Committing to evaluations");
+        let powers = ck.lagrange_powers();
+
+        let mut commitments = Vec::new();
+        let mut states = Vec::new();
+        for e in evaluations {
+            let (comm, rand) = kzg10::KZG10::commit_evaluations(
+                &powers,
+                e.evaluations(),
+                e.hiding_bound(),
+                Some(rng),
+            )?;
+            commitments.push(LabeledCommitment::new(
+                e.label().to_string(),
+                Commitment {
+                    comm,
+                    shifted_comm: None,
+                },
+                None,
+            ));
+            states.push(Randomness {
+                rand,
+                shifted_rand: None,
+            });
+        }
+        end_timer!(commit_time);
+        Ok((commitments, states))
+    }
+
+    /// On input evaluations over the domain of `ck` and a point `point`,
+    /// outputs a proof for the same, which is checked with `check`.
+    pub fn open_evaluations<'a, D: EvaluationDomain<E::ScalarField> + 'a>(
+        ck: &LagrangeCommitterKey<E, D>,
+        labeled_evaluations: impl IntoIterator<Item = &'a LabeledEvaluations<E::ScalarField, D>>,
+        point: E::ScalarField,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Randomness<E::ScalarField, P>>,
+    ) -> Result<kzg10::Proof<E>, Error>
+    where
+        P: 'a,
+    {
+        let powers = ck.lagrange_powers();
+        let mut evals = vec![E::ScalarField::zero(); powers.size()];
+        let mut r = kzg10::Randomness::empty();
+        sponge.bind_context(Self::PROTOCOL_NAME, &ck.ck.transcript_parameters())?;
+        for (e, rand) in labeled_evaluations.into_iter().zip(states) {
+            powers.check_evaluations(e.evaluations())?;
+            let challenge_j = sponge.challenge(b"opening challenge");
+            for (acc, eval) in evals.iter_mut().zip(&e.evaluations().evals) {
+                *acc += challenge_j * eval;
+            }
+            r += (challenge_j, &rand.rand);
+        }
+        let evals = UVEvaluations::from_vec_and_domain(evals, ck.domain);
+        kzg10::KZG10::open_evaluations(&powers, &evals, point, &r)
+    }
+
+    /// On input a polynomial without degree bound and an evaluation domain
+    /// `domain`, outputs a proof for every element of `domain` with
+    /// `kzg10::KZG10::open_all`. Each proof is checked against the commitment
+    /// to the polynomial with `kzg10::KZG10::check`.
+    pub fn open_all<D: EvaluationDomain<E::ScalarField>>(
+        ck: &CommitterKey<E>,
+        labeled_polynomial: &LabeledPolynomial<E::ScalarField, P>,
+        domain: D,
+        state: &Randomness<E::ScalarField, P>,
+    ) -> Result<Vec<kzg10::Proof<E>>, Error> {
+        if let Some(degree_bound) = labeled_polynomial.degree_bound() {
+            return Err(Error::UnsupportedDegreeBound(degree_bound));
+        }
+        kzg10::KZG10::open_all(
+            &ck.powers(),
+            labeled_polynomial.polynomial(),
+            domain,
+            &state.rand,
+        )
+    }
+
+    /// Opens the evaluations over the domain of `ck` at the points of
+    /// `query_set`, with one proof per point, which are checked with
+    /// `batch_check`.
+    pub fn batch_open_evaluations<'a, D: EvaluationDomain<E::ScalarField> + 'a>(
+        ck: &LagrangeCommitterKey<E, D>,
+        labeled_evaluations: impl IntoIterator<Item = &'a LabeledEvaluations<E::ScalarField, D>>,
+        query_set: &QuerySet<E::ScalarField>,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Randomness<E::ScalarField, P>>,
+    ) -> Result<Vec<kzg10::Proof<E>>, Error>
+    where
+        P: 'a,
+    {
+        kzg10::batch_open_evaluations(
+            labeled_evaluations,
+            states,
+            query_set,
+            |evals, states, point| {
+                Self::open_evaluations(
+                    ck,
+                    evals.iter().copied(),
+                    point,
+                    sponge,
+                    states.iter().copied(),
+                )
+            },
+        )
+    }
+}
+
 impl<E, P> PolynomialCommitment<E::ScalarField, P> for MarlinKZG10<E, P>
 where
     E: Pairing,
@@ -75,6 +219,7 @@ where
         _num_vars: Option<usize>,
         rng: &mut R,
     ) -> Result<Self::UniversalParams, Self::Error> {
+        phase_span!("MarlinKZG10::setup", max_degree);
         kzg10::KZG10::setup(max_degree, false, rng).map_err(Into::into)
     }
 
@@ -84,6 +229,11 @@ where
         supported_hiding_bound: usize,
         enforced_degree_bounds: Option<&[usize]>,
     ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
+        phase_span!(
+            "MarlinKZG10::trim",
+            supported_degree,
+            supported_hiding_bound
+        );
         let max_degree = pp.max_degree();
         if supported_degree > max_degree {
             return Err(Error::TrimmingDegreeTooLarge);
@@ -152,19 +302,20 @@ where
                 (None, None)
             };
 
+        let vk = VerifierKey {
+            vk,
+            degree_bounds_and_shift_powers,
+            supported_degree,
+            max_degree,
+        };
+
         let ck = CommitterKey {
             powers,
             shifted_powers,
             powers_of_gamma_g,
             enforced_degree_bounds: enforced_degree_bounds,
             max_degree,
-        };
-
-        let vk = VerifierKey {
-            vk,
-            degree_bounds_and_shift_powers,
-            supported_degree,
-            max_degree,
+            verifier_key_digest: vk.transcript_parameters()?.verifier_key_digest,
         };
         Ok((ck, vk))
     }
@@ -184,6 +335,7 @@ where
     where
         P: 'a,
     {
+        phase_span!("MarlinKZG10::commit");
         let rng = &mut crate::optional_rng::OptionalRng(rng);
         let commit_time = start_timer!(|| "Committing to polynomials");
 
@@ -207,6 +359,7 @@ where
                 &p,
             )?;
 
+            phase_span!("commit_polynomial", label = %label, degree = polynomial.degree());
             let commit_time = start_timer!(|| format!(
                 "Polynomial {} of degree {}, degree bound {:?}, and hiding bound {:?}",
                 label,
@@ -257,6 +410,7 @@ where
         Self::CommitmentState: 'a,
         Self::Commitment: 'a,
     {
+        phase_span!("MarlinKZG10::open");
         let mut p = P::zero();
         let mut r = kzg10::Randomness::empty();
         let mut shifted_w = P::zero();
@@ -264,6 +418,7 @@ where
         let mut shifted_r_witness = P::zero();
 
         let mut enforce_degree_bound = false;
+        sponge.bind_context(Self::PROTOCOL_NAME, &ck.transcript_parameters())?;
         for (polynomial, rand) in labeled_polynomials.into_iter().zip(states) {
             let degree_bound = polynomial.degree_bound();
             assert_eq!(degree_bound.is_some(), rand.shifted_rand.is_some());
@@ -280,7 +435,7 @@ where
             )?;
 
             // compute next challenges challenge^j and challenge^{j+1}.
-            let challenge_j = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+            let challenge_j = sponge.challenge(b"opening challenge");
 
             assert_eq!(degree_bound.is_some(), rand.shifted_rand.is_some());
 
@@ -296,7 +451,7 @@ where
                         *point,
                         &shifted_rand,
                     )?;
-                let challenge_j_1 = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+                let challenge_j_1 = sponge.challenge(b"shifted opening challenge");
 
                 let shifted_witness = shift_polynomial(ck, &witness, degree_bound);
 
@@ -346,22 +501,31 @@ where
         proof: &Self::Proof,
         sponge: &mut impl CryptographicSponge,
         _rng: Option<&mut dyn RngCore>,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
+        phase_span!("MarlinKZG10::check");
         let check_time = start_timer!(|| "Checking evaluations");
         let (combined_comm, combined_value) =
             Marlin::<E, P, Self>::accumulate_commitments_and_values(
                 commitments,
                 values,
                 sponge,
+                Self::PROTOCOL_NAME,
+                &vk.transcript_parameters()?,
                 Some(vk),
             )?;
         let combined_comm = kzg10::Commitment(combined_comm.into());
         let result = kzg10::KZG10::check(&vk.vk, &combined_comm, *point, combined_value, proof)?;
         end_timer!(check_time);
-        Ok(result)
+        if !result {
+            return Err(VerificationFailure::PairingCheck {
+                point: Some(format!("{:?}", point)),
+            }
+            .into());
+        }
+        Ok(())
     }
 
     fn batch_check<'a, R: RngCore>(
@@ -372,19 +536,32 @@ where
         proof: &Self::BatchProof,
         sponge: &mut impl CryptographicSponge,
         rng: &mut R,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
+        phase_span!("MarlinKZG10::batch_check", num_proofs = proof.len());
         let (combined_comms, combined_queries, combined_evals) =
             Marlin::<E, P, Self>::combine_and_normalize(
                 commitments,
                 query_set,
                 values,
                 sponge,
+                Self::PROTOCOL_NAME,
+                &vk.transcript_parameters()?,
                 Some(vk),
             )?;
-        assert_eq!(proof.len(), combined_queries.len());
+        if proof.len() != combined_queries.len() {
+            return Err(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!(
+                    "expected {} proofs, one per point, found {}",
+                    combined_queries.len(),
+                    proof.len()
+                ),
+            }
+            .into());
+        }
         let proof_time = start_timer!(|| "Checking KZG10::Proof");
         let result = kzg10::KZG10::batch_check(
             &vk.vk,
@@ -395,7 +572,10 @@ where
             rng,
         )?;
         end_timer!(proof_time);
-        Ok(result)
+        if !result {
+            return Err(VerificationFailure::PairingCheck { point: None }.into());
+        }
+        Ok(())
     }
 
     fn open_combinations<'a>(
@@ -436,7 +616,7 @@ where
         proof: &BatchLCProof<E::ScalarField, Self::BatchProof>,
         sponge: &mut impl CryptographicSponge,
         rng: &mut R,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
@@ -468,6 +648,7 @@ where
         Self::CommitmentState: 'a,
         Self::Commitment: 'a,
     {
+        phase_span!("MarlinKZG10::batch_open", num_queries = query_set.len());
         let rng = &mut crate::optional_rng::OptionalRng(rng);
         let poly_rand_comm: BTreeMap<_, _> = labeled_polynomials
             .into_iter()
@@ -529,6 +710,223 @@ where
     }
 }
 
+impl<E, P> HomomorphicCommitment<E::ScalarField, P> for MarlinKZG10<E, P>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+    P: DenseUVPolynomial<E::ScalarField, Point = E::ScalarField>,
+    for<'a, 'b> &'a P: Div<&'b P, Output = P>,
+{
+    fn linear_combination(
+        label: PolynomialLabel,
+        terms: &[CombinationTerm<E::ScalarField, Self::Commitment, Self::CommitmentState>],
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error> {
+        Marlin::<E, P, Self>::combine_commitments_and_states(label, terms)
+    }
+}
+
+impl<E, P> UpdatableCommitment<E::ScalarField, P> for MarlinKZG10<E, P>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+    P: DenseUVPolynomial<E::ScalarField, Point = E::ScalarField>,
+    for<'a, 'b> &'a P: Div<&'b P, Output = P>,
+{
+    fn update_commitment(
+        ck: &Self::CommitterKey,
+        commitment: &LabeledCommitment<Self::Commitment>,
+        state: &Self::CommitmentState,
+        deltas: &[(usize, E::ScalarField)],
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error> {
+        let deltas = merge_deltas(deltas);
+        let label = commitment.label();
+        let degree_bound = commitment.degree_bound();
+        let update_time = start_timer!(|| format!(
+            "Updating {} coefficients of polynomial {}",
+            deltas.len(),
+            label
+        ));
+
+        if let (Some(degree_bound), Some((degree, _))) = (degree_bound, deltas.last()) {
+            if *degree > degree_bound {
+                return Err(Error::PolynomialDegreeTooLarge {
+                    poly_degree: *degree,
+                    supported_degree: degree_bound,
+                    label: label.to_string(),
+                });
+            }
+        }
+
+        // The blinding polynomial of a hiding commitment has degree
+        // `hiding_bound + 1`, and the update is blinded in the same way.
+        let hiding_bound = if state.rand.is_hiding() {
+            Some(state.rand.blinding_polynomial.degree().saturating_sub(1))
+        } else {
+            None
+        };
+        if hiding_bound.is_some() && rng.is_none() {
+            return Err(Error::MissingRng);
+        }
+        let rng = &mut crate::optional_rng::OptionalRng(rng);
+
+        let (delta_comm, delta_rand) =
+            kzg10::KZG10::commit_sparse(&ck.powers(), &deltas, hiding_bound, Some(rng))?;
+        let comm = (commitment.commitment().comm.0 + delta_comm.0).into_affine();
+
+        let (shifted_comm, shifted_rand) =
+            match (degree_bound, commitment.commitment().shifted_comm) {
+                (Some(degree_bound), Some(shifted_comm)) => {
+                    let shifted_powers = ck
+                        .shifted_powers(degree_bound)
+                        .ok_or(Error::UnsupportedDegreeBound(degree_bound))?;
+                    let (delta_comm, delta_rand) = kzg10::KZG10::commit_sparse(
+                        &shifted_powers,
+                        &deltas,
+                        hiding_bound,
+                        Some(rng),
+                    )?;
+                    let shifted_comm = (shifted_comm.0 + delta_comm.0).into_affine();
+                    (Some(kzg10::Commitment(shifted_comm)), Some(delta_rand))
+                }
+                _ => (None, None),
+            };
+
+        let comm = Commitment {
+            comm: kzg10::Commitment(comm),
+            shifted_comm,
+        };
+        let state = state.clone()
+            + &Randomness {
+                rand: delta_rand,
+                shifted_rand,
+            };
+        end_timer!(update_time);
+        Ok((
+            LabeledCommitment::new(label.to_string(), comm, degree_bound),
+            state,
+        ))
+    }
+}
+
+impl<E, P> HiddenEvaluations<E::ScalarField, P> for MarlinKZG10<E, P>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+    P: DenseUVPolynomial<E::ScalarField, Point = E::ScalarField>,
+    for<'a, 'b> &'a P: Div<&'b P, Output = P>,
+{
+    type EvaluationCommitment = kzg10::Commitment<E>;
+
+    /// Evaluations are committed to as `value G + randomness γG`, with the
+    /// generators of the verifier key.
+    fn commit_evaluation(
+        vk: &Self::VerifierKey,
+        value: E::ScalarField,
+        randomness: E::ScalarField,
+    ) -> Self::EvaluationCommitment {
+        kzg10_commit_constant(vk.vk.g, vk.vk.gamma_g, value, randomness)
+    }
+
+    /// Polynomials with degree bounds are not supported.
+    fn open_hidden<'a>(
+        ck: &Self::CommitterKey,
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<E::ScalarField, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Self::Proof,
+            Vec<(Self::EvaluationCommitment, E::ScalarField)>,
+        ),
+        Self::Error,
+    >
+    where
+        P: 'a,
+        Self::CommitmentState: 'a,
+        Self::Commitment: 'a,
+    {
+        let rng = rng.ok_or(Error::MissingRng)?;
+        let mut differences = Vec::new();
+        let mut difference_comms = Vec::new();
+        let mut difference_states = Vec::new();
+        let mut evaluations = Vec::new();
+        for ((polynomial, commitment), state) in
+            labeled_polynomials.into_iter().zip(commitments).zip(states)
+        {
+            check_no_degree_bound(polynomial.degree_bound())?;
+            check_hiding(polynomial, state.rand.is_hiding())?;
+            let value = polynomial.evaluate(point);
+            let randomness = E::ScalarField::rand(rng);
+            let evaluation_comm =
+                kzg10_commit_constant(ck.powers[0], ck.powers_of_gamma_g[0], value, randomness);
+
+            differences.push(subtract_constant(polynomial, value));
+            difference_comms.push(LabeledCommitment::new(
+                commitment.label().clone(),
+                Commitment {
+                    comm: kzg10_subtract(&commitment.commitment().comm, &evaluation_comm),
+                    shifted_comm: None,
+                },
+                None,
+            ));
+            difference_states.push(Randomness {
+                rand: kzg10_subtract_randomness(&state.rand, randomness),
+                shifted_rand: None,
+            });
+            evaluations.push((evaluation_comm, randomness));
+        }
+
+        let evaluation_comms: Vec<_> = evaluations.iter().map(|(c, _)| *c).collect();
+        sponge.absorb_serialized(b"evaluation commitments", &evaluation_comms)?;
+        let proof = Self::open(
+            ck,
+            &differences,
+            &difference_comms,
+            point,
+            sponge,
+            &difference_states,
+            Some(rng),
+        )?;
+        Ok((proof, evaluations))
+    }
+
+    fn check_hidden<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        evaluation_commitments: impl IntoIterator<Item = &'a Self::EvaluationCommitment>,
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a,
+        Self::EvaluationCommitment: 'a,
+    {
+        let pairs = pair_evaluation_commitments(commitments, evaluation_commitments)?;
+        let mut difference_comms = Vec::new();
+        for (commitment, evaluation_comm) in &pairs {
+            check_no_degree_bound(commitment.degree_bound())?;
+            difference_comms.push(LabeledCommitment::new(
+                commitment.label().clone(),
+                Commitment {
+                    comm: kzg10_subtract(&commitment.commitment().comm, evaluation_comm),
+                    shifted_comm: None,
+                },
+                None,
+            ));
+        }
+
+        let evaluation_comms: Vec<_> = pairs.iter().map(|(_, c)| **c).collect();
+        sponge.absorb_serialized(b"evaluation commitments", &evaluation_comms)?;
+        let zeros = vec![E::ScalarField::zero(); difference_comms.len()];
+        Self::check(vk, &difference_comms, point, zeros, proof, sponge, None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(non_camel_case_types)]
@@ -813,4 +1211,220 @@ mod tests {
         .expect("test failed for bls12-381");
         println!("Finished bls12-381");
     }
+
+    #[test]
+    fn cost_model_test() {
+        use crate::tests::*;
+        cost_model_test::<_, _, PC_Bls12_377, _>(
+            20,
+            None,
+            21,
+            rand_poly::<Bls12_377>,
+            rand_point::<Bls12_377>,
+            poseidon_sponge_for_test::<<Bls12_377 as Pairing>::ScalarField>,
+        )
+        .expect("test failed for bls12-377");
+        cost_model_test::<_, _, PC_Bls12_381, _>(
+            20,
+            None,
+            21,
+            rand_poly::<Bls12_381>,
+            rand_point::<Bls12_381>,
+            poseidon_sponge_for_test::<<Bls12_381 as Pairing>::ScalarField>,
+        )
+        .expect("test failed for bls12-381");
+    }
+
+    #[test]
+    fn lagrange_basis_test() {
+        use crate::tests::poseidon_sponge_for_test;
+        use crate::{
+            Evaluations, LabeledEvaluations, LabeledPolynomial, PolynomialCommitment, QuerySet,
+        };
+        use ark_ff::FftField;
+        use ark_poly::{
+            EvaluationDomain, Evaluations as UVEvaluations, GeneralEvaluationDomain, Polynomial,
+        };
+        use ark_std::test_rng;
+
+        type F = <Bls12_381 as Pairing>::ScalarField;
+
+        let rng = &mut test_rng();
+        let degree = 15;
+        let pp = PC_Bls12_381::setup(degree, None, rng).unwrap();
+        let (ck, vk) = PC_Bls12_381::trim(&pp, degree, 2, None).unwrap();
+
+        let subgroup = GeneralEvaluationDomain::<F>::new(degree + 1).unwrap();
+        let coset = subgroup.get_coset(F::GENERATOR).unwrap();
+        for domain in [subgroup, coset] {
+            let ck = ck.clone().with_lagrange_basis(domain).unwrap();
+            let polys: Vec<_> = (0..2)
+                .map(|i| {
+                    let p = UniPoly_381::rand(degree, rng);
+                    LabeledPolynomial::new(format!("p{}", i), p, None, Some(1))
+                })
+                .collect();
+            let labeled_evals: Vec<_> = polys
+                .iter()
+                .map(|p| {
+                    let evals = UVEvaluations::from_vec_and_domain(domain.fft(p.coeffs()), domain);
+                    LabeledEvaluations::new(p.label().clone(), evals, p.hiding_bound())
+                })
+                .collect();
+
+            // Without hiding, the commitments match those of the coefficients.
+            let plain_poly = LabeledPolynomial::new(
+                polys[0].label().clone(),
+                polys[0].polynomial().clone(),
+                None,
+                None,
+            );
+            let (plain_comms, _) = PC_Bls12_381::commit(&ck.ck, [&plain_poly], None).unwrap();
+            let plain_evals = LabeledEvaluations::new(
+                labeled_evals[0].label().clone(),
+                labeled_evals[0].evaluations().clone(),
+                None,
+            );
+            let (plain_eval_comms, _) =
+                PC_Bls12_381::commit_evaluations(&ck, [&plain_evals], None).unwrap();
+            assert_eq!(
+                plain_comms[0].commitment(),
+                plain_eval_comms[0].commitment()
+            );
+
+            let (comms, states) =
+                PC_Bls12_381::commit_evaluations(&ck, &labeled_evals, Some(rng)).unwrap();
+
+            // Open at a random point and at a point of the domain.
+            let points = [
+                ("rand".to_string(), F::rand(rng)),
+                ("domain".to_string(), domain.element(3)),
+            ];
+            let mut query_set = QuerySet::new();
+            let mut values = Evaluations::new();
+            for p in &polys {
+                for (point_label, point) in &points {
+                    query_set.insert((p.label().clone(), (point_label.clone(), *point)));
+                    values.insert((p.label().clone(), *point), p.polynomial().evaluate(point));
+                }
+            }
+
+            let sponge = poseidon_sponge_for_test::<F>();
+            let proof = PC_Bls12_381::batch_open_evaluations(
+                &ck,
+                &labeled_evals,
+                &query_set,
+                &mut (sponge.clone()),
+                &states,
+            )
+            .unwrap();
+            PC_Bls12_381::batch_check(
+                &vk,
+                &comms,
+                &query_set,
+                &values,
+                &proof,
+                &mut (sponge.clone()),
+                rng,
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn open_all_test() {
+        use crate::{kzg10::KZG10, LabeledPolynomial, PolynomialCommitment};
+        use ark_poly::{EvaluationDomain, GeneralEvaluationDomain, Polynomial};
+        use ark_std::test_rng;
+
+        type F = <Bls12_381 as Pairing>::ScalarField;
+
+        let rng = &mut test_rng();
+        let degree = 10;
+        let pp = PC_Bls12_381::setup(degree, None, rng).unwrap();
+        let (ck, vk) = PC_Bls12_381::trim(&pp, degree, 1, None).unwrap();
+        let p = LabeledPolynomial::new(
+            "p".to_string(),
+            UniPoly_381::rand(degree, rng),
+            None,
+            Some(1),
+        );
+        let (comms, states) = PC_Bls12_381::commit(&ck, [&p], Some(rng)).unwrap();
+
+        let domain = GeneralEvaluationDomain::<F>::new(16).unwrap();
+        let proofs = PC_Bls12_381::open_all(&ck, &p, domain, &states[0]).unwrap();
+        for (point, proof) in domain.elements().zip(&proofs) {
+            let value = p.polynomial().evaluate(&point);
+            assert!(KZG10::<Bls12_381, UniPoly_381>::check(
+                &vk.vk,
+                &comms[0].commitment().comm,
+                point,
+                value,
+                proof,
+            )
+            .unwrap());
+        }
+    }
+
+    #[test]
+    fn update_commitment_test() {
+        use crate::tests::*;
+        for degree_bound in [None, Some(10)] {
+            update_commitment_test::<_, _, PC_Bls12_381, _>(
+                20,
+                None,
+                degree_bound,
+                true,
+                rand_poly::<Bls12_381>,
+                rand_point::<Bls12_381>,
+                apply_coefficient_deltas,
+                poseidon_sponge_for_test::<<Bls12_381 as Pairing>::ScalarField>,
+            )
+            .expect("test failed for bls12-381");
+        }
+    }
+
+    #[test]
+    fn homomorphic_commitment_test() {
+        use crate::tests::*;
+        for degree_bound in [None, Some(10)] {
+            homomorphic_commitment_test::<_, _, PC_Bls12_381, _>(
+                20,
+                None,
+                degree_bound,
+                rand_poly::<Bls12_381>,
+                rand_point::<Bls12_381>,
+                poseidon_sponge_for_test::<<Bls12_381 as Pairing>::ScalarField>,
+            )
+            .expect("test failed for bls12-381");
+        }
+    }
+
+    #[test]
+    fn hidden_evaluations_test() {
+        use crate::tests::*;
+        hidden_evaluations_test::<_, _, PC_Bls12_381, _>(
+            20,
+            None,
+            None,
+            true,
+            rand_poly::<Bls12_381>,
+            rand_point::<Bls12_381>,
+            poseidon_sponge_for_test::<<Bls12_381 as Pairing>::ScalarField>,
+        )
+        .expect("test failed for bls12-381");
+    }
+
+    #[test]
+    fn deterministic_hiding_test() {
+        use crate::tests::*;
+        deterministic_hiding_test::<_, _, PC_Bls12_381, _>(
+            20,
+            None,
+            rand_poly::<Bls12_381>,
+            rand_point::<Bls12_381>,
+            poseidon_sponge_for_test::<<Bls12_381 as Pairing>::ScalarField>,
+        )
+        .expect("test failed for bls12-381");
+    }
 }
@@ -1,5 +1,15 @@
 use crate::{
-    BTreeMap, PCCommitmentState, PCCommitterKey, PCPreparedVerifierKey, PCUniversalParams,
+    cost::{
+        compressed_size, repeat, vec_bytes, BatchShape, CostEstimate, CostModel, ProverCost,
+        VerifierCost, OPTION_BYTES,
+    },
+    kzg10,
+    security::{
+        field_bits, pairing_bits, statistical_bits, SecurityAssumptions, SecurityEstimate,
+        SecurityEstimator,
+    },
+    utils::deserialize_vec,
+    BTreeMap, Error, PCCommitmentState, PCCommitterKey, PCPreparedVerifierKey, PCUniversalParams,
     PCVerifierKey,
 };
 use ark_ec::pairing::Pairing;
@@ -15,6 +25,8 @@ use ark_std::{
     ops::{Add, AddAssign, Index},
     rand::RngCore,
 };
+#[cfg(all(not(feature = "std"), target_arch = "aarch64"))]
+use num_traits::Float;
 
 /// `UniversalParams` are the universal parameters for the MarlinPST13 scheme.
 #[derive(Derivative)]
@@ -177,6 +189,23 @@ where
     /// The maximum degree supported by the `UniversalParams` `self` was derived
     /// from.
     pub max_degree: usize,
+    /// The digest of the verifier key that was trimmed along with `self`.
+    pub verifier_key_digest: Vec<u8>,
+}
+
+impl<E, P> CommitterKey<E, P>
+where
+    E: Pairing,
+    P: DenseMVPolynomial<E::ScalarField>,
+    P::Point: Index<usize, Output = E::ScalarField>,
+{
+    /// Outputs the parameters that `self` shares with its verifier key, which
+    /// are bound into the transcript of openings.
+    pub(crate) fn transcript_parameters(&self) -> kzg10::TranscriptParameters {
+        kzg10::TranscriptParameters {
+            verifier_key_digest: self.verifier_key_digest.clone(),
+        }
+    }
 }
 
 impl<E, P> PCCommitterKey for CommitterKey<E, P>
@@ -303,6 +332,14 @@ impl<E: Pairing> CanonicalDeserialize for VerifierKey<E> {
     }
 }
 
+impl<E: Pairing> VerifierKey<E> {
+    /// Outputs the parameters that `self` shares with its committer key, which
+    /// are bound into the transcript of openings.
+    pub(crate) fn transcript_parameters(&self) -> Result<kzg10::TranscriptParameters, Error> {
+        kzg10::TranscriptParameters::new(self)
+    }
+}
+
 impl<E: Pairing> PCVerifierKey for VerifierKey<E> {
     fn max_degree(&self) -> usize {
         self.max_degree
@@ -313,6 +350,52 @@ impl<E: Pairing> PCVerifierKey for VerifierKey<E> {
     }
 }
 
+impl<E: Pairing> CostModel for VerifierKey<E> {
+    /// `poly_len` is the number of terms of the polynomials.
+    fn estimate_cost(&self, poly_len: usize, batch: &BatchShape) -> Result<CostEstimate, Error> {
+        let g1_bytes = compressed_size(&self.g);
+        let proof_bytes = vec_bytes(self.num_vars, g1_bytes) + OPTION_BYTES;
+        Ok(CostEstimate {
+            commitment_bytes: g1_bytes + OPTION_BYTES,
+            proof_bytes: vec_bytes(batch.num_points, proof_bytes),
+            prover: ProverCost {
+                // A commitment to each polynomial, and to the `num_vars`
+                // witness polynomials of the combination opened at each point.
+                msms: [
+                    repeat(poly_len, batch.num_polys),
+                    repeat(poly_len, batch.num_points * self.num_vars),
+                ]
+                .concat(),
+                encodings: Vec::new(),
+                hashes: 0,
+            },
+            verifier: VerifierCost {
+                pairings: self.num_vars + 1,
+                msms: vec![batch.num_points * (batch.num_polys + 2 * self.num_vars + 2) + 2],
+                encodings: Vec::new(),
+                hashes: 0,
+            },
+        })
+    }
+}
+
+impl<E: Pairing> SecurityEstimator for VerifierKey<E> {
+    /// The statistical error only depends on the number of variables and the
+    /// supported degree of the polynomials, so `poly_len` is ignored.
+    fn estimate_security(
+        &self,
+        _poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<SecurityEstimate, Error> {
+        let total_degree = (self.num_vars * self.supported_degree).max(1);
+        let log2_error = (total_degree as f64).log2() - field_bits::<E::ScalarField>();
+        Ok(SecurityEstimate {
+            statistical_bits: statistical_bits(log2_error, assumptions),
+            computational_bits: Some(pairing_bits::<E>(self.max_degree)),
+        })
+    }
+}
+
 /// Nothing to do to prepare this verifier key (for now).
 pub type PreparedVerifierKey<E> = VerifierKey<E>;
 
@@ -447,7 +530,7 @@ where
 }
 
 /// `Proof` is an evaluation proof that is output by `KZG10::open`.
-#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Derivative, CanonicalSerialize)]
 #[derivative(
     Default(bound = ""),
     Hash(bound = ""),
@@ -463,3 +546,26 @@ pub struct Proof<E: Pairing> {
     /// the evaluation proof was produced.
     pub random_v: Option<E::ScalarField>,
 }
+
+impl<E: Pairing> Valid for Proof<E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.w.check()?;
+        self.random_v.check()
+    }
+}
+
+impl<E: Pairing> CanonicalDeserialize for Proof<E> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let w = deserialize_vec(&mut reader, compress)?;
+        let random_v = Option::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+        let result = Self { w, random_v };
+        if let Validate::Yes = validate {
+            result.check()?;
+        }
+        Ok(result)
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{
+    homomorphic::{CombinationTerm, HomomorphicCommitment},
     kzg10,
     marlin::{marlin_pc, Marlin},
     BatchLCProof, Error, Evaluations, LabeledCommitment, LabeledPolynomial, LinearCombination,
-    PCCommitmentState, PCUniversalParams, PolynomialCommitment, QuerySet, CHALLENGE_SIZE,
+    PCCommitmentState, PCUniversalParams, PolynomialCommitment, PolynomialLabel, QuerySet,
+    Transcript, VerificationFailure,
 };
 use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
 use ark_ec::{
@@ -12,9 +14,9 @@ use ark_ec::{
 };
 use ark_ff::{One, PrimeField, UniformRand, Zero};
 use ark_poly::{multivariate::Term, DenseMVPolynomial};
-use ark_std::{marker::PhantomData, ops::Index, ops::Mul, rand::RngCore};
 #[cfg(not(feature = "std"))]
-use ark_std::{string::ToString, vec::Vec};
+use ark_std::{format, string::ToString, vec::Vec};
+use ark_std::{marker::PhantomData, ops::Index, ops::Mul, rand::RngCore};
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
@@ -36,6 +38,25 @@ pub struct MarlinPST13<E: Pairing, P: DenseMVPolynomial<E::ScalarField>> {
 }
 
 impl<E: Pairing, P: DenseMVPolynomial<E::ScalarField>> MarlinPST13<E, P> {
+    /// The name of the scheme, bound into the transcript of its openings.
+    pub const PROTOCOL_NAME: &'static [u8] = b"ark-poly-commit/MarlinPST13";
+
+    /// Checks that `proof` has one witness per variable of `vk`.
+    fn check_proof_shape(vk: &VerifierKey<E>, proof: &Proof<E>) -> Result<(), Error> {
+        if proof.w.len() != vk.num_vars {
+            return Err(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!(
+                    "expected {} witnesses, found {}",
+                    vk.num_vars,
+                    proof.w.len()
+                ),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
     /// Given some point `z`, compute the quotients `w_i(X)` s.t
     ///
     /// `p(X) - p(z) = (X_1-z_1)*w_1(X) + (X_2-z_2)*w_2(X) + ... + (X_l-z_l)*w_l(X)`
@@ -159,6 +180,7 @@ where
         num_vars: Option<usize>,
         rng: &mut R,
     ) -> Result<UniversalParams<E, P>, Error> {
+        phase_span!("MarlinPST13::setup", max_degree, num_vars = ?num_vars);
         let num_vars = num_vars.ok_or(Error::InvalidNumberOfVariables)?;
         if num_vars < 1 {
             return Err(Error::InvalidNumberOfVariables);
@@ -270,6 +292,7 @@ where
         _supported_hiding_bound: usize,
         _enforced_degree_bounds: Option<&[usize]>,
     ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
+        phase_span!("MarlinPST13::trim", supported_degree);
         let max_degree = pp.max_degree();
         if supported_degree > max_degree {
             return Err(Error::TrimmingDegreeTooLarge);
@@ -294,15 +317,6 @@ where
             .collect();
         end_timer!(ck_time);
 
-        let ck = CommitterKey {
-            powers_of_g,
-            gamma_g: pp.gamma_g,
-            powers_of_gamma_g,
-            num_vars: pp.num_vars,
-            supported_degree,
-            max_degree,
-        };
-
         let vk = VerifierKey {
             g: pp.powers_of_g[&P::Term::new(vec![])],
             gamma_g: pp.gamma_g,
@@ -314,6 +328,16 @@ where
             supported_degree,
             max_degree,
         };
+
+        let ck = CommitterKey {
+            powers_of_g,
+            gamma_g: pp.gamma_g,
+            powers_of_gamma_g,
+            num_vars: pp.num_vars,
+            supported_degree,
+            max_degree,
+            verifier_key_digest: vk.transcript_parameters()?.verifier_key_digest,
+        };
         Ok((ck, vk))
     }
 
@@ -332,6 +356,7 @@ where
     where
         P: 'a,
     {
+        phase_span!("MarlinPST13::commit");
         let rng = &mut crate::optional_rng::OptionalRng(rng);
         let commit_time = start_timer!(|| "Committing to polynomials");
         let mut commitments = Vec::new();
@@ -342,6 +367,7 @@ where
             let polynomial: &P = p.polynomial();
             Self::check_degrees_and_bounds(ck.supported_degree, &p)?;
 
+            phase_span!("commit_polynomial", label = %label, degree = polynomial.degree());
             let commit_time = start_timer!(|| {
                 format!(
                     "Polynomial {} with degree {} and hiding bound {:?}",
@@ -361,6 +387,7 @@ where
 
             let msm_time = start_timer!(|| "MSM to compute commitment to plaintext poly");
             let mut commitment = <E::G1 as VariableBaseMSM>::msm_bigint(&powers_of_g, &plain_ints);
+            count!(MSM, plain_ints.len());
             end_timer!(msm_time);
 
             // Sample random polynomial
@@ -398,6 +425,7 @@ where
             let random_commitment =
                 <E::G1 as VariableBaseMSM>::msm_bigint(&powers_of_gamma_g, &random_ints)
                     .into_affine();
+            count!(MSM, random_ints.len());
             end_timer!(msm_time);
 
             // Mask commitment with random poly
@@ -431,14 +459,16 @@ where
         Self::CommitmentState: 'a,
         Self::Commitment: 'a,
     {
+        phase_span!("MarlinPST13::open");
         // Compute random linear combinations of committed polynomials and randomness
         let mut p = P::zero();
         let mut r = Randomness::empty();
+        sponge.bind_context(Self::PROTOCOL_NAME, &ck.transcript_parameters())?;
         for (polynomial, state) in labeled_polynomials.into_iter().zip(states) {
             Self::check_degrees_and_bounds(ck.supported_degree, &polynomial)?;
 
             // compute challenge^j and challenge^{j+1}.
-            let challenge_j = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+            let challenge_j = sponge.challenge(b"opening challenge");
 
             p += (challenge_j, polynomial.polynomial());
             r += (challenge_j, state);
@@ -465,6 +495,7 @@ where
                 // Convert coefficients to BigInt
                 let witness_ints = Self::convert_to_bigints(&w);
                 // Compute MSM
+                count!(MSM, witness_ints.len());
                 <E::G1 as VariableBaseMSM>::msm_bigint(&powers_of_g, &witness_ints)
             })
             .collect::<Vec<_>>();
@@ -500,6 +531,12 @@ where
                         &hiding_witness_ints,
                     );
                 });
+            // The MSMs are counted here, in the span of the opening, rather
+            // than in the threads which compute them.
+            #[cfg(feature = "tracing")]
+            for hiding_witness in &hiding_witnesses {
+                count!(MSM, hiding_witness.terms().len());
+            }
             end_timer!(witness_comm_time);
             Some(r.blinding_polynomial.evaluate(point))
         } else {
@@ -522,10 +559,11 @@ where
         proof: &Self::Proof,
         sponge: &mut impl CryptographicSponge,
         _rng: Option<&mut dyn RngCore>,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
+        phase_span!("MarlinPST13::check");
         let check_time = start_timer!(|| "Checking evaluations");
         // Accumulate commitments and values
         let (combined_comm, combined_value) =
@@ -533,8 +571,11 @@ where
                 commitments,
                 values,
                 sponge,
+                Self::PROTOCOL_NAME,
+                &vk.transcript_parameters()?,
                 None,
             )?;
+        Self::check_proof_shape(vk, proof)?;
         // Compute both sides of the pairing equation
         let mut inner = combined_comm.into().into_group() - &vk.g.mul(combined_value);
         if let Some(random_v) = proof.random_v {
@@ -553,9 +594,16 @@ where
                 })
                 .unzip();
         let rhs = E::multi_pairing(rhs_product_g1, rhs_product_g2);
+        count!(PAIRING, proof.w.len() + 1);
         end_timer!(check_time);
 
-        Ok(lhs == rhs)
+        if lhs != rhs {
+            return Err(VerificationFailure::PairingCheck {
+                point: Some(format!("{:?}", point)),
+            }
+            .into());
+        }
+        Ok(())
     }
 
     fn batch_check<'a, R: RngCore>(
@@ -566,18 +614,35 @@ where
         proof: &Self::BatchProof,
         sponge: &mut impl CryptographicSponge,
         rng: &mut R,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
+        phase_span!("MarlinPST13::batch_check", num_proofs = proof.len());
         let (combined_comms, combined_queries, combined_evals) =
             Marlin::<E, P, Self>::combine_and_normalize(
                 commitments,
                 query_set,
                 values,
                 sponge,
+                Self::PROTOCOL_NAME,
+                &vk.transcript_parameters()?,
                 None,
             )?;
+        if proof.len() != combined_queries.len() {
+            return Err(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!(
+                    "expected {} proofs, one per point, found {}",
+                    combined_queries.len(),
+                    proof.len()
+                ),
+            }
+            .into());
+        }
+        for proof in proof {
+            Self::check_proof_shape(vk, proof)?;
+        }
         let check_time =
             start_timer!(|| format!("Checking {} evaluation proofs", combined_comms.len()));
         let g = vk.g.into_group();
@@ -630,10 +695,14 @@ where
         end_timer!(to_affine_time);
 
         let pairing_time = start_timer!(|| "Performing product of pairings");
+        count!(PAIRING, p1.len());
         let result = E::multi_pairing(p1, p2).0.is_one();
         end_timer!(pairing_time);
         end_timer!(check_time);
-        Ok(result)
+        if !result {
+            return Err(VerificationFailure::PairingCheck { point: None }.into());
+        }
+        Ok(())
     }
 
     fn open_combinations<'a>(
@@ -674,7 +743,7 @@ where
         proof: &BatchLCProof<E::ScalarField, Self::BatchProof>,
         sponge: &mut impl CryptographicSponge,
         rng: &mut R,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
@@ -691,6 +760,21 @@ where
     }
 }
 
+impl<E, P> HomomorphicCommitment<E::ScalarField, P> for MarlinPST13<E, P>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+    P: DenseMVPolynomial<E::ScalarField> + Sync,
+    P::Point: Index<usize, Output = E::ScalarField>,
+{
+    fn linear_combination(
+        label: PolynomialLabel,
+        terms: &[CombinationTerm<E::ScalarField, Self::Commitment, Self::CommitmentState>],
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error> {
+        Marlin::<E, P, Self>::combine_commitments_and_states(label, terms)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(non_camel_case_types)]
@@ -842,4 +926,41 @@ mod tests {
         .expect("test failed for bls12-381");
         println!("Finished bls12-381");
     }
+
+    #[test]
+    fn cost_model_test() {
+        use crate::tests::*;
+        cost_model_test::<_, _, PC_Bls12_377, _>(
+            3,
+            Some(5),
+            1 << 5,
+            rand_poly::<Bls12_377>,
+            rand_point::<Bls12_377>,
+            poseidon_sponge_for_test::<<Bls12_377 as Pairing>::ScalarField>,
+        )
+        .expect("test failed for bls12-377");
+        cost_model_test::<_, _, PC_Bls12_381, _>(
+            3,
+            Some(5),
+            1 << 5,
+            rand_poly::<Bls12_381>,
+            rand_point::<Bls12_381>,
+            poseidon_sponge_for_test::<<Bls12_381 as Pairing>::ScalarField>,
+        )
+        .expect("test failed for bls12-381");
+    }
+
+    #[test]
+    fn homomorphic_commitment_test() {
+        use crate::tests::*;
+        homomorphic_commitment_test::<_, _, PC_Bls12_381, _>(
+            3,
+            Some(5),
+            None,
+            rand_poly::<Bls12_381>,
+            rand_point::<Bls12_381>,
+            poseidon_sponge_for_test::<<Bls12_381 as Pairing>::ScalarField>,
+        )
+        .expect("test failed for bls12-381");
+    }
 }
@@ -1,7 +1,8 @@
 use crate::{
+    homomorphic::{common_degree_bound, CombinationTerm},
     kzg10, BTreeMap, BTreeSet, BatchLCProof, Debug, Error, Evaluations, LabeledCommitment,
     LabeledPolynomial, LinearCombination, PCCommitmentState, Polynomial, PolynomialCommitment,
-    QuerySet, RngCore, CHALLENGE_SIZE,
+    QuerySet, RngCore, Transcript, VerificationFailure,
 };
 use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
@@ -69,6 +70,32 @@ where
         (combined_comm, combined_shifted_comm)
     }
 
+    /// Outputs the linear combination of the commitments and states of
+    /// `terms`, as in `HomomorphicCommitment::linear_combination`.
+    fn combine_commitments_and_states<S>(
+        label: String,
+        terms: &[CombinationTerm<E::ScalarField, marlin_pc::Commitment<E>, S>],
+    ) -> Result<(LabeledCommitment<marlin_pc::Commitment<E>>, S), Error>
+    where
+        S: PCCommitmentState + for<'a> AddAssign<(E::ScalarField, &'a S)>,
+    {
+        let degree_bound = common_degree_bound(&label, terms)?;
+        let (comm, shifted_comm) = Self::combine_commitments(
+            terms
+                .iter()
+                .map(|(coeff, comm, _)| (*coeff, comm.commitment())),
+        );
+        let mut state = S::empty();
+        for (coeff, _, s) in terms {
+            state += (*coeff, *s);
+        }
+        let comm = marlin_pc::Commitment {
+            comm: kzg10::Commitment(comm.into_affine()),
+            shifted_comm: shifted_comm.map(|c| kzg10::Commitment(c.into_affine())),
+        };
+        Ok((LabeledCommitment::new(label, comm, degree_bound), state))
+    }
+
     /// Normalize a list of commitments
     fn normalize_commitments<'a>(
         commitments: Vec<(E::G1, Option<E::G1>)>,
@@ -106,35 +133,42 @@ where
             .collect()
     }
 
-    /// Accumulate `commitments` and `values` according to the challenges produces by `challenge_gen`.
+    /// Accumulate `commitments` and `values` according to the challenges
+    /// squeezed from `sponge`, after binding it to `protocol` and `parameters`.
     fn accumulate_commitments_and_values<'a>(
         commitments: impl IntoIterator<Item = &'a LabeledCommitment<marlin_pc::Commitment<E>>>,
         values: impl IntoIterator<Item = E::ScalarField>,
         sponge: &mut impl CryptographicSponge,
+        protocol: &[u8],
+        parameters: &kzg10::TranscriptParameters,
         vk: Option<&marlin_pc::VerifierKey<E>>,
     ) -> Result<(E::G1, E::ScalarField), Error> {
         let acc_time = start_timer!(|| "Accumulating commitments and values");
+        sponge.bind_context(protocol, parameters)?;
         let mut combined_comm = E::G1::zero();
         let mut combined_value = E::ScalarField::zero();
         for (labeled_commitment, value) in commitments.into_iter().zip(values) {
             let degree_bound = labeled_commitment.degree_bound();
             let commitment = labeled_commitment.commitment();
-            assert_eq!(degree_bound.is_some(), commitment.shifted_comm.is_some());
+            if degree_bound.is_some() != commitment.shifted_comm.is_some() {
+                return Err(VerificationFailure::DegreeBound {
+                    label: labeled_commitment.label().clone(),
+                }
+                .into());
+            }
 
-            let challenge_i = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+            let challenge_i = sponge.challenge(b"opening challenge");
 
             combined_comm += &commitment.comm.0.mul(challenge_i);
             combined_value += &(value * &challenge_i);
 
             if let Some(degree_bound) = degree_bound {
-                let challenge_i_1: E::ScalarField =
-                    sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+                let challenge_i_1: E::ScalarField = sponge.challenge(b"shifted opening challenge");
 
                 let shifted_comm = commitment.shifted_comm.as_ref().unwrap().0.into_group();
 
                 let shift_power = vk
-                    .unwrap()
-                    .get_shift_power(degree_bound)
+                    .and_then(|vk| vk.get_shift_power(degree_bound))
                     .ok_or(Error::UnsupportedDegreeBound(degree_bound))?;
 
                 let mut adjusted_comm = shifted_comm - &shift_power.mul(value);
@@ -154,6 +188,8 @@ where
         query_set: &QuerySet<D>,
         evaluations: &Evaluations<D, E::ScalarField>,
         sponge: &mut impl CryptographicSponge,
+        protocol: &[u8],
+        parameters: &kzg10::TranscriptParameters,
         vk: Option<&marlin_pc::VerifierKey<E>>,
     ) -> Result<(Vec<kzg10::Commitment<E>>, Vec<D>, Vec<E::ScalarField>), Error>
     where
@@ -181,12 +217,6 @@ where
                 let commitment = commitments.get(label).ok_or(Error::MissingPolynomial {
                     label: label.to_string(),
                 })?;
-                let degree_bound = commitment.degree_bound();
-                assert_eq!(
-                    degree_bound.is_some(),
-                    commitment.commitment().shifted_comm.is_some()
-                );
-
                 let v_i = evaluations.get(&(label.clone(), point.clone())).ok_or(
                     Error::MissingEvaluation {
                         label: label.to_string(),
@@ -201,6 +231,8 @@ where
                 comms_to_combine,
                 values_to_combine,
                 sponge,
+                protocol,
+                parameters,
                 vk,
             )?;
             end_timer!(lc_time);
@@ -325,7 +357,7 @@ where
         proof: &BatchLCProof<E::ScalarField, PC::BatchProof>,
         sponge: &mut impl CryptographicSponge,
         rng: &mut R,
-    ) -> Result<bool, Error>
+    ) -> Result<(), Error>
     where
         R: RngCore,
         P: Polynomial<E::ScalarField, Point = D>,
@@ -369,14 +401,13 @@ where
                         label: label.to_string(),
                     })?;
 
-                    if num_polys == 1 && cur_comm.degree_bound().is_some() {
-                        assert!(
-                            coeff.is_one(),
-                            "Coefficient must be one for degree-bounded equations"
-                        );
+                    // Degree-bounded commitments may only appear alone and
+                    // with a unit coefficient.
+                    if cur_comm.degree_bound().is_some() {
+                        if num_polys != 1 || !coeff.is_one() {
+                            return Err(Error::EquationHasDegreeBounds(lc_label));
+                        }
                         degree_bound = cur_comm.degree_bound();
-                    } else if cur_comm.degree_bound().is_some() {
-                        return Err(Error::EquationHasDegreeBounds(lc_label));
                     }
                     coeffs_and_comms.push((*coeff, cur_comm.commitment()));
                 }
@@ -1,7 +1,17 @@
+use crate::{
+    security::{
+        field_bits, pairing_bits, statistical_bits, SecurityAssumptions, SecurityEstimate,
+        SecurityEstimator,
+    },
+    srs_file::{FileBackedSrs, SrsFile, SrsFileBuilder},
+    Error,
+};
 use ark_ec::pairing::Pairing;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 #[cfg(not(feature = "std"))]
 use ark_std::vec::Vec;
+#[cfg(all(not(feature = "std"), target_arch = "aarch64"))]
+use num_traits::Float;
 #[allow(type_alias_bounds)]
 /// Evaluations over {0,1}^n for G1
 pub type EvaluationHyperCubeOnG1<E: Pairing> = Vec<E::G1Affine>;
@@ -26,6 +36,67 @@ pub struct UniversalParams<E: Pairing> {
     pub g_mask: Vec<E::G1Affine>,
 }
 
+/// The file holds the `num_vars` hypercubes of `powers_of_g`, followed by
+/// those of `powers_of_h`, as `2 * num_vars` sections.
+impl<E: Pairing> FileBackedSrs for UniversalParams<E> {
+    const TAG: [u8; 8] = *b"MLPC\0\0\0\0";
+
+    fn build<'a>(&'a self, builder: &mut SrsFileBuilder<'a>) -> Result<(), Error> {
+        if self.powers_of_g.len() != self.num_vars || self.powers_of_h.len() != self.num_vars {
+            return Err(Error::InvalidNumberOfVariables);
+        }
+        builder.metadata(&(self.num_vars, self.g, self.h, self.g_mask.clone()))?;
+        for powers in &self.powers_of_g {
+            builder.section(powers);
+        }
+        for powers in &self.powers_of_h {
+            builder.section(powers);
+        }
+        Ok(())
+    }
+
+    fn validate(file: &SrsFile<'_, Self>) -> Result<(), Error> {
+        let (num_vars, ..) = file.metadata::<Metadata<E>>()?;
+        for i in 0..num_vars {
+            file.validate_section::<E::G1Affine>(i)?;
+            file.validate_section::<E::G2Affine>(num_vars + i)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the parameters for `supported_num_vars` variables, which are
+    /// those of the last `supported_num_vars` hypercubes.
+    fn load(file: &SrsFile<'_, Self>, supported_num_vars: usize) -> Result<Self, Error> {
+        let (num_vars, g, h, g_mask) = file.metadata::<Metadata<E>>()?;
+        if supported_num_vars > num_vars || file.num_sections() != 2 * num_vars {
+            return Err(Error::InvalidNumberOfVariables);
+        }
+        let to_reduce = num_vars - supported_num_vars;
+        let range = |i| 0..file.section_len(i).unwrap_or(0);
+        Ok(Self {
+            num_vars: supported_num_vars,
+            powers_of_g: (to_reduce..num_vars)
+                .map(|i| file.read_section(i, range(i)))
+                .collect::<Result<_, _>>()?,
+            powers_of_h: (to_reduce..num_vars)
+                .map(|i| file.read_section(num_vars + i, range(num_vars + i)))
+                .collect::<Result<_, _>>()?,
+            g,
+            h,
+            g_mask: g_mask.get(to_reduce..).unwrap_or_default().to_vec(),
+        })
+    }
+}
+
+/// The metadata of a file holding `UniversalParams`: `num_vars`, `g`, `h`
+/// and `g_mask`.
+type Metadata<E> = (
+    usize,
+    <E as Pairing>::G1Affine,
+    <E as Pairing>::G2Affine,
+    Vec<<E as Pairing>::G1Affine>,
+);
+
 /// Public Parameter used by prover
 #[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug)]
 pub struct CommitterKey<E: Pairing> {
@@ -54,6 +125,28 @@ pub struct VerifierKey<E: Pairing> {
     pub g_mask_random: Vec<E::G1Affine>,
 }
 
+impl<E: Pairing> SecurityEstimator for VerifierKey<E> {
+    fn estimate_security(
+        &self,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<SecurityEstimate, Error> {
+        if self.nv >= usize::BITS as usize || poly_len > 1 << self.nv {
+            return Err(Error::TooManyCoefficients {
+                num_coefficients: poly_len,
+                num_powers: 1 << self.nv.min(usize::BITS as usize - 1),
+            });
+        }
+        // Evaluating a multilinear polynomial at a random point.
+        let log2_error = (self.nv.max(1) as f64).log2() - field_bits::<E::ScalarField>();
+        Ok(SecurityEstimate {
+            statistical_bits: statistical_bits(log2_error, assumptions),
+            // The SRS only contains products of distinct secrets.
+            computational_bits: Some(pairing_bits::<E>(1)),
+        })
+    }
+}
+
 #[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug)]
 /// commitment
 pub struct Commitment<E: Pairing> {
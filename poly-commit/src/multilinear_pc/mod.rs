@@ -1,5 +1,8 @@
-use crate::multilinear_pc::data_structures::{
-    Commitment, CommitterKey, Proof, UniversalParams, VerifierKey,
+use crate::{
+    multilinear_pc::data_structures::{
+        Commitment, CommitterKey, Proof, UniversalParams, VerifierKey,
+    },
+    Error,
 };
 use ark_ec::{
     pairing::Pairing,
@@ -8,12 +11,12 @@ use ark_ec::{
 };
 use ark_ff::{Field, One, PrimeField, Zero};
 use ark_poly::{DenseMultilinearExtension, MultilinearExtension};
-#[cfg(not(feature = "std"))]
-use ark_std::vec::Vec;
 use ark_std::{
     collections::LinkedList, iter::FromIterator, marker::PhantomData, ops::Mul, rand::RngCore,
     UniformRand,
 };
+#[cfg(not(feature = "std"))]
+use ark_std::{string::ToString, vec::Vec};
 
 /// data structures used by multilinear extension commitment scheme
 pub mod data_structures;
@@ -26,6 +29,7 @@ pub struct MultilinearPC<E: Pairing> {
 impl<E: Pairing> MultilinearPC<E> {
     /// setup
     pub fn setup<R: RngCore>(num_vars: usize, rng: &mut R) -> UniversalParams<E> {
+        phase_span!("MultilinearPC::setup", num_vars);
         assert!(num_vars > 0, "constant polynomial not supported");
         let g = E::G1::rand(rng);
         let h = E::G2::rand(rng);
@@ -92,6 +96,7 @@ impl<E: Pairing> MultilinearPC<E> {
         params: &UniversalParams<E>,
         supported_num_vars: usize,
     ) -> (CommitterKey<E>, VerifierKey<E>) {
+        phase_span!("MultilinearPC::trim", supported_num_vars);
         assert!(supported_num_vars <= params.num_vars);
         let to_reduce = params.num_vars - supported_num_vars;
         let ck = CommitterKey {
@@ -115,6 +120,7 @@ impl<E: Pairing> MultilinearPC<E> {
         ck: &CommitterKey<E>,
         polynomial: &impl MultilinearExtension<E::ScalarField>,
     ) -> Commitment<E> {
+        phase_span!("MultilinearPC::commit", num_vars = polynomial.num_vars());
         let nv = polynomial.num_vars();
         let scalars: Vec<_> = polynomial
             .to_evaluations()
@@ -124,15 +130,53 @@ impl<E: Pairing> MultilinearPC<E> {
         let g_product =
             <E::G1 as VariableBaseMSM>::msm_bigint(&ck.powers_of_g[0], scalars.as_slice())
                 .into_affine();
+        count!(MSM, scalars.len());
         Commitment { nv, g_product }
     }
 
+    /// Outputs the commitment to `Σ coeff_i p_i` given the terms
+    /// `(coeff_i, commitment_i)`, where the polynomials `p_i` have the same
+    /// number of variables. Commitments of this scheme are not hiding, so
+    /// there is no state to combine.
+    pub fn linear_combination(
+        terms: &[(E::ScalarField, &Commitment<E>)],
+    ) -> Result<Commitment<E>, Error> {
+        let (_, first) = terms.first().ok_or_else(|| {
+            Error::IncorrectInputLength("a linear combination needs at least one term".to_string())
+        })?;
+        let nv = first.nv;
+        if terms.iter().any(|(_, c)| c.nv != nv) {
+            return Err(Error::InvalidNumberOfVariables);
+        }
+        let g_product = terms
+            .iter()
+            .map(|(coeff, c)| c.g_product.mul(coeff))
+            .sum::<E::G1>()
+            .into_affine();
+        Ok(Commitment { nv, g_product })
+    }
+
+    /// Outputs the commitment to the sum of two committed polynomials.
+    pub fn add(left: &Commitment<E>, right: &Commitment<E>) -> Result<Commitment<E>, Error> {
+        let one = E::ScalarField::one();
+        Self::linear_combination(&[(one, left), (one, right)])
+    }
+
+    /// Outputs the commitment to a committed polynomial multiplied by `coeff`.
+    pub fn scale(coeff: E::ScalarField, commitment: &Commitment<E>) -> Commitment<E> {
+        Commitment {
+            nv: commitment.nv,
+            g_product: commitment.g_product.mul(coeff).into_affine(),
+        }
+    }
+
     /// On input a polynomial `p` and a point `point`, outputs a proof for the same.
     pub fn open(
         ck: &CommitterKey<E>,
         polynomial: &impl MultilinearExtension<E::ScalarField>,
         point: &[E::ScalarField],
     ) -> Proof<E> {
+        phase_span!("MultilinearPC::open");
         assert_eq!(polynomial.num_vars(), ck.nv, "Invalid size of polynomial");
         let nv = polynomial.num_vars();
         let mut r: Vec<Vec<E::ScalarField>> = (0..nv + 1).map(|_| Vec::new()).collect();
@@ -161,6 +205,7 @@ impl<E: Pairing> MultilinearPC<E> {
 
             let pi_h =
                 <E::G2 as VariableBaseMSM>::msm_bigint(&ck.powers_of_h[i], &scalars).into_affine(); // no need to move outside and partition
+            count!(MSM, scalars.len());
             proofs.push(pi_h);
         }
 
@@ -176,6 +221,7 @@ impl<E: Pairing> MultilinearPC<E> {
         value: E::ScalarField,
         proof: &Proof<E>,
     ) -> bool {
+        phase_span!("MultilinearPC::check");
         let left = E::pairing(commitment.g_product.into_group() - &vk.g.mul(value), vk.h);
 
         let g_mul = vk.g.into_group().batch_mul(point);
@@ -195,6 +241,7 @@ impl<E: Pairing> MultilinearPC<E> {
             .map(|x| E::G2Prepared::from(*x))
             .collect();
 
+        count!(PAIRING, 1 + pairing_lefts.len());
         let right = E::multi_pairing(pairing_lefts, pairing_rights);
         left == right
     }
@@ -311,4 +358,44 @@ mod tests {
         let result = MultilinearPC::check(&vk, &com, &point, value + &(1u16.into()), &proof);
         assert!(!result);
     }
+
+    #[test]
+    fn linear_combination_of_commitments_verifies() {
+        let mut rng = test_rng();
+        let nv = 6;
+        let uni_params = MultilinearPC::setup(nv, &mut rng);
+        let (ck, vk) = MultilinearPC::<E>::trim(&uni_params, nv);
+        let p = DenseMultilinearExtension::rand(nv, &mut rng);
+        let q = DenseMultilinearExtension::rand(nv, &mut rng);
+        let (a, b) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+        let combined = DenseMultilinearExtension::from_evaluations_vec(
+            nv,
+            p.iter().zip(q.iter()).map(|(x, y)| a * x + b * y).collect(),
+        );
+
+        let (com_p, com_q) = (
+            MultilinearPC::commit(&ck, &p),
+            MultilinearPC::commit(&ck, &q),
+        );
+        let com = MultilinearPC::linear_combination(&[(a, &com_p), (b, &com_q)]).unwrap();
+        let sum = MultilinearPC::add(
+            &MultilinearPC::scale(a, &com_p),
+            &MultilinearPC::scale(b, &com_q),
+        )
+        .unwrap();
+        assert_eq!(com.g_product, sum.g_product);
+        assert_eq!(
+            com.g_product,
+            MultilinearPC::commit(&ck, &combined).g_product
+        );
+
+        let point: Vec<_> = (0..nv).map(|_| Fr::rand(&mut rng)).collect();
+        let proof = MultilinearPC::open(&ck, &combined, &point);
+        let value = combined.evaluate(&point);
+        assert!(MultilinearPC::check(&vk, &com, &point, value, &proof));
+
+        let (small_ck, _) = MultilinearPC::<E>::trim(&uni_params, 2);
+        let small = MultilinearPC::commit(&small_ck, &DenseMultilinearExtension::rand(2, &mut rng));
+        assert!(MultilinearPC::linear_combination(&[(a, &com_p), (b, &small)]).is_err());
+    }
 }
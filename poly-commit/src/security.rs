@@ -0,0 +1,212 @@
+use crate::{Error, CHALLENGE_SIZE};
+
+use ark_crypto_primitives::sponge::FieldElementSize;
+use ark_ec::pairing::Pairing;
+use ark_ff::{Field, PrimeField};
+use core::f64::consts::LN_2;
+#[cfg(all(not(feature = "std"), target_arch = "aarch64"))]
+use num_traits::Float;
+
+/// The radius up to which the proximity of a committed codeword to the code
+/// is analysed, for schemes based on linear codes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DecodingRegime {
+    /// Up to half the minimum distance of the code. Proximity gaps hold for
+    /// any linear code in this regime, which is the one `sec_param` targets.
+    #[default]
+    Unique,
+    /// Up to (almost) the Johnson bound `1 - sqrt(rho)`. Proximity gaps in this
+    /// regime are only known for Reed-Solomon codes [[BCI+20]][bci], and come with
+    /// a much larger error term, so fewer queries are needed only over large fields.
+    ///
+    /// [bci]: https://eprint.iacr.org/2020/654
+    List,
+}
+
+/// The capabilities of the adversary against which the security of a scheme
+/// is estimated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SecurityAssumptions {
+    /// The regime in which the proximity tests of linear-code-based schemes are
+    /// analysed. Ignored by the other schemes.
+    pub regime: DecodingRegime,
+    /// Base-2 logarithm of the number of queries to the Fiat-Shamir sponge
+    /// that the adversary can make. Each query allows the adversary to resample
+    /// the verifier's challenges, so every bit here costs one bit of
+    /// statistical security. Use `0` for interactive verifiers.
+    pub fs_queries_log2: u32,
+}
+
+impl SecurityAssumptions {
+    /// Create assumptions with the given regime and Fiat-Shamir query budget.
+    pub fn new(regime: DecodingRegime, fs_queries_log2: u32) -> Self {
+        Self {
+            regime,
+            fs_queries_log2,
+        }
+    }
+}
+
+/// An estimate of the concrete security of a scheme, in bits.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SecurityEstimate {
+    /// Security against a prover that only relies on the randomness of the
+    /// challenges: `-log2` of the soundness error, after accounting for the
+    /// Fiat-Shamir queries of the adversary.
+    pub statistical_bits: f64,
+    /// Cost of breaking the computational assumptions of the scheme with the
+    /// best known generic attacks, or `None` if the scheme does not rely on any
+    /// assumption other than those of its hash functions, which are not
+    /// accounted for.
+    pub computational_bits: Option<f64>,
+}
+
+impl SecurityEstimate {
+    /// The overall security level, i.e. the minimum of the statistical and
+    /// computational security.
+    pub fn bits(&self) -> f64 {
+        match self.computational_bits {
+            Some(computational_bits) => self.statistical_bits.min(computational_bits),
+            None => self.statistical_bits,
+        }
+    }
+}
+
+/// Public parameters or keys that can report the concrete security level of
+/// the scheme they are used with.
+pub trait SecurityEstimator {
+    /// Estimate the security of committing to and opening polynomials with
+    /// `poly_len` coefficients (or evaluations over the Boolean hypercube, for
+    /// multilinear polynomials) under `assumptions`.
+    ///
+    /// Returns an error if the parameters do not support such polynomials, or
+    /// if the requested analysis does not apply to the scheme.
+    fn estimate_security(
+        &self,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<SecurityEstimate, Error>;
+}
+
+/// A lower bound on `log2(|F|)`.
+pub(crate) fn field_bits<F: PrimeField>() -> f64 {
+    (F::MODULUS_BIT_SIZE - 1) as f64
+}
+
+/// A lower bound on `log2` of the number of challenges of size [`CHALLENGE_SIZE`]
+/// in `F`, which are used to batch openings and in the rounds of the inner
+/// product argument.
+pub(crate) fn challenge_bits<F: PrimeField>() -> f64 {
+    match CHALLENGE_SIZE {
+        FieldElementSize::Full => field_bits::<F>(),
+        FieldElementSize::Truncated(bits) => field_bits::<F>().min(bits as f64),
+    }
+}
+
+/// The statistical security of a non-interactive argument obtained by applying
+/// Fiat-Shamir to a protocol with soundness error `2^log2_error`.
+pub(crate) fn statistical_bits(log2_error: f64, assumptions: &SecurityAssumptions) -> f64 {
+    -log2_error - assumptions.fs_queries_log2 as f64
+}
+
+/// Computes `log2(2^a + 2^b)` without leaving the logarithmic domain.
+pub(crate) fn log2_add(a: f64, b: f64) -> f64 {
+    let (hi, lo) = if a > b { (a, b) } else { (b, a) };
+    hi + (1.0 + (lo - hi).exp2()).log2()
+}
+
+/// The cost of computing discrete logarithms with Pollard's rho in a group
+/// whose order is the modulus of `F`.
+pub(crate) fn dlog_bits<F: PrimeField>() -> f64 {
+    field_bits::<F>() / 2.0
+}
+
+/// The cost of breaking the assumptions of a pairing-based scheme whose
+/// structured reference string contains the powers up to `max_power` of the
+/// same secret.
+///
+/// This is the minimum of:
+/// - Cheon's attack on the q-strong Diffie-Hellman assumption, which saves a
+///   factor `sqrt(max_power)` over generic discrete logarithms in the source
+///   groups, and
+/// - the (extended tower) number field sieve in the target group. Its cost is
+///   the asymptotic `L[1/3, (48/9)^(1/3)]` calibrated against the estimates of
+///   [[Guillevic20]][guillevic] for BN254 (~100 bits) and BLS12-381 (~124 bits).
+///
+/// [guillevic]: https://eprint.iacr.org/2019/1371
+pub(crate) fn pairing_bits<E: Pairing>(max_power: usize) -> f64 {
+    const EXTNFS_CONSTANT: f64 = 1.747;
+    const EXTNFS_CALIBRATION: f64 = 25.0;
+
+    let cheon = dlog_bits::<E::ScalarField>() - (max_power.max(1) as f64).log2() / 2.0;
+
+    let target_bits = E::TargetField::extension_degree() as f64
+        * <E::TargetField as Field>::BasePrimeField::MODULUS_BIT_SIZE as f64;
+    let ln_q = target_bits * LN_2;
+    let extnfs =
+        EXTNFS_CONSTANT * ln_q.cbrt() * ln_q.ln().powf(2.0 / 3.0) / LN_2 - EXTNFS_CALIBRATION;
+
+    cheon.min(extnfs)
+}
+
+/// The security of a pairing-based scheme committing to polynomials with
+/// `poly_len` coefficients, with an SRS of powers up to `max_power`. The
+/// statistical error comes from evaluating polynomials with at most `poly_len`
+/// coefficients at random points, and from batching them with short challenges.
+pub(crate) fn pairing_scheme_security<E: Pairing>(
+    poly_len: usize,
+    max_power: usize,
+    assumptions: &SecurityAssumptions,
+) -> SecurityEstimate {
+    let log2_error = log2_add(
+        (poly_len.max(1) as f64).log2() - field_bits::<E::ScalarField>(),
+        -challenge_bits::<E::ScalarField>(),
+    );
+    SecurityEstimate {
+        statistical_bits: statistical_bits(log2_error, assumptions),
+        computational_bits: Some(pairing_bits::<E>(max_power)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Bls12_381, Fr};
+
+    #[test]
+    fn test_log2_add() {
+        assert!((log2_add(3.0, 3.0) - 4.0).abs() < 1e-9);
+        assert!((log2_add(-200.0, 5.0) - 5.0).abs() < 1e-9);
+        assert_eq!(log2_add(f64::NEG_INFINITY, 1.0), 1.0);
+    }
+
+    #[test]
+    fn test_pairing_bits() {
+        // The target group is the bottleneck for BLS12-381 with small SRSs...
+        let bits = pairing_bits::<Bls12_381>(1);
+        assert!(bits > 120.0 && bits < 128.0, "{}", bits);
+        // ...while Cheon's attack is for large SRSs.
+        let bits = pairing_bits::<Bls12_381>(1 << 30);
+        assert!((bits - (dlog_bits::<Fr>() - 15.0)).abs() < 1e-9, "{}", bits);
+    }
+
+    #[test]
+    fn test_fiat_shamir_queries() {
+        let interactive =
+            pairing_scheme_security::<Bls12_381>(1 << 10, 1 << 10, &Default::default());
+        let non_interactive = pairing_scheme_security::<Bls12_381>(
+            1 << 10,
+            1 << 10,
+            &SecurityAssumptions::new(DecodingRegime::Unique, 64),
+        );
+        assert_eq!(
+            interactive.statistical_bits - non_interactive.statistical_bits,
+            64.0
+        );
+        assert_eq!(
+            interactive.computational_bits,
+            non_interactive.computational_bits
+        );
+        assert!(interactive.bits() <= interactive.statistical_bits);
+    }
+}
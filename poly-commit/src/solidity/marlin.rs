@@ -0,0 +1,211 @@
+use super::*;
+use crate::marlin::marlin_pc::{Commitment, MarlinKZG10, VerifierKey};
+use ark_bn254::Bn254;
+use ark_poly::DenseUVPolynomial;
+use ark_std::ops::Div;
+
+impl<P> SolidityVerifier<P> for MarlinKZG10<Bn254, P>
+where
+    P: DenseUVPolynomial<Fr, Point = Fr>,
+    for<'a, 'b> &'a P: Div<&'b P, Output = P>,
+{
+    fn solidity_verifier(
+        vk: &VerifierKey<Bn254>,
+        shape: &QueryShape<Fr>,
+    ) -> Result<VerifierContract, Error> {
+        let layout = Layout::new(shape, true);
+        let mut generator =
+            Generator::new(&layout, Self::PROTOCOL_NAME, &vk.transcript_parameters()?)?;
+        generator.require_scalar_inputs();
+
+        // The challenges of each linear combination, squeezed as in
+        // `Marlin::accumulate_commitments_and_values`.
+        generator
+            .program
+            .comment("Opening challenges, squeezed after absorbing the statement.");
+        let queries = shape.queries_by_point();
+        let mut absorb = layout.statement.clone();
+        let mut challenges = BTreeMap::new();
+        for (point_label, lc_labels) in &queries {
+            generator.bind_context();
+            for &lc_label in lc_labels {
+                let challenge = generator.challenge(absorb, OPENING_CHALLENGE);
+                absorb = 0..0;
+                let lc = &shape.linear_combinations[lc_label];
+                let shift_challenge = shape
+                    .degree_bound(lc)
+                    .map(|_| generator.challenge(0..0, SHIFTED_OPENING_CHALLENGE));
+                challenges.insert((*point_label, lc_label), (challenge, shift_challenge));
+            }
+        }
+        let randomizers = generator.randomizers(queries.len());
+
+        // The scalars of the check of `KZG10::batch_check`, where the
+        // commitments to the linear combinations are expanded.
+        generator.program.comment("Scalars of the check.");
+        let mut scalars = BTreeMap::new();
+        let mut shifted_scalars = BTreeMap::new();
+        let mut shift_power_scalars = BTreeMap::new();
+        for (label, degree_bound) in &shape.polynomials {
+            scalars.insert(label, generator.scalar_variable());
+            if let Some(degree_bound) = degree_bound {
+                shifted_scalars.insert(label, generator.scalar_variable());
+                if !shift_power_scalars.contains_key(degree_bound) {
+                    let variable = generator.scalar_variable();
+                    shift_power_scalars.insert(*degree_bound, variable);
+                }
+            }
+        }
+        let g_scalar = generator.scalar_variable();
+        let gamma_g_scalar = generator.scalar_variable();
+        for ((point_label, lc_labels), randomizer) in queries.iter().zip(&randomizers) {
+            let factors = |first: Vec<Expr>| -> Vec<Expr> {
+                first.into_iter().chain(randomizer.clone()).collect()
+            };
+            for &lc_label in lc_labels {
+                let (challenge, shift_challenge) = challenges[&(*point_label, lc_label)];
+                let lc = &shape.linear_combinations[lc_label];
+                let value = generator.evaluation(lc, point_label);
+                generator.accumulate(g_scalar, factors(vec![mload(challenge), value.clone()]));
+                for (coeff, term) in lc.iter() {
+                    if let LCTerm::PolyLabel(label) = term {
+                        let mut first = vec![mload(challenge)];
+                        if !coeff.is_one() {
+                            first.push(scalar(coeff));
+                        }
+                        generator.accumulate(scalars[label], factors(first));
+                    }
+                }
+                if let (Some(degree_bound), Some(shift_challenge)) =
+                    (shape.degree_bound(lc), shift_challenge)
+                {
+                    let label = lc
+                        .iter()
+                        .find_map(|(_, term)| match term {
+                            LCTerm::PolyLabel(label) => Some(label),
+                            LCTerm::One => None,
+                        })
+                        .unwrap();
+                    generator.accumulate(
+                        shifted_scalars[label],
+                        factors(vec![mload(shift_challenge)]),
+                    );
+                    generator.accumulate(
+                        shift_power_scalars[&degree_bound],
+                        factors(vec![mload(shift_challenge), Generator::negate(value)]),
+                    );
+                }
+            }
+            let proof = layout.proofs[*point_label];
+            generator.accumulate(gamma_g_scalar, factors(vec![generator.input(proof + 2)]));
+        }
+
+        generator.program.comment(
+            "The sum of the commitments, of the proofs at their points and of the evaluations.",
+        );
+        let mut terms = Vec::new();
+        for (label, scalar) in &scalars {
+            terms.push((Base::Input(layout.commitments[*label]), mload(*scalar)));
+        }
+        for (label, scalar) in &shifted_scalars {
+            terms.push((
+                Base::Input(layout.shifted_commitments[*label]),
+                mload(*scalar),
+            ));
+        }
+        for (degree_bound, scalar) in &shift_power_scalars {
+            let shift_power = vk
+                .get_shift_power(*degree_bound)
+                .ok_or(Error::UnsupportedDegreeBound(*degree_bound))?;
+            terms.push((Base::Constant(shift_power), mload(*scalar)));
+        }
+        for ((point_label, _), randomizer) in queries.iter().zip(&randomizers) {
+            let point = generator.input(layout.points[*point_label]);
+            terms.push((
+                Base::Input(layout.proofs[*point_label]),
+                Generator::product(vec![point].into_iter().chain(randomizer.clone()).collect()),
+            ));
+        }
+        terms.push((Base::Constant(vk.vk.g), Generator::negate(mload(g_scalar))));
+        terms.push((
+            Base::Constant(vk.vk.gamma_g),
+            Generator::negate(mload(gamma_g_scalar)),
+        ));
+        let combination = generator.msm(terms);
+
+        generator
+            .program
+            .comment("The opposite of the sum of the proofs.");
+        let terms = queries
+            .keys()
+            .zip(&randomizers)
+            .map(|(point_label, randomizer)| {
+                let randomizer = randomizer.clone().unwrap_or_else(|| lit(1));
+                (
+                    Base::Input(layout.proofs[*point_label]),
+                    Generator::negate(randomizer),
+                )
+            })
+            .collect();
+        let proofs = generator.msm(terms);
+
+        generator.program.comment("The pairing check.");
+        generator.pairing(vec![(combination, vk.vk.h), (proofs, vk.vk.beta_h)]);
+        Ok(generator.finish(
+            "MarlinKZG10Verifier",
+            &format!(
+                "@title Verifier of `MarlinKZG10` proofs on BN254\n\
+                 @notice Checks the openings of {} linear combinations of {} polynomials at {} points,\n\
+                 as `MarlinKZG10::check_combinations`.",
+                shape.linear_combinations.len(),
+                shape.polynomials.len(),
+                queries.len()
+            ),
+        ))
+    }
+
+    fn encode_input<'a>(
+        shape: &QueryShape<Fr>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Commitment<Bn254>>>,
+        query_set: &QuerySet<Fr>,
+        evaluations: &Evaluations<Fr, Fr>,
+        proof: &BatchLCProof<Fr, Self::BatchProof>,
+    ) -> Result<Vec<u8>, Error> {
+        let words = encode(
+            shape,
+            true,
+            commitments,
+            commitment_words,
+            query_set,
+            evaluations,
+            Some(&proof.proof),
+        )?;
+        Ok(words.concat())
+    }
+
+    fn transcript<'a>(
+        shape: &QueryShape<Fr>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Commitment<Bn254>>>,
+        query_set: &QuerySet<Fr>,
+        evaluations: &Evaluations<Fr, Fr>,
+    ) -> Result<KeccakSponge, Error> {
+        let statement = encode(
+            shape,
+            true,
+            commitments,
+            commitment_words,
+            query_set,
+            evaluations,
+            None,
+        )?;
+        Ok(statement_transcript(&statement))
+    }
+}
+
+fn commitment_words(commitment: &Commitment<Bn254>) -> Result<Vec<Word>, Error> {
+    let mut words = g1_words(&commitment.comm.0).to_vec();
+    if let Some(shifted_comm) = &commitment.shifted_comm {
+        words.extend_from_slice(&g1_words(&shifted_comm.0));
+    }
+    Ok(words)
+}
@@ -0,0 +1,789 @@
+use crate::{
+    transcript::{challenge_header, context_messages},
+    BTreeMap, BTreeSet, BatchLCProof, Error, Evaluations, LCTerm, LabeledCommitment,
+    LinearCombination, PCCommitment, Polynomial, PolynomialCommitment, QuerySet,
+};
+use ark_bn254::{Fq, Fr, G1Affine, G2Affine};
+use ark_ff::{BigInteger, One, PrimeField, Zero};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{convert::TryInto, ops::Range};
+#[cfg(not(feature = "std"))]
+use ark_std::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+mod program;
+use program::*;
+
+mod transcript;
+pub use transcript::*;
+
+mod marlin;
+mod sonic;
+
+#[cfg(test)]
+mod tests;
+
+/// The shape of the statements checked by a generated verifier: the labels
+/// and degree bounds of the committed polynomials, the linear combinations of
+/// them that are opened, and the labels of the queries to these combinations.
+///
+/// The shape is fixed in the contract, together with the verifier key, while
+/// the commitments, the points, the evaluations and the proof are inputs of
+/// the contract.
+#[derive(Clone, Debug)]
+pub struct QueryShape<F: PrimeField> {
+    polynomials: Vec<(String, Option<usize>)>,
+    linear_combinations: BTreeMap<String, LinearCombination<F>>,
+    queries: BTreeSet<(String, String)>,
+}
+
+impl<F: PrimeField> QueryShape<F> {
+    /// Creates the shape of the openings of `linear_combinations` of the
+    /// polynomials with the given labels and degree bounds, where each query
+    /// is a pair of the label of a linear combination and the label of a
+    /// point. The commitments in the input of the verifier are in the order of
+    /// `polynomials`.
+    pub fn new(
+        polynomials: impl IntoIterator<Item = (String, Option<usize>)>,
+        linear_combinations: impl IntoIterator<Item = LinearCombination<F>>,
+        queries: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, Error> {
+        let polynomials: Vec<_> = polynomials.into_iter().collect();
+        let degree_bounds: BTreeMap<_, _> = polynomials.iter().cloned().collect();
+        if degree_bounds.len() != polynomials.len() {
+            return Err(Error::InvalidParameters(
+                "the labels of the polynomials are not distinct".to_string(),
+            ));
+        }
+        let linear_combinations: BTreeMap<_, _> = linear_combinations
+            .into_iter()
+            .map(|lc| (lc.label().clone(), lc))
+            .collect();
+        for (lc_label, lc) in &linear_combinations {
+            for (coeff, term) in lc.iter() {
+                if let LCTerm::PolyLabel(label) = term {
+                    let degree_bound =
+                        degree_bounds.get(label).ok_or(Error::MissingPolynomial {
+                            label: label.clone(),
+                        })?;
+                    if degree_bound.is_some() && (lc.len() != 1 || !coeff.is_one()) {
+                        return Err(Error::EquationHasDegreeBounds(lc_label.clone()));
+                    }
+                }
+            }
+        }
+        let queries: BTreeSet<_> = queries.into_iter().collect();
+        for (lc_label, _) in &queries {
+            if !linear_combinations.contains_key(lc_label) {
+                return Err(Error::InvalidParameters(format!(
+                    "no linear combination with label {}",
+                    lc_label
+                )));
+            }
+        }
+        Ok(Self {
+            polynomials,
+            linear_combinations,
+            queries,
+        })
+    }
+
+    /// Creates a shape from the labels of the queries in `query_set`.
+    pub fn from_query_set<T>(
+        polynomials: impl IntoIterator<Item = (String, Option<usize>)>,
+        linear_combinations: impl IntoIterator<Item = LinearCombination<F>>,
+        query_set: &QuerySet<T>,
+    ) -> Result<Self, Error> {
+        Self::new(
+            polynomials,
+            linear_combinations,
+            query_set
+                .iter()
+                .map(|(lc_label, (point_label, _))| (lc_label.clone(), point_label.clone())),
+        )
+    }
+
+    /// Outputs the labels of the linear combinations queried at each point,
+    /// ordered by the labels of the points as in `batch_check`.
+    fn queries_by_point(&self) -> BTreeMap<&String, Vec<&String>> {
+        let mut queries = BTreeMap::new();
+        for (lc_label, point_label) in &self.queries {
+            queries
+                .entry(point_label)
+                .or_insert_with(Vec::new)
+                .push(lc_label);
+        }
+        for lc_labels in queries.values_mut() {
+            lc_labels.sort();
+        }
+        queries
+    }
+
+    /// Outputs the degree bound of a linear combination, which is the degree
+    /// bound of its polynomial if it has one.
+    fn degree_bound(&self, lc: &LinearCombination<F>) -> Option<usize> {
+        lc.iter().find_map(|(_, term)| match term {
+            LCTerm::PolyLabel(label) => self.polynomial_degree_bound(label),
+            LCTerm::One => None,
+        })
+    }
+
+    fn polynomial_degree_bound(&self, label: &str) -> Option<usize> {
+        self.polynomials
+            .iter()
+            .find(|(l, _)| l == label)
+            .and_then(|(_, degree_bound)| *degree_bound)
+    }
+}
+
+/// A verifier contract for the EVM.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifierContract {
+    /// The name of the contract.
+    pub name: String,
+    /// The Solidity source of the contract.
+    pub source: String,
+    /// The runtime bytecode of the contract, assembled from the same program as
+    /// the inline assembly of `source`.
+    pub runtime_bytecode: Vec<u8>,
+    /// The number of 32-byte words in the input of the contract.
+    pub num_inputs: usize,
+}
+
+impl VerifierContract {
+    /// The signature of the verification function of the contract.
+    pub fn signature(&self) -> String {
+        format!("verify(uint256[{}])", self.num_inputs)
+    }
+
+    /// The selector of the verification function of the contract.
+    pub fn selector(&self) -> [u8; 4] {
+        keccak256(self.signature().as_bytes())[..4]
+            .try_into()
+            .unwrap()
+    }
+
+    /// Outputs the calldata of a call to the verification function, given the
+    /// input of the contract.
+    pub fn calldata(&self, input: &[u8]) -> Vec<u8> {
+        let mut calldata = self.selector().to_vec();
+        calldata.extend_from_slice(input);
+        calldata
+    }
+
+    /// The creation bytecode that deploys `runtime_bytecode`.
+    pub fn creation_bytecode(&self) -> Vec<u8> {
+        creation_bytecode(&self.runtime_bytecode)
+    }
+}
+
+/// Polynomial commitment schemes over BN254 whose `check_combinations` can be
+/// run on the EVM, with the `ecAdd`, `ecMul` and `ecPairing` precompiles.
+///
+/// The generated contract replays the opening challenges of the proofs with a
+/// [`KeccakSponge`] that has absorbed the statement, as output by
+/// [`transcript`](SolidityVerifier::transcript), so the proofs it checks must
+/// be computed with that sponge. The randomizers that `batch_check` samples
+/// from its random number generator are instead squeezed from the sponge
+/// after the proof. The linear combinations of the shape are fixed in the
+/// contract, and must not depend on the inputs of the contract.
+pub trait SolidityVerifier<P: Polynomial<Fr, Point = Fr>>: PolynomialCommitment<Fr, P> {
+    /// Outputs a contract that checks proofs of the openings in `shape` under
+    /// `vk`.
+    fn solidity_verifier(
+        vk: &Self::VerifierKey,
+        shape: &QueryShape<Fr>,
+    ) -> Result<VerifierContract, Error>;
+
+    /// Outputs the input of the contract generated for `shape`, for the given
+    /// commitments, query set, evaluations and proof.
+    fn encode_input<'a>(
+        shape: &QueryShape<Fr>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        query_set: &QuerySet<Fr>,
+        evaluations: &Evaluations<Fr, Fr>,
+        proof: &BatchLCProof<Fr, Self::BatchProof>,
+    ) -> Result<Vec<u8>, Error>
+    where
+        Self::Commitment: 'a;
+
+    /// Outputs the sponge of the contract generated for `shape`, after it
+    /// has absorbed the commitments, the points and the evaluations.
+    fn transcript<'a>(
+        shape: &QueryShape<Fr>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        query_set: &QuerySet<Fr>,
+        evaluations: &Evaluations<Fr, Fr>,
+    ) -> Result<KeccakSponge, Error>
+    where
+        Self::Commitment: 'a;
+}
+
+/// The position of each value in the input of a contract, in words.
+#[derive(Clone, Debug)]
+struct Layout {
+    commitments: BTreeMap<String, usize>,
+    shifted_commitments: BTreeMap<String, usize>,
+    points: BTreeMap<String, usize>,
+    evaluations: BTreeMap<(String, String), usize>,
+    proofs: BTreeMap<String, usize>,
+    /// The statement, which is absorbed before the opening challenges are
+    /// squeezed, is made of the commitments, the points and the evaluations.
+    statement: Range<usize>,
+    /// The points and the evaluations, at the end of the statement.
+    scalars: Range<usize>,
+    proof: Range<usize>,
+    descriptions: Vec<(Range<usize>, String)>,
+}
+
+impl Layout {
+    /// Lays out the commitments, with the shifted commitments of
+    /// degree-bounded polynomials if `shifted`, then the points and the
+    /// evaluations, ordered by their labels, and finally the proofs, each
+    /// with its `random_v`, in the order of the points.
+    fn new<F: PrimeField>(shape: &QueryShape<F>, shifted: bool) -> Self {
+        fn end(descriptions: &[(Range<usize>, String)]) -> usize {
+            descriptions.last().map_or(0, |(range, _)| range.end)
+        }
+        let mut descriptions = Vec::new();
+        let mut take = |len: usize, description: String| {
+            let start = end(&descriptions);
+            descriptions.push((start..start + len, description));
+            start
+        };
+
+        let mut commitments = BTreeMap::new();
+        let mut shifted_commitments = BTreeMap::new();
+        for (label, degree_bound) in &shape.polynomials {
+            let index = take(2, format!("commitment to `{}`", label));
+            commitments.insert(label.clone(), index);
+            if let (true, Some(degree_bound)) = (shifted, degree_bound) {
+                let index = take(
+                    2,
+                    format!(
+                        "shifted commitment to `{}`, of degree bound {}",
+                        label, degree_bound
+                    ),
+                );
+                shifted_commitments.insert(label.clone(), index);
+            }
+        }
+        let queries = shape.queries_by_point();
+        let mut points = BTreeMap::new();
+        for point_label in queries.keys() {
+            let index = take(1, format!("point `{}`", point_label));
+            points.insert(point_label.to_string(), index);
+        }
+        let mut evaluations = BTreeMap::new();
+        for (lc_label, point_label) in &shape.queries {
+            let index = take(
+                1,
+                format!("evaluation of `{}` at `{}`", lc_label, point_label),
+            );
+            evaluations.insert((lc_label.clone(), point_label.clone()), index);
+        }
+        let mut proofs = BTreeMap::new();
+        for point_label in queries.keys() {
+            let index = take(
+                3,
+                format!("proof at `{}`, with its `random_v`", point_label),
+            );
+            proofs.insert(point_label.to_string(), index);
+        }
+
+        let scalars_start = 2 * (commitments.len() + shifted_commitments.len());
+        let statement_len = scalars_start + points.len() + evaluations.len();
+        Self {
+            commitments,
+            shifted_commitments,
+            points,
+            evaluations,
+            proofs,
+            statement: 0..statement_len,
+            scalars: scalars_start..statement_len,
+            proof: statement_len..end(&descriptions),
+            descriptions,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.proof.end
+    }
+}
+
+/// Outputs the literal `value`, a scalar.
+fn scalar(value: &Fr) -> Expr {
+    Expr::Lit(fr_word(value))
+}
+
+fn fr_word(x: &Fr) -> Word {
+    x.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+fn fq_word(x: &Fq) -> Word {
+    x.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+/// Encodes a point of G1 as the `ecAdd` and `ecMul` precompiles, where the
+/// point at infinity is `(0, 0)`.
+fn g1_words(p: &G1Affine) -> [Word; 2] {
+    if p.infinity {
+        [[0u8; 32]; 2]
+    } else {
+        [fq_word(&p.x), fq_word(&p.y)]
+    }
+}
+
+/// Encodes a point of G2 as the `ecPairing` precompile, with the imaginary
+/// parts of the coordinates first.
+fn g2_words(p: &G2Affine) -> [Word; 4] {
+    if p.infinity {
+        [[0u8; 32]; 4]
+    } else {
+        [
+            fq_word(&p.x.c1),
+            fq_word(&p.x.c0),
+            fq_word(&p.y.c1),
+            fq_word(&p.y.c0),
+        ]
+    }
+}
+
+/// Encodes the statement and, if given, the proofs in the layout of `shape`,
+/// where `commitment_words` encodes a commitment and its shifted commitment.
+fn encode<'a, C: PCCommitment + 'a>(
+    shape: &QueryShape<Fr>,
+    shifted: bool,
+    commitments: impl IntoIterator<Item = &'a LabeledCommitment<C>>,
+    commitment_words: impl Fn(&C) -> Result<Vec<Word>, Error>,
+    query_set: &QuerySet<Fr>,
+    evaluations: &Evaluations<Fr, Fr>,
+    proofs: Option<&[crate::kzg10::Proof<ark_bn254::Bn254>]>,
+) -> Result<Vec<Word>, Error> {
+    let layout = Layout::new(shape, shifted);
+    let mut words = vec![[0u8; 32]; layout.len()];
+    let commitments: BTreeMap<_, _> = commitments.into_iter().map(|c| (c.label(), c)).collect();
+    for (label, degree_bound) in &shape.polynomials {
+        let commitment = commitments.get(label).ok_or(Error::MissingPolynomial {
+            label: label.clone(),
+        })?;
+        if commitment.degree_bound() != *degree_bound {
+            return Err(Error::InvalidParameters(format!(
+                "the degree bound of {} differs from the shape",
+                label
+            )));
+        }
+        let index = layout.commitments[label];
+        let encoding = commitment_words(commitment.commitment())?;
+        let expected = if layout.shifted_commitments.contains_key(label) {
+            4
+        } else {
+            2
+        };
+        if encoding.len() != expected {
+            return Err(Error::IncorrectCommitmentSize {
+                encountered: encoding.len(),
+                expected,
+            });
+        }
+        words[index..index + expected].copy_from_slice(&encoding);
+    }
+
+    let points: BTreeMap<_, _> = query_set
+        .iter()
+        .map(|(_, (point_label, point))| (point_label, point))
+        .collect();
+    for (point_label, &index) in &layout.points {
+        let point = points
+            .get(point_label)
+            .ok_or(Error::InvalidParameters(format!(
+                "no point with label {}",
+                point_label
+            )))?;
+        words[index] = fr_word(point);
+    }
+    for ((lc_label, point_label), &index) in &layout.evaluations {
+        let point = points[point_label];
+        let evaluation =
+            evaluations
+                .get(&(lc_label.clone(), *point))
+                .ok_or(Error::MissingEvaluation {
+                    label: lc_label.clone(),
+                })?;
+        words[index] = fr_word(evaluation);
+    }
+
+    match proofs {
+        Some(proofs) if proofs.len() != layout.proofs.len() => {
+            return Err(Error::IncorrectInputLength(format!(
+                "expected {} proofs, got {}",
+                layout.proofs.len(),
+                proofs.len()
+            )))
+        }
+        Some(proofs) => {
+            for (proof, &index) in proofs.iter().zip(layout.proofs.values()) {
+                words[index..index + 2].copy_from_slice(&g1_words(&proof.w));
+                words[index + 2] = fr_word(&proof.random_v.unwrap_or_default());
+            }
+        }
+        None => words.truncate(layout.statement.end),
+    }
+    Ok(words)
+}
+
+/// Outputs the sponge of the contract after it has absorbed `statement`.
+fn statement_transcript(statement: &[Word]) -> KeccakSponge {
+    let mut sponge = KeccakSponge::default();
+    sponge.absorb_bytes(&statement.concat());
+    sponge
+}
+
+/// The offset of the input in the calldata, after the selector.
+const INPUT_OFFSET: usize = 4;
+/// The memory addresses of the result of the checks so far, of the output of
+/// the pairing, and of the state of the sponge, followed by its buffer.
+const OK: usize = 0x80;
+const PAIRING_OUTPUT: usize = 0xa0;
+const TRANSCRIPT: usize = 0xc0;
+/// The precompiles of BN254.
+const EC_ADD: usize = 6;
+const EC_MUL: usize = 7;
+const EC_PAIRING: usize = 8;
+
+/// A group element of a multi-scalar multiplication.
+enum Base {
+    /// A point of the input, at the given word.
+    Input(usize),
+    /// A point of the verifier key.
+    Constant(G1Affine),
+}
+
+/// The labels of the opening challenges, as in `Marlin` and `SonicKZG10`.
+const OPENING_CHALLENGE: &[u8] = b"opening challenge";
+const SHIFTED_OPENING_CHALLENGE: &[u8] = b"shifted opening challenge";
+
+/// The program of a verifier, with the allocation of its memory.
+struct Generator<'a> {
+    layout: &'a Layout,
+    program: Program,
+    next_address: usize,
+    /// The bytes absorbed by `Transcript::bind_context` for the scheme.
+    context: Vec<u8>,
+    /// The constant bytes absorbed after the inputs, before the next squeeze.
+    constants: Vec<u8>,
+}
+
+impl<'a> Generator<'a> {
+    /// Creates the generator of a verifier of the scheme `protocol` with the
+    /// given `parameters`, as bound in its transcript.
+    fn new<T: CanonicalSerialize + ?Sized>(
+        layout: &'a Layout,
+        protocol: &[u8],
+        parameters: &T,
+    ) -> Result<Self, Error> {
+        let context = context_messages(protocol, parameters)?.concat();
+        let longest_header = challenge_header(SHIFTED_OPENING_CHALLENGE, 1).len();
+        let buffer_len = (32 * layout.statement.len() + context.len() + longest_header)
+            .max(32 * layout.proof.len());
+        let mut generator = Self {
+            layout,
+            program: Program::default(),
+            next_address: TRANSCRIPT + 32 * (1 + buffer_len.div_ceil(32)),
+            context,
+            constants: Vec::new(),
+        };
+        generator.program.mstore(OK, lit(1));
+        generator.program.mstore(TRANSCRIPT, lit(0));
+        Ok(generator)
+    }
+
+    /// Allocates `num_words` words of memory, set to zero.
+    fn alloc(&mut self, num_words: usize) -> usize {
+        let address = self.next_address;
+        self.next_address += 32 * num_words;
+        address
+    }
+
+    fn input(&self, index: usize) -> Expr {
+        call(Builtin::CallDataLoad, vec![lit(INPUT_OFFSET + 32 * index)])
+    }
+
+    /// The order of the scalar field, as the constant `R` of the contract.
+    fn modulus() -> Expr {
+        Expr::Const("R", Fr::MODULUS.to_bytes_be().try_into().unwrap())
+    }
+
+    /// Records `condition` in the result of the checks.
+    fn require(&mut self, condition: Expr) {
+        self.program
+            .mstore(OK, call(Builtin::And, vec![mload(OK), condition]));
+    }
+
+    /// Checks that the inputs in `range` are scalars.
+    fn require_scalars(&mut self, range: Range<usize>) {
+        for index in range {
+            let condition = call(Builtin::Lt, vec![self.input(index), Self::modulus()]);
+            self.require(condition);
+        }
+    }
+
+    /// Absorbs the protocol and the parameters before the next squeeze, as
+    /// `Transcript::bind_context`.
+    fn bind_context(&mut self) {
+        self.constants.extend_from_slice(&self.context);
+    }
+
+    /// Absorbs the inputs in `range`, if any, and the header of a challenge
+    /// labelled `label`, and squeezes it, as `Transcript::challenge`.
+    fn challenge(&mut self, absorb: Range<usize>, label: &[u8]) -> usize {
+        self.constants
+            .extend_from_slice(&challenge_header(label, 1));
+        self.squeeze(absorb)
+    }
+
+    /// Absorbs the inputs in `range`, if any, then the pending constant
+    /// bytes, and squeezes a 128-bit challenge into a new variable.
+    fn squeeze(&mut self, absorb: Range<usize>) -> usize {
+        if !absorb.is_empty() {
+            self.program.push(call(
+                Builtin::CallDataCopy,
+                vec![
+                    lit(TRANSCRIPT + 32),
+                    lit(INPUT_OFFSET + 32 * absorb.start),
+                    lit(32 * absorb.len()),
+                ],
+            ));
+        }
+        let constants = ark_std::mem::take(&mut self.constants);
+        let constants_address = TRANSCRIPT + 32 * (1 + absorb.len());
+        for (i, chunk) in constants.chunks(32).enumerate() {
+            let mut word = [0u8; 32];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.program
+                .mstore(constants_address + 32 * i, Expr::Lit(word));
+        }
+        self.program.mstore(
+            TRANSCRIPT,
+            call(
+                Builtin::Keccak256,
+                vec![
+                    lit(TRANSCRIPT),
+                    lit(constants_address - TRANSCRIPT + constants.len()),
+                ],
+            ),
+        );
+        let mut mask = [0u8; 32];
+        mask[16..].copy_from_slice(&[0xff; 16]);
+        let challenge = self.alloc(1);
+        self.program.mstore(
+            challenge,
+            call(
+                Builtin::And,
+                vec![mload(TRANSCRIPT), Expr::Const("CHALLENGE_MASK", mask)],
+            ),
+        );
+        challenge
+    }
+
+    /// Checks that the points, the evaluations and the `random_v`s of the
+    /// input are scalars.
+    fn require_scalar_inputs(&mut self) {
+        self.program
+            .comment("Check that the points and the evaluations are scalars.");
+        let layout = self.layout;
+        self.require_scalars(layout.scalars.clone());
+        for &index in layout.proofs.values() {
+            self.require_scalars(index + 2..index + 3);
+        }
+    }
+
+    /// Squeezes the randomizers of the proofs at `num_points` points after
+    /// absorbing the proofs. As in `batch_check`, the first randomizer is one,
+    /// which is output as `None`.
+    fn randomizers(&mut self, num_points: usize) -> Vec<Option<Expr>> {
+        let mut randomizers = vec![None];
+        if num_points > 1 {
+            self.program
+                .comment("Randomizers of the proofs, squeezed after absorbing them.");
+        }
+        let mut absorb = self.layout.proof.clone();
+        for _ in 1..num_points {
+            let randomizer = self.squeeze(absorb);
+            randomizers.push(Some(mload(randomizer)));
+            absorb = 0..0;
+        }
+        randomizers
+    }
+
+    /// Outputs the evaluation of `lc` at `point_label` in the input, minus the
+    /// constant term of `lc`, as in `check_combinations`.
+    fn evaluation(&self, lc: &LinearCombination<Fr>, point_label: &str) -> Expr {
+        let index = self.layout.evaluations[&(lc.label().clone(), point_label.to_string())];
+        let constant: Fr = lc
+            .iter()
+            .filter(|(_, term)| term.is_one())
+            .map(|(coeff, _)| coeff)
+            .sum();
+        if constant.is_zero() {
+            self.input(index)
+        } else {
+            call(
+                Builtin::AddMod,
+                vec![self.input(index), scalar(&-constant), Self::modulus()],
+            )
+        }
+    }
+
+    /// Outputs the product of `factors` modulo the order of the scalar field.
+    fn product(factors: Vec<Expr>) -> Expr {
+        factors
+            .into_iter()
+            .reduce(|a, b| call(Builtin::MulMod, vec![a, b, Self::modulus()]))
+            .unwrap_or_else(|| lit(1))
+    }
+
+    /// Allocates a scalar variable, set to zero.
+    fn scalar_variable(&mut self) -> usize {
+        let address = self.alloc(1);
+        self.program.mstore(address, lit(0));
+        address
+    }
+
+    /// Adds the product of `factors` to the scalar variable at `address`.
+    fn accumulate(&mut self, address: usize, factors: Vec<Expr>) {
+        let value = Self::product(factors);
+        self.program.mstore(
+            address,
+            call(
+                Builtin::AddMod,
+                vec![mload(address), value, Self::modulus()],
+            ),
+        );
+    }
+
+    /// Outputs the negation of `value`, a scalar.
+    fn negate(value: Expr) -> Expr {
+        call(
+            Builtin::AddMod,
+            vec![
+                call(Builtin::Sub, vec![Self::modulus(), value]),
+                lit(0),
+                Self::modulus(),
+            ],
+        )
+    }
+
+    fn staticcall(&mut self, precompile: usize, input: Range<usize>, output: Range<usize>) {
+        let result = call(
+            Builtin::StaticCall,
+            vec![
+                call(Builtin::Gas, vec![]),
+                lit(precompile),
+                lit(input.start),
+                lit(input.len()),
+                lit(output.start),
+                lit(output.len()),
+            ],
+        );
+        self.require(result);
+    }
+
+    /// Computes the multi-scalar multiplication of `terms` into a new point
+    /// variable, of two words. Each term is added with `ecMul` and `ecAdd`,
+    /// from a region of five words: the sum so far, followed by the base and
+    /// the scalar of the term.
+    fn msm(&mut self, terms: Vec<(Base, Expr)>) -> usize {
+        let sum = self.alloc(5);
+        let term = sum + 64;
+        self.program.mstore(sum, lit(0));
+        self.program.mstore(sum + 32, lit(0));
+        for (base, scalar) in terms {
+            let (x, y) = match base {
+                Base::Input(index) => (self.input(index), self.input(index + 1)),
+                Base::Constant(point) => {
+                    let [x, y] = g1_words(&point);
+                    (Expr::Lit(x), Expr::Lit(y))
+                }
+            };
+            self.program.mstore(term, x);
+            self.program.mstore(term + 32, y);
+            self.program.mstore(term + 64, scalar);
+            self.staticcall(EC_MUL, term..term + 96, term..term + 64);
+            self.staticcall(EC_ADD, sum..sum + 128, sum..sum + 64);
+        }
+        sum
+    }
+
+    /// Checks that the product of the pairings of the point variables of G1
+    /// with the points of G2 is one.
+    fn pairing(&mut self, pairs: Vec<(usize, G2Affine)>) {
+        let input = self.alloc(6 * pairs.len());
+        for (i, (g1, g2)) in pairs.iter().enumerate() {
+            let pair = input + 192 * i;
+            self.program.mstore(pair, mload(*g1));
+            self.program.mstore(pair + 32, mload(g1 + 32));
+            for (j, word) in g2_words(g2).iter().enumerate() {
+                self.program.mstore(pair + 64 + 32 * j, Expr::Lit(*word));
+            }
+        }
+        self.program.mstore(PAIRING_OUTPUT, lit(0));
+        self.staticcall(
+            EC_PAIRING,
+            input..input + 192 * pairs.len(),
+            PAIRING_OUTPUT..PAIRING_OUTPUT + 32,
+        );
+        self.require(mload(PAIRING_OUTPUT));
+    }
+
+    /// Returns the result of the checks, and outputs the contract.
+    fn finish(mut self, name: &str, description: &str) -> VerifierContract {
+        self.program.mstore(0, mload(OK));
+        self.program
+            .push(call(Builtin::Return, vec![lit(0), lit(32)]));
+
+        let num_inputs = self.layout.len();
+        let mut source = String::new();
+        source.push_str("// SPDX-License-Identifier: MIT OR Apache-2.0\n");
+        source.push_str("// This file is generated by ark-poly-commit. Do not edit.\n");
+        source.push_str("pragma solidity >=0.8.0;\n\n");
+        for line in description.lines() {
+            source.push_str(&format!("/// {}\n", line).replace("/// \n", "///\n"));
+        }
+        source.push_str("///\n/// The input is made of 32-byte words, laid out as follows.\n");
+        for (range, description) in &self.layout.descriptions {
+            if range.len() == 1 {
+                source.push_str(&format!("///  - {}: {}\n", range.start, description));
+            } else {
+                source.push_str(&format!(
+                    "///  - {}..{}: {}\n",
+                    range.start, range.end, description
+                ));
+            }
+        }
+        source.push_str(&format!("contract {} {{\n", name));
+        source.push_str(&self.program.constants("    "));
+        source.push_str("    /// @notice Returns whether the input holds a valid proof.\n");
+        source.push_str(&format!(
+            "    function verify(uint256[{}] calldata /* input */) external view returns (bool) {{\n",
+            num_inputs
+        ));
+        source.push_str("        assembly {\n");
+        source.push_str(&self.program.to_yul("            "));
+        source.push_str("        }\n    }\n}\n");
+
+        let mut contract = VerifierContract {
+            name: name.to_string(),
+            source,
+            runtime_bytecode: Vec::new(),
+            num_inputs,
+        };
+        contract.runtime_bytecode = self
+            .program
+            .assemble(contract.selector(), INPUT_OFFSET + 32 * num_inputs);
+        contract
+    }
+}
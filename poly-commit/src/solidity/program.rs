@@ -0,0 +1,310 @@
+//! A straight-line EVM program, printed as Yul for the Solidity source of a
+//! verifier and assembled to the bytecode of the same verifier.
+
+use crate::BTreeMap;
+#[cfg(not(feature = "std"))]
+use ark_std::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// A big-endian 256-bit EVM word.
+pub(crate) type Word = [u8; 32];
+
+/// The EVM instructions used by generated verifiers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Builtin {
+    Sub,
+    AddMod,
+    MulMod,
+    Lt,
+    And,
+    Keccak256,
+    CallDataLoad,
+    CallDataCopy,
+    MLoad,
+    MStore,
+    Gas,
+    StaticCall,
+    Return,
+}
+
+impl Builtin {
+    fn name(self) -> &'static str {
+        match self {
+            Builtin::Sub => "sub",
+            Builtin::AddMod => "addmod",
+            Builtin::MulMod => "mulmod",
+            Builtin::Lt => "lt",
+            Builtin::And => "and",
+            Builtin::Keccak256 => "keccak256",
+            Builtin::CallDataLoad => "calldataload",
+            Builtin::CallDataCopy => "calldatacopy",
+            Builtin::MLoad => "mload",
+            Builtin::MStore => "mstore",
+            Builtin::Gas => "gas",
+            Builtin::StaticCall => "staticcall",
+            Builtin::Return => "return",
+        }
+    }
+
+    fn opcode(self) -> u8 {
+        match self {
+            Builtin::Sub => opcode::SUB,
+            Builtin::AddMod => opcode::ADDMOD,
+            Builtin::MulMod => opcode::MULMOD,
+            Builtin::Lt => opcode::LT,
+            Builtin::And => opcode::AND,
+            Builtin::Keccak256 => opcode::KECCAK256,
+            Builtin::CallDataLoad => opcode::CALLDATALOAD,
+            Builtin::CallDataCopy => opcode::CALLDATACOPY,
+            Builtin::MLoad => opcode::MLOAD,
+            Builtin::MStore => opcode::MSTORE,
+            Builtin::Gas => opcode::GAS,
+            Builtin::StaticCall => opcode::STATICCALL,
+            Builtin::Return => opcode::RETURN,
+        }
+    }
+}
+
+/// The opcodes emitted by the assembler.
+pub(crate) mod opcode {
+    pub(crate) const SUB: u8 = 0x03;
+    pub(crate) const ADDMOD: u8 = 0x08;
+    pub(crate) const MULMOD: u8 = 0x09;
+    pub(crate) const LT: u8 = 0x10;
+    pub(crate) const EQ: u8 = 0x14;
+    pub(crate) const ISZERO: u8 = 0x15;
+    pub(crate) const AND: u8 = 0x16;
+    pub(crate) const SHR: u8 = 0x1c;
+    pub(crate) const KECCAK256: u8 = 0x20;
+    pub(crate) const CALLVALUE: u8 = 0x34;
+    pub(crate) const CALLDATALOAD: u8 = 0x35;
+    pub(crate) const CALLDATASIZE: u8 = 0x36;
+    pub(crate) const CALLDATACOPY: u8 = 0x37;
+    pub(crate) const CODECOPY: u8 = 0x39;
+    pub(crate) const MLOAD: u8 = 0x51;
+    pub(crate) const MSTORE: u8 = 0x52;
+    pub(crate) const JUMPI: u8 = 0x57;
+    pub(crate) const GAS: u8 = 0x5a;
+    pub(crate) const JUMPDEST: u8 = 0x5b;
+    pub(crate) const PUSH1: u8 = 0x60;
+    pub(crate) const DUP1: u8 = 0x80;
+    pub(crate) const RETURN: u8 = 0xf3;
+    pub(crate) const STATICCALL: u8 = 0xfa;
+    pub(crate) const REVERT: u8 = 0xfd;
+}
+
+/// An expression of the program: a literal, or an instruction applied to
+/// the values of its arguments.
+#[derive(Clone, Debug)]
+pub(crate) enum Expr {
+    Lit(Word),
+    /// A literal, declared as a constant of the contract.
+    Const(&'static str, Word),
+    Call(Builtin, Vec<Expr>),
+}
+
+/// Outputs the literal `value`.
+pub(crate) fn lit(value: usize) -> Expr {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    Expr::Lit(word)
+}
+
+/// Outputs `builtin(args...)`.
+pub(crate) fn call(builtin: Builtin, args: Vec<Expr>) -> Expr {
+    Expr::Call(builtin, args)
+}
+
+/// Outputs the word at `address` in memory.
+pub(crate) fn mload(address: usize) -> Expr {
+    call(Builtin::MLoad, vec![lit(address)])
+}
+
+impl Expr {
+    fn to_yul(&self) -> String {
+        match self {
+            Expr::Lit(word) => {
+                let digits: String = word.iter().map(|b| format!("{:02x}", b)).collect();
+                let digits = digits.trim_start_matches('0');
+                match digits.len() {
+                    0 => "0".into(),
+                    1 if digits.as_bytes()[0] <= b'9' => digits.into(),
+                    _ => format!("0x{}", digits),
+                }
+            }
+            Expr::Const(name, _) => name.to_string(),
+            Expr::Call(builtin, args) => {
+                let args: Vec<_> = args.iter().map(Expr::to_yul).collect();
+                format!("{}({})", builtin.name(), args.join(", "))
+            }
+        }
+    }
+
+    fn assemble(&self, code: &mut Vec<u8>) {
+        match self {
+            Expr::Lit(word) | Expr::Const(_, word) => push(code, word),
+            // As in Yul, the arguments are evaluated from right to left, so that
+            // the first one is at the top of the stack.
+            Expr::Call(builtin, args) => {
+                for arg in args.iter().rev() {
+                    arg.assemble(code);
+                }
+                code.push(builtin.opcode());
+            }
+        }
+    }
+}
+
+/// Appends the shortest `PUSH` of `word` to `code`.
+pub(crate) fn push(code: &mut Vec<u8>, word: &Word) {
+    let start = word.iter().position(|&b| b != 0).unwrap_or(31);
+    code.push(opcode::PUSH1 + (31 - start) as u8);
+    code.extend_from_slice(&word[start..]);
+}
+
+#[derive(Clone, Debug)]
+enum Stmt {
+    Comment(String),
+    Expr(Expr),
+}
+
+/// A sequence of expressions evaluated for their effects, with comments.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Program {
+    stmts: Vec<Stmt>,
+}
+
+impl Program {
+    /// Appends a comment, which is only part of the Yul output.
+    pub(crate) fn comment(&mut self, text: impl Into<String>) {
+        self.stmts.push(Stmt::Comment(text.into()));
+    }
+
+    /// Appends an expression without value, such as a `mstore`.
+    pub(crate) fn push(&mut self, expr: Expr) {
+        self.stmts.push(Stmt::Expr(expr));
+    }
+
+    /// Appends `mstore(address, value)`.
+    pub(crate) fn mstore(&mut self, address: usize, value: Expr) {
+        self.push(call(Builtin::MStore, vec![lit(address), value]));
+    }
+
+    /// Outputs the Solidity declarations of the constants of the program.
+    pub(crate) fn constants(&self, indent: &str) -> String {
+        fn collect(expr: &Expr, constants: &mut BTreeMap<&'static str, Word>) {
+            match expr {
+                Expr::Lit(_) => {}
+                Expr::Const(name, word) => {
+                    constants.insert(name, *word);
+                }
+                Expr::Call(_, args) => args.iter().for_each(|arg| collect(arg, constants)),
+            }
+        }
+        let mut constants = BTreeMap::new();
+        for stmt in &self.stmts {
+            if let Stmt::Expr(expr) = stmt {
+                collect(expr, &mut constants);
+            }
+        }
+        constants
+            .into_iter()
+            .map(|(name, word)| {
+                format!(
+                    "{}uint256 internal constant {} = {};\n",
+                    indent,
+                    name,
+                    Expr::Lit(word).to_yul()
+                )
+            })
+            .collect()
+    }
+
+    /// Outputs the program as Yul statements, one per line.
+    pub(crate) fn to_yul(&self, indent: &str) -> String {
+        let mut yul = String::new();
+        for stmt in &self.stmts {
+            match stmt {
+                Stmt::Comment(text) => {
+                    yul.push_str(indent);
+                    yul.push_str("// ");
+                    yul.push_str(text);
+                }
+                Stmt::Expr(expr) => {
+                    yul.push_str(indent);
+                    yul.push_str(&expr.to_yul());
+                }
+            }
+            yul.push('\n');
+        }
+        yul
+    }
+
+    /// Outputs the runtime bytecode of a contract with a single function,
+    /// which runs the program. Like the dispatcher of a Solidity contract, it
+    /// reverts if the call has a different selector, if it sends value, or if
+    /// its calldata is shorter than `min_calldata_size`.
+    pub(crate) fn assemble(&self, selector: [u8; 4], min_calldata_size: usize) -> Vec<u8> {
+        let mut body = Vec::new();
+        for stmt in &self.stmts {
+            if let Stmt::Expr(expr) = stmt {
+                expr.assemble(&mut body);
+            }
+        }
+
+        // The prologue jumps to `revert` on invalid calls. Its jumps use
+        // `PUSH2`, hence the fixed size of the prologue.
+        const PROLOGUE_SIZE: usize = 31;
+        let revert = (PROLOGUE_SIZE + body.len()) as u16;
+        let mut code = Vec::with_capacity(PROLOGUE_SIZE + body.len() + 5);
+        let jump_to_revert = |code: &mut Vec<u8>| {
+            code.push(opcode::PUSH1 + 1);
+            code.extend_from_slice(&revert.to_be_bytes());
+            code.push(opcode::JUMPI);
+        };
+        code.push(opcode::CALLVALUE);
+        jump_to_revert(&mut code);
+        code.extend_from_slice(&[opcode::PUSH1, 0, opcode::CALLDATALOAD, opcode::PUSH1, 0xe0]);
+        code.push(opcode::SHR);
+        code.push(opcode::PUSH1 + 3);
+        code.extend_from_slice(&selector);
+        code.extend_from_slice(&[opcode::EQ, opcode::ISZERO]);
+        jump_to_revert(&mut code);
+        code.push(opcode::PUSH1 + 1);
+        code.extend_from_slice(&(min_calldata_size as u16).to_be_bytes());
+        code.extend_from_slice(&[opcode::CALLDATASIZE, opcode::LT]);
+        jump_to_revert(&mut code);
+        debug_assert_eq!(code.len(), PROLOGUE_SIZE);
+
+        code.append(&mut body);
+        code.extend_from_slice(&[opcode::JUMPDEST, opcode::PUSH1, 0, opcode::DUP1]);
+        code.push(opcode::REVERT);
+        code
+    }
+}
+
+/// Outputs the creation bytecode that deploys `runtime`.
+pub(crate) fn creation_bytecode(runtime: &[u8]) -> Vec<u8> {
+    // PUSH2 len DUP1 PUSH1 offset PUSH1 0 CODECOPY PUSH1 0 RETURN
+    const HEADER_SIZE: u8 = 12;
+    let len = (runtime.len() as u16).to_be_bytes();
+    let mut code = vec![
+        opcode::PUSH1 + 1,
+        len[0],
+        len[1],
+        opcode::DUP1,
+        opcode::PUSH1,
+        HEADER_SIZE,
+        opcode::PUSH1,
+        0,
+        opcode::CODECOPY,
+        opcode::PUSH1,
+        0,
+        opcode::RETURN,
+    ];
+    code.extend_from_slice(runtime);
+    code
+}
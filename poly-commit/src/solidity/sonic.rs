@@ -0,0 +1,206 @@
+use super::*;
+use crate::{
+    kzg10::Commitment,
+    sonic_pc::{SonicKZG10, VerifierKey},
+};
+use ark_bn254::Bn254;
+use ark_poly::DenseUVPolynomial;
+use ark_std::ops::Div;
+
+impl<P> SolidityVerifier<P> for SonicKZG10<Bn254, P>
+where
+    P: DenseUVPolynomial<Fr, Point = Fr>,
+    for<'a, 'b> &'a P: Div<&'b P, Output = P>,
+{
+    fn solidity_verifier(
+        vk: &VerifierKey<Bn254>,
+        shape: &QueryShape<Fr>,
+    ) -> Result<VerifierContract, Error> {
+        let layout = Layout::new(shape, false);
+        let mut generator =
+            Generator::new(&layout, Self::PROTOCOL_NAME, &vk.transcript_parameters()?)?;
+        generator.require_scalar_inputs();
+
+        // The challenges of each linear combination, squeezed as in
+        // `SonicKZG10::accumulate_elems`, which squeezes one more challenge
+        // than there are commitments at each point.
+        generator
+            .program
+            .comment("Opening challenges, squeezed after absorbing the statement.");
+        let queries = shape.queries_by_point();
+        let mut absorb = layout.statement.clone();
+        let mut challenges = BTreeMap::new();
+        for (point_label, lc_labels) in &queries {
+            generator.bind_context();
+            for &lc_label in lc_labels {
+                let challenge = generator.challenge(absorb, OPENING_CHALLENGE);
+                absorb = 0..0;
+                challenges.insert((*point_label, lc_label), challenge);
+            }
+            generator.challenge(absorb.clone(), OPENING_CHALLENGE);
+            absorb = 0..0;
+        }
+        let randomizers = generator.randomizers(queries.len());
+
+        // The scalars of the check of `SonicKZG10::check_elems`, where the
+        // commitments to the linear combinations are expanded.
+        generator.program.comment("Scalars of the check.");
+        let mut scalars = BTreeMap::new();
+        for (label, _) in &shape.polynomials {
+            scalars.insert(label, generator.scalar_variable());
+        }
+        let g_scalar = generator.scalar_variable();
+        let gamma_g_scalar = generator.scalar_variable();
+        for ((point_label, lc_labels), randomizer) in queries.iter().zip(&randomizers) {
+            let factors = |first: Vec<Expr>| -> Vec<Expr> {
+                first.into_iter().chain(randomizer.clone()).collect()
+            };
+            for &lc_label in lc_labels {
+                let challenge = challenges[&(*point_label, lc_label)];
+                let lc = &shape.linear_combinations[lc_label];
+                let value = generator.evaluation(lc, point_label);
+                generator.accumulate(g_scalar, factors(vec![mload(challenge), value]));
+                for (coeff, term) in lc.iter() {
+                    if let LCTerm::PolyLabel(label) = term {
+                        let mut first = vec![mload(challenge)];
+                        if !coeff.is_one() {
+                            first.push(scalar(coeff));
+                        }
+                        generator.accumulate(scalars[label], factors(first));
+                    }
+                }
+            }
+            let proof = layout.proofs[*point_label];
+            generator.accumulate(gamma_g_scalar, factors(vec![generator.input(proof + 2)]));
+        }
+
+        // The commitments of each degree bound are paired with its shifted
+        // power of `h`, and those without degree bound with `h`, as is the
+        // opposite of the adjusted witness.
+        let mut degree_bounds = BTreeMap::new();
+        degree_bounds.insert(None, Vec::new());
+        for (label, degree_bound) in &shape.polynomials {
+            degree_bounds
+                .entry(*degree_bound)
+                .or_insert_with(Vec::new)
+                .push(label);
+        }
+        let mut pairs = Vec::new();
+        for (degree_bound, labels) in degree_bounds {
+            let mut terms: Vec<_> = labels
+                .iter()
+                .map(|label| {
+                    (
+                        Base::Input(layout.commitments[*label]),
+                        mload(scalars[label]),
+                    )
+                })
+                .collect();
+            let h = match degree_bound {
+                Some(degree_bound) => {
+                    generator.program.comment(format!(
+                        "The sum of the commitments of degree bound {}.",
+                        degree_bound
+                    ));
+                    vk.degree_bounds_and_neg_powers_of_h
+                        .as_ref()
+                        .and_then(|v| v.iter().find(|(d, _)| *d == degree_bound).map(|(_, h)| *h))
+                        .ok_or(Error::UnsupportedDegreeBound(degree_bound))?
+                }
+                None => {
+                    generator.program.comment(
+                        "The sum of the commitments without degree bound, \
+                         of the proofs at their points and of the evaluations.",
+                    );
+                    for ((point_label, _), randomizer) in queries.iter().zip(&randomizers) {
+                        let point = generator.input(layout.points[*point_label]);
+                        terms.push((
+                            Base::Input(layout.proofs[*point_label]),
+                            Generator::product(
+                                vec![point].into_iter().chain(randomizer.clone()).collect(),
+                            ),
+                        ));
+                    }
+                    terms.push((Base::Constant(vk.g), Generator::negate(mload(g_scalar))));
+                    terms.push((
+                        Base::Constant(vk.gamma_g),
+                        Generator::negate(mload(gamma_g_scalar)),
+                    ));
+                    vk.h
+                }
+            };
+            pairs.push((generator.msm(terms), h));
+        }
+
+        generator
+            .program
+            .comment("The opposite of the sum of the proofs.");
+        let terms = queries
+            .keys()
+            .zip(&randomizers)
+            .map(|(point_label, randomizer)| {
+                let randomizer = randomizer.clone().unwrap_or_else(|| lit(1));
+                (
+                    Base::Input(layout.proofs[*point_label]),
+                    Generator::negate(randomizer),
+                )
+            })
+            .collect();
+        pairs.push((generator.msm(terms), vk.beta_h));
+
+        generator.program.comment("The pairing check.");
+        generator.pairing(pairs);
+        Ok(generator.finish(
+            "SonicKZG10Verifier",
+            &format!(
+                "@title Verifier of `SonicKZG10` proofs on BN254\n\
+                 @notice Checks the openings of {} linear combinations of {} polynomials at {} points,\n\
+                 as `SonicKZG10::check_combinations`.",
+                shape.linear_combinations.len(),
+                shape.polynomials.len(),
+                queries.len()
+            ),
+        ))
+    }
+
+    fn encode_input<'a>(
+        shape: &QueryShape<Fr>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Commitment<Bn254>>>,
+        query_set: &QuerySet<Fr>,
+        evaluations: &Evaluations<Fr, Fr>,
+        proof: &BatchLCProof<Fr, Self::BatchProof>,
+    ) -> Result<Vec<u8>, Error> {
+        let words = encode(
+            shape,
+            false,
+            commitments,
+            commitment_words,
+            query_set,
+            evaluations,
+            Some(&proof.proof),
+        )?;
+        Ok(words.concat())
+    }
+
+    fn transcript<'a>(
+        shape: &QueryShape<Fr>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Commitment<Bn254>>>,
+        query_set: &QuerySet<Fr>,
+        evaluations: &Evaluations<Fr, Fr>,
+    ) -> Result<KeccakSponge, Error> {
+        let statement = encode(
+            shape,
+            false,
+            commitments,
+            commitment_words,
+            query_set,
+            evaluations,
+            None,
+        )?;
+        Ok(statement_transcript(&statement))
+    }
+}
+
+fn commitment_words(commitment: &Commitment<Bn254>) -> Result<Vec<Word>, Error> {
+    Ok(g1_words(&commitment.0).to_vec())
+}
@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// This file is generated by ark-poly-commit. Do not edit.
+pragma solidity >=0.8.0;
+
+/// @title Verifier of `MarlinKZG10` proofs on BN254
+/// @notice Checks the openings of 3 linear combinations of 3 polynomials at 2 points,
+/// as `MarlinKZG10::check_combinations`.
+///
+/// The input is made of 32-byte words, laid out as follows.
+///  - 0..2: commitment to `a`
+///  - 2..4: commitment to `b`
+///  - 4..6: shifted commitment to `b`, of degree bound 8
+///  - 6..8: commitment to `c`
+///  - 8: point `beta`
+///  - 9: point `gamma`
+///  - 10: evaluation of `a` at `beta`
+///  - 11: evaluation of `a` at `gamma`
+///  - 12: evaluation of `ac` at `gamma`
+///  - 13: evaluation of `b` at `beta`
+///  - 14..17: proof at `beta`, with its `random_v`
+///  - 17..20: proof at `gamma`, with its `random_v`
+contract MarlinKZG10Verifier {
+    uint256 internal constant CHALLENGE_MASK = 0xffffffffffffffffffffffffffffffff;
+    uint256 internal constant R = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
+    /// @notice Returns whether the input holds a valid proof.
+    function verify(uint256[20] calldata /* input */) external view returns (bool) {
+        assembly {
+            mstore(0x80, 1)
+            mstore(0xc0, 0)
+            // Check that the points and the evaluations are scalars.
+            mstore(0x80, and(mload(0x80), lt(calldataload(0x104), R)))
+            mstore(0x80, and(mload(0x80), lt(calldataload(0x124), R)))
+            mstore(0x80, and(mload(0x80), lt(calldataload(0x144), R)))
+            mstore(0x80, and(mload(0x80), lt(calldataload(0x164), R)))
+            mstore(0x80, and(mload(0x80), lt(calldataload(0x184), R)))
+            mstore(0x80, and(mload(0x80), lt(calldataload(0x1a4), R)))
+            mstore(0x80, and(mload(0x80), lt(calldataload(0x204), R)))
+            mstore(0x80, and(mload(0x80), lt(calldataload(0x264), R)))
+            // Opening challenges, squeezed after absorbing the statement.
+            calldatacopy(0xe0, 4, 0x1c0)
+            mstore(0x2a0, 0x80000000000000070726f746f636f6c1b0000000000000061726b2d706f6c)
+            mstore(0x2c0, 0x792d636f6d6d69742f4d61726c696e4b5a473130000a00000000000000706172)
+            mstore(0x2e0, 0x616d65746572732000000000000000c1c211dc0f7dde658f0e2700d51eb085c9)
+            mstore(0x300, 0xb0450232f8ebc8a301ccc672df2fc70111000000000000006f70656e696e6720)
+            mstore(0x320, 0x6368616c6c656e67650100000000000000000000000000000000000000000000)
+            mstore(0xc0, keccak256(0xc0, 0x271))
+            mstore(0x340, and(mload(0xc0), CHALLENGE_MASK))
+            mstore(0xe0, 0x111000000000000006f70656e696e67206368616c6c656e6765010000000000)
+            mstore(0x100, 0)
+            mstore(0xc0, keccak256(0xc0, 0x42))
+            mstore(0x360, and(mload(0xc0), CHALLENGE_MASK))
+            mstore(0xe0, 0x1190000000000000073686966746564206f70656e696e67206368616c6c656e)
+            mstore(0x100, 0x6765010000000000000000000000000000000000000000000000000000000000)
+            mstore(0xc0, keccak256(0xc0, 0x4a))
+            mstore(0x380, and(mload(0xc0), CHALLENGE_MASK))
+            mstore(0xe0, 0x80000000000000070726f746f636f6c1b0000000000000061726b2d706f6c)
+            mstore(0x100, 0x792d636f6d6d69742f4d61726c696e4b5a473130000a00000000000000706172)
+            mstore(0x120, 0x616d65746572732000000000000000c1c211dc0f7dde658f0e2700d51eb085c9)
+            mstore(0x140, 0xb0450232f8ebc8a301ccc672df2fc70111000000000000006f70656e696e6720)
+            mstore(0x160, 0x6368616c6c656e67650100000000000000000000000000000000000000000000)
+            mstore(0xc0, keccak256(0xc0, 0xb1))
+            mstore(0x3a0, and(mload(0xc0), CHALLENGE_MASK))
+            mstore(0xe0, 0x111000000000000006f70656e696e67206368616c6c656e6765010000000000)
+            mstore(0x100, 0)
+            mstore(0xc0, keccak256(0xc0, 0x42))
+            mstore(0x3c0, and(mload(0xc0), CHALLENGE_MASK))
+            // Randomizers of the proofs, squeezed after absorbing them.
+            calldatacopy(0xe0, 0x1c4, 0xc0)
+            mstore(0xc0, keccak256(0xc0, 0xe0))
+            mstore(0x3e0, and(mload(0xc0), CHALLENGE_MASK))
+            // Scalars of the check.
+            mstore(0x400, 0)
+            mstore(0x420, 0)
+            mstore(0x440, 0)
+            mstore(0x460, 0)
+            mstore(0x480, 0)
+            mstore(0x4a0, 0)
+            mstore(0x4c0, 0)
+            mstore(0x4a0, addmod(mload(0x4a0), mulmod(mload(0x340), calldataload(0x144), R), R))
+            mstore(0x400, addmod(mload(0x400), mload(0x340), R))
+            mstore(0x4a0, addmod(mload(0x4a0), mulmod(mload(0x360), calldataload(0x1a4), R), R))
+            mstore(0x420, addmod(mload(0x420), mload(0x360), R))
+            mstore(0x440, addmod(mload(0x440), mload(0x380), R))
+            mstore(0x460, addmod(mload(0x460), mulmod(mload(0x380), addmod(sub(R, calldataload(0x1a4)), 0, R), R), R))
+            mstore(0x4c0, addmod(mload(0x4c0), calldataload(0x204), R))
+            mstore(0x4a0, addmod(mload(0x4a0), mulmod(mulmod(mload(0x3a0), calldataload(0x164), R), mload(0x3e0), R), R))
+            mstore(0x400, addmod(mload(0x400), mulmod(mload(0x3a0), mload(0x3e0), R), R))
+            mstore(0x4a0, addmod(mload(0x4a0), mulmod(mulmod(mload(0x3c0), addmod(calldataload(0x184), 5, R), R), mload(0x3e0), R), R))
+            mstore(0x400, addmod(mload(0x400), mulmod(mload(0x3c0), mload(0x3e0), R), R))
+            mstore(0x480, addmod(mload(0x480), mulmod(mulmod(mload(0x3c0), 3, R), mload(0x3e0), R), R))
+            mstore(0x4c0, addmod(mload(0x4c0), mulmod(calldataload(0x264), mload(0x3e0), R), R))
+            // The sum of the commitments, of the proofs at their points and of the evaluations.
+            mstore(0x4e0, 0)
+            mstore(0x500, 0)
+            mstore(0x520, calldataload(4))
+            mstore(0x540, calldataload(0x24))
+            mstore(0x560, mload(0x400))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 7, 0x520, 0x60, 0x520, 0x40)))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 6, 0x4e0, 0x80, 0x4e0, 0x40)))
+            mstore(0x520, calldataload(0x44))
+            mstore(0x540, calldataload(0x64))
+            mstore(0x560, mload(0x420))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 7, 0x520, 0x60, 0x520, 0x40)))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 6, 0x4e0, 0x80, 0x4e0, 0x40)))
+            mstore(0x520, calldataload(0xc4))
+            mstore(0x540, calldataload(0xe4))
+            mstore(0x560, mload(0x480))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 7, 0x520, 0x60, 0x520, 0x40)))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 6, 0x4e0, 0x80, 0x4e0, 0x40)))
+            mstore(0x520, calldataload(0x84))
+            mstore(0x540, calldataload(0xa4))
+            mstore(0x560, mload(0x440))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 7, 0x520, 0x60, 0x520, 0x40)))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 6, 0x4e0, 0x80, 0x4e0, 0x40)))
+            mstore(0x520, 0x211bc7ce0a0b6ffc9c29783800a02835263bfdd66dcbb57d3eb6d0a413dbdbba)
+            mstore(0x540, 0x2ec6193eb6509fdae01402de5b7e9a1badbd25e97a4a2c722dcb49b10d45c7b5)
+            mstore(0x560, mload(0x460))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 7, 0x520, 0x60, 0x520, 0x40)))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 6, 0x4e0, 0x80, 0x4e0, 0x40)))
+            mstore(0x520, calldataload(0x1c4))
+            mstore(0x540, calldataload(0x1e4))
+            mstore(0x560, calldataload(0x104))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 7, 0x520, 0x60, 0x520, 0x40)))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 6, 0x4e0, 0x80, 0x4e0, 0x40)))
+            mstore(0x520, calldataload(0x224))
+            mstore(0x540, calldataload(0x244))
+            mstore(0x560, mulmod(calldataload(0x124), mload(0x3e0), R))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 7, 0x520, 0x60, 0x520, 0x40)))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 6, 0x4e0, 0x80, 0x4e0, 0x40)))
+            mstore(0x520, 1)
+            mstore(0x540, 2)
+            mstore(0x560, addmod(sub(R, mload(0x4a0)), 0, R))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 7, 0x520, 0x60, 0x520, 0x40)))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 6, 0x4e0, 0x80, 0x4e0, 0x40)))
+            mstore(0x520, 0x17c139df0efee0f766bc0204762b774362e4ded88953a39ce849a8a7fa163fa9)
+            mstore(0x540, 0x1e0559bacb160664764a357af8a9fe70baa9258e0b959273ffc5718c6d4cc7c)
+            mstore(0x560, addmod(sub(R, mload(0x4c0)), 0, R))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 7, 0x520, 0x60, 0x520, 0x40)))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 6, 0x4e0, 0x80, 0x4e0, 0x40)))
+            // The opposite of the sum of the proofs.
+            mstore(0x580, 0)
+            mstore(0x5a0, 0)
+            mstore(0x5c0, calldataload(0x1c4))
+            mstore(0x5e0, calldataload(0x1e4))
+            mstore(0x600, addmod(sub(R, 1), 0, R))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 7, 0x5c0, 0x60, 0x5c0, 0x40)))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 6, 0x580, 0x80, 0x580, 0x40)))
+            mstore(0x5c0, calldataload(0x224))
+            mstore(0x5e0, calldataload(0x244))
+            mstore(0x600, addmod(sub(R, mload(0x3e0)), 0, R))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 7, 0x5c0, 0x60, 0x5c0, 0x40)))
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 6, 0x580, 0x80, 0x580, 0x40)))
+            // The pairing check.
+            mstore(0x620, mload(0x4e0))
+            mstore(0x640, mload(0x500))
+            mstore(0x660, 0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2)
+            mstore(0x680, 0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed)
+            mstore(0x6a0, 0x90689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b)
+            mstore(0x6c0, 0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa)
+            mstore(0x6e0, mload(0x580))
+            mstore(0x700, mload(0x5a0))
+            mstore(0x720, 0x2903ba015a9abde26a5d081e84551e63be0fd4516e46ee6d593edeba46362455)
+            mstore(0x740, 0x224bdc5d4327fcf8ed702e01de1c2f1657a253ba75e32a89c390142aaa28b308)
+            mstore(0x760, 0x3c8b7cda6b2dedb7aeeaf5fda464ad17036bea1c4e6f7adbaed1ebe0335e0d8)
+            mstore(0x780, 0x1d92fff52a265017eeccb372e37d7a7bd431800eca28dfd82e21e8054114233f)
+            mstore(0xa0, 0)
+            mstore(0x80, and(mload(0x80), staticcall(gas(), 8, 0x620, 0x180, 0xa0, 0x20)))
+            mstore(0x80, and(mload(0x80), mload(0xa0)))
+            mstore(0, mload(0x80))
+            return(0, 0x20)
+        }
+    }
+}
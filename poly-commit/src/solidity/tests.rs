@@ -0,0 +1,759 @@
+use super::*;
+use crate::{kzg10, marlin::marlin_pc::MarlinKZG10, sonic_pc::SonicKZG10, LabeledPolynomial};
+use ark_bn254::Bn254;
+use ark_crypto_primitives::sponge::CryptographicSponge;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{Field, UniformRand};
+use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial};
+use ark_std::test_rng;
+use std::{
+    fs,
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+type UniPoly = DensePolynomial<Fr>;
+type Marlin = MarlinKZG10<Bn254, UniPoly>;
+type Sonic = SonicKZG10<Bn254, UniPoly>;
+
+/// A minimal interpreter of the EVM, for the instructions used by generated
+/// verifiers and by their compilation with `solc`, with the precompiles of
+/// BN254.
+mod evm {
+    use super::program::opcode::*;
+    use super::transcript::keccak256;
+    use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+    use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+    use ark_ff::{One, PrimeField, Zero};
+    use ark_std::convert::TryInto;
+    use num_bigint::BigUint;
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub(super) enum Outcome {
+        Return(Vec<u8>),
+        Revert,
+    }
+
+    fn word(value: &BigUint) -> [u8; 32] {
+        let bytes = value.to_bytes_be();
+        let mut word = [0u8; 32];
+        word[32 - bytes.len()..].copy_from_slice(&bytes);
+        word
+    }
+
+    fn read(data: &[u8], offset: usize, len: usize) -> Vec<u8> {
+        (offset..offset + len)
+            .map(|i| data.get(i).copied().unwrap_or(0))
+            .collect()
+    }
+
+    fn usize_of(value: &BigUint) -> usize {
+        value.try_into().expect("offset too large")
+    }
+
+    fn pop(stack: &mut Vec<BigUint>) -> BigUint {
+        stack.pop().expect("stack underflow")
+    }
+
+    /// Flips the sign bit of `value`, so that signed words compare as unsigned.
+    fn flip_sign(value: BigUint) -> BigUint {
+        value ^ (BigUint::one() << 255)
+    }
+
+    /// Runs `code` on `calldata`, in a fresh context with empty memory.
+    pub(super) fn call(code: &[u8], calldata: &[u8]) -> Outcome {
+        let modulus = BigUint::one() << 256;
+        let mut stack: Vec<BigUint> = Vec::new();
+        let mut memory: Vec<u8> = Vec::new();
+        let mut return_data: Vec<u8> = Vec::new();
+        let mut pc = 0;
+        let expand = |memory: &mut Vec<u8>, end: usize| {
+            if memory.len() < end {
+                memory.resize(end.div_ceil(32) * 32, 0);
+            }
+        };
+        while pc < code.len() {
+            let op = code[pc];
+            pc += 1;
+            match op {
+                0x00 => return Outcome::Return(vec![]),
+                // ADD, MUL
+                0x01 | 0x02 => {
+                    let (a, b) = (pop(&mut stack), pop(&mut stack));
+                    let result = if op == 0x01 { a + b } else { a * b };
+                    stack.push(result % &modulus);
+                }
+                SUB => {
+                    let (a, b) = (pop(&mut stack), pop(&mut stack));
+                    stack.push((a + &modulus - b) % &modulus);
+                }
+                // DIV, MOD
+                0x04 | 0x06 => {
+                    let (a, b) = (pop(&mut stack), pop(&mut stack));
+                    stack.push(if b.is_zero() {
+                        BigUint::zero()
+                    } else if op == 0x04 {
+                        a / b
+                    } else {
+                        a % b
+                    });
+                }
+                ADDMOD | MULMOD => {
+                    let (a, b, n) = (pop(&mut stack), pop(&mut stack), pop(&mut stack));
+                    let result = if n.is_zero() {
+                        BigUint::zero()
+                    } else if op == ADDMOD {
+                        (a + b) % n
+                    } else {
+                        (a * b) % n
+                    };
+                    stack.push(result);
+                }
+                // EXP
+                0x0a => {
+                    let (a, b) = (pop(&mut stack), pop(&mut stack));
+                    stack.push(a.modpow(&b, &modulus));
+                }
+                LT => {
+                    let (a, b) = (pop(&mut stack), pop(&mut stack));
+                    stack.push(BigUint::from((a < b) as u8));
+                }
+                // GT
+                0x11 => {
+                    let (a, b) = (pop(&mut stack), pop(&mut stack));
+                    stack.push(BigUint::from((a > b) as u8));
+                }
+                // SLT, SGT
+                0x12 | 0x13 => {
+                    let (a, b) = (flip_sign(pop(&mut stack)), flip_sign(pop(&mut stack)));
+                    let result = if op == 0x12 { a < b } else { a > b };
+                    stack.push(BigUint::from(result as u8));
+                }
+                EQ => {
+                    let (a, b) = (pop(&mut stack), pop(&mut stack));
+                    stack.push(BigUint::from((a == b) as u8));
+                }
+                ISZERO => {
+                    let a = pop(&mut stack);
+                    stack.push(BigUint::from(a.is_zero() as u8));
+                }
+                AND => {
+                    let (a, b) = (pop(&mut stack), pop(&mut stack));
+                    stack.push(a & b);
+                }
+                // OR, XOR
+                0x17 | 0x18 => {
+                    let (a, b) = (pop(&mut stack), pop(&mut stack));
+                    stack.push(if op == 0x17 { a | b } else { a ^ b });
+                }
+                // NOT
+                0x19 => {
+                    let a = pop(&mut stack);
+                    stack.push(&modulus - BigUint::one() - a);
+                }
+                // BYTE
+                0x1a => {
+                    let (i, value) = (pop(&mut stack), pop(&mut stack));
+                    let byte = if i < BigUint::from(32u8) {
+                        word(&value)[usize_of(&i)]
+                    } else {
+                        0
+                    };
+                    stack.push(BigUint::from(byte));
+                }
+                // SHL
+                0x1b => {
+                    let (shift, value) = (pop(&mut stack), pop(&mut stack));
+                    stack.push((value << usize_of(&shift).min(256)) % &modulus);
+                }
+                SHR => {
+                    let (shift, value) = (pop(&mut stack), pop(&mut stack));
+                    stack.push(value >> usize_of(&shift).min(256));
+                }
+                // SAR
+                0x1d => {
+                    let (shift, value) = (pop(&mut stack), pop(&mut stack));
+                    let shift = usize_of(&shift).min(256);
+                    let negative = value.bit(255);
+                    let ones = &modulus - BigUint::one();
+                    stack.push(if negative {
+                        &ones - ((&ones - value) >> shift)
+                    } else {
+                        value >> shift
+                    });
+                }
+                KECCAK256 => {
+                    let (offset, len) = (usize_of(&pop(&mut stack)), usize_of(&pop(&mut stack)));
+                    expand(&mut memory, offset + len);
+                    let hash = keccak256(&memory[offset..offset + len]);
+                    stack.push(BigUint::from_bytes_be(&hash));
+                }
+                CALLVALUE => stack.push(BigUint::zero()),
+                CALLDATALOAD => {
+                    let offset = usize_of(&pop(&mut stack));
+                    stack.push(BigUint::from_bytes_be(&read(calldata, offset, 32)));
+                }
+                CALLDATASIZE => stack.push(BigUint::from(calldata.len())),
+                CALLDATACOPY | CODECOPY => {
+                    let (dest, offset, len) = (
+                        usize_of(&pop(&mut stack)),
+                        usize_of(&pop(&mut stack)),
+                        usize_of(&pop(&mut stack)),
+                    );
+                    let source = if op == CALLDATACOPY { calldata } else { code };
+                    expand(&mut memory, dest + len);
+                    memory[dest..dest + len].copy_from_slice(&read(source, offset, len));
+                }
+                // CODESIZE
+                0x38 => stack.push(BigUint::from(code.len())),
+                // RETURNDATASIZE
+                0x3d => stack.push(BigUint::from(return_data.len())),
+                // RETURNDATACOPY
+                0x3e => {
+                    let (dest, offset, len) = (
+                        usize_of(&pop(&mut stack)),
+                        usize_of(&pop(&mut stack)),
+                        usize_of(&pop(&mut stack)),
+                    );
+                    if offset + len > return_data.len() {
+                        return Outcome::Revert;
+                    }
+                    expand(&mut memory, dest + len);
+                    memory[dest..dest + len].copy_from_slice(&return_data[offset..offset + len]);
+                }
+                // POP
+                0x50 => {
+                    pop(&mut stack);
+                }
+                MLOAD => {
+                    let offset = usize_of(&pop(&mut stack));
+                    expand(&mut memory, offset + 32);
+                    stack.push(BigUint::from_bytes_be(&memory[offset..offset + 32]));
+                }
+                MSTORE => {
+                    let (offset, value) = (usize_of(&pop(&mut stack)), pop(&mut stack));
+                    expand(&mut memory, offset + 32);
+                    memory[offset..offset + 32].copy_from_slice(&word(&value));
+                }
+                // MSTORE8
+                0x53 => {
+                    let (offset, value) = (usize_of(&pop(&mut stack)), pop(&mut stack));
+                    expand(&mut memory, offset + 1);
+                    memory[offset] = word(&value)[31];
+                }
+                // JUMP
+                0x56 => {
+                    let dest = usize_of(&pop(&mut stack));
+                    assert_eq!(code[dest], JUMPDEST, "invalid jump destination");
+                    pc = dest;
+                }
+                JUMPI => {
+                    let (dest, condition) = (usize_of(&pop(&mut stack)), pop(&mut stack));
+                    if !condition.is_zero() {
+                        assert_eq!(code[dest], JUMPDEST, "invalid jump destination");
+                        pc = dest;
+                    }
+                }
+                GAS => stack.push(BigUint::from(u64::MAX)),
+                JUMPDEST => {}
+                // PUSH0
+                0x5f => stack.push(BigUint::zero()),
+                0x60..=0x7f => {
+                    let len = (op - PUSH1 + 1) as usize;
+                    stack.push(BigUint::from_bytes_be(&read(code, pc, len)));
+                    pc += len;
+                }
+                DUP1..=0x8f => {
+                    let depth = (op - DUP1 + 1) as usize;
+                    assert!(stack.len() >= depth, "stack underflow");
+                    stack.push(stack[stack.len() - depth].clone());
+                }
+                // SWAP1 to SWAP16
+                0x90..=0x9f => {
+                    let depth = (op - 0x90 + 1) as usize;
+                    assert!(stack.len() > depth, "stack underflow");
+                    let top = stack.len() - 1;
+                    stack.swap(top, top - depth);
+                }
+                RETURN | REVERT => {
+                    let (offset, len) = (usize_of(&pop(&mut stack)), usize_of(&pop(&mut stack)));
+                    expand(&mut memory, offset + len);
+                    return if op == RETURN {
+                        Outcome::Return(memory[offset..offset + len].to_vec())
+                    } else {
+                        Outcome::Revert
+                    };
+                }
+                STATICCALL => {
+                    let _gas = pop(&mut stack);
+                    let address = usize_of(&pop(&mut stack));
+                    let (input, input_len) =
+                        (usize_of(&pop(&mut stack)), usize_of(&pop(&mut stack)));
+                    let (output, output_len) =
+                        (usize_of(&pop(&mut stack)), usize_of(&pop(&mut stack)));
+                    expand(&mut memory, (input + input_len).max(output + output_len));
+                    let result = precompile(address, &memory[input..input + input_len]);
+                    if let Some(result) = &result {
+                        let len = output_len.min(result.len());
+                        memory[output..output + len].copy_from_slice(&result[..len]);
+                    }
+                    stack.push(BigUint::from(result.is_some() as u8));
+                    return_data = result.unwrap_or_default();
+                }
+                // INVALID
+                0xfe => return Outcome::Revert,
+                _ => panic!("unsupported opcode {:#04x}", op),
+            }
+            assert!(stack.len() <= 1024, "stack overflow");
+        }
+        Outcome::Return(vec![])
+    }
+
+    fn fq(bytes: &[u8]) -> Option<Fq> {
+        let value = BigUint::from_bytes_be(bytes);
+        (value < Fq::MODULUS.into()).then(|| Fq::from_be_bytes_mod_order(bytes))
+    }
+
+    fn g1(bytes: &[u8]) -> Option<G1Affine> {
+        let (x, y) = (fq(&bytes[..32])?, fq(&bytes[32..64])?);
+        if x.is_zero() && y.is_zero() {
+            return Some(G1Affine::zero());
+        }
+        let point = G1Affine::new_unchecked(x, y);
+        point.is_on_curve().then_some(point)
+    }
+
+    fn g2(bytes: &[u8]) -> Option<G2Affine> {
+        let x = Fq2::new(fq(&bytes[32..64])?, fq(&bytes[..32])?);
+        let y = Fq2::new(fq(&bytes[96..128])?, fq(&bytes[64..96])?);
+        if x.is_zero() && y.is_zero() {
+            return Some(G2Affine::zero());
+        }
+        let point = G2Affine::new_unchecked(x, y);
+        (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+    }
+
+    fn g1_output(point: G1Affine) -> Vec<u8> {
+        super::g1_words(&point).concat()
+    }
+
+    /// Runs the precompile at `address`, which outputs `None` on failure.
+    fn precompile(address: usize, input: &[u8]) -> Option<Vec<u8>> {
+        match address {
+            6 => {
+                let input = read(input, 0, 128);
+                let (a, b) = (g1(&input[..64])?, g1(&input[64..])?);
+                Some(g1_output((a + b).into_affine()))
+            }
+            7 => {
+                let input = read(input, 0, 96);
+                let point = g1(&input[..64])?;
+                let scalar = Fr::from_be_bytes_mod_order(&input[64..]);
+                Some(g1_output((point * scalar).into_affine()))
+            }
+            8 => {
+                if !input.len().is_multiple_of(192) {
+                    return None;
+                }
+                let mut g1s = Vec::new();
+                let mut g2s = Vec::new();
+                for pair in input.chunks(192) {
+                    g1s.push(g1(&pair[..64])?);
+                    g2s.push(g2(&pair[64..])?);
+                }
+                let result = Bn254::multi_pairing(g1s, g2s).0.is_one();
+                let mut output = vec![0u8; 32];
+                output[31] = result as u8;
+                Some(output)
+            }
+            _ => panic!("unsupported precompile {}", address),
+        }
+    }
+}
+
+fn true_word() -> Vec<u8> {
+    let mut word = vec![0u8; 32];
+    word[31] = 1;
+    word
+}
+
+fn poly_label(label: &str) -> LCTerm {
+    LCTerm::PolyLabel(label.to_string())
+}
+
+/// A shape with a polynomial of degree bound 8, opened alone, and linear
+/// combinations with and without constant terms, at two points.
+fn test_shape() -> (Vec<LinearCombination<Fr>>, QueryShape<Fr>) {
+    let linear_combinations = vec![
+        LinearCombination::new("a", vec![(Fr::one(), poly_label("a"))]),
+        LinearCombination::new("b", vec![(Fr::one(), poly_label("b"))]),
+        LinearCombination::new(
+            "ac",
+            vec![
+                (Fr::one(), poly_label("a")),
+                (Fr::from(3u64), poly_label("c")),
+                (-Fr::from(5u64), LCTerm::One),
+            ],
+        ),
+    ];
+    let shape = QueryShape::new(
+        vec![
+            ("a".to_string(), None),
+            ("b".to_string(), Some(8)),
+            ("c".to_string(), None),
+        ],
+        linear_combinations.clone(),
+        vec![
+            ("a".to_string(), "beta".to_string()),
+            ("b".to_string(), "beta".to_string()),
+            ("a".to_string(), "gamma".to_string()),
+            ("ac".to_string(), "gamma".to_string()),
+        ],
+    )
+    .unwrap();
+    (linear_combinations, shape)
+}
+
+/// A verifier key of degree 16 and degree bound 8, fixed for the golden files.
+fn golden_kzg10_vk() -> (kzg10::VerifierKey<Bn254>, Fr) {
+    let beta = Fr::from(7u64);
+    let g = G1Affine::generator();
+    let h = G2Affine::generator();
+    let beta_h = (h * beta).into_affine();
+    let vk = kzg10::VerifierKey {
+        g,
+        gamma_g: (g * Fr::from(5u64)).into_affine(),
+        h,
+        beta_h,
+        prepared_h: h.into(),
+        prepared_beta_h: beta_h.into(),
+    };
+    (vk, beta)
+}
+
+fn check_golden(file_name: &str, source: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src/solidity/testdata")
+        .join(file_name);
+    if std::env::var("UPDATE_GOLDEN").is_ok() {
+        fs::write(&path, source).unwrap();
+    }
+    let expected = fs::read_to_string(&path).unwrap();
+    assert!(
+        source == expected,
+        "{} is outdated, regenerate it with UPDATE_GOLDEN=1",
+        file_name
+    );
+}
+
+#[test]
+fn keccak256_test() {
+    let hex = |bytes: [u8; 32]| -> String { bytes.iter().map(|b| format!("{:02x}", b)).collect() };
+    assert_eq!(
+        hex(keccak256(b"")),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+    assert_eq!(
+        hex(keccak256(b"abc")),
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+    );
+    // Inputs of one and two blocks.
+    let input: Vec<u8> = (0..136u8).collect();
+    assert_ne!(keccak256(&input), keccak256(&input[..135]));
+    assert_eq!(
+        VerifierContract {
+            name: String::new(),
+            source: String::new(),
+            runtime_bytecode: vec![],
+            num_inputs: 2,
+        }
+        .selector(),
+        keccak256(b"verify(uint256[2])")[..4]
+    );
+}
+
+#[test]
+fn keccak_sponge_test() {
+    let mut sponge = KeccakSponge::new(&());
+    sponge.absorb_bytes(b"statement");
+    let mut other = sponge.clone();
+    let challenge: Fr = sponge.squeeze_field_elements_with_sizes(&[crate::CHALLENGE_SIZE])[0];
+    assert!(challenge.into_bigint().num_bits() <= 128);
+
+    // A challenge is the low 128 bits of `keccak256(state || buffer)`.
+    let block = keccak256(&[&[0u8; 32][..], b"statement"].concat());
+    assert_eq!(challenge, Fr::from_be_bytes_mod_order(&block[16..]));
+    assert_eq!(other.squeeze_bytes(32), block.to_vec());
+    let next = keccak256(&block);
+    assert_eq!(other.squeeze_bytes(40)[..32], next);
+}
+
+#[test]
+fn shape_test() {
+    let (linear_combinations, _) = test_shape();
+    let polynomials = vec![("a".to_string(), None), ("b".to_string(), Some(8))];
+    let scaled = LinearCombination::new("2b", vec![(Fr::from(2u64), poly_label("b"))]);
+    assert!(QueryShape::new(
+        polynomials.clone(),
+        vec![scaled],
+        vec![("2b".to_string(), "beta".to_string())]
+    )
+    .is_err());
+    assert!(QueryShape::new(
+        polynomials.clone(),
+        linear_combinations,
+        vec![("a".to_string(), "beta".to_string())]
+    )
+    .is_err());
+    assert!(QueryShape::<Fr>::new(
+        polynomials,
+        vec![],
+        vec![("a".to_string(), "beta".to_string())]
+    )
+    .is_err());
+}
+
+#[test]
+fn marlin_golden_test() {
+    let (vk, beta) = golden_kzg10_vk();
+    let shift_power = (vk.g * beta.pow([16 - 8])).into_affine();
+    let vk = crate::marlin::marlin_pc::VerifierKey {
+        vk,
+        degree_bounds_and_shift_powers: Some(vec![(8, shift_power)]),
+        max_degree: 16,
+        supported_degree: 16,
+    };
+    let (_, shape) = test_shape();
+    let contract = Marlin::solidity_verifier(&vk, &shape).unwrap();
+    check_golden("marlin_kzg10_verifier.sol", &contract.source);
+}
+
+#[test]
+fn sonic_golden_test() {
+    let (vk, beta) = golden_kzg10_vk();
+    let shifted_h = (vk.h * beta.pow([16 - 8]).inverse().unwrap()).into_affine();
+    let vk = crate::sonic_pc::VerifierKey {
+        g: vk.g,
+        gamma_g: vk.gamma_g,
+        h: vk.h,
+        beta_h: vk.beta_h,
+        prepared_h: vk.prepared_h,
+        prepared_beta_h: vk.prepared_beta_h,
+        degree_bounds_and_neg_powers_of_h: Some(vec![(8, shifted_h)]),
+        supported_degree: 16,
+        max_degree: 16,
+    };
+    let (_, shape) = test_shape();
+    let contract = Sonic::solidity_verifier(&vk, &shape).unwrap();
+    check_golden("sonic_kzg10_verifier.sol", &contract.source);
+}
+
+/// The creation and runtime bytecodes that `solidity_verifier` assembles.
+fn assembled(contract: &VerifierContract) -> (Vec<u8>, Vec<u8>) {
+    (
+        contract.creation_bytecode(),
+        contract.runtime_bytecode.clone(),
+    )
+}
+
+/// Compiles `source` with the `solc` at `$SOLC`, or else on the `PATH`, and
+/// outputs the creation and runtime bytecodes of its contract.
+fn solc(source: &str) -> (Vec<u8>, Vec<u8>) {
+    let solc = std::env::var("SOLC").unwrap_or_else(|_| "solc".to_string());
+    let mut child = Command::new(&solc)
+        .args(["--optimize", "--bin", "--bin-runtime", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| panic!("cannot run {}: {}", solc, e));
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(source.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let bytecode_after = |header: &str| -> Vec<u8> {
+        let hex = stdout
+            .lines()
+            .skip_while(|line| line.trim() != header)
+            .nth(1)
+            .unwrap_or_else(|| panic!("no {} in the output of solc", header))
+            .trim();
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    };
+    (
+        bytecode_after("Binary:"),
+        bytecode_after("Binary of the runtime part:"),
+    )
+}
+
+/// Proves the openings of `test_shape` with `PC`, and checks that the
+/// verifier generated for them, whose bytecodes `compile` outputs, accepts
+/// the proof, and rejects it when any word of the input is tampered with.
+fn evm_test<PC: SolidityVerifier<UniPoly>>(
+    compile: impl Fn(&VerifierContract) -> (Vec<u8>, Vec<u8>),
+) {
+    let rng = &mut test_rng();
+    let pp = PC::setup(16, None, rng).unwrap();
+    let (ck, vk) = PC::trim(&pp, 16, 1, Some(&[8])).unwrap();
+    let polynomials = vec![
+        LabeledPolynomial::new("a".to_string(), UniPoly::rand(16, rng), None, Some(1)),
+        LabeledPolynomial::new("b".to_string(), UniPoly::rand(8, rng), Some(8), Some(1)),
+        LabeledPolynomial::new("c".to_string(), UniPoly::rand(12, rng), None, None),
+    ];
+    let (commitments, states) = PC::commit(&ck, &polynomials, Some(rng)).unwrap();
+
+    let (linear_combinations, shape) = test_shape();
+    let points = [("beta", Fr::rand(rng)), ("gamma", Fr::rand(rng))];
+    let mut query_set = QuerySet::new();
+    let mut evaluations = Evaluations::new();
+    for (lc_label, point_label) in &shape.queries {
+        let point = points.iter().find(|(l, _)| l == point_label).unwrap().1;
+        query_set.insert((lc_label.clone(), (point_label.clone(), point)));
+        let lc = &shape.linear_combinations[lc_label];
+        let value: Fr = lc
+            .iter()
+            .map(|(coeff, term)| match term {
+                LCTerm::One => *coeff,
+                LCTerm::PolyLabel(label) => {
+                    let p = polynomials.iter().find(|p| p.label() == label).unwrap();
+                    *coeff * p.evaluate(&point)
+                }
+            })
+            .sum();
+        evaluations.insert((lc_label.clone(), point), value);
+    }
+
+    let sponge = PC::transcript(&shape, &commitments, &query_set, &evaluations).unwrap();
+    let proof = PC::open_combinations(
+        &ck,
+        &linear_combinations,
+        &polynomials,
+        &commitments,
+        &query_set,
+        &mut sponge.clone(),
+        &states,
+        Some(rng),
+    )
+    .unwrap();
+    PC::check_combinations(
+        &vk,
+        &linear_combinations,
+        &commitments,
+        &query_set,
+        &evaluations,
+        &proof,
+        &mut sponge.clone(),
+        rng,
+    )
+    .unwrap();
+
+    let contract = PC::solidity_verifier(&vk, &shape).unwrap();
+    let (creation_bytecode, runtime_bytecode) = compile(&contract);
+    let input = PC::encode_input(&shape, &commitments, &query_set, &evaluations, &proof).unwrap();
+    assert_eq!(input.len(), 32 * contract.num_inputs);
+    let calldata = contract.calldata(&input);
+    assert_eq!(
+        evm::call(&runtime_bytecode, &calldata),
+        evm::Outcome::Return(true_word())
+    );
+
+    let false_word = vec![0u8; 32];
+    for i in 0..contract.num_inputs {
+        let mut tampered = calldata.clone();
+        tampered[4 + 32 * i + 31] ^= 1;
+        assert_eq!(
+            evm::call(&runtime_bytecode, &tampered),
+            evm::Outcome::Return(false_word.clone()),
+            "accepted a tampered word {}",
+            i
+        );
+    }
+
+    // Evaluations must be reduced.
+    let index = 4 + 32 * layout_of(&shape, &contract).scalars.start;
+    let mut unreduced = calldata.clone();
+    let value = num_bigint::BigUint::from_bytes_be(&unreduced[index..index + 32])
+        + num_bigint::BigUint::from_bytes_be(&Fr::MODULUS.to_bytes_be());
+    unreduced[index..index + 32].copy_from_slice(&value.to_bytes_be());
+    assert_eq!(
+        evm::call(&runtime_bytecode, &unreduced),
+        evm::Outcome::Return(false_word)
+    );
+
+    // Calls with another selector or too short calldata revert.
+    let mut wrong_selector = calldata.clone();
+    wrong_selector[0] ^= 1;
+    assert_eq!(
+        evm::call(&runtime_bytecode, &wrong_selector),
+        evm::Outcome::Revert
+    );
+    assert_eq!(
+        evm::call(&runtime_bytecode, &calldata[..calldata.len() - 1]),
+        evm::Outcome::Revert
+    );
+
+    // The creation bytecode deploys the runtime bytecode.
+    assert_eq!(
+        evm::call(&creation_bytecode, &[]),
+        evm::Outcome::Return(runtime_bytecode)
+    );
+}
+
+fn layout_of(shape: &QueryShape<Fr>, contract: &VerifierContract) -> Layout {
+    let layout = Layout::new(shape, contract.name.starts_with("Marlin"));
+    assert_eq!(layout.len(), contract.num_inputs);
+    layout
+}
+
+#[test]
+fn marlin_evm_test() {
+    evm_test::<Marlin>(assembled);
+}
+
+#[test]
+fn sonic_evm_test() {
+    evm_test::<Sonic>(assembled);
+}
+
+// The tests below need `solc`, or the compiler that `$SOLC` points to, so they
+// are ignored by default. CI runs them with `-- --ignored solc`.
+
+#[test]
+#[ignore]
+fn solc_golden_test() {
+    for file_name in ["marlin_kzg10_verifier.sol", "sonic_kzg10_verifier.sol"] {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/solidity/testdata")
+            .join(file_name);
+        let (creation_bytecode, runtime_bytecode) = solc(&fs::read_to_string(path).unwrap());
+        assert_eq!(
+            evm::call(&creation_bytecode, &[]),
+            evm::Outcome::Return(runtime_bytecode)
+        );
+    }
+}
+
+#[test]
+#[ignore]
+fn marlin_solc_test() {
+    evm_test::<Marlin>(|contract| solc(&contract.source));
+}
+
+#[test]
+#[ignore]
+fn sonic_solc_test() {
+    evm_test::<Sonic>(|contract| solc(&contract.source));
+}
@@ -0,0 +1,117 @@
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge, FieldElementSize};
+use ark_ff::PrimeField;
+use ark_std::convert::TryInto;
+#[cfg(not(feature = "std"))]
+use ark_std::vec::Vec;
+
+const KECCAK_RATE: usize = 136;
+
+/// Computes the Keccak-256 hash of `input`, as the `KECCAK256` opcode of the
+/// EVM.
+pub fn keccak256(input: &[u8]) -> [u8; 32] {
+    let mut state = [0u64; 25];
+    let mut absorb_block = |block: &[u8]| {
+        for (lane, chunk) in state.iter_mut().zip(block.chunks(8)) {
+            *lane ^= u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        keccak::f1600(&mut state);
+    };
+    let mut blocks = input.chunks_exact(KECCAK_RATE);
+    for block in &mut blocks {
+        absorb_block(block);
+    }
+    let remainder = blocks.remainder();
+    let mut last = [0u8; KECCAK_RATE];
+    last[..remainder.len()].copy_from_slice(remainder);
+    last[remainder.len()] ^= 0x01;
+    last[KECCAK_RATE - 1] ^= 0x80;
+    absorb_block(&last);
+
+    let mut output = [0u8; 32];
+    for (chunk, lane) in output.chunks_mut(8).zip(&state) {
+        chunk.copy_from_slice(&lane.to_le_bytes());
+    }
+    output
+}
+
+/// A sponge over Keccak-256 that is cheap to replay on the EVM.
+///
+/// The sponge keeps a 32-byte state and a buffer of absorbed bytes. Each
+/// squeezed block is `state = keccak256(state || buffer)`, after which the
+/// buffer is emptied. A field element of size `FieldElementSize::Truncated(n)`
+/// is the block, read as a big-endian integer, truncated to its low `n` bits,
+/// and a full field element is the block reduced modulo the field order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeccakSponge {
+    state: [u8; 32],
+    buffer: Vec<u8>,
+}
+
+impl KeccakSponge {
+    /// Absorbs `bytes` as they are, without the length prefixes that
+    /// `Absorb` adds to some types.
+    pub fn absorb_bytes(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    fn squeeze_block(&mut self) -> [u8; 32] {
+        let mut input = self.state.to_vec();
+        input.append(&mut self.buffer);
+        self.state = keccak256(&input);
+        self.state
+    }
+}
+
+impl CryptographicSponge for KeccakSponge {
+    type Config = ();
+
+    fn new(_params: &Self::Config) -> Self {
+        Self::default()
+    }
+
+    fn absorb(&mut self, input: &impl Absorb) {
+        input.to_sponge_bytes(&mut self.buffer);
+    }
+
+    fn squeeze_bytes(&mut self, num_bytes: usize) -> Vec<u8> {
+        let mut output = Vec::with_capacity(num_bytes + 32);
+        while output.len() < num_bytes {
+            output.extend_from_slice(&self.squeeze_block());
+        }
+        output.truncate(num_bytes);
+        output
+    }
+
+    fn squeeze_bits(&mut self, num_bits: usize) -> Vec<bool> {
+        let bytes = self.squeeze_bytes(num_bits.div_ceil(8));
+        let mut bits: Vec<bool> = bytes
+            .iter()
+            .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+            .collect();
+        bits.truncate(num_bits);
+        bits
+    }
+
+    fn squeeze_field_elements_with_sizes<F: PrimeField>(
+        &mut self,
+        sizes: &[FieldElementSize],
+    ) -> Vec<F> {
+        sizes
+            .iter()
+            .map(|size| {
+                let mut block = self.squeeze_block();
+                if let FieldElementSize::Truncated(num_bits) = size {
+                    let num_bits = (*num_bits).min(F::MODULUS_BIT_SIZE as usize - 1);
+                    for (i, byte) in block.iter_mut().rev().enumerate() {
+                        if 8 * i >= num_bits {
+                            *byte = 0;
+                        } else if 8 * (i + 1) > num_bits {
+                            *byte &= (1u8 << (num_bits - 8 * i)) - 1;
+                        }
+                    }
+                }
+                F::from_be_bytes_mod_order(&block)
+            })
+            .collect()
+    }
+}
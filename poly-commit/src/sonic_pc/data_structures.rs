@@ -1,8 +1,12 @@
 use crate::{
-    kzg10, BTreeMap, PCCommitterKey, PCPreparedCommitment, PCPreparedVerifierKey, PCVerifierKey,
+    cost::{compressed_size, kzg_cost, BatchShape, CostEstimate, CostModel},
+    kzg10,
+    security::{pairing_scheme_security, SecurityAssumptions, SecurityEstimate, SecurityEstimator},
+    BTreeMap, Error, PCCommitterKey, PCPreparedCommitment, PCPreparedVerifierKey, PCVerifierKey,
 };
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::{pairing::Pairing, AdditiveGroup};
+use ark_poly::EvaluationDomain;
 use ark_serialize::{
     CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
 };
@@ -71,11 +75,22 @@ pub struct CommitterKey<E: Pairing> {
 
     /// The maximum degree supported by the `UniversalParams` from which `self` was derived
     pub max_degree: usize,
+
+    /// The digest of the verifier key that was trimmed along with `self`.
+    pub verifier_key_digest: Vec<u8>,
 }
 
 impl<E: Pairing> CommitterKey<E> {
+    /// Outputs the parameters that `self` shares with its verifier key, which
+    /// are bound into the transcript of openings.
+    pub(crate) fn transcript_parameters(&self) -> kzg10::TranscriptParameters {
+        kzg10::TranscriptParameters {
+            verifier_key_digest: self.verifier_key_digest.clone(),
+        }
+    }
+
     /// Obtain powers for the underlying KZG10 construction
-    pub fn powers(&self) -> kzg10::Powers<E> {
+    pub fn powers(&self) -> kzg10::Powers<'_, E> {
         kzg10::Powers {
             powers_of_g: self.powers_of_g.as_slice().into(),
             powers_of_gamma_g: self.powers_of_gamma_g.as_slice().into(),
@@ -86,7 +101,7 @@ impl<E: Pairing> CommitterKey<E> {
     pub fn shifted_powers(
         &self,
         degree_bound: impl Into<Option<usize>>,
-    ) -> Option<kzg10::Powers<E>> {
+    ) -> Option<kzg10::Powers<'_, E>> {
         match (&self.shifted_powers_of_g, &self.shifted_powers_of_gamma_g) {
             (Some(shifted_powers_of_g), Some(shifted_powers_of_gamma_g)) => {
                 let max_bound = self
@@ -117,6 +132,22 @@ impl<E: Pairing> CommitterKey<E> {
             (_, _) => None,
         }
     }
+
+    /// Extends `self` with its powers in the Lagrange basis of `domain`, so
+    /// that it can commit to polynomials given by their evaluations over it.
+    pub fn with_lagrange_basis<D: EvaluationDomain<E::ScalarField>>(
+        self,
+        domain: D,
+    ) -> Result<LagrangeCommitterKey<E, D>, Error> {
+        let lagrange_powers_of_g = kzg10::LagrangePowers::new(&self.powers(), domain)?
+            .lagrange_powers_of_g
+            .into_owned();
+        Ok(LagrangeCommitterKey {
+            ck: self,
+            domain,
+            lagrange_powers_of_g,
+        })
+    }
 }
 
 impl<E: Pairing> PCCommitterKey for CommitterKey<E> {
@@ -129,6 +160,31 @@ impl<E: Pairing> PCCommitterKey for CommitterKey<E> {
     }
 }
 
+/// `LagrangeCommitterKey` is a `CommitterKey` that also carries its powers in
+/// the Lagrange basis of an evaluation domain.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct LagrangeCommitterKey<E: Pairing, D: EvaluationDomain<E::ScalarField>> {
+    /// The key used to commit to polynomials in coefficient form.
+    pub ck: CommitterKey<E>,
+    /// The domain over which the committed evaluations are given.
+    pub domain: D,
+    /// The key used to commit to polynomials given by their evaluations over
+    /// `domain`.
+    pub lagrange_powers_of_g: Vec<E::G1Affine>,
+}
+
+impl<E: Pairing, D: EvaluationDomain<E::ScalarField>> LagrangeCommitterKey<E, D> {
+    /// Obtain Lagrange powers for the underlying KZG10 construction
+    pub fn lagrange_powers(&self) -> kzg10::LagrangePowers<'_, E, D> {
+        kzg10::LagrangePowers {
+            domain: self.domain,
+            lagrange_powers_of_g: self.lagrange_powers_of_g.as_slice().into(),
+            powers_of_gamma_g: self.ck.powers_of_gamma_g.as_slice().into(),
+        }
+    }
+}
+
 /// `VerifierKey` is used to check evaluation proofs for a given commitment.
 #[derive(Derivative)]
 #[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
@@ -165,6 +221,12 @@ pub struct VerifierKey<E: Pairing> {
 }
 
 impl<E: Pairing> VerifierKey<E> {
+    /// Outputs the parameters that `self` shares with its committer key, which
+    /// are bound into the transcript of openings.
+    pub(crate) fn transcript_parameters(&self) -> Result<kzg10::TranscriptParameters, Error> {
+        kzg10::TranscriptParameters::new(self)
+    }
+
     /// Find the appropriate shift for the degree bound.
     pub fn get_shift_power(&self, degree_bound: usize) -> Option<E::G2Prepared> {
         self.degree_bounds_and_neg_powers_of_h
@@ -273,6 +335,40 @@ impl<E: Pairing> PCVerifierKey for VerifierKey<E> {
     }
 }
 
+impl<E: Pairing> CostModel for VerifierKey<E> {
+    fn estimate_cost(&self, poly_len: usize, batch: &BatchShape) -> Result<CostEstimate, Error> {
+        if poly_len > self.supported_degree + 1 {
+            return Err(Error::TooManyCoefficients {
+                num_coefficients: poly_len,
+                num_powers: self.supported_degree + 1,
+            });
+        }
+        // The combined commitments, the combined witnesses and the adjusted
+        // witnesses are checked against a single product of pairings.
+        Ok(kzg_cost::<E>(compressed_size(&self.g), poly_len, batch, 3))
+    }
+}
+
+impl<E: Pairing> SecurityEstimator for VerifierKey<E> {
+    fn estimate_security(
+        &self,
+        poly_len: usize,
+        assumptions: &SecurityAssumptions,
+    ) -> Result<SecurityEstimate, Error> {
+        if poly_len > self.supported_degree + 1 {
+            return Err(Error::TooManyCoefficients {
+                num_coefficients: poly_len,
+                num_powers: self.supported_degree + 1,
+            });
+        }
+        Ok(pairing_scheme_security::<E>(
+            poly_len,
+            self.max_degree,
+            assumptions,
+        ))
+    }
+}
+
 /// Nothing to do to prepare this verifier key (for now).
 pub type PreparedVerifierKey<E> = VerifierKey<E>;
 
@@ -1,15 +1,26 @@
 use crate::{
-    kzg10, BTreeMap, BTreeSet, BatchLCProof, DenseUVPolynomial, Error, Evaluations,
-    LabeledCommitment, LabeledPolynomial, LinearCombination, PCCommitmentState, PCCommitterKey,
-    PCUniversalParams, PolynomialCommitment, QuerySet, CHALLENGE_SIZE,
+    hidden::{
+        check_hiding, check_no_degree_bound, kzg10_commit_constant, kzg10_subtract,
+        kzg10_subtract_randomness, pair_evaluation_commitments, subtract_constant,
+        HiddenEvaluations,
+    },
+    homomorphic::{common_degree_bound, CombinationTerm, HomomorphicCommitment},
+    kzg10,
+    update::{merge_deltas, UpdatableCommitment},
+    BTreeMap, BTreeSet, BatchLCProof, DenseUVPolynomial, Error, Evaluations, LabeledCommitment,
+    LabeledEvaluations, LabeledPolynomial, LinearCombination, PCCommitmentState, PCCommitterKey,
+    PCUniversalParams, PolynomialCommitment, PolynomialLabel, QuerySet, Transcript,
+    VerificationFailure,
 };
 use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_ff::{One, UniformRand, Zero};
+use ark_poly::{EvaluationDomain, Evaluations as UVEvaluations};
 use ark_std::{convert::TryInto, marker::PhantomData, ops::Div, ops::Mul, rand::RngCore};
 
 #[cfg(not(feature = "std"))]
 use ark_std::{
+    format,
     string::{String, ToString},
     vec::Vec,
 };
@@ -38,6 +49,9 @@ where
     E::G1Affine: Absorb,
     P: DenseUVPolynomial<E::ScalarField>,
 {
+    /// The name of the scheme, bound into the transcript of its openings.
+    pub const PROTOCOL_NAME: &'static [u8] = b"ark-poly-commit/SonicKZG10";
+
     fn accumulate_elems<'a>(
         combined_comms: &mut BTreeMap<Option<usize>, E::G1>,
         combined_witness: &mut E::G1,
@@ -49,10 +63,11 @@ where
         proof: &kzg10::Proof<E>,
         sponge: &mut impl CryptographicSponge,
         randomizer: Option<E::ScalarField>,
-    ) {
+    ) -> Result<(), Error> {
         let acc_time = start_timer!(|| "Accumulating elements");
 
-        let mut curr_challenge = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+        sponge.bind_context(Self::PROTOCOL_NAME, &vk.transcript_parameters()?)?;
+        let mut curr_challenge = sponge.challenge(b"opening challenge");
 
         // Keeps track of running combination of values
         let mut combined_values = E::ScalarField::zero();
@@ -73,7 +88,7 @@ where
 
             // Accumulate values in the BTreeMap
             *combined_comms.entry(degree_bound).or_insert(E::G1::zero()) += &comm_with_challenge;
-            curr_challenge = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+            curr_challenge = sponge.challenge(b"opening challenge");
         }
 
         // Push expected results into list of elems. Power will be the negative of the expected power
@@ -91,6 +106,7 @@ where
         *combined_witness += &witness;
         *combined_adjusted_witness += &adjusted_witness;
         end_timer!(acc_time);
+        Ok(())
     }
 
     fn check_elems(
@@ -98,7 +114,8 @@ where
         combined_witness: E::G1,
         combined_adjusted_witness: E::G1,
         vk: &VerifierKey<E>,
-    ) -> Result<bool, Error> {
+        point: Option<String>,
+    ) -> Result<(), Error> {
         let check_time = start_timer!(|| "Checking elems");
         let mut g1_projective_elems: Vec<E::G1> = Vec::new();
         let mut g2_prepared_elems: Vec<E::G2Prepared> = Vec::new();
@@ -127,11 +144,118 @@ where
                 .map(|a| a.into())
                 .collect::<Vec<_>>();
 
+        count!(PAIRING, g2_prepared_elems.len());
         let is_one: bool = E::multi_pairing(g1_prepared_elems_iter, g2_prepared_elems)
             .0
             .is_one();
         end_timer!(check_time);
-        Ok(is_one)
+        if is_one {
+            Ok(())
+        } else {
+            Err(VerificationFailure::PairingCheck { point }.into())
+        }
+    }
+}
+
+impl<E, P> SonicKZG10<E, P>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+    P: DenseUVPolynomial<E::ScalarField, Point = E::ScalarField>,
+    for<'a, 'b> &'a P: Div<&'b P, Output = P>,
+{
+    /// Outputs commitments to polynomials given by their evaluations over the
+    /// domain of `ck`. These are the commitments output by `commit` on the
+    /// interpolated polynomials, which cannot have degree bounds.
+    #[allow(clippy::type_complexity)]
+    pub fn commit_evaluations<'a, D: EvaluationDomain<E::ScalarField> + 'a>(
+        ck: &LagrangeCommitterKey<E, D>,
+        evaluations: impl IntoIterator<Item = &'a LabeledEvaluations<E::ScalarField, D>>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<Commitment<E>>>,
+            Vec<Randomness<E::ScalarField, P>>,
+        ),
+        Error,
+    > {
+        let rng = &mut crate::optional_rng::OptionalRng(rng);
+        let commit_time = start_timer!(|| "Committing to evaluations");
+        let powers = ck.lagrange_powers();
+
+        let mut labeled_comms = Vec::new();
+        let mut randomness = Vec::new();
+        for e in evaluations {
+            let (comm, rand) = kzg10::KZG10::commit_evaluations(
+                &powers,
+                e.evaluations(),
+                e.hiding_bound(),
+                Some(rng),
+            )?;
+            labeled_comms.push(LabeledCommitment::new(e.label().to_string(), comm, None));
+            randomness.push(rand);
+        }
+        end_timer!(commit_time);
+        Ok((labeled_comms, randomness))
+    }
+
+    /// On input evaluations over the domain of `ck` and a point `point`,
+    /// outputs a proof for the same, which is checked with `check`.
+    pub fn open_evaluations<'a, D: EvaluationDomain<E::ScalarField> + 'a>(
+        ck: &LagrangeCommitterKey<E, D>,
+        labeled_evaluations: impl IntoIterator<Item = &'a LabeledEvaluations<E::ScalarField, D>>,
+        point: E::ScalarField,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Randomness<E::ScalarField, P>>,
+    ) -> Result<kzg10::Proof<E>, Error>
+    where
+        P: 'a,
+    {
+        let powers = ck.lagrange_powers();
+        let mut combined_evals = vec![E::ScalarField::zero(); powers.size()];
+        let mut combined_rand = kzg10::Randomness::empty();
+
+        sponge.bind_context(Self::PROTOCOL_NAME, &ck.ck.transcript_parameters())?;
+        let mut curr_challenge = sponge.challenge(b"opening challenge");
+        for (e, state) in labeled_evaluations.into_iter().zip(states) {
+            powers.check_evaluations(e.evaluations())?;
+            for (acc, eval) in combined_evals.iter_mut().zip(&e.evaluations().evals) {
+                *acc += curr_challenge * eval;
+            }
+            combined_rand += (curr_challenge, state);
+            curr_challenge = sponge.challenge(b"opening challenge");
+        }
+        let combined_evals = UVEvaluations::from_vec_and_domain(combined_evals, ck.domain);
+        kzg10::KZG10::open_evaluations(&powers, &combined_evals, point, &combined_rand)
+    }
+
+    /// Opens the evaluations over the domain of `ck` at the points of
+    /// `query_set`, with one proof per point, which are checked with
+    /// `batch_check`.
+    pub fn batch_open_evaluations<'a, D: EvaluationDomain<E::ScalarField> + 'a>(
+        ck: &LagrangeCommitterKey<E, D>,
+        labeled_evaluations: impl IntoIterator<Item = &'a LabeledEvaluations<E::ScalarField, D>>,
+        query_set: &QuerySet<E::ScalarField>,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Randomness<E::ScalarField, P>>,
+    ) -> Result<Vec<kzg10::Proof<E>>, Error>
+    where
+        P: 'a,
+    {
+        kzg10::batch_open_evaluations(
+            labeled_evaluations,
+            states,
+            query_set,
+            |evals, states, point| {
+                Self::open_evaluations(
+                    ck,
+                    evals.iter().copied(),
+                    point,
+                    sponge,
+                    states.iter().copied(),
+                )
+            },
+        )
     }
 }
 
@@ -156,6 +280,7 @@ where
         _: Option<usize>,
         rng: &mut R,
     ) -> Result<Self::UniversalParams, Self::Error> {
+        phase_span!("SonicKZG10::setup", max_degree);
         kzg10::KZG10::<E, P>::setup(max_degree, true, rng).map_err(Into::into)
     }
 
@@ -165,6 +290,7 @@ where
         supported_hiding_bound: usize,
         enforced_degree_bounds: Option<&[usize]>,
     ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
+        phase_span!("SonicKZG10::trim", supported_degree, supported_hiding_bound);
         let trim_time = start_timer!(|| "Trimming public parameters");
         let neg_powers_of_h = &pp.neg_powers_of_h;
         let max_degree = pp.max_degree();
@@ -241,15 +367,6 @@ where
             .map(|i| pp.powers_of_gamma_g[&i])
             .collect();
 
-        let ck = CommitterKey {
-            powers_of_g,
-            powers_of_gamma_g,
-            shifted_powers_of_g,
-            shifted_powers_of_gamma_g,
-            enforced_degree_bounds,
-            max_degree,
-        };
-
         let g = pp.powers_of_g[0];
         let h = pp.h;
         let beta_h = pp.beta_h;
@@ -269,6 +386,16 @@ where
             max_degree,
         };
 
+        let ck = CommitterKey {
+            powers_of_g,
+            powers_of_gamma_g,
+            shifted_powers_of_g,
+            shifted_powers_of_gamma_g,
+            enforced_degree_bounds,
+            max_degree,
+            verifier_key_digest: vk.transcript_parameters()?.verifier_key_digest,
+        };
+
         end_timer!(trim_time);
         Ok((ck, vk))
     }
@@ -288,6 +415,7 @@ where
     where
         P: 'a,
     {
+        phase_span!("SonicKZG10::commit");
         let rng = &mut crate::optional_rng::OptionalRng(rng);
         let commit_time = start_timer!(|| "Committing to polynomials");
         let mut labeled_comms: Vec<LabeledCommitment<Self::Commitment>> = Vec::new();
@@ -311,6 +439,7 @@ where
             let hiding_bound = labeled_polynomial.hiding_bound();
             let label = labeled_polynomial.label();
 
+            phase_span!("commit_polynomial", label = %label, degree = polynomial.degree());
             let commit_time = start_timer!(|| format!(
                 "Polynomial {} of degree {}, degree bound {:?}, and hiding bound {:?}",
                 label,
@@ -354,10 +483,12 @@ where
         Self::Commitment: 'a,
         P: 'a,
     {
+        phase_span!("SonicKZG10::open");
         let mut combined_polynomial = P::zero();
         let mut combined_rand = kzg10::Randomness::empty();
 
-        let mut curr_challenge = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+        sponge.bind_context(Self::PROTOCOL_NAME, &ck.transcript_parameters())?;
+        let mut curr_challenge = sponge.challenge(b"opening challenge");
 
         for (polynomial, state) in labeled_polynomials.into_iter().zip(states) {
             let enforced_degree_bounds: Option<&[usize]> = ck
@@ -374,7 +505,7 @@ where
 
             combined_polynomial += (curr_challenge, polynomial.polynomial());
             combined_rand += (curr_challenge, state);
-            curr_challenge = sponge.squeeze_field_elements_with_sizes(&[CHALLENGE_SIZE])[0];
+            curr_challenge = sponge.challenge(b"opening challenge");
         }
 
         let proof_time = start_timer!(|| "Creating proof for polynomials");
@@ -392,10 +523,11 @@ where
         proof: &Self::Proof,
         sponge: &mut impl CryptographicSponge,
         _rng: Option<&mut dyn RngCore>,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
+        phase_span!("SonicKZG10::check");
         let check_time = start_timer!(|| "Checking evaluations");
         let mut combined_comms: BTreeMap<Option<usize>, E::G1> = BTreeMap::new();
         let mut combined_witness: E::G1 = E::G1::zero();
@@ -412,13 +544,14 @@ where
             proof,
             sponge,
             None,
-        );
+        )?;
 
         let res = Self::check_elems(
             combined_comms,
             combined_witness,
             combined_adjusted_witness,
             vk,
+            Some(format!("{:?}", point)),
         );
         end_timer!(check_time);
         res
@@ -432,10 +565,11 @@ where
         proof: &Self::BatchProof,
         sponge: &mut impl CryptographicSponge,
         rng: &mut R,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
+        phase_span!("SonicKZG10::batch_check", num_proofs = proof.len());
         let commitments: BTreeMap<_, _> = commitments.into_iter().map(|c| (c.label(), c)).collect();
         let mut query_to_labels_map = BTreeMap::new();
 
@@ -446,7 +580,17 @@ where
             labels.1.insert(label);
         }
 
-        assert_eq!(proof.len(), query_to_labels_map.len());
+        if proof.len() != query_to_labels_map.len() {
+            return Err(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!(
+                    "expected {} proofs, one per point, found {}",
+                    query_to_labels_map.len(),
+                    proof.len()
+                ),
+            }
+            .into());
+        }
 
         let mut randomizer = E::ScalarField::one();
 
@@ -483,7 +627,7 @@ where
                 p,
                 sponge,
                 Some(randomizer),
-            );
+            )?;
 
             randomizer = u128::rand(rng).into();
         }
@@ -493,6 +637,7 @@ where
             combined_witness,
             combined_adjusted_witness,
             vk,
+            None,
         )
     }
 
@@ -546,7 +691,6 @@ where
                     );
                     degree_bound = cur_poly.degree_bound();
                 } else if cur_poly.degree_bound().is_some() {
-                    eprintln!("Degree bound when number of equations is non-zero");
                     return Err(Self::Error::EquationHasDegreeBounds(lc_label));
                 }
 
@@ -599,7 +743,7 @@ where
         proof: &BatchLCProof<E::ScalarField, Self::BatchProof>,
         sponge: &mut impl CryptographicSponge,
         rng: &mut R,
-    ) -> Result<bool, Self::Error>
+    ) -> Result<(), Self::Error>
     where
         Self::Commitment: 'a,
     {
@@ -632,14 +776,13 @@ where
                         label: label.to_string(),
                     })?;
 
-                    if num_polys == 1 && cur_comm.degree_bound().is_some() {
-                        assert!(
-                            coeff.is_one(),
-                            "Coefficient must be one for degree-bounded equations"
-                        );
+                    // Degree-bounded commitments may only appear alone and
+                    // with a unit coefficient.
+                    if cur_comm.degree_bound().is_some() {
+                        if num_polys != 1 || !coeff.is_one() {
+                            return Err(Self::Error::EquationHasDegreeBounds(lc_label));
+                        }
                         degree_bound = cur_comm.degree_bound();
-                    } else if cur_comm.degree_bound().is_some() {
-                        return Err(Self::Error::EquationHasDegreeBounds(lc_label));
                     }
                     combined_comm += &cur_comm.commitment().0.mul(*coeff);
                 }
@@ -672,6 +815,202 @@ where
     }
 }
 
+impl<E, P> HomomorphicCommitment<E::ScalarField, P> for SonicKZG10<E, P>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+    P: DenseUVPolynomial<E::ScalarField, Point = E::ScalarField>,
+    for<'a, 'b> &'a P: Div<&'b P, Output = P>,
+{
+    fn linear_combination(
+        label: PolynomialLabel,
+        terms: &[CombinationTerm<E::ScalarField, Self::Commitment, Self::CommitmentState>],
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error> {
+        let degree_bound = common_degree_bound(&label, terms)?;
+        let mut comm = kzg10::Commitment::default();
+        let mut state = kzg10::Randomness::empty();
+        for (coeff, c, s) in terms {
+            comm += (*coeff, c.commitment());
+            state += (*coeff, *s);
+        }
+        Ok((LabeledCommitment::new(label, comm, degree_bound), state))
+    }
+}
+
+impl<E, P> UpdatableCommitment<E::ScalarField, P> for SonicKZG10<E, P>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+    P: DenseUVPolynomial<E::ScalarField, Point = E::ScalarField>,
+    for<'a, 'b> &'a P: Div<&'b P, Output = P>,
+{
+    fn update_commitment(
+        ck: &Self::CommitterKey,
+        commitment: &LabeledCommitment<Self::Commitment>,
+        state: &Self::CommitmentState,
+        deltas: &[(usize, E::ScalarField)],
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error> {
+        let deltas = merge_deltas(deltas);
+        let label = commitment.label();
+        let degree_bound = commitment.degree_bound();
+        let update_time = start_timer!(|| format!(
+            "Updating {} coefficients of polynomial {}",
+            deltas.len(),
+            label
+        ));
+
+        let powers = if let Some(degree_bound) = degree_bound {
+            if let Some((degree, _)) = deltas.last() {
+                if *degree > degree_bound {
+                    return Err(Error::PolynomialDegreeTooLarge {
+                        poly_degree: *degree,
+                        supported_degree: degree_bound,
+                        label: label.to_string(),
+                    });
+                }
+            }
+            ck.shifted_powers(degree_bound)
+                .ok_or(Error::UnsupportedDegreeBound(degree_bound))?
+        } else {
+            ck.powers()
+        };
+
+        // The blinding polynomial of a hiding commitment has degree
+        // `hiding_bound + 1`, and the update is blinded in the same way.
+        let hiding_bound = if state.is_hiding() {
+            Some(state.blinding_polynomial.degree().saturating_sub(1))
+        } else {
+            None
+        };
+
+        let (delta_comm, delta_rand) =
+            kzg10::KZG10::commit_sparse(&powers, &deltas, hiding_bound, rng)?;
+        let comm = kzg10::Commitment((commitment.commitment().0 + delta_comm.0).into_affine());
+        let state = state.clone() + &delta_rand;
+        end_timer!(update_time);
+        Ok((
+            LabeledCommitment::new(label.to_string(), comm, degree_bound),
+            state,
+        ))
+    }
+}
+
+impl<E, P> HiddenEvaluations<E::ScalarField, P> for SonicKZG10<E, P>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+    P: DenseUVPolynomial<E::ScalarField, Point = E::ScalarField>,
+    for<'a, 'b> &'a P: Div<&'b P, Output = P>,
+{
+    type EvaluationCommitment = kzg10::Commitment<E>;
+
+    /// Evaluations are committed to as `value G + randomness γG`, with the
+    /// generators of the verifier key.
+    fn commit_evaluation(
+        vk: &Self::VerifierKey,
+        value: E::ScalarField,
+        randomness: E::ScalarField,
+    ) -> Self::EvaluationCommitment {
+        kzg10_commit_constant(vk.g, vk.gamma_g, value, randomness)
+    }
+
+    /// Polynomials with degree bounds are committed to with shifted powers,
+    /// to which the evaluation commitments do not belong, so they are not
+    /// supported.
+    fn open_hidden<'a>(
+        ck: &Self::CommitterKey,
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<E::ScalarField, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Self::Proof,
+            Vec<(Self::EvaluationCommitment, E::ScalarField)>,
+        ),
+        Self::Error,
+    >
+    where
+        P: 'a,
+        Self::CommitmentState: 'a,
+        Self::Commitment: 'a,
+    {
+        let rng = rng.ok_or(Error::MissingRng)?;
+        let mut differences = Vec::new();
+        let mut difference_comms = Vec::new();
+        let mut difference_states = Vec::new();
+        let mut evaluations = Vec::new();
+        for ((polynomial, commitment), state) in
+            labeled_polynomials.into_iter().zip(commitments).zip(states)
+        {
+            check_no_degree_bound(polynomial.degree_bound())?;
+            check_hiding(polynomial, state.is_hiding())?;
+            let value = polynomial.evaluate(point);
+            let randomness = E::ScalarField::rand(rng);
+            let evaluation_comm = kzg10_commit_constant(
+                ck.powers_of_g[0],
+                ck.powers_of_gamma_g[0],
+                value,
+                randomness,
+            );
+
+            differences.push(subtract_constant(polynomial, value));
+            difference_comms.push(LabeledCommitment::new(
+                commitment.label().clone(),
+                kzg10_subtract(commitment.commitment(), &evaluation_comm),
+                None,
+            ));
+            difference_states.push(kzg10_subtract_randomness(state, randomness));
+            evaluations.push((evaluation_comm, randomness));
+        }
+
+        let evaluation_comms: Vec<_> = evaluations.iter().map(|(c, _)| *c).collect();
+        sponge.absorb_serialized(b"evaluation commitments", &evaluation_comms)?;
+        let proof = Self::open(
+            ck,
+            &differences,
+            &difference_comms,
+            point,
+            sponge,
+            &difference_states,
+            Some(rng),
+        )?;
+        Ok((proof, evaluations))
+    }
+
+    fn check_hidden<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a P::Point,
+        evaluation_commitments: impl IntoIterator<Item = &'a Self::EvaluationCommitment>,
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a,
+        Self::EvaluationCommitment: 'a,
+    {
+        let pairs = pair_evaluation_commitments(commitments, evaluation_commitments)?;
+        let mut difference_comms = Vec::new();
+        for (commitment, evaluation_comm) in &pairs {
+            check_no_degree_bound(commitment.degree_bound())?;
+            difference_comms.push(LabeledCommitment::new(
+                commitment.label().clone(),
+                kzg10_subtract(commitment.commitment(), evaluation_comm),
+                None,
+            ));
+        }
+
+        let evaluation_comms: Vec<_> = pairs.iter().map(|(_, c)| **c).collect();
+        sponge.absorb_serialized(b"evaluation commitments", &evaluation_comms)?;
+        let zeros = vec![E::ScalarField::zero(); difference_comms.len()];
+        Self::check(vk, &difference_comms, point, zeros, proof, sponge, None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(non_camel_case_types)]
@@ -928,4 +1267,172 @@ mod tests {
         .expect("test failed for bls12-381");
         println!("Finished bls12-381");
     }
+
+    #[test]
+    fn cost_model_test() {
+        use crate::tests::*;
+        cost_model_test::<_, _, PC_Bls12_377, _>(
+            20,
+            None,
+            21,
+            rand_poly::<Bls12_377>,
+            rand_point::<Bls12_377>,
+            poseidon_sponge_for_test::<<Bls12_377 as Pairing>::ScalarField>,
+        )
+        .expect("test failed for bls12-377");
+        cost_model_test::<_, _, PC_Bls12_381, _>(
+            20,
+            None,
+            21,
+            rand_poly::<Bls12_381>,
+            rand_point::<Bls12_381>,
+            poseidon_sponge_for_test::<<Bls12_381 as Pairing>::ScalarField>,
+        )
+        .expect("test failed for bls12-381");
+    }
+
+    #[test]
+    fn lagrange_basis_test() {
+        use crate::tests::poseidon_sponge_for_test;
+        use crate::{
+            Evaluations, LabeledEvaluations, LabeledPolynomial, PolynomialCommitment, QuerySet,
+        };
+        use ark_ff::FftField;
+        use ark_poly::{
+            EvaluationDomain, Evaluations as UVEvaluations, GeneralEvaluationDomain, Polynomial,
+        };
+        use ark_std::test_rng;
+
+        type F = <Bls12_381 as Pairing>::ScalarField;
+
+        let rng = &mut test_rng();
+        let degree = 15;
+        let pp = PC_Bls12_381::setup(degree, None, rng).unwrap();
+        let (ck, vk) = PC_Bls12_381::trim(&pp, degree, 2, None).unwrap();
+
+        let subgroup = GeneralEvaluationDomain::<F>::new(degree + 1).unwrap();
+        let coset = subgroup.get_coset(F::GENERATOR).unwrap();
+        for domain in [subgroup, coset] {
+            let ck = ck.clone().with_lagrange_basis(domain).unwrap();
+            let polys: Vec<_> = (0..2)
+                .map(|i| {
+                    let p = UniPoly_381::rand(degree, rng);
+                    LabeledPolynomial::new(format!("p{}", i), p, None, Some(1))
+                })
+                .collect();
+            let labeled_evals: Vec<_> = polys
+                .iter()
+                .map(|p| {
+                    let evals = UVEvaluations::from_vec_and_domain(domain.fft(p.coeffs()), domain);
+                    LabeledEvaluations::new(p.label().clone(), evals, p.hiding_bound())
+                })
+                .collect();
+
+            // Without hiding, the commitments match those of the coefficients.
+            let plain_poly = LabeledPolynomial::new(
+                polys[0].label().clone(),
+                polys[0].polynomial().clone(),
+                None,
+                None,
+            );
+            let (plain_comms, _) = PC_Bls12_381::commit(&ck.ck, [&plain_poly], None).unwrap();
+            let plain_evals = LabeledEvaluations::new(
+                labeled_evals[0].label().clone(),
+                labeled_evals[0].evaluations().clone(),
+                None,
+            );
+            let (plain_eval_comms, _) =
+                PC_Bls12_381::commit_evaluations(&ck, [&plain_evals], None).unwrap();
+            assert_eq!(
+                plain_comms[0].commitment(),
+                plain_eval_comms[0].commitment()
+            );
+
+            let (comms, states) =
+                PC_Bls12_381::commit_evaluations(&ck, &labeled_evals, Some(rng)).unwrap();
+
+            // Open at a random point and at a point of the domain.
+            let points = [
+                ("rand".to_string(), F::rand(rng)),
+                ("domain".to_string(), domain.element(3)),
+            ];
+            let mut query_set = QuerySet::new();
+            let mut values = Evaluations::new();
+            for p in &polys {
+                for (point_label, point) in &points {
+                    query_set.insert((p.label().clone(), (point_label.clone(), *point)));
+                    values.insert((p.label().clone(), *point), p.polynomial().evaluate(point));
+                }
+            }
+
+            let sponge = poseidon_sponge_for_test::<F>();
+            let proof = PC_Bls12_381::batch_open_evaluations(
+                &ck,
+                &labeled_evals,
+                &query_set,
+                &mut (sponge.clone()),
+                &states,
+            )
+            .unwrap();
+            PC_Bls12_381::batch_check(
+                &vk,
+                &comms,
+                &query_set,
+                &values,
+                &proof,
+                &mut (sponge.clone()),
+                rng,
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn update_commitment_test() {
+        use crate::tests::*;
+        for degree_bound in [None, Some(10)] {
+            update_commitment_test::<_, _, PC_Bls12_381, _>(
+                20,
+                None,
+                degree_bound,
+                true,
+                rand_poly::<Bls12_381>,
+                rand_point::<Bls12_381>,
+                apply_coefficient_deltas,
+                poseidon_sponge_for_test::<<Bls12_381 as Pairing>::ScalarField>,
+            )
+            .expect("test failed for bls12-381");
+        }
+    }
+
+    #[test]
+    fn homomorphic_commitment_test() {
+        use crate::tests::*;
+        for degree_bound in [None, Some(10)] {
+            homomorphic_commitment_test::<_, _, PC_Bls12_381, _>(
+                20,
+                None,
+                degree_bound,
+                rand_poly::<Bls12_381>,
+                rand_point::<Bls12_381>,
+                poseidon_sponge_for_test::<<Bls12_381 as Pairing>::ScalarField>,
+            )
+            .expect("test failed for bls12-381");
+        }
+    }
+
+    #[test]
+    fn hidden_evaluations_test() {
+        use crate::tests::*;
+        hidden_evaluations_test::<_, _, PC_Bls12_381, _>(
+            20,
+            None,
+            None,
+            true,
+            rand_poly::<Bls12_381>,
+            rand_point::<Bls12_381>,
+            poseidon_sponge_for_test::<<Bls12_381 as Pairing>::ScalarField>,
+        )
+        .expect("test failed for bls12-381");
+    }
 }
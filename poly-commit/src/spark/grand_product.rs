@@ -0,0 +1,173 @@
+use super::{
+    eq, eq_evaluations,
+    sumcheck::{self, SumcheckProof},
+};
+use crate::{Error, Transcript, VerificationFailure};
+use ark_crypto_primitives::sponge::CryptographicSponge;
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::format;
+#[cfg(not(feature = "std"))]
+use ark_std::vec::Vec;
+
+/// Proof of the reduction of the claims on the `j`-variate layers of a batch
+/// of product circuits to claims on their `(j + 1)`-variate layers.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LayerProof<F: PrimeField> {
+    /// The sumcheck of the random combination of the claims.
+    pub sumcheck: SumcheckProof<F>,
+    /// The evaluations at the point of the sumcheck of the lower halves of
+    /// the next layers.
+    pub left: Vec<F>,
+    /// The evaluations at the point of the sumcheck of the upper halves of
+    /// the next layers.
+    pub right: Vec<F>,
+}
+
+/// Proof of the products of a batch of vectors of the same length, with
+/// binary trees of multiplications checked layer by layer as in GKR.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct GrandProductProof<F: PrimeField> {
+    /// The products of the vectors.
+    pub products: Vec<F>,
+    /// The proofs of the layers, from the root to the leaves.
+    pub layers: Vec<LayerProof<F>>,
+}
+
+/// Samples the challenge combining the claims of a layer and outputs its
+/// powers.
+fn batching_powers<F: PrimeField>(num: usize, sponge: &mut impl CryptographicSponge) -> Vec<F> {
+    let mu: F = sponge.challenge(b"batching challenge");
+    ark_std::iter::successors(Some(F::one()), |power| Some(*power * mu))
+        .take(num)
+        .collect()
+}
+
+/// Proves the products of `leaves`, vectors of the same power-of-two length.
+/// The `j`-variate layer `V_j` of each circuit is
+/// `V_j(x) = V_{j + 1}(x, 0) V_{j + 1}(x, 1)`, the last variable being the
+/// highest bit of the index. Outputs the proof and the point at which the
+/// verifier is left to evaluate the leaves.
+pub(crate) fn prove<F: PrimeField>(
+    leaves: Vec<Vec<F>>,
+    sponge: &mut impl CryptographicSponge,
+) -> Result<(GrandProductProof<F>, Vec<F>), Error> {
+    let num_vars = leaves[0].len().trailing_zeros() as usize;
+    let mut circuit = vec![leaves];
+    for _ in 0..num_vars {
+        let layer = circuit
+            .last()
+            .unwrap()
+            .iter()
+            .map(|values| {
+                let (left, right) = values.split_at(values.len() / 2);
+                left.iter().zip(right).map(|(l, r)| *l * r).collect()
+            })
+            .collect::<Vec<Vec<F>>>();
+        circuit.push(layer);
+    }
+    let products: Vec<F> = circuit.pop().unwrap().into_iter().map(|v| v[0]).collect();
+    sponge.absorb_serialized(b"products", &products)?;
+
+    let mut point = Vec::with_capacity(num_vars);
+    let mut layers = Vec::with_capacity(num_vars);
+    while let Some(next) = circuit.pop() {
+        let powers = batching_powers::<F>(next.len(), sponge);
+        let mut tables = vec![eq_evaluations(&point)];
+        for values in next {
+            let (left, right) = values.split_at(values.len() / 2);
+            tables.push(left.to_vec());
+            tables.push(right.to_vec());
+        }
+        let (sumcheck, mut rho, evaluations) =
+            sumcheck::prove(tables, 3, |v| v[0] * combine(&powers, &v[1..]), sponge)?;
+        let left: Vec<F> = evaluations[1..].iter().step_by(2).copied().collect();
+        let right: Vec<F> = evaluations[2..].iter().step_by(2).copied().collect();
+        sponge.absorb_serialized(b"left evaluations", &left)?;
+        sponge.absorb_serialized(b"right evaluations", &right)?;
+        rho.push(sponge.challenge(b"layer challenge"));
+        point = rho;
+        layers.push(LayerProof {
+            sumcheck,
+            left,
+            right,
+        });
+    }
+    Ok((GrandProductProof { products, layers }, point))
+}
+
+/// Outputs `sum_k powers[k] L_k R_k`, for `pairs = [L_0, R_0, L_1, R_1, ...]`.
+fn combine<F: PrimeField>(powers: &[F], pairs: &[F]) -> F {
+    powers
+        .iter()
+        .zip(pairs.chunks(2))
+        .map(|(power, pair)| *power * pair[0] * pair[1])
+        .sum()
+}
+
+/// Verifies a proof of the products of `num_circuits` vectors of `2^num_vars`
+/// elements. Outputs the point at which the leaves must be evaluated and the
+/// evaluations they must have there.
+pub(crate) fn verify<F: PrimeField>(
+    num_circuits: usize,
+    num_vars: usize,
+    proof: &GrandProductProof<F>,
+    sponge: &mut impl CryptographicSponge,
+) -> Result<(Vec<F>, Vec<F>), Error> {
+    let malformed = |reason| -> Error {
+        VerificationFailure::MalformedProof {
+            label: None,
+            reason,
+        }
+        .into()
+    };
+    if proof.products.len() != num_circuits || proof.layers.len() != num_vars {
+        return Err(malformed(format!(
+            "expected {} products over {} layers, found {} over {}",
+            num_circuits,
+            num_vars,
+            proof.products.len(),
+            proof.layers.len()
+        )));
+    }
+    sponge.absorb_serialized(b"products", &proof.products)?;
+
+    let mut point = Vec::with_capacity(num_vars);
+    let mut claims = proof.products.clone();
+    for (j, layer) in proof.layers.iter().enumerate() {
+        if layer.left.len() != num_circuits || layer.right.len() != num_circuits {
+            return Err(malformed(format!(
+                "expected {} evaluations in each half of layer {}",
+                num_circuits,
+                j + 1
+            )));
+        }
+        let powers = batching_powers::<F>(num_circuits, sponge);
+        let claim = powers.iter().zip(&claims).map(|(p, c)| *p * c).sum();
+        let (mut rho, evaluation) = sumcheck::verify(claim, j, 3, &layer.sumcheck, sponge)?;
+        let pairs = layer
+            .left
+            .iter()
+            .zip(&layer.right)
+            .flat_map(|(l, r)| [*l, *r])
+            .collect::<Vec<_>>();
+        if evaluation != eq(&point, &rho) * combine(&powers, &pairs) {
+            return Err(malformed(format!(
+                "the evaluations of layer {} are inconsistent with its sumcheck",
+                j + 1
+            )));
+        }
+        sponge.absorb_serialized(b"left evaluations", &layer.left)?;
+        sponge.absorb_serialized(b"right evaluations", &layer.right)?;
+        let c: F = sponge.challenge(b"layer challenge");
+        claims = layer
+            .left
+            .iter()
+            .zip(&layer.right)
+            .map(|(l, r)| *l + c * (*r - l))
+            .collect();
+        rho.push(c);
+        point = rho;
+    }
+    Ok((point, claims))
+}
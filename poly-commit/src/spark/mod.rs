@@ -0,0 +1,1029 @@
+use crate::{
+    optional_rng::OptionalRng, univariate_adapter::FromHypercubeEvaluations, Error, Evaluations,
+    LabeledCommitment, LabeledPolynomial, PCCommitment, PCCommitmentState, PCCommitterKey,
+    PCUniversalParams, PCVerifierKey, PolynomialCommitment, QuerySet, Transcript,
+    VerificationFailure,
+};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ff::PrimeField;
+use ark_poly::SparseMultilinearExtension;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{convert::TryInto, format, marker::PhantomData, rand::RngCore};
+#[cfg(not(feature = "std"))]
+use ark_std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+mod grand_product;
+mod sumcheck;
+
+pub use grand_product::{GrandProductProof, LayerProof};
+pub use sumcheck::SumcheckProof;
+
+#[cfg(test)]
+mod tests;
+
+/// Binds the transcript of every opening to this construction.
+const PROTOCOL_NAME: &[u8] = b"SPARK";
+
+/// Labels of the dense polynomials committed to along with an opening, whose
+/// evaluations are the entries of the tables `eq(r_row, .)` and `eq(r_col, .)`
+/// read by the non-zero evaluations.
+const LOOKUP_NAMES: [&str; 2] = ["row_lookups", "col_lookups"];
+
+/// Labels of the points at which the dense polynomials are opened.
+const POINT_NAMES: [&str; 4] = ["sumcheck", "reads", "row_audit", "col_audit"];
+
+/// Outputs the evaluations over the hypercube of `eq(point, .)`, the first
+/// variable being the lowest bit of the index.
+pub(crate) fn eq_evaluations<F: PrimeField>(point: &[F]) -> Vec<F> {
+    let mut evaluations = Vec::with_capacity(1 << point.len());
+    evaluations.push(F::one());
+    for r in point {
+        let upper = evaluations.iter().map(|e| *e * r).collect::<Vec<_>>();
+        for (e, u) in evaluations.iter_mut().zip(&upper) {
+            *e -= u;
+        }
+        evaluations.extend(upper);
+    }
+    evaluations
+}
+
+/// Outputs `eq(a, b) = prod_i (a_i b_i + (1 - a_i) (1 - b_i))`.
+pub(crate) fn eq<F: PrimeField>(a: &[F], b: &[F]) -> F {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| *a * b + (F::one() - a) * (F::one() - b))
+        .product()
+}
+
+/// Outputs the evaluation of the multilinear extension of the identity
+/// `i -> i` over the hypercube.
+fn identity<F: PrimeField>(point: &[F]) -> F {
+    point
+        .iter()
+        .rev()
+        .fold(F::zero(), |acc, r| acc.double() + r)
+}
+
+/// The rows, columns and values of the non-zero evaluations of a sparse
+/// polynomial.
+type Entries<F> = (Vec<usize>, Vec<usize>, Vec<F>);
+
+/// Reborrows an optional randomness source for a single call.
+fn reborrow<'a>(rng: &'a mut Option<&mut dyn RngCore>) -> Option<&'a mut dyn RngCore> {
+    match rng {
+        Some(rng) => Some(&mut **rng),
+        None => None,
+    }
+}
+
+/// Parameters and keys of `SparkPC`: those of the underlying multilinear
+/// scheme, along with the number of variables `s` of the sparse polynomials
+/// and the number of variables `m` of the dense polynomials encoding them.
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SparkKey<K: CanonicalSerialize + CanonicalDeserialize> {
+    /// The parameters or key of the underlying scheme.
+    pub inner: K,
+    /// The number of variables `s` of the sparse polynomials.
+    pub num_vars: usize,
+    /// The number of variables `m` of the dense polynomials.
+    pub dense_num_vars: usize,
+}
+
+impl<K: CanonicalSerialize + CanonicalDeserialize> SparkKey<K> {
+    /// The maximum number `2^m` of non-zero evaluations of the sparse
+    /// polynomials.
+    fn capacity(&self) -> usize {
+        1 << self.dense_num_vars
+    }
+
+    /// The number of variables selecting the column of an evaluation, the
+    /// lowest bits of its index.
+    fn col_num_vars(&self) -> usize {
+        self.num_vars / 2
+    }
+
+    /// The number of variables selecting the row of an evaluation, the
+    /// highest bits of its index.
+    fn row_num_vars(&self) -> usize {
+        self.num_vars - self.col_num_vars()
+    }
+}
+
+impl<K: PCUniversalParams> PCUniversalParams for SparkKey<K> {
+    fn max_degree(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<K: PCCommitterKey> PCCommitterKey for SparkKey<K> {
+    fn max_degree(&self) -> usize {
+        self.capacity()
+    }
+
+    fn supported_degree(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<K: PCVerifierKey> PCVerifierKey for SparkKey<K> {
+    fn max_degree(&self) -> usize {
+        self.capacity()
+    }
+
+    fn supported_degree(&self) -> usize {
+        self.capacity()
+    }
+}
+
+/// The dense vectors of `2^m` entries encoding a sparse multilinear
+/// polynomial, one entry per non-zero evaluation, or the commitments to the
+/// polynomials with these evaluations, or their states. The index of an
+/// evaluation is split into a row, its highest `ceil(s / 2)` bits, and a
+/// column, its lowest `floor(s / 2)` bits. The timestamps are those of the
+/// offline memory checking of the reads of each row and column.
+#[derive(Clone, Debug, Default, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SparseEncoding<T: CanonicalSerialize + CanonicalDeserialize> {
+    /// The rows of the evaluations.
+    pub rows: T,
+    /// The number of earlier evaluations in the row of each evaluation.
+    pub row_read_timestamps: T,
+    /// The number of evaluations in each row, padded with zeros.
+    pub row_final_timestamps: T,
+    /// The columns of the evaluations.
+    pub cols: T,
+    /// The number of earlier evaluations in the column of each evaluation.
+    pub col_read_timestamps: T,
+    /// The number of evaluations in each column, padded with zeros.
+    pub col_final_timestamps: T,
+    /// The evaluations.
+    pub values: T,
+}
+
+impl<T: CanonicalSerialize + CanonicalDeserialize> SparseEncoding<T> {
+    /// The names of the components, in the order of `components`.
+    const NAMES: [&'static str; 7] = [
+        "rows",
+        "row_read_timestamps",
+        "row_final_timestamps",
+        "cols",
+        "col_read_timestamps",
+        "col_final_timestamps",
+        "values",
+    ];
+
+    /// Outputs the components, in the order of their names.
+    pub fn components(&self) -> Vec<&T> {
+        vec![
+            &self.rows,
+            &self.row_read_timestamps,
+            &self.row_final_timestamps,
+            &self.cols,
+            &self.col_read_timestamps,
+            &self.col_final_timestamps,
+            &self.values,
+        ]
+    }
+
+    /// Outputs the components, in the order of their names.
+    fn into_components(self) -> Vec<T> {
+        vec![
+            self.rows,
+            self.row_read_timestamps,
+            self.row_final_timestamps,
+            self.cols,
+            self.col_read_timestamps,
+            self.col_final_timestamps,
+            self.values,
+        ]
+    }
+
+    /// Takes the next seven components out of `components`.
+    fn from_components(components: &mut impl Iterator<Item = T>) -> Self {
+        let mut next = || components.next().expect("missing component");
+        Self {
+            rows: next(),
+            row_read_timestamps: next(),
+            row_final_timestamps: next(),
+            cols: next(),
+            col_read_timestamps: next(),
+            col_final_timestamps: next(),
+            values: next(),
+        }
+    }
+}
+
+impl<T: Absorb + CanonicalSerialize + CanonicalDeserialize> Absorb for SparseEncoding<T> {
+    fn to_sponge_bytes(&self, dest: &mut Vec<u8>) {
+        for component in self.components() {
+            component.to_sponge_bytes(dest);
+        }
+    }
+
+    fn to_sponge_field_elements<F: PrimeField>(&self, dest: &mut Vec<F>) {
+        for component in self.components() {
+            component.to_sponge_field_elements(dest);
+        }
+    }
+}
+
+impl<C: PCCommitment> PCCommitment for SparseEncoding<C> {
+    #[inline]
+    fn empty() -> Self {
+        Self::from_components(&mut ark_std::iter::repeat_with(C::empty))
+    }
+
+    fn has_degree_bound(&self) -> bool {
+        false
+    }
+}
+
+impl<S: PCCommitmentState> PCCommitmentState for SparseEncoding<S> {
+    type Randomness = S::Randomness;
+
+    fn empty() -> Self {
+        Self::from_components(&mut ark_std::iter::repeat_with(S::empty))
+    }
+
+    fn rand<R: RngCore>(
+        num_queries: usize,
+        has_degree_bound: bool,
+        num_vars: Option<usize>,
+        rng: &mut R,
+    ) -> Self::Randomness {
+        S::rand(num_queries, has_degree_bound, num_vars, rng)
+    }
+}
+
+/// Proof of the evaluation of a sparse polynomial committed to with
+/// `SparkPC`.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(Clone(bound = ""))]
+pub struct SparkProof<F, C, B>
+where
+    F: PrimeField,
+    C: PCCommitment,
+    B: Clone + CanonicalSerialize + CanonicalDeserialize,
+{
+    /// The commitments to the entries of `eq(r_row, .)` and `eq(r_col, .)`
+    /// read by the non-zero evaluations.
+    pub lookups: Vec<C>,
+    /// The sumcheck of `sum_k values[k] row_lookups[k] col_lookups[k]`.
+    pub sumcheck: SumcheckProof<F>,
+    /// The products of the fingerprints of the reads and writes of the rows
+    /// and columns.
+    pub reads: GrandProductProof<F>,
+    /// The products of the fingerprints of the initial and final rows.
+    pub row_audit: GrandProductProof<F>,
+    /// The products of the fingerprints of the initial and final columns.
+    pub col_audit: GrandProductProof<F>,
+    /// The evaluations of the dense polynomials at the points left by the
+    /// sumcheck and the products, in the order of the queries.
+    pub evaluations: Vec<F>,
+    /// The openings of the dense polynomials at these points.
+    pub openings: B,
+}
+
+/// Commitment scheme for sparse multilinear polynomials in the style of
+/// Spark [[Setty20]][spartan], built on top of a multilinear scheme `PC`
+/// for dense multilinear polynomials of type `P`.
+///
+/// A polynomial in `s` variables with at most `2^m` non-zero evaluations over
+/// the hypercube, such as the matrices of an R1CS instance, is committed to
+/// through seven `m`-variate dense polynomials: the rows, columns and values
+/// of its non-zero evaluations, and the timestamps of the reads of the rows
+/// and columns (see `SparseEncoding`). Its evaluation at
+/// `r = (r_col, r_row)` is
+/// `sum_k values[k] eq(r_row, rows[k]) eq(r_col, cols[k])`.
+/// To open it, the prover commits to the entries `row_lookups[k]` and
+/// `col_lookups[k]` of the tables `eq(r_row, .)` and `eq(r_col, .)` read by
+/// each evaluation, and proves
+///
+/// 1. the sum with a sumcheck over the `m` variables, and
+/// 2. that the lookups are the entries of the tables with offline memory
+///    checking: the multiset of the initial entries and the writes is that
+///    of the reads and the final entries, with the products of their
+///    fingerprints `a gamma^2 + v gamma + t - tau` proved with layered
+///    circuits as in GKR. The verifier evaluates the tables itself.
+///
+/// Both reduce to evaluations of the dense polynomials, which are opened with
+/// `PC::batch_open`. The cost of committing and opening is thus linear in the
+/// number of non-zero evaluations and in `2^{s / 2}`, rather than in `2^s`.
+///
+/// `setup` expects the number of variables `s`, and reads `max_degree` as the
+/// maximum number of non-zero evaluations, which fixes
+/// `m >= max(log2(max_degree), ceil(s / 2))`. Since `HyraxPC` only supports
+/// an even number of variables, `m` is always even. Degree bounds are not
+/// supported.
+///
+/// [spartan]: https://eprint.iacr.org/2019/550
+pub struct SparkPC<F, P, PC> {
+    _field: PhantomData<F>,
+    _poly: PhantomData<P>,
+    _pc: PhantomData<PC>,
+}
+
+impl<F, P, PC> SparkPC<F, P, PC>
+where
+    F: PrimeField,
+    P: FromHypercubeEvaluations<F, Point = Vec<F>>,
+    PC: PolynomialCommitment<F, P>,
+{
+    fn dense_label(label: &str, name: &str) -> String {
+        format!("{}:{}", label, name)
+    }
+
+    /// Outputs the rows, columns and values of the non-zero evaluations of
+    /// `polynomial`, padded to `2^m` entries with zero values in row and
+    /// column zero.
+    fn entries<K: CanonicalSerialize + CanonicalDeserialize>(
+        key: &SparkKey<K>,
+        polynomial: &LabeledPolynomial<F, SparseMultilinearExtension<F>>,
+    ) -> Result<Entries<F>, Error> {
+        if let Some(degree_bound) = polynomial.degree_bound() {
+            return Err(Error::UnsupportedDegreeBound(degree_bound));
+        }
+        if polynomial.num_vars != key.num_vars {
+            return Err(Error::MismatchedNumVars {
+                poly_nv: polynomial.num_vars,
+                point_nv: key.num_vars,
+            });
+        }
+        let size = key.capacity();
+        if polynomial.evaluations.len() > size {
+            return Err(Error::TooManyCoefficients {
+                num_coefficients: polynomial.evaluations.len(),
+                num_powers: size,
+            });
+        }
+        let col_num_vars = key.col_num_vars();
+        let mut rows = vec![0; size];
+        let mut cols = vec![0; size];
+        let mut values = vec![F::zero(); size];
+        for (k, (index, value)) in polynomial.evaluations.iter().enumerate() {
+            rows[k] = index >> col_num_vars;
+            cols[k] = index & ((1 << col_num_vars) - 1);
+            values[k] = *value;
+        }
+        Ok((rows, cols, values))
+    }
+
+    /// Outputs the read timestamps of `addresses` in a memory of
+    /// `memory_size` cells, and its final timestamps, padded to the number of
+    /// addresses.
+    fn timestamps(addresses: &[usize], memory_size: usize) -> (Vec<F>, Vec<F>) {
+        let mut counters = vec![0u64; memory_size];
+        let reads = addresses
+            .iter()
+            .map(|a| {
+                counters[*a] += 1;
+                F::from(counters[*a] - 1)
+            })
+            .collect();
+        let mut finals = counters.into_iter().map(F::from).collect::<Vec<_>>();
+        finals.resize(addresses.len(), F::zero());
+        (reads, finals)
+    }
+
+    /// Outputs the dense vectors encoding `polynomial`.
+    pub fn encode<K: CanonicalSerialize + CanonicalDeserialize>(
+        key: &SparkKey<K>,
+        polynomial: &LabeledPolynomial<F, SparseMultilinearExtension<F>>,
+    ) -> Result<SparseEncoding<Vec<F>>, Error> {
+        let (rows, cols, values) = Self::entries(key, polynomial)?;
+        Ok(Self::encode_entries(key, &rows, &cols, values))
+    }
+
+    /// Outputs the dense vectors encoding the entries output by `entries`.
+    fn encode_entries<K: CanonicalSerialize + CanonicalDeserialize>(
+        key: &SparkKey<K>,
+        rows: &[usize],
+        cols: &[usize],
+        values: Vec<F>,
+    ) -> SparseEncoding<Vec<F>> {
+        let (row_read_timestamps, row_final_timestamps) =
+            Self::timestamps(rows, 1 << key.row_num_vars());
+        let (col_read_timestamps, col_final_timestamps) =
+            Self::timestamps(cols, 1 << key.col_num_vars());
+        let to_field = |addresses: &[usize]| {
+            addresses
+                .iter()
+                .map(|a| F::from(*a as u64))
+                .collect::<Vec<_>>()
+        };
+        SparseEncoding {
+            rows: to_field(rows),
+            row_read_timestamps,
+            row_final_timestamps,
+            cols: to_field(cols),
+            col_read_timestamps,
+            col_final_timestamps,
+            values,
+        }
+    }
+
+    /// Outputs the labelled dense polynomials with evaluations `components`.
+    fn dense_polynomials(
+        polynomial: &LabeledPolynomial<F, SparseMultilinearExtension<F>>,
+        names: &[&str],
+        components: Vec<Vec<F>>,
+        num_vars: usize,
+    ) -> Vec<LabeledPolynomial<F, P>> {
+        names
+            .iter()
+            .zip(components)
+            .map(|(name, evaluations)| {
+                LabeledPolynomial::new(
+                    Self::dense_label(polynomial.label(), name),
+                    P::from_hypercube_evaluations(num_vars, evaluations),
+                    None,
+                    polynomial.hiding_bound(),
+                )
+            })
+            .collect()
+    }
+
+    /// Outputs the labelled commitments to the dense polynomials encoding the
+    /// polynomial labelled `label`, followed by those to its lookups.
+    fn dense_commitments(
+        commitment: &LabeledCommitment<SparseEncoding<PC::Commitment>>,
+        lookups: &[PC::Commitment],
+    ) -> Vec<LabeledCommitment<PC::Commitment>> {
+        let label = commitment.label();
+        SparseEncoding::<PC::Commitment>::NAMES
+            .iter()
+            .zip(commitment.commitment().components())
+            .chain(LOOKUP_NAMES.iter().zip(lookups))
+            .map(|(name, c)| {
+                LabeledCommitment::new(Self::dense_label(label, name), c.clone(), None)
+            })
+            .collect()
+    }
+
+    /// Outputs the queries of the dense polynomials encoding the polynomial
+    /// labelled `label`, given the points left by the sumcheck, the reads and
+    /// the audits of the rows and columns, in the order of the evaluations of
+    /// `SparkProof`.
+    fn queries(
+        label: &str,
+        points: [Vec<F>; 4],
+        num_vars: usize,
+    ) -> Vec<(String, (String, Vec<F>))> {
+        let [sumcheck, reads, mut row_audit, mut col_audit] = points;
+        row_audit.resize(num_vars, F::zero());
+        col_audit.resize(num_vars, F::zero());
+        let queries: [(&[&str], Vec<F>); 4] = [
+            (&["values", "row_lookups", "col_lookups"], sumcheck),
+            (
+                &[
+                    "rows",
+                    "row_read_timestamps",
+                    "row_lookups",
+                    "cols",
+                    "col_read_timestamps",
+                    "col_lookups",
+                ],
+                reads,
+            ),
+            (&["row_final_timestamps"], row_audit),
+            (&["col_final_timestamps"], col_audit),
+        ];
+        POINT_NAMES
+            .iter()
+            .zip(queries)
+            .flat_map(|(point_name, (names, point))| {
+                names.iter().map(move |name| {
+                    (
+                        Self::dense_label(label, name),
+                        (Self::dense_label(label, point_name), point.clone()),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Samples the challenges `gamma` and `tau` of the fingerprints of the
+    /// memory operations.
+    fn fingerprint_challenges(sponge: &mut impl CryptographicSponge) -> (F, F) {
+        let gamma = sponge.challenge(b"fingerprint challenge");
+        let tau = sponge.challenge(b"fingerprint offset");
+        (gamma, tau)
+    }
+
+    /// Outputs the fingerprint `a gamma^2 + v gamma + t - tau` of the memory
+    /// operation at address `a` with value `v` and timestamp `t`.
+    fn fingerprint((gamma, tau): (F, F), a: F, v: F, t: F) -> F {
+        (a * gamma + v) * gamma + t - tau
+    }
+
+    /// Absorbs the statement of an opening.
+    fn absorb_statement<K: CanonicalSerialize + CanonicalDeserialize>(
+        key: &SparkKey<K>,
+        commitment: &SparseEncoding<PC::Commitment>,
+        point: &[F],
+        value: &F,
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<(), Error> {
+        sponge.bind_context(
+            PROTOCOL_NAME,
+            &(key.num_vars as u64, key.dense_num_vars as u64),
+        )?;
+        sponge.absorb_serialized(b"commitment", commitment)?;
+        sponge.absorb_serialized(b"point", point)?;
+        sponge.absorb_serialized(b"value", value)?;
+        Ok(())
+    }
+
+    /// Proves the evaluation of `polynomial` at `point`.
+    fn prove_evaluation(
+        ck: &SparkKey<PC::CommitterKey>,
+        polynomial: &LabeledPolynomial<F, SparseMultilinearExtension<F>>,
+        commitment: &LabeledCommitment<SparseEncoding<PC::Commitment>>,
+        state: &SparseEncoding<PC::CommitmentState>,
+        point: &[F],
+        sponge: &mut impl CryptographicSponge,
+        mut rng: Option<&mut dyn RngCore>,
+    ) -> Result<SparkProof<F, PC::Commitment, PC::BatchProof>, PC::Error> {
+        phase_span!("prove_evaluation", label = %polynomial.label());
+        let label = polynomial.label();
+        if label != commitment.label() {
+            return Err(Error::MismatchedLabels {
+                commitment_label: commitment.label().to_string(),
+                polynomial_label: label.to_string(),
+            }
+            .into());
+        }
+        if point.len() != ck.num_vars {
+            return Err(Error::MismatchedNumVars {
+                poly_nv: ck.num_vars,
+                point_nv: point.len(),
+            }
+            .into());
+        }
+        let num_vars = ck.dense_num_vars;
+        let (rows, cols, values) = Self::entries(ck, polynomial)?;
+        let encoding = Self::encode_entries(ck, &rows, &cols, values);
+        let value = polynomial.evaluate(&point.to_vec());
+        Self::absorb_statement(ck, commitment.commitment(), point, &value, sponge)?;
+
+        // Commits to the entries of the tables read by the evaluations.
+        let (r_col, r_row) = point.split_at(ck.col_num_vars());
+        let tables = [eq_evaluations(r_row), eq_evaluations(r_col)];
+        let lookups = tables
+            .iter()
+            .zip([&rows, &cols])
+            .map(|(table, addresses)| addresses.iter().map(|a| table[*a]).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let lookup_polynomials =
+            Self::dense_polynomials(polynomial, &LOOKUP_NAMES, lookups.clone(), num_vars);
+        let (lookup_commitments, lookup_states) =
+            PC::commit(&ck.inner, &lookup_polynomials, reborrow(&mut rng))?;
+        let lookup_commitments = lookup_commitments
+            .into_iter()
+            .map(|c| c.commitment().clone())
+            .collect::<Vec<_>>();
+        sponge.absorb_serialized(b"lookup commitments", &lookup_commitments)?;
+
+        // The value is the sum of the products of the evaluations with their
+        // lookups.
+        let (sumcheck, sumcheck_point, _) = sumcheck::prove(
+            vec![
+                encoding.values.clone(),
+                lookups[0].clone(),
+                lookups[1].clone(),
+            ],
+            3,
+            |v| v[0] * v[1] * v[2],
+            sponge,
+        )?;
+
+        // The lookups are consistent with the tables.
+        let challenges = Self::fingerprint_challenges(sponge);
+        let mut reads = Vec::with_capacity(4);
+        for ((addresses, timestamps), lookups) in [
+            (&encoding.rows, &encoding.row_read_timestamps),
+            (&encoding.cols, &encoding.col_read_timestamps),
+        ]
+        .iter()
+        .zip(&lookups)
+        {
+            let read = addresses
+                .iter()
+                .zip(lookups)
+                .zip(timestamps.iter())
+                .map(|((a, v), t)| Self::fingerprint(challenges, *a, *v, *t))
+                .collect::<Vec<_>>();
+            let write = read.iter().map(|f| *f + F::one()).collect();
+            reads.push(read);
+            reads.push(write);
+        }
+        let (reads, reads_point) = grand_product::prove(reads, sponge)?;
+        let mut audits = Vec::with_capacity(2);
+        for (table, final_timestamps) in tables.iter().zip([
+            &encoding.row_final_timestamps,
+            &encoding.col_final_timestamps,
+        ]) {
+            let (initial, last) = table
+                .iter()
+                .zip(final_timestamps)
+                .enumerate()
+                .map(|(a, (v, t))| {
+                    let a = F::from(a as u64);
+                    (
+                        Self::fingerprint(challenges, a, *v, F::zero()),
+                        Self::fingerprint(challenges, a, *v, *t),
+                    )
+                })
+                .unzip();
+            audits.push(grand_product::prove(vec![initial, last], sponge)?);
+        }
+        let (col_audit, col_audit_point) = audits.pop().unwrap();
+        let (row_audit, row_audit_point) = audits.pop().unwrap();
+
+        // Opens the dense polynomials at the points left by the reductions.
+        let mut dense = Self::dense_polynomials(
+            polynomial,
+            &SparseEncoding::<Vec<F>>::NAMES,
+            encoding.into_components(),
+            num_vars,
+        );
+        dense.extend(lookup_polynomials);
+        let commitments = Self::dense_commitments(commitment, &lookup_commitments);
+        let states = state
+            .components()
+            .into_iter()
+            .chain(&lookup_states)
+            .collect::<Vec<_>>();
+        let queries = Self::queries(
+            label,
+            [
+                sumcheck_point,
+                reads_point,
+                row_audit_point,
+                col_audit_point,
+            ],
+            num_vars,
+        );
+        let evaluations = queries
+            .iter()
+            .map(|(name, (_, point))| {
+                let polynomial = dense.iter().find(|p| p.label() == name).unwrap();
+                polynomial.evaluate(point)
+            })
+            .collect();
+        let query_set: QuerySet<Vec<F>> = queries.into_iter().collect();
+        let openings = PC::batch_open(
+            &ck.inner,
+            &dense,
+            &commitments,
+            &query_set,
+            sponge,
+            states.iter().copied(),
+            rng,
+        )?;
+        Ok(SparkProof {
+            lookups: lookup_commitments,
+            sumcheck,
+            reads,
+            row_audit,
+            col_audit,
+            evaluations,
+            openings,
+        })
+    }
+
+    /// Checks the proof of the evaluation `value` at `point` of the
+    /// polynomial committed to in `commitment`.
+    fn check_evaluation<R: RngCore>(
+        vk: &SparkKey<PC::VerifierKey>,
+        commitment: &LabeledCommitment<SparseEncoding<PC::Commitment>>,
+        point: &[F],
+        value: F,
+        proof: &SparkProof<F, PC::Commitment, PC::BatchProof>,
+        sponge: &mut impl CryptographicSponge,
+        rng: &mut R,
+    ) -> Result<(), PC::Error> {
+        phase_span!("check_evaluation", label = %commitment.label());
+        let label = commitment.label();
+        let malformed = |reason: &str| -> Error {
+            VerificationFailure::MalformedProof {
+                label: Some(label.clone()),
+                reason: reason.to_string(),
+            }
+            .into()
+        };
+        let mismatch = || -> Error {
+            VerificationFailure::EvaluationMismatch {
+                label: label.clone(),
+                point: format!("{:?}", point),
+            }
+            .into()
+        };
+        if point.len() != vk.num_vars {
+            return Err(Error::MismatchedNumVars {
+                poly_nv: vk.num_vars,
+                point_nv: point.len(),
+            }
+            .into());
+        }
+        if proof.lookups.len() != LOOKUP_NAMES.len() {
+            return Err(malformed("expected commitments to the row and column lookups").into());
+        }
+        let evaluations: &[F; 11] = proof.evaluations[..]
+            .try_into()
+            .map_err(|_| malformed("expected 11 evaluations"))?;
+        let [values, row_lookups, col_lookups, rows, row_read_timestamps, row_lookups_at_reads, cols, col_read_timestamps, col_lookups_at_reads, row_final_timestamps, col_final_timestamps] =
+            *evaluations;
+        let num_vars = vk.dense_num_vars;
+        Self::absorb_statement(vk, commitment.commitment(), point, &value, sponge)?;
+        sponge.absorb_serialized(b"lookup commitments", &proof.lookups)?;
+
+        // The value is the sum of the products of the evaluations with their
+        // lookups.
+        let (sumcheck_point, sumcheck_value) =
+            sumcheck::verify(value, num_vars, 3, &proof.sumcheck, sponge)?;
+        if sumcheck_value != values * row_lookups * col_lookups {
+            return Err(mismatch().into());
+        }
+
+        // The fingerprints of the reads and writes are those of the dense
+        // polynomials.
+        let challenges = Self::fingerprint_challenges(sponge);
+        let (reads_point, reads) = grand_product::verify(4, num_vars, &proof.reads, sponge)?;
+        let row_read =
+            Self::fingerprint(challenges, rows, row_lookups_at_reads, row_read_timestamps);
+        let col_read =
+            Self::fingerprint(challenges, cols, col_lookups_at_reads, col_read_timestamps);
+        if reads != [row_read, row_read + F::one(), col_read, col_read + F::one()] {
+            return Err(mismatch().into());
+        }
+
+        // The fingerprints of the initial and final memories are those of the
+        // tables, which the verifier evaluates itself, with the final
+        // timestamps.
+        let (r_col, r_row) = point.split_at(vk.col_num_vars());
+        let mut audit_points = Vec::with_capacity(2);
+        for ((r, audit), (audit_num_vars, final_timestamps)) in [r_row, r_col]
+            .iter()
+            .zip([&proof.row_audit, &proof.col_audit])
+            .zip([
+                (vk.row_num_vars(), row_final_timestamps),
+                (vk.col_num_vars(), col_final_timestamps),
+            ])
+        {
+            let (audit_point, audits) = grand_product::verify(2, audit_num_vars, audit, sponge)?;
+            let a = identity(&audit_point);
+            let v = eq(r, &audit_point);
+            if audits
+                != [
+                    Self::fingerprint(challenges, a, v, F::zero()),
+                    Self::fingerprint(challenges, a, v, final_timestamps),
+                ]
+            {
+                return Err(mismatch().into());
+            }
+            audit_points.push(audit_point);
+        }
+
+        // The initial memory and the writes are the reads and the final
+        // memory.
+        for (read_products, audit) in proof
+            .reads
+            .products
+            .chunks(2)
+            .zip([&proof.row_audit, &proof.col_audit])
+        {
+            if audit.products[0] * read_products[1] != read_products[0] * audit.products[1] {
+                return Err(mismatch().into());
+            }
+        }
+
+        // The evaluations are those of the committed polynomials.
+        let col_audit_point = audit_points.pop().unwrap();
+        let row_audit_point = audit_points.pop().unwrap();
+        let queries = Self::queries(
+            label,
+            [
+                sumcheck_point,
+                reads_point,
+                row_audit_point,
+                col_audit_point,
+            ],
+            num_vars,
+        );
+        let commitments = Self::dense_commitments(commitment, &proof.lookups);
+        let evaluations: Evaluations<Vec<F>, F> = queries
+            .iter()
+            .zip(&proof.evaluations)
+            .map(|((name, (_, point)), value)| ((name.clone(), point.clone()), *value))
+            .collect();
+        let query_set: QuerySet<Vec<F>> = queries.into_iter().collect();
+        PC::batch_check(
+            &vk.inner,
+            &commitments,
+            &query_set,
+            &evaluations,
+            &proof.openings,
+            sponge,
+            rng,
+        )
+    }
+}
+
+impl<F, P, PC> PolynomialCommitment<F, SparseMultilinearExtension<F>> for SparkPC<F, P, PC>
+where
+    F: PrimeField,
+    P: FromHypercubeEvaluations<F, Point = Vec<F>>,
+    PC: PolynomialCommitment<F, P>,
+    PC::Commitment: 'static,
+    PC::CommitmentState: 'static,
+{
+    type UniversalParams = SparkKey<PC::UniversalParams>;
+    type CommitterKey = SparkKey<PC::CommitterKey>;
+    type VerifierKey = SparkKey<PC::VerifierKey>;
+    type Commitment = SparseEncoding<PC::Commitment>;
+    type CommitmentState = SparseEncoding<PC::CommitmentState>;
+    type Proof = Vec<SparkProof<F, PC::Commitment, PC::BatchProof>>;
+    type BatchProof = Vec<Self::Proof>;
+    type Error = PC::Error;
+
+    /// Sets up `PC` for the dense polynomials encoding `num_vars`-variate
+    /// sparse polynomials with at most `max_degree` non-zero evaluations.
+    fn setup<R: RngCore>(
+        max_degree: usize,
+        num_vars: Option<usize>,
+        rng: &mut R,
+    ) -> Result<Self::UniversalParams, Self::Error> {
+        phase_span!("SparkPC::setup", max_degree, num_vars = ?num_vars);
+        let num_vars = match num_vars {
+            Some(num_vars) if num_vars < usize::BITS as usize => num_vars,
+            _ => return Err(Error::InvalidNumberOfVariables.into()),
+        };
+        let entries_num_vars = max_degree
+            .max(1)
+            .checked_next_power_of_two()
+            .ok_or(Error::InvalidNumberOfVariables)?
+            .trailing_zeros() as usize;
+        let mut dense_num_vars = entries_num_vars.max(num_vars - num_vars / 2).max(1);
+        dense_num_vars += dense_num_vars % 2;
+        if dense_num_vars >= usize::BITS as usize {
+            return Err(Error::InvalidNumberOfVariables.into());
+        }
+        let inner = PC::setup(1, Some(dense_num_vars), rng)?;
+        Ok(SparkKey {
+            inner,
+            num_vars,
+            dense_num_vars,
+        })
+    }
+
+    fn trim(
+        pp: &Self::UniversalParams,
+        supported_degree: usize,
+        supported_hiding_bound: usize,
+        enforced_degree_bounds: Option<&[usize]>,
+    ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
+        phase_span!("SparkPC::trim", supported_degree);
+        if supported_degree > pp.capacity() {
+            return Err(Error::TrimmingDegreeTooLarge.into());
+        }
+        if let Some(degree_bound) = enforced_degree_bounds.and_then(|b| b.first()) {
+            return Err(Error::UnsupportedDegreeBound(*degree_bound).into());
+        }
+        let (ck, vk) = PC::trim(&pp.inner, 1, supported_hiding_bound, None)?;
+        Ok((
+            SparkKey {
+                inner: ck,
+                num_vars: pp.num_vars,
+                dense_num_vars: pp.dense_num_vars,
+            },
+            SparkKey {
+                inner: vk,
+                num_vars: pp.num_vars,
+                dense_num_vars: pp.dense_num_vars,
+            },
+        ))
+    }
+
+    /// Commits to the dense polynomials encoding `polynomials`.
+    fn commit<'a>(
+        ck: &Self::CommitterKey,
+        polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, SparseMultilinearExtension<F>>>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<Self::Commitment>>,
+            Vec<Self::CommitmentState>,
+        ),
+        Self::Error,
+    > {
+        phase_span!("SparkPC::commit");
+        let polynomials = polynomials.into_iter().collect::<Vec<_>>();
+        let mut dense = Vec::with_capacity(7 * polynomials.len());
+        for polynomial in &polynomials {
+            let encoding = Self::encode(ck, polynomial)?;
+            dense.extend(Self::dense_polynomials(
+                polynomial,
+                &SparseEncoding::<Vec<F>>::NAMES,
+                encoding.into_components(),
+                ck.dense_num_vars,
+            ));
+        }
+        let (commitments, states) = PC::commit(&ck.inner, &dense, rng)?;
+        let mut commitments = commitments.into_iter().map(|c| c.commitment().clone());
+        let mut states = states.into_iter();
+        Ok(polynomials
+            .iter()
+            .map(|polynomial| {
+                (
+                    LabeledCommitment::new(
+                        polynomial.label().clone(),
+                        SparseEncoding::from_components(&mut commitments),
+                        None,
+                    ),
+                    SparseEncoding::from_components(&mut states),
+                )
+            })
+            .unzip())
+    }
+
+    /// Proves the evaluation of each polynomial at `point` with a separate
+    /// `SparkProof`.
+    fn open<'a>(
+        ck: &Self::CommitterKey,
+        labeled_polynomials: impl IntoIterator<
+            Item = &'a LabeledPolynomial<F, SparseMultilinearExtension<F>>,
+        >,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a Vec<F>,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
+        mut rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Self::Error>
+    where
+        Self::CommitmentState: 'a,
+        Self::Commitment: 'a,
+    {
+        phase_span!("SparkPC::open");
+        labeled_polynomials
+            .into_iter()
+            .zip(commitments)
+            .zip(states)
+            .map(|((polynomial, commitment), state)| {
+                Self::prove_evaluation(
+                    ck,
+                    polynomial,
+                    commitment,
+                    state,
+                    point,
+                    sponge,
+                    reborrow(&mut rng),
+                )
+            })
+            .collect()
+    }
+
+    fn check<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a Vec<F>,
+        values: impl IntoIterator<Item = F>,
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+        mut rng: Option<&mut dyn RngCore>,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a,
+    {
+        phase_span!("SparkPC::check");
+        let commitments = commitments.into_iter().collect::<Vec<_>>();
+        let values = values.into_iter().collect::<Vec<_>>();
+        if commitments.len() != proof.len() || values.len() != proof.len() {
+            return Err(Error::from(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!(
+                    "expected one proof per commitment and value, found {} for {} commitments \
+                     and {} values",
+                    proof.len(),
+                    commitments.len(),
+                    values.len()
+                ),
+            })
+            .into());
+        }
+        for ((commitment, value), proof) in commitments.into_iter().zip(values).zip(proof) {
+            Self::check_evaluation(
+                vk,
+                commitment,
+                point,
+                value,
+                proof,
+                sponge,
+                &mut OptionalRng(reborrow(&mut rng)),
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{Error, Transcript, VerificationFailure};
+use ark_crypto_primitives::sponge::CryptographicSponge;
+use ark_ff::{batch_inversion, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::format;
+#[cfg(not(feature = "std"))]
+use ark_std::vec::Vec;
+
+/// Proof of a sumcheck over the Boolean hypercube: the evaluations at
+/// `0, 1, ..., d` of the univariate polynomial of degree `d` sent in each
+/// round, the first round being that of the lowest variable.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SumcheckProof<F: PrimeField> {
+    /// The evaluations of the polynomial of each round.
+    pub rounds: Vec<Vec<F>>,
+}
+
+/// A sumcheck proof, along with the point the variables were bound to and the
+/// evaluations of the summed polynomials at that point.
+type Reduction<F> = (SumcheckProof<F>, Vec<F>, Vec<F>);
+
+/// Proves that the sum over the hypercube of `combine` applied to the
+/// evaluations of the multilinear polynomials with evaluation `tables` is
+/// the one known to the verifier, where `combine` has total degree `degree`.
+/// Outputs the proof, the point the variables were bound to and the
+/// evaluations of the polynomials at that point.
+pub(crate) fn prove<F: PrimeField>(
+    mut tables: Vec<Vec<F>>,
+    degree: usize,
+    combine: impl Fn(&[F]) -> F,
+    sponge: &mut impl CryptographicSponge,
+) -> Result<Reduction<F>, Error> {
+    let num_vars = tables[0].len().trailing_zeros() as usize;
+    let mut rounds = Vec::with_capacity(num_vars);
+    let mut point = Vec::with_capacity(num_vars);
+    let mut values = vec![F::zero(); tables.len()];
+    let mut differences = vec![F::zero(); tables.len()];
+    for _ in 0..num_vars {
+        let half = tables[0].len() / 2;
+        let mut evaluations = vec![F::zero(); degree + 1];
+        for i in 0..half {
+            for (j, table) in tables.iter().enumerate() {
+                values[j] = table[2 * i];
+                differences[j] = table[2 * i + 1] - table[2 * i];
+            }
+            for evaluation in evaluations.iter_mut() {
+                *evaluation += combine(&values);
+                for (value, difference) in values.iter_mut().zip(&differences) {
+                    *value += difference;
+                }
+            }
+        }
+
+        sponge.absorb_serialized(b"sumcheck round", &evaluations)?;
+        let r: F = sponge.challenge(b"sumcheck challenge");
+        for table in tables.iter_mut() {
+            for i in 0..half {
+                table[i] = table[2 * i] + r * (table[2 * i + 1] - table[2 * i]);
+            }
+            table.truncate(half);
+        }
+        rounds.push(evaluations);
+        point.push(r);
+    }
+    let evaluations = tables.iter().map(|table| table[0]).collect();
+    Ok((SumcheckProof { rounds }, point, evaluations))
+}
+
+/// Verifies a sumcheck of `claim` over `num_vars` variables with round
+/// polynomials of degree `degree`. Outputs the point the variables were bound
+/// to and the evaluation at that point which the summed polynomial must have.
+pub(crate) fn verify<F: PrimeField>(
+    mut claim: F,
+    num_vars: usize,
+    degree: usize,
+    proof: &SumcheckProof<F>,
+    sponge: &mut impl CryptographicSponge,
+) -> Result<(Vec<F>, F), Error> {
+    if proof.rounds.len() != num_vars {
+        return Err(VerificationFailure::MalformedProof {
+            label: None,
+            reason: format!(
+                "expected {} sumcheck rounds, found {}",
+                num_vars,
+                proof.rounds.len()
+            ),
+        }
+        .into());
+    }
+    let mut point = Vec::with_capacity(num_vars);
+    for (i, evaluations) in proof.rounds.iter().enumerate() {
+        if evaluations.len() != degree + 1 {
+            return Err(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!(
+                    "expected {} evaluations in sumcheck round {}, found {}",
+                    degree + 1,
+                    i,
+                    evaluations.len()
+                ),
+            }
+            .into());
+        }
+        if evaluations[0] + evaluations[1] != claim {
+            return Err(VerificationFailure::MalformedProof {
+                label: None,
+                reason: format!("sumcheck round {} is inconsistent with its claim", i),
+            }
+            .into());
+        }
+        sponge.absorb_serialized(b"sumcheck round", evaluations)?;
+        let r: F = sponge.challenge(b"sumcheck challenge");
+        claim = interpolate_at(evaluations, r);
+        point.push(r);
+    }
+    Ok((point, claim))
+}
+
+/// Evaluates at `x` the polynomial of degree less than `evaluations.len()`
+/// with the given evaluations at `0, 1, 2, ...`.
+fn interpolate_at<F: PrimeField>(evaluations: &[F], x: F) -> F {
+    let nodes = (0..evaluations.len())
+        .map(|i| F::from(i as u64))
+        .collect::<Vec<_>>();
+    let mut denominators = nodes
+        .iter()
+        .map(|xi| {
+            nodes
+                .iter()
+                .filter(|xj| *xj != xi)
+                .map(|xj| *xi - xj)
+                .product::<F>()
+        })
+        .collect::<Vec<_>>();
+    batch_inversion(&mut denominators);
+    nodes
+        .iter()
+        .zip(evaluations)
+        .zip(denominators)
+        .map(|((xi, value), inverse)| {
+            let numerator = nodes
+                .iter()
+                .filter(|xj| *xj != xi)
+                .map(|xj| x - xj)
+                .product::<F>();
+            *value * numerator * inverse
+        })
+        .sum()
+}
@@ -0,0 +1,232 @@
+use super::*;
+use crate::{
+    hyrax::HyraxPC,
+    test_types::{test_sponge, TestMLLigero},
+};
+use ark_bls12_381::Fr;
+use ark_ec::AffineRepr;
+use ark_ed_on_bls12_381::EdwardsAffine;
+use ark_poly::DenseMultilinearExtension;
+use ark_std::{rand::Rng, test_rng, UniformRand};
+
+type JubjubFr = <EdwardsAffine as AffineRepr>::ScalarField;
+type Hyrax = HyraxPC<EdwardsAffine, DenseMultilinearExtension<JubjubFr>>;
+
+/// A random `num_vars`-variate polynomial with `num_entries` non-zero
+/// evaluations, the first of which shares its row with the next two and its
+/// column with the two after.
+fn rand_sparse<F: PrimeField, R: Rng>(
+    num_vars: usize,
+    num_entries: usize,
+    rng: &mut R,
+) -> SparseMultilinearExtension<F> {
+    let mask = (1 << num_vars) - 1;
+    let col_mask = (1 << (num_vars / 2)) - 1;
+    let mut indices = Vec::with_capacity(num_entries);
+    while indices.len() < num_entries {
+        let index = match indices.len() {
+            1 | 2 => indices[0] ^ (rng.gen::<usize>() & col_mask),
+            3 | 4 => indices[0] ^ (rng.gen::<usize>() & mask & !col_mask),
+            _ => rng.gen::<usize>() & mask,
+        };
+        if !indices.contains(&index) {
+            indices.push(index);
+        }
+    }
+    let evaluations = indices
+        .into_iter()
+        .map(|i| (i, F::rand(rng)))
+        .collect::<Vec<_>>();
+    SparseMultilinearExtension::from_evaluations(num_vars, &evaluations)
+}
+
+#[test]
+fn grand_product_test() {
+    let rng = &mut test_rng();
+    for num_vars in [0, 1, 4] {
+        let leaves = (0..3)
+            .map(|_| (0..1 << num_vars).map(|_| Fr::rand(rng)).collect())
+            .collect::<Vec<Vec<Fr>>>();
+        let proof = grand_product::prove(leaves.clone(), &mut test_sponge::<Fr>())
+            .unwrap()
+            .0;
+        for (values, product) in leaves.iter().zip(&proof.products) {
+            assert_eq!(*product, values.iter().product::<Fr>());
+        }
+
+        // The verifier is left with the evaluations of the leaves, unless it
+        // rejects the proof.
+        let accepts = |proof: &GrandProductProof<Fr>| {
+            grand_product::verify(3, num_vars, proof, &mut test_sponge::<Fr>()).is_ok_and(
+                |(point, claims)| {
+                    let eq = eq_evaluations(&point);
+                    leaves.iter().zip(claims).all(|(values, claim)| {
+                        claim == values.iter().zip(&eq).map(|(v, e)| *v * e).sum::<Fr>()
+                    })
+                },
+            )
+        };
+        assert!(accepts(&proof));
+        let mut wrong = proof.clone();
+        wrong.products[1] += Fr::from(1u64);
+        assert!(!accepts(&wrong));
+        if num_vars > 0 {
+            let mut wrong = proof.clone();
+            wrong.layers[0].left[2] += Fr::from(1u64);
+            assert!(!accepts(&wrong));
+        }
+    }
+}
+
+fn spark_test<F, P, PC>(num_vars: usize)
+where
+    F: PrimeField,
+    P: FromHypercubeEvaluations<F, Point = Vec<F>>,
+    PC: PolynomialCommitment<F, P>,
+    PC::Commitment: 'static,
+    PC::CommitmentState: 'static,
+{
+    type Spark<F, P, PC> = SparkPC<F, P, PC>;
+    let rng = &mut test_rng();
+    let sponge = test_sponge::<F>();
+
+    let pp = Spark::<F, P, PC>::setup(40, Some(num_vars), rng).unwrap();
+    let (ck, vk) = Spark::<F, P, PC>::trim(&pp, 40, 1, None).unwrap();
+
+    // The second polynomial has fewer non-zero evaluations than the key
+    // supports.
+    let polynomials = [40, 7]
+        .iter()
+        .enumerate()
+        .map(|(i, num_entries)| {
+            LabeledPolynomial::new(
+                format!("Test{}", i),
+                rand_sparse(num_vars, *num_entries, rng),
+                None,
+                Some(1),
+            )
+        })
+        .collect::<Vec<_>>();
+    let (comms, states) = Spark::<F, P, PC>::commit(&ck, &polynomials, Some(rng)).unwrap();
+
+    let point = (0..num_vars).map(|_| F::rand(rng)).collect::<Vec<_>>();
+    let values: Vec<_> = polynomials.iter().map(|p| p.evaluate(&point)).collect();
+    let proof = Spark::<F, P, PC>::open(
+        &ck,
+        &polynomials,
+        &comms,
+        &point,
+        &mut sponge.clone(),
+        &states,
+        Some(rng),
+    )
+    .unwrap();
+    let check = |values: &[F], proof: &Vec<SparkProof<_, _, _>>| {
+        Spark::<F, P, PC>::check(
+            &vk,
+            &comms,
+            &point,
+            values.iter().copied(),
+            proof,
+            &mut sponge.clone(),
+            None,
+        )
+    };
+    check(&values, &proof).unwrap();
+
+    let wrong_values = [values[0], values[1] + F::one()];
+    assert!(check(&wrong_values, &proof).is_err());
+    let mut wrong = proof.clone();
+    wrong[0].evaluations[4] += F::one();
+    assert!(check(&values, &wrong).is_err());
+    let mut wrong = proof.clone();
+    wrong[0].lookups.swap(0, 1);
+    assert!(check(&values, &wrong).is_err());
+
+    let too_many = LabeledPolynomial::new(
+        "TooMany".into(),
+        rand_sparse(num_vars, ck.supported_degree() + 1, rng),
+        None,
+        None,
+    );
+    assert!(Spark::<F, P, PC>::commit(&ck, [&too_many], Some(rng)).is_err());
+    let wrong_num_vars = LabeledPolynomial::new(
+        "WrongNumVars".into(),
+        rand_sparse(num_vars + 1, 3, rng),
+        None,
+        None,
+    );
+    assert!(Spark::<F, P, PC>::commit(&ck, [&wrong_num_vars], Some(rng)).is_err());
+}
+
+#[test]
+fn encode_test() {
+    type Spark = SparkPC<Fr, SparseMultilinearExtension<Fr>, TestMLLigero<Fr>>;
+    let key = SparkKey {
+        inner: (),
+        num_vars: 5,
+        dense_num_vars: 3,
+    };
+    // The entries `(row, col)` are `(0, 1)`, `(2, 1)` and `(2, 3)`.
+    let evaluations = [
+        (1, Fr::from(5u64)),
+        (9, Fr::from(6u64)),
+        (11, Fr::from(7u64)),
+    ];
+    let polynomial = LabeledPolynomial::new(
+        "Test".into(),
+        SparseMultilinearExtension::from_evaluations(5, &evaluations),
+        None,
+        None,
+    );
+    let encoding = Spark::encode(&key, &polynomial).unwrap();
+    let to_field = |v: &[u64]| v.iter().map(|x| Fr::from(*x)).collect::<Vec<_>>();
+    assert_eq!(encoding.rows, to_field(&[0, 2, 2, 0, 0, 0, 0, 0]));
+    assert_eq!(encoding.cols, to_field(&[1, 1, 3, 0, 0, 0, 0, 0]));
+    assert_eq!(
+        encoding.row_read_timestamps,
+        to_field(&[0, 0, 1, 1, 2, 3, 4, 5])
+    );
+    assert_eq!(
+        encoding.row_final_timestamps,
+        to_field(&[6, 0, 2, 0, 0, 0, 0, 0])
+    );
+    assert_eq!(
+        encoding.col_read_timestamps,
+        to_field(&[0, 1, 0, 0, 1, 2, 3, 4])
+    );
+    assert_eq!(
+        encoding.col_final_timestamps,
+        to_field(&[5, 2, 0, 1, 0, 0, 0, 0])
+    );
+    assert_eq!(encoding.values[..3], to_field(&[5, 6, 7]));
+
+    // The evaluations are those of the sum of the values weighted by the
+    // tables of their rows and columns.
+    let rng = &mut test_rng();
+    let point = (0..5).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    let (r_col, r_row) = point.split_at(2);
+    let (rows, cols) = (eq_evaluations(r_row), eq_evaluations(r_col));
+    let expected = evaluations
+        .iter()
+        .map(|(i, v)| *v * rows[i >> 2] * cols[i & 3])
+        .sum::<Fr>();
+    assert_eq!(polynomial.evaluate(&point), expected);
+    assert_eq!(identity(&to_field(&[1, 0, 1])), Fr::from(5u64));
+}
+
+#[test]
+fn hyrax_spark_test() {
+    // The rows and columns have the same number of variables.
+    spark_test::<JubjubFr, DenseMultilinearExtension<JubjubFr>, Hyrax>(12);
+}
+
+#[test]
+fn hyrax_spark_odd_num_vars_test() {
+    spark_test::<JubjubFr, DenseMultilinearExtension<JubjubFr>, Hyrax>(9);
+}
+
+#[test]
+fn ligero_spark_test() {
+    spark_test::<Fr, SparseMultilinearExtension<Fr>, TestMLLigero<Fr>>(14);
+}
@@ -0,0 +1,686 @@
+use crate::Error;
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(not(feature = "std"))]
+use ark_std::string::ToString;
+use ark_std::{
+    borrow::Cow, boxed::Box, convert::TryFrom, format, io::Write, marker::PhantomData, ops::Range,
+    vec::Vec,
+};
+use blake2::Blake2s256;
+use digest::Digest;
+
+#[cfg(feature = "parallel")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+/// Identifies SRS files, and the version of their format.
+const MAGIC: &[u8; 8] = b"ARKSRS01";
+
+/// The number of points in each chunk of a section. Each chunk has its own
+/// digest, so only the chunks that are read have to be hashed.
+const CHUNK_LEN: usize = 1 << 16;
+
+const DIGEST_BYTES: usize = 32;
+
+type ChunkDigest = [u8; DIGEST_BYTES];
+
+fn digest(bytes: &[u8]) -> ChunkDigest {
+    let mut out = [0u8; DIGEST_BYTES];
+    out.copy_from_slice(&Blake2s256::digest(bytes));
+    out
+}
+
+fn malformed(reason: &str) -> Error {
+    Error::InvalidParameters(format!("malformed SRS file: {}", reason))
+}
+
+/// The number of chunks of `chunk_len` points needed to hold `len` points.
+fn num_chunks(len: usize, chunk_len: usize) -> usize {
+    len.div_ceil(chunk_len)
+}
+
+/// The checksum of an SRS file: the digest of its header, which holds the
+/// digests of the metadata and of every chunk of points.
+///
+/// It is returned when the file is written or validated in full, and binds
+/// the file to that validation: a file opened with [`SrsFile::open`] under
+/// the same checksum can be used without validating its points again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SrsChecksum(pub [u8; DIGEST_BYTES]);
+
+/// Universal parameters that can be stored in an [`SrsFile`], and loaded
+/// lazily from it.
+pub trait FileBackedSrs: Sized {
+    /// Identifies the type of the parameters in the header of the file.
+    const TAG: [u8; 8];
+
+    /// Adds the metadata and the sections of points of `self` to `builder`.
+    fn build<'a>(&'a self, builder: &mut SrsFileBuilder<'a>) -> Result<(), Error>;
+
+    /// Validates the metadata and every point of `file`.
+    fn validate(file: &SrsFile<'_, Self>) -> Result<(), Error>;
+
+    /// Reads from `file` the parameters supporting `supported_size`, which is
+    /// a degree or a number of variables, without validating the points.
+    fn load(file: &SrsFile<'_, Self>, supported_size: usize) -> Result<Self, Error>;
+}
+
+/// Serializes the points of a section at the given range.
+type SerializeRange<'a> = Box<dyn Fn(Range<usize>) -> Result<Vec<u8>, Error> + Send + Sync + 'a>;
+
+/// A section of points to be written, serialized chunk by chunk.
+struct SectionSource<'a> {
+    len: usize,
+    item_size: usize,
+    serialize: SerializeRange<'a>,
+}
+
+impl SectionSource<'_> {
+    fn chunk(&self, index: usize, chunk_len: usize) -> Result<Vec<u8>, Error> {
+        let start = index * chunk_len;
+        let end = (start + chunk_len).min(self.len);
+        let bytes = (self.serialize)(start..end)?;
+        if bytes.len() != (end - start) * self.item_size {
+            return Err(Error::InvalidParameters(
+                "the points of a section must all have the same size".to_string(),
+            ));
+        }
+        Ok(bytes)
+    }
+}
+
+/// Collects the metadata and the sections of points of the parameters being
+/// written to an [`SrsFile`].
+pub struct SrsFileBuilder<'a> {
+    metadata: Vec<u8>,
+    sections: Vec<SectionSource<'a>>,
+}
+
+impl<'a> SrsFileBuilder<'a> {
+    /// Sets the metadata: the part of the parameters that is small enough to
+    /// be validated in full whenever the file is read.
+    pub fn metadata<T: CanonicalSerialize>(&mut self, metadata: &T) -> Result<(), Error> {
+        self.metadata.clear();
+        metadata
+            .serialize_uncompressed(&mut self.metadata)
+            .map_err(|e| Error::InvalidParameters(format!("could not serialize: {}", e)))
+    }
+
+    /// Appends a section holding `points`, which must all have the same
+    /// uncompressed size.
+    pub fn section<G: CanonicalSerialize + Sync>(&mut self, points: &'a [G]) {
+        self.section_with(points.len(), move |i| &points[i])
+    }
+
+    /// Appends a section holding the `len` points `point(0), point(1), ...`,
+    /// which must all have the same uncompressed size.
+    pub fn section_with<G, F>(&mut self, len: usize, point: F)
+    where
+        G: CanonicalSerialize + 'a,
+        F: Fn(usize) -> &'a G + Send + Sync + 'a,
+    {
+        let item_size = if len == 0 {
+            0
+        } else {
+            point(0).uncompressed_size()
+        };
+        self.sections.push(SectionSource {
+            len,
+            item_size,
+            serialize: Box::new(move |range| {
+                let mut bytes = Vec::new();
+                for i in range {
+                    point(i).serialize_uncompressed(&mut bytes).map_err(|e| {
+                        Error::InvalidParameters(format!("could not serialize: {}", e))
+                    })?;
+                }
+                Ok(bytes)
+            }),
+        });
+    }
+}
+
+struct Section {
+    offset: usize,
+    item_size: usize,
+    len: usize,
+    digests: Vec<ChunkDigest>,
+}
+
+/// A reader of an SRS file, which is only ever read at given offsets.
+#[cfg(feature = "std")]
+trait SeekRead: std::io::Read + std::io::Seek {}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read + std::io::Seek> SeekRead for R {}
+
+/// Where the bytes of an SRS file are read from.
+enum Source<'a> {
+    Bytes(&'a [u8]),
+    /// A reader, and the length of the file it reads.
+    #[cfg(feature = "std")]
+    Reader(std::sync::Mutex<Box<dyn SeekRead + Send + 'a>>, usize),
+}
+
+impl<'a> Source<'a> {
+    fn len(&self) -> usize {
+        match self {
+            Source::Bytes(bytes) => bytes.len(),
+            #[cfg(feature = "std")]
+            Source::Reader(_, len) => *len,
+        }
+    }
+
+    /// The bytes at `range`, which must be within the file.
+    fn read(&self, range: Range<usize>) -> Result<Cow<'a, [u8]>, Error> {
+        match self {
+            Source::Bytes(bytes) => Ok(Cow::Borrowed(&bytes[range])),
+            #[cfg(feature = "std")]
+            Source::Reader(reader, _) => {
+                let read_error =
+                    |e: std::io::Error| Error::InvalidParameters(format!("could not read: {}", e));
+                let mut reader = reader.lock().map_err(|_| {
+                    Error::InvalidParameters("could not read: a read panicked".to_string())
+                })?;
+                reader
+                    .seek(std::io::SeekFrom::Start(range.start as u64))
+                    .map_err(read_error)?;
+                let mut bytes = vec![0u8; range.len()];
+                reader.read_exact(&mut bytes).map_err(read_error)?;
+                Ok(Cow::Owned(bytes))
+            }
+        }
+    }
+}
+
+/// Reads the header of an SRS file.
+struct HeaderReader<'s, 'a> {
+    source: &'s Source<'a>,
+    pos: usize,
+}
+
+impl<'a> HeaderReader<'_, 'a> {
+    fn take(&mut self, n: usize) -> Result<Cow<'a, [u8]>, Error> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.source.len())
+            .ok_or_else(|| malformed("the header is truncated"))?;
+        let bytes = self.source.read(self.pos..end)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn read_usize(&mut self) -> Result<usize, Error> {
+        let mut le_bytes = [0u8; 8];
+        le_bytes.copy_from_slice(&self.take(8)?);
+        usize::try_from(u64::from_le_bytes(le_bytes)).map_err(|_| malformed("length overflow"))
+    }
+
+    fn read_digest(&mut self) -> Result<ChunkDigest, Error> {
+        let mut out = [0u8; DIGEST_BYTES];
+        out.copy_from_slice(&self.take(DIGEST_BYTES)?);
+        Ok(out)
+    }
+}
+
+/// The parameters `P` stored in an SRS file, either given as bytes or, with
+/// the `std` feature, read from a file as they are needed.
+///
+/// The file holds the points uncompressed, split into sections and chunks.
+/// It is validated in full once, by [`SrsFile::write`] or
+/// [`SrsFile::validate`], which return its [`SrsChecksum`]. Afterwards,
+/// [`SrsFile::open`] only hashes the header, and the points of a chunk are
+/// decoded without validation once the chunk matches its digest.
+///
+/// A file opened with [`SrsFile::open_reader`] is read with `Seek` and
+/// `Read` calls, one chunk at a time, so that only the header and the points
+/// being loaded are held in memory: trimming the parameters of a large file
+/// reads and decodes only the prefix of each section that is kept.
+pub struct SrsFile<'a, P> {
+    source: Source<'a>,
+    chunk_len: usize,
+    metadata: Range<usize>,
+    metadata_digest: ChunkDigest,
+    sections: Vec<Section>,
+    checksum: SrsChecksum,
+    _params: PhantomData<fn() -> P>,
+}
+
+impl<'a, P: FileBackedSrs> SrsFile<'a, P> {
+    /// Writes `params` to `writer`, and outputs the checksum of the file.
+    /// The parameters are trusted to be valid.
+    pub fn write<W: Write>(params: &P, writer: W) -> Result<SrsChecksum, Error> {
+        Self::write_with_chunk_len(params, writer, CHUNK_LEN)
+    }
+
+    pub(crate) fn write_with_chunk_len<W: Write>(
+        params: &P,
+        mut writer: W,
+        chunk_len: usize,
+    ) -> Result<SrsChecksum, Error> {
+        let mut builder = SrsFileBuilder {
+            metadata: Vec::new(),
+            sections: Vec::new(),
+        };
+        params.build(&mut builder)?;
+
+        let mut header = Vec::new();
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&P::TAG);
+        for n in [chunk_len, builder.metadata.len()] {
+            header.extend_from_slice(&(n as u64).to_le_bytes());
+        }
+        header.extend_from_slice(&digest(&builder.metadata));
+        header.extend_from_slice(&(builder.sections.len() as u64).to_le_bytes());
+        for section in &builder.sections {
+            for n in [section.item_size, section.len] {
+                header.extend_from_slice(&(n as u64).to_le_bytes());
+            }
+            let digests = cfg_into_iter!(0..num_chunks(section.len, chunk_len))
+                .map(|i| Ok(digest(&section.chunk(i, chunk_len)?)))
+                .collect::<Result<Vec<_>, Error>>()?;
+            for d in digests {
+                header.extend_from_slice(&d);
+            }
+        }
+
+        let io_error =
+            |e: ark_std::io::Error| Error::InvalidParameters(format!("could not write: {}", e));
+        writer.write_all(&header).map_err(io_error)?;
+        writer.write_all(&builder.metadata).map_err(io_error)?;
+        for section in &builder.sections {
+            for i in 0..num_chunks(section.len, chunk_len) {
+                writer
+                    .write_all(&section.chunk(i, chunk_len)?)
+                    .map_err(io_error)?;
+            }
+        }
+        Ok(SrsChecksum(digest(&header)))
+    }
+
+    /// Validates the whole file, and outputs its checksum. This is as slow as
+    /// deserializing the parameters with validation, and only needs to be
+    /// done once.
+    pub fn validate(bytes: &'a [u8]) -> Result<SrsChecksum, Error> {
+        Self::validate_source(Source::Bytes(bytes))
+    }
+
+    /// Opens a file that was written or validated with the given `checksum`.
+    /// Only the header is hashed: the points are checked against their
+    /// digests when they are read.
+    pub fn open(bytes: &'a [u8], checksum: &SrsChecksum) -> Result<Self, Error> {
+        Self::open_source(Source::Bytes(bytes), checksum)
+    }
+
+    /// Validates the whole file read by `reader`, e.g. a `std::fs::File`, and
+    /// outputs its checksum, as [`SrsFile::validate`]. The file is read one
+    /// chunk at a time.
+    #[cfg(feature = "std")]
+    pub fn validate_reader<R>(reader: R) -> Result<SrsChecksum, Error>
+    where
+        R: std::io::Read + std::io::Seek + Send + 'a,
+    {
+        Self::validate_source(Self::reader_source(reader)?)
+    }
+
+    /// Opens the file read by `reader`, e.g. a `std::fs::File`, as
+    /// [`SrsFile::open`]. Only the header is read here: the chunks of points
+    /// are read from `reader` when they are loaded.
+    #[cfg(feature = "std")]
+    pub fn open_reader<R>(reader: R, checksum: &SrsChecksum) -> Result<Self, Error>
+    where
+        R: std::io::Read + std::io::Seek + Send + 'a,
+    {
+        Self::open_source(Self::reader_source(reader)?, checksum)
+    }
+
+    #[cfg(feature = "std")]
+    fn reader_source<R>(mut reader: R) -> Result<Source<'a>, Error>
+    where
+        R: std::io::Read + std::io::Seek + Send + 'a,
+    {
+        let len = reader
+            .seek(std::io::SeekFrom::End(0))
+            .map_err(|e| Error::InvalidParameters(format!("could not read: {}", e)))?;
+        let len = usize::try_from(len).map_err(|_| malformed("length overflow"))?;
+        Ok(Source::Reader(std::sync::Mutex::new(Box::new(reader)), len))
+    }
+
+    fn validate_source(source: Source<'a>) -> Result<SrsChecksum, Error> {
+        let file = Self::parse(source)?;
+        P::validate(&file)?;
+        Ok(file.checksum)
+    }
+
+    fn open_source(source: Source<'a>, checksum: &SrsChecksum) -> Result<Self, Error> {
+        let file = Self::parse(source)?;
+        if file.checksum != *checksum {
+            return Err(malformed("the checksum does not match"));
+        }
+        Ok(file)
+    }
+
+    fn parse(source: Source<'a>) -> Result<Self, Error> {
+        let mut reader = HeaderReader {
+            source: &source,
+            pos: 0,
+        };
+        if *reader.take(MAGIC.len())? != MAGIC[..] {
+            return Err(malformed("unknown format"));
+        }
+        if *reader.take(P::TAG.len())? != P::TAG[..] {
+            return Err(malformed("the parameters are of another type"));
+        }
+        let chunk_len = reader.read_usize()?;
+        if chunk_len == 0 {
+            return Err(malformed("empty chunks"));
+        }
+        let metadata_len = reader.read_usize()?;
+        let metadata_digest = reader.read_digest()?;
+
+        let num_sections = reader.read_usize()?;
+        let mut sections = Vec::new();
+        for _ in 0..num_sections {
+            let item_size = reader.read_usize()?;
+            let len = reader.read_usize()?;
+            let digest_bytes = num_chunks(len, chunk_len)
+                .checked_mul(DIGEST_BYTES)
+                .ok_or_else(|| malformed("length overflow"))?;
+            let digests = reader
+                .take(digest_bytes)?
+                .chunks_exact(DIGEST_BYTES)
+                .map(|d| {
+                    let mut out = [0u8; DIGEST_BYTES];
+                    out.copy_from_slice(d);
+                    out
+                })
+                .collect();
+            sections.push(Section {
+                offset: 0,
+                item_size,
+                len,
+                digests,
+            });
+        }
+        let header_len = reader.pos;
+        let checksum = SrsChecksum(digest(&source.read(0..header_len)?));
+
+        let overflow = || malformed("length overflow");
+        let metadata = header_len..header_len.checked_add(metadata_len).ok_or_else(overflow)?;
+        let mut offset = metadata.end;
+        for section in &mut sections {
+            section.offset = offset;
+            let size = section.item_size.checked_mul(section.len);
+            offset = size
+                .and_then(|size| offset.checked_add(size))
+                .ok_or_else(overflow)?;
+        }
+        if offset != source.len() {
+            return Err(malformed("the file does not have the length of its header"));
+        }
+
+        Ok(Self {
+            source,
+            chunk_len,
+            metadata,
+            metadata_digest,
+            sections,
+            checksum,
+            _params: PhantomData,
+        })
+    }
+
+    /// The checksum of the file.
+    pub fn checksum(&self) -> SrsChecksum {
+        self.checksum
+    }
+
+    /// Reads the parameters supporting `supported_size`, which is a degree or
+    /// a number of variables, as in `P::load`.
+    pub fn load(&self, supported_size: usize) -> Result<P, Error> {
+        P::load(self, supported_size)
+    }
+
+    /// The number of sections of points in the file.
+    pub fn num_sections(&self) -> usize {
+        self.sections.len()
+    }
+
+    /// The number of points in the section at `index`, if there is one.
+    pub fn section_len(&self, index: usize) -> Option<usize> {
+        self.sections.get(index).map(|s| s.len)
+    }
+
+    /// Reads and validates the metadata.
+    pub fn metadata<T: CanonicalDeserialize>(&self) -> Result<T, Error> {
+        let metadata_bytes = self.source.read(self.metadata.clone())?;
+        let mut bytes = &*metadata_bytes;
+        if digest(bytes) != self.metadata_digest {
+            return Err(malformed("the metadata does not match its digest"));
+        }
+        let metadata = T::deserialize_uncompressed(&mut bytes)
+            .map_err(|e| Error::InvalidEncoding(format!("{}", e)))?;
+        if !bytes.is_empty() {
+            return Err(malformed("trailing bytes after the metadata"));
+        }
+        Ok(metadata)
+    }
+
+    fn section(&self, index: usize) -> Result<&Section, Error> {
+        self.sections
+            .get(index)
+            .ok_or_else(|| malformed("missing section"))
+    }
+
+    /// The bytes of the chunk at `index` of `section`, once they match their
+    /// digest.
+    fn chunk(&self, section: &Section, index: usize) -> Result<Cow<'a, [u8]>, Error> {
+        let start = index * self.chunk_len;
+        let end = (start + self.chunk_len).min(section.len);
+        let offset = section.offset + start * section.item_size;
+        let bytes = self
+            .source
+            .read(offset..offset + (end - start) * section.item_size)?;
+        if digest(&bytes) != section.digests[index] {
+            return Err(malformed("a chunk of points does not match its digest"));
+        }
+        Ok(bytes)
+    }
+
+    /// Decodes the points of `bytes`, without validating them.
+    fn decode<G: CanonicalDeserialize>(bytes: &[u8], item_size: usize) -> Result<Vec<G>, Error> {
+        bytes
+            .chunks_exact(item_size)
+            .map(|mut point_bytes| {
+                let point = G::deserialize_uncompressed_unchecked(&mut point_bytes)
+                    .map_err(|e| Error::InvalidEncoding(format!("{}", e)))?;
+                if !point_bytes.is_empty() {
+                    return Err(malformed("the points do not have the size of the section"));
+                }
+                Ok(point)
+            })
+            .collect()
+    }
+
+    /// Reads the points at `range` of the section at `index`, decoded into
+    /// an owned vector. Only the chunks overlapping `range` are hashed, and
+    /// the points are not validated.
+    pub fn read_section<G: CanonicalDeserialize + Send>(
+        &self,
+        index: usize,
+        range: Range<usize>,
+    ) -> Result<Vec<G>, Error> {
+        let section = self.section(index)?;
+        if range.end > section.len {
+            return Err(Error::InvalidIndex {
+                index: range.end,
+                len: section.len,
+            });
+        }
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        if section.item_size == 0 {
+            return Err(malformed("empty points"));
+        }
+
+        let first = range.start / self.chunk_len;
+        let last = (range.end - 1) / self.chunk_len;
+        let chunks = cfg_into_iter!(first..=last)
+            .map(|i| {
+                let bytes = self.chunk(section, i)?;
+                let start = range.start.max(i * self.chunk_len) - i * self.chunk_len;
+                let end = range.end.min((i + 1) * self.chunk_len) - i * self.chunk_len;
+                Self::decode(
+                    &bytes[start * section.item_size..end * section.item_size],
+                    section.item_size,
+                )
+            })
+            .collect::<Result<Vec<Vec<G>>, Error>>()?;
+        Ok(chunks.into_iter().flatten().collect())
+    }
+
+    /// Validates every point of the section at `index`, one chunk at a time.
+    pub fn validate_section<G: CanonicalDeserialize + Send + Sync>(
+        &self,
+        index: usize,
+    ) -> Result<(), Error> {
+        let section = self.section(index)?;
+        if section.len != 0 && section.item_size == 0 {
+            return Err(malformed("empty points"));
+        }
+        cfg_into_iter!(0..section.digests.len()).try_for_each(|i| {
+            let points = Self::decode::<G>(&self.chunk(section, i)?, section.item_size)?;
+            G::batch_check(points.iter()).map_err(|e| Error::InvalidEncoding(format!("{}", e)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ipa_pc::{self, InnerProductArgPC},
+        kzg10::{self, KZG10},
+        multilinear_pc::{data_structures as multilinear_pc, MultilinearPC},
+        PolynomialCommitment,
+    };
+
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::pairing::Pairing;
+    use ark_ed_on_bls12_381::EdwardsAffine;
+    use ark_poly::univariate::DensePolynomial;
+    use ark_std::{borrow::Cow, test_rng};
+
+    type Fr = <Bls12_381 as Pairing>::ScalarField;
+    type Kzg = KZG10<Bls12_381, DensePolynomial<Fr>>;
+    type KzgFile<'a> = SrsFile<'a, kzg10::UniversalParams<Bls12_381>>;
+
+    type EdFr = <EdwardsAffine as ark_ec::AffineRepr>::ScalarField;
+    type Ipa = InnerProductArgPC<EdwardsAffine, Blake2s256, DensePolynomial<EdFr>>;
+    type IpaFile<'a> = SrsFile<'a, ipa_pc::UniversalParams<EdwardsAffine>>;
+
+    type MlFile<'a> = SrsFile<'a, multilinear_pc::UniversalParams<Bls12_381>>;
+
+    #[test]
+    fn test_kzg10_file() {
+        let rng = &mut test_rng();
+        let pp = Kzg::setup(40, true, rng).unwrap();
+        let mut bytes = Vec::new();
+        let checksum = KzgFile::write_with_chunk_len(&pp, &mut bytes, 16).unwrap();
+        assert_eq!(KzgFile::validate(&bytes).unwrap(), checksum);
+
+        // The loaded parameters are a prefix of the original ones.
+        let file = KzgFile::open(&bytes, &checksum).unwrap();
+        assert_eq!(file.load(40).unwrap(), pp);
+        let loaded = file.load(20).unwrap();
+        assert_eq!(loaded.powers_of_g[..], pp.powers_of_g[..=20]);
+        assert_eq!(loaded.powers_of_gamma_g[&21], pp.powers_of_gamma_g[&21]);
+        assert_eq!(loaded.neg_powers_of_h[&20], pp.neg_powers_of_h[&20]);
+        assert!(file.load(41).is_err());
+
+        let (file_powers, vk) = file.trim(10).unwrap();
+        let powers = file_powers.powers();
+        assert!(matches!(powers.powers_of_g, Cow::Borrowed(_)));
+        assert_eq!(powers.powers_of_g[..], pp.powers_of_g[..=10]);
+        assert_eq!(powers.powers_of_gamma_g.len(), 11);
+        assert_eq!(vk.beta_h, pp.beta_h);
+
+        // Another checksum is rejected, and so is a corrupted point, once it
+        // is read.
+        assert!(KzgFile::open(&bytes, &SrsChecksum([0u8; 32])).is_err());
+        drop(file);
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        assert!(KzgFile::validate(&bytes).is_err());
+        let file = KzgFile::open(&bytes, &checksum).unwrap();
+        assert!(file.trim(10).is_ok());
+        assert!(file.load(40).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_kzg10_file_reader() {
+        let rng = &mut test_rng();
+        let pp = Kzg::setup(40, true, rng).unwrap();
+        let path = std::env::temp_dir().join(format!("kzg10-{}.srs", std::process::id()));
+        let checksum =
+            KzgFile::write_with_chunk_len(&pp, std::fs::File::create(&path).unwrap(), 16).unwrap();
+        let open = || std::fs::File::open(&path).unwrap();
+        assert_eq!(KzgFile::validate_reader(open()).unwrap(), checksum);
+
+        let file = KzgFile::open_reader(open(), &checksum).unwrap();
+        assert_eq!(file.load(40).unwrap(), pp);
+        let (file_powers, vk) = file.trim(10).unwrap();
+        let powers = file_powers.powers();
+        assert!(matches!(powers.powers_of_g, Cow::Borrowed(_)));
+        assert_eq!(powers.powers_of_g[..], pp.powers_of_g[..=10]);
+        assert_eq!(vk.beta_h, pp.beta_h);
+        assert!(KzgFile::open_reader(open(), &SrsChecksum([0u8; 32])).is_err());
+
+        // Only the chunks that are read are checked against their digests.
+        let mut bytes = std::fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        std::fs::remove_file(&path).unwrap();
+        let reader = || std::io::Cursor::new(bytes.as_slice());
+        assert!(KzgFile::validate_reader(reader()).is_err());
+        let file = KzgFile::open_reader(reader(), &checksum).unwrap();
+        assert!(file.trim(10).is_ok());
+        assert!(file.load(40).is_err());
+    }
+
+    #[test]
+    fn test_ipa_pc_file() {
+        let rng = &mut test_rng();
+        let pp = Ipa::setup(31, None, rng).unwrap();
+        let mut bytes = Vec::new();
+        let checksum = IpaFile::write(&pp, &mut bytes).unwrap();
+        assert_eq!(IpaFile::validate(&bytes).unwrap(), checksum);
+
+        let file = IpaFile::open(&bytes, &checksum).unwrap();
+        let loaded = file.load(15).unwrap();
+        assert_eq!(loaded.comm_key[..], pp.comm_key[..=15]);
+        assert_eq!((loaded.h, loaded.s), (pp.h, pp.s));
+        assert!(file.load(32).is_err());
+    }
+
+    #[test]
+    fn test_multilinear_pc_file() {
+        let rng = &mut test_rng();
+        let pp = MultilinearPC::<Bls12_381>::setup(5, rng);
+        let mut bytes = Vec::new();
+        let checksum = MlFile::write(&pp, &mut bytes).unwrap();
+        assert_eq!(MlFile::validate(&bytes).unwrap(), checksum);
+
+        let file = MlFile::open(&bytes, &checksum).unwrap();
+        let loaded = file.load(3).unwrap();
+        let (ck, vk) = MultilinearPC::trim(&loaded, 3);
+        let (expected_ck, expected_vk) = MultilinearPC::trim(&pp, 3);
+        assert_eq!(ck.powers_of_g, expected_ck.powers_of_g);
+        assert_eq!(ck.powers_of_h, expected_ck.powers_of_h);
+        assert_eq!(vk.g_mask_random, expected_vk.g_mask_random);
+        assert!(file.load(6).is_err());
+    }
+}
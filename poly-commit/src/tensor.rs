@@ -0,0 +1,148 @@
+use crate::{Error, LabeledCommitment, LabeledPolynomial, PolynomialCommitment, Transcript};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ff::{Field, PrimeField};
+use ark_poly::Polynomial;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{fmt::Debug, format, rand::RngCore};
+#[cfg(not(feature = "std"))]
+use ark_std::{string::String, vec::Vec};
+
+/// The tensor-structured linear functional `t = b ⊗ a`, on vectors of length
+/// `a.len() * b.len()`, with `t[i * a.len() + j] = b[i] a[j]`.
+///
+/// The schemes implementing `TensorOpenings` arrange the vector `f` of the
+/// coefficients of a polynomial, or of its evaluations over the Boolean
+/// hypercube for multilinear polynomials, into a matrix `M` with `a.len()`
+/// columns in row-major order, so that `<f, t> = b^T M a`. An evaluation at
+/// a point is the special case where `a` and `b` are derived from the point.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Tensor<F: Field> {
+    /// The factor applied to the columns of `M`.
+    pub a: Vec<F>,
+    /// The factor applied to the rows of `M`.
+    pub b: Vec<F>,
+}
+
+impl<F: Field> Tensor<F> {
+    /// Outputs the tensor `b ⊗ a`.
+    pub fn new(a: Vec<F>, b: Vec<F>) -> Self {
+        Self { a, b }
+    }
+
+    /// Outputs the dense vector `b ⊗ a`.
+    pub fn to_vec(&self) -> Vec<F> {
+        self.b
+            .iter()
+            .flat_map(|b_i| self.a.iter().map(move |a_j| *b_i * a_j))
+            .collect()
+    }
+
+    /// Outputs `<f, b ⊗ a>`, where `f` is padded with zeroes to the length
+    /// of the tensor. Fails if `f` is longer than the tensor.
+    pub fn apply(&self, f: &[F]) -> Result<F, Error> {
+        if f.len() > self.a.len() * self.b.len() {
+            return Err(Error::IncorrectInputLength(format!(
+                "Expected at most {} entries, found {}",
+                self.a.len() * self.b.len(),
+                f.len()
+            )));
+        }
+        if self.a.is_empty() {
+            return Ok(F::zero());
+        }
+        Ok(f.chunks(self.a.len())
+            .zip(&self.b)
+            .map(|(row, b_i)| row.iter().zip(&self.a).map(|(f, a_j)| *f * a_j).sum::<F>() * b_i)
+            .sum())
+    }
+
+    /// Fails unless the tensor applies to matrices with `n_cols` columns and
+    /// `n_rows` rows.
+    pub(crate) fn check_dimensions(&self, n_cols: usize, n_rows: usize) -> Result<(), Error> {
+        if self.a.len() != n_cols || self.b.len() != n_rows {
+            return Err(Error::IncorrectInputLength(format!(
+                "Expected a tensor of dimensions ({}, {}), found ({}, {})",
+                n_cols,
+                n_rows,
+                self.a.len(),
+                self.b.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<F: PrimeField + Absorb> Tensor<F> {
+    /// Absorbs the tensor in place of the point of an opening.
+    pub(crate) fn absorb(&self, sponge: &mut impl CryptographicSponge) {
+        sponge.absorb_labelled(b"tensor a", &self.a);
+        sponge.absorb_labelled(b"tensor b", &self.b);
+    }
+}
+
+/// Describes the interface of the polynomial commitment schemes which can
+/// prove the values `<f, t>` of tensor-structured linear functionals
+/// `t = b ⊗ a` on the committed vectors `f`, as described in `Tensor`.
+///
+/// The proofs are those of `open`, and are bound to the tensor instead of the
+/// point in the transcript.
+pub trait TensorOpenings<F: PrimeField, P: Polynomial<F>>: PolynomialCommitment<F, P> {
+    /// Outputs the dimensions `(a.len(), b.len())` of the tensors at which
+    /// `commitment` can be opened.
+    fn tensor_dimensions(commitment: &Self::Commitment) -> (usize, usize);
+
+    /// On input a list of labeled polynomials, their commitments and states,
+    /// and a tensor, outputs a proof of the values of the tensor on the
+    /// committed vectors.
+    fn open_tensor<'a>(
+        ck: &Self::CommitterKey,
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, P>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        tensor: &Tensor<F>,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Self::Error>
+    where
+        P: 'a,
+        Self::CommitmentState: 'a,
+        Self::Commitment: 'a;
+
+    /// Verifies that `values` are the values of `tensor` on the vectors
+    /// committed inside `commitments`.
+    fn check_tensor<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        tensor: &Tensor<F>,
+        values: impl IntoIterator<Item = F>,
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a;
+}
+
+/// The query of an opening: a point of the polynomials, or a tensor.
+pub(crate) enum Query<'a, F: Field, Pt> {
+    Point(&'a Pt),
+    Tensor(&'a Tensor<F>),
+}
+
+impl<'a, F: Field, Pt> Clone for Query<'a, F, Pt> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, F: Field, Pt> Copy for Query<'a, F, Pt> {}
+
+impl<'a, F: Field, Pt: Debug> Query<'a, F, Pt> {
+    /// Describes the query in the errors of the verifier.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Query::Point(point) => format!("{:?}", point),
+            Query::Tensor(tensor) => format!("{:?}", tensor),
+        }
+    }
+}
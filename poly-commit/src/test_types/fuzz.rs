@@ -0,0 +1,466 @@
+use crate::{Error, LabeledCommitment, LabeledPolynomial, PolynomialCommitment};
+use ark_crypto_primitives::sponge::CryptographicSponge;
+use ark_ff::PrimeField;
+use ark_poly::Polynomial;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::RngCore;
+#[cfg(not(feature = "std"))]
+use ark_std::{string::ToString, vec::Vec};
+
+/// A fixed, honestly generated verification instance against which arbitrary
+/// bytes can be fed to deserialisation and verification.
+///
+/// [`FuzzHarness::run`] never panics for a correct scheme: every malformed input
+/// must be reported as a deserialisation error or as a verification error.
+/// The targets in `poly-commit/fuzz` drive this harness through `cargo fuzz`.
+pub struct FuzzHarness<F, P, PC, S>
+where
+    F: PrimeField,
+    P: Polynomial<F>,
+    PC: PolynomialCommitment<F, P>,
+    S: CryptographicSponge,
+{
+    vk: PC::VerifierKey,
+    commitments: Vec<LabeledCommitment<PC::Commitment>>,
+    point: P::Point,
+    values: Vec<F>,
+    proof: PC::Proof,
+    proof_bytes: Vec<u8>,
+    mutable: Vec<usize>,
+    sponge: S,
+}
+
+impl<F, P, PC, S> FuzzHarness<F, P, PC, S>
+where
+    F: PrimeField,
+    P: Polynomial<F>,
+    PC: PolynomialCommitment<F, P>,
+    PC::Proof: CanonicalSerialize + CanonicalDeserialize,
+    S: CryptographicSponge,
+{
+    /// Sets up the scheme, commits to `polynomials` and opens them at `point`.
+    ///
+    /// The polynomials are also opened at `other_point`, which must differ from
+    /// `point`: the bytes on which both proofs agree are taken to be structural
+    /// (length prefixes, flags, digest lengths) and are left untouched by
+    /// [`Self::run_mutation`]. Both proofs must have the same shape, which may
+    /// not hold for schemes whose Merkle paths contain padding leaves.
+    pub fn new<R: RngCore>(
+        max_degree: usize,
+        num_vars: Option<usize>,
+        polynomials: Vec<LabeledPolynomial<F, P>>,
+        point: P::Point,
+        other_point: P::Point,
+        sponge: S,
+        rng: &mut R,
+    ) -> Result<Self, PC::Error> {
+        let pp = PC::setup(max_degree, num_vars, rng)?;
+        let hiding_bound = polynomials
+            .iter()
+            .filter_map(|p| p.hiding_bound())
+            .max()
+            .unwrap_or(0);
+        let mut degree_bounds = polynomials
+            .iter()
+            .filter_map(|p| p.degree_bound())
+            .collect::<Vec<_>>();
+        degree_bounds.sort_unstable();
+        degree_bounds.dedup();
+        let degree_bounds = if degree_bounds.is_empty() {
+            None
+        } else {
+            Some(degree_bounds.as_slice())
+        };
+        let (ck, vk) = PC::trim(&pp, max_degree, hiding_bound, degree_bounds)?;
+
+        let (commitments, states) = PC::commit(&ck, &polynomials, Some(rng))?;
+        let mut open = |point: &P::Point| {
+            let proof = PC::open(
+                &ck,
+                &polynomials,
+                &commitments,
+                point,
+                &mut sponge.clone(),
+                &states,
+                Some(&mut *rng),
+            )?;
+            let mut bytes = Vec::new();
+            proof
+                .serialize_compressed(&mut bytes)
+                .expect("serialising into a vector cannot fail");
+            Ok::<_, PC::Error>((proof, bytes))
+        };
+        let (proof, proof_bytes) = open(&point)?;
+        let (_, other_bytes) = open(&other_point)?;
+        if proof_bytes.len() != other_bytes.len() {
+            return Err(Error::IncorrectInputLength(
+                "the proofs at `point` and `other_point` differ in shape".to_string(),
+            )
+            .into());
+        }
+        let mutable = proof_bytes
+            .iter()
+            .zip(&other_bytes)
+            .enumerate()
+            .filter_map(|(i, (a, b))| (a != b).then_some(i))
+            .collect();
+        let values = polynomials.iter().map(|p| p.evaluate(&point)).collect();
+
+        Ok(Self {
+            vk,
+            commitments,
+            point,
+            values,
+            proof,
+            proof_bytes,
+            mutable,
+            sponge,
+        })
+    }
+
+    /// The compressed serialisation of the honest proof, useful as a seed corpus.
+    pub fn proof_bytes(&self) -> &[u8] {
+        &self.proof_bytes
+    }
+
+    /// The number of bytes of the honest proof that [`Self::run_mutation`] alters.
+    pub fn num_mutable_bytes(&self) -> usize {
+        self.mutable.len()
+    }
+
+    /// Verifies the honest proof against the honest commitments.
+    pub fn check_honest(&self) -> Result<(), PC::Error> {
+        self.check(&self.commitments, &self.proof)
+    }
+
+    /// Feeds `data` to [`Self::run_raw`] and [`Self::run_mutation`] and returns
+    /// the total number of accepted inputs.
+    pub fn run(&self, data: &[u8]) -> usize {
+        self.run_raw(data) + self.run_mutation(data)
+    }
+
+    /// Deserialises `data` as a proof for the honest commitments, and as a
+    /// replacement for the first commitment checked against the honest proof.
+    ///
+    /// `Vec` deserialisation in `ark-serialize` 0.5 reserves capacity for the
+    /// announced length before reading any element. Types of this crate read
+    /// vectors element by element, but those of dependencies (e.g. byte
+    /// digests of a Merkle tree config) may still abort on allocation failure
+    /// for a forged length prefix.
+    ///
+    /// Returns the number of inputs which deserialised and were accepted by the
+    /// verifier. For sound schemes this is zero unless `data` reproduces the
+    /// honest instance.
+    pub fn run_raw(&self, data: &[u8]) -> usize {
+        let mut accepted = 0;
+
+        if let Ok(proof) = PC::Proof::deserialize_compressed(data) {
+            accepted += self.check(&self.commitments, &proof).is_ok() as usize;
+        }
+
+        if let Ok(commitment) = PC::Commitment::deserialize_compressed(data) {
+            let mut commitments = self.commitments.clone();
+            let first = &commitments[0];
+            commitments[0] =
+                LabeledCommitment::new(first.label().clone(), commitment, first.degree_bound());
+            accepted += self.check(&commitments, &self.proof).is_ok() as usize;
+        }
+
+        accepted
+    }
+
+    /// XORs `data`, repeated cyclically, into the non-structural bytes of the
+    /// serialised honest proof and checks the result. This reaches the
+    /// verifier with well-shaped proofs carrying corrupted field elements,
+    /// group elements, digests and indices.
+    ///
+    /// Returns 1 if the mutated proof was accepted and 0 otherwise.
+    pub fn run_mutation(&self, data: &[u8]) -> usize {
+        let mut bytes = self.proof_bytes.clone();
+        let mut changed = false;
+        for (&i, d) in self.mutable.iter().zip(data.iter().cycle()) {
+            bytes[i] ^= d;
+            changed |= *d != 0;
+        }
+        if !changed {
+            return 0;
+        }
+        match PC::Proof::deserialize_compressed(bytes.as_slice()) {
+            Ok(proof) => self.check(&self.commitments, &proof).is_ok() as usize,
+            Err(_) => 0,
+        }
+    }
+
+    fn check(
+        &self,
+        commitments: &[LabeledCommitment<PC::Commitment>],
+        proof: &PC::Proof,
+    ) -> Result<(), PC::Error> {
+        PC::check(
+            &self.vk,
+            commitments,
+            &self.point,
+            self.values.iter().cloned(),
+            proof,
+            &mut self.sponge.clone(),
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FuzzHarness;
+    use crate::{
+        hyrax::HyraxPC,
+        ipa_pc::InnerProductArgPC,
+        linear_codes::{PoseidonMLBrakedown, PoseidonMLLigero, PoseidonUVLigero},
+        marlin_pc::MarlinKZG10,
+        marlin_pst13_pc::MarlinPST13,
+        sonic_pc::SonicKZG10,
+        test_types::test_sponge,
+        LabeledPolynomial, PolynomialCommitment,
+    };
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_crypto_primitives::sponge::poseidon::PoseidonSponge;
+    use ark_ed_on_bls12_381::{EdwardsAffine, Fr as EdFr};
+    use ark_ff::PrimeField;
+    use ark_poly::{
+        multivariate::{SparsePolynomial, SparseTerm},
+        univariate::DensePolynomial,
+        DenseMVPolynomial, DenseMultilinearExtension, DenseUVPolynomial, MultilinearExtension,
+        Polynomial, SparseMultilinearExtension,
+    };
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::{rand::RngCore, test_rng, UniformRand};
+    use blake2::Blake2s256;
+
+    // Drives the harness with the honest proof under single-bit flips,
+    // random masks and truncations, none of which may panic or verify.
+    fn fuzz_test<F, P, PC>(harness: FuzzHarness<F, P, PC, PoseidonSponge<F>>)
+    where
+        F: PrimeField,
+        P: Polynomial<F>,
+        PC: PolynomialCommitment<F, P>,
+        PC::Proof: CanonicalSerialize + CanonicalDeserialize,
+    {
+        let rng = &mut test_rng();
+        assert!(harness.check_honest().is_ok());
+
+        let num_mutable = harness.num_mutable_bytes();
+        assert!(num_mutable > 0);
+        // Cap the work spent on large proofs.
+        let stride = num_mutable / 128 + 1;
+
+        for i in (0..num_mutable).step_by(stride) {
+            let mut mask = vec![0u8; num_mutable];
+            mask[i] = 1 << (rng.next_u32() % 8);
+            assert_eq!(harness.run_mutation(&mask), 0);
+        }
+        for _ in 0..64 {
+            let mut mask = vec![0u8; 1 + rng.next_u32() as usize % num_mutable];
+            rng.fill_bytes(&mut mask);
+            assert_eq!(harness.run_mutation(&mask), 0);
+        }
+
+        let honest = harness.proof_bytes();
+        for len in (0..honest.len()).step_by(stride) {
+            assert!(PC::Proof::deserialize_compressed(&honest[..len]).is_err());
+        }
+
+        // Raw inputs, including a forged length prefix in place of the first
+        // one of the honest proof, must be rejected without allocating for it.
+        for _ in 0..64 {
+            let mut bytes = vec![0u8; rng.next_u32() as usize % (honest.len() + 1)];
+            rng.fill_bytes(&mut bytes);
+            assert_eq!(harness.run_raw(&bytes), 0);
+        }
+        let mut forged = honest.to_vec();
+        forged[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(harness.run_raw(&forged), 0);
+
+        assert!(harness.check_honest().is_ok());
+    }
+
+    fn uv_polys<F: PrimeField, R: RngCore>(
+        degree: usize,
+        degree_bound: Option<usize>,
+        rng: &mut R,
+    ) -> Vec<LabeledPolynomial<F, DensePolynomial<F>>> {
+        let mut polys = vec![LabeledPolynomial::new(
+            "a".to_string(),
+            DensePolynomial::rand(degree, rng),
+            None,
+            None,
+        )];
+        if let Some(bound) = degree_bound {
+            polys.push(LabeledPolynomial::new(
+                "b".to_string(),
+                DensePolynomial::rand(bound, rng),
+                Some(bound),
+                None,
+            ));
+        }
+        polys
+    }
+
+    #[test]
+    fn fuzz_marlin_kzg10() {
+        let rng = &mut test_rng();
+        let polys = uv_polys(15, Some(10), rng);
+        let harness = FuzzHarness::<_, _, MarlinKZG10<Bls12_381, DensePolynomial<Fr>>, _>::new(
+            15,
+            None,
+            polys,
+            Fr::rand(rng),
+            Fr::rand(rng),
+            test_sponge::<Fr>(),
+            rng,
+        )
+        .unwrap();
+        fuzz_test(harness);
+    }
+
+    #[test]
+    fn fuzz_sonic_kzg10() {
+        let rng = &mut test_rng();
+        let polys = uv_polys(15, Some(10), rng);
+        let harness = FuzzHarness::<_, _, SonicKZG10<Bls12_381, DensePolynomial<Fr>>, _>::new(
+            15,
+            None,
+            polys,
+            Fr::rand(rng),
+            Fr::rand(rng),
+            test_sponge::<Fr>(),
+            rng,
+        )
+        .unwrap();
+        fuzz_test(harness);
+    }
+
+    #[test]
+    fn fuzz_marlin_pst13() {
+        let rng = &mut test_rng();
+        let num_vars = 3;
+        let poly = SparsePolynomial::<Fr, SparseTerm>::rand(4, num_vars, rng);
+        let polys = vec![LabeledPolynomial::new("a".to_string(), poly, None, None)];
+        let point = (0..num_vars).map(|_| Fr::rand(rng)).collect();
+        let other_point = (0..num_vars).map(|_| Fr::rand(rng)).collect();
+        let harness = FuzzHarness::<_, _, MarlinPST13<Bls12_381, _>, _>::new(
+            4,
+            Some(num_vars),
+            polys,
+            point,
+            other_point,
+            test_sponge::<Fr>(),
+            rng,
+        )
+        .unwrap();
+        fuzz_test(harness);
+    }
+
+    #[test]
+    fn fuzz_ipa_pc() {
+        let rng = &mut test_rng();
+        let polys = uv_polys(15, Some(10), rng);
+        let harness = FuzzHarness::<
+            _,
+            _,
+            InnerProductArgPC<EdwardsAffine, Blake2s256, DensePolynomial<EdFr>>,
+            _,
+        >::new(
+            15,
+            None,
+            polys,
+            EdFr::rand(rng),
+            EdFr::rand(rng),
+            test_sponge::<EdFr>(),
+            rng,
+        )
+        .unwrap();
+        fuzz_test(harness);
+    }
+
+    #[test]
+    fn fuzz_hyrax() {
+        let rng = &mut test_rng();
+        let num_vars = 4;
+        let poly = DenseMultilinearExtension::rand(num_vars, rng);
+        let polys = vec![LabeledPolynomial::new("a".to_string(), poly, None, None)];
+        let point = (0..num_vars).map(|_| EdFr::rand(rng)).collect();
+        let other_point = (0..num_vars).map(|_| EdFr::rand(rng)).collect();
+        let harness = FuzzHarness::<_, _, HyraxPC<EdwardsAffine, _>, _>::new(
+            1,
+            Some(num_vars),
+            polys,
+            point,
+            other_point,
+            test_sponge::<EdFr>(),
+            rng,
+        )
+        .unwrap();
+        fuzz_test(harness);
+    }
+
+    #[test]
+    fn fuzz_univariate_ligero() {
+        let rng = &mut test_rng();
+        let polys = uv_polys(15, None, rng);
+        let harness = FuzzHarness::<_, _, PoseidonUVLigero<Fr, _>, _>::new(
+            15,
+            None,
+            polys,
+            Fr::rand(rng),
+            Fr::rand(rng),
+            test_sponge::<Fr>(),
+            rng,
+        )
+        .unwrap();
+        fuzz_test(harness);
+    }
+
+    #[test]
+    fn fuzz_multilinear_ligero() {
+        let rng = &mut test_rng();
+        let num_vars = 4;
+        let poly = SparseMultilinearExtension::rand(num_vars, rng);
+        let polys = vec![LabeledPolynomial::new("a".to_string(), poly, None, None)];
+        let point = (0..num_vars).map(|_| Fr::rand(rng)).collect();
+        let other_point = (0..num_vars).map(|_| Fr::rand(rng)).collect();
+        let harness = FuzzHarness::<_, _, PoseidonMLLigero<Fr, _>, _>::new(
+            1,
+            Some(num_vars),
+            polys,
+            point,
+            other_point,
+            test_sponge::<Fr>(),
+            rng,
+        )
+        .unwrap();
+        fuzz_test(harness);
+    }
+
+    #[test]
+    fn fuzz_multilinear_brakedown() {
+        let rng = &mut test_rng();
+        // The encoded rows are not a power of two long, so some Merkle paths
+        // have padding siblings; `num_vars` is chosen such that both openings
+        // query columns with the same path shapes.
+        let num_vars = 7;
+        let poly = SparseMultilinearExtension::rand(num_vars, rng);
+        let polys = vec![LabeledPolynomial::new("a".to_string(), poly, None, None)];
+        let point = (0..num_vars).map(|_| Fr::rand(rng)).collect();
+        let other_point = (0..num_vars).map(|_| Fr::rand(rng)).collect();
+        let harness = FuzzHarness::<_, _, PoseidonMLBrakedown<Fr, _>, _>::new(
+            1,
+            Some(num_vars),
+            polys,
+            point,
+            other_point,
+            test_sponge::<Fr>(),
+            rng,
+        )
+        .unwrap();
+        fuzz_test(harness);
+    }
+}
@@ -1,12 +1,26 @@
+#[cfg(test)]
+use super::test_sponge;
 use crate::{
-    linear_codes::{LinearCodePCS, MultilinearBrakedown, MultilinearLigero, UnivariateLigero},
+    linear_codes::{
+        IncrementalColumnHash, LinearCodePCS, MultilinearBrakedown, MultilinearLigero,
+        UnivariateLigero,
+    },
     to_bytes,
 };
+#[cfg(test)]
+use crate::{
+    linear_codes::{LPCPArray, LinearEncode},
+    Error, LabeledPolynomial, PolynomialCommitment,
+};
+#[cfg(test)]
+use ark_crypto_primitives::sponge::Absorb;
 use ark_crypto_primitives::{
     crh::{sha256::Sha256, CRHScheme, TwoToOneCRHScheme},
     merkle_tree::{ByteDigestConverter, Config},
 };
 use ark_ff::PrimeField;
+#[cfg(test)]
+use ark_poly::Polynomial;
 use ark_poly::{univariate::DensePolynomial, SparseMultilinearExtension};
 use ark_serialize::CanonicalSerialize;
 use ark_std::{borrow::Borrow, marker::PhantomData, rand::RngCore};
@@ -68,6 +82,34 @@ where
     }
 }
 
+impl<F, D> IncrementalColumnHash<F> for FieldToBytesColHasher<F, D>
+where
+    F: PrimeField + CanonicalSerialize,
+    D: Digest + Clone + Send,
+{
+    type Accumulator = D;
+
+    fn init(_parameters: &Self::Parameters, len: usize) -> Self::Accumulator {
+        // The serialization of a vector starts with its length.
+        let mut dig = D::new();
+        dig.update(to_bytes!(&(len as u64)).unwrap());
+        dig
+    }
+
+    fn update(_parameters: &Self::Parameters, accumulators: &mut [Self::Accumulator], row: &[F]) {
+        for (acc, entry) in accumulators.iter_mut().zip(row) {
+            acc.update(to_bytes!(entry).unwrap());
+        }
+    }
+
+    fn finalize(
+        _parameters: &Self::Parameters,
+        accumulator: Self::Accumulator,
+    ) -> Result<Self::Output, ark_crypto_primitives::Error> {
+        Ok(accumulator.finalize().to_vec())
+    }
+}
+
 pub struct TestMerkleTreeParams;
 
 impl Config for TestMerkleTreeParams {
@@ -117,3 +159,87 @@ pub type TestMLBrakedown<F> = LinearCodePCS<
     TestMerkleTreeParams,
     ColHasher<F, Blake2s256>,
 >;
+
+/// Checks that the streaming commitment and proof of `polynomial`, whose
+/// coefficients or evaluations are read from `entries`, are the ones
+/// computed in memory, and that the streaming proof verifies.
+#[cfg(test)]
+pub(crate) fn streaming_test<L, F, P, C, H>(
+    ck: &L::LinCodePCParams,
+    vk: &L::LinCodePCParams,
+    polynomial: LabeledPolynomial<F, P>,
+    entries: &[F],
+    point: P::Point,
+) where
+    L: LinearEncode<F, C, P, H>,
+    F: PrimeField + Absorb,
+    P: Polynomial<F>,
+    C: Config + 'static,
+    Vec<F>: Borrow<<H as CRHScheme>::Input>,
+    H::Output: Into<C::Leaf> + Send,
+    C::Leaf: Sized + Clone + Default + Send + AsRef<C::Leaf>,
+    H: IncrementalColumnHash<F> + 'static,
+{
+    let (c, states) = LinearCodePCS::<L, F, P, C, H>::commit(ck, [&polynomial], None).unwrap();
+    let (c_stream, state_stream) =
+        LinearCodePCS::<L, F, P, C, H>::commit_stream(ck, &entries).unwrap();
+    assert_eq!(c[0].commitment().root, c_stream.root);
+
+    let value = polynomial.evaluate(&point);
+    let test_sponge = test_sponge::<F>();
+    let proof = LinearCodePCS::<L, F, P, C, H>::open(
+        ck,
+        [&polynomial],
+        &c,
+        &point,
+        &mut (test_sponge.clone()),
+        &states,
+        None,
+    )
+    .unwrap();
+    let proof_stream = LinearCodePCS::<L, F, P, C, H>::open_stream(
+        ck,
+        &entries,
+        &c_stream,
+        &state_stream,
+        &point,
+        &mut (test_sponge.clone()),
+    )
+    .unwrap();
+    let proof_stream = LPCPArray(vec![proof_stream]);
+    assert_eq!(
+        to_bytes!(&proof).unwrap(),
+        to_bytes!(&proof_stream).unwrap()
+    );
+
+    LinearCodePCS::<L, F, P, C, H>::check(
+        vk,
+        &c,
+        &point,
+        [value],
+        &proof_stream,
+        &mut (test_sponge.clone()),
+        None,
+    )
+    .unwrap();
+
+    // Empty streams, and streams that do not fit in the committed matrix, are
+    // rejected.
+    let empty: &[F] = &[];
+    assert!(matches!(
+        LinearCodePCS::<L, F, P, C, H>::commit_stream(ck, &empty),
+        Err(Error::IncorrectInputLength(_))
+    ));
+    let too_long = vec![F::one(); c_stream.metadata.n_rows * c_stream.metadata.n_cols + 1];
+    assert!(matches!(
+        LinearCodePCS::<L, F, P, C, H>::open_stream(
+            ck,
+            &too_long.as_slice(),
+            &c_stream,
+            &state_stream,
+            &point,
+            &mut (test_sponge.clone()),
+        ),
+        Err(Error::IncorrectInputLength(_))
+    ));
+}
@@ -1,8 +1,12 @@
+mod fuzz;
 mod linear_codes;
 mod poseidon_sponge;
 
+pub use fuzz::FuzzHarness;
 pub use linear_codes::{TestMLBrakedown, TestMLLigero, TestUVLigero};
 pub use poseidon_sponge::test_sponge;
 
 #[cfg(test)]
-pub(crate) use linear_codes::{FieldToBytesColHasher, LeafIdentityHasher, TestMerkleTreeParams};
+pub(crate) use linear_codes::{
+    streaming_test, FieldToBytesColHasher, LeafIdentityHasher, TestMerkleTreeParams,
+};
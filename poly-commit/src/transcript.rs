@@ -0,0 +1,242 @@
+//! The Fiat–Shamir transcript shared by the schemes of this crate.
+//!
+//! A transcript is any `CryptographicSponge`, through the [`Transcript`]
+//! extension trait. Every message and every challenge is framed by a header,
+//! absorbed as bytes, so that messages with different labels or lengths
+//! cannot be confused. With `le64(n)` the 8-byte little-endian encoding of
+//! `n`, the headers are:
+//!
+//! - before a message: `0x00 || le64(len(label)) || label || le64(len(m))`,
+//!   where `len(m)` is the length of the sponge bytes of the message `m`,
+//!   which is then absorbed with its own `Absorb` implementation;
+//! - before a challenge: `0x01 || le64(len(label)) || label || le64(n)`, where
+//!   `n` is the number of field elements or bytes squeezed after it.
+//!
+//! Each `open` and `check` starts with [`Transcript::bind_context`], which
+//! absorbs the name of the scheme under the label `protocol`, then the
+//! BLAKE2s-256 digest of the parameters of the scheme, which its committer
+//! and verifier keys share, in their compressed serialization, under the
+//! label `parameters`. A proof is thus only valid in the transcript of the
+//! scheme and configuration that produced it. For the schemes based on KZG10,
+//! these parameters are the digest of the whole verifier key, which their
+//! committer keys record when trimmed.
+
+use crate::{Error, CHALLENGE_SIZE};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge, FieldElementSize};
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+#[cfg(not(feature = "std"))]
+use ark_std::vec::Vec;
+use blake2::{Blake2s256, Digest};
+
+const MESSAGE: u8 = 0x00;
+const CHALLENGE: u8 = 0x01;
+
+/// Outputs the header of a message or a challenge labelled `label`, of length
+/// `len`.
+pub(crate) fn header(kind: u8, label: &[u8], len: usize) -> Vec<u8> {
+    let mut header = Vec::with_capacity(17 + label.len());
+    header.push(kind);
+    header.extend_from_slice(&(label.len() as u64).to_le_bytes());
+    header.extend_from_slice(label);
+    header.extend_from_slice(&(len as u64).to_le_bytes());
+    header
+}
+
+/// Outputs the header absorbed before the message `label` of `len` sponge
+/// bytes.
+pub(crate) fn message_header(label: &[u8], len: usize) -> Vec<u8> {
+    header(MESSAGE, label, len)
+}
+
+/// Outputs the header absorbed before squeezing `len` elements under `label`.
+pub(crate) fn challenge_header(label: &[u8], len: usize) -> Vec<u8> {
+    header(CHALLENGE, label, len)
+}
+
+/// Outputs the BLAKE2s-256 digest of the compressed serialization of
+/// `parameters`.
+pub(crate) fn parameters_digest<T: CanonicalSerialize + ?Sized>(
+    parameters: &T,
+) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    parameters
+        .serialize_compressed(&mut bytes)
+        .map_err(|_| Error::TranscriptError)?;
+    Ok(Blake2s256::digest(&bytes).to_vec())
+}
+
+/// Outputs the byte strings that [`Transcript::bind_context`] absorbs, one
+/// `absorb` each, for the transcripts that are not computed natively.
+pub(crate) fn context_messages<T: CanonicalSerialize + ?Sized>(
+    protocol: &[u8],
+    parameters: &T,
+) -> Result<[Vec<u8>; 4], Error> {
+    let digest = parameters_digest(parameters)?;
+    Ok([
+        message_header(b"protocol", protocol.len()),
+        protocol.to_vec(),
+        message_header(b"parameters", digest.len()),
+        digest,
+    ])
+}
+
+/// Labelled absorption and squeezing on top of a sponge, with the byte layout
+/// of the [module documentation](self).
+pub trait Transcript: CryptographicSponge {
+    /// Absorbs `message` under `label`.
+    fn absorb_labelled(&mut self, label: &[u8], message: &impl Absorb) {
+        let len = message.to_sponge_bytes_as_vec().len();
+        self.absorb(&message_header(label, len));
+        self.absorb(message);
+    }
+
+    /// Absorbs the compressed serialization of `value` under `label`.
+    fn absorb_serialized<T: CanonicalSerialize + ?Sized>(
+        &mut self,
+        label: &[u8],
+        value: &T,
+    ) -> Result<(), Error> {
+        let mut bytes = Vec::new();
+        value
+            .serialize_compressed(&mut bytes)
+            .map_err(|_| Error::TranscriptError)?;
+        self.absorb_labelled(label, &bytes);
+        Ok(())
+    }
+
+    /// Squeezes field elements of the given `sizes` under `label`.
+    fn squeeze_labelled_field_elements<F: PrimeField>(
+        &mut self,
+        label: &[u8],
+        sizes: &[FieldElementSize],
+    ) -> Vec<F> {
+        self.absorb(&challenge_header(label, sizes.len()));
+        self.squeeze_field_elements_with_sizes(sizes)
+    }
+
+    /// Squeezes `num_bytes` bytes under `label`.
+    fn squeeze_labelled_bytes(&mut self, label: &[u8], num_bytes: usize) -> Vec<u8> {
+        self.absorb(&challenge_header(label, num_bytes));
+        self.squeeze_bytes(num_bytes)
+    }
+
+    /// Squeezes a challenge of `CHALLENGE_SIZE` bits under `label`.
+    fn challenge<F: PrimeField>(&mut self, label: &[u8]) -> F {
+        self.squeeze_labelled_field_elements(label, &[CHALLENGE_SIZE])[0]
+    }
+
+    /// Binds the transcript to the scheme `protocol` and its `parameters`.
+    fn bind_context<T: CanonicalSerialize + ?Sized>(
+        &mut self,
+        protocol: &[u8],
+        parameters: &T,
+    ) -> Result<(), Error> {
+        for bytes in context_messages(protocol, parameters)? {
+            self.absorb(&bytes);
+        }
+        Ok(())
+    }
+}
+
+impl<S: CryptographicSponge> Transcript for S {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        marlin_pc::MarlinKZG10, tests::poseidon_sponge_for_test, LabeledPolynomial,
+        PolynomialCommitment, VerificationFailure,
+    };
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_ff::UniformRand;
+    use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial};
+    use ark_std::test_rng;
+
+    type PC = MarlinKZG10<Bls12_381, DensePolynomial<Fr>>;
+
+    #[test]
+    fn header_layout_test() {
+        let mut expected = vec![0x01, 2, 0, 0, 0, 0, 0, 0, 0, b'a', b'b'];
+        expected.extend_from_slice(&[3, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(challenge_header(b"ab", 3), expected);
+        expected[0] = 0x00;
+        assert_eq!(message_header(b"ab", 3), expected);
+
+        let mut sponge = poseidon_sponge_for_test::<Fr>();
+        sponge.absorb_labelled(b"ab", &vec![1u8, 2, 3]);
+        let mut expected = poseidon_sponge_for_test::<Fr>();
+        expected.absorb(&message_header(b"ab", 3));
+        expected.absorb(&vec![1u8, 2, 3]);
+        assert_eq!(
+            sponge.challenge::<Fr>(b"c"),
+            expected.squeeze_labelled_field_elements::<Fr>(b"c", &[CHALLENGE_SIZE])[0]
+        );
+    }
+
+    #[test]
+    fn bind_context_test() {
+        let challenge = |protocol: &[u8], parameters: &[u64]| -> Fr {
+            let mut sponge = poseidon_sponge_for_test::<Fr>();
+            sponge.bind_context(protocol, parameters).unwrap();
+            sponge.challenge(b"challenge")
+        };
+        assert_eq!(challenge(b"a", &[1]), challenge(b"a", &[1]));
+        assert_ne!(challenge(b"a", &[1]), challenge(b"b", &[1]));
+        assert_ne!(challenge(b"a", &[1]), challenge(b"a", &[2]));
+    }
+
+    #[test]
+    fn proofs_are_bound_to_parameters_test() {
+        let rng = &mut test_rng();
+        let pp = PC::setup(16, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 8, 0, None).unwrap();
+        let (_, other_vk) = PC::trim(&pp, 12, 0, None).unwrap();
+        let polynomial =
+            LabeledPolynomial::new("a".into(), DensePolynomial::<Fr>::rand(8, rng), None, None);
+        let (commitments, states) = PC::commit(&ck, [&polynomial], None).unwrap();
+        let point = Fr::rand(rng);
+        let value = polynomial.evaluate(&point);
+
+        let sponge = poseidon_sponge_for_test::<Fr>();
+        let proof = PC::open(
+            &ck,
+            [&polynomial],
+            &commitments,
+            &point,
+            &mut sponge.clone(),
+            &states,
+            None,
+        )
+        .unwrap();
+        let check = |vk| {
+            PC::check(
+                vk,
+                &commitments,
+                &point,
+                [value],
+                &proof,
+                &mut sponge.clone(),
+                None,
+            )
+        };
+        check(&vk).unwrap();
+        // The committer key records the digest of the whole verifier key,
+        // which covers the elements of G2 that only the latter holds.
+        let digest = |vk: &<PC as PolynomialCommitment<_, _>>::VerifierKey| {
+            vk.transcript_parameters().unwrap().verifier_key_digest
+        };
+        assert_eq!(ck.transcript_parameters().verifier_key_digest, digest(&vk));
+        let mut other_beta_h = vk.clone();
+        other_beta_h.vk.beta_h = other_beta_h.vk.h;
+        assert_ne!(digest(&other_beta_h), digest(&vk));
+        // The keys only differ in the supported degree, which the proof does
+        // not depend on, but which the transcript binds.
+        assert!(matches!(
+            check(&other_vk),
+            Err(Error::VerificationFailed(
+                VerificationFailure::PairingCheck { .. }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,427 @@
+use crate::{
+    Error, LabeledCommitment, LabeledPolynomial, PCCommitterKey, PCUniversalParams, PCVerifierKey,
+    PolynomialCommitment,
+};
+use ark_crypto_primitives::sponge::CryptographicSponge;
+use ark_ff::PrimeField;
+use ark_poly::{
+    univariate::DensePolynomial, DenseMultilinearExtension, MultilinearExtension,
+    SparseMultilinearExtension,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(not(feature = "std"))]
+use ark_std::vec::Vec;
+use ark_std::{marker::PhantomData, rand::RngCore};
+
+/// Multilinear polynomials which can be built from their evaluations over the
+/// Boolean hypercube, in the order of `MultilinearExtension::to_evaluations`.
+pub trait FromHypercubeEvaluations<F: PrimeField>: MultilinearExtension<F> {
+    /// Outputs the `num_vars`-variate polynomial with the given evaluations.
+    fn from_hypercube_evaluations(num_vars: usize, evaluations: Vec<F>) -> Self;
+}
+
+impl<F: PrimeField> FromHypercubeEvaluations<F> for DenseMultilinearExtension<F> {
+    fn from_hypercube_evaluations(num_vars: usize, evaluations: Vec<F>) -> Self {
+        Self::from_evaluations_vec(num_vars, evaluations)
+    }
+}
+
+impl<F: PrimeField> FromHypercubeEvaluations<F> for SparseMultilinearExtension<F> {
+    fn from_hypercube_evaluations(num_vars: usize, evaluations: Vec<F>) -> Self {
+        let non_zero: Vec<_> = evaluations
+            .into_iter()
+            .enumerate()
+            .filter(|(_, e)| !e.is_zero())
+            .collect();
+        Self::from_evaluations(num_vars, &non_zero)
+    }
+}
+
+/// Parameters and keys of `UnivariateAdapter`: those of the underlying
+/// multilinear scheme, along with the number of variables `n` of the
+/// multilinear polynomials which univariate polynomials are mapped to.
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AdapterKey<K: CanonicalSerialize + CanonicalDeserialize> {
+    /// The parameters or key of the underlying scheme.
+    pub inner: K,
+    /// The number of variables `n` of the committed multilinear polynomials.
+    pub num_vars: usize,
+}
+
+impl<K: CanonicalSerialize + CanonicalDeserialize> AdapterKey<K> {
+    /// The maximum degree `2^n - 1` of the univariate polynomials.
+    fn degree(&self) -> usize {
+        (1 << self.num_vars) - 1
+    }
+}
+
+impl<K: PCUniversalParams> PCUniversalParams for AdapterKey<K> {
+    fn max_degree(&self) -> usize {
+        self.degree()
+    }
+}
+
+impl<K: PCCommitterKey> PCCommitterKey for AdapterKey<K> {
+    fn max_degree(&self) -> usize {
+        self.degree()
+    }
+
+    fn supported_degree(&self) -> usize {
+        self.degree()
+    }
+}
+
+impl<K: PCVerifierKey> PCVerifierKey for AdapterKey<K> {
+    fn max_degree(&self) -> usize {
+        self.degree()
+    }
+
+    fn supported_degree(&self) -> usize {
+        self.degree()
+    }
+}
+
+/// Univariate polynomial commitment scheme built on top of a multilinear
+/// scheme `PC`, for multilinear polynomials of type `P`.
+///
+/// A polynomial `p(X) = Σ c_i X^i` of degree at most `2^n - 1` is mapped to
+/// the `n`-variate multilinear polynomial `g` whose coefficients in the
+/// monomial basis are those of `p`: `g(X_0, ..., X_{n-1}) = Σ c_i Π X_j^{i_j}`,
+/// where `i_j` is the `j`-th bit of `i`. The evaluation `p(z)` is then the
+/// evaluation of `g` at the tensor query `(z, z^2, z^4, ..., z^{2^{n-1}})`,
+/// which `PC` opens like any other point. The evaluations of `g` over the
+/// Boolean hypercube, which multilinear schemes commit to, are the subset sums
+/// `g(b) = Σ_{i ⊆ b} c_i` of the coefficients.
+///
+/// The number of variables `n` is fixed by `setup`, to `num_vars` if given
+/// and to the smallest `n` with `2^n > max_degree` otherwise. Schemes with
+/// constraints on `n`, such as `HyraxPC` which requires it to be even, should
+/// be given a suitable `num_vars`. Degree bounds are not supported.
+pub struct UnivariateAdapter<F, P, PC> {
+    _field: PhantomData<F>,
+    _poly: PhantomData<P>,
+    _pc: PhantomData<PC>,
+}
+
+impl<F, P, PC> UnivariateAdapter<F, P, PC>
+where
+    F: PrimeField,
+    P: FromHypercubeEvaluations<F, Point = Vec<F>>,
+    PC: PolynomialCommitment<F, P>,
+{
+    /// Outputs the `num_vars`-variate multilinear polynomial whose
+    /// coefficients are those of `polynomial`.
+    pub fn to_multilinear(
+        polynomial: &LabeledPolynomial<F, DensePolynomial<F>>,
+        num_vars: usize,
+    ) -> Result<LabeledPolynomial<F, P>, Error> {
+        if let Some(degree_bound) = polynomial.degree_bound() {
+            return Err(Error::UnsupportedDegreeBound(degree_bound));
+        }
+        let size = 1 << num_vars;
+        if polynomial.coeffs.len() > size {
+            return Err(Error::PolynomialDegreeTooLarge {
+                poly_degree: polynomial.degree(),
+                supported_degree: size - 1,
+                label: polynomial.label().clone(),
+            });
+        }
+        let mut evaluations = polynomial.coeffs.clone();
+        evaluations.resize(size, F::zero());
+        // Subset sums over the bits of the indices, one variable at a time.
+        for j in 0..num_vars {
+            let bit = 1 << j;
+            for i in 0..size {
+                if i & bit != 0 {
+                    let lower = evaluations[i ^ bit];
+                    evaluations[i] += lower;
+                }
+            }
+        }
+        Ok(LabeledPolynomial::new(
+            polynomial.label().clone(),
+            P::from_hypercube_evaluations(num_vars, evaluations),
+            None,
+            polynomial.hiding_bound(),
+        ))
+    }
+
+    /// Outputs the tensor query `(z, z^2, z^4, ..., z^{2^{num_vars - 1}})`
+    /// at which the multilinear polynomials are opened.
+    pub fn to_multilinear_point(point: &F, num_vars: usize) -> Vec<F> {
+        ark_std::iter::successors(Some(*point), |z| Some(z.square()))
+            .take(num_vars)
+            .collect()
+    }
+}
+
+impl<F, P, PC> PolynomialCommitment<F, DensePolynomial<F>> for UnivariateAdapter<F, P, PC>
+where
+    F: PrimeField,
+    P: FromHypercubeEvaluations<F, Point = Vec<F>>,
+    PC: PolynomialCommitment<F, P>,
+{
+    type UniversalParams = AdapterKey<PC::UniversalParams>;
+    type CommitterKey = AdapterKey<PC::CommitterKey>;
+    type VerifierKey = AdapterKey<PC::VerifierKey>;
+    type Commitment = PC::Commitment;
+    type CommitmentState = PC::CommitmentState;
+    type Proof = PC::Proof;
+    type BatchProof = PC::BatchProof;
+    type Error = PC::Error;
+
+    /// Sets up `PC` for multilinear polynomials in `num_vars` variables, or
+    /// in as many variables as needed for polynomials of degree `max_degree`
+    /// if `num_vars` is `None`.
+    fn setup<R: RngCore>(
+        max_degree: usize,
+        num_vars: Option<usize>,
+        rng: &mut R,
+    ) -> Result<Self::UniversalParams, Self::Error> {
+        let num_vars = match num_vars {
+            Some(num_vars) if num_vars < usize::BITS as usize => num_vars,
+            Some(_) => return Err(Error::InvalidNumberOfVariables.into()),
+            None => (max_degree + 1).next_power_of_two().trailing_zeros() as usize,
+        };
+        if num_vars == 0 || max_degree >> num_vars != 0 {
+            return Err(Error::InvalidNumberOfVariables.into());
+        }
+        let inner = PC::setup(max_degree, Some(num_vars), rng)?;
+        Ok(AdapterKey { inner, num_vars })
+    }
+
+    fn trim(
+        pp: &Self::UniversalParams,
+        supported_degree: usize,
+        supported_hiding_bound: usize,
+        enforced_degree_bounds: Option<&[usize]>,
+    ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
+        if supported_degree > pp.degree() {
+            return Err(Error::TrimmingDegreeTooLarge.into());
+        }
+        if let Some(degree_bound) = enforced_degree_bounds.and_then(|b| b.first()) {
+            return Err(Error::UnsupportedDegreeBound(*degree_bound).into());
+        }
+        let (ck, vk) = PC::trim(
+            &pp.inner,
+            supported_degree,
+            supported_hiding_bound,
+            enforced_degree_bounds,
+        )?;
+        let num_vars = pp.num_vars;
+        Ok((
+            AdapterKey {
+                inner: ck,
+                num_vars,
+            },
+            AdapterKey {
+                inner: vk,
+                num_vars,
+            },
+        ))
+    }
+
+    /// Commits to the multilinear polynomials whose coefficients are those of
+    /// `polynomials`.
+    fn commit<'a>(
+        ck: &Self::CommitterKey,
+        polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, DensePolynomial<F>>>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<Self::Commitment>>,
+            Vec<Self::CommitmentState>,
+        ),
+        Self::Error,
+    > {
+        let polynomials = polynomials
+            .into_iter()
+            .map(|p| Self::to_multilinear(p, ck.num_vars))
+            .collect::<Result<Vec<_>, _>>()?;
+        PC::commit(&ck.inner, &polynomials, rng)
+    }
+
+    /// Opens the multilinear polynomials at the tensor query derived from
+    /// `point`.
+    fn open<'a>(
+        ck: &Self::CommitterKey,
+        labeled_polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, DensePolynomial<F>>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a F,
+        sponge: &mut impl CryptographicSponge,
+        states: impl IntoIterator<Item = &'a Self::CommitmentState>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Self::Error>
+    where
+        Self::CommitmentState: 'a,
+        Self::Commitment: 'a,
+    {
+        let polynomials = labeled_polynomials
+            .into_iter()
+            .map(|p| Self::to_multilinear(p, ck.num_vars))
+            .collect::<Result<Vec<_>, _>>()?;
+        let point = Self::to_multilinear_point(point, ck.num_vars);
+        // Collected so that they can be passed along with the polynomials
+        // and the point, which do not live for `'a`.
+        let commitments: Vec<_> = commitments.into_iter().collect();
+        let states: Vec<_> = states.into_iter().collect();
+        PC::open(
+            &ck.inner,
+            &polynomials,
+            commitments.iter().copied(),
+            &point,
+            sponge,
+            states.iter().copied(),
+            rng,
+        )
+    }
+
+    /// Checks the opening of the multilinear polynomials at the tensor query
+    /// derived from `point`.
+    fn check<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a F,
+        values: impl IntoIterator<Item = F>,
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Commitment: 'a,
+    {
+        let point = Self::to_multilinear_point(point, vk.num_vars);
+        let commitments: Vec<_> = commitments.into_iter().collect();
+        PC::check(
+            &vk.inner,
+            commitments.iter().copied(),
+            &point,
+            values,
+            proof,
+            sponge,
+            rng,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hyrax::HyraxPC,
+        test_types::{test_sponge, TestMLBrakedown, TestMLLigero},
+    };
+    use ark_bls12_381::Fr;
+    use ark_ec::AffineRepr;
+    use ark_ed_on_bls12_381::EdwardsAffine;
+    use ark_poly::DenseUVPolynomial;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    type JubjubFr = <EdwardsAffine as AffineRepr>::ScalarField;
+    type Hyrax = HyraxPC<EdwardsAffine, DenseMultilinearExtension<JubjubFr>>;
+
+    fn univariate_adapter_test<F, P, PC>(num_vars: Option<usize>)
+    where
+        F: PrimeField,
+        P: FromHypercubeEvaluations<F, Point = Vec<F>>,
+        PC: PolynomialCommitment<F, P>,
+    {
+        type Adapter<F, P, PC> = UnivariateAdapter<F, P, PC>;
+        let rng = &mut test_rng();
+        let sponge = test_sponge::<F>();
+
+        let pp = Adapter::<F, P, PC>::setup(60, num_vars, rng).unwrap();
+        let (ck, vk) = Adapter::<F, P, PC>::trim(&pp, 60, 1, None).unwrap();
+
+        // The second polynomial has fewer coefficients than the hypercube.
+        let polynomials = [60, 9]
+            .iter()
+            .enumerate()
+            .map(|(i, degree)| {
+                LabeledPolynomial::new(
+                    format!("Test{}", i),
+                    DensePolynomial::rand(*degree, rng),
+                    None,
+                    Some(1),
+                )
+            })
+            .collect::<Vec<_>>();
+        let (comms, states) = Adapter::<F, P, PC>::commit(&ck, &polynomials, Some(rng)).unwrap();
+
+        // The powers `z^{2^j}` of the second point are not distinct.
+        for point in [F::rand(rng), -F::one()] {
+            let values: Vec<_> = polynomials.iter().map(|p| p.evaluate(&point)).collect();
+            let proof = Adapter::<F, P, PC>::open(
+                &ck,
+                &polynomials,
+                &comms,
+                &point,
+                &mut sponge.clone(),
+                &states,
+                Some(rng),
+            )
+            .unwrap();
+            Adapter::<F, P, PC>::check(
+                &vk,
+                &comms,
+                &point,
+                values.clone(),
+                &proof,
+                &mut sponge.clone(),
+                Some(rng),
+            )
+            .unwrap();
+
+            let wrong_values = [values[0] + F::one(), values[1]];
+            assert!(Adapter::<F, P, PC>::check(
+                &vk,
+                &comms,
+                &point,
+                wrong_values,
+                &proof,
+                &mut sponge.clone(),
+                Some(rng),
+            )
+            .is_err());
+        }
+
+        let too_large = LabeledPolynomial::new(
+            "TooLarge".into(),
+            DensePolynomial::rand(ck.supported_degree() + 1, rng),
+            None,
+            None,
+        );
+        assert!(Adapter::<F, P, PC>::commit(&ck, [&too_large], Some(rng)).is_err());
+    }
+
+    #[test]
+    fn to_multilinear_test() {
+        type Adapter = UnivariateAdapter<Fr, SparseMultilinearExtension<Fr>, TestMLLigero<Fr>>;
+        let rng = &mut test_rng();
+        let polynomial =
+            LabeledPolynomial::new("Test".into(), DensePolynomial::rand(12, rng), None, None);
+        let multilinear = Adapter::to_multilinear(&polynomial, 4).unwrap();
+        let point = Fr::rand(rng);
+        assert_eq!(
+            multilinear.evaluate(&Adapter::to_multilinear_point(&point, 4)),
+            polynomial.evaluate(&point)
+        );
+    }
+
+    #[test]
+    fn hyrax_univariate_adapter_test() {
+        // Hyrax only supports an even number of variables.
+        univariate_adapter_test::<JubjubFr, DenseMultilinearExtension<JubjubFr>, Hyrax>(Some(6));
+    }
+
+    #[test]
+    fn ligero_univariate_adapter_test() {
+        univariate_adapter_test::<Fr, SparseMultilinearExtension<Fr>, TestMLLigero<Fr>>(None);
+    }
+
+    #[test]
+    fn brakedown_univariate_adapter_test() {
+        univariate_adapter_test::<Fr, SparseMultilinearExtension<Fr>, TestMLBrakedown<Fr>>(None);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{LabeledCommitment, PolynomialCommitment};
+use ark_ff::PrimeField;
+use ark_poly::Polynomial;
+#[cfg(not(feature = "std"))]
+use ark_std::vec::Vec;
+use ark_std::{collections::BTreeMap, rand::RngCore};
+
+/// Describes the interface of the homomorphic polynomial commitment schemes
+/// whose commitments can be updated after a sparse change of the committed
+/// polynomial, without recommitting to the whole polynomial.
+///
+/// A change is given as a list of deltas `(i, d_i)`, which add `d_i` to the
+/// `i`-th coefficient of a univariate polynomial, or to the `i`-th evaluation
+/// over the boolean hypercube of a multilinear polynomial, in the order of
+/// `MultilinearExtension::to_evaluations`. Deltas to the same index are
+/// summed. The caller is expected to apply the same deltas to its own copy
+/// of the polynomial before opening the updated commitment.
+pub trait UpdatableCommitment<F: PrimeField, P: Polynomial<F>>: PolynomialCommitment<F, P> {
+    /// Outputs the commitment to the polynomial committed to in `commitment`
+    /// plus the sparse polynomial given by `deltas`, along with the state
+    /// needed to open it. This costs a multi-scalar multiplication of the
+    /// size of `deltas`.
+    ///
+    /// If `state` is that of a hiding commitment, the updated commitment is
+    /// re-randomised with fresh randomness sampled from `rng`, so that the
+    /// two commitments cannot be linked through their difference. In that
+    /// case `rng` is required.
+    #[allow(clippy::type_complexity)]
+    fn update_commitment(
+        ck: &Self::CommitterKey,
+        commitment: &LabeledCommitment<Self::Commitment>,
+        state: &Self::CommitmentState,
+        deltas: &[(usize, F)],
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::CommitmentState), Self::Error>;
+}
+
+/// Sums the deltas to the same index and drops those which cancel out,
+/// sorting the remaining ones by index.
+pub(crate) fn merge_deltas<F: PrimeField>(deltas: &[(usize, F)]) -> Vec<(usize, F)> {
+    let mut merged = BTreeMap::new();
+    for (i, d) in deltas {
+        *merged.entry(*i).or_insert_with(F::zero) += d;
+    }
+    merged.into_iter().filter(|(_, d)| !d.is_zero()).collect()
+}
@@ -7,9 +7,12 @@ use rayon::{
     prelude::IndexedParallelIterator,
 };
 
+use crate::Error;
 use ark_ff::Field;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::vec::Vec;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Validate,
+};
+use ark_std::{format, io::Read, vec::Vec};
 
 /// Takes as input a struct, and converts them to a series of bytes. All traits
 /// that implement `CanonicalSerialize` can be automatically converted to bytes
@@ -58,6 +61,34 @@ pub(crate) fn vector_sum<F: Field>(v1: &[F], v2: &[F]) -> Vec<F> {
         .collect()
 }
 
+/// Deserialises a length-prefixed vector, reading each element with `f`.
+///
+/// Unlike `Vec::deserialize_with_mode`, no capacity is reserved from the
+/// untrusted length prefix, so memory use is bounded by the input itself.
+/// Elements are read without validation; callers check the result.
+pub(crate) fn deserialize_vec_with<T, R: Read>(
+    mut reader: R,
+    compress: Compress,
+    mut f: impl FnMut(&mut R) -> Result<T, SerializationError>,
+) -> Result<Vec<T>, SerializationError> {
+    let len = u64::deserialize_with_mode(&mut reader, compress, Validate::No)?;
+    let mut values = Vec::new();
+    for _ in 0..len {
+        values.push(f(&mut reader)?);
+    }
+    Ok(values)
+}
+
+/// Deserialises a length-prefixed vector as in [`deserialize_vec_with`].
+pub(crate) fn deserialize_vec<T: CanonicalDeserialize, R: Read>(
+    reader: R,
+    compress: Compress,
+) -> Result<Vec<T>, SerializationError> {
+    deserialize_vec_with(reader, compress, |r| {
+        T::deserialize_with_mode(r, compress, Validate::No)
+    })
+}
+
 #[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
 #[derivative(Default(bound = ""), Clone(bound = ""), Debug(bound = ""))]
 pub struct Matrix<F: Field> {
@@ -160,6 +191,17 @@ impl<F: Field> Matrix<F> {
     }
 }
 
+/// Fails unless all the inputs of a batch have `expected` elements.
+pub(crate) fn check_lengths(expected: usize, lengths: &[usize]) -> Result<(), Error> {
+    match lengths.iter().find(|len| **len != expected) {
+        Some(len) => Err(Error::IncorrectInputLength(format!(
+            "Expected {} elements in each input, got {}",
+            expected, len
+        ))),
+        None => Ok(()),
+    }
+}
+
 #[inline]
 pub(crate) fn inner_product<F: Field>(v1: &[F], v2: &[F]) -> F {
     ark_std::cfg_iter!(v1)
@@ -181,6 +223,21 @@ pub(crate) mod tests {
 
     use ark_bls12_377::Fr;
 
+    #[test]
+    fn test_deserialize_vec_forged_length() {
+        let mut bytes = Vec::new();
+        u64::MAX.serialize_compressed(&mut bytes).unwrap();
+        Fr::from(7u64).serialize_compressed(&mut bytes).unwrap();
+        assert!(deserialize_vec::<Fr, _>(bytes.as_slice(), Compress::Yes).is_err());
+
+        let mut bytes = Vec::new();
+        vec![Fr::from(7u64); 3]
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+        let v = deserialize_vec::<Fr, _>(bytes.as_slice(), Compress::Yes).unwrap();
+        assert_eq!(v, vec![Fr::from(7u64); 3]);
+    }
+
     #[test]
     fn test_matrix_constructor_flat() {
         let entries: Vec<Fr> = to_field(vec![10, 100, 4, 67, 44, 50]);
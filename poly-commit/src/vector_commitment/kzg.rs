@@ -0,0 +1,262 @@
+use super::{check_subset, check_values_len, VectorCommitment};
+use crate::{
+    kzg10::{self, Commitment, Powers, Proof, KZG10},
+    Error, VerificationFailure,
+};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ec::{pairing::Pairing, scalar_mul::ScalarMul, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, UniformRand, Zero};
+use ark_poly::{
+    univariate::{DenseOrSparsePolynomial, DensePolynomial},
+    DenseUVPolynomial, EvaluationDomain,
+};
+#[cfg(not(feature = "std"))]
+use ark_std::vec::Vec;
+use ark_std::{borrow::Cow, format, marker::PhantomData, rand::RngCore};
+
+type UniPoly<E> = DensePolynomial<<E as Pairing>::ScalarField>;
+
+/// Vector commitment based on [[KZG10]][kzg], where the `i`-th entry of a
+/// vector is the evaluation of the committed polynomial at the `i`-th element
+/// of an evaluation domain `D`. A subset `S` of indices is opened with a single
+/// group element, the commitment to the quotient of the polynomial by the
+/// vanishing polynomial `Z_S` of `S`, which is checked against a commitment to
+/// `Z_S` in G2. The commitments are not hiding.
+///
+/// [kzg]: http://cacr.uwaterloo.ca/techreports/2010/cacr2010-10.pdf
+pub struct KZGVectorCommitment<E: Pairing, D: EvaluationDomain<E::ScalarField>> {
+    _engine: PhantomData<E>,
+    _domain: PhantomData<D>,
+}
+
+/// `KZGVectorParams` are the universal parameters of `KZGVectorCommitment`.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct KZGVectorParams<E: Pairing> {
+    /// The universal parameters of KZG10.
+    pub params: kzg10::UniversalParams<E>,
+    /// Group elements of the form `{ \beta^i H }`, where `i` ranges from 0 to
+    /// the maximum size of an opened subset.
+    pub powers_of_h: Vec<E::G2Affine>,
+}
+
+/// `KZGVectorCommitterKey` is used to commit to vectors and open subsets.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct KZGVectorCommitterKey<E: Pairing, D: EvaluationDomain<E::ScalarField>> {
+    /// Group elements of the form `{ \beta^i G }`, where `i` ranges from 0 to
+    /// `domain.size() - 1`.
+    pub powers_of_g: Vec<E::G1Affine>,
+    /// The domain indexing the entries of the vectors.
+    pub domain: D,
+}
+
+/// `KZGVectorVerifierKey` is used to check subset openings.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct KZGVectorVerifierKey<E: Pairing, D: EvaluationDomain<E::ScalarField>> {
+    /// Group elements of the form `{ \beta^i G }`, where `i` ranges from 0 to
+    /// `domain.size() - 1`, used to commit to the interpolant of the subset.
+    pub powers_of_g: Vec<E::G1Affine>,
+    /// Group elements of the form `{ \beta^i H }`, where `i` ranges from 0 to
+    /// `domain.size()`, used to commit to the vanishing polynomial of the subset.
+    pub powers_of_h: Vec<E::G2Affine>,
+    /// The domain indexing the entries of the vectors.
+    pub domain: D,
+}
+
+impl<E, D> KZGVectorCommitment<E, D>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+    D: EvaluationDomain<E::ScalarField>,
+{
+    fn powers(powers_of_g: &[E::G1Affine]) -> Powers<'_, E> {
+        Powers {
+            powers_of_g: Cow::Borrowed(powers_of_g),
+            powers_of_gamma_g: Cow::Owned(Vec::new()),
+        }
+    }
+
+    /// Outputs the polynomial `\prod_i (X - points[i])`.
+    fn vanishing_polynomial(points: &[E::ScalarField]) -> UniPoly<E> {
+        let mut coeffs = vec![E::ScalarField::one()];
+        for point in points {
+            coeffs.insert(0, E::ScalarField::zero());
+            for i in 0..coeffs.len() - 1 {
+                let c = coeffs[i + 1] * point;
+                coeffs[i] -= c;
+            }
+        }
+        UniPoly::<E>::from_coefficients_vec(coeffs)
+    }
+
+    /// Outputs the polynomial of degree less than `points.len()` which
+    /// evaluates to `values[i]` at `points[i]`.
+    fn interpolate(
+        points: &[E::ScalarField],
+        values: &[E::ScalarField],
+        vanishing: &UniPoly<E>,
+    ) -> UniPoly<E> {
+        let mut coeffs = vec![E::ScalarField::zero(); points.len()];
+        for (point, value) in points.iter().zip(values) {
+            // Divide the vanishing polynomial by `X - point` with synthetic
+            // division, and evaluate the quotient at `point` on the way.
+            let mut quotient = vec![E::ScalarField::zero(); points.len()];
+            let mut acc = E::ScalarField::zero();
+            for i in (0..points.len()).rev() {
+                acc = vanishing.coeffs[i + 1] + acc * point;
+                quotient[i] = acc;
+            }
+            let denominator: E::ScalarField = quotient
+                .iter()
+                .rev()
+                .fold(E::ScalarField::zero(), |acc, c| acc * point + c);
+            let scale = *value * denominator.inverse().unwrap();
+            for (c, q) in coeffs.iter_mut().zip(quotient) {
+                *c += scale * q;
+            }
+        }
+        UniPoly::<E>::from_coefficients_vec(coeffs)
+    }
+}
+
+impl<E, D> VectorCommitment<E::ScalarField> for KZGVectorCommitment<E, D>
+where
+    E: Pairing,
+    E::G1Affine: Absorb,
+    D: EvaluationDomain<E::ScalarField>,
+{
+    type UniversalParams = KZGVectorParams<E>;
+    type CommitterKey = KZGVectorCommitterKey<E, D>;
+    type VerifierKey = KZGVectorVerifierKey<E, D>;
+    type Commitment = Commitment<E>;
+    type CommitmentState = UniPoly<E>;
+    type Proof = Proof<E>;
+    type Error = Error;
+
+    fn setup<R: RngCore>(max_len: usize, rng: &mut R) -> Result<Self::UniversalParams, Error> {
+        let size = D::compute_size_of_domain(max_len).ok_or(Error::InvalidParameters(format!(
+            "no evaluation domain of size {}",
+            max_len
+        )))?;
+        let beta = E::ScalarField::rand(rng);
+        // KZG10 supports degrees of at least 1, even for vectors of length 1
+        let max_degree = ark_std::cmp::max(size - 1, 1);
+        let params = KZG10::<E, UniPoly<E>>::setup_with_trapdoor(max_degree, false, beta, rng)?;
+
+        let mut powers_of_beta = vec![E::ScalarField::one()];
+        for i in 0..size {
+            powers_of_beta.push(powers_of_beta[i] * beta);
+        }
+        let powers_of_h = params.h.into_group().batch_mul(&powers_of_beta);
+        Ok(KZGVectorParams {
+            params,
+            powers_of_h,
+        })
+    }
+
+    fn trim(
+        pp: &Self::UniversalParams,
+        len: usize,
+    ) -> Result<(Self::CommitterKey, Self::VerifierKey), Error> {
+        let domain = D::new(len).ok_or(Error::InvalidParameters(format!(
+            "no evaluation domain of size {}",
+            len
+        )))?;
+        let size = domain.size();
+        if size > pp.params.powers_of_g.len() || size >= pp.powers_of_h.len() {
+            return Err(Error::TrimmingDegreeTooLarge);
+        }
+        let powers_of_g = pp.params.powers_of_g[..size].to_vec();
+        let ck = KZGVectorCommitterKey {
+            powers_of_g: powers_of_g.clone(),
+            domain,
+        };
+        let vk = KZGVectorVerifierKey {
+            powers_of_g,
+            powers_of_h: pp.powers_of_h[..=size].to_vec(),
+            domain,
+        };
+        Ok((ck, vk))
+    }
+
+    fn len(vk: &Self::VerifierKey) -> usize {
+        vk.domain.size()
+    }
+
+    fn commit(
+        ck: &Self::CommitterKey,
+        values: &[E::ScalarField],
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<(Self::Commitment, Self::CommitmentState), Error> {
+        check_values_len(values.len(), ck.domain.size())?;
+        let polynomial = UniPoly::<E>::from_coefficients_vec(ck.domain.ifft(values));
+        let (comm, _) = KZG10::commit(&Self::powers(&ck.powers_of_g), &polynomial, None, None)?;
+        Ok((comm, polynomial))
+    }
+
+    fn open_subset(
+        ck: &Self::CommitterKey,
+        _values: &[E::ScalarField],
+        _commitment: &Self::Commitment,
+        indices: &[usize],
+        _sponge: &mut impl CryptographicSponge,
+        state: &Self::CommitmentState,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Error> {
+        check_subset::<E::ScalarField>(indices, None, ck.domain.size())?;
+        let open_time = start_timer!(|| format!("Opening a subset of size {}", indices.len()));
+        let points: Vec<_> = indices.iter().map(|&i| ck.domain.element(i)).collect();
+        let vanishing = Self::vanishing_polynomial(&points);
+        // The remainder of the division is the interpolant of the subset.
+        let (quotient, _) = DenseOrSparsePolynomial::from(state)
+            .divide_with_q_and_r(&(&vanishing).into())
+            .ok_or(Error::InvalidParameters(
+                "empty vanishing polynomial".into(),
+            ))?;
+        let (w, _) = KZG10::commit(&Self::powers(&ck.powers_of_g), &quotient, None, None)?;
+        end_timer!(open_time);
+        Ok(Proof {
+            w: w.0,
+            random_v: None,
+        })
+    }
+
+    fn verify_subset<R: RngCore>(
+        vk: &Self::VerifierKey,
+        commitment: &Self::Commitment,
+        indices: &[usize],
+        subset_values: &[E::ScalarField],
+        proof: &Self::Proof,
+        _sponge: &mut impl CryptographicSponge,
+        _rng: &mut R,
+    ) -> Result<(), Error> {
+        check_subset(indices, Some(subset_values), vk.domain.size())?;
+        let check_time = start_timer!(|| format!("Checking a subset of size {}", indices.len()));
+        let points: Vec<_> = indices.iter().map(|&i| vk.domain.element(i)).collect();
+        let vanishing = Self::vanishing_polynomial(&points);
+        let interpolant = Self::interpolate(&points, subset_values, &vanishing);
+
+        let interpolant_comm = <E::G1 as VariableBaseMSM>::msm_unchecked(
+            &vk.powers_of_g[..interpolant.coeffs.len()],
+            &interpolant.coeffs,
+        );
+        let vanishing_comm = <E::G2 as VariableBaseMSM>::msm_unchecked(
+            &vk.powers_of_h[..vanishing.coeffs.len()],
+            &vanishing.coeffs,
+        );
+        let lhs = (commitment.0.into_group() - interpolant_comm).into_affine();
+        let result = E::multi_pairing(
+            [lhs, (-proof.w.into_group()).into_affine()],
+            [vk.powers_of_h[0], vanishing_comm.into_affine()],
+        )
+        .is_zero();
+        end_timer!(check_time);
+        if result {
+            Ok(())
+        } else {
+            Err(VerificationFailure::PairingCheck { point: None }.into())
+        }
+    }
+}
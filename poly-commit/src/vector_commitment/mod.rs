@@ -0,0 +1,692 @@
+use crate::{
+    Error, Evaluations, LabeledCommitment, LabeledPolynomial, PolynomialCommitment, QuerySet,
+};
+use ark_crypto_primitives::sponge::CryptographicSponge;
+use ark_ff::PrimeField;
+use ark_poly::{DenseMultilinearExtension, DenseUVPolynomial, EvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{collections::BTreeSet, format, marker::PhantomData, rand::RngCore};
+#[cfg(not(feature = "std"))]
+use ark_std::{string::ToString, vec::Vec};
+
+mod kzg;
+pub use kzg::*;
+
+/// Label of the polynomial committed to by the vector commitments derived
+/// from polynomial commitment schemes.
+const VECTOR_LABEL: &str = "vector";
+
+/// Describes the interface for a vector commitment scheme that allows a sender
+/// to commit to a vector and later open the entries at an arbitrary subset of
+/// indices, with a single proof.
+pub trait VectorCommitment<F: PrimeField>: Sized {
+    /// The universal parameters for the commitment scheme. These are "trimmed"
+    /// down to `Self::CommitterKey` and `Self::VerifierKey` by `Self::trim`.
+    type UniversalParams: Clone;
+    /// The committer key for the scheme; used to commit to a vector and then
+    /// open the commitment at a subset of indices.
+    type CommitterKey: Clone;
+    /// The verifier key for the scheme; used to check a subset opening.
+    type VerifierKey: Clone;
+    /// The commitment to a vector.
+    type Commitment: Clone + CanonicalSerialize + CanonicalDeserialize;
+    /// Auxiliary state of the commitment, output by `commit` and reused by
+    /// the committer in `open_subset`. Not to be shared with the verifier.
+    type CommitmentState: Clone;
+    /// The opening proof for a subset of indices.
+    type Proof: Clone + CanonicalSerialize + CanonicalDeserialize;
+    /// The error type for the scheme.
+    type Error: ark_std::error::Error + From<Error>;
+
+    /// Constructs public parameters for vectors of length at most `max_len`.
+    fn setup<R: RngCore>(max_len: usize, rng: &mut R)
+        -> Result<Self::UniversalParams, Self::Error>;
+
+    /// Specializes the public parameters for vectors of length at most `len`.
+    /// The indices of the entries of the vectors range over `0..Self::len(vk)`,
+    /// which may be larger than `len` since the vectors are padded with zeros.
+    fn trim(
+        pp: &Self::UniversalParams,
+        len: usize,
+    ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error>;
+
+    /// The number of entries of the (padded) vectors supported by `vk`.
+    fn len(vk: &Self::VerifierKey) -> usize;
+
+    /// Outputs a commitment to `values`, which is padded with zeros. Some
+    /// schemes (such as Hyrax) require randomness even when committing.
+    fn commit(
+        ck: &Self::CommitterKey,
+        values: &[F],
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(Self::Commitment, Self::CommitmentState), Self::Error>;
+
+    /// On input the committed `values` and a list of distinct `indices`,
+    /// outputs a proof that the entries of the committed vector at `indices`
+    /// are the corresponding entries of `values`.
+    fn open_subset(
+        ck: &Self::CommitterKey,
+        values: &[F],
+        commitment: &Self::Commitment,
+        indices: &[usize],
+        sponge: &mut impl CryptographicSponge,
+        state: &Self::CommitmentState,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Self::Error>;
+
+    /// Verifies that `subset_values[i]` is the entry at `indices[i]` of the
+    /// vector committed to in `commitment`. A rejected proof is reported as
+    /// [`Error::VerificationFailed`].
+    fn verify_subset<R: RngCore>(
+        vk: &Self::VerifierKey,
+        commitment: &Self::Commitment,
+        indices: &[usize],
+        subset_values: &[F],
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+        rng: &mut R,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Checks that `indices` are distinct and smaller than `len`, and that there
+/// are as many of them as `subset_values`, if provided.
+pub(crate) fn check_subset<F>(
+    indices: &[usize],
+    subset_values: Option<&[F]>,
+    len: usize,
+) -> Result<(), Error> {
+    if let Some(values) = subset_values {
+        if values.len() != indices.len() {
+            return Err(Error::IncorrectInputLength(format!(
+                "{} values were provided for {} indices",
+                values.len(),
+                indices.len()
+            )));
+        }
+    }
+    let mut seen = BTreeSet::new();
+    for &index in indices {
+        if index >= len || !seen.insert(index) {
+            return Err(Error::InvalidIndex { index, len });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a vector with `num_values` entries fits in `len` entries.
+fn check_values_len(num_values: usize, len: usize) -> Result<(), Error> {
+    if num_values > len {
+        return Err(Error::IncorrectInputLength(format!(
+            "a vector of length {} does not fit in {} entries",
+            num_values, len
+        )));
+    }
+    Ok(())
+}
+
+/// Committer or verifier key of a vector commitment derived from a
+/// polynomial commitment scheme, along with the indexing of the vectors.
+#[derive(Clone, Debug)]
+pub struct IndexedKey<K, I> {
+    /// The key of the polynomial commitment scheme.
+    pub key: K,
+    /// The points at which the committed polynomials are evaluated: an
+    /// evaluation domain, or the number of variables of the boolean hypercube.
+    pub indexing: I,
+}
+
+/// Vector commitment obtained from a univariate polynomial commitment scheme
+/// `PC`. The `i`-th entry of a vector is the evaluation of the committed
+/// polynomial at the `i`-th element of an evaluation domain `D`, and subsets
+/// are opened with `PC::batch_open`.
+///
+/// `F` must have domains `D` of the maximum length of the vectors, which
+/// `setup` and `trim` reject otherwise. With `Radix2EvaluationDomain`, for
+/// instance, vectors of length `n` need a two-adicity of `F` of at least
+/// `log2(n)`, which rules out fields with a small two-adicity, such as the
+/// scalar field of Jubjub.
+pub struct UnivariateVectorCommitment<F, P, PC, D> {
+    _field: PhantomData<F>,
+    _poly: PhantomData<P>,
+    _pc: PhantomData<PC>,
+    _domain: PhantomData<D>,
+}
+
+impl<F, P, PC, D> UnivariateVectorCommitment<F, P, PC, D>
+where
+    F: PrimeField,
+    P: DenseUVPolynomial<F, Point = F>,
+    PC: PolynomialCommitment<F, P>,
+    D: EvaluationDomain<F>,
+{
+    fn polynomial(domain: &D, values: &[F]) -> Result<LabeledPolynomial<F, P>, Error> {
+        check_values_len(values.len(), domain.size())?;
+        let coeffs = domain.ifft(values);
+        Ok(LabeledPolynomial::new(
+            VECTOR_LABEL.to_string(),
+            P::from_coefficients_vec(coeffs),
+            None,
+            None,
+        ))
+    }
+
+    fn query_set(domain: &D, indices: &[usize]) -> QuerySet<F> {
+        indices
+            .iter()
+            .map(|&i| {
+                (
+                    VECTOR_LABEL.to_string(),
+                    (format!("{}", i), domain.element(i)),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<F, P, PC, D> VectorCommitment<F> for UnivariateVectorCommitment<F, P, PC, D>
+where
+    F: PrimeField,
+    P: DenseUVPolynomial<F, Point = F>,
+    PC: PolynomialCommitment<F, P>,
+    PC::UniversalParams: Clone,
+    PC::CommitterKey: Clone,
+    PC::VerifierKey: Clone,
+    D: EvaluationDomain<F>,
+{
+    type UniversalParams = PC::UniversalParams;
+    type CommitterKey = IndexedKey<PC::CommitterKey, D>;
+    type VerifierKey = IndexedKey<PC::VerifierKey, D>;
+    type Commitment = PC::Commitment;
+    type CommitmentState = PC::CommitmentState;
+    type Proof = PC::BatchProof;
+    type Error = PC::Error;
+
+    fn setup<R: RngCore>(
+        max_len: usize,
+        rng: &mut R,
+    ) -> Result<Self::UniversalParams, Self::Error> {
+        let size = D::compute_size_of_domain(max_len).ok_or(Error::InvalidParameters(format!(
+            "no evaluation domain of size {}",
+            max_len
+        )))?;
+        // Schemes such as KZG10 do not support a maximum degree of 0
+        PC::setup(ark_std::cmp::max(size - 1, 1), None, rng)
+    }
+
+    fn trim(
+        pp: &Self::UniversalParams,
+        len: usize,
+    ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
+        let domain = D::new(len).ok_or(Error::InvalidParameters(format!(
+            "no evaluation domain of size {}",
+            len
+        )))?;
+        let (ck, vk) = PC::trim(pp, domain.size() - 1, 0, None)?;
+        Ok((
+            IndexedKey {
+                key: ck,
+                indexing: domain,
+            },
+            IndexedKey {
+                key: vk,
+                indexing: domain,
+            },
+        ))
+    }
+
+    fn len(vk: &Self::VerifierKey) -> usize {
+        vk.indexing.size()
+    }
+
+    fn commit(
+        ck: &Self::CommitterKey,
+        values: &[F],
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(Self::Commitment, Self::CommitmentState), Self::Error> {
+        let polynomial = Self::polynomial(&ck.indexing, values)?;
+        let (mut comms, mut states) = PC::commit(&ck.key, [&polynomial], rng)?;
+        Ok((comms.remove(0).commitment().clone(), states.remove(0)))
+    }
+
+    fn open_subset(
+        ck: &Self::CommitterKey,
+        values: &[F],
+        commitment: &Self::Commitment,
+        indices: &[usize],
+        sponge: &mut impl CryptographicSponge,
+        state: &Self::CommitmentState,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Self::Error> {
+        check_subset::<F>(indices, None, ck.indexing.size())?;
+        let polynomial = Self::polynomial(&ck.indexing, values)?;
+        let commitment = LabeledCommitment::new(VECTOR_LABEL.to_string(), commitment.clone(), None);
+        PC::batch_open(
+            &ck.key,
+            [&polynomial],
+            [&commitment],
+            &Self::query_set(&ck.indexing, indices),
+            sponge,
+            [state],
+            rng,
+        )
+    }
+
+    fn verify_subset<R: RngCore>(
+        vk: &Self::VerifierKey,
+        commitment: &Self::Commitment,
+        indices: &[usize],
+        subset_values: &[F],
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+        rng: &mut R,
+    ) -> Result<(), Self::Error> {
+        check_subset(indices, Some(subset_values), vk.indexing.size())?;
+        let evaluations: Evaluations<F, F> = indices
+            .iter()
+            .zip(subset_values)
+            .map(|(&i, &v)| ((VECTOR_LABEL.to_string(), vk.indexing.element(i)), v))
+            .collect();
+        let commitment = LabeledCommitment::new(VECTOR_LABEL.to_string(), commitment.clone(), None);
+        PC::batch_check(
+            &vk.key,
+            [&commitment],
+            &Self::query_set(&vk.indexing, indices),
+            &evaluations,
+            proof,
+            sponge,
+            rng,
+        )
+    }
+}
+
+/// Vector commitment obtained from a multilinear polynomial commitment scheme
+/// `PC`, such as Hyrax or multilinear Ligero. The `i`-th entry of a vector is
+/// the evaluation of the committed polynomial at the point of the boolean
+/// hypercube whose `j`-th coordinate is the `j`-th bit of `i`.
+pub struct MultilinearVectorCommitment<F, PC> {
+    _field: PhantomData<F>,
+    _pc: PhantomData<PC>,
+}
+
+impl<F, PC> MultilinearVectorCommitment<F, PC>
+where
+    F: PrimeField,
+    PC: PolynomialCommitment<F, DenseMultilinearExtension<F>>,
+{
+    /// The number of variables needed to index `len` entries, rounded up to
+    /// an even number since schemes arranging the evaluations in a square
+    /// matrix (such as Hyrax) require it.
+    fn num_vars(len: usize) -> usize {
+        let n = ark_std::log2(len) as usize;
+        n + n % 2
+    }
+
+    fn polynomial(
+        num_vars: usize,
+        values: &[F],
+    ) -> Result<LabeledPolynomial<F, DenseMultilinearExtension<F>>, Error> {
+        check_values_len(values.len(), 1 << num_vars)?;
+        let mut evaluations = values.to_vec();
+        evaluations.resize(1 << num_vars, F::zero());
+        Ok(LabeledPolynomial::new(
+            VECTOR_LABEL.to_string(),
+            DenseMultilinearExtension::from_evaluations_vec(num_vars, evaluations),
+            None,
+            None,
+        ))
+    }
+
+    fn point(num_vars: usize, index: usize) -> Vec<F> {
+        (0..num_vars)
+            .map(|j| {
+                if (index >> j) & 1 == 1 {
+                    F::one()
+                } else {
+                    F::zero()
+                }
+            })
+            .collect()
+    }
+
+    fn query_set(num_vars: usize, indices: &[usize]) -> QuerySet<Vec<F>> {
+        indices
+            .iter()
+            .map(|&i| {
+                (
+                    VECTOR_LABEL.to_string(),
+                    (format!("{}", i), Self::point(num_vars, i)),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<F, PC> VectorCommitment<F> for MultilinearVectorCommitment<F, PC>
+where
+    F: PrimeField,
+    PC: PolynomialCommitment<F, DenseMultilinearExtension<F>>,
+    PC::UniversalParams: Clone,
+    PC::CommitterKey: Clone,
+    PC::VerifierKey: Clone,
+{
+    type UniversalParams = IndexedKey<PC::UniversalParams, usize>;
+    type CommitterKey = IndexedKey<PC::CommitterKey, usize>;
+    type VerifierKey = IndexedKey<PC::VerifierKey, usize>;
+    type Commitment = PC::Commitment;
+    type CommitmentState = PC::CommitmentState;
+    type Proof = PC::BatchProof;
+    type Error = PC::Error;
+
+    fn setup<R: RngCore>(
+        max_len: usize,
+        rng: &mut R,
+    ) -> Result<Self::UniversalParams, Self::Error> {
+        let num_vars = Self::num_vars(max_len);
+        Ok(IndexedKey {
+            key: PC::setup(1, Some(num_vars), rng)?,
+            indexing: num_vars,
+        })
+    }
+
+    /// The number of variables is fixed by `setup`, so `len` must not exceed
+    /// the length supported by `pp`.
+    fn trim(
+        pp: &Self::UniversalParams,
+        len: usize,
+    ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
+        check_values_len(len, 1 << pp.indexing)?;
+        let (ck, vk) = PC::trim(&pp.key, 1, 0, None)?;
+        Ok((
+            IndexedKey {
+                key: ck,
+                indexing: pp.indexing,
+            },
+            IndexedKey {
+                key: vk,
+                indexing: pp.indexing,
+            },
+        ))
+    }
+
+    fn len(vk: &Self::VerifierKey) -> usize {
+        1 << vk.indexing
+    }
+
+    fn commit(
+        ck: &Self::CommitterKey,
+        values: &[F],
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(Self::Commitment, Self::CommitmentState), Self::Error> {
+        let polynomial = Self::polynomial(ck.indexing, values)?;
+        let (mut comms, mut states) = PC::commit(&ck.key, [&polynomial], rng)?;
+        Ok((comms.remove(0).commitment().clone(), states.remove(0)))
+    }
+
+    fn open_subset(
+        ck: &Self::CommitterKey,
+        values: &[F],
+        commitment: &Self::Commitment,
+        indices: &[usize],
+        sponge: &mut impl CryptographicSponge,
+        state: &Self::CommitmentState,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Self::Error> {
+        check_subset::<F>(indices, None, 1 << ck.indexing)?;
+        let polynomial = Self::polynomial(ck.indexing, values)?;
+        let commitment = LabeledCommitment::new(VECTOR_LABEL.to_string(), commitment.clone(), None);
+        PC::batch_open(
+            &ck.key,
+            [&polynomial],
+            [&commitment],
+            &Self::query_set(ck.indexing, indices),
+            sponge,
+            [state],
+            rng,
+        )
+    }
+
+    fn verify_subset<R: RngCore>(
+        vk: &Self::VerifierKey,
+        commitment: &Self::Commitment,
+        indices: &[usize],
+        subset_values: &[F],
+        proof: &Self::Proof,
+        sponge: &mut impl CryptographicSponge,
+        rng: &mut R,
+    ) -> Result<(), Self::Error> {
+        check_subset(indices, Some(subset_values), 1 << vk.indexing)?;
+        let evaluations: Evaluations<Vec<F>, F> = indices
+            .iter()
+            .zip(subset_values)
+            .map(|(&i, &v)| ((VECTOR_LABEL.to_string(), Self::point(vk.indexing, i)), v))
+            .collect();
+        let commitment = LabeledCommitment::new(VECTOR_LABEL.to_string(), commitment.clone(), None);
+        PC::batch_check(
+            &vk.key,
+            [&commitment],
+            &Self::query_set(vk.indexing, indices),
+            &evaluations,
+            proof,
+            sponge,
+            rng,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hyrax::HyraxPC,
+        ipa_pc::InnerProductArgPC,
+        linear_codes::{LinearCodePCS, MultilinearLigero},
+        test_types::{test_sponge, FieldToBytesColHasher, TestMerkleTreeParams},
+    };
+    use ark_bls12_381::{Bls12_381, G1Affine};
+    use ark_ec::pairing::Pairing;
+    use ark_ed_on_bls12_381::{EdwardsAffine, Fr};
+    use ark_ff::FftField;
+    use ark_poly::{univariate::DensePolynomial, GeneralEvaluationDomain, Radix2EvaluationDomain};
+    use ark_std::{test_rng, UniformRand};
+    use blake2::Blake2s256;
+
+    type Ipa = InnerProductArgPC<G1Affine, Blake2s256, DensePolynomial<Fr381>>;
+    type IpaVC = UnivariateVectorCommitment<
+        Fr381,
+        DensePolynomial<Fr381>,
+        Ipa,
+        Radix2EvaluationDomain<Fr381>,
+    >;
+    // The scalar field of Jubjub has a two-adicity of 1.
+    type JubjubIpa = InnerProductArgPC<EdwardsAffine, Blake2s256, DensePolynomial<Fr>>;
+    type JubjubIpaVC =
+        UnivariateVectorCommitment<Fr, DensePolynomial<Fr>, JubjubIpa, Radix2EvaluationDomain<Fr>>;
+    type HyraxVC =
+        MultilinearVectorCommitment<Fr, HyraxPC<EdwardsAffine, DenseMultilinearExtension<Fr>>>;
+    type Fr381 = <Bls12_381 as Pairing>::ScalarField;
+    type Ligero = LinearCodePCS<
+        MultilinearLigero<
+            Fr381,
+            TestMerkleTreeParams,
+            DenseMultilinearExtension<Fr381>,
+            FieldToBytesColHasher<Fr381, Blake2s256>,
+        >,
+        Fr381,
+        DenseMultilinearExtension<Fr381>,
+        TestMerkleTreeParams,
+        FieldToBytesColHasher<Fr381, Blake2s256>,
+    >;
+    type LigeroVC = MultilinearVectorCommitment<Fr381, Ligero>;
+    type KzgVC = KZGVectorCommitment<Bls12_381, GeneralEvaluationDomain<Fr381>>;
+
+    /// Commits to a random vector of length `len` and checks subset openings
+    /// of correct and incorrect values, as well as invalid subsets.
+    pub(crate) fn vector_commitment_test<F, VC>(len: usize) -> Result<(), VC::Error>
+    where
+        F: PrimeField,
+        VC: VectorCommitment<F>,
+    {
+        let rng = &mut test_rng();
+        let pp = VC::setup(len, rng)?;
+        let (ck, vk) = VC::trim(&pp, len)?;
+        assert!(VC::len(&vk) >= len);
+
+        let values: Vec<F> = (0..len).map(|_| F::rand(rng)).collect();
+        let (comm, state) = VC::commit(&ck, &values, Some(rng))?;
+
+        let padded_len = VC::len(&vk);
+        for indices in [
+            vec![3],
+            vec![len - 1, 0, 5, 2],
+            (0..len).collect(),
+            vec![padded_len - 1, 1],
+        ] {
+            let subset_values: Vec<F> = indices
+                .iter()
+                .map(|&i| values.get(i).copied().unwrap_or_else(F::zero))
+                .collect();
+            let proof = VC::open_subset(
+                &ck,
+                &values,
+                &comm,
+                &indices,
+                &mut test_sponge::<F>(),
+                &state,
+                Some(rng),
+            )?;
+            VC::verify_subset(
+                &vk,
+                &comm,
+                &indices,
+                &subset_values,
+                &proof,
+                &mut test_sponge::<F>(),
+                rng,
+            )?;
+
+            let mut wrong_values = subset_values.clone();
+            wrong_values[0] += F::one();
+            assert!(VC::verify_subset(
+                &vk,
+                &comm,
+                &indices,
+                &wrong_values,
+                &proof,
+                &mut test_sponge::<F>(),
+                rng,
+            )
+            .is_err());
+        }
+
+        for indices in [vec![1, 1], vec![padded_len]] {
+            assert!(VC::open_subset(
+                &ck,
+                &values,
+                &comm,
+                &indices,
+                &mut test_sponge::<F>(),
+                &state,
+                Some(rng),
+            )
+            .is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn kzg_vector_commitment_test() {
+        vector_commitment_test::<_, KzgVC>(20).expect("test failed for bls12-381");
+    }
+
+    /// Commits to a vector of length 1 and opens its only entry.
+    fn single_entry_test<F, VC>() -> Result<(), VC::Error>
+    where
+        F: PrimeField,
+        VC: VectorCommitment<F>,
+    {
+        let rng = &mut test_rng();
+        let pp = VC::setup(1, rng)?;
+        let (ck, vk) = VC::trim(&pp, 1)?;
+        let values = [F::rand(rng)];
+        let (comm, state) = VC::commit(&ck, &values, Some(rng))?;
+        let proof = VC::open_subset(
+            &ck,
+            &values,
+            &comm,
+            &[0],
+            &mut test_sponge::<F>(),
+            &state,
+            Some(rng),
+        )?;
+        VC::verify_subset(
+            &vk,
+            &comm,
+            &[0],
+            &values,
+            &proof,
+            &mut test_sponge::<F>(),
+            rng,
+        )?;
+        assert!(VC::verify_subset(
+            &vk,
+            &comm,
+            &[0],
+            &[values[0] + F::one()],
+            &proof,
+            &mut test_sponge::<F>(),
+            rng,
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn single_entry_vector_commitment_test() {
+        single_entry_test::<_, KzgVC>().expect("test failed for bls12-381");
+        single_entry_test::<_, IpaVC>().expect("test failed for bls12-381");
+        single_entry_test::<_, JubjubIpaVC>().expect("test failed for ed_on_bls12_381");
+    }
+
+    #[test]
+    fn ipa_vector_commitment_test() {
+        vector_commitment_test::<_, IpaVC>(13).expect("test failed for bls12-381");
+    }
+
+    #[test]
+    fn small_two_adicity_test() {
+        // Radix-2 domains hold at most `2^TWO_ADICITY` entries, which is 2 for
+        // Jubjub.
+        let max_len = 1 << <Fr as FftField>::TWO_ADICITY;
+        let rng = &mut test_rng();
+        assert!(JubjubIpaVC::setup(max_len, rng).is_ok());
+        assert!(matches!(
+            JubjubIpaVC::setup(max_len + 1, rng),
+            Err(Error::InvalidParameters(_))
+        ));
+        let pp = JubjubIpaVC::setup(max_len, rng).unwrap();
+        assert!(matches!(
+            JubjubIpaVC::trim(&pp, max_len + 1),
+            Err(Error::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn hyrax_vector_commitment_test() {
+        vector_commitment_test::<_, HyraxVC>(100).expect("test failed for ed_on_bls12_381");
+    }
+
+    #[test]
+    fn ligero_vector_commitment_test() {
+        vector_commitment_test::<_, LigeroVC>(64).expect("test failed for bls12-381");
+    }
+
+    #[test]
+    fn hypercube_indexing_test() {
+        let rng = &mut test_rng();
+        let values: Vec<Fr> = (0..16).map(|_| Fr::rand(rng)).collect();
+        let poly = HyraxVC::polynomial(4, &values).unwrap();
+        for (i, v) in values.iter().enumerate() {
+            assert_eq!(poly.evaluate(&HyraxVC::point(4, i)), *v);
+        }
+    }
+}
@@ -0,0 +1,523 @@
+use crate::{
+    ipa_pc::{CommitterKey, InnerProductArgPC, Proof},
+    Error, PolynomialCommitment, VerificationFailure,
+};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, PrimeField, Zero};
+use ark_poly::univariate::DensePolynomial;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
+    format,
+    rand::RngCore,
+};
+#[cfg(not(feature = "std"))]
+use ark_std::{string::ToString, vec::Vec};
+use blake2::{Blake2s256, Digest};
+
+mod multiproof;
+
+mod store;
+pub use store::*;
+
+#[cfg(test)]
+mod tests;
+
+/// The keys of a `VerkleTree`.
+pub type Key = [u8; 32];
+
+/// The inner-product argument committing to the nodes of a `VerkleTree`.
+type Ipa<G> = InnerProductArgPC<G, Blake2s256, DensePolynomial<<G as AffineRepr>::ScalarField>>;
+
+/// The number of bits of a key.
+const KEY_BITS: usize = 256;
+
+/// A node of a `VerkleTree`.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), PartialEq(bound = ""))]
+pub enum Node<G: AffineRepr> {
+    /// An internal node, with the digests of its children in `values`, the
+    /// empty children having digest zero.
+    Internal {
+        /// The digests of the children of the node.
+        values: Vec<G::ScalarField>,
+        /// The commitment to the polynomial of degree less than the width of
+        /// the tree whose evaluation at `i` is `values[i]`.
+        commitment: G,
+    },
+    /// A leaf, holding the value of a key.
+    Leaf {
+        /// The key of the leaf.
+        key: Key,
+        /// The value of the key.
+        value: G::ScalarField,
+    },
+}
+
+/// The parameters of a `VerkleTree` of width `n`: an IPA committer key for
+/// polynomials of degree less than `n`, which the verifier uses as well, and
+/// its Lagrange basis over the domain `{0, 1, ..., n - 1}`.
+///
+/// The domain is not a subgroup, since the scalar fields of the Edwards
+/// curves that the trees are meant for have few roots of unity of order a
+/// power of two.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct VerkleKey<G: AffineRepr> {
+    /// The IPA committer key, in the monomial basis.
+    pub ck: CommitterKey<G>,
+    /// Group elements committing to the Lagrange polynomials of the domain,
+    /// so that the commitment to the polynomial with evaluations `v` over the
+    /// domain is `sum_i v[i] lagrange_basis[i]`.
+    pub lagrange_basis: Vec<G>,
+    /// The coefficients of the vanishing polynomial `A(X)` of the domain.
+    vanishing: Vec<G::ScalarField>,
+    /// The barycentric weights `A'(i)` of the domain.
+    weights: Vec<G::ScalarField>,
+    /// The inverses of the barycentric weights.
+    inverse_weights: Vec<G::ScalarField>,
+    /// The inverses of `1, ..., n - 1`.
+    inverses: Vec<G::ScalarField>,
+}
+
+impl<G> VerkleKey<G>
+where
+    G: AffineRepr + Absorb,
+    G::Group: VariableBaseMSM<MulBase = G>,
+{
+    /// Derives the parameters of a tree whose width is the number of
+    /// generators of `ck`, which must be a power of two between 2 and 256.
+    pub fn new(ck: CommitterKey<G>) -> Result<Self, Error> {
+        let n = ck.comm_key.len();
+        check_width(n)?;
+        let domain = (0..n as u64).map(G::ScalarField::from).collect::<Vec<_>>();
+
+        let mut vanishing = vec![G::ScalarField::one()];
+        for x in &domain {
+            vanishing = multiply_by_linear(&vanishing, *x);
+        }
+        let weights = domain
+            .iter()
+            .map(|x| {
+                domain
+                    .iter()
+                    .filter(|y| *y != x)
+                    .map(|y| *x - y)
+                    .product::<G::ScalarField>()
+            })
+            .collect::<Vec<_>>();
+        let mut inverse_weights = weights.clone();
+        ark_ff::batch_inversion(&mut inverse_weights);
+        let mut inverses = domain[1..].to_vec();
+        ark_ff::batch_inversion(&mut inverses);
+
+        let mut key = Self {
+            ck,
+            lagrange_basis: Vec::new(),
+            vanishing,
+            weights,
+            inverse_weights,
+            inverses,
+        };
+        let lagrange_basis = (0..n)
+            .map(|i| {
+                let mut evaluations = vec![G::ScalarField::zero(); n];
+                evaluations[i] = G::ScalarField::one();
+                let coeffs = key.interpolate(&evaluations);
+                count!(MSM, n);
+                G::Group::msm_unchecked(&key.ck.comm_key, &coeffs)
+            })
+            .collect::<Vec<_>>();
+        key.lagrange_basis = G::Group::normalize_batch(&lagrange_basis);
+        Ok(key)
+    }
+
+    /// The width of the tree: the number of children of its internal nodes.
+    pub fn width(&self) -> usize {
+        self.ck.comm_key.len()
+    }
+
+    /// The number of children indices of a key, each made of `log2(width)`
+    /// bits, the last one being padded with zeros.
+    pub fn key_len(&self) -> usize {
+        let bits = ark_std::log2(self.width()) as usize;
+        KEY_BITS.div_ceil(bits)
+    }
+
+    /// The index of the child at depth `depth` on the path of `key`, from
+    /// the most significant bits of `key`.
+    fn digit(&self, key: &Key, depth: usize) -> u8 {
+        let bits = ark_std::log2(self.width()) as usize;
+        (depth * bits..(depth + 1) * bits).fold(0, |digit, bit| {
+            let set = bit < KEY_BITS && (key[bit / 8] >> (7 - bit % 8)) & 1 == 1;
+            (digit << 1) | set as u8
+        })
+    }
+
+    /// The path of the node at depth `depth` on the path of `key`.
+    fn path(&self, key: &Key, depth: usize) -> Vec<u8> {
+        (0..depth).map(|j| self.digit(key, j)).collect()
+    }
+
+    /// The internal node without children.
+    fn empty_node(&self) -> Node<G> {
+        Node::Internal {
+            values: vec![G::ScalarField::zero(); self.width()],
+            commitment: G::zero(),
+        }
+    }
+}
+
+/// Fails unless `width` is a power of two between 2 and 256.
+fn check_width(width: usize) -> Result<(), Error> {
+    if !width.is_power_of_two() || !(2..=256).contains(&width) {
+        return Err(Error::InvalidParameters(format!(
+            "The width of a Verkle tree must be a power of two between 2 and 256, found {}",
+            width
+        )));
+    }
+    Ok(())
+}
+
+/// Multiplies the polynomial with coefficients `p` by `X - x`.
+fn multiply_by_linear<F: Field>(p: &[F], x: F) -> Vec<F> {
+    let mut result = vec![F::zero(); p.len() + 1];
+    for (i, c) in p.iter().enumerate() {
+        result[i + 1] += c;
+        result[i] -= x * c;
+    }
+    result
+}
+
+/// Maps the message `bytes`, labelled `label`, to a field element.
+fn hash_to_field<F: PrimeField>(label: &[u8], bytes: &[u8]) -> F {
+    let mut hasher = Blake2s256::new();
+    hasher.update(label);
+    hasher.update(bytes);
+    F::from_le_bytes_mod_order(&hasher.finalize())
+}
+
+/// The digest of a leaf, in the node of its parent.
+fn leaf_digest<F: PrimeField>(key: &Key, value: &F) -> Result<F, Error> {
+    let mut bytes = key.to_vec();
+    value
+        .serialize_compressed(&mut bytes)
+        .map_err(|_| Error::HashingError)?;
+    Ok(hash_to_field(b"verkle leaf", &bytes))
+}
+
+/// The digest of an internal node with commitment `commitment`, in the node
+/// of its parent.
+fn node_digest<G: AffineRepr>(commitment: &G) -> Result<G::ScalarField, Error> {
+    let mut bytes = Vec::new();
+    commitment
+        .serialize_compressed(&mut bytes)
+        .map_err(|_| Error::HashingError)?;
+    Ok(hash_to_field(b"verkle node", &bytes))
+}
+
+/// A proof of the values of several keys of a `VerkleTree`, or of their
+/// absence. The internal nodes on the paths of the keys are opened at the
+/// indices of the paths with a single inner-product argument.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct VerkleProof<G: AffineRepr> {
+    /// For each key, the depth of the internal node whose child on the path
+    /// of the key is not an internal node.
+    pub depths: Vec<u8>,
+    /// For each key, the key and value of that child if it is the leaf of
+    /// another key, which proves the absence of the key.
+    pub other_leaves: Vec<Option<(Key, G::ScalarField)>>,
+    /// The commitments to the internal nodes on the paths of the keys, other
+    /// than the root, ordered by path.
+    pub commitments: Vec<G>,
+    /// The commitment to the quotient polynomial which aggregates the
+    /// openings of the internal nodes.
+    pub quotient_commitment: G,
+    /// The inner-product argument opening the aggregated polynomial.
+    pub opening: Proof<G>,
+}
+
+/// A key/value store committed to with a tree of IPA commitments, as in the
+/// Verkle trees of Ethereum, whose nodes are held by the store `S`.
+///
+/// The path of a key is the sequence of its digits in base `width`. Each
+/// leaf sits below the shallowest internal node at which its path diverges
+/// from the paths of all the other keys, so that the tree only depends on
+/// its content. An internal node commits to the digests of its children
+/// with a commitment in the Lagrange basis, which an update of a child
+/// changes with a single scalar multiplication.
+pub struct VerkleTree<G, S>
+where
+    G: AffineRepr,
+    S: NodeStore<G>,
+{
+    params: VerkleKey<G>,
+    store: S,
+}
+
+impl<G, S> VerkleTree<G, S>
+where
+    G: AffineRepr + Absorb,
+    G::Group: VariableBaseMSM<MulBase = G>,
+    S: NodeStore<G>,
+{
+    /// Constructs the parameters of trees of width `width`, which must be a
+    /// power of two between 2 and 256.
+    pub fn setup<R: RngCore>(width: usize, rng: &mut R) -> Result<VerkleKey<G>, Error> {
+        phase_span!("VerkleTree::setup", width);
+        check_width(width)?;
+        let pp = Ipa::<G>::setup(width - 1, None, rng)?;
+        let (ck, _) = Ipa::<G>::trim(&pp, width - 1, 0, None)?;
+        VerkleKey::new(ck)
+    }
+
+    /// Outputs the tree held by `store`, which is initialized with an empty
+    /// root if it has none.
+    pub fn new(params: VerkleKey<G>, mut store: S) -> Result<Self, Error> {
+        if store.get(&[])?.is_none() {
+            store.put(&[], params.empty_node())?;
+        }
+        Ok(Self { params, store })
+    }
+
+    /// The parameters of the tree.
+    pub fn params(&self) -> &VerkleKey<G> {
+        &self.params
+    }
+
+    /// The store holding the nodes of the tree.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Outputs the store holding the nodes of the tree.
+    pub fn into_store(self) -> S {
+        self.store
+    }
+
+    /// The commitment to the root of the tree, which commits to its content.
+    pub fn root(&self) -> Result<G, Error> {
+        Ok(self.internal(&[])?.1)
+    }
+
+    /// Outputs the value of `key`, if any.
+    pub fn get(&self, key: &Key) -> Result<Option<G::ScalarField>, Error> {
+        let mut path = Vec::new();
+        loop {
+            path.push(self.params.digit(key, path.len()));
+            match self.store.get(&path)? {
+                Some(Node::Internal { .. }) => continue,
+                Some(Node::Leaf { key: k, value }) if k == *key => return Ok(Some(value)),
+                _ => return Ok(None),
+            }
+        }
+    }
+
+    /// Sets the value of `key` to `value`, inserting it if it is absent. The
+    /// commitments to the nodes on the path of `key` are updated rather than
+    /// recomputed.
+    pub fn insert(&mut self, key: Key, value: G::ScalarField) -> Result<(), Error> {
+        phase_span!("VerkleTree::insert");
+        self.insert_at(&mut Vec::new(), &key, value)?;
+        Ok(())
+    }
+
+    /// Inserts `key` below the internal node at `path`, and outputs the new
+    /// digest of that node.
+    fn insert_at(
+        &mut self,
+        path: &mut Vec<u8>,
+        key: &Key,
+        value: G::ScalarField,
+    ) -> Result<G::ScalarField, Error> {
+        let (mut values, commitment) = self.internal(path)?;
+        let index = self.params.digit(key, path.len());
+        path.push(index);
+        let digest = match self.store.get(path)? {
+            Some(Node::Internal { .. }) => self.insert_at(path, key, value)?,
+            // The leaf of another key moves down to a new internal node,
+            // along with the leaf of `key`.
+            Some(Node::Leaf {
+                key: other_key,
+                value: other_value,
+            }) if other_key != *key => {
+                self.store.put(path, self.params.empty_node())?;
+                self.insert_at(path, &other_key, other_value)?;
+                self.insert_at(path, key, value)?
+            }
+            _ => {
+                self.store.put(path, Node::Leaf { key: *key, value })?;
+                leaf_digest(key, &value)?
+            }
+        };
+        path.pop();
+
+        let index = index as usize;
+        let delta = digest - values[index];
+        values[index] = digest;
+        count!(MSM, 1);
+        let commitment = (commitment + self.params.lagrange_basis[index] * delta).into_affine();
+        self.store
+            .put(path, Node::Internal { values, commitment })?;
+        node_digest(&commitment)
+    }
+
+    /// Outputs the digests of the children and the commitment of the
+    /// internal node at `path`.
+    fn internal(&self, path: &[u8]) -> Result<(Vec<G::ScalarField>, G), Error> {
+        match self.store.get(path)? {
+            Some(Node::Internal { values, commitment }) => Ok((values, commitment)),
+            _ => Err(Error::InvalidParameters(format!(
+                "The store has no internal node at {:?}",
+                path
+            ))),
+        }
+    }
+
+    /// Outputs a proof of the values of `keys`, or of their absence, against
+    /// the root of the tree.
+    pub fn open(
+        &self,
+        keys: &[Key],
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<VerkleProof<G>, Error> {
+        phase_span!("VerkleTree::open", num_keys = keys.len());
+        let mut nodes = BTreeMap::new();
+        let mut openings = BTreeSet::new();
+        let mut depths = Vec::with_capacity(keys.len());
+        let mut other_leaves = Vec::with_capacity(keys.len());
+        for key in keys {
+            let mut path = Vec::new();
+            loop {
+                if !nodes.contains_key(&path) {
+                    nodes.insert(path.clone(), self.internal(&path)?);
+                }
+                let index = self.params.digit(key, path.len());
+                openings.insert((path.clone(), index));
+                path.push(index);
+                match self.store.get(&path)? {
+                    Some(Node::Internal { .. }) => continue,
+                    child => {
+                        depths.push((path.len() - 1) as u8);
+                        other_leaves.push(match child {
+                            Some(Node::Leaf { key: k, value }) if k != *key => Some((k, value)),
+                            _ => None,
+                        });
+                        break;
+                    }
+                }
+            }
+        }
+
+        let queries = openings
+            .iter()
+            .map(|(path, index)| {
+                let (values, commitment) = &nodes[path];
+                (*commitment, *index as usize, values.as_slice())
+            })
+            .collect::<Vec<_>>();
+        let (quotient_commitment, opening) = self.params.open_multiproof(&queries, sponge)?;
+        Ok(VerkleProof {
+            depths,
+            other_leaves,
+            commitments: nodes.values().skip(1).map(|(_, c)| *c).collect(),
+            quotient_commitment,
+            opening,
+        })
+    }
+
+    /// Verifies that `proof` proves, against the commitment `root` to the
+    /// root of a tree, that the value of each key of `entries` is the given
+    /// one, or that the key is absent if it is `None`.
+    pub fn verify(
+        params: &VerkleKey<G>,
+        root: &G,
+        entries: &[(Key, Option<G::ScalarField>)],
+        proof: &VerkleProof<G>,
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<(), Error> {
+        phase_span!("VerkleTree::verify", num_keys = entries.len());
+        let malformed = |reason: &str| {
+            Error::from(VerificationFailure::MalformedProof {
+                label: None,
+                reason: reason.to_string(),
+            })
+        };
+        if proof.depths.len() != entries.len() || proof.other_leaves.len() != entries.len() {
+            return Err(malformed("one depth and one leaf per key"));
+        }
+        if proof
+            .depths
+            .iter()
+            .any(|depth| *depth as usize >= params.key_len())
+        {
+            return Err(malformed("depths smaller than the length of the keys"));
+        }
+
+        let paths = entries
+            .iter()
+            .zip(&proof.depths)
+            .flat_map(|((key, _), depth)| (1..=*depth as usize).map(move |j| params.path(key, j)))
+            .collect::<BTreeSet<_>>();
+        if paths.len() != proof.commitments.len() {
+            return Err(malformed("one commitment per internal node on the paths"));
+        }
+        let mut commitments = paths
+            .into_iter()
+            .zip(proof.commitments.iter().copied())
+            .collect::<BTreeMap<_, _>>();
+        commitments.insert(Vec::new(), *root);
+
+        let mut openings = BTreeMap::new();
+        let mut add_opening = |path: Vec<u8>, index: u8, value: G::ScalarField| {
+            match openings.entry((path, index)) {
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+                Entry::Occupied(entry) if *entry.get() != value => {
+                    return Err(malformed("consistent children on the paths"))
+                }
+                Entry::Occupied(_) => {}
+            }
+            Ok(())
+        };
+        for (((key, value), depth), other_leaf) in
+            entries.iter().zip(&proof.depths).zip(&proof.other_leaves)
+        {
+            let depth = *depth as usize;
+            for j in 0..depth {
+                let child = &commitments[&params.path(key, j + 1)];
+                add_opening(
+                    params.path(key, j),
+                    params.digit(key, j),
+                    node_digest(child)?,
+                )?;
+            }
+            let digest = match (value, other_leaf) {
+                (Some(value), None) => leaf_digest(key, value)?,
+                (None, None) => G::ScalarField::zero(),
+                (None, Some((other_key, other_value)))
+                    if other_key != key
+                        && (0..=depth)
+                            .all(|j| params.digit(other_key, j) == params.digit(key, j)) =>
+                {
+                    leaf_digest(other_key, other_value)?
+                }
+                _ => {
+                    return Err(malformed(
+                        "a leaf of another key on the path of an absent key",
+                    ))
+                }
+            };
+            add_opening(params.path(key, depth), params.digit(key, depth), digest)?;
+        }
+
+        let queries = openings
+            .into_iter()
+            .map(|((path, index), value)| (commitments[&path], index as usize, value))
+            .collect::<Vec<_>>();
+        params.check_multiproof(&queries, &proof.quotient_commitment, &proof.opening, sponge)
+    }
+}
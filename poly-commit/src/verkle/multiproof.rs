@@ -0,0 +1,257 @@
+use super::{Ipa, VerkleKey};
+use crate::{
+    ipa_pc::{Commitment, Proof, Randomness},
+    Error, LabeledCommitment, LabeledPolynomial, PCCommitmentState, PolynomialCommitment,
+    Transcript,
+};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{batch_inversion, One, Zero};
+use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial};
+use ark_std::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use ark_std::{string::ToString, vec::Vec};
+
+/// Binds the transcript of every multiproof to this construction.
+const PROTOCOL_NAME: &[u8] = b"VERKLE-MULTIPROOF-IPA";
+
+/// Label of the aggregated polynomial opened by the inner-product argument.
+const AGGREGATED_LABEL: &str = "aggregated";
+
+/// The multiproofs of the internal nodes of Verkle trees. Opening the
+/// polynomials `f_i` with commitments `C_i` at the points `z_i` of the
+/// domain, to the values `y_i`, reduces to a single opening:
+///
+/// 1. on a challenge `r`, the prover commits to the quotient
+///    `g(X) = sum_i r^i (f_i(X) - y_i) / (X - z_i)` with `D`;
+/// 2. on a challenge `t`, both parties compute the commitment
+///    `E - D = sum_i r^i / (t - z_i) C_i - D` to
+///    `h(X) - g(X) = sum_i r^i f_i(X) / (t - z_i) - g(X)`, whose evaluation
+///    at `t` is `sum_i r^i y_i / (t - z_i)`, which the prover opens with the
+///    inner-product argument.
+///
+/// The polynomials are kept in the Lagrange basis of the domain, where
+/// dividing by `X - z_i` is linear, and only `h - g` is interpolated.
+impl<G> VerkleKey<G>
+where
+    G: AffineRepr + Absorb,
+    G::Group: VariableBaseMSM<MulBase = G>,
+{
+    /// Outputs the coefficients of the polynomial of degree less than the
+    /// width whose evaluations over the domain are `evaluations`.
+    pub(crate) fn interpolate(&self, evaluations: &[G::ScalarField]) -> Vec<G::ScalarField> {
+        let n = self.width();
+        let mut coeffs = vec![G::ScalarField::zero(); n];
+        for (i, value) in evaluations.iter().enumerate() {
+            if value.is_zero() {
+                continue;
+            }
+            // Adds `value L_i(X) = value A(X) / ((X - i) A'(i))`, dividing
+            // `A(X)` by `X - i` from its leading coefficient.
+            let scale = *value * self.inverse_weights[i];
+            let x = G::ScalarField::from(i as u64);
+            let mut quotient = self.vanishing[n];
+            for k in (0..n).rev() {
+                coeffs[k] += scale * quotient;
+                quotient = self.vanishing[k] + x * quotient;
+            }
+        }
+        coeffs
+    }
+
+    /// Outputs the evaluations over the domain of `(f(X) - f(m)) / (X - m)`,
+    /// where `f` has evaluations `evaluations`. At `m`, the quotient is the
+    /// derivative of `f`, `-sum_{j != m} q(j) A'(m) / A'(j)`.
+    pub(crate) fn divide_on_domain(
+        &self,
+        m: usize,
+        evaluations: &[G::ScalarField],
+    ) -> Vec<G::ScalarField> {
+        let y = evaluations[m];
+        let mut quotient = vec![G::ScalarField::zero(); evaluations.len()];
+        for (j, value) in evaluations.iter().enumerate() {
+            if j == m {
+                continue;
+            }
+            let inverse = if j > m {
+                self.inverses[j - m - 1]
+            } else {
+                -self.inverses[m - j - 1]
+            };
+            let q = (*value - y) * inverse;
+            quotient[j] = q;
+            quotient[m] -= q * self.weights[m] * self.inverse_weights[j];
+        }
+        quotient
+    }
+
+    /// Absorbs the queries and outputs the powers of the challenge `r`.
+    fn aggregation_challenge<'a>(
+        &self,
+        queries: impl ExactSizeIterator<Item = (&'a G, usize, &'a G::ScalarField)>,
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<Vec<G::ScalarField>, Error> {
+        let num_queries = queries.len();
+        sponge.bind_context(PROTOCOL_NAME, &self.ck)?;
+        for (commitment, index, value) in queries {
+            sponge.absorb_serialized(b"commitment", commitment)?;
+            sponge.absorb_serialized(b"index", &(index as u64))?;
+            sponge.absorb_serialized(b"value", value)?;
+        }
+        let r: G::ScalarField = sponge.challenge(b"aggregation challenge");
+        Ok(
+            ark_std::iter::successors(Some(G::ScalarField::one()), |power| Some(*power * r))
+                .take(num_queries)
+                .collect(),
+        )
+    }
+
+    /// Absorbs the commitment to the quotient and outputs the challenge `t`,
+    /// along with the coefficients `r^i / (t - z_i)`.
+    fn evaluation_challenge(
+        &self,
+        quotient_commitment: &G,
+        indices: impl Iterator<Item = usize>,
+        powers: &[G::ScalarField],
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<(G::ScalarField, Vec<G::ScalarField>), Error> {
+        sponge.absorb_serialized(b"quotient commitment", quotient_commitment)?;
+        let t: G::ScalarField = sponge.challenge(b"evaluation point");
+        let mut coefficients = indices
+            .map(|i| t - G::ScalarField::from(i as u64))
+            .collect::<Vec<_>>();
+        if coefficients.iter().any(Zero::is_zero) {
+            return Err(Error::InvalidParameters(
+                "The evaluation point lies in the domain".to_string(),
+            ));
+        }
+        batch_inversion(&mut coefficients);
+        for (c, power) in coefficients.iter_mut().zip(powers) {
+            *c *= power;
+        }
+        Ok((t, coefficients))
+    }
+
+    /// Outputs the commitment `E - D` to `h - g`.
+    fn aggregated_commitment(
+        commitments: &[G],
+        coefficients: &[G::ScalarField],
+        quotient_commitment: &G,
+    ) -> LabeledCommitment<Commitment<G>> {
+        count!(MSM, commitments.len());
+        let comm = (G::Group::msm_unchecked(commitments, coefficients) - quotient_commitment)
+            .into_affine();
+        LabeledCommitment::new(
+            AGGREGATED_LABEL.to_string(),
+            Commitment {
+                comm,
+                shifted_comm: None,
+            },
+            None,
+        )
+    }
+
+    /// Opens each commitment of `queries` at its index, given the
+    /// evaluations of its polynomial over the domain, with a multiproof.
+    /// Outputs the commitment to the quotient and the inner-product argument.
+    pub(crate) fn open_multiproof(
+        &self,
+        queries: &[(G, usize, &[G::ScalarField])],
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<(G, Proof<G>), Error> {
+        phase_span!("VerkleKey::open_multiproof", num_queries = queries.len());
+        let n = self.width();
+        let powers = self.aggregation_challenge(
+            queries
+                .iter()
+                .map(|(commitment, index, evaluations)| (commitment, *index, &evaluations[*index])),
+            sponge,
+        )?;
+
+        // The polynomials opened at the same point are divided together.
+        let mut combined = BTreeMap::new();
+        for ((_, index, evaluations), power) in queries.iter().zip(&powers) {
+            let sum = combined
+                .entry(*index)
+                .or_insert_with(|| vec![G::ScalarField::zero(); n]);
+            for (s, value) in sum.iter_mut().zip(evaluations.iter()) {
+                *s += *power * value;
+            }
+        }
+        let mut quotient = vec![G::ScalarField::zero(); n];
+        for (index, sum) in combined {
+            for (q, value) in quotient.iter_mut().zip(self.divide_on_domain(index, &sum)) {
+                *q += value;
+            }
+        }
+        count!(MSM, n);
+        let quotient_commitment =
+            G::Group::msm_unchecked(&self.lagrange_basis, &quotient).into_affine();
+
+        let (t, coefficients) = self.evaluation_challenge(
+            &quotient_commitment,
+            queries.iter().map(|(_, index, _)| *index),
+            &powers,
+            sponge,
+        )?;
+        let mut evaluations = quotient.iter().map(|q| -*q).collect::<Vec<_>>();
+        for ((_, _, values), c) in queries.iter().zip(&coefficients) {
+            for (e, value) in evaluations.iter_mut().zip(values.iter()) {
+                *e += *c * value;
+            }
+        }
+        let polynomial = LabeledPolynomial::new(
+            AGGREGATED_LABEL.to_string(),
+            DensePolynomial::from_coefficients_vec(self.interpolate(&evaluations)),
+            None,
+            None,
+        );
+        let commitments = queries.iter().map(|(c, _, _)| *c).collect::<Vec<_>>();
+        let commitment =
+            Self::aggregated_commitment(&commitments, &coefficients, &quotient_commitment);
+        let state = Randomness::empty();
+        let proof = Ipa::<G>::open(
+            &self.ck,
+            [&polynomial],
+            [&commitment],
+            &t,
+            sponge,
+            [&state],
+            None,
+        )?;
+        Ok((quotient_commitment, proof))
+    }
+
+    /// Verifies a multiproof that each commitment of `queries` opens to the
+    /// given value at the given index.
+    pub(crate) fn check_multiproof(
+        &self,
+        queries: &[(G, usize, G::ScalarField)],
+        quotient_commitment: &G,
+        proof: &Proof<G>,
+        sponge: &mut impl CryptographicSponge,
+    ) -> Result<(), Error> {
+        phase_span!("VerkleKey::check_multiproof", num_queries = queries.len());
+        let powers = self.aggregation_challenge(
+            queries
+                .iter()
+                .map(|(commitment, index, value)| (commitment, *index, value)),
+            sponge,
+        )?;
+        let (t, coefficients) = self.evaluation_challenge(
+            quotient_commitment,
+            queries.iter().map(|(_, index, _)| *index),
+            &powers,
+            sponge,
+        )?;
+        let value = queries
+            .iter()
+            .zip(&coefficients)
+            .map(|((_, _, value), c)| *c * value)
+            .sum::<G::ScalarField>();
+        let commitments = queries.iter().map(|(c, _, _)| *c).collect::<Vec<_>>();
+        let commitment =
+            Self::aggregated_commitment(&commitments, &coefficients, quotient_commitment);
+        Ipa::<G>::check(&self.ck, [&commitment], &t, [value], proof, sponge, None)
+    }
+}
@@ -0,0 +1,47 @@
+use super::Node;
+use crate::Error;
+use ark_ec::AffineRepr;
+use ark_std::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use ark_std::vec::Vec;
+
+/// Storage of the nodes of a `VerkleTree`. A node is identified by its path:
+/// the indices of the children leading to it from the root, whose path is
+/// empty.
+pub trait NodeStore<G: AffineRepr> {
+    /// Outputs the node at `path`, if any.
+    fn get(&self, path: &[u8]) -> Result<Option<Node<G>>, Error>;
+
+    /// Stores `node` at `path`, replacing the node there, if any.
+    fn put(&mut self, path: &[u8], node: Node<G>) -> Result<(), Error>;
+}
+
+/// A `NodeStore` keeping the nodes in memory.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), Default(bound = ""))]
+pub struct MemoryStore<G: AffineRepr> {
+    nodes: BTreeMap<Vec<u8>, Node<G>>,
+}
+
+impl<G: AffineRepr> MemoryStore<G> {
+    /// Outputs an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of nodes in the store.
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+impl<G: AffineRepr> NodeStore<G> for MemoryStore<G> {
+    fn get(&self, path: &[u8]) -> Result<Option<Node<G>>, Error> {
+        Ok(self.nodes.get(path).cloned())
+    }
+
+    fn put(&mut self, path: &[u8], node: Node<G>) -> Result<(), Error> {
+        self.nodes.insert(path.to_vec(), node);
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+use super::*;
+use crate::{test_types::test_sponge, LabeledPolynomial};
+use ark_ed_on_bls12_381::{EdwardsAffine, Fr};
+use ark_poly::{DenseUVPolynomial, Polynomial};
+use ark_std::{rand::RngCore, test_rng, UniformRand};
+
+type Tree = VerkleTree<EdwardsAffine, MemoryStore<EdwardsAffine>>;
+
+/// Random keys, half of which share their first byte and a quarter their
+/// first two bytes, so that their leaves sit deep in the tree.
+fn rand_keys<R: RngCore>(num_keys: usize, rng: &mut R) -> Vec<Key> {
+    let mut prefix = Key::default();
+    rng.fill_bytes(&mut prefix);
+    (0..num_keys)
+        .map(|i| {
+            let mut key = Key::default();
+            rng.fill_bytes(&mut key);
+            if i % 2 == 0 {
+                key[0] = prefix[0];
+            }
+            if i % 4 == 0 {
+                key[1] = prefix[1];
+            }
+            key
+        })
+        .collect()
+}
+
+fn rand_tree<R: RngCore>(width: usize, entries: &[(Key, Fr)], rng: &mut R) -> Tree {
+    let params = Tree::setup(width, rng).unwrap();
+    let mut tree = Tree::new(params, MemoryStore::new()).unwrap();
+    for (key, value) in entries {
+        tree.insert(*key, *value).unwrap();
+    }
+    tree
+}
+
+#[test]
+fn lagrange_basis_test() {
+    let rng = &mut test_rng();
+    let params = Tree::setup(8, rng).unwrap();
+    let evaluations = (0..8).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    let polynomial = DensePolynomial::from_coefficients_vec(params.interpolate(&evaluations));
+    for (i, value) in evaluations.iter().enumerate() {
+        assert_eq!(polynomial.evaluate(&Fr::from(i as u64)), *value);
+    }
+
+    // The commitments in the Lagrange basis are those of the IPA.
+    let labeled = LabeledPolynomial::new("f".into(), polynomial.clone(), None, None);
+    let (commitments, _) = Ipa::<EdwardsAffine>::commit(&params.ck, [&labeled], None).unwrap();
+    let lagrange_commitment: <EdwardsAffine as AffineRepr>::Group =
+        VariableBaseMSM::msm_unchecked(&params.lagrange_basis, &evaluations);
+    assert_eq!(
+        commitments[0].commitment().comm,
+        lagrange_commitment.into_affine()
+    );
+
+    let x = Fr::rand(rng);
+    for m in [0, 3, 7] {
+        let quotient = DensePolynomial::from_coefficients_vec(
+            params.interpolate(&params.divide_on_domain(m, &evaluations)),
+        );
+        assert_eq!(
+            quotient.evaluate(&x) * (x - Fr::from(m as u64)),
+            polynomial.evaluate(&x) - evaluations[m]
+        );
+    }
+}
+
+#[test]
+fn insertion_test() {
+    let rng = &mut test_rng();
+    let keys = rand_keys(40, rng);
+    let entries = keys
+        .iter()
+        .map(|key| (*key, Fr::rand(rng)))
+        .collect::<Vec<_>>();
+    let tree = rand_tree(16, &entries, rng);
+    for (key, value) in &entries {
+        assert_eq!(tree.get(key).unwrap(), Some(*value));
+    }
+    assert_eq!(tree.get(&rand_keys(1, rng)[0]).unwrap(), None);
+
+    // The tree only depends on its content, whatever the order of insertion
+    // and the updates.
+    let reversed = entries.iter().rev().copied().collect::<Vec<_>>();
+    assert_eq!(
+        rand_tree(16, &reversed, rng).root().unwrap(),
+        tree.root().unwrap()
+    );
+    let mut updated = rand_tree(
+        16,
+        &keys
+            .iter()
+            .map(|key| (*key, Fr::rand(rng)))
+            .collect::<Vec<_>>(),
+        rng,
+    );
+    assert_ne!(updated.root().unwrap(), tree.root().unwrap());
+    for (key, value) in &entries {
+        updated.insert(*key, *value).unwrap();
+    }
+    assert_eq!(updated.root().unwrap(), tree.root().unwrap());
+
+    // The updated commitment of the root is the commitment to its children.
+    match tree.store().get(&[]).unwrap() {
+        Some(Node::Internal { values, commitment }) => {
+            let expected: <EdwardsAffine as AffineRepr>::Group =
+                VariableBaseMSM::msm_unchecked(&tree.params().lagrange_basis, &values);
+            assert_eq!(commitment, expected.into_affine());
+        }
+        _ => panic!("the root is not an internal node"),
+    }
+}
+
+fn multiproof_test(width: usize) {
+    let rng = &mut test_rng();
+    let keys = rand_keys(30, rng);
+    let entries = keys
+        .iter()
+        .map(|key| (*key, Fr::rand(rng)))
+        .collect::<Vec<_>>();
+    let mut tree = rand_tree(width, &entries, rng);
+    let params = tree.params().clone();
+    let root = tree.root().unwrap();
+
+    // An absent key on the path of a present one ends at the leaf of the
+    // latter, and a random absent key most likely at an empty child.
+    let mut neighbour = keys[0];
+    neighbour[31] ^= 1;
+    let absent = rand_keys(1, rng)[0];
+    let mut claims = entries
+        .iter()
+        .step_by(3)
+        .map(|(key, value)| (*key, Some(*value)))
+        .collect::<Vec<_>>();
+    claims.push((neighbour, None));
+    claims.push((absent, None));
+    let opened = claims.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+
+    let proof = tree.open(&opened, &mut test_sponge::<Fr>()).unwrap();
+    assert!(proof.other_leaves.iter().any(Option::is_some));
+    Tree::verify(&params, &root, &claims, &proof, &mut test_sponge::<Fr>()).unwrap();
+
+    let rejects = |root: &EdwardsAffine, claims: &[(Key, Option<Fr>)], proof: &VerkleProof<_>| {
+        Tree::verify(&params, root, claims, proof, &mut test_sponge::<Fr>()).is_err()
+    };
+    let mut wrong = claims.clone();
+    wrong[0].1 = Some(Fr::rand(rng));
+    assert!(rejects(&root, &wrong, &proof));
+    let mut wrong = claims.clone();
+    wrong[1].1 = None;
+    assert!(rejects(&root, &wrong, &proof));
+    let mut wrong = claims.clone();
+    wrong.last_mut().unwrap().1 = Some(Fr::rand(rng));
+    assert!(rejects(&root, &wrong, &proof));
+    let mut wrong = proof.clone();
+    wrong.commitments[0] = (wrong.commitments[0] + params.lagrange_basis[0]).into_affine();
+    assert!(rejects(&root, &claims, &wrong));
+    let mut wrong = proof.clone();
+    wrong.depths[0] += 1;
+    assert!(rejects(&root, &claims, &wrong));
+
+    // After an update, the proof is only valid against the former root.
+    let value = Fr::rand(rng);
+    tree.insert(keys[0], value).unwrap();
+    let new_root = tree.root().unwrap();
+    assert!(rejects(&new_root, &claims, &proof));
+    claims[0].1 = Some(value);
+    let proof = tree.open(&opened, &mut test_sponge::<Fr>()).unwrap();
+    Tree::verify(
+        &params,
+        &new_root,
+        &claims,
+        &proof,
+        &mut test_sponge::<Fr>(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn multiproof_width_16_test() {
+    multiproof_test(16);
+}
+
+#[test]
+fn multiproof_width_8_test() {
+    multiproof_test(8);
+}
+
+#[test]
+fn invalid_width_test() {
+    let rng = &mut test_rng();
+    assert!(Tree::setup(12, rng).is_err());
+    assert!(Tree::setup(512, rng).is_err());
+}
@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+# This script vendors the KZG test vectors of a consensus-spec-tests release
+# under poly-commit/src/eip4844/test_vectors, where the `official_test_vectors`
+# test of the `eip4844` feature runs them. It takes the tag of the release,
+# and prints the SHA-256 of the downloaded archive, to be compared with the
+# one published with the release before committing the vectors.
+set -euo pipefail
+
+VERSION="${1:-v1.5.0}"
+DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" && pwd )"
+DEST="${DIR}/../poly-commit/src/eip4844/test_vectors"
+TMP="$(mktemp -d)"
+trap 'rm -rf "$TMP"' EXIT
+
+curl -fsSL -o "$TMP/general.tar.gz" \
+  "https://github.com/ethereum/consensus-spec-tests/releases/download/${VERSION}/general.tar.gz"
+sha256sum "$TMP/general.tar.gz"
+tar -xzf "$TMP/general.tar.gz" -C "$TMP"
+
+for kzg in "$TMP"/tests/general/*/kzg; do
+  fork="$(basename "$(dirname "$kzg")")"
+  rm -rf "${DEST:?}/$fork"
+  mkdir -p "$DEST/$fork"
+  cp -r "$kzg" "$DEST/$fork/"
+done